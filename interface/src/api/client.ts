@@ -202,6 +202,17 @@ export interface ToolCompletedEvent {
 	result: string;
 }
 
+export interface ToolOutputEvent {
+	type: "tool_output";
+	agent_id: string;
+	channel_id: string | null;
+	process_type: ProcessType;
+	process_id: string;
+	tool_name: string;
+	line: string;
+	stream: "stdout" | "stderr";
+}
+
 // -- Agent link events --
 
 export interface AgentMessageEvent {
@@ -260,6 +271,7 @@ export type ApiEvent =
 	| BranchCompletedEvent
 	| ToolStartedEvent
 	| ToolCompletedEvent
+	| ToolOutputEvent
 	| OpenCodePartUpdatedEvent
 	| WorkerTextEvent
 	| CortexChatMessageEvent;
@@ -397,6 +409,8 @@ export const MEMORY_TYPES: MemoryType[] = [
 
 export type MemorySort = "recent" | "importance" | "most_accessed";
 
+export type MemoryTier = "recent" | "long_term";
+
 // Extended MemoryItem with forgotten field (not yet in schema)
 export interface MemoryItem {
 	id: string;
@@ -409,6 +423,9 @@ export interface MemoryItem {
 	access_count: number;
 	source: string | null;
 	channel_id: string | null;
+	user_id: string | null;
+	tier: MemoryTier;
+	demoted_at: string | null;
 	forgotten: boolean;
 }
 
@@ -444,11 +461,13 @@ export interface MemoriesListParams {
 	offset?: number;
 	memory_type?: MemoryType;
 	sort?: MemorySort;
+	user_id?: string;
 }
 
 export interface MemoriesSearchParams {
 	limit?: number;
 	memory_type?: MemoryType;
+	user_id?: string;
 }
 
 // --- Cortex event types ---
@@ -564,6 +583,8 @@ export interface CortexSection {
 	bulletin_interval_secs: number;
 	bulletin_max_words: number;
 	bulletin_max_turns: number;
+	autonomy_enabled: boolean;
+	autonomy_interval_secs: number;
 }
 
 export interface CoalesceSection {
@@ -663,6 +684,8 @@ export interface CortexUpdate {
 	bulletin_interval_secs?: number;
 	bulletin_max_words?: number;
 	bulletin_max_turns?: number;
+	autonomy_enabled?: boolean;
+	autonomy_interval_secs?: number;
 }
 
 export interface CoalesceUpdate {
@@ -733,8 +756,10 @@ export interface CronJobWithStats {
 	delivery_target: string;
 	enabled: boolean;
 	run_once: boolean;
+	stateful: boolean;
 	active_hours: [number, number] | null;
 	timeout_secs: number | null;
+	state: CronJobState | null;
 	execution_success_count: number;
 	execution_failure_count: number;
 	delivery_success_count: number;
@@ -743,6 +768,11 @@ export interface CronJobWithStats {
 	last_executed_at: string | null;
 }
 
+export interface CronJobState {
+	state: string;
+	updated_at: string;
+}
+
 export interface CronExecutionEntry {
 	id: string;
 	cron_id: string | null;
@@ -780,6 +810,7 @@ export interface CreateCronRequest {
 	active_end_hour?: number;
 	enabled: boolean;
 	run_once: boolean;
+	stateful?: boolean;
 	timeout_secs?: number;
 }
 
@@ -1317,6 +1348,36 @@ export interface MigrateResponse {
 	message: string;
 }
 
+export type LogLevel = "INFO" | "WARN" | "ERROR";
+
+export interface LogEntry {
+	timestamp: string;
+	level: LogLevel;
+	target: string;
+	message: string;
+	agent_id?: string;
+}
+
+export interface LogsResponse {
+	entries: LogEntry[];
+}
+
+export interface LogFilterParams {
+	level?: "info" | "warn" | "error";
+	target?: string;
+	agentId?: string;
+}
+
+function logFilterQuery(filter: LogFilterParams, limit?: number): string {
+	const params = new URLSearchParams();
+	if (limit) params.set("limit", String(limit));
+	if (filter.level) params.set("level", filter.level);
+	if (filter.target) params.set("target", filter.target);
+	if (filter.agentId) params.set("agent_id", filter.agentId);
+	const query = params.toString();
+	return query ? `?${query}` : "";
+}
+
 export const api = {
 	status: () => fetchJson<Types.StatusResponse>("/status"),
 	overview: () => fetchJson<Types.InstanceOverviewResponse>("/agents/instance"),
@@ -1365,18 +1426,20 @@ export const api = {
 	},
 	workerDetail: (agentId: string, workerId: string) =>
 		fetchJson<Types.WorkerDetailResponse>(`/agents/workers/detail?agent_id=${encodeURIComponent(agentId)}&worker_id=${encodeURIComponent(workerId)}`),
-	agentMemories: (agentId: string, params: MemoryGraphParams = {}) => {
+	agentMemories: (agentId: string, params: MemoriesListParams = {}) => {
 		const search = new URLSearchParams({ agent_id: agentId });
 		if (params.limit) search.set("limit", String(params.limit));
 		if (params.offset) search.set("offset", String(params.offset));
 		if (params.memory_type) search.set("memory_type", params.memory_type);
 		if (params.sort) search.set("sort", params.sort);
+		if (params.user_id) search.set("user_id", params.user_id);
 		return fetchJson<MemoriesListResponse>(`/agents/memories?${search}`);
 	},
 	searchMemories: (agentId: string, query: string, params: MemoriesSearchParams = {}) => {
 		const search = new URLSearchParams({ agent_id: agentId, q: query });
 		if (params.limit) search.set("limit", String(params.limit));
 		if (params.memory_type) search.set("memory_type", params.memory_type);
+		if (params.user_id) search.set("user_id", params.user_id);
 		return fetchJson<MemoriesSearchResponse>(`/agents/memories/search?${search}`);
 	},
 	memoryGraph: (agentId: string, params: MemoryGraphParams = {}) => {
@@ -2337,4 +2400,9 @@ export const api = {
 	},
 
 	getEventsUrl: () => `${getApiBase()}/events`,
+
+	logs: (filter: LogFilterParams = {}, limit?: number) =>
+		fetchJson<LogsResponse>(`/logs${logFilterQuery(filter, limit)}`),
+	getLogsStreamUrl: (filter: LogFilterParams = {}) =>
+		`${getApiBase()}/logs/stream${logFilterQuery(filter)}`,
 };
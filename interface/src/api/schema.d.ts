@@ -1253,6 +1253,78 @@ export interface paths {
         patch?: never;
         trace?: never;
     };
+    "/messaging/outbox": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /** `GET /messaging/outbox` — list queued and dead-lettered proactive messages. */
+        get: operations["list_outbox"];
+        put?: never;
+        post?: never;
+        /** `DELETE /messaging/outbox` — purge messages, optionally only one status. */
+        delete: operations["purge_outbox"];
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/messaging/outbox/replay": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /** `POST /messaging/outbox/replay` — requeue every dead-lettered message. */
+        post: operations["replay_dead_letters"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/messaging/outbox/{id}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post?: never;
+        /** `DELETE /messaging/outbox/{id}` — drop one message without delivering it. */
+        delete: operations["delete_outbox_message"];
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/messaging/outbox/{id}/replay": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /**
+         * `POST /messaging/outbox/{id}/replay` — retry one message now with a fresh
+         *     attempt budget.
+         */
+        post: operations["replay_outbox_message"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
     "/messaging/status": {
         parameters: {
             query?: never;
@@ -2032,10 +2104,17 @@ export interface components {
             /** Format: int64 */
             workers: number;
         };
+        /** @description Connection state tracked by an adapter's background loop. */
+        AdapterHealth: {
+            /** @description Short human-readable summary, e.g. which folders get push delivery. */
+            detail: string;
+            healthy: boolean;
+        };
         AdapterInstanceStatus: {
             binding_count: number;
             configured: boolean;
             enabled: boolean;
+            health?: null | components["schemas"]["AdapterHealth"];
             /** @description `None` means the default instance for the platform. */
             name?: string | null;
             platform: string;
@@ -2312,6 +2391,9 @@ export interface components {
             total: number;
         };
         CortexSection: {
+            autonomy_enabled: boolean;
+            /** Format: int64 */
+            autonomy_interval_secs: number;
             /** Format: int64 */
             branch_timeout_secs: number;
             /** Format: int64 */
@@ -2339,6 +2421,9 @@ export interface components {
             worker_timeout_secs: number;
         };
         CortexUpdate: {
+            autonomy_enabled?: boolean | null;
+            /** Format: int64 */
+            autonomy_interval_secs?: number | null;
             /** Format: int64 */
             branch_timeout_secs?: number | null;
             /** Format: int64 */
@@ -2403,6 +2488,7 @@ export interface components {
             interval_secs?: number;
             prompt: string;
             run_once?: boolean;
+            stateful?: boolean;
             /** Format: int64 */
             timeout_secs?: number | null;
         };
@@ -2525,9 +2611,14 @@ export interface components {
             interval_secs: number;
             prompt: string;
             run_once: boolean;
+            stateful: boolean;
             /** Format: int64 */
             timeout_secs?: number | null;
         };
+        CronJobState: {
+            state: string;
+            updated_at: string;
+        };
         CronJobWithStats: {
             active_hours?: [
                 number,
@@ -2552,6 +2643,8 @@ export interface components {
             last_executed_at?: string | null;
             prompt: string;
             run_once: boolean;
+            state?: null | components["schemas"]["CronJobState"];
+            stateful: boolean;
             /** Format: int64 */
             timeout_secs?: number | null;
         };
@@ -2742,11 +2835,27 @@ export interface components {
             email_smtp_username?: string | null;
             mattermost_base_url?: string | null;
             mattermost_token?: string | null;
+            /** @description Comma-separated channel list (e.g. `#rust,#spacebot`). */
+            irc_channels?: string | null;
+            irc_nickname?: string | null;
+            /** Format: int32 */
+            irc_port?: number | null;
+            irc_sasl_password?: string | null;
+            irc_server?: string | null;
+            irc_tls?: boolean | null;
+            matrix_access_token?: string | null;
+            matrix_homeserver_url?: string | null;
             signal_account?: string | null;
             signal_dm_allowed_users?: string | null;
             signal_http_url?: string | null;
             slack_app_token?: string | null;
             slack_bot_token?: string | null;
+            teams_app_id?: string | null;
+            teams_app_password?: string | null;
+            teams_bind?: string | null;
+            /** Format: int32 */
+            teams_port?: number | null;
+            teams_tenant_id?: string | null;
             telegram_token?: string | null;
             twitch_client_id?: string | null;
             twitch_client_secret?: string | null;
@@ -2800,6 +2909,12 @@ export interface components {
             content: string;
             /** Format: date-time */
             created_at: string;
+            /**
+             * Format: date-time
+             * @description When the memory was last demoted from the recent tier. Starts the
+             *     long-term retention clock used by pruning.
+             */
+            demoted_at?: string | null;
             /**
              * @description Soft-delete flag. Forgotten memories are excluded from search and recall
              *     but remain in the database.
@@ -2812,8 +2927,16 @@ export interface components {
             last_accessed_at: string;
             memory_type: components["schemas"]["MemoryType"];
             source?: string | null;
+            /** @description Lifecycle tier. Recent memories get a search boost and skip decay. */
+            tier?: components["schemas"]["MemoryTier"];
             /** Format: date-time */
             updated_at: string;
+            /**
+             * @description Platform-qualified ID of the person this memory is about (for example
+             *     `discord:123456`). `None` means the memory is global and visible to
+             *     every user's recall.
+             */
+            user_id?: string | null;
         };
         MemoryGraphNeighborsResponse: {
             edges: components["schemas"]["Association"][];
@@ -2843,6 +2966,8 @@ export interface components {
          * @description Memory types.
          * @enum {string}
          */
+        /** @description Lifecycle tier of a memory. */
+        MemoryTier: "recent" | "long_term";
         MemoryType: "fact" | "preference" | "decision" | "identity" | "event" | "observation" | "goal" | "todo";
         MessagesResponse: {
             has_more: boolean;
@@ -2856,9 +2981,12 @@ export interface components {
             discord: components["schemas"]["PlatformStatus"];
             email: components["schemas"]["PlatformStatus"];
             instances: components["schemas"]["AdapterInstanceStatus"][];
+            irc: components["schemas"]["PlatformStatus"];
             mattermost: components["schemas"]["PlatformStatus"];
+            matrix: components["schemas"]["PlatformStatus"];
             signal: components["schemas"]["PlatformStatus"];
             slack: components["schemas"]["PlatformStatus"];
+            teams: components["schemas"]["PlatformStatus"];
             telegram: components["schemas"]["PlatformStatus"];
             twitch: components["schemas"]["PlatformStatus"];
             webhook: components["schemas"]["PlatformStatus"];
@@ -2945,6 +3073,35 @@ export interface components {
             /** Format: int64 */
             server_startup_timeout_secs?: number | null;
         };
+        OutboxActionResponse: {
+            message: string;
+            success: boolean;
+        };
+        /**
+         * @description An outbox row as shown by the API. The response body is summarized rather
+         *     than returned, since file payloads can be large.
+         */
+        OutboxEntry: {
+            adapter: string;
+            /** Format: int64 */
+            attempts: number;
+            created_at: string;
+            id: string;
+            last_error?: string | null;
+            next_attempt_at: string;
+            /** @description Leading text of the message (or the file name). */
+            preview: string;
+            status: components["schemas"]["OutboxStatus"];
+            target: string;
+            updated_at: string;
+            /** @description `OutboundResponse` variant, e.g. `Text` or `File`. */
+            variant: string;
+        };
+        OutboxListResponse: {
+            entries: components["schemas"]["OutboxEntry"][];
+        };
+        /** @enum {string} */
+        OutboxStatus: "pending" | "dead_letter";
         PlatformCredentials: {
             discord_token?: string | null;
             email_from_address?: string | null;
@@ -6925,6 +7082,177 @@ export interface operations {
             };
         };
     };
+    list_outbox: {
+        parameters: {
+            query?: {
+                /** @description Filter by status: `pending` or `dead_letter`. Optional. */
+                status?: string | null;
+                limit?: number;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["OutboxListResponse"];
+                };
+            };
+            /** @description Invalid status filter */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+            /** @description Outbox not initialized */
+            503: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    purge_outbox: {
+        parameters: {
+            query?: {
+                /** @description Only purge rows with this status. Purges everything when omitted. */
+                status?: string | null;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["OutboxActionResponse"];
+                };
+            };
+            /** @description Invalid status filter */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+            /** @description Outbox not initialized */
+            503: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    replay_dead_letters: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["OutboxActionResponse"];
+                };
+            };
+            /** @description Outbox not initialized */
+            503: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    replay_outbox_message: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Outbox message ID */
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["OutboxActionResponse"];
+                };
+            };
+            /** @description Message not found */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+            /** @description Outbox not initialized */
+            503: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    delete_outbox_message: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Outbox message ID */
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["OutboxActionResponse"];
+                };
+            };
+            /** @description Message not found */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+            /** @description Outbox not initialized */
+            503: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
     messaging_status: {
         parameters: {
             query?: never;
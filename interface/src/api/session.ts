@@ -0,0 +1,76 @@
+import { BASE_PATH } from "./client";
+
+/**
+ * Single sign-on session for the embedded interface. When `api.oidc` is
+ * configured the server authenticates the browser with a session cookie,
+ * and every mutating request must echo the session's CSRF token.
+ */
+
+const CSRF_HEADER = "X-CSRF-Token";
+const SAFE_METHODS = new Set(["GET", "HEAD", "OPTIONS"]);
+
+let csrfToken: string | null = null;
+
+interface AuthSessionResponse {
+	oidc_enabled: boolean;
+	session: { subject: string; email: string | null; name: string | null } | null;
+	csrf_token: string | null;
+}
+
+function isSameOriginApi(url: string): boolean {
+	const target = new URL(url, window.location.href);
+	return target.origin === window.location.origin && target.pathname.startsWith(`${BASE_PATH}/api/`);
+}
+
+function installCsrfHeader() {
+	const originalFetch = window.fetch.bind(window);
+	window.fetch = (input: RequestInfo | URL, init?: RequestInit) => {
+		const request = input instanceof Request ? input : null;
+		const method = (init?.method ?? request?.method ?? "GET").toUpperCase();
+		const url = request ? request.url : input.toString();
+		if (!csrfToken || SAFE_METHODS.has(method) || !isSameOriginApi(url)) {
+			return originalFetch(input, init);
+		}
+		const headers = new Headers(init?.headers ?? request?.headers);
+		headers.set(CSRF_HEADER, csrfToken);
+		return originalFetch(input, { ...init, headers });
+	};
+}
+
+/**
+ * Load the login session before the app renders. Returns false when the
+ * browser is being sent to the identity provider to log in.
+ */
+export async function initSession(): Promise<boolean> {
+	let session: AuthSessionResponse;
+	try {
+		const response = await fetch(`${BASE_PATH}/api/auth/session`);
+		if (!response.ok) return true;
+		session = await response.json();
+	} catch {
+		return true;
+	}
+
+	if (session.oidc_enabled && !session.session) {
+		const returnTo = window.location.pathname + window.location.search;
+		window.location.href = `${BASE_PATH}/api/auth/oidc/login?return_to=${encodeURIComponent(returnTo)}`;
+		return false;
+	}
+
+	if (session.csrf_token) {
+		csrfToken = session.csrf_token;
+		installCsrfHeader();
+	}
+	return true;
+}
+
+/** Whether the interface is logged in through single sign-on. */
+export function hasSsoSession(): boolean {
+	return csrfToken !== null;
+}
+
+/** End the single sign-on session and go back through the login flow. */
+export async function logout() {
+	await fetch(`${BASE_PATH}/api/auth/logout`, { method: "POST" });
+	window.location.reload();
+}
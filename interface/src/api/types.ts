@@ -30,6 +30,7 @@ export type InstanceOverviewResponse =
 // - BranchCompletedEvent
 // - ToolStartedEvent
 // - ToolCompletedEvent
+// - ToolOutputEvent
 // - OpenCodePartUpdatedEvent
 // - WorkerTextEvent
 // - CortexChatMessageEvent
@@ -136,6 +137,7 @@ export type ConversationSettings = {
 	delegation?: "standard" | "direct";
 	response_mode?: "active" | "observe" | "mention_only";
 	save_attachments?: boolean;
+	streaming?: boolean;
 	worker_context?: {
 		history?: "none" | "summary" | "recent" | "full";
 		memory?: "none" | "ambient" | "tools" | "full";
@@ -28,7 +28,7 @@ import {TagInput} from "@/components/TagInput";
 import {FontAwesomeIcon} from "@fortawesome/react-fontawesome";
 import {faChevronDown, faPlus} from "@fortawesome/free-solid-svg-icons";
 
-type Platform = "discord" | "slack" | "telegram" | "twitch" | "email" | "webhook" | "mattermost" | "signal";
+type Platform = "discord" | "slack" | "telegram" | "twitch" | "email" | "webhook" | "mattermost" | "matrix" | "irc" | "teams" | "signal";
 
 const PLATFORM_LABELS: Record<Platform, string> = {
 	discord: "Discord",
@@ -38,6 +38,9 @@ const PLATFORM_LABELS: Record<Platform, string> = {
 	email: "Email",
 	webhook: "Webhook",
 	mattermost: "Mattermost",
+	matrix: "Matrix",
+	irc: "IRC",
+	teams: "Microsoft Teams",
 	signal: "Signal",
 };
 
@@ -47,6 +50,9 @@ const DOC_LINKS: Partial<Record<Platform, string>> = {
 	telegram: "https://docs.spacebot.sh/telegram-setup",
 	twitch: "https://docs.spacebot.sh/twitch-setup",
 	mattermost: "https://docs.spacebot.sh/mattermost-setup",
+	matrix: "https://docs.spacebot.sh/matrix-setup",
+	irc: "https://docs.spacebot.sh/irc-setup",
+	teams: "https://docs.spacebot.sh/teams-setup",
 	signal: "https://docs.spacebot.sh/signal-setup",
 };
 
@@ -65,14 +71,15 @@ export function PlatformCatalog({onAddInstance}: PlatformCatalogProps) {
 		"email",
 		"webhook",
 		"mattermost",
+		"matrix",
+		"irc",
+		"teams",
 		"signal",
 	];
 
 	const COMING_SOON = [
 		{platform: "whatsapp", name: "WhatsApp"},
-		{platform: "matrix", name: "Matrix"},
 		{platform: "imessage", name: "iMessage"},
-		{platform: "irc", name: "IRC"},
 		{platform: "lark", name: "Lark"},
 		{platform: "dingtalk", name: "DingTalk"},
 	];
@@ -654,6 +661,48 @@ export function AddInstanceCard({platform, isDefault, onCancel, onCreated}: AddI
 			}
 			credentials.mattermost_base_url = credentialInputs.mattermost_base_url.trim();
 			credentials.mattermost_token = credentialInputs.mattermost_token.trim();
+		} else if (platform === "matrix") {
+			if (!credentialInputs.matrix_homeserver_url?.trim()) {
+				setMessage({text: "Homeserver URL is required", type: "error"});
+				return;
+			}
+			if (!credentialInputs.matrix_access_token?.trim()) {
+				setMessage({text: "Access token is required", type: "error"});
+				return;
+			}
+			credentials.matrix_homeserver_url = credentialInputs.matrix_homeserver_url.trim();
+			credentials.matrix_access_token = credentialInputs.matrix_access_token.trim();
+		} else if (platform === "irc") {
+			if (!credentialInputs.irc_server?.trim()) {
+				setMessage({text: "Server is required", type: "error"});
+				return;
+			}
+			if (!credentialInputs.irc_nickname?.trim()) {
+				setMessage({text: "Nickname is required", type: "error"});
+				return;
+			}
+			credentials.irc_server = credentialInputs.irc_server.trim();
+			credentials.irc_nickname = credentialInputs.irc_nickname.trim();
+			if (credentialInputs.irc_channels?.trim()) {
+				credentials.irc_channels = credentialInputs.irc_channels.trim();
+			}
+			if (credentialInputs.irc_sasl_password?.trim()) {
+				credentials.irc_sasl_password = credentialInputs.irc_sasl_password.trim();
+			}
+		} else if (platform === "teams") {
+			if (!credentialInputs.teams_app_id?.trim()) {
+				setMessage({text: "App ID is required", type: "error"});
+				return;
+			}
+			if (!credentialInputs.teams_app_password?.trim()) {
+				setMessage({text: "App password is required", type: "error"});
+				return;
+			}
+			credentials.teams_app_id = credentialInputs.teams_app_id.trim();
+			credentials.teams_app_password = credentialInputs.teams_app_password.trim();
+			if (credentialInputs.teams_tenant_id?.trim()) {
+				credentials.teams_tenant_id = credentialInputs.teams_tenant_id.trim();
+			}
 		} else if (platform === "signal") {
 			if (!credentialInputs.signal_http_url?.trim()) {
 				setMessage({text: "HTTP URL is required", type: "error"});
@@ -999,6 +1048,108 @@ export function AddInstanceCard({platform, isDefault, onCancel, onCreated}: AddI
 					</>
 				)}
 
+				{platform === "matrix" && (
+					<>
+						<div>
+							<label className="mb-1.5 block text-sm font-medium text-ink-dull">Homeserver URL</label>
+							<Input
+								size="lg"
+								value={credentialInputs.matrix_homeserver_url ?? ""}
+								onChange={(e) => setCredentialInputs({...credentialInputs, matrix_homeserver_url: e.target.value})}
+								placeholder="https://matrix.example.org"
+							/>
+						</div>
+						<div>
+							<label className="mb-1.5 block text-sm font-medium text-ink-dull">Access Token</label>
+							<Input
+								type="password"
+								size="lg"
+								value={credentialInputs.matrix_access_token ?? ""}
+								onChange={(e) => setCredentialInputs({...credentialInputs, matrix_access_token: e.target.value})}
+								placeholder="Access token for the bot account"
+								onKeyDown={(e) => { if (e.key === "Enter") handleSave(); }}
+							/>
+						</div>
+					</>
+				)}
+
+				{platform === "irc" && (
+					<>
+						<div>
+							<label className="mb-1.5 block text-sm font-medium text-ink-dull">Server</label>
+							<Input
+								size="lg"
+								value={credentialInputs.irc_server ?? ""}
+								onChange={(e) => setCredentialInputs({...credentialInputs, irc_server: e.target.value})}
+								placeholder="irc.libera.chat"
+							/>
+						</div>
+						<div>
+							<label className="mb-1.5 block text-sm font-medium text-ink-dull">Nickname</label>
+							<Input
+								size="lg"
+								value={credentialInputs.irc_nickname ?? ""}
+								onChange={(e) => setCredentialInputs({...credentialInputs, irc_nickname: e.target.value})}
+								placeholder="spacebot"
+							/>
+						</div>
+						<div>
+							<label className="mb-1.5 block text-sm font-medium text-ink-dull">Channels</label>
+							<Input
+								size="lg"
+								value={credentialInputs.irc_channels ?? ""}
+								onChange={(e) => setCredentialInputs({...credentialInputs, irc_channels: e.target.value})}
+								placeholder="#spacebot, #rust"
+							/>
+						</div>
+						<div>
+							<label className="mb-1.5 block text-sm font-medium text-ink-dull">SASL Password</label>
+							<Input
+								type="password"
+								size="lg"
+								value={credentialInputs.irc_sasl_password ?? ""}
+								onChange={(e) => setCredentialInputs({...credentialInputs, irc_sasl_password: e.target.value})}
+								placeholder="Optional: NickServ account password"
+								onKeyDown={(e) => { if (e.key === "Enter") handleSave(); }}
+							/>
+						</div>
+					</>
+				)}
+
+				{platform === "teams" && (
+					<>
+						<div>
+							<label className="mb-1.5 block text-sm font-medium text-ink-dull">App ID</label>
+							<Input
+								size="lg"
+								value={credentialInputs.teams_app_id ?? ""}
+								onChange={(e) => setCredentialInputs({...credentialInputs, teams_app_id: e.target.value})}
+								placeholder="Microsoft App ID of the Azure Bot"
+							/>
+						</div>
+						<div>
+							<label className="mb-1.5 block text-sm font-medium text-ink-dull">App Password</label>
+							<Input
+								type="password"
+								size="lg"
+								value={credentialInputs.teams_app_password ?? ""}
+								onChange={(e) => setCredentialInputs({...credentialInputs, teams_app_password: e.target.value})}
+								placeholder="Client secret value"
+							/>
+						</div>
+						<div>
+							<label className="mb-1.5 block text-sm font-medium text-ink-dull">Tenant ID</label>
+							<Input
+								size="lg"
+								value={credentialInputs.teams_tenant_id ?? ""}
+								onChange={(e) => setCredentialInputs({...credentialInputs, teams_tenant_id: e.target.value})}
+								placeholder="Optional: required for single-tenant bots"
+								onKeyDown={(e) => { if (e.key === "Enter") handleSave(); }}
+							/>
+						</div>
+					</>
+				)}
+
 				{platform === "signal" && (
 					<>
 						<div>
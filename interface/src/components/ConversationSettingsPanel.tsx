@@ -107,6 +107,15 @@ const RESPONSE_MODE_DESCRIPTIONS: Record<string, string> = {
 		"Messages are still visible to the agent for context, but it only responds when explicitly @mentioned, replied to, or given a command. To block messages entirely, use the binding-level require mention setting instead.",
 };
 
+const STREAMING_OPTIONS = [
+	{ value: "__inherit__", label: "Default" },
+	{ value: "on", label: "On" },
+	{ value: "off", label: "Off" },
+] as const;
+
+const STREAMING_DESCRIPTION =
+	"Show replies as they are written by editing the message in place. Platforms that can't edit messages get the finished reply.";
+
 const WORKER_HISTORY_OPTIONS = [
 	{ value: "none", label: "None" },
 	{ value: "summary", label: "Summary" },
@@ -185,6 +194,12 @@ export function ConversationSettingsPanel({
 	const currentMemory = currentSettings.memory || defaults.memory;
 	const currentDelegation = currentSettings.delegation || defaults.delegation;
 	const currentResponseMode = currentSettings.response_mode || "active";
+	const currentStreaming =
+		currentSettings.streaming === undefined
+			? "__inherit__"
+			: currentSettings.streaming
+				? "on"
+				: "off";
 
 	return (
 		<div className="flex flex-col gap-3">
@@ -328,6 +343,36 @@ export function ConversationSettingsPanel({
 						</SelectContent>
 					</Select>
 				</SettingField>
+
+				<SettingField label="Streaming" description={STREAMING_DESCRIPTION}>
+					<Select
+						value={currentStreaming}
+						onValueChange={(value) => {
+							const next = { ...currentSettings };
+							if (value === "__inherit__") {
+								delete next.streaming;
+							} else {
+								next.streaming = value === "on";
+							}
+							onChange(next);
+						}}
+					>
+						<SelectTrigger className="h-7 text-xs">
+							<SelectValue />
+						</SelectTrigger>
+						<SelectContent>
+							{STREAMING_OPTIONS.map((opt) => (
+								<SelectItem
+									key={opt.value}
+									value={opt.value}
+									className="text-xs"
+								>
+									{opt.label}
+								</SelectItem>
+							))}
+						</SelectContent>
+					</Select>
+				</SettingField>
 			</div>
 
 			{/* Advanced toggle */}
@@ -19,9 +19,10 @@ import {
 } from "@dnd-kit/sortable";
 import { CSS } from "@dnd-kit/utilities";
 import { api } from "@/api/client";
+import { hasSsoSession, logout } from "@/api/session";
 import type { ChannelLiveState } from "@/hooks/useChannelLiveState";
 import { useAgentOrder } from "@/hooks/useAgentOrder";
-import { DashboardSquare01Icon, Settings01Icon, LeftToRightListBulletIcon } from "@hugeicons/core-free-icons";
+import { DashboardSquare01Icon, Settings01Icon, LeftToRightListBulletIcon, CodeIcon } from "@hugeicons/core-free-icons";
 import { HugeiconsIcon } from "@hugeicons/react";
 import { CreateAgentDialog } from "@/components/CreateAgentDialog";
 import { ProfileAvatar } from "@/components/ProfileAvatar";
@@ -113,6 +114,7 @@ export function Sidebar({ liveStates: _liveStates }: SidebarProps) {
 	const isOverview = matchRoute({ to: "/" });
 	const isTasks = matchRoute({ to: "/tasks" });
 	const isSettings = matchRoute({ to: "/settings" });
+	const isLogs = matchRoute({ to: "/logs" });
 	const isOrchestrate = matchRoute({ to: "/orchestrate" });
 
 	const sensors = useSensors(
@@ -180,6 +182,15 @@ export function Sidebar({ liveStates: _liveStates }: SidebarProps) {
 				>
 					<HugeiconsIcon icon={Settings01Icon} className="h-4 w-4" />
 				</Link>
+				<Link
+					to="/logs"
+					className={`flex h-8 w-8 items-center justify-center rounded-md ${
+						isLogs ? "bg-sidebar-selected text-sidebar-ink" : "text-sidebar-inkDull hover:bg-sidebar-selected/50"
+					}`}
+					title="Logs"
+				>
+					<HugeiconsIcon icon={CodeIcon} className="h-4 w-4" />
+				</Link>
 				<div className="my-1 h-px w-5 bg-sidebar-line" />
 				<DndContext
 					sensors={sensors}
@@ -212,6 +223,19 @@ export function Sidebar({ liveStates: _liveStates }: SidebarProps) {
 					</button>
 				)}
 			</div>
+			{hasSsoSession() && (
+				<button
+					onClick={() => void logout()}
+					className="mb-2 mt-auto flex h-8 w-8 items-center justify-center rounded-md text-sidebar-inkDull hover:bg-sidebar-selected/50"
+					title="Log out"
+				>
+					<svg width="16" height="16" viewBox="0 0 16 16" fill="none" stroke="currentColor" strokeWidth="1.5" strokeLinecap="round" strokeLinejoin="round">
+						<path d="M6 14H3a1 1 0 0 1-1-1V3a1 1 0 0 1 1-1h3" />
+						<path d="M10.5 11.5 14 8l-3.5-3.5" />
+						<path d="M14 8H6" />
+					</svg>
+				</button>
+			)}
 			{agents[0] && (
 				<CreateAgentDialog open={createOpen} onOpenChange={setCreateOpen} agentId={agents[0].id} />
 			)}
@@ -1,4 +1,4 @@
-import { useState } from "react";
+import { useEffect, useRef, useState } from "react";
 import { cx } from "@/ui/utils";
 import type { TranscriptStep, OpenCodePart } from "@/api/client";
 
@@ -25,6 +25,8 @@ export interface ToolCallPair {
 	status: ToolCallStatus;
 	/** Human-readable summary provided by live opencode parts */
 	title?: string | null;
+	/** Output lines streamed while the call is running (shell only) */
+	liveOutput?: string[];
 }
 
 // ---------------------------------------------------------------------------
@@ -1131,6 +1133,10 @@ function renderArgs(pair: ToolCallPair, renderer: ToolRenderer): React.ReactNode
 }
 
 function renderResult(pair: ToolCallPair, renderer: ToolRenderer): React.ReactNode {
+	if (pair.status === "running" && pair.liveOutput && pair.liveOutput.length > 0) {
+		return <LiveOutput lines={pair.liveOutput} />;
+	}
+
 	if (pair.status === "running") {
 		return (
 			<div className="flex items-center gap-2 px-3 py-2 text-tiny text-ink-faint">
@@ -1191,3 +1197,23 @@ function truncate(text: string, maxLen: number): string {
 	if (text.length <= maxLen) return text;
 	return text.slice(0, maxLen) + "...";
 }
+
+/** Auto-scrolling view of output lines from a running command. */
+function LiveOutput({ lines }: { lines: string[] }) {
+	const ref = useRef<HTMLPreElement>(null);
+
+	useEffect(() => {
+		if (ref.current) {
+			ref.current.scrollTop = ref.current.scrollHeight;
+		}
+	}, [lines]);
+
+	return (
+		<pre
+			ref={ref}
+			className="max-h-60 overflow-auto whitespace-pre-wrap px-3 py-2 font-mono text-tiny text-ink-dull"
+		>
+			{lines.join("\n")}
+		</pre>
+	);
+}
@@ -1,6 +1,6 @@
 import { createContext, useContext, useCallback, useEffect, useRef, useState, useMemo, type ReactNode } from "react";
 import { useQuery, useQueryClient } from "@tanstack/react-query";
-import { api, type AgentMessageEvent, type ChannelInfo, type ToolStartedEvent, type ToolCompletedEvent, type TranscriptStep, type OpenCodePart, type OpenCodePartUpdatedEvent, type WorkerTextEvent } from "@/api/client";
+import { api, type AgentMessageEvent, type ChannelInfo, type ToolStartedEvent, type ToolCompletedEvent, type ToolOutputEvent, type TranscriptStep, type OpenCodePart, type OpenCodePartUpdatedEvent, type WorkerTextEvent } from "@/api/client";
 import { generateId } from "@/lib/id";
 import { useEventSource, type ConnectionState } from "@/hooks/useEventSource";
 import { useChannelLiveState, type ChannelLiveState, type ActiveWorker } from "@/hooks/useChannelLiveState";
@@ -24,6 +24,8 @@ interface LiveContextValue {
 	liveTranscripts: Record<string, TranscriptStep[]>;
 	/** Live OpenCode parts for running workers, keyed by worker_id. Parts are insertion-ordered Maps keyed by part ID. */
 	liveOpenCodeParts: Record<string, Map<string, OpenCodePart>>;
+	/** Output lines from the currently running shell call, keyed by worker_id. Cleared when the call completes. */
+	liveToolOutput: Record<string, string[]>;
 }
 
 const LiveContext = createContext<LiveContextValue>({
@@ -38,6 +40,7 @@ const LiveContext = createContext<LiveContextValue>({
 	taskEventVersion: 0,
 	liveTranscripts: {},
 	liveOpenCodeParts: {},
+	liveToolOutput: {},
 });
 
 export function useLiveContext() {
@@ -47,6 +50,9 @@ export function useLiveContext() {
 /** Duration (ms) an edge stays "active" after a message flows through it. */
 const LINK_ACTIVE_DURATION = 3000;
 
+/** Max live output lines kept per running tool call. */
+const MAX_LIVE_TOOL_OUTPUT_LINES = 500;
+
 export function LiveContextProvider({ children, onBootstrapped }: { children: ReactNode; onBootstrapped?: () => void }) {
 	const queryClient = useQueryClient();
 
@@ -76,6 +82,18 @@ export function LiveContextProvider({ children, onBootstrapped }: { children: Re
 	// Updated via opencode_part_updated SSE events. Cleared when worker completes.
 	const [liveOpenCodeParts, setLiveOpenCodeParts] = useState<Record<string, Map<string, OpenCodePart>>>({});
 
+	// Live shell output: lines streamed while a worker's tool call is running.
+	// Replaced by the tool result when tool_completed arrives.
+	const [liveToolOutput, setLiveToolOutput] = useState<Record<string, string[]>>({});
+	const clearLiveToolOutput = useCallback((workerId: string) => {
+		setLiveToolOutput((prev) => {
+			if (!(workerId in prev)) return prev;
+			const next = { ...prev };
+			delete next[workerId];
+			return next;
+		});
+	}, []);
+
 	// Derive flat active workers from channel live states
 	const pendingToolCallIdsRef = useRef<Record<string, Record<string, string[]>>>({});
 
@@ -163,6 +181,7 @@ export function LiveContextProvider({ children, onBootstrapped }: { children: Re
 		channelHandlers.worker_completed(data);
 		const event = data as { worker_id: string };
 		delete pendingToolCallIdsRef.current[event.worker_id];
+		clearLiveToolOutput(event.worker_id);
 		// Clean up live OpenCode parts — persisted transcript takes over
 		setLiveOpenCodeParts((prev) => {
 			const next = { ...prev };
@@ -170,7 +189,7 @@ export function LiveContextProvider({ children, onBootstrapped }: { children: Re
 			return next;
 		});
 		bumpWorkerVersion();
-	}, [channelHandlers, bumpWorkerVersion]);
+	}, [channelHandlers, bumpWorkerVersion, clearLiveToolOutput]);
 
 	const wrappedToolStarted = useCallback((data: unknown) => {
 		channelHandlers.tool_started(data);
@@ -202,6 +221,7 @@ export function LiveContextProvider({ children, onBootstrapped }: { children: Re
 		channelHandlers.tool_completed(data);
 		const event = data as ToolCompletedEvent;
 		if (event.process_type === "worker") {
+			clearLiveToolOutput(event.process_id);
 			const pendingByTool = pendingToolCallIdsRef.current[event.process_id];
 			const queue = pendingByTool?.[event.tool_name] ?? [];
 			const [callId, ...rest] = queue;
@@ -227,7 +247,17 @@ export function LiveContextProvider({ children, onBootstrapped }: { children: Re
 			});
 			bumpWorkerVersion();
 		}
-	}, [channelHandlers, bumpWorkerVersion]);
+	}, [channelHandlers, bumpWorkerVersion, clearLiveToolOutput]);
+
+	// Handle live tool output — append lines for the worker's running call
+	const handleToolOutput = useCallback((data: unknown) => {
+		const event = data as ToolOutputEvent;
+		if (event.process_type !== "worker") return;
+		setLiveToolOutput((prev) => {
+			const lines = [...(prev[event.process_id] ?? []), event.line];
+			return { ...prev, [event.process_id]: lines.slice(-MAX_LIVE_TOOL_OUTPUT_LINES) };
+		});
+	}, []);
 
 	// Handle OpenCode part updates — upsert parts into the per-worker ordered map
 	const handleOpenCodePartUpdated = useCallback((data: unknown) => {
@@ -271,6 +301,7 @@ export function LiveContextProvider({ children, onBootstrapped }: { children: Re
 			worker_completed: wrappedWorkerCompleted,
 			tool_started: wrappedToolStarted,
 			tool_completed: wrappedToolCompleted,
+			tool_output: handleToolOutput,
 			opencode_part_updated: handleOpenCodePartUpdated,
 			worker_text: handleWorkerText,
 			agent_message_sent: handleAgentMessage,
@@ -278,7 +309,7 @@ export function LiveContextProvider({ children, onBootstrapped }: { children: Re
 			task_updated: bumpTaskVersion,
 			cortex_chat_message: handleCortexChatMessage,
 		}),
-		[channelHandlers, wrappedWorkerStarted, wrappedWorkerStatus, wrappedWorkerIdle, wrappedWorkerCompleted, wrappedToolStarted, wrappedToolCompleted, handleOpenCodePartUpdated, handleWorkerText, handleAgentMessage, bumpTaskVersion, handleCortexChatMessage],
+		[channelHandlers, wrappedWorkerStarted, wrappedWorkerStatus, wrappedWorkerIdle, wrappedWorkerCompleted, wrappedToolStarted, wrappedToolCompleted, handleToolOutput, handleOpenCodePartUpdated, handleWorkerText, handleAgentMessage, bumpTaskVersion, handleCortexChatMessage],
 	);
 
 	const onReconnect = useCallback(() => {
@@ -316,7 +347,7 @@ export function LiveContextProvider({ children, onBootstrapped }: { children: Re
 	}, [hasData, onBootstrapped]);
 
 	return (
-		<LiveContext.Provider value={{ liveStates, channels, connectionState, hasData, loadOlderMessages, activeLinks, activeWorkers, workerEventVersion, taskEventVersion, liveTranscripts, liveOpenCodeParts }}>
+		<LiveContext.Provider value={{ liveStates, channels, connectionState, hasData, loadOlderMessages, activeLinks, activeWorkers, workerEventVersion, taskEventVersion, liveTranscripts, liveOpenCodeParts, liveToolOutput }}>
 			{children}
 		</LiveContext.Provider>
 	);
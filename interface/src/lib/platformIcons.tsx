@@ -1,5 +1,5 @@
 import { FontAwesomeIcon } from "@fortawesome/react-fontawesome";
-import { faDiscord, faSlack, faTelegram, faTwitch, faWhatsapp } from "@fortawesome/free-brands-svg-icons";
+import { faDiscord, faMicrosoft, faSlack, faTelegram, faTwitch, faWhatsapp } from "@fortawesome/free-brands-svg-icons";
 import { faLink, faEnvelope, faComments, faComment, faServer } from "@fortawesome/free-solid-svg-icons";
 
 interface PlatformIconProps {
@@ -22,6 +22,7 @@ export function PlatformIcon({ platform, className = "text-ink-faint", size = "1
 		matrix: faComments,
 		imessage: faComment,
 		irc: faComments,
+		teams: faMicrosoft,
 		lark: faComment,
 		dingtalk: faComment,
 	};
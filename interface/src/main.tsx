@@ -1,6 +1,7 @@
 import React from "react";
 import ReactDOM from "react-dom/client";
 import { App } from "./App";
+import { initSession } from "./api/session";
 import "./ui/style/style.scss";
 import "@fontsource/ibm-plex-sans/400.css";
 import "@fontsource/ibm-plex-sans/500.css";
@@ -17,8 +18,12 @@ if (IS_TAURI) {
 	}
 }
 
-ReactDOM.createRoot(document.getElementById("root")!).render(
-	<React.StrictMode>
-		<App />
-	</React.StrictMode>,
-);
+// With single sign-on enabled, nothing renders until the browser is logged in.
+initSession().then((ready) => {
+	if (!ready) return;
+	ReactDOM.createRoot(document.getElementById("root")!).render(
+		<React.StrictMode>
+			<App />
+		</React.StrictMode>,
+	);
+});
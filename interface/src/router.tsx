@@ -25,6 +25,7 @@ import {AgentTasks} from "@/routes/AgentTasks";
 import {GlobalTasks} from "@/routes/GlobalTasks";
 import {AgentChat} from "@/routes/AgentChat";
 import {Settings} from "@/routes/Settings";
+import {Logs} from "@/routes/Logs";
 import {Orchestrate} from "@/routes/Orchestrate";
 import {useLiveContext} from "@/hooks/useLiveContext";
 import {AgentTabs} from "@/components/AgentTabs";
@@ -113,16 +114,7 @@ const logsRoute = createRoute({
 	getParentRoute: () => rootRoute,
 	path: "/logs",
 	component: function LogsPage() {
-		useSetTopBar(
-			<div className="flex h-full items-center px-6">
-				<h1 className="font-plex text-sm font-medium text-ink">Logs</h1>
-			</div>,
-		);
-		return (
-			<div className="flex flex-1 items-center justify-center">
-				<p className="text-sm text-ink-faint">Logs coming soon</p>
-			</div>
-		);
+		return <Logs />;
 	},
 });
 
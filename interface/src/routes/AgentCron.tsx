@@ -54,6 +54,7 @@ interface CronFormData {
 	active_end_hour: string;
 	enabled: boolean;
 	run_once: boolean;
+	stateful: boolean;
 	timeout_secs: string;
 }
 
@@ -70,6 +71,7 @@ function defaultFormData(): CronFormData {
 		active_end_hour: "",
 		enabled: true,
 		run_once: false,
+		stateful: false,
 		timeout_secs: "",
 	};
 }
@@ -88,6 +90,7 @@ function jobToFormData(job: CronJobWithStats): CronFormData {
 		active_end_hour: job.active_hours?.[1]?.toString() ?? "",
 		enabled: job.enabled,
 		run_once: job.run_once,
+		stateful: job.stateful,
 		timeout_secs: job.timeout_secs?.toString() ?? "",
 	};
 }
@@ -106,6 +109,7 @@ function formDataToRequest(data: CronFormData): CreateCronRequest {
 		active_end_hour: active_end,
 		enabled: data.enabled,
 		run_once: data.run_once,
+		stateful: data.stateful,
 		timeout_secs: timeout || undefined,
 	};
 }
@@ -514,6 +518,14 @@ export function AgentCron({ agentId }: AgentCronProps) {
 								<Label>Run Once</Label>
 								<Toggle checked={formData.run_once} onCheckedChange={(checked) => setFormData((d) => ({ ...d, run_once: checked }))} size="lg" />
 							</div>
+
+							<div className="flex items-center justify-between">
+								<div>
+									<Label>Stateful</Label>
+									<p className="text-tiny text-ink-faint">Carry notes and recent results between runs</p>
+								</div>
+								<Toggle checked={formData.stateful} onCheckedChange={(checked) => setFormData((d) => ({ ...d, stateful: checked }))} size="lg" />
+							</div>
 						</div>
 					</div>
 
@@ -640,6 +652,14 @@ function CronJobCard({
 						{job.run_once && (
 							<span className="rounded bg-accent/20 px-1.5 py-0.5 text-tiny text-accent">one-time</span>
 						)}
+						{job.stateful && (
+							<span
+								className="rounded bg-violet-500/20 px-1.5 py-0.5 text-tiny text-violet-400"
+								title={job.state ? `State (updated ${job.state.updated_at}):\n${job.state.state}` : "No state saved yet"}
+							>
+								stateful
+							</span>
+						)}
 					</div>
 
 					<p className="mb-2 text-sm text-ink-dull" title={job.prompt}>
@@ -48,7 +48,7 @@ export function AgentWorkers({agentId}: {agentId: string}) {
 	const navigate = useNavigate();
 	const routeSearch = useSearch({strict: false}) as {worker?: string};
 	const selectedWorkerId = routeSearch.worker ?? null;
-	const {activeWorkers, workerEventVersion, liveTranscripts, liveOpenCodeParts} = useLiveContext();
+	const {activeWorkers, workerEventVersion, liveTranscripts, liveOpenCodeParts, liveToolOutput} = useLiveContext();
 
 	// Invalidate worker queries when SSE events fire
 	const prevVersion = useRef(workerEventVersion);
@@ -253,6 +253,7 @@ export function AgentWorkers({agentId}: {agentId: string}) {
 						liveWorker={scopedActiveWorkers[selectedWorkerId]}
 						liveTranscript={liveTranscripts[selectedWorkerId]}
 						liveOpenCodeParts={liveOpenCodeParts[selectedWorkerId]}
+						liveToolOutput={liveToolOutput[selectedWorkerId]}
 					/>
 				) : (
 					<div className="flex flex-1 items-center justify-center">
@@ -363,11 +364,13 @@ function WorkerDetail({
 	liveWorker,
 	liveTranscript,
 	liveOpenCodeParts,
+	liveToolOutput,
 }: {
 	detail: WorkerDetailResponse;
 	liveWorker?: LiveWorker;
 	liveTranscript?: TranscriptStep[];
 	liveOpenCodeParts?: Map<string, OpenCodePart>;
+	liveToolOutput?: string[];
 }) {
 	const isLive = detail.status === "running" || !!liveWorker;
 	const isIdle = liveWorker?.isIdle ?? detail.status === "idle";
@@ -580,7 +583,13 @@ function WorkerDetail({
 												<Markdown>{item.text}</Markdown>
 											</div>
 										) : (
-											<ToolCall pair={item.pair} />
+											<ToolCall
+												pair={
+													item.pair.status === "running"
+														? {...item.pair, liveOutput: liveToolOutput}
+														: item.pair
+												}
+											/>
 										)}
 									</motion.div>
 								))}
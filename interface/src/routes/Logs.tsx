@@ -0,0 +1,230 @@
+import { useCallback, useEffect, useMemo, useRef, useState } from "react";
+import { useQuery } from "@tanstack/react-query";
+import { api, type LogEntry, type LogFilterParams } from "@/api/client";
+import { useEventSource } from "@/hooks/useEventSource";
+import { useServer } from "@/hooks/useServer";
+import { useSetTopBar } from "@/components/TopBar";
+import { Button, SearchInput } from "@/ui";
+import { PauseIcon, PlayIcon } from "@hugeicons/core-free-icons";
+import { HugeiconsIcon } from "@hugeicons/react";
+
+/** Cap on entries held in the browser, independent of the server buffer. */
+const MAX_ENTRIES = 2000;
+
+type LevelFilter = "all" | "info" | "warn" | "error";
+
+const LEVEL_OPTIONS: { value: LevelFilter; label: string }[] = [
+	{ value: "all", label: "All levels" },
+	{ value: "info", label: "Info+" },
+	{ value: "warn", label: "Warn+" },
+	{ value: "error", label: "Error" },
+];
+
+const LEVEL_STYLES: Record<string, string> = {
+	INFO: "bg-cyan-500/15 text-cyan-400",
+	WARN: "bg-amber-500/15 text-amber-400",
+	ERROR: "bg-red-500/15 text-red-400",
+};
+
+function formatTime(timestamp: string): string {
+	const date = new Date(timestamp);
+	if (Number.isNaN(date.getTime())) return timestamp;
+	const pad = (value: number, width = 2) => String(value).padStart(width, "0");
+	return `${pad(date.getHours())}:${pad(date.getMinutes())}:${pad(date.getSeconds())}.${pad(date.getMilliseconds(), 3)}`;
+}
+
+/** Keep the last few segments of a module path: `spacebot::agent::channel` -> `agent::channel`. */
+function shortTarget(target: string): string {
+	const segments = target.split("::");
+	return segments.length > 2 ? segments.slice(-2).join("::") : target;
+}
+
+export function Logs() {
+	const { serverUrl } = useServer();
+	const [level, setLevel] = useState<LevelFilter>("all");
+	const [agentId, setAgentId] = useState("");
+	const [target, setTarget] = useState("");
+	const [search, setSearch] = useState("");
+	const [paused, setPaused] = useState(false);
+	const [autoScroll, setAutoScroll] = useState(true);
+	const [entries, setEntries] = useState<LogEntry[]>([]);
+	const [hasNewBelow, setHasNewBelow] = useState(false);
+
+	const scrollRef = useRef<HTMLDivElement>(null);
+	const pausedRef = useRef(paused);
+	pausedRef.current = paused;
+
+	const { data: agentsData } = useQuery({
+		queryKey: ["agents"],
+		queryFn: api.agents,
+		staleTime: 10_000,
+	});
+	const agents = agentsData?.agents ?? [];
+
+	// Level, target and agent are filtered server-side; free-text search is local.
+	const filter = useMemo<LogFilterParams>(
+		() => ({
+			level: level === "all" ? undefined : level,
+			target: target.trim() || undefined,
+			agentId: agentId || undefined,
+		}),
+		[level, target, agentId],
+	);
+
+	const loadHistory = useCallback(() => {
+		api.logs(filter)
+			.then((response) => setEntries(response.entries.slice(-MAX_ENTRIES)))
+			.catch((error) => console.warn("failed to load logs", error));
+	}, [filter, serverUrl]);
+
+	useEffect(() => {
+		loadHistory();
+	}, [loadHistory]);
+
+	const streamUrl = useMemo(() => api.getLogsStreamUrl(filter), [filter, serverUrl]);
+
+	const handlers = useMemo(
+		() => ({
+			log: (data: unknown) => {
+				if (pausedRef.current) return;
+				const entry = data as LogEntry;
+				setEntries((previous) => {
+					const next = [...previous, entry];
+					return next.length > MAX_ENTRIES ? next.slice(-MAX_ENTRIES) : next;
+				});
+			},
+		}),
+		[],
+	);
+
+	const { connectionState } = useEventSource(streamUrl, {
+		handlers,
+		onReconnect: loadHistory,
+	});
+
+	const visible = useMemo(() => {
+		const needle = search.trim().toLowerCase();
+		if (!needle) return entries;
+		return entries.filter(
+			(entry) =>
+				entry.message.toLowerCase().includes(needle) ||
+				entry.target.toLowerCase().includes(needle),
+		);
+	}, [entries, search]);
+
+	useEffect(() => {
+		const element = scrollRef.current;
+		if (!element) return;
+		if (autoScroll) {
+			element.scrollTop = element.scrollHeight;
+			setHasNewBelow(false);
+		} else {
+			setHasNewBelow(true);
+		}
+	}, [visible, autoScroll]);
+
+	const handleScroll = () => {
+		const element = scrollRef.current;
+		if (!element) return;
+		const atBottom = element.scrollHeight - element.scrollTop - element.clientHeight < 24;
+		setAutoScroll(atBottom);
+		if (atBottom) setHasNewBelow(false);
+	};
+
+	useSetTopBar(
+		<div className="flex h-full items-center gap-3 px-6">
+			<h1 className="font-plex text-sm font-medium text-ink">Logs</h1>
+			<span className="text-tiny text-ink-faint">
+				{connectionState === "connected" ? (paused ? "paused" : "live") : connectionState}
+			</span>
+		</div>,
+	);
+
+	return (
+		<div className="flex h-full flex-col overflow-hidden">
+			<div className="flex items-center gap-3 border-b border-app-line/50 bg-app-darkBox/20 px-6 py-3">
+				<SearchInput
+					placeholder="Search messages and targets..."
+					value={search}
+					onChange={(event) => setSearch(event.target.value)}
+					className="flex-1"
+				/>
+				<select
+					className="rounded-md border border-app-line bg-app-darkBox px-2 py-1.5 text-sm text-ink focus:border-accent focus:outline-none"
+					value={level}
+					onChange={(event) => setLevel(event.target.value as LevelFilter)}
+				>
+					{LEVEL_OPTIONS.map((option) => (
+						<option key={option.value} value={option.value}>
+							{option.label}
+						</option>
+					))}
+				</select>
+				<select
+					className="rounded-md border border-app-line bg-app-darkBox px-2 py-1.5 text-sm text-ink focus:border-accent focus:outline-none"
+					value={agentId}
+					onChange={(event) => setAgentId(event.target.value)}
+				>
+					<option value="">All agents</option>
+					{agents.map((agent) => (
+						<option key={agent.id} value={agent.id}>
+							{agent.display_name ?? agent.id}
+						</option>
+					))}
+				</select>
+				<input
+					className="w-44 rounded-md border border-app-line bg-app-darkBox px-2 py-1.5 text-sm text-ink placeholder:text-ink-faint focus:border-accent focus:outline-none"
+					placeholder="Target contains..."
+					value={target}
+					onChange={(event) => setTarget(event.target.value)}
+				/>
+				<Button
+					onClick={() => setPaused(!paused)}
+					variant="ghost"
+					size="icon"
+					title={paused ? "Resume streaming" : "Pause streaming"}
+				>
+					<HugeiconsIcon icon={paused ? PlayIcon : PauseIcon} className="h-4 w-4" />
+				</Button>
+			</div>
+
+			<div className="relative flex-1 overflow-hidden">
+				<div
+					ref={scrollRef}
+					onScroll={handleScroll}
+					className="h-full overflow-y-auto px-6 py-2 font-mono text-xs"
+				>
+					{visible.length === 0 ? (
+						<p className="py-8 text-center text-sm text-ink-faint">No log entries</p>
+					) : (
+						visible.map((entry, index) => (
+							<div key={`${entry.timestamp}-${index}`} className="flex items-start gap-3 py-0.5">
+								<span className="shrink-0 text-ink-faint">{formatTime(entry.timestamp)}</span>
+								<span
+									className={`shrink-0 rounded px-1.5 text-tiny font-medium ${LEVEL_STYLES[entry.level] ?? "text-ink-dull"}`}
+								>
+									{entry.level}
+								</span>
+								<span className="w-40 shrink-0 truncate text-ink-faint" title={entry.target}>
+									{shortTarget(entry.target)}
+								</span>
+								<span className="min-w-0 flex-1 whitespace-pre-wrap break-words text-ink-dull">
+									{entry.message}
+								</span>
+							</div>
+						))
+					)}
+				</div>
+				{hasNewBelow && !autoScroll && (
+					<button
+						type="button"
+						onClick={() => setAutoScroll(true)}
+						className="absolute bottom-3 left-1/2 -translate-x-1/2 rounded-full bg-accent px-3 py-1 text-xs text-white shadow"
+					>
+						New logs below
+					</button>
+				)}
+			</div>
+		</div>
+	);
+}
@@ -0,0 +1,18 @@
+-- LLM spend ledger: one row per completion that reported token usage.
+-- `day` is the calendar day in the agent's timezone, used for daily and
+-- monthly budget windows.
+CREATE TABLE IF NOT EXISTS llm_spend (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    day TEXT NOT NULL,
+    channel_id TEXT,
+    process_type TEXT NOT NULL,
+    model TEXT NOT NULL,
+    input_tokens INTEGER NOT NULL DEFAULT 0,
+    output_tokens INTEGER NOT NULL DEFAULT 0,
+    cached_input_tokens INTEGER NOT NULL DEFAULT 0,
+    cost_usd REAL NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_llm_spend_day ON llm_spend(day);
+CREATE INDEX idx_llm_spend_channel ON llm_spend(channel_id, day);
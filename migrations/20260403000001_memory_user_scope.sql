@@ -0,0 +1,7 @@
+-- Scope memories to the person they are about. NULL means global: visible to
+-- every user's recall. Values are platform-qualified sender IDs such as
+-- `discord:123456789`.
+ALTER TABLE memories ADD COLUMN user_id TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_memories_user_id ON memories(user_id);
+CREATE INDEX IF NOT EXISTS idx_memories_user_type ON memories(user_id, memory_type);
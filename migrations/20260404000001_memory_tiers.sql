@@ -0,0 +1,9 @@
+-- Two-tier memory lifecycle. New memories saved by branches enter the
+-- `recent` tier, which gets a search boost and is exempt from decay, and are
+-- demoted to `long_term` once they go unaccessed. Existing memories start in
+-- `long_term`. `demoted_at` starts the long-term retention clock.
+ALTER TABLE memories ADD COLUMN tier TEXT NOT NULL DEFAULT 'long_term';
+ALTER TABLE memories ADD COLUMN demoted_at TIMESTAMP;
+
+CREATE INDEX IF NOT EXISTS idx_memories_tier ON memories(tier, forgotten);
+CREATE INDEX IF NOT EXISTS idx_memories_tier_access ON memories(tier, last_accessed_at);
@@ -0,0 +1,16 @@
+-- Known humans, one row per platform identity. Filled as people message the
+-- agent; the cortex participant loop caches a short summary per person.
+CREATE TABLE IF NOT EXISTS humans (
+    id TEXT PRIMARY KEY,
+    display_name TEXT NOT NULL,
+    platform TEXT NOT NULL,               -- "discord", "slack", "telegram", ...
+    platform_user_id TEXT NOT NULL,       -- raw platform sender ID
+    human_def_id TEXT,                    -- matching [[humans]] config entry, if any
+    summary TEXT,                         -- cortex-generated 2-3 sentence bio
+    last_seen_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_summary_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(platform, platform_user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_humans_last_seen ON humans(last_seen_at);
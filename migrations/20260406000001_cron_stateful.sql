@@ -0,0 +1,10 @@
+-- Opt-in memory continuity for cron jobs. Stateful jobs see their state
+-- document and recent results on each run and write the state back.
+ALTER TABLE cron_jobs ADD COLUMN stateful INTEGER NOT NULL DEFAULT 0;
+
+CREATE TABLE IF NOT EXISTS cron_job_state (
+    cron_id TEXT PRIMARY KEY,
+    state TEXT NOT NULL,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (cron_id) REFERENCES cron_jobs(id) ON DELETE CASCADE
+);
@@ -0,0 +1,2 @@
+-- The autonomy loop recalls its own previous findings by source tag.
+CREATE INDEX IF NOT EXISTS idx_memories_source ON memories(source, created_at);
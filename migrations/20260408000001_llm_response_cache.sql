@@ -0,0 +1,19 @@
+-- Cached completions for background processes (cortex, compactor).
+-- `cache_key` hashes the whole request; `context_key` hashes everything but
+-- the conversation, so similarity lookups only compare prompts that share a
+-- preamble, model, and settings. `embedding` holds little-endian f32s of the
+-- conversation text when similarity matching is enabled.
+CREATE TABLE IF NOT EXISTS llm_response_cache (
+    cache_key TEXT PRIMARY KEY,
+    context_key TEXT NOT NULL,
+    process_type TEXT NOT NULL,
+    model TEXT NOT NULL,
+    embedding BLOB,
+    response TEXT NOT NULL,
+    hits INTEGER NOT NULL DEFAULT 0,
+    created_at INTEGER NOT NULL,
+    last_used_at INTEGER NOT NULL
+);
+
+CREATE INDEX idx_llm_response_cache_context ON llm_response_cache(context_key, created_at);
+CREATE INDEX idx_llm_response_cache_last_used ON llm_response_cache(last_used_at);
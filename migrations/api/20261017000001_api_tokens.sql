@@ -0,0 +1,42 @@
+-- Named API tokens and the audit log of mutating API calls.
+--
+-- Tokens are stored as SHA-256 hashes; the plaintext is shown once at
+-- creation. Revoked tokens are kept so audit rows still name them.
+
+CREATE TABLE IF NOT EXISTS api_tokens (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    token_hash TEXT NOT NULL UNIQUE,
+    token_prefix TEXT NOT NULL,        -- leading characters, for recognizing a token
+    scopes TEXT NOT NULL,              -- JSON array, e.g. ["read", "messaging"]
+    agent_ids TEXT NOT NULL DEFAULT '[]',  -- JSON array; empty means every agent
+    expires_at TEXT,
+    last_used_at TEXT,
+    revoked_at TEXT,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+CREATE TABLE IF NOT EXISTS audit_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    actor TEXT NOT NULL,               -- 'root', 'anonymous', or 'token:<name>'
+    token_id TEXT,
+    method TEXT NOT NULL,
+    path TEXT NOT NULL,
+    agent_id TEXT,
+    status INTEGER NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_token ON audit_log(token_id, id);
+
+CREATE TRIGGER IF NOT EXISTS audit_log_no_update
+BEFORE UPDATE ON audit_log
+BEGIN
+    SELECT RAISE(ABORT, 'audit log is append-only');
+END;
+
+CREATE TRIGGER IF NOT EXISTS audit_log_no_delete
+BEFORE DELETE ON audit_log
+BEGIN
+    SELECT RAISE(ABORT, 'audit log is append-only');
+END;
@@ -0,0 +1,20 @@
+-- Web interface sessions created by OpenID Connect single sign-on.
+--
+-- Session cookies are stored as SHA-256 hashes, like API tokens. The CSRF
+-- token is returned to the logged-in interface and must be echoed on every
+-- mutating request.
+
+CREATE TABLE IF NOT EXISTS api_sessions (
+    id TEXT PRIMARY KEY,
+    session_hash TEXT NOT NULL UNIQUE,
+    csrf_token TEXT NOT NULL,
+    subject TEXT NOT NULL,             -- the ID token's `sub` claim
+    email TEXT,
+    name TEXT,
+    groups TEXT NOT NULL DEFAULT '[]', -- JSON array, from the configured groups claim
+    scopes TEXT NOT NULL,              -- JSON array of API scopes granted at login
+    expires_at TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_api_sessions_expires ON api_sessions(expires_at);
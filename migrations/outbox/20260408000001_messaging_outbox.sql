@@ -0,0 +1,19 @@
+-- Durable outbox for proactive messages (cron deliveries, budget warnings).
+-- Rows are written before the first delivery attempt and deleted once
+-- delivered, so anything left here is either waiting for a retry or
+-- dead-lettered.
+
+CREATE TABLE IF NOT EXISTS outbox (
+    id TEXT PRIMARY KEY,
+    adapter TEXT NOT NULL,             -- runtime adapter key, e.g. 'discord' or 'slack:ops'
+    target TEXT NOT NULL,
+    response TEXT NOT NULL,            -- JSON-serialized OutboundResponse
+    status TEXT NOT NULL DEFAULT 'pending',  -- 'pending' | 'dead_letter'
+    attempts INTEGER NOT NULL DEFAULT 0,     -- completed delivery rounds
+    last_error TEXT,
+    next_attempt_at TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_outbox_due ON outbox(status, next_attempt_at);
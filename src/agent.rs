@@ -15,6 +15,7 @@ mod invariant_harness;
 pub mod process_control;
 pub mod prompt_snapshot;
 pub mod status;
+pub mod tool_approval;
 pub mod worker;
 
 pub(crate) fn panic_payload_to_string(panic_payload: &(dyn std::any::Any + Send)) -> String {
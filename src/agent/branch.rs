@@ -110,14 +110,25 @@ impl Branch {
         self.maybe_compact_history();
 
         let routing = self.deps.runtime_config.routing.load();
-        let model_name = self
-            .model_override
-            .as_deref()
-            .unwrap_or_else(|| routing.resolve(ProcessType::Branch, None))
-            .to_string();
+        // Memory persistence branches run a fixed system prompt, so scoring
+        // it for prompt routing would only add noise.
+        let routing_text = self
+            .memory_persistence_contract
+            .is_none()
+            .then_some(prompt.as_str());
+        let (model_name, prompt_tier) = match self.model_override.as_deref() {
+            Some(model_override) => (model_override.to_string(), None),
+            None => {
+                let (model_name, prompt_tier) =
+                    routing.resolve_for_message(ProcessType::Branch, None, routing_text);
+                (model_name.to_string(), prompt_tier)
+            }
+        };
         let model = SpacebotModel::make(&self.deps.llm_manager, &model_name)
             .with_context(&*self.deps.agent_id, "branch")
-            .with_routing((**routing).clone());
+            .with_channel(&*self.channel_id)
+            .with_routing((**routing).clone())
+            .with_prompt_tier(prompt_tier);
 
         let agent = AgentBuilder::new(model)
             .preamble(&self.system_prompt)
@@ -10,18 +10,30 @@ use crate::agent::channel_history::{
 };
 use crate::agent::channel_prompt::{
     MAX_RETRIGGERS_PER_TURN, RETRIGGER_DEBOUNCE_MS, RETRIGGER_MAX_TURNS, TemporalContext,
+    build_participant_info,
 };
 use crate::agent::compactor::Compactor;
 use crate::agent::process_control::ControlActionResult;
 use crate::agent::status::{StatusBlock, SystemInfo};
+use crate::agent::tool_approval::{
+    PendingToolApproval, ToolApprovalDecision, ToolApprovalError, ToolApprovalRegistry,
+};
 use crate::agent::worker::Worker;
+use crate::commands::CommandRegistry;
+use crate::config::{
+    AccessCapability, AccessConfig, AccessDenied, AccessGrant, AccessRole, CommandAction,
+};
+use crate::conversation::humans::match_human_def;
 use crate::conversation::settings::{
     DelegationMode, MemoryMode, ResolvedConversationSettings, ResponseMode,
 };
-use crate::conversation::{ChannelStore, ConversationLogger, ProcessRunLogger};
+use crate::conversation::{ChannelStore, ConversationLogger, HumanStore, ProcessRunLogger};
 use crate::error::{AgentError, Result};
 use crate::hooks::SpacebotHook;
 use crate::llm::SpacebotModel;
+use crate::llm::budget::BudgetDecision;
+use crate::memory::types::user_scope_id;
+use crate::messaging::streaming::ReplyStream;
 use crate::{
     AgentDeps, BranchId, ChannelId, InboundMessage, OutboundResponse, ProcessEvent, ProcessId,
     ProcessType, RoutedResponse, RoutedSender, WorkerId,
@@ -59,8 +71,54 @@ struct PendingResult {
     success: bool,
 }
 
+/// Outcome of matching an inbound message against the command registry.
+enum RegistryCommandOutcome {
+    /// Not a registered command; process the message normally.
+    NotMatched,
+    /// The command replied or dispatched its work directly.
+    Handled,
+    /// Run the rendered prompt as the user's turn.
+    Prompt(String),
+}
+
 const EVENT_LAG_WARNING_INTERVAL_SECS: u64 = 30;
 
+/// Most senders a channel session keeps for `## Participant Info`. The least
+/// recently seen sender is dropped to make room for a new one.
+const MAX_TRACKED_PARTICIPANTS: usize = 64;
+
+/// A sender seen in this channel session.
+struct ActiveParticipant {
+    display_name: String,
+    last_seen: std::time::Instant,
+}
+
+/// Record `key` as seen now, evicting the least recently seen sender when the
+/// map is full.
+fn remember_participant(
+    participants: &mut HashMap<(String, String), ActiveParticipant>,
+    key: (String, String),
+    display_name: &str,
+    now: std::time::Instant,
+) {
+    if !participants.contains_key(&key)
+        && participants.len() >= MAX_TRACKED_PARTICIPANTS
+        && let Some(oldest) = participants
+            .iter()
+            .min_by_key(|(_, participant)| participant.last_seen)
+            .map(|(key, _)| key.clone())
+    {
+        participants.remove(&oldest);
+    }
+    participants.insert(
+        key,
+        ActiveParticipant {
+            display_name: display_name.to_string(),
+            last_seen: now,
+        },
+    );
+}
+
 async fn recv_channel_event(
     event_rx: &mut broadcast::Receiver<ProcessEvent>,
 ) -> crate::BroadcastRecvResult<ProcessEvent> {
@@ -113,7 +171,15 @@ pub struct ChannelState {
     pub process_run_logger: ProcessRunLogger,
     /// Discord message ID to reply to for work spawned in the current turn.
     pub reply_target_message_id: Arc<RwLock<Option<String>>>,
+    /// Memory scope ID (`platform:sender_id`) of the sender driving the
+    /// current turn. Branches and direct-mode memory tools tag saves with it
+    /// and scope recall to it.
+    pub current_user_id: Arc<RwLock<Option<String>>>,
+    /// Access role of the sender driving the current turn. Tools built for
+    /// the turn capture it through [`ChannelState::access_grant`].
+    pub sender_role: Arc<RwLock<AccessRole>>,
     pub channel_store: ChannelStore,
+    pub human_store: HumanStore,
     pub screenshot_dir: std::path::PathBuf,
     pub logs_dir: std::path::PathBuf,
     /// Prompt snapshot store for debugging prompt construction.
@@ -136,6 +202,14 @@ pub struct ChannelState {
 }
 
 impl ChannelState {
+    /// The current sender's role under the agent's live access policy.
+    pub async fn access_grant(&self) -> AccessGrant {
+        AccessGrant::new(
+            self.deps.runtime_config.access.load_full(),
+            *self.sender_role.read().await,
+        )
+    }
+
     /// Cancel a running worker by aborting its tokio task and cleaning up state.
     /// Returns an error message if the worker is not found.
     pub async fn cancel_worker(&self, worker_id: WorkerId) -> std::result::Result<(), String> {
@@ -428,6 +502,10 @@ pub struct Channel {
     pub conversation_context: Option<String>,
     /// Context monitor that triggers background compaction.
     pub compactor: Compactor,
+    /// Senders active in this channel session, keyed by `(platform, sender_id)`,
+    /// with their latest display name. Drives `## Participant Info`. Bounded
+    /// by `MAX_TRACKED_PARTICIPANTS`.
+    participants: HashMap<(String, String), ActiveParticipant>,
     /// Count of user messages since last memory persistence branch.
     message_count: usize,
     /// When the last memory persistence branch was triggered.
@@ -542,7 +620,10 @@ impl Channel {
             conversation_logger,
             process_run_logger,
             reply_target_message_id: Arc::new(RwLock::new(None)),
+            current_user_id: Arc::new(RwLock::new(None)),
+            sender_role: Arc::new(RwLock::new(AccessRole::Owner)),
             channel_store: channel_store.clone(),
+            human_store: HumanStore::new(deps.sqlite_pool.clone()),
             screenshot_dir,
             logs_dir,
             prompt_snapshot_store,
@@ -593,6 +674,7 @@ impl Channel {
             source_adapter: None,
             conversation_context: None,
             compactor,
+            participants: HashMap::new(),
             message_count: 0,
             last_persistence_at: std::time::Instant::now(),
             memory_persistence_branches: HashSet::new(),
@@ -736,7 +818,7 @@ impl Channel {
     }
 
     fn persist_inbound_user_message(
-        &self,
+        &mut self,
         message: &InboundMessage,
         raw_text: &str,
         saved_attachments: Option<&[channel_attachments::SavedAttachmentMeta]>,
@@ -771,6 +853,56 @@ impl Channel {
         self.state
             .channel_store
             .upsert(&message.conversation_id, &metadata);
+        self.track_participant(message, sender_name);
+    }
+
+    /// Record a sender in the `humans` table and this session's participant map.
+    fn track_participant(&mut self, message: &InboundMessage, sender_name: &str) {
+        let human_defs = self.deps.humans.load();
+        let human_def_id = match_human_def(&human_defs, &message.source, &message.sender_id)
+            .map(|human| human.id.as_str());
+        self.state.human_store.upsert(
+            &message.source,
+            &message.sender_id,
+            sender_name,
+            human_def_id,
+        );
+        remember_participant(
+            &mut self.participants,
+            (message.source.clone(), message.sender_id.clone()),
+            sender_name,
+            std::time::Instant::now(),
+        );
+    }
+
+    /// Render `## Participant Info` for the senders active in this session, or
+    /// `None` when disabled or below `min_participants`.
+    async fn build_participant_info(&self) -> Option<String> {
+        let config = **self.deps.runtime_config.participants.load();
+        if !config.enabled || self.participants.len() < config.min_participants {
+            return None;
+        }
+
+        let identities: Vec<(String, String)> = self.participants.keys().cloned().collect();
+        let humans = match self
+            .state
+            .human_store
+            .get_by_platform_ids(&identities)
+            .await
+        {
+            Ok(humans) => humans,
+            Err(error) => {
+                tracing::warn!(channel_id = %self.id, %error, "failed to load participant summaries");
+                return None;
+            }
+        };
+
+        let display_names: HashMap<(String, String), String> = self
+            .participants
+            .iter()
+            .map(|(key, participant)| (key.clone(), participant.display_name.clone()))
+            .collect();
+        build_participant_info(&humans, &self.deps.humans.load(), &display_names)
     }
 
     fn suppress_plaintext_fallback(&self) -> bool {
@@ -880,6 +1012,136 @@ impl Channel {
         }
     }
 
+    /// Tell the user and skip the turn when a hard spend limit covering this
+    /// channel has been reached. Returns true if the turn was skipped.
+    async fn reply_if_over_budget(&mut self) -> bool {
+        let Some(tracker) = self.deps.llm_manager.spend_tracker(&self.deps.agent_id) else {
+            return false;
+        };
+        let BudgetDecision::Stop(exceeded) = tracker.check(Some(&*self.id)).await else {
+            return false;
+        };
+        tracing::info!(channel_id = %self.id, %exceeded, "spend budget exhausted, skipping turn");
+        self.send_builtin_text(exceeded.user_message(), "budget-exceeded")
+            .await;
+        true
+    }
+
+    /// Post approve/deny buttons for a worker tool call paused by the
+    /// approval policy.
+    async fn send_tool_approval_prompt(
+        &mut self,
+        worker_id: WorkerId,
+        approval_id: &str,
+        tool_name: &str,
+        args: &str,
+        timeout_secs: u64,
+    ) {
+        use crate::agent::tool_approval::action_id;
+
+        let text = format!(
+            "Worker {} wants to run `{tool_name}`:\n```\n{args}\n```\n\
+             It will be denied if nobody decides within {timeout_secs}s.",
+            &worker_id.to_string()[..8]
+        );
+        let response = OutboundResponse::RichMessage {
+            text: text.clone(),
+            blocks: Vec::new(),
+            cards: Vec::new(),
+            interactive_elements: vec![crate::InteractiveElements::Buttons {
+                buttons: vec![
+                    crate::Button {
+                        label: "Approve".into(),
+                        custom_id: Some(action_id(ToolApprovalDecision::Approved, approval_id)),
+                        style: crate::ButtonStyle::Success,
+                        url: None,
+                    },
+                    crate::Button {
+                        label: "Deny".into(),
+                        custom_id: Some(action_id(ToolApprovalDecision::Denied, approval_id)),
+                        style: crate::ButtonStyle::Danger,
+                        url: None,
+                    },
+                ],
+            }],
+            poll: None,
+        };
+        match self.send_routed(response).await {
+            Ok(()) => self.state.conversation_logger.log_bot_message_with_name(
+                &self.state.channel_id,
+                &text,
+                Some(self.agent_display_name()),
+            ),
+            Err(error) => {
+                tracing::error!(%error, channel_id = %self.id, approval_id, "failed to send tool approval prompt");
+            }
+        }
+    }
+
+    /// Resolve a pending tool approval from an approve/deny button press.
+    /// Returns true if the interaction was one of ours.
+    async fn try_resolve_tool_approval(&mut self, message: &InboundMessage) -> bool {
+        let crate::MessageContent::Interaction { action_id, .. } = &message.content else {
+            return false;
+        };
+        let Some((decision, approval_id)) = crate::agent::tool_approval::parse_action_id(action_id)
+        else {
+            return false;
+        };
+        let access = self.deps.runtime_config.access.load_full();
+        // The resolution notice is posted when the hook reports the outcome.
+        match resolve_tool_approval_interaction(
+            access,
+            &self.deps.tool_approvals,
+            message,
+            decision,
+            approval_id,
+        ) {
+            Ok(Ok(_)) => {}
+            Ok(Err(error)) => {
+                tracing::debug!(%error, channel_id = %self.id, "ignoring stale tool approval interaction");
+                self.send_builtin_text(
+                    "That approval request is no longer pending.".to_string(),
+                    "tool-approval-stale",
+                )
+                .await;
+            }
+            Err(denied) => {
+                tracing::info!(
+                    channel_id = %self.id,
+                    sender_id = %message.sender_id,
+                    role = %denied.role,
+                    approval_id,
+                    "tool approval interaction denied"
+                );
+                self.send_builtin_text(
+                    format!("You may not decide on this tool call: {denied}."),
+                    "access-denied",
+                )
+                .await;
+            }
+        }
+        true
+    }
+
+    /// Check the sender may run a channel-changing built-in command, replying
+    /// with the reason when they may not.
+    async fn builtin_command_allowed(&mut self, message: &InboundMessage) -> bool {
+        let access = self.deps.runtime_config.access.load_full();
+        let Err(denied) = access.check(access.role_for(message), AccessCapability::Commands) else {
+            return true;
+        };
+        tracing::info!(
+            channel_id = %self.id,
+            sender_id = %message.sender_id,
+            role = %denied.role,
+            "built-in command denied"
+        );
+        self.send_builtin_text(format!("{denied}."), "access-denied")
+            .await;
+        false
+    }
+
     async fn try_handle_builtin_ops_commands(
         &mut self,
         raw_text: &str,
@@ -941,12 +1203,19 @@ impl Channel {
                 self.send_builtin_text(body, "status").await;
                 return Ok(true);
             }
+            "/quiet" | "/observe" | "/active" | "/mention-only"
+                if !self.builtin_command_allowed(message).await =>
+            {
+                return Ok(true);
+            }
             "/quiet" | "/observe" => {
                 self.set_response_mode(ResponseMode::Observe).await;
                 self.send_builtin_text(
-                    "observe mode enabled. i'll learn from this conversation but won't respond.".to_string(),
+                    "observe mode enabled. i'll learn from this conversation but won't respond."
+                        .to_string(),
                     "observe",
-                ).await;
+                )
+                .await;
                 return Ok(true);
             }
             "/active" => {
@@ -969,18 +1238,8 @@ impl Channel {
                 return Ok(true);
             }
             "/help" => {
-                let lines = [
-                    "commands:".to_string(),
-                    "- /status: current mode, models, binding snapshot".to_string(),
-                    "- /today: in-progress + ready task snapshot".to_string(),
-                    "- /tasks: ready task list".to_string(),
-                    "- /digest: one-shot day digest (00:00 -> now)".to_string(),
-                    "- /observe: learn from conversation, never respond".to_string(),
-                    "- /mention-only: only respond when @mentioned, replied to, or given a command".to_string(),
-                    "- /active: normal reply mode".to_string(),
-                    "- /agent-id: runtime agent id".to_string(),
-                ];
-                let body = lines.join("\n");
+                let role = self.deps.runtime_config.access.load().role_for(message);
+                let body = CommandRegistry::from_runtime(&self.deps.runtime_config).help_text(role);
                 self.send_builtin_text(body, "help").await;
                 return Ok(true);
             }
@@ -990,6 +1249,148 @@ impl Channel {
         Ok(false)
     }
 
+    /// Run a user-defined command from `[[commands]]` config or a skill.
+    async fn try_handle_registry_command(
+        &mut self,
+        raw_text: &str,
+        message: &InboundMessage,
+    ) -> Result<RegistryCommandOutcome> {
+        if message.source == "system" {
+            return Ok(RegistryCommandOutcome::NotMatched);
+        }
+        let Some(invocation) = crate::commands::parse_invocation(raw_text) else {
+            return Ok(RegistryCommandOutcome::NotMatched);
+        };
+        let registry = CommandRegistry::from_runtime(&self.deps.runtime_config);
+        let Some(command) = registry.get(&invocation.name).cloned() else {
+            return Ok(RegistryCommandOutcome::NotMatched);
+        };
+        let log_label = format!("command:{}", command.name);
+
+        let policy = self.deps.runtime_config.access.load_full();
+        let role = policy.role_for(message);
+        if role < command.role {
+            tracing::info!(
+                channel_id = %self.id,
+                sender_id = %message.sender_id,
+                command = %command.name,
+                %role,
+                "command denied"
+            );
+            self.send_builtin_text(
+                format!(
+                    "/{} requires the {} role or higher; your role is {role}.",
+                    command.name, command.role
+                ),
+                &log_label,
+            )
+            .await;
+            return Ok(RegistryCommandOutcome::Handled);
+        }
+
+        let bound = match crate::commands::bind_args(&command, invocation.args) {
+            Ok(bound) => bound,
+            Err(error) => {
+                self.send_builtin_text(error.to_string(), &log_label).await;
+                return Ok(RegistryCommandOutcome::Handled);
+            }
+        };
+
+        tracing::info!(channel_id = %self.id, command = %command.name, "running chat command");
+        // The sender's grant goes with this invocation only, so it never
+        // leaks into the channel's shared turn state.
+        let access = AccessGrant::new(policy, role);
+        let reply = match &command.action {
+            CommandAction::Prompt(template) => {
+                return Ok(RegistryCommandOutcome::Prompt(
+                    crate::commands::render_template(template, &bound),
+                ));
+            }
+            CommandAction::Skill { skill, task } => {
+                let task = task
+                    .as_deref()
+                    .map(|template| crate::commands::render_template(template, &bound))
+                    .unwrap_or_else(|| bound["args"].clone());
+                let task = if task.trim().is_empty() {
+                    format!("run the {skill} skill")
+                } else {
+                    task
+                };
+                if let Err(denied) = access.check(AccessCapability::SpawnWorker) {
+                    format!("{denied}.")
+                } else {
+                    let worker_context = self.state.worker_context_settings.read().await.clone();
+                    match crate::agent::channel_dispatch::spawn_worker_from_state(
+                        &self.state,
+                        task,
+                        false,
+                        &[skill.as_str()],
+                        &worker_context,
+                        Some(access),
+                    )
+                    .await
+                    {
+                        Ok(worker_id) => {
+                            format!("started a worker with the {skill} skill ({worker_id}).")
+                        }
+                        Err(error) => format!("couldn't start the {skill} skill: {error}"),
+                    }
+                }
+            }
+            CommandAction::Cron(cron_id) => {
+                if let Err(denied) = access.check(AccessCapability::Cron) {
+                    format!("{denied}.")
+                } else {
+                    match &self.deps.cron_tool {
+                        Some(cron_tool) if cron_tool.scheduler().is_registered(cron_id).await => {
+                            cron_tool
+                                .scheduler()
+                                .trigger_in_background(cron_id.as_str());
+                            format!("triggered cron job {cron_id}.")
+                        }
+                        _ => format!("cron job {cron_id} isn't scheduled."),
+                    }
+                }
+            }
+            CommandAction::Task {
+                title,
+                description,
+                priority,
+            } => {
+                let title = crate::commands::render_template(title, &bound);
+                let description = description
+                    .as_deref()
+                    .map(|template| crate::commands::render_template(template, &bound))
+                    .filter(|description| !description.trim().is_empty());
+                match self
+                    .deps
+                    .task_store
+                    .create(crate::tasks::CreateTaskInput {
+                        owner_agent_id: self.deps.agent_id.to_string(),
+                        assigned_agent_id: self.deps.agent_id.to_string(),
+                        title,
+                        description,
+                        status: crate::tasks::TaskStatus::Backlog,
+                        priority: *priority,
+                        subtasks: Vec::new(),
+                        metadata: serde_json::json!({
+                            "command": command.name,
+                            "requested_by": message.sender_id,
+                        }),
+                        source_memory_id: None,
+                        created_by: "command".to_string(),
+                    })
+                    .await
+                {
+                    Ok(task) => format!("created task #{}: {}", task.task_number, task.title),
+                    Err(error) => format!("couldn't create the task: {error}"),
+                }
+            }
+        };
+        self.send_builtin_text(reply, &log_label).await;
+        Ok(RegistryCommandOutcome::Handled)
+    }
+
     /// Run the channel event loop.
     pub async fn run(mut self) -> Result<()> {
         tracing::info!(channel_id = %self.id, "channel started");
@@ -1323,6 +1724,9 @@ impl Channel {
         let mut conversation_id = String::new();
         let temporal_context = TemporalContext::from_runtime(self.deps.runtime_config.as_ref());
         let mut batch_has_invoke = false;
+        // Raw text of every user message in the batch, scored as one prompt
+        // by prompt routing.
+        let mut batch_routing_text = String::new();
 
         for message in &messages {
             if message.source != "system" {
@@ -1388,6 +1792,7 @@ impl Channel {
                 self.state
                     .channel_store
                     .upsert(&message.conversation_id, &metadata);
+                self.track_participant(message, sender_name);
 
                 conversation_id = message.conversation_id.clone();
 
@@ -1405,6 +1810,11 @@ impl Channel {
                 };
                 let absolute_timestamp = temporal_context.format_timestamp(message.timestamp);
 
+                if !batch_routing_text.is_empty() {
+                    batch_routing_text.push('\n');
+                }
+                batch_routing_text.push_str(&raw_text);
+
                 let display_name = message_display_name(message);
 
                 let formatted_text = format_batched_user_message(
@@ -1453,6 +1863,10 @@ impl Channel {
             return Ok(());
         }
 
+        if self.reply_if_over_budget().await {
+            return Ok(());
+        }
+
         let mut user_contents: Vec<UserContent> = Vec::new();
         for (formatted_text, attachments, saved_data) in pending_batch_entries {
             if !attachments.is_empty() {
@@ -1518,6 +1932,24 @@ impl Channel {
             let mut reply_target = self.state.reply_target_message_id.write().await;
             *reply_target = messages.iter().rev().find_map(extract_message_id);
         }
+        {
+            let mut current_user = self.state.current_user_id.write().await;
+            *current_user = messages
+                .iter()
+                .rev()
+                .find_map(|m| user_scope_id(&m.source, &m.sender_id));
+        }
+        // A batch acts with the least trusted sender's role so nobody gains
+        // permissions by arriving alongside an admin.
+        let access = self.deps.runtime_config.access.load();
+        if let Some(role) = messages
+            .iter()
+            .filter(|m| m.source != "system")
+            .map(|m| access.role_for(m))
+            .min()
+        {
+            *self.state.sender_role.write().await = role;
+        }
 
         // Pin the inbound routing target from the last non-system message in the
         // batch so the RoutedSender (and send_routed) carry the correct platform
@@ -1530,6 +1962,7 @@ impl Channel {
         let (result, skip_flag, replied_flag, _) = self
             .run_agent_turn(
                 &combined_text,
+                Some(batch_routing_text.as_str()),
                 &system_prompt,
                 &conversation_id,
                 attachment_parts,
@@ -1653,6 +2086,8 @@ impl Channel {
             }
         };
 
+        let participant_info = self.build_participant_info().await;
+
         let routing = rc.routing.load();
         let model_name = routing.resolve(ProcessType::Channel, None).to_string();
         let tool_use_enforcement = rc.tool_use_enforcement.load();
@@ -1673,6 +2108,7 @@ impl Channel {
             self.backfill_transcript.clone(),
             empty_to_none(working_memory),
             empty_to_none(channel_activity_map),
+            participant_info,
         )?;
 
         prompt_engine.maybe_append_tool_use_enforcement(
@@ -1741,6 +2177,12 @@ impl Channel {
                 .or_else(|| Some(message.source.clone()));
         }
 
+        // Approve/deny buttons resolve a paused worker tool call directly and
+        // never reach the model.
+        if self.try_resolve_tool_approval(&message).await {
+            return Ok(());
+        }
+
         let (raw_text, attachments) = match &message.content {
             crate::MessageContent::Text(text) => (text.clone(), Vec::new()),
             crate::MessageContent::Media { text, attachments } => {
@@ -1834,10 +2276,20 @@ impl Channel {
             return Ok(());
         }
 
+        let command_prompt = match self
+            .try_handle_registry_command(&raw_text, &message)
+            .await?
+        {
+            RegistryCommandOutcome::Handled => return Ok(()),
+            RegistryCommandOutcome::Prompt(prompt) => Some(prompt),
+            RegistryCommandOutcome::NotMatched => None,
+        };
+
         let rewritten_text = if message.source == "system" {
             raw_text.clone()
         } else {
-            self.rewrite_tool_routed_command_prompt(&raw_text)
+            command_prompt
+                .or_else(|| self.rewrite_tool_routed_command_prompt(&raw_text))
                 .unwrap_or_else(|| raw_text.clone())
         };
 
@@ -1891,12 +2343,23 @@ impl Channel {
             }
         }
 
+        if message.source != "system" && self.reply_if_over_budget().await {
+            return Ok(());
+        }
+
         let system_prompt = self.build_system_prompt().await?;
 
         {
             let mut reply_target = self.state.reply_target_message_id.write().await;
             *reply_target = extract_message_id(&message);
         }
+        // Retriggers keep the scope of the turn that spawned the work.
+        if message.source != "system" {
+            let mut current_user = self.state.current_user_id.write().await;
+            *current_user = user_scope_id(&message.source, &message.sender_id);
+            let role = self.deps.runtime_config.access.load().role_for(&message);
+            *self.state.sender_role.write().await = role;
+        }
 
         let is_retrigger = message.source == "system";
         let attachment_content = if !attachments.is_empty() {
@@ -1940,6 +2403,7 @@ impl Channel {
         let (result, skip_flag, replied_flag, retrigger_reply_preserved) = self
             .run_agent_turn(
                 &user_text,
+                (!is_retrigger).then_some(rewritten_text.as_str()),
                 &system_prompt,
                 &message.conversation_id,
                 attachment_content,
@@ -2391,6 +2855,8 @@ impl Channel {
         };
 
         let empty_to_none = |s: String| if s.is_empty() { None } else { Some(s) };
+        let participant_info = self.build_participant_info().await;
+
         let routing = rc.routing.load();
         let model_name = routing.resolve(ProcessType::Channel, None).to_string();
         let tool_use_enforcement = rc.tool_use_enforcement.load();
@@ -2411,6 +2877,7 @@ impl Channel {
             self.backfill_transcript.clone(),
             empty_to_none(working_memory),
             empty_to_none(channel_activity_map),
+            participant_info,
         )?;
 
         prompt_engine.maybe_append_tool_use_enforcement(
@@ -2423,11 +2890,14 @@ impl Channel {
     /// Register per-turn tools, run the LLM agentic loop, and clean up.
     ///
     /// Returns the prompt result and per-turn flags for the caller to dispatch.
-    #[allow(clippy::type_complexity)]
-    #[tracing::instrument(skip(self, user_text, system_prompt, attachment_content), fields(channel_id = %self.id, agent_id = %self.deps.agent_id))]
+    /// `routing_text` is the raw user text scored by prompt routing; `None`
+    /// keeps the process-type default model.
+    #[allow(clippy::type_complexity, clippy::too_many_arguments)]
+    #[tracing::instrument(skip(self, user_text, routing_text, system_prompt, attachment_content), fields(channel_id = %self.id, agent_id = %self.deps.agent_id))]
     async fn run_agent_turn(
         &self,
         user_text: &str,
+        routing_text: Option<&str>,
         system_prompt: &str,
         conversation_id: &str,
         attachment_content: Vec<UserContent>,
@@ -2455,6 +2925,8 @@ impl Channel {
             .clone()
             .unwrap_or_else(InboundMessage::empty);
         let routed_sender = RoutedSender::new(self.response_tx.clone(), current_inbound.clone());
+        let reply_stream = (self.resolved_settings.streaming && allow_direct_reply)
+            .then(|| ReplyStream::new(routed_sender.clone()));
 
         // Extract Slack thread_ts from the current inbound message so cron
         // delivery targets include the originating thread.
@@ -2480,6 +2952,7 @@ impl Channel {
                     allow_direct_reply,
                     adapter.map(|s| s.to_string()),
                     slack_thread_ts.as_deref(),
+                    reply_stream.clone(),
                 )
                 .await
                 {
@@ -2501,6 +2974,7 @@ impl Channel {
                     allow_direct_reply,
                     adapter.map(|s| s.to_string()),
                     slack_thread_ts.as_deref(),
+                    reply_stream.clone(),
                 )
                 .await
                 {
@@ -2519,17 +2993,30 @@ impl Channel {
         };
 
         // Check for model override from conversation settings.
-        // Priority: per-process override > blanket override > routing config.
-        let model_name =
+        // Priority: per-process override > blanket override > prompt tier >
+        // routing config.
+        let (model_name, prompt_tier) =
             if let Some(override_model) = self.resolved_settings.resolve_model("channel") {
-                override_model
+                (override_model, None)
             } else {
-                routing.resolve(ProcessType::Channel, None)
+                routing.resolve_for_message(ProcessType::Channel, None, routing_text)
             };
 
+        if let Some(tier) = prompt_tier {
+            tracing::debug!(
+                channel_id = %self.id,
+                prompt_tier = %tier,
+                model = %model_name,
+                default_model = %routing.resolve(ProcessType::Channel, None),
+                "prompt routing selected model"
+            );
+        }
+
         let model = SpacebotModel::make(&self.deps.llm_manager, model_name)
             .with_context(&*self.deps.agent_id, "channel")
-            .with_routing((**routing).clone());
+            .with_channel(&*self.id)
+            .with_routing((**routing).clone())
+            .with_prompt_tier(prompt_tier);
 
         let agent = AgentBuilder::new(model)
             .preamble(system_prompt)
@@ -2586,6 +3073,7 @@ impl Channel {
         // ── Prompt snapshot capture (fire-and-forget) ──
         self.maybe_capture_snapshot(system_prompt, user_text, &history);
 
+        self.hook.set_reply_stream(reply_stream.clone());
         let mut result = self
             .hook
             .prompt_once_streaming(&agent, &mut history, user_text, max_turns)
@@ -2618,6 +3106,14 @@ impl Channel {
                 .await;
         }
 
+        // Withdraw a preview the reply tool never finalized, e.g. when the
+        // tool call was rejected or the turn errored mid-reply, so the partial
+        // text doesn't stay in the chat.
+        self.hook.set_reply_stream(None);
+        if let Some(reply_stream) = &reply_stream {
+            reply_stream.withdraw().await;
+        }
+
         let retrigger_reply_preserved = {
             let mut guard = self.state.history.write().await;
             apply_history_after_turn(
@@ -3143,6 +3639,39 @@ impl Channel {
             ProcessEvent::SettingsUpdated { channel_id, .. } if *channel_id == self.id => {
                 self.reload_settings().await;
             }
+            ProcessEvent::ToolApprovalRequested {
+                worker_id,
+                approval_id,
+                tool_name,
+                args,
+                timeout_secs,
+                ..
+            } => {
+                self.send_tool_approval_prompt(
+                    *worker_id,
+                    approval_id,
+                    tool_name,
+                    args,
+                    *timeout_secs,
+                )
+                .await;
+            }
+            ProcessEvent::ToolApprovalResolved {
+                tool_name,
+                decision,
+                resolved_by,
+                ..
+            } => {
+                let by = resolved_by
+                    .as_deref()
+                    .map(|name| format!(" by {name}"))
+                    .unwrap_or_default();
+                self.send_builtin_text(
+                    format!("`{tool_name}` call {decision}{by}."),
+                    "tool-approval-resolved",
+                )
+                .await;
+            }
             _ => {}
         }
 
@@ -3563,6 +4092,16 @@ fn compute_listen_mode_invocation(message: &InboundMessage, raw_text: &str) -> (
             .get("twitch_mentions_or_replies_to_bot")
             .and_then(|v| v.as_bool())
             .unwrap_or(false),
+        "irc" => message
+            .metadata
+            .get("irc_mentions_or_replies_to_bot")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+        "teams" => message
+            .metadata
+            .get("teams_mentions_or_replies_to_bot")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
         _ => false,
     };
     let invoked_by_reply = match message.source.as_str() {
@@ -3668,11 +4207,34 @@ fn is_dm_conversation_id(conv_id: &str) -> bool {
                 .is_some_and(|last| last.starts_with('D'))
 }
 
+/// Resolve an approve/deny button press on behalf of its sender. A sender
+/// whose role may not approve tool calls is refused and the call stays
+/// pending.
+fn resolve_tool_approval_interaction(
+    access: Arc<AccessConfig>,
+    registry: &ToolApprovalRegistry,
+    message: &InboundMessage,
+    decision: ToolApprovalDecision,
+    approval_id: &str,
+) -> std::result::Result<std::result::Result<PendingToolApproval, ToolApprovalError>, AccessDenied>
+{
+    let role = access.role_for(message);
+    AccessGrant::new(access, role).check(AccessCapability::ToolApproval)?;
+    let resolved_by = message
+        .metadata
+        .get("sender_display_name")
+        .and_then(|v| v.as_str())
+        .unwrap_or(&message.sender_id)
+        .to_string();
+    Ok(registry.resolve(approval_id, decision, Some(resolved_by)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
-        ObserveModeFallbackState, compute_listen_mode_invocation, is_dm_conversation_id,
-        recv_channel_event, should_process_event_for_channel,
+        MAX_TRACKED_PARTICIPANTS, ObserveModeFallbackState, compute_listen_mode_invocation,
+        is_dm_conversation_id, recv_channel_event, remember_participant,
+        resolve_tool_approval_interaction, should_process_event_for_channel,
         should_send_discord_quiet_mode_ping_ack, should_send_quiet_mode_fallback,
     };
     use crate::memory::MemoryType;
@@ -3704,6 +4266,82 @@ mod tests {
         }
     }
 
+    #[test]
+    fn guest_tool_approval_is_ignored() {
+        use crate::agent::tool_approval::{ToolApprovalDecision, ToolApprovalRegistry};
+        use crate::config::{AccessConfig, AccessRole};
+
+        let access = Arc::new(AccessConfig {
+            enabled: true,
+            ..AccessConfig::default()
+        });
+        let registry = ToolApprovalRegistry::new();
+        let (request, _decision_rx) = registry.request(uuid::Uuid::new_v4(), None, "shell", "{}");
+
+        let guest = inbound_message(
+            "discord",
+            &[(
+                crate::metadata_keys::SENDER_ROLE,
+                serde_json::to_value(AccessRole::Guest).unwrap(),
+            )],
+            "",
+        );
+        let denied = resolve_tool_approval_interaction(
+            access.clone(),
+            &registry,
+            &guest,
+            ToolApprovalDecision::Approved,
+            &request.approval_id,
+        )
+        .unwrap_err();
+        assert_eq!(denied.role, AccessRole::Guest);
+        assert_eq!(registry.pending().len(), 1);
+
+        let admin = inbound_message(
+            "discord",
+            &[(
+                crate::metadata_keys::SENDER_ROLE,
+                serde_json::to_value(AccessRole::Admin).unwrap(),
+            )],
+            "",
+        );
+        let resolved = resolve_tool_approval_interaction(
+            access,
+            &registry,
+            &admin,
+            ToolApprovalDecision::Approved,
+            &request.approval_id,
+        )
+        .unwrap();
+        assert!(resolved.is_ok());
+        assert!(registry.pending().is_empty());
+    }
+
+    #[test]
+    fn participants_evict_least_recently_seen() {
+        let start = std::time::Instant::now();
+        let key = |index: usize| ("discord".to_string(), format!("user-{index}"));
+        let mut participants = HashMap::new();
+        for index in 0..MAX_TRACKED_PARTICIPANTS {
+            remember_participant(
+                &mut participants,
+                key(index),
+                "name",
+                start + std::time::Duration::from_secs(index as u64),
+            );
+        }
+        // user-0 speaks again, so user-1 is now the least recently seen.
+        let later = start + std::time::Duration::from_secs(1_000);
+        remember_participant(&mut participants, key(0), "renamed", later);
+        assert_eq!(participants.len(), MAX_TRACKED_PARTICIPANTS);
+
+        remember_participant(&mut participants, key(999), "newcomer", later);
+        assert_eq!(participants.len(), MAX_TRACKED_PARTICIPANTS);
+        assert!(!participants.contains_key(&key(1)));
+        assert_eq!(participants[&key(0)].display_name, "renamed");
+        assert!(participants.contains_key(&key(999)));
+    }
+
     #[tokio::test]
     async fn channel_event_loop_continues_after_lagged_broadcast() {
         let (event_tx, mut event_rx) = tokio::sync::broadcast::channel::<ProcessEvent>(2);
@@ -3863,6 +4501,40 @@ mod tests {
         assert!(!invoked_by_reply);
     }
 
+    #[test]
+    fn quiet_mode_invocation_uses_irc_mention_metadata() {
+        let mentioned = inbound_message(
+            "irc",
+            &[("irc_mentions_or_replies_to_bot", true.into())],
+            "spacebot: status?",
+        );
+        let ambient = inbound_message(
+            "irc",
+            &[("irc_mentions_or_replies_to_bot", false.into())],
+            "status?",
+        );
+
+        assert!(compute_listen_mode_invocation(&mentioned, "spacebot: status?").1);
+        assert!(!compute_listen_mode_invocation(&ambient, "status?").1);
+    }
+
+    #[test]
+    fn quiet_mode_invocation_uses_teams_mention_metadata() {
+        let mentioned = inbound_message(
+            "teams",
+            &[("teams_mentions_or_replies_to_bot", true.into())],
+            "status?",
+        );
+        let ambient = inbound_message(
+            "teams",
+            &[("teams_mentions_or_replies_to_bot", false.into())],
+            "status?",
+        );
+
+        assert!(compute_listen_mode_invocation(&mentioned, "status?").1);
+        assert!(!compute_listen_mode_invocation(&ambient, "status?").1);
+    }
+
     #[test]
     fn discord_quiet_mode_ping_ack_requires_directed_ping() {
         let directed_message = inbound_message(
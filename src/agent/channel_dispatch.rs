@@ -8,6 +8,7 @@ use crate::agent::branch::{Branch, BranchExecutionConfig};
 use crate::agent::channel::ChannelState;
 use crate::agent::channel_prompt::TemporalContext;
 use crate::agent::worker::Worker;
+use crate::config::AccessGrant;
 use crate::conversation::settings::{WorkerContextMode, WorkerHistoryMode};
 use crate::error::{AgentError, Error as SpacebotError};
 use crate::tools::{BranchToolProfile, MemoryPersistenceContractState};
@@ -290,6 +291,8 @@ async fn spawn_branch(
         state.channel_store.clone(),
         crate::conversation::ProcessRunLogger::new(state.deps.sqlite_pool.clone()),
         profile,
+        state.current_user_id.read().await.clone(),
+        Some(state.access_grant().await),
     );
     let branch_max_turns = **state.deps.runtime_config.branch_max_turns.load();
 
@@ -459,14 +462,22 @@ pub async fn spawn_worker_from_state(
     interactive: bool,
     suggested_skills: &[&str],
     worker_context: &WorkerContextMode,
+    access: Option<AccessGrant>,
 ) -> std::result::Result<WorkerId, AgentError> {
     check_worker_limit(state).await?;
     let task = task.into();
     reserve_task_if_unique(state, &task).await?;
     ensure_dispatch_readiness(state, "worker");
 
-    let result =
-        spawn_worker_inner(state, &task, interactive, suggested_skills, worker_context).await;
+    let result = spawn_worker_inner(
+        state,
+        &task,
+        interactive,
+        suggested_skills,
+        worker_context,
+        access,
+    )
+    .await;
 
     // Release the reservation regardless of success or failure.
     // On success the task is now in the status block; on failure it needs cleanup.
@@ -483,6 +494,7 @@ async fn spawn_worker_inner(
     interactive: bool,
     suggested_skills: &[&str],
     worker_context: &WorkerContextMode,
+    access: Option<AccessGrant>,
 ) -> std::result::Result<WorkerId, AgentError> {
     let rc = &state.deps.runtime_config;
     let prompt_engine = rc.prompts.load();
@@ -646,6 +658,10 @@ async fn spawn_worker_inner(
             .insert(worker.id, inject_tx);
         worker
     };
+    let worker = match access {
+        Some(access) => worker.with_access(access),
+        None => worker,
+    };
 
     let worker_id = worker.id;
 
@@ -390,6 +390,10 @@ pub(crate) fn event_is_for_channel(event: &ProcessEvent, channel_id: &ChannelId)
             channel_id: event_channel,
             ..
         }
+        | ProcessEvent::ToolOutput {
+            channel_id: event_channel,
+            ..
+        }
         | ProcessEvent::MemorySaved {
             channel_id: event_channel,
             ..
@@ -401,6 +405,14 @@ pub(crate) fn event_is_for_channel(event: &ProcessEvent, channel_id: &ChannelId)
         | ProcessEvent::WorkerQuestion {
             channel_id: event_channel,
             ..
+        }
+        | ProcessEvent::ToolApprovalRequested {
+            channel_id: event_channel,
+            ..
+        }
+        | ProcessEvent::ToolApprovalResolved {
+            channel_id: event_channel,
+            ..
         } => event_channel.as_ref() == Some(channel_id),
         ProcessEvent::CompactionTriggered {
             channel_id: event_channel,
@@ -4,7 +4,7 @@
 //! all the prompt-building methods that assemble the channel's
 //! system prompt from identity, memory bulletin, skills, status, etc.
 
-use chrono::{DateTime, Local, Utc};
+use chrono::{DateTime, Local, Timelike as _, Utc};
 use chrono_tz::Tz;
 
 /// Debounce window for retriggers: coalesce rapid branch/worker completions
@@ -114,6 +114,16 @@ impl TemporalContext {
         }
     }
 
+    /// Hour of day (0-23) in the resolved timezone.
+    pub(crate) fn local_hour(&self) -> u8 {
+        match &self.timezone {
+            TemporalTimezone::Named { timezone, .. } => {
+                self.now_utc.with_timezone(timezone).hour() as u8
+            }
+            TemporalTimezone::SystemLocal => self.now_utc.with_timezone(&Local).hour() as u8,
+        }
+    }
+
     pub(crate) fn current_time_line(&self) -> String {
         format!(
             "{}; UTC {}",
@@ -122,3 +132,52 @@ impl TemporalContext {
         )
     }
 }
+
+/// Render `## Participant Info` lines for the active senders of a channel.
+///
+/// `participants` maps `(platform, sender_id)` to the sender's current display
+/// name, which wins over the stored one. Linked `[[humans]]` entries contribute
+/// their role. Senders with neither a summary nor a role are skipped.
+pub(crate) fn build_participant_info(
+    humans: &[crate::conversation::Human],
+    human_defs: &[crate::config::HumanDef],
+    participants: &std::collections::HashMap<(String, String), String>,
+) -> Option<String> {
+    let mut lines: Vec<(String, String)> = humans
+        .iter()
+        .filter_map(|human| {
+            let display_name = participants
+                .get(&(human.platform.clone(), human.platform_user_id.clone()))
+                .unwrap_or(&human.display_name);
+            let role = human.human_def_id.as_deref().and_then(|id| {
+                human_defs
+                    .iter()
+                    .find(|human_def| human_def.id == id)
+                    .and_then(|human_def| human_def.role.as_deref())
+            });
+
+            let line = match (role, human.summary.as_deref()) {
+                (Some(role), Some(summary)) => {
+                    format!("**{display_name}** ({role}) — {summary}")
+                }
+                (None, Some(summary)) => format!("**{display_name}** — {summary}"),
+                (Some(role), None) => format!("**{display_name}** ({role})"),
+                (None, None) => return None,
+            };
+            Some((display_name.to_lowercase(), line))
+        })
+        .collect();
+
+    if lines.is_empty() {
+        return None;
+    }
+
+    lines.sort();
+    Some(
+        lines
+            .into_iter()
+            .map(|(_, line)| line)
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+    )
+}
@@ -246,6 +246,7 @@ async fn run_compaction(
     };
     let model = SpacebotModel::make(&deps.llm_manager, &model_name)
         .with_context(&*deps.agent_id, "compactor")
+        .with_channel(&**channel_id)
         .with_routing((**routing).clone());
 
     // Give the compaction worker memory_save so it can directly persist memories
@@ -939,6 +939,10 @@ impl Cortex {
             ProcessEvent::ToolStarted {
                 process_id: ProcessId::Worker(worker_id),
                 ..
+            }
+            | ProcessEvent::ToolOutput {
+                process_id: ProcessId::Worker(worker_id),
+                ..
             } => {
                 state.track_worker_activity(*worker_id);
             }
@@ -1283,6 +1287,29 @@ fn signal_from_event(event: ProcessEvent) -> Option<Signal> {
             permission_id,
             description: summarize_signal_text(&description),
         },
+        ProcessEvent::ToolApprovalRequested {
+            worker_id,
+            channel_id,
+            approval_id,
+            tool_name,
+            ..
+        } => Signal::WorkerPermission {
+            worker_id,
+            channel_id,
+            permission_id: approval_id,
+            description: format!("approve {tool_name} call"),
+        },
+        ProcessEvent::ToolApprovalResolved {
+            worker_id,
+            channel_id,
+            tool_name,
+            decision,
+            ..
+        } => Signal::WorkerStatus {
+            worker_id,
+            channel_id,
+            status: format!("{tool_name} call {decision}"),
+        },
         ProcessEvent::WorkerQuestion {
             worker_id,
             channel_id,
@@ -1345,7 +1372,8 @@ fn signal_from_event(event: ProcessEvent) -> Option<Signal> {
             status: "idle".to_string(),
         },
         // UI-only events — no cortex signal needed.
-        ProcessEvent::OpenCodeSessionCreated { .. }
+        ProcessEvent::ToolOutput { .. }
+        | ProcessEvent::OpenCodeSessionCreated { .. }
         | ProcessEvent::OpenCodePartUpdated { .. }
         | ProcessEvent::WorkerInitialResult { .. }
         | ProcessEvent::WorkerText { .. }
@@ -1973,6 +2001,7 @@ async fn run_cortex_loop(
                                 "maintenance_completed",
                                 "Memory maintenance completed",
                                 Some(serde_json::json!({
+                                    "demoted": report.demoted,
                                     "decayed": report.decayed,
                                     "pruned": report.pruned,
                                     "merged": report.merged,
@@ -2130,6 +2159,8 @@ async fn run_cortex_loop(
                             min_age_days: cortex_config.maintenance_min_age_days,
                             merge_similarity_threshold: cortex_config
                                 .maintenance_merge_similarity_threshold,
+                            recent_tier_ttl_days: cortex_config.recent_tier_ttl_days,
+                            recent_tier_max: cortex_config.recent_tier_max,
                         };
                         let memory_search = cortex.deps.memory_search.clone();
                         logger.log(
@@ -3209,6 +3240,530 @@ async fn generate_profile(deps: &AgentDeps, logger: &CortexLogger) {
     }
 }
 
+// -- Participant loop --
+
+/// Spawn the participant summary loop for an agent.
+///
+/// Periodically regenerates short summaries for humans who have been active
+/// since their last summary. Channels inject these as `## Participant Info`.
+pub fn spawn_participant_loop(
+    deps: AgentDeps,
+    logger: CortexLogger,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        if let Err(error) = run_participant_loop(&deps, &logger).await {
+            tracing::error!(%error, "cortex participant loop exited with error");
+        }
+    })
+}
+
+async fn run_participant_loop(deps: &AgentDeps, logger: &CortexLogger) -> anyhow::Result<()> {
+    tracing::info!("cortex participant loop started");
+
+    let human_store = crate::conversation::HumanStore::new(deps.sqlite_pool.clone());
+
+    loop {
+        let config = **deps.runtime_config.participants.load();
+        tokio::time::sleep(Duration::from_secs(config.summary_interval_secs.max(1))).await;
+
+        if !config.enabled {
+            continue;
+        }
+
+        run_participant_pass(deps, logger, &human_store, config).await;
+    }
+}
+
+/// Regenerate stale participant summaries. Returns the number updated.
+async fn run_participant_pass(
+    deps: &AgentDeps,
+    logger: &CortexLogger,
+    human_store: &crate::conversation::HumanStore,
+    config: crate::config::ParticipantConfig,
+) -> usize {
+    let stale = match human_store
+        .get_stale_summaries(
+            config.summary_stale_after_secs,
+            config.max_summaries_per_pass,
+        )
+        .await
+    {
+        Ok(stale) => stale,
+        Err(error) => {
+            tracing::warn!(%error, "failed to load stale participant summaries");
+            return 0;
+        }
+    };
+    if stale.is_empty() {
+        return 0;
+    }
+
+    let started = Instant::now();
+    let mut updated = 0;
+    for human in &stale {
+        match generate_participant_summary(deps, human_store, human, config.summary_max_words).await
+        {
+            Ok(()) => updated += 1,
+            Err(error) => {
+                tracing::warn!(
+                    %error,
+                    human_id = %human.id,
+                    platform = %human.platform,
+                    "participant summary generation failed"
+                );
+            }
+        }
+    }
+
+    let duration_ms = started.elapsed().as_millis() as u64;
+    tracing::info!(
+        updated,
+        stale = stale.len(),
+        duration_ms,
+        "participant summary pass complete"
+    );
+    logger.log(
+        "participant_summaries",
+        &format!(
+            "Updated {updated} of {} participant summaries ({duration_ms}ms)",
+            stale.len()
+        ),
+        Some(serde_json::json!({
+            "updated": updated,
+            "stale": stale.len(),
+            "duration_ms": duration_ms,
+        })),
+    );
+
+    updated
+}
+
+/// Recall what the agent knows about one human and store a fresh summary.
+async fn generate_participant_summary(
+    deps: &AgentDeps,
+    human_store: &crate::conversation::HumanStore,
+    human: &crate::conversation::Human,
+    max_words: usize,
+) -> anyhow::Result<()> {
+    let human_def = human.human_def_id.as_deref().and_then(|id| {
+        deps.humans
+            .load()
+            .iter()
+            .find(|human_def| human_def.id == id)
+            .cloned()
+    });
+    let known_context = human_def.as_ref().map(|human_def| {
+        [
+            human_def
+                .role
+                .as_deref()
+                .map(|role| format!("Role: {role}")),
+            human_def.bio.as_deref().map(|bio| format!("Bio: {bio}")),
+            human_def.description.clone(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join("\n")
+    });
+
+    let search_config = SearchConfig {
+        mode: SearchMode::Hybrid,
+        max_results: 15,
+        user_id: human.user_scope_id(),
+        ..Default::default()
+    };
+    let memories = deps
+        .memory_search
+        .search(&human.display_name, &search_config)
+        .await?;
+    let memory_results = memories
+        .iter()
+        .map(|result| {
+            format!(
+                "- [{}] {}",
+                result.memory.memory_type, result.memory.content
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let recent_messages = human_store
+        .recent_messages(human, 20)
+        .await?
+        .into_iter()
+        .rev()
+        .map(|(content, created_at)| {
+            format!("- [{}] {}", created_at.format("%Y-%m-%d %H:%M"), content)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let prompt_engine = deps.runtime_config.prompts.load();
+    let system_prompt = prompt_engine.render_static("cortex_participant")?;
+    let synthesis_prompt = prompt_engine.render_system_participant_synthesis(
+        &human.display_name,
+        max_words,
+        known_context
+            .as_deref()
+            .filter(|context| !context.is_empty()),
+        &memory_results,
+        &recent_messages,
+    )?;
+
+    let routing = deps.runtime_config.routing.load();
+    let model_name = routing.resolve(ProcessType::Cortex, None).to_string();
+    let model = SpacebotModel::make(&deps.llm_manager, &model_name)
+        .with_context(&*deps.agent_id, "cortex")
+        .with_routing((**routing).clone());
+    let agent = AgentBuilder::new(model)
+        .preamble(&system_prompt)
+        .hook(CortexHook::new())
+        .build();
+
+    let summary = agent.prompt(&synthesis_prompt).await?;
+    let summary = summary.trim();
+    if summary.is_empty() {
+        anyhow::bail!("empty participant summary");
+    }
+
+    human_store.update_summary(&human.id, summary).await?;
+    Ok(())
+}
+
+// -- Autonomy loop --
+
+/// Source tag on every memory the autonomy loop saves.
+pub const AUTONOMY_MEMORY_SOURCE: &str = "cortex:autonomy";
+
+/// Synthetic channel ID the autonomy branch runs under. No channel listens on
+/// it, so branch events stay internal.
+const AUTONOMY_CHANNEL_ID: &str = "cortex:autonomy";
+
+/// How many previous autonomy memories to show each cycle.
+const AUTONOMY_PREVIOUS_FINDINGS: i64 = 10;
+
+/// How many goal and todo memories (each) to show each cycle.
+const AUTONOMY_GOALS_PER_TYPE: i64 = 10;
+
+/// Max characters of each worker result kept in the cycle summary memory.
+const AUTONOMY_WORKER_RESULT_CHARS: usize = 1000;
+
+/// Spawn the autonomy loop: a periodic, identity-driven pass where the cortex
+/// decides on self-directed work, spawns workers within budget, and records
+/// what it found as memories and tasks.
+pub fn spawn_autonomy_loop(deps: AgentDeps, logger: CortexLogger) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        if let Err(error) = run_autonomy_loop(&deps, &logger).await {
+            tracing::error!(%error, "cortex autonomy loop exited with error");
+        }
+    })
+}
+
+async fn run_autonomy_loop(deps: &AgentDeps, logger: &CortexLogger) -> anyhow::Result<()> {
+    tracing::info!("cortex autonomy loop started");
+
+    let mut consecutive_failures: u8 = 0;
+    loop {
+        let interval = deps.runtime_config.cortex.load().autonomy_interval_secs;
+        tokio::time::sleep(Duration::from_secs(interval.max(300))).await;
+
+        let cortex_config = **deps.runtime_config.cortex.load();
+        if !cortex_config.autonomy_enabled {
+            // Toggling the kill switch also re-arms a tripped circuit breaker.
+            consecutive_failures = 0;
+            continue;
+        }
+        if consecutive_failures >= cortex_config.circuit_breaker_threshold {
+            continue;
+        }
+        if let Some((start_hour, end_hour)) = cortex_config.autonomy_active_hours {
+            let current_hour =
+                crate::agent::channel_prompt::TemporalContext::from_runtime(&deps.runtime_config)
+                    .local_hour();
+            if !crate::cron::scheduler::hour_in_active_window(current_hour, start_hour, end_hour) {
+                tracing::debug!(
+                    current_hour,
+                    start_hour,
+                    end_hour,
+                    "outside autonomy active hours, skipping cycle"
+                );
+                continue;
+            }
+        }
+        if !deps.runtime_config.ready_for_work() {
+            tracing::debug!("agent not ready for work, skipping autonomy cycle");
+            continue;
+        }
+
+        match run_autonomy_cycle(deps, logger, cortex_config).await {
+            Ok(()) => consecutive_failures = 0,
+            Err(error) => {
+                consecutive_failures = consecutive_failures.saturating_add(1);
+                tracing::warn!(%error, consecutive_failures, "autonomy cycle failed");
+                if consecutive_failures >= cortex_config.circuit_breaker_threshold {
+                    logger.log(
+                        "autonomy_circuit_open",
+                        &format!(
+                            "Autonomy loop paused after {consecutive_failures} consecutive failures: {error}"
+                        ),
+                        Some(serde_json::json!({
+                            "consecutive_failures": consecutive_failures,
+                            "last_error": error.to_string(),
+                        })),
+                    );
+                } else {
+                    logger.log(
+                        "autonomy_failed",
+                        &format!("Autonomy cycle failed: {error}"),
+                        Some(serde_json::json!({
+                            "consecutive_failures": consecutive_failures,
+                            "error": error.to_string(),
+                        })),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Run one autonomy cycle: assemble context, let a branch decide and act,
+/// wait for its workers, and save a cycle summary memory.
+async fn run_autonomy_cycle(
+    deps: &AgentDeps,
+    logger: &CortexLogger,
+    cortex_config: crate::config::CortexConfig,
+) -> anyhow::Result<()> {
+    let started = Instant::now();
+    let store = deps.memory_search.store();
+
+    let identity_context =
+        Some(deps.runtime_config.identity.load().render()).filter(|rendered| !rendered.is_empty());
+    let memory_bulletin = Some(deps.runtime_config.memory_bulletin.load().as_ref().clone())
+        .filter(|bulletin| !bulletin.is_empty());
+
+    let mut goals = Vec::new();
+    for memory_type in [MemoryType::Goal, MemoryType::Todo] {
+        goals.extend(
+            store
+                .get_by_type(memory_type, AUTONOMY_GOALS_PER_TYPE)
+                .await?,
+        );
+    }
+    let goals = format_autonomy_memories(&goals);
+
+    let previous_findings = store
+        .get_by_source(AUTONOMY_MEMORY_SOURCE, AUTONOMY_PREVIOUS_FINDINGS)
+        .await?;
+    let previous_findings = format_autonomy_memories(&previous_findings);
+
+    let active_tasks = gather_active_tasks(deps).await?;
+    let active_tasks = Some(active_tasks).filter(|tasks| !tasks.is_empty());
+
+    let current_time =
+        crate::agent::channel_prompt::TemporalContext::from_runtime(&deps.runtime_config)
+            .current_time_line();
+
+    let prompt_engine = deps.runtime_config.prompts.load();
+    let system_prompt = prompt_engine.render_autonomy_prompt(
+        &current_time,
+        identity_context,
+        memory_bulletin,
+        goals,
+        previous_findings,
+        active_tasks,
+        cortex_config.autonomy_max_workers,
+        cortex_config.autonomy_tasks_require_approval,
+    )?;
+
+    let worker_budget = crate::tools::DetachedWorkerBudget::new(cortex_config.autonomy_max_workers);
+    let tool_server = crate::tools::create_autonomy_tool_server(
+        deps.clone(),
+        AUTONOMY_MEMORY_SOURCE,
+        worker_budget.clone(),
+        cortex_config.autonomy_tasks_require_approval,
+    );
+
+    // Subscribe before the branch runs so fast workers can't finish unseen.
+    let mut event_rx = deps.event_tx.subscribe();
+
+    let branch = crate::agent::branch::Branch::new(
+        Arc::from(AUTONOMY_CHANNEL_ID),
+        "autonomy cycle",
+        deps.clone(),
+        system_prompt,
+        Vec::new(),
+        tool_server,
+        crate::agent::branch::BranchExecutionConfig {
+            max_turns: cortex_config.autonomy_max_turns,
+            memory_persistence_contract: None,
+        },
+        None,
+    );
+    let conclusion = branch
+        .run("Begin this autonomy cycle.")
+        .await
+        .map_err(|error| anyhow::anyhow!(error))?;
+
+    let spawned = worker_budget.spawned();
+    let worker_results = collect_worker_results(
+        &mut event_rx,
+        &spawned,
+        Duration::from_secs(cortex_config.worker_timeout_secs),
+    )
+    .await;
+
+    let summary = render_autonomy_cycle_summary(&current_time, &conclusion, &worker_results);
+    save_autonomy_summary(deps, summary).await?;
+
+    let duration_ms = started.elapsed().as_millis() as u64;
+    tracing::info!(
+        workers = spawned.len(),
+        duration_ms,
+        "autonomy cycle complete"
+    );
+    logger.log(
+        "autonomy_cycle",
+        &format!(
+            "Autonomy cycle finished with {} worker(s) ({duration_ms}ms)",
+            spawned.len()
+        ),
+        Some(serde_json::json!({
+            "workers_spawned": spawned.len(),
+            "workers_finished": worker_results.len(),
+            "duration_ms": duration_ms,
+            "conclusion": conclusion,
+        })),
+    );
+
+    Ok(())
+}
+
+/// Format memories as `- [date] (type) content` lines, or `None` when empty.
+fn format_autonomy_memories(memories: &[crate::memory::Memory]) -> Option<String> {
+    if memories.is_empty() {
+        return None;
+    }
+    Some(
+        memories
+            .iter()
+            .map(|memory| {
+                format!(
+                    "- [{}] ({}) {}",
+                    memory.created_at.format("%Y-%m-%d %H:%M"),
+                    memory.memory_type,
+                    memory.content
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+    )
+}
+
+/// A finished autonomy worker and what it reported.
+struct AutonomyWorkerResult {
+    worker_id: WorkerId,
+    success: bool,
+    result: String,
+}
+
+/// Wait for the given workers to complete, up to `timeout`. Workers still
+/// running at the deadline are left alone and simply missing from the result.
+async fn collect_worker_results(
+    event_rx: &mut broadcast::Receiver<ProcessEvent>,
+    worker_ids: &[WorkerId],
+    timeout: Duration,
+) -> Vec<AutonomyWorkerResult> {
+    let mut results = Vec::new();
+    if worker_ids.is_empty() {
+        return results;
+    }
+
+    let deadline = tokio::time::Instant::now() + timeout;
+    while results.len() < worker_ids.len() {
+        match tokio::time::timeout_at(deadline, event_rx.recv()).await {
+            Ok(Ok(ProcessEvent::WorkerComplete {
+                worker_id,
+                result,
+                success,
+                ..
+            })) if worker_ids.contains(&worker_id) => {
+                results.push(AutonomyWorkerResult {
+                    worker_id,
+                    success,
+                    result,
+                });
+            }
+            Ok(Ok(_)) => {}
+            Ok(Err(broadcast::error::RecvError::Lagged(skipped))) => {
+                tracing::warn!(skipped, "autonomy loop lagged behind process events");
+            }
+            Ok(Err(broadcast::error::RecvError::Closed)) => break,
+            Err(_) => {
+                tracing::info!(
+                    finished = results.len(),
+                    spawned = worker_ids.len(),
+                    "autonomy workers still running at deadline"
+                );
+                break;
+            }
+        }
+    }
+    results
+}
+
+fn render_autonomy_cycle_summary(
+    current_time: &str,
+    conclusion: &str,
+    worker_results: &[AutonomyWorkerResult],
+) -> String {
+    let mut summary = format!("Autonomy cycle at {current_time}.\n\n{}", conclusion.trim());
+    if !worker_results.is_empty() {
+        summary.push_str("\n\nWorker results:");
+        for worker in worker_results {
+            let status = if worker.success { "done" } else { "failed" };
+            let mut result: String = worker
+                .result
+                .chars()
+                .take(AUTONOMY_WORKER_RESULT_CHARS)
+                .collect();
+            if result.len() < worker.result.len() {
+                result.push_str("...");
+            }
+            summary.push_str(&format!(
+                "\n- worker {} ({status}): {}",
+                worker.worker_id,
+                result.trim()
+            ));
+        }
+    }
+    summary
+}
+
+/// Save the cycle summary the same way the branch's `memory_save` would, so
+/// it is embedded and searchable.
+async fn save_autonomy_summary(deps: &AgentDeps, summary: String) -> anyhow::Result<()> {
+    use rig::tool::Tool as _;
+
+    let tool = crate::tools::MemorySaveTool::new(deps.memory_search.clone())
+        .with_event_bus(deps.agent_id.clone(), deps.event_tx.clone())
+        .with_source(AUTONOMY_MEMORY_SOURCE);
+    tool.call(crate::tools::MemorySaveArgs {
+        content: summary,
+        memory_type: MemoryType::Observation.to_string(),
+        importance: Some(0.6),
+        source: None,
+        channel_id: None,
+        user_id: None,
+        global: true,
+        tier: None,
+        associations: Vec::new(),
+    })
+    .await
+    .map_err(|error| anyhow::anyhow!(error))?;
+    Ok(())
+}
+
 // -- Association loop --
 
 /// Spawn the association loop for an agent.
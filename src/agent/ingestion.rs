@@ -506,6 +506,8 @@ async fn process_chunk(
             working_memory: Some(deps.working_memory.clone()),
             channel_id: None,
         },
+        None,
+        None,
     );
 
     let agent = AgentBuilder::new(model)
@@ -0,0 +1,287 @@
+//! Human-in-the-loop approval for built-in worker tool calls.
+//!
+//! `SpacebotHook` parks a call that matches the agent's `[tool_approval]`
+//! policy here and waits on the returned receiver. The channel resolves it
+//! from an approve/deny button interaction, and the API resolves it from the
+//! dashboard. Whoever is first wins; later decisions find nothing pending.
+
+use crate::{ChannelId, WorkerId};
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tokio::sync::oneshot;
+
+/// Prefix of the button `custom_id`s posted with an approval prompt.
+const ACTION_PREFIX: &str = "tool_approval";
+
+/// Outcome of a paused tool call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolApprovalDecision {
+    Approved,
+    Denied,
+    /// Nobody decided before `timeout_secs` elapsed. Treated as a denial.
+    TimedOut,
+}
+
+impl std::fmt::Display for ToolApprovalDecision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Approved => write!(f, "approved"),
+            Self::Denied => write!(f, "denied"),
+            Self::TimedOut => write!(f, "timed out"),
+        }
+    }
+}
+
+/// A decision plus who made it, as delivered to the waiting hook.
+#[derive(Debug, Clone)]
+pub struct ToolApprovalResolution {
+    pub decision: ToolApprovalDecision,
+    /// Display name or ID of the person who decided. `None` for timeouts.
+    pub resolved_by: Option<String>,
+}
+
+/// A tool call waiting for a decision.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct PendingToolApproval {
+    pub approval_id: String,
+    pub worker_id: String,
+    pub channel_id: Option<String>,
+    pub tool_name: String,
+    pub args: String,
+    pub requested_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// A resolved approval kept by the hook for the worker transcript.
+#[derive(Debug, Clone)]
+pub struct ToolApprovalRecord {
+    pub call_id: Option<String>,
+    pub tool_name: String,
+    pub args: String,
+    pub decision: ToolApprovalDecision,
+    pub resolved_by: Option<String>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ToolApprovalError {
+    #[error("no pending tool approval with id '{0}'")]
+    NotFound(String),
+}
+
+struct PendingEntry {
+    request: PendingToolApproval,
+    decision_tx: oneshot::Sender<ToolApprovalResolution>,
+}
+
+/// Per-agent table of tool calls waiting for a decision.
+pub struct ToolApprovalRegistry {
+    pending: Mutex<HashMap<String, PendingEntry>>,
+}
+
+impl Default for ToolApprovalRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ToolApprovalRegistry {
+    pub fn new() -> Self {
+        Self {
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Park a tool call. The receiver yields once someone resolves it.
+    pub fn request(
+        &self,
+        worker_id: WorkerId,
+        channel_id: Option<&ChannelId>,
+        tool_name: &str,
+        args: &str,
+    ) -> (
+        PendingToolApproval,
+        oneshot::Receiver<ToolApprovalResolution>,
+    ) {
+        let request = PendingToolApproval {
+            approval_id: uuid::Uuid::new_v4().to_string(),
+            worker_id: worker_id.to_string(),
+            channel_id: channel_id.map(|id| id.to_string()),
+            tool_name: tool_name.to_string(),
+            args: args.to_string(),
+            requested_at: chrono::Utc::now(),
+        };
+        let (decision_tx, decision_rx) = oneshot::channel();
+        self.lock().insert(
+            request.approval_id.clone(),
+            PendingEntry {
+                request: request.clone(),
+                decision_tx,
+            },
+        );
+        (request, decision_rx)
+    }
+
+    /// Deliver a decision to the waiting call and return what was decided on.
+    pub fn resolve(
+        &self,
+        approval_id: &str,
+        decision: ToolApprovalDecision,
+        resolved_by: Option<String>,
+    ) -> Result<PendingToolApproval, ToolApprovalError> {
+        let entry = self
+            .lock()
+            .remove(approval_id)
+            .ok_or_else(|| ToolApprovalError::NotFound(approval_id.to_string()))?;
+        // The hook may have timed out between the lookup and this send; the
+        // decision is then simply dropped.
+        entry
+            .decision_tx
+            .send(ToolApprovalResolution {
+                decision,
+                resolved_by,
+            })
+            .ok();
+        Ok(entry.request)
+    }
+
+    /// Drop a pending call without delivering a decision. Returns `false` if
+    /// it was already resolved.
+    pub fn expire(&self, approval_id: &str) -> bool {
+        self.lock().remove(approval_id).is_some()
+    }
+
+    /// Calls currently waiting, oldest first.
+    pub fn pending(&self) -> Vec<PendingToolApproval> {
+        let mut pending: Vec<_> = self
+            .lock()
+            .values()
+            .map(|entry| entry.request.clone())
+            .collect();
+        pending.sort_by_key(|request| request.requested_at);
+        pending
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, PendingEntry>> {
+        self.pending
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// Arguments as shown to approvers and kept in the transcript. The value
+/// passed to `secret_set` is masked, and known secret patterns are scrubbed
+/// from every other call.
+pub fn redact_args(tool_name: &str, args: &str) -> String {
+    let args = if tool_name == <crate::tools::SecretSetTool as rig::tool::Tool>::NAME {
+        match serde_json::from_str::<serde_json::Value>(args) {
+            Ok(mut parsed) => {
+                if let Some(value) = parsed.get_mut("value") {
+                    *value = serde_json::Value::String("[REDACTED]".into());
+                }
+                parsed.to_string()
+            }
+            Err(_) => "[REDACTED]".to_string(),
+        }
+    } else {
+        args.to_string()
+    };
+    crate::secrets::scrub::scrub_leaks(&args)
+}
+
+/// Button `custom_id` that resolves `approval_id` with `decision`.
+pub fn action_id(decision: ToolApprovalDecision, approval_id: &str) -> String {
+    let verb = match decision {
+        ToolApprovalDecision::Approved => "approve",
+        ToolApprovalDecision::Denied | ToolApprovalDecision::TimedOut => "deny",
+    };
+    format!("{ACTION_PREFIX}:{verb}:{approval_id}")
+}
+
+/// Parse a button `custom_id` produced by [`action_id`].
+pub fn parse_action_id(action_id: &str) -> Option<(ToolApprovalDecision, &str)> {
+    let rest = action_id.strip_prefix(ACTION_PREFIX)?.strip_prefix(':')?;
+    let (verb, approval_id) = rest.split_once(':')?;
+    let decision = match verb {
+        "approve" => ToolApprovalDecision::Approved,
+        "deny" => ToolApprovalDecision::Denied,
+        _ => return None,
+    };
+    (!approval_id.is_empty()).then_some((decision, approval_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn action_ids_round_trip() {
+        let approve = action_id(ToolApprovalDecision::Approved, "abc-123");
+        assert_eq!(approve, "tool_approval:approve:abc-123");
+        assert_eq!(
+            parse_action_id(&approve),
+            Some((ToolApprovalDecision::Approved, "abc-123"))
+        );
+        assert_eq!(
+            parse_action_id(&action_id(ToolApprovalDecision::Denied, "abc-123")),
+            Some((ToolApprovalDecision::Denied, "abc-123"))
+        );
+        assert_eq!(parse_action_id("tool_approval:maybe:abc"), None);
+        assert_eq!(parse_action_id("tool_approval:approve:"), None);
+        assert_eq!(parse_action_id("approve"), None);
+    }
+
+    #[tokio::test]
+    async fn resolve_delivers_decision_once() {
+        let registry = ToolApprovalRegistry::new();
+        let (request, decision_rx) =
+            registry.request(uuid::Uuid::new_v4(), None, "shell", r#"{"command":"ls"}"#);
+        assert_eq!(registry.pending().len(), 1);
+
+        let resolved = registry
+            .resolve(
+                &request.approval_id,
+                ToolApprovalDecision::Approved,
+                Some("alice".into()),
+            )
+            .expect("pending approval");
+        assert_eq!(resolved.tool_name, "shell");
+
+        let resolution = decision_rx.await.expect("decision delivered");
+        assert_eq!(resolution.decision, ToolApprovalDecision::Approved);
+        assert_eq!(resolution.resolved_by.as_deref(), Some("alice"));
+        assert!(registry.pending().is_empty());
+        assert!(matches!(
+            registry.resolve(&request.approval_id, ToolApprovalDecision::Denied, None),
+            Err(ToolApprovalError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn redact_args_masks_secret_values() {
+        let redacted = redact_args(
+            "secret_set",
+            r#"{"name":"GH_TOKEN","value":"hunter2-very-secret"}"#,
+        );
+        assert!(!redacted.contains("hunter2"));
+        assert!(redacted.contains("GH_TOKEN"));
+        assert_eq!(redact_args("secret_set", "not json hunter2"), "[REDACTED]");
+
+        let shell = redact_args(
+            "shell",
+            r#"{"command":"curl -H 'Authorization: Bearer sk-ant-REDACTED'"}"#,
+        );
+        assert!(!shell.contains("sk-ant-REDACTED"));
+        assert!(shell.contains("curl"));
+    }
+
+    #[test]
+    fn expire_removes_pending_call() {
+        let registry = ToolApprovalRegistry::new();
+        let (request, _decision_rx) = registry.request(uuid::Uuid::new_v4(), None, "shell", "{}");
+        assert!(registry.expire(&request.approval_id));
+        assert!(!registry.expire(&request.approval_id));
+        assert!(registry.pending().is_empty());
+    }
+}
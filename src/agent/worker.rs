@@ -1,7 +1,7 @@
 //! Worker: Independent task execution process.
 
 use crate::agent::compactor::estimate_history_tokens;
-use crate::config::BrowserConfig;
+use crate::config::{AccessGrant, BrowserConfig};
 use crate::conversation::settings::WorkerMemoryMode;
 use crate::error::Result;
 use crate::hooks::SpacebotHook;
@@ -95,6 +95,8 @@ pub struct Worker {
     pub worker_memory_mode: WorkerMemoryMode,
     /// Model override from conversation settings (per-process or blanket).
     pub model_override: Option<String>,
+    /// Role of whoever asked for this worker. `None` means unrestricted.
+    pub access: Option<AccessGrant>,
 }
 
 impl Worker {
@@ -121,7 +123,8 @@ impl Worker {
             ProcessType::Worker,
             channel_id.clone(),
             deps.event_tx.clone(),
-        );
+        )
+        .with_tool_approval(deps.tool_approvals.clone(), deps.runtime_config.clone());
         let (status_tx, status_rx) = watch::channel("starting".to_string());
         let (inject_tx, inject_rx) = mpsc::channel(8);
 
@@ -149,6 +152,7 @@ impl Worker {
                 },
                 worker_memory_mode,
                 model_override,
+                access: None,
             },
             inject_tx,
         )
@@ -271,6 +275,10 @@ impl Worker {
             ProcessType::Worker,
             worker.channel_id.clone(),
             worker.deps.event_tx.clone(),
+        )
+        .with_tool_approval(
+            worker.deps.tool_approvals.clone(),
+            worker.deps.runtime_config.clone(),
         );
         worker.state = WorkerState::WaitingForInput;
         // Stash the prior history so `run_follow_up_loop()` can pick it up.
@@ -278,6 +286,12 @@ impl Worker {
         (worker, input_tx, inject_tx)
     }
 
+    /// Restrict the worker's tools to what `access` permits.
+    pub fn with_access(mut self, access: AccessGrant) -> Self {
+        self.access = Some(access);
+        self
+    }
+
     /// Check if the worker can transition to a new state.
     pub fn can_transition_to(&self, target: WorkerState) -> bool {
         use WorkerState::*;
@@ -342,6 +356,7 @@ impl Worker {
             self.deps.runtime_config.clone(),
             self.worker_memory_mode,
             self.deps.memory_search.clone(),
+            self.access.clone(),
         );
 
         let routing = self.deps.runtime_config.routing.load();
@@ -350,10 +365,13 @@ impl Worker {
             .as_deref()
             .unwrap_or_else(|| routing.resolve(ProcessType::Worker, None))
             .to_string();
-        let model = SpacebotModel::make(&self.deps.llm_manager, &model_name)
+        let mut model = SpacebotModel::make(&self.deps.llm_manager, &model_name)
             .with_context(&*self.deps.agent_id, "worker")
             .with_worker_type("builtin")
             .with_routing((**routing).clone());
+        if let Some(channel_id) = &self.channel_id {
+            model = model.with_channel(&**channel_id);
+        }
 
         let agent = AgentBuilder::new(model)
             .preamble(&self.system_prompt)
@@ -819,7 +837,10 @@ impl Worker {
         let mut full_history = compacted_history.to_vec();
         full_history.extend(history.iter().cloned());
         let transcript_blob =
-            crate::conversation::worker_transcript::serialize_transcript(&full_history);
+            crate::conversation::worker_transcript::serialize_transcript_with_approvals(
+                &full_history,
+                &self.hook.tool_approval_records(),
+            );
         let worker_id = self.id.to_string();
 
         // Count tool calls from the Rig history (each ToolCall in an Assistant message)
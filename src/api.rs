@@ -4,7 +4,9 @@
 //! managing agents, viewing status, and interacting with the system.
 //! Includes an SSE endpoint for realtime event streaming.
 
+pub mod access;
 pub mod agents;
+mod auth;
 mod bindings;
 mod channels;
 mod config;
@@ -13,11 +15,14 @@ mod cron;
 mod factory;
 mod ingest;
 mod links;
+mod logs;
 mod mcp;
 mod memories;
 mod messaging;
 mod models;
+pub mod oidc;
 mod opencode_proxy;
+mod outbox;
 mod portal;
 mod projects;
 mod providers;
@@ -25,12 +30,15 @@ mod secrets;
 mod server;
 mod settings;
 mod skills;
+mod spend;
 pub(crate) mod ssh;
 mod state;
 mod system;
 mod tasks;
+mod tokens;
 mod tools;
 mod workers;
 
+pub use logs::{LogBuffer, LogBufferLayer, LogEntry};
 pub use server::{api_router, start_http_server};
 pub use state::{AgentInfo, ApiEvent, ApiState};
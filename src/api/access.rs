@@ -0,0 +1,869 @@
+//! Named API tokens, their scopes, and the audit log (SQLite).
+//!
+//! The `api.auth_token` from config stays the root credential. Named tokens
+//! are created through the API or CLI, stored as SHA-256 hashes, and carry a
+//! set of scopes plus an optional list of agents they may touch. Users who log
+//! into the web interface through OIDC get a session whose scopes come from
+//! their identity provider groups. Every mutating API call is appended to the
+//! audit log with the actor that made it.
+
+use crate::error::Result;
+
+use anyhow::Context as _;
+use axum::http::Method;
+use base64::Engine as _;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, Utc};
+use rand::RngCore as _;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest as _, Sha256};
+use sqlx::{Row as _, SqlitePool};
+
+/// Prefix on every generated token, so leaked tokens are easy to grep for.
+const TOKEN_PREFIX: &str = "sbt_";
+/// Prefix on session cookie values.
+const SESSION_PREFIX: &str = "sbs_";
+/// Characters of the token kept in plaintext for display.
+const DISPLAY_PREFIX_CHARS: usize = 12;
+/// `last_used_at` is only rewritten when it is older than this, so busy
+/// tokens don't turn every request into a write.
+const LAST_USED_RESOLUTION_SECS: i64 = 60;
+
+/// What a named token may do.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiScope {
+    /// `GET` requests outside secrets and admin routes.
+    Read,
+    /// Changes to agent state: channels, memories, cron, tasks, workers, skills.
+    Agents,
+    /// Adapter control and the outbox.
+    Messaging,
+    /// Instance configuration: settings, providers, bindings, MCP, links,
+    /// and creating or deleting agents.
+    Config,
+    /// The secret store.
+    Secrets,
+    /// Everything, including token management, the audit log, backups and updates.
+    Admin,
+}
+
+impl ApiScope {
+    pub const ALL: [ApiScope; 6] = [
+        ApiScope::Read,
+        ApiScope::Agents,
+        ApiScope::Messaging,
+        ApiScope::Config,
+        ApiScope::Secrets,
+        ApiScope::Admin,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ApiScope::Read => "read",
+            ApiScope::Agents => "agents",
+            ApiScope::Messaging => "messaging",
+            ApiScope::Config => "config",
+            ApiScope::Secrets => "secrets",
+            ApiScope::Admin => "admin",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        ApiScope::ALL
+            .into_iter()
+            .find(|scope| scope.as_str() == value)
+    }
+
+    /// Whether holding this scope permits a request that needs `required`.
+    /// Every scope includes `read`; `admin` includes everything.
+    pub fn grants(self, required: ApiScope) -> bool {
+        self == ApiScope::Admin || self == required || required == ApiScope::Read
+    }
+
+    /// The scope a request needs, from its method and path.
+    pub fn required_for(method: &Method, path: &str) -> ApiScope {
+        let path = path.strip_prefix("/api").unwrap_or(path);
+        let read = method == Method::GET || method == Method::HEAD;
+        let section = path.trim_start_matches('/').split('/').next().unwrap_or("");
+
+        match section {
+            "tokens" | "audit" | "ssh" | "update-apply" => ApiScope::Admin,
+            "update-check" if !read => ApiScope::Admin,
+            "system" if !(read && path == "/system/storage") => ApiScope::Admin,
+            "secrets" => ApiScope::Secrets,
+            // Raw config and provider config can hold plaintext keys.
+            "settings" | "providers" | "bindings" | "mcp" | "links" | "models" => {
+                if read && path != "/settings/raw" && !path.ends_with("/config") {
+                    ApiScope::Read
+                } else {
+                    ApiScope::Config
+                }
+            }
+            "agents" if !read && (path == "/agents" || path == "/agents/config") => {
+                ApiScope::Config
+            }
+            "messaging" if !read => ApiScope::Messaging,
+            _ if read => ApiScope::Read,
+            _ => ApiScope::Agents,
+        }
+    }
+}
+
+impl std::fmt::Display for ApiScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A named token as shown by the API. The secret itself is never stored.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct ApiToken {
+    pub id: String,
+    pub name: String,
+    /// Leading characters of the token, e.g. `sbt_AbC12345`.
+    pub prefix: String,
+    pub scopes: Vec<ApiScope>,
+    /// Agents this token may act on. Empty means every agent.
+    pub agent_ids: Vec<String>,
+    pub expires_at: Option<String>,
+    pub last_used_at: Option<String>,
+    pub revoked_at: Option<String>,
+    pub created_at: String,
+}
+
+impl ApiToken {
+    /// Whether any of the token's scopes permits `required`.
+    pub fn allows(&self, required: ApiScope) -> bool {
+        scopes_allow(&self.scopes, required)
+    }
+
+    /// Whether the token may act on `agent_id`. Agent-restricted tokens must
+    /// name one of their agents on every request.
+    pub fn allows_agent(&self, agent_id: Option<&str>) -> bool {
+        self.agent_ids.is_empty()
+            || agent_id.is_some_and(|agent_id| self.agent_ids.iter().any(|id| id == agent_id))
+    }
+}
+
+/// A freshly created token. `secret` is only ever returned here.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct CreatedApiToken {
+    pub token: ApiToken,
+    pub secret: String,
+}
+
+/// A web interface login from OIDC single sign-on.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct ApiSession {
+    pub id: String,
+    /// The ID token's `sub` claim.
+    pub subject: String,
+    pub email: Option<String>,
+    pub name: Option<String>,
+    pub groups: Vec<String>,
+    /// Scopes granted from `api.oidc.group_scopes` at login.
+    pub scopes: Vec<ApiScope>,
+    /// Must be sent as `X-CSRF-Token` on mutating requests.
+    #[serde(skip)]
+    pub csrf_token: String,
+    pub expires_at: String,
+    pub created_at: String,
+}
+
+impl ApiSession {
+    /// Whether any of the session's scopes permits `required`.
+    pub fn allows(&self, required: ApiScope) -> bool {
+        scopes_allow(&self.scopes, required)
+    }
+
+    /// The email address if the provider sent one, otherwise the subject.
+    pub fn user(&self) -> &str {
+        self.email.as_deref().unwrap_or(&self.subject)
+    }
+
+    /// Whether `presented` matches the session's CSRF token. Both sides are
+    /// hashed first so the comparison leaks nothing useful through timing.
+    pub fn csrf_matches(&self, presented: Option<&str>) -> bool {
+        presented.is_some_and(|presented| {
+            Sha256::digest(presented.as_bytes()) == Sha256::digest(self.csrf_token.as_bytes())
+        })
+    }
+}
+
+/// A freshly created session. `secret` is the cookie value.
+#[derive(Debug, Clone)]
+pub struct CreatedApiSession {
+    pub session: ApiSession,
+    pub secret: String,
+}
+
+/// Who made an API call.
+#[derive(Debug, Clone)]
+pub enum ApiActor {
+    /// The `api.auth_token` from config.
+    Root,
+    /// Any caller while neither `api.auth_token` nor `api.oidc` is set.
+    Anonymous,
+    Token(ApiToken),
+    Session(ApiSession),
+}
+
+impl ApiActor {
+    /// How the actor is recorded in the audit log.
+    pub fn label(&self) -> String {
+        match self {
+            ApiActor::Root => "root".to_string(),
+            ApiActor::Anonymous => "anonymous".to_string(),
+            ApiActor::Token(token) => format!("token:{}", token.name),
+            ApiActor::Session(session) => format!("user:{}", session.user()),
+        }
+    }
+
+    fn token_id(&self) -> Option<&str> {
+        match self {
+            ApiActor::Token(token) => Some(token.id.as_str()),
+            ApiActor::Root | ApiActor::Anonymous | ApiActor::Session(_) => None,
+        }
+    }
+}
+
+/// One mutating API call.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct AuditEntry {
+    pub id: i64,
+    /// `root`, `anonymous`, `token:<name>`, or `user:<email>`.
+    pub actor: String,
+    pub token_id: Option<String>,
+    pub method: String,
+    pub path: String,
+    pub agent_id: Option<String>,
+    pub status: i64,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ApiAccessStore {
+    pool: SqlitePool,
+}
+
+impl ApiAccessStore {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Create a token and return its one-time plaintext secret.
+    pub async fn create_token(
+        &self,
+        name: &str,
+        scopes: &[ApiScope],
+        agent_ids: &[String],
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<CreatedApiToken> {
+        let id = uuid::Uuid::new_v4().to_string();
+        let secret = generate_secret(TOKEN_PREFIX);
+        let prefix: String = secret.chars().take(DISPLAY_PREFIX_CHARS).collect();
+        let scopes_json = serde_json::to_string(scopes).context("failed to serialize scopes")?;
+        let agent_ids_json =
+            serde_json::to_string(agent_ids).context("failed to serialize agent IDs")?;
+
+        sqlx::query(
+            "INSERT INTO api_tokens (id, name, token_hash, token_prefix, scopes, agent_ids, \
+             expires_at) VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&id)
+        .bind(name)
+        .bind(hash_secret(&secret))
+        .bind(&prefix)
+        .bind(scopes_json)
+        .bind(agent_ids_json)
+        .bind(expires_at.map(format_timestamp))
+        .execute(&self.pool)
+        .await
+        .context("failed to create API token")?;
+
+        let token = self
+            .get_token(&id)
+            .await?
+            .context("created API token not found")?;
+        Ok(CreatedApiToken { token, secret })
+    }
+
+    pub async fn get_token(&self, id: &str) -> Result<Option<ApiToken>> {
+        let row = sqlx::query(&format!("{TOKEN_COLUMNS} WHERE id = ?"))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .context("failed to load API token")?;
+        row.map(|row| token_from_row(&row)).transpose()
+    }
+
+    /// All tokens, newest first, including revoked and expired ones.
+    pub async fn list_tokens(&self) -> Result<Vec<ApiToken>> {
+        let rows = sqlx::query(&format!(
+            "{TOKEN_COLUMNS} ORDER BY created_at DESC, name ASC"
+        ))
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to list API tokens")?;
+        rows.iter().map(token_from_row).collect()
+    }
+
+    /// Revoke a token. Returns `false` if no such active token exists.
+    pub async fn revoke_token(&self, id: &str) -> Result<bool> {
+        let result = sqlx::query(
+            "UPDATE api_tokens SET revoked_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now') \
+             WHERE id = ? AND revoked_at IS NULL",
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .context("failed to revoke API token")?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Resolve a presented secret to an active token and record its use.
+    /// Unknown, revoked and expired tokens all resolve to `None`.
+    pub async fn authenticate(&self, secret: &str) -> Result<Option<ApiToken>> {
+        if !secret.starts_with(TOKEN_PREFIX) {
+            return Ok(None);
+        }
+        let now = Utc::now();
+        let row = sqlx::query(&format!(
+            "{TOKEN_COLUMNS} WHERE token_hash = ? AND revoked_at IS NULL \
+             AND (expires_at IS NULL OR expires_at > ?)"
+        ))
+        .bind(hash_secret(secret))
+        .bind(format_timestamp(now))
+        .fetch_optional(&self.pool)
+        .await
+        .context("failed to look up API token")?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let token = token_from_row(&row)?;
+
+        let stale_before = now - chrono::Duration::seconds(LAST_USED_RESOLUTION_SECS);
+        sqlx::query(
+            "UPDATE api_tokens SET last_used_at = ? \
+             WHERE id = ? AND (last_used_at IS NULL OR last_used_at < ?)",
+        )
+        .bind(format_timestamp(now))
+        .bind(&token.id)
+        .bind(format_timestamp(stale_before))
+        .execute(&self.pool)
+        .await
+        .context("failed to record API token use")?;
+
+        Ok(Some(token))
+    }
+
+    /// Start a web interface session. Expired sessions are purged first.
+    pub async fn create_session(
+        &self,
+        subject: &str,
+        email: Option<&str>,
+        name: Option<&str>,
+        groups: &[String],
+        scopes: &[ApiScope],
+        expires_at: DateTime<Utc>,
+    ) -> Result<CreatedApiSession> {
+        sqlx::query("DELETE FROM api_sessions WHERE expires_at <= ?")
+            .bind(format_timestamp(Utc::now()))
+            .execute(&self.pool)
+            .await
+            .context("failed to purge expired sessions")?;
+
+        let id = uuid::Uuid::new_v4().to_string();
+        let secret = generate_secret(SESSION_PREFIX);
+        let csrf_token = generate_secret("");
+        let groups_json = serde_json::to_string(groups).context("failed to serialize groups")?;
+        let scopes_json = serde_json::to_string(scopes).context("failed to serialize scopes")?;
+
+        sqlx::query(
+            "INSERT INTO api_sessions (id, session_hash, csrf_token, subject, email, name, \
+             groups, scopes, expires_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&id)
+        .bind(hash_secret(&secret))
+        .bind(&csrf_token)
+        .bind(subject)
+        .bind(email)
+        .bind(name)
+        .bind(groups_json)
+        .bind(scopes_json)
+        .bind(format_timestamp(expires_at))
+        .execute(&self.pool)
+        .await
+        .context("failed to create session")?;
+
+        let row = sqlx::query(&format!("{SESSION_COLUMNS} WHERE id = ?"))
+            .bind(&id)
+            .fetch_one(&self.pool)
+            .await
+            .context("created session not found")?;
+        Ok(CreatedApiSession {
+            session: session_from_row(&row)?,
+            secret,
+        })
+    }
+
+    /// Resolve a session cookie to a live session.
+    pub async fn authenticate_session(&self, secret: &str) -> Result<Option<ApiSession>> {
+        if !secret.starts_with(SESSION_PREFIX) {
+            return Ok(None);
+        }
+        let row = sqlx::query(&format!(
+            "{SESSION_COLUMNS} WHERE session_hash = ? AND expires_at > ?"
+        ))
+        .bind(hash_secret(secret))
+        .bind(format_timestamp(Utc::now()))
+        .fetch_optional(&self.pool)
+        .await
+        .context("failed to look up session")?;
+        row.map(|row| session_from_row(&row)).transpose()
+    }
+
+    /// End a session. Returns `false` if it was already gone.
+    pub async fn delete_session(&self, id: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM api_sessions WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .context("failed to delete session")?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Append a mutating call to the audit log.
+    pub async fn record_audit(
+        &self,
+        actor: &ApiActor,
+        method: &str,
+        path: &str,
+        agent_id: Option<&str>,
+        status: u16,
+    ) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO audit_log (actor, token_id, method, path, agent_id, status) \
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(actor.label())
+        .bind(actor.token_id())
+        .bind(method)
+        .bind(path)
+        .bind(agent_id)
+        .bind(i64::from(status))
+        .execute(&self.pool)
+        .await
+        .context("failed to write audit log entry")?;
+        Ok(())
+    }
+
+    /// Audit entries, newest first. `before` pages backwards by entry ID.
+    pub async fn list_audit(
+        &self,
+        token_id: Option<&str>,
+        agent_id: Option<&str>,
+        before: Option<i64>,
+        limit: i64,
+    ) -> Result<Vec<AuditEntry>> {
+        let rows = sqlx::query(
+            "SELECT id, actor, token_id, method, path, agent_id, status, created_at \
+             FROM audit_log \
+             WHERE (? IS NULL OR token_id = ?) AND (? IS NULL OR agent_id = ?) \
+             AND (? IS NULL OR id < ?) \
+             ORDER BY id DESC LIMIT ?",
+        )
+        .bind(token_id)
+        .bind(token_id)
+        .bind(agent_id)
+        .bind(agent_id)
+        .bind(before)
+        .bind(before)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to list audit log")?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(AuditEntry {
+                    id: row.try_get("id").context("failed to read id")?,
+                    actor: row.try_get("actor").context("failed to read actor")?,
+                    token_id: row.try_get("token_id").context("failed to read token_id")?,
+                    method: row.try_get("method").context("failed to read method")?,
+                    path: row.try_get("path").context("failed to read path")?,
+                    agent_id: row.try_get("agent_id").context("failed to read agent_id")?,
+                    status: row.try_get("status").context("failed to read status")?,
+                    created_at: row
+                        .try_get("created_at")
+                        .context("failed to read created_at")?,
+                })
+            })
+            .collect()
+    }
+}
+
+const TOKEN_COLUMNS: &str = "SELECT id, name, token_prefix, scopes, agent_ids, expires_at, \
+                             last_used_at, revoked_at, created_at FROM api_tokens";
+
+fn token_from_row(row: &sqlx::sqlite::SqliteRow) -> Result<ApiToken> {
+    let scopes: String = row.try_get("scopes").context("failed to read scopes")?;
+    let agent_ids: String = row
+        .try_get("agent_ids")
+        .context("failed to read agent_ids")?;
+    Ok(ApiToken {
+        id: row.try_get("id").context("failed to read id")?,
+        name: row.try_get("name").context("failed to read name")?,
+        prefix: row
+            .try_get("token_prefix")
+            .context("failed to read token_prefix")?,
+        scopes: serde_json::from_str(&scopes).context("invalid token scopes")?,
+        agent_ids: serde_json::from_str(&agent_ids).context("invalid token agent IDs")?,
+        expires_at: row
+            .try_get("expires_at")
+            .context("failed to read expires_at")?,
+        last_used_at: row
+            .try_get("last_used_at")
+            .context("failed to read last_used_at")?,
+        revoked_at: row
+            .try_get("revoked_at")
+            .context("failed to read revoked_at")?,
+        created_at: row
+            .try_get("created_at")
+            .context("failed to read created_at")?,
+    })
+}
+
+const SESSION_COLUMNS: &str = "SELECT id, csrf_token, subject, email, name, groups, scopes, \
+                               expires_at, created_at FROM api_sessions";
+
+fn session_from_row(row: &sqlx::sqlite::SqliteRow) -> Result<ApiSession> {
+    let groups: String = row.try_get("groups").context("failed to read groups")?;
+    let scopes: String = row.try_get("scopes").context("failed to read scopes")?;
+    Ok(ApiSession {
+        id: row.try_get("id").context("failed to read id")?,
+        subject: row.try_get("subject").context("failed to read subject")?,
+        email: row.try_get("email").context("failed to read email")?,
+        name: row.try_get("name").context("failed to read name")?,
+        groups: serde_json::from_str(&groups).context("invalid session groups")?,
+        scopes: serde_json::from_str(&scopes).context("invalid session scopes")?,
+        csrf_token: row
+            .try_get("csrf_token")
+            .context("failed to read csrf_token")?,
+        expires_at: row
+            .try_get("expires_at")
+            .context("failed to read expires_at")?,
+        created_at: row
+            .try_get("created_at")
+            .context("failed to read created_at")?,
+    })
+}
+
+fn scopes_allow(scopes: &[ApiScope], required: ApiScope) -> bool {
+    scopes.iter().any(|scope| scope.grants(required))
+}
+
+/// The agent a request path names, for routes that carry it in the path.
+pub fn agent_id_from_path(path: &str) -> Option<&str> {
+    let path = path.strip_prefix("/api").unwrap_or(path);
+    let segments: Vec<&str> = path.trim_start_matches('/').split('/').collect();
+    match segments.as_slice() {
+        ["agents", agent_id, "spend", ..] | ["links", "agent", agent_id] => Some(*agent_id),
+        _ => None,
+    }
+}
+
+/// Whether a route only acts on the agent the request names. Agent-limited
+/// tokens are refused on every other route: instance-wide routes and routes
+/// that look a resource up by a global ID (outbox rows, task numbers, live
+/// channels) would ignore the named agent.
+pub fn route_is_agent_scoped(method: &Method, path: &str) -> bool {
+    let path = path.strip_prefix("/api").unwrap_or(path);
+    let read = method == Method::GET || method == Method::HEAD;
+    let segments: Vec<&str> = path.trim_start_matches('/').split('/').collect();
+    match segments.as_slice() {
+        [
+            "agents",
+            "overview" | "profile" | "identity" | "avatar" | "config" | "cron" | "mcp" | "memories"
+            | "skills" | "warmup" | "workers" | "ingest" | "projects",
+            ..,
+        ]
+        | ["agents", _, "spend", ..]
+        | ["links", "agent", _]
+        | ["channels"]
+        | ["channels", "archive"]
+        | ["channels", _, "settings"]
+        | ["cortex" | "cortex-chat" | "portal", ..]
+        | ["conversation-defaults"] => true,
+        // Listing filters on the named agent; creating takes the owner from
+        // `owner_agent_id`, which the middleware does not inspect.
+        ["tasks"] => read,
+        _ => false,
+    }
+}
+
+fn generate_secret(prefix: &str) -> String {
+    let mut bytes = [0u8; 32];
+    rand::rng().fill_bytes(&mut bytes);
+    format!("{prefix}{}", URL_SAFE_NO_PAD.encode(bytes))
+}
+
+/// Tokens and session cookies carry 256 bits of randomness, so an unsalted
+/// hash is enough.
+fn hash_secret(secret: &str) -> String {
+    hex::encode(Sha256::digest(secret.as_bytes()))
+}
+
+/// Same fixed-width UTC format as the SQL defaults, so string comparison
+/// orders timestamps correctly.
+fn format_timestamp(time: DateTime<Utc>) -> String {
+    time.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_store() -> ApiAccessStore {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("in-memory sqlite should connect");
+        sqlx::migrate!("./migrations/api")
+            .run(&pool)
+            .await
+            .expect("api migrations should apply");
+        ApiAccessStore::new(pool)
+    }
+
+    #[tokio::test]
+    async fn tokens_authenticate_until_revoked_or_expired() {
+        let store = setup_store().await;
+        let created = store
+            .create_token("ci", &[ApiScope::Messaging], &["main".into()], None)
+            .await
+            .unwrap();
+        assert!(created.secret.starts_with(created.token.prefix.as_str()));
+        assert!(created.secret.starts_with(TOKEN_PREFIX));
+
+        let token = store.authenticate(&created.secret).await.unwrap().unwrap();
+        assert_eq!(token.name, "ci");
+        assert_eq!(token.agent_ids, vec!["main".to_string()]);
+        assert!(
+            store
+                .get_token(&token.id)
+                .await
+                .unwrap()
+                .unwrap()
+                .last_used_at
+                .is_some()
+        );
+        assert!(store.authenticate("sbt_wrong").await.unwrap().is_none());
+        assert!(store.authenticate("not-a-token").await.unwrap().is_none());
+
+        assert!(store.revoke_token(&token.id).await.unwrap());
+        assert!(!store.revoke_token(&token.id).await.unwrap());
+        assert!(store.authenticate(&created.secret).await.unwrap().is_none());
+
+        let expired = store
+            .create_token(
+                "old",
+                &[ApiScope::Read],
+                &[],
+                Some(Utc::now() - chrono::Duration::hours(1)),
+            )
+            .await
+            .unwrap();
+        assert!(store.authenticate(&expired.secret).await.unwrap().is_none());
+        assert_eq!(store.list_tokens().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn audit_log_is_append_only() {
+        let store = setup_store().await;
+        let created = store
+            .create_token("deploy", &[ApiScope::Config], &[], None)
+            .await
+            .unwrap();
+        store
+            .record_audit(&ApiActor::Root, "POST", "/api/agents", None, 200)
+            .await
+            .unwrap();
+        store
+            .record_audit(
+                &ApiActor::Token(created.token.clone()),
+                "PUT",
+                "/api/agents/config",
+                Some("main"),
+                403,
+            )
+            .await
+            .unwrap();
+
+        let entries = store.list_audit(None, None, None, 10).await.unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].actor, "token:deploy");
+        assert_eq!(entries[0].status, 403);
+        assert_eq!(entries[1].actor, "root");
+
+        let filtered = store
+            .list_audit(Some(&created.token.id), None, None, 10)
+            .await
+            .unwrap();
+        assert_eq!(filtered.len(), 1);
+        let older = store
+            .list_audit(None, None, Some(entries[0].id), 10)
+            .await
+            .unwrap();
+        assert_eq!(older[0].id, entries[1].id);
+
+        assert!(
+            sqlx::query("DELETE FROM audit_log")
+                .execute(&store.pool)
+                .await
+                .is_err()
+        );
+        assert!(
+            sqlx::query("UPDATE audit_log SET status = 200")
+                .execute(&store.pool)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn sessions_authenticate_until_expired_or_deleted() {
+        let store = setup_store().await;
+        let created = store
+            .create_session(
+                "user-1",
+                Some("ada@example.com"),
+                Some("Ada"),
+                &["ops".into()],
+                &[ApiScope::Read, ApiScope::Messaging],
+                Utc::now() + chrono::Duration::hours(1),
+            )
+            .await
+            .unwrap();
+        assert!(created.secret.starts_with(SESSION_PREFIX));
+
+        let session = store
+            .authenticate_session(&created.secret)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(session.groups, vec!["ops".to_string()]);
+        assert!(session.allows(ApiScope::Messaging));
+        assert!(!session.allows(ApiScope::Secrets));
+        assert!(session.csrf_matches(Some(created.session.csrf_token.as_str())));
+        assert!(!session.csrf_matches(Some("forged")));
+        assert!(!session.csrf_matches(None));
+        assert_eq!(
+            ApiActor::Session(session.clone()).label(),
+            "user:ada@example.com"
+        );
+        assert!(
+            store
+                .authenticate_session("sbs_wrong")
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(store.authenticate("sbt_wrong").await.unwrap().is_none());
+
+        assert!(store.delete_session(&session.id).await.unwrap());
+        assert!(!store.delete_session(&session.id).await.unwrap());
+        assert!(
+            store
+                .authenticate_session(&created.secret)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let expired = store
+            .create_session(
+                "user-2",
+                None,
+                None,
+                &[],
+                &[ApiScope::Read],
+                Utc::now() - chrono::Duration::minutes(1),
+            )
+            .await
+            .unwrap();
+        assert!(
+            store
+                .authenticate_session(&expired.secret)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn required_scope_by_route() {
+        let scope = |method: Method, path: &str| ApiScope::required_for(&method, path);
+        assert_eq!(scope(Method::GET, "/api/agents/memories"), ApiScope::Read);
+        assert_eq!(scope(Method::POST, "/api/agents/cron"), ApiScope::Agents);
+        assert_eq!(scope(Method::POST, "/api/agents"), ApiScope::Config);
+        assert_eq!(scope(Method::GET, "/api/settings"), ApiScope::Read);
+        assert_eq!(scope(Method::GET, "/api/settings/raw"), ApiScope::Config);
+        assert_eq!(
+            scope(Method::GET, "/api/providers/openai/config"),
+            ApiScope::Config
+        );
+        assert_eq!(scope(Method::PUT, "/api/bindings"), ApiScope::Config);
+        assert_eq!(scope(Method::GET, "/api/secrets"), ApiScope::Secrets);
+        assert_eq!(scope(Method::GET, "/api/messaging/status"), ApiScope::Read);
+        assert_eq!(
+            scope(Method::POST, "/api/messaging/toggle"),
+            ApiScope::Messaging
+        );
+        assert_eq!(scope(Method::GET, "/api/system/storage"), ApiScope::Read);
+        assert_eq!(
+            scope(Method::GET, "/api/system/backup/export"),
+            ApiScope::Admin
+        );
+        assert_eq!(scope(Method::GET, "/api/audit"), ApiScope::Admin);
+
+        assert!(ApiScope::Messaging.grants(ApiScope::Read));
+        assert!(!ApiScope::Read.grants(ApiScope::Secrets));
+        assert!(ApiScope::Admin.grants(ApiScope::Secrets));
+    }
+
+    #[test]
+    fn agent_id_from_path_matches_agent_routes() {
+        assert_eq!(agent_id_from_path("/api/agents/main/spend"), Some("main"));
+        assert_eq!(agent_id_from_path("/api/links/agent/ops"), Some("ops"));
+        assert_eq!(agent_id_from_path("/api/agents/memories"), None);
+    }
+
+    #[test]
+    fn instance_wide_routes_are_not_agent_scoped() {
+        assert!(route_is_agent_scoped(&Method::GET, "/api/agents/memories"));
+        assert!(route_is_agent_scoped(
+            &Method::POST,
+            "/api/agents/workers/approvals/abc"
+        ));
+        assert!(route_is_agent_scoped(&Method::GET, "/api/tasks"));
+        assert!(!route_is_agent_scoped(&Method::POST, "/api/tasks"));
+        assert!(!route_is_agent_scoped(
+            &Method::POST,
+            "/api/tasks/7/approve"
+        ));
+        assert!(!route_is_agent_scoped(
+            &Method::GET,
+            "/api/messaging/outbox"
+        ));
+        assert!(!route_is_agent_scoped(
+            &Method::GET,
+            "/api/channels/messages"
+        ));
+        assert!(!route_is_agent_scoped(&Method::GET, "/api/logs"));
+        assert!(!route_is_agent_scoped(&Method::GET, "/api/agents"));
+    }
+}
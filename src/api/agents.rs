@@ -71,6 +71,7 @@ struct CronJobInfo {
     delivery_target: String,
     enabled: bool,
     run_once: bool,
+    stateful: bool,
     active_hours: Option<(u8, u8)>,
     timeout_secs: Option<u64>,
 }
@@ -500,6 +501,7 @@ pub(super) async fn trigger_warmup(
                 process_control_registry: Arc::new(
                     crate::agent::process_control::ProcessControlRegistry::new(),
                 ),
+                tool_approvals: Arc::new(crate::agent::tool_approval::ToolApprovalRegistry::new()),
                 injection_tx,
                 working_memory,
             };
@@ -714,10 +716,16 @@ pub async fn create_agent_internal(
         memory_persistence: None,
         coalesce: None,
         ingestion: None,
+        participants: None,
         cortex: None,
         warmup: None,
         browser: None,
         channel: None,
+        budget: None,
+        response_cache: None,
+        tool_approval: None,
+        access: None,
+        commands: None,
         mcp: None,
         brave_search_key: None,
         cron_timezone: None,
@@ -782,7 +790,7 @@ pub async fn create_agent_internal(
     let memory_search = std::sync::Arc::new(crate::memory::MemorySearch::new(
         memory_store,
         embedding_table,
-        embedding_model,
+        embedding_model.clone(),
     ));
     let task_store = state
         .task_store
@@ -851,6 +859,28 @@ pub async fn create_agent_internal(
             .clone()
     };
 
+    let working_memory_timezone = agent_config
+        .user_timezone
+        .as_deref()
+        .or(agent_config.cron_timezone.as_deref())
+        .and_then(|tz| tz.parse::<chrono_tz::Tz>().ok())
+        .unwrap_or(chrono_tz::Tz::UTC);
+
+    let spend_tracker = crate::llm::budget::SpendTracker::new(
+        agent_id.clone(),
+        db.sqlite.clone(),
+        working_memory_timezone,
+        runtime_config.clone(),
+    );
+    spend_tracker.set_messaging_manager(messaging_manager.clone());
+    llm_manager.register_spend_tracker(spend_tracker);
+    llm_manager.register_response_cache(crate::llm::response_cache::ResponseCache::new(
+        agent_id.clone(),
+        db.sqlite.clone(),
+        runtime_config.clone(),
+        Some(embedding_model.clone()),
+    ));
+
     let mcp_manager = std::sync::Arc::new(crate::mcp::McpManager::new(agent_config.mcp.clone()));
     mcp_manager.connect_all().await;
 
@@ -869,6 +899,7 @@ pub async fn create_agent_internal(
     // Inject active project root paths into the sandbox allowlist.
     crate::projects::refresh_sandbox_project_paths(&project_store, &arc_agent_id, &sandbox).await;
 
+    let tool_approvals = Arc::new(crate::agent::tool_approval::ToolApprovalRegistry::new());
     let deps = crate::AgentDeps {
         agent_id: arc_agent_id.clone(),
         memory_search: memory_search.clone(),
@@ -889,6 +920,7 @@ pub async fn create_agent_internal(
         process_control_registry: Arc::new(
             crate::agent::process_control::ProcessControlRegistry::new(),
         ),
+        tool_approvals: tool_approvals.clone(),
         injection_tx: state.injection_tx.clone(),
         agent_names: {
             let configs = state.agent_configs.load();
@@ -913,15 +945,10 @@ pub async fn create_agent_internal(
         humans: Arc::new(arc_swap::ArcSwap::from_pointee(
             (**state.agent_humans.load()).clone(),
         )),
-        working_memory: {
-            let tz = agent_config
-                .user_timezone
-                .as_deref()
-                .or(agent_config.cron_timezone.as_deref())
-                .and_then(|tz| tz.parse::<chrono_tz::Tz>().ok())
-                .unwrap_or(chrono_tz::Tz::UTC);
-            crate::memory::WorkingMemoryStore::new(db.sqlite.clone(), tz)
-        },
+        working_memory: crate::memory::WorkingMemoryStore::new(
+            db.sqlite.clone(),
+            working_memory_timezone,
+        ),
     };
 
     let event_rx = event_tx.subscribe();
@@ -992,6 +1019,14 @@ pub async fn create_agent_internal(
         deps.clone(),
         crate::agent::cortex::CortexLogger::new(db.sqlite.clone()),
     );
+    crate::agent::cortex::spawn_participant_loop(
+        deps.clone(),
+        crate::agent::cortex::CortexLogger::new(db.sqlite.clone()),
+    );
+    crate::agent::cortex::spawn_autonomy_loop(
+        deps.clone(),
+        crate::agent::cortex::CortexLogger::new(db.sqlite.clone()),
+    );
 
     let ingestion_config = **runtime_config.ingestion.load();
     if ingestion_config.enabled {
@@ -1048,6 +1083,10 @@ pub async fn create_agent_internal(
         sandboxes.insert(agent_id.clone(), sandbox);
         state.sandboxes.store(std::sync::Arc::new(sandboxes));
 
+        let mut approvals = (**state.tool_approvals.load()).clone();
+        approvals.insert(agent_id.clone(), tool_approvals);
+        state.tool_approvals.store(std::sync::Arc::new(approvals));
+
         let mut project_stores_map = (**state.project_stores.load()).clone();
         project_stores_map.insert(agent_id.clone(), project_store);
         state
@@ -1363,6 +1402,10 @@ pub(super) async fn delete_agent(
         sandboxes.remove(&agent_id);
         state.sandboxes.store(std::sync::Arc::new(sandboxes));
 
+        let mut approvals = (**state.tool_approvals.load()).clone();
+        approvals.remove(&agent_id);
+        state.tool_approvals.store(std::sync::Arc::new(approvals));
+
         let mut agent_infos = (**state.agent_configs.load()).clone();
         agent_infos.retain(|a| a.id != agent_id);
         state.agent_configs.store(std::sync::Arc::new(agent_infos));
@@ -1448,7 +1491,7 @@ pub(super) async fn agent_overview(
     let channel_count = channels.len();
 
     let cron_rows = sqlx::query(
-        "SELECT id, prompt, cron_expr, interval_secs, delivery_target, active_start_hour, active_end_hour, enabled, run_once, timeout_secs FROM cron_jobs ORDER BY created_at ASC",
+        "SELECT id, prompt, cron_expr, interval_secs, delivery_target, active_start_hour, active_end_hour, enabled, run_once, stateful, timeout_secs FROM cron_jobs ORDER BY created_at ASC",
     )
     .fetch_all(pool)
     .await
@@ -1467,6 +1510,7 @@ pub(super) async fn agent_overview(
                 delivery_target: row.get("delivery_target"),
                 enabled: row.get::<i64, _>("enabled") != 0,
                 run_once: row.get::<i64, _>("run_once") != 0,
+                stateful: row.get::<i64, _>("stateful") != 0,
                 active_hours: match (active_start, active_end) {
                     (Some(s), Some(e)) => Some((s as u8, e as u8)),
                     _ => None,
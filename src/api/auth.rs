@@ -0,0 +1,379 @@
+use super::access::{ApiAccessStore, ApiActor, ApiSession};
+use super::oidc::{OidcClient, PENDING_LOGIN_TTL};
+use super::state::ApiState;
+
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::{AppendHeaders, IntoResponse, Redirect, Response};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Cookie holding the session secret.
+const SESSION_COOKIE: &str = "spacebot_session";
+/// Cookie binding an in-progress login to the browser that started it.
+const STATE_COOKIE: &str = "spacebot_oidc_state";
+/// Header that must carry the session's CSRF token on mutating requests.
+pub(super) const CSRF_HEADER: &str = "x-csrf-token";
+
+// ---------------------------------------------------------------------------
+// Request / response types
+// ---------------------------------------------------------------------------
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct AuthSessionResponse {
+    /// Whether single sign-on is configured.
+    oidc_enabled: bool,
+    /// The logged-in user, if the request carries a live session cookie.
+    session: Option<ApiSession>,
+    /// Send as `X-CSRF-Token` on mutating requests.
+    csrf_token: Option<String>,
+}
+
+#[derive(Deserialize, utoipa::ToSchema, utoipa::IntoParams)]
+pub(super) struct LoginQuery {
+    /// Interface path to return to after login.
+    #[serde(default)]
+    return_to: Option<String>,
+}
+
+#[derive(Deserialize, utoipa::ToSchema, utoipa::IntoParams)]
+pub(super) struct CallbackQuery {
+    #[serde(default)]
+    code: Option<String>,
+    #[serde(default)]
+    state: Option<String>,
+    /// Set by the provider when the login was refused or failed.
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    error_description: Option<String>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct LogoutResponse {
+    success: bool,
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// The value of cookie `name` from the request's `Cookie` headers.
+fn cookie_value<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .find_map(|pair| {
+            let (key, value) = pair.trim().split_once('=')?;
+            (key == name).then_some(value)
+        })
+}
+
+/// The session secret from the request's cookies.
+pub(super) fn session_cookie(headers: &HeaderMap) -> Option<&str> {
+    cookie_value(headers, SESSION_COOKIE)
+}
+
+/// The session named by the request's cookie, if it is still live.
+pub(super) async fn current_session(
+    store: &ApiAccessStore,
+    headers: &HeaderMap,
+) -> crate::error::Result<Option<ApiSession>> {
+    match session_cookie(headers) {
+        Some(secret) => store.authenticate_session(secret).await,
+        None => Ok(None),
+    }
+}
+
+fn set_cookie(name: &str, value: &str, path: &str, max_age_secs: i64, secure: bool) -> String {
+    let secure = if secure { "; Secure" } else { "" };
+    format!("{name}={value}; Path={path}; Max-Age={max_age_secs}; HttpOnly; SameSite=Lax{secure}")
+}
+
+fn get_oidc(state: &ApiState) -> Result<Arc<OidcClient>, Response> {
+    state
+        .oidc
+        .clone()
+        .ok_or_else(|| error_response(StatusCode::NOT_FOUND, "single sign-on is not configured"))
+}
+
+fn get_access_store(state: &ApiState) -> Result<Arc<ApiAccessStore>, Response> {
+    state
+        .api_access_store
+        .load()
+        .as_ref()
+        .clone()
+        .ok_or_else(|| {
+            error_response(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "session store not initialized",
+            )
+        })
+}
+
+fn error_response(status: StatusCode, message: impl Into<String>) -> Response {
+    (status, Json(serde_json::json!({"error": message.into()}))).into_response()
+}
+
+async fn audit(
+    store: &ApiAccessStore,
+    session: &ApiSession,
+    method: &str,
+    path: &str,
+    status: u16,
+) {
+    if let Err(error) = store
+        .record_audit(
+            &ApiActor::Session(session.clone()),
+            method,
+            path,
+            None,
+            status,
+        )
+        .await
+    {
+        tracing::warn!(%error, path, "failed to write audit log entry");
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Handlers
+// ---------------------------------------------------------------------------
+
+/// `GET /auth/session` — whether single sign-on is on, and who is logged in.
+#[utoipa::path(
+    get,
+    path = "/auth/session",
+    responses(
+        (status = 200, body = AuthSessionResponse),
+    ),
+    tag = "auth",
+)]
+pub(super) async fn auth_session(
+    State(state): State<Arc<ApiState>>,
+    headers: HeaderMap,
+) -> Response {
+    let session = match (state.oidc.is_some(), get_access_store(&state)) {
+        (true, Ok(store)) => match current_session(&store, &headers).await {
+            Ok(session) => session,
+            Err(error) => {
+                tracing::warn!(%error, "session lookup failed");
+                return error_response(StatusCode::INTERNAL_SERVER_ERROR, error.to_string());
+            }
+        },
+        _ => None,
+    };
+
+    Json(AuthSessionResponse {
+        oidc_enabled: state.oidc.is_some(),
+        csrf_token: session.as_ref().map(|session| session.csrf_token.clone()),
+        session,
+    })
+    .into_response()
+}
+
+/// `GET /auth/oidc/login` — redirect the browser to the identity provider.
+#[utoipa::path(
+    get,
+    path = "/auth/oidc/login",
+    params(LoginQuery),
+    responses(
+        (status = 303, description = "Redirect to the identity provider"),
+        (status = 404, description = "Single sign-on is not configured"),
+        (status = 502, description = "Identity provider unreachable"),
+    ),
+    tag = "auth",
+)]
+pub(super) async fn oidc_login(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<LoginQuery>,
+) -> Response {
+    let oidc = match get_oidc(&state) {
+        Ok(oidc) => oidc,
+        Err(response) => return response,
+    };
+
+    match oidc.begin_login(query.return_to.as_deref()).await {
+        Ok(login) => {
+            let state_cookie = set_cookie(
+                STATE_COOKIE,
+                &login.state,
+                "/",
+                PENDING_LOGIN_TTL.as_secs() as i64,
+                oidc.secure_cookies(),
+            );
+            (
+                AppendHeaders([(header::SET_COOKIE, state_cookie)]),
+                Redirect::to(&login.authorize_url),
+            )
+                .into_response()
+        }
+        Err(error) => {
+            tracing::warn!(%error, "failed to start OIDC login");
+            error_response(StatusCode::BAD_GATEWAY, format!("{error:#}"))
+        }
+    }
+}
+
+/// `GET /auth/oidc/callback` — finish a login and set the session cookie.
+#[utoipa::path(
+    get,
+    path = "/auth/oidc/callback",
+    params(CallbackQuery),
+    responses(
+        (status = 303, description = "Logged in; redirect into the interface"),
+        (status = 400, description = "Login failed or was not started in this browser"),
+        (status = 403, description = "User is in no group mapped to API scopes"),
+    ),
+    tag = "auth",
+)]
+pub(super) async fn oidc_callback(
+    State(state): State<Arc<ApiState>>,
+    headers: HeaderMap,
+    Query(query): Query<CallbackQuery>,
+) -> Response {
+    let (oidc, store) = match (get_oidc(&state), get_access_store(&state)) {
+        (Ok(oidc), Ok(store)) => (oidc, store),
+        (Err(response), _) | (_, Err(response)) => return response,
+    };
+
+    if let Some(error) = query.error {
+        let detail = query.error_description.unwrap_or_default();
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            format!("identity provider refused the login: {error} {detail}"),
+        );
+    }
+    let (Some(code), Some(login_state)) = (query.code, query.state) else {
+        return error_response(StatusCode::BAD_REQUEST, "missing code or state");
+    };
+    if cookie_value(&headers, STATE_COOKIE) != Some(login_state.as_str()) {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            "login was not started in this browser, start again",
+        );
+    }
+
+    let identity = match oidc.complete_login(&code, &login_state).await {
+        Ok(identity) => identity,
+        Err(error) => {
+            tracing::warn!(%error, "OIDC login failed");
+            return error_response(StatusCode::BAD_REQUEST, format!("login failed: {error:#}"));
+        }
+    };
+
+    let scopes = oidc.scopes_for_groups(&identity.groups);
+    if scopes.is_empty() {
+        tracing::info!(
+            subject = %identity.subject,
+            groups = ?identity.groups,
+            "OIDC login refused: no group is mapped to API scopes"
+        );
+        return error_response(
+            StatusCode::FORBIDDEN,
+            "your account is not in any group with access to this instance",
+        );
+    }
+
+    let created = match store
+        .create_session(
+            &identity.subject,
+            identity.email.as_deref(),
+            identity.name.as_deref(),
+            &identity.groups,
+            &scopes,
+            chrono::Utc::now() + oidc.session_ttl(),
+        )
+        .await
+    {
+        Ok(created) => created,
+        Err(error) => {
+            tracing::warn!(%error, "failed to create session");
+            return error_response(StatusCode::INTERNAL_SERVER_ERROR, error.to_string());
+        }
+    };
+    tracing::info!(user = %created.session.user(), ?scopes, "OIDC login");
+    audit(
+        &store,
+        &created.session,
+        "GET",
+        "/api/auth/oidc/callback",
+        StatusCode::SEE_OTHER.as_u16(),
+    )
+    .await;
+
+    let secure = oidc.secure_cookies();
+    let session_cookie = set_cookie(
+        SESSION_COOKIE,
+        &created.secret,
+        "/",
+        oidc.session_ttl().num_seconds(),
+        secure,
+    );
+    let clear_state = set_cookie(STATE_COOKIE, "", "/", 0, secure);
+    (
+        AppendHeaders([
+            (header::SET_COOKIE, session_cookie),
+            (header::SET_COOKIE, clear_state),
+        ]),
+        Redirect::to(&identity.return_to),
+    )
+        .into_response()
+}
+
+/// `POST /auth/logout` — end the current session. Requires `X-CSRF-Token`.
+#[utoipa::path(
+    post,
+    path = "/auth/logout",
+    responses(
+        (status = 200, body = LogoutResponse),
+        (status = 403, description = "Missing or invalid CSRF token"),
+    ),
+    tag = "auth",
+)]
+pub(super) async fn logout(State(state): State<Arc<ApiState>>, headers: HeaderMap) -> Response {
+    let store = match get_access_store(&state) {
+        Ok(store) => store,
+        Err(response) => return response,
+    };
+    let session = match current_session(&store, &headers).await {
+        Ok(session) => session,
+        Err(error) => {
+            tracing::warn!(%error, "session lookup failed");
+            return error_response(StatusCode::INTERNAL_SERVER_ERROR, error.to_string());
+        }
+    };
+
+    if let Some(session) = &session {
+        let presented = headers
+            .get(CSRF_HEADER)
+            .and_then(|value| value.to_str().ok());
+        if !session.csrf_matches(presented) {
+            return error_response(StatusCode::FORBIDDEN, "missing or invalid CSRF token");
+        }
+        if let Err(error) = store.delete_session(&session.id).await {
+            tracing::warn!(%error, "failed to delete session");
+            return error_response(StatusCode::INTERNAL_SERVER_ERROR, error.to_string());
+        }
+        tracing::info!(user = %session.user(), "OIDC logout");
+        audit(&store, session, "POST", "/api/auth/logout", 200).await;
+    }
+
+    let secure = state
+        .oidc
+        .as_ref()
+        .is_some_and(|oidc| oidc.secure_cookies());
+    (
+        AppendHeaders([(
+            header::SET_COOKIE,
+            set_cookie(SESSION_COOKIE, "", "/", 0, secure),
+        )]),
+        Json(LogoutResponse {
+            success: session.is_some(),
+        }),
+    )
+        .into_response()
+}
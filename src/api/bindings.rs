@@ -535,6 +535,13 @@ pub(super) async fn create_binding(
 
         let manager_guard = state.messaging_manager.read().await;
         if let Some(manager) = manager_guard.as_ref() {
+            let chat_commands =
+                {
+                    let runtime_configs = state.runtime_configs.load();
+                    crate::commands::agent_command_specs(runtime_configs.iter().map(
+                        |(agent_id, runtime_config)| (agent_id.as_str(), runtime_config.as_ref()),
+                    ))
+                };
             if let Some(token) = new_discord_token {
                 let discord_perms = {
                     let perms_guard = state.discord_permissions.read().await;
@@ -563,7 +570,8 @@ pub(super) async fn create_binding(
                     "discord",
                     &token,
                     discord_perms,
-                );
+                )
+                .with_commands(chat_commands.clone());
                 if let Err(error) = manager.register_and_start(adapter).await {
                     tracing::error!(%error, "failed to hot-start discord adapter");
                 }
@@ -607,6 +615,7 @@ pub(super) async fn create_binding(
                     slack_commands,
                 ) {
                     Ok(adapter) => {
+                        let adapter = adapter.with_commands(chat_commands.clone());
                         if let Err(error) = manager.register_and_start(adapter).await {
                             tracing::error!(%error, "failed to hot-start slack adapter");
                         }
@@ -633,7 +642,8 @@ pub(super) async fn create_binding(
                     "telegram",
                     &token,
                     telegram_perms,
-                );
+                )
+                .with_commands(chat_commands);
                 if let Err(error) = manager.register_and_start(adapter).await {
                     tracing::error!(%error, "failed to hot-start telegram adapter");
                 }
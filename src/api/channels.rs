@@ -780,6 +780,7 @@ pub(super) async fn inspect_prompt(
             None, // backfill_transcript — only set during channel initialization
             empty_to_none(working_memory),
             empty_to_none(channel_activity_map),
+            None, // participant_info — tracked per live channel session
         )
         .unwrap_or_default();
 
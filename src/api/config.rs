@@ -51,6 +51,8 @@ pub(super) struct CortexSection {
     maintenance_prune_threshold: f32,
     maintenance_min_age_days: i64,
     maintenance_merge_similarity_threshold: f32,
+    autonomy_enabled: bool,
+    autonomy_interval_secs: u64,
 }
 
 #[derive(Serialize, Debug, utoipa::ToSchema)]
@@ -207,6 +209,8 @@ pub(super) struct CortexUpdate {
     maintenance_prune_threshold: Option<f32>,
     maintenance_min_age_days: Option<i64>,
     maintenance_merge_similarity_threshold: Option<f32>,
+    autonomy_enabled: Option<bool>,
+    autonomy_interval_secs: Option<u64>,
 }
 
 #[derive(Deserialize, Debug, utoipa::ToSchema)]
@@ -338,6 +342,8 @@ pub(super) async fn get_agent_config(
             maintenance_prune_threshold: cortex.maintenance_prune_threshold,
             maintenance_min_age_days: cortex.maintenance_min_age_days,
             maintenance_merge_similarity_threshold: cortex.maintenance_merge_similarity_threshold,
+            autonomy_enabled: cortex.autonomy_enabled,
+            autonomy_interval_secs: cortex.autonomy_interval_secs,
         },
         warmup: WarmupSection {
             enabled: warmup.enabled,
@@ -769,6 +775,20 @@ fn update_cortex_table(
         validate_maintenance_unit_interval("maintenance_merge_similarity_threshold", v)?;
         table["maintenance_merge_similarity_threshold"] = toml_edit::value(v as f64);
     }
+    if let Some(v) = cortex.autonomy_enabled {
+        table["autonomy_enabled"] = toml_edit::value(v);
+    }
+    if let Some(v) = cortex.autonomy_interval_secs {
+        if v < 300 {
+            tracing::warn!(
+                autonomy_interval_secs = v,
+                "autonomy_interval_secs must be >= 300"
+            );
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        table["autonomy_interval_secs"] =
+            toml_edit::value(to_i64_from_u64("autonomy_interval_secs", v)?);
+    }
     Ok(())
 }
 
@@ -1124,6 +1144,8 @@ id = "main"
             maintenance_prune_threshold: None,
             maintenance_min_age_days: None,
             maintenance_merge_similarity_threshold: None,
+            autonomy_enabled: None,
+            autonomy_interval_secs: None,
         };
 
         let result = update_cortex_table(&mut doc, agent_idx, &update);
@@ -1148,6 +1170,8 @@ id = "main"
             maintenance_prune_threshold: None,
             maintenance_min_age_days: None,
             maintenance_merge_similarity_threshold: None,
+            autonomy_enabled: None,
+            autonomy_interval_secs: None,
         };
         assert_eq!(
             update_cortex_table(&mut doc, agent_idx, &overflow_u64_update),
@@ -1182,6 +1206,8 @@ id = "main"
             maintenance_prune_threshold: None,
             maintenance_min_age_days: None,
             maintenance_merge_similarity_threshold: None,
+            autonomy_enabled: None,
+            autonomy_interval_secs: None,
         };
         assert_eq!(
             update_cortex_table(&mut doc, agent_idx, &invalid_decay),
@@ -1203,6 +1229,8 @@ id = "main"
             maintenance_prune_threshold: None,
             maintenance_min_age_days: Some(-1),
             maintenance_merge_similarity_threshold: None,
+            autonomy_enabled: None,
+            autonomy_interval_secs: None,
         };
         assert_eq!(
             update_cortex_table(&mut doc, agent_idx, &invalid_min_age),
@@ -1224,6 +1252,8 @@ id = "main"
             maintenance_prune_threshold: None,
             maintenance_min_age_days: None,
             maintenance_merge_similarity_threshold: None,
+            autonomy_enabled: None,
+            autonomy_interval_secs: None,
         };
         assert_eq!(
             update_cortex_table(&mut doc, agent_idx, &invalid_interval),
@@ -1257,6 +1287,8 @@ id = "main"
             maintenance_prune_threshold: Some(0.17),
             maintenance_min_age_days: Some(15),
             maintenance_merge_similarity_threshold: Some(0.98),
+            autonomy_enabled: Some(false),
+            autonomy_interval_secs: Some(900),
         };
 
         update_cortex_table(&mut doc, agent_idx, &update).expect("failed to update cortex");
@@ -1308,6 +1340,8 @@ id = "main"
                 < 1e-6
         );
         assert_eq!(cortex["circuit_breaker_threshold"].as_integer(), Some(6));
+        assert_eq!(cortex["autonomy_enabled"].as_bool(), Some(false));
+        assert_eq!(cortex["autonomy_interval_secs"].as_integer(), Some(900));
     }
 
     #[test]
@@ -1336,6 +1370,8 @@ id = "main"
             maintenance_prune_threshold: None,
             maintenance_min_age_days: None,
             maintenance_merge_similarity_threshold: None,
+            autonomy_enabled: None,
+            autonomy_interval_secs: None,
         };
 
         update_cortex_table(&mut doc, agent_idx, &initial).expect("failed to apply initial update");
@@ -1355,6 +1391,8 @@ id = "main"
             maintenance_prune_threshold: None,
             maintenance_min_age_days: None,
             maintenance_merge_similarity_threshold: Some(0.85),
+            autonomy_enabled: None,
+            autonomy_interval_secs: None,
         };
 
         update_cortex_table(&mut doc, agent_idx, &second).expect("failed to apply partial update");
@@ -44,6 +44,8 @@ pub(super) struct CreateCronRequest {
     #[serde(default)]
     run_once: bool,
     #[serde(default)]
+    stateful: bool,
+    #[serde(default)]
     timeout_secs: Option<u64>,
 }
 
@@ -83,8 +85,11 @@ struct CronJobWithStats {
     delivery_target: String,
     enabled: bool,
     run_once: bool,
+    stateful: bool,
     active_hours: Option<(u8, u8)>,
     timeout_secs: Option<u64>,
+    /// Latest state document written back by a stateful job.
+    state: Option<crate::cron::CronJobState>,
     execution_success_count: u64,
     execution_failure_count: u64,
     delivery_success_count: u64,
@@ -147,6 +152,12 @@ pub(super) async fn list_cron_jobs(
             .await
             .unwrap_or_default();
 
+        let state = if config.stateful {
+            store.load_state(&config.id).await.unwrap_or_default()
+        } else {
+            None
+        };
+
         jobs.push(CronJobWithStats {
             id: config.id,
             prompt: config.prompt,
@@ -155,8 +166,10 @@ pub(super) async fn list_cron_jobs(
             delivery_target: config.delivery_target,
             enabled: config.enabled,
             run_once: config.run_once,
+            stateful: config.stateful,
             active_hours: config.active_hours,
             timeout_secs: config.timeout_secs,
+            state,
             execution_success_count: stats.execution_success_count,
             execution_failure_count: stats.execution_failure_count,
             delivery_success_count: stats.delivery_success_count,
@@ -378,6 +391,7 @@ pub(super) async fn create_or_update_cron(
         active_hours,
         enabled: request.enabled,
         run_once: request.run_once,
+        stateful: request.stateful,
         next_run_at: None,
         timeout_secs: request.timeout_secs,
     };
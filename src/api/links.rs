@@ -73,6 +73,8 @@ struct TopologyHuman {
     slack_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    access_role: Option<crate::config::AccessRole>,
 }
 
 #[derive(Debug, Serialize, utoipa::ToSchema)]
@@ -159,6 +161,7 @@ pub async fn topology(State(state): State<Arc<ApiState>>) -> impl IntoResponse {
             telegram_id: h.telegram_id.clone(),
             slack_id: h.slack_id.clone(),
             email: h.email.clone(),
+            access_role: h.access_role,
         })
         .collect();
 
@@ -775,6 +778,8 @@ pub struct CreateHumanRequest {
     pub telegram_id: Option<String>,
     pub slack_id: Option<String>,
     pub email: Option<String>,
+    /// Access role: `guest`, `member`, `admin` or `owner`. Empty clears it.
+    pub access_role: Option<String>,
 }
 
 #[derive(Debug, Deserialize, utoipa::ToSchema)]
@@ -787,6 +792,8 @@ pub struct UpdateHumanRequest {
     pub telegram_id: Option<String>,
     pub slack_id: Option<String>,
     pub email: Option<String>,
+    /// Access role: `guest`, `member`, `admin` or `owner`. Empty clears it.
+    pub access_role: Option<String>,
 }
 
 /// List all humans.
@@ -844,11 +851,24 @@ fn ensure_humans_in_doc(doc: &mut toml_edit::DocumentMut, humans: &[crate::confi
         if let Some(ref email) = human.email {
             table["email"] = toml_edit::value(email.as_str());
         }
+        if let Some(access_role) = human.access_role {
+            table["access_role"] = toml_edit::value(access_role.to_string());
+        }
         humans_array.push(table);
     }
     doc["humans"] = toml_edit::Item::ArrayOfTables(humans_array);
 }
 
+/// Parse an `access_role` request field. Empty means no role.
+fn parse_access_role(value: &str) -> Result<Option<crate::config::AccessRole>, StatusCode> {
+    if value.is_empty() {
+        return Ok(None);
+    }
+    serde_json::from_value(serde_json::Value::String(value.to_string()))
+        .map(Some)
+        .map_err(|_| StatusCode::BAD_REQUEST)
+}
+
 /// Create an org-level human.
 #[utoipa::path(
     post,
@@ -869,6 +889,10 @@ pub async fn create_human(
     if id.is_empty() {
         return Err(StatusCode::BAD_REQUEST);
     }
+    let access_role = match &request.access_role {
+        Some(value) => parse_access_role(value)?,
+        None => None,
+    };
 
     let existing = state.agent_humans.load();
     if existing.iter().any(|h| h.id == id) {
@@ -939,6 +963,9 @@ pub async fn create_human(
     {
         table["email"] = toml_edit::value(email.as_str());
     }
+    if let Some(access_role) = access_role {
+        table["access_role"] = toml_edit::value(access_role.to_string());
+    }
     humans_array.push(table);
 
     tokio::fs::write(&config_path, doc.to_string())
@@ -978,6 +1005,7 @@ pub async fn create_human(
         telegram_id: request.telegram_id.clone().filter(|s| !s.is_empty()),
         slack_id: request.slack_id.clone().filter(|s| !s.is_empty()),
         email: request.email.clone().filter(|s| !s.is_empty()),
+        access_role,
     };
     let mut humans = (**existing).clone();
     humans.push(new_human.clone());
@@ -1001,6 +1029,7 @@ pub async fn create_human(
     request_body = UpdateHumanRequest,
     responses(
         (status = 200, body = serde_json::Value),
+        (status = 400, description = "Invalid access role"),
         (status = 404, description = "Human not found"),
     ),
     tag = "links",
@@ -1073,6 +1102,9 @@ pub async fn update_human(
             Some(email.clone())
         };
     }
+    if let Some(access_role) = &request.access_role {
+        updated.access_role = parse_access_role(access_role)?;
+    }
 
     let config_path = state.config_path.read().await.clone();
     let content = tokio::fs::read_to_string(&config_path)
@@ -1130,6 +1162,11 @@ pub async fn update_human(
                 } else if request.email.is_some() {
                     table.remove("email");
                 }
+                if let Some(access_role) = updated.access_role {
+                    table["access_role"] = toml_edit::value(access_role.to_string());
+                } else if request.access_role.is_some() {
+                    table.remove("access_role");
+                }
                 break;
             }
         }
@@ -0,0 +1,502 @@
+//! Live log viewer: in-memory tracing ring buffer and the endpoints that
+//! expose it.
+//!
+//! `LogBufferLayer` is composed into the tracing registry at startup and keeps
+//! the most recent info/warn/error events in a bounded ring. Every captured
+//! entry is passed through `scrub_leaks` before it is stored, so neither the
+//! history endpoint nor the SSE stream can echo a credential that slipped into
+//! a log line.
+
+use super::state::ApiState;
+
+use crate::secrets::scrub::scrub_leaks;
+
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::response::Sse;
+use futures::stream::Stream;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex};
+
+/// Number of entries kept in the ring buffer.
+const LOG_BUFFER_CAPACITY: usize = 1000;
+
+/// Capacity of the broadcast channel feeding SSE subscribers.
+const LOG_STREAM_CAPACITY: usize = 256;
+
+/// Longest message stored per entry. Anything beyond is truncated.
+const MAX_MESSAGE_CHARS: usize = 4096;
+
+/// A single captured tracing event.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct LogEntry {
+    /// RFC 3339 timestamp.
+    pub timestamp: String,
+    /// "INFO", "WARN" or "ERROR".
+    pub level: String,
+    /// Tracing target (module path).
+    pub target: String,
+    /// Formatted message, followed by the event's other fields as `key=value`.
+    pub message: String,
+    /// Agent the event belongs to, taken from an `agent_id` field on the
+    /// event or any enclosing span.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent_id: Option<String>,
+}
+
+/// Shared handle to the ring buffer. Cheap to clone.
+#[derive(Clone)]
+pub struct LogBuffer {
+    entries: Arc<Mutex<VecDeque<LogEntry>>>,
+    capacity: usize,
+    tx: broadcast::Sender<LogEntry>,
+}
+
+impl std::fmt::Debug for LogBuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LogBuffer").finish_non_exhaustive()
+    }
+}
+
+impl Default for LogBuffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LogBuffer {
+    pub fn new() -> Self {
+        Self::with_capacity(LOG_BUFFER_CAPACITY)
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        let (tx, _) = broadcast::channel(LOG_STREAM_CAPACITY);
+        Self {
+            entries: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity,
+            tx,
+        }
+    }
+
+    /// Build the tracing layer that feeds this buffer.
+    pub fn layer(&self) -> LogBufferLayer {
+        LogBufferLayer {
+            buffer: self.clone(),
+        }
+    }
+
+    /// The most recent entries matching `filter`, oldest first.
+    pub fn snapshot(&self, limit: usize, filter: &LogFilter) -> Vec<LogEntry> {
+        let entries = self.entries.lock().expect("log buffer mutex poisoned");
+        let mut matching = entries
+            .iter()
+            .rev()
+            .filter(|entry| filter.matches(entry))
+            .take(limit)
+            .cloned()
+            .collect::<Vec<_>>();
+        matching.reverse();
+        matching
+    }
+
+    /// Receive every entry captured from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<LogEntry> {
+        self.tx.subscribe()
+    }
+
+    fn push(&self, entry: LogEntry) {
+        {
+            let mut entries = self.entries.lock().expect("log buffer mutex poisoned");
+            if entries.len() >= self.capacity {
+                entries.pop_front();
+            }
+            entries.push_back(entry.clone());
+        }
+        // No subscribers is the normal case when nobody has the page open.
+        let _ = self.tx.send(entry);
+    }
+}
+
+/// `tracing_subscriber` layer that records info/warn/error events into a
+/// [`LogBuffer`]. Debug and trace events are never captured, even when the
+/// instance runs with `--debug`.
+pub struct LogBufferLayer {
+    buffer: LogBuffer,
+}
+
+/// `agent_id` recorded on a span, stored in the span's extensions.
+struct SpanAgent(String);
+
+impl<S> Layer<S> for LogBufferLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut visitor = AgentVisitor::default();
+        attrs.record(&mut visitor);
+        if let (Some(agent_id), Some(span)) = (visitor.agent_id, ctx.span(id)) {
+            span.extensions_mut().insert(SpanAgent(agent_id));
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let mut visitor = AgentVisitor::default();
+        values.record(&mut visitor);
+        if let (Some(agent_id), Some(span)) = (visitor.agent_id, ctx.span(id)) {
+            span.extensions_mut().replace(SpanAgent(agent_id));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        if *metadata.level() > Level::INFO {
+            return;
+        }
+
+        let mut visitor = EventVisitor::default();
+        event.record(&mut visitor);
+
+        let agent_id = visitor.agent_id.clone().or_else(|| {
+            ctx.event_scope(event).and_then(|scope| {
+                scope
+                    .filter_map(|span| {
+                        span.extensions()
+                            .get::<SpanAgent>()
+                            .map(|agent| agent.0.clone())
+                    })
+                    .next()
+            })
+        });
+
+        let message = scrub_leaks(&visitor.into_message());
+        let message = match message.char_indices().nth(MAX_MESSAGE_CHARS) {
+            Some((index, _)) => format!("{}...", &message[..index]),
+            None => message,
+        };
+
+        self.buffer.push(LogEntry {
+            timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            level: metadata.level().as_str().to_string(),
+            target: metadata.target().to_string(),
+            message,
+            agent_id,
+        });
+    }
+}
+
+/// Pulls `agent_id` out of span attributes.
+#[derive(Default)]
+struct AgentVisitor {
+    agent_id: Option<String>,
+}
+
+impl Visit for AgentVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "agent_id" {
+            self.agent_id = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "agent_id" {
+            self.agent_id = Some(format!("{value:?}"));
+        }
+    }
+}
+
+/// Formats an event into a message plus trailing `key=value` fields.
+#[derive(Default)]
+struct EventVisitor {
+    message: String,
+    fields: String,
+    agent_id: Option<String>,
+}
+
+impl EventVisitor {
+    fn into_message(self) -> String {
+        match (self.message.is_empty(), self.fields.is_empty()) {
+            (_, true) => self.message,
+            (true, false) => self.fields,
+            (false, false) => format!("{} {}", self.message, self.fields),
+        }
+    }
+
+    fn push_field(&mut self, name: &str, value: std::fmt::Arguments<'_>) {
+        if !self.fields.is_empty() {
+            self.fields.push(' ');
+        }
+        let _ = write!(self.fields, "{name}={value}");
+    }
+}
+
+impl Visit for EventVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "message" => self.message = value.to_string(),
+            name => {
+                if name == "agent_id" {
+                    self.agent_id = Some(value.to_string());
+                }
+                self.push_field(name, format_args!("{value}"));
+            }
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        match field.name() {
+            "message" => self.message = format!("{value:?}"),
+            // Prompt and tool payloads are skipped, same as the fmt layer.
+            "gen_ai.system_instructions"
+            | "gen_ai.tool.call.arguments"
+            | "gen_ai.tool.call.result" => {}
+            name => {
+                if name == "agent_id" {
+                    self.agent_id = Some(format!("{value:?}"));
+                }
+                self.push_field(name, format_args!("{value:?}"));
+            }
+        }
+    }
+}
+
+/// Server-side filter shared by the history and stream endpoints.
+#[derive(Debug, Default, Deserialize, utoipa::ToSchema, utoipa::IntoParams)]
+pub struct LogFilter {
+    /// Minimum level to include: "info", "warn" or "error".
+    level: Option<String>,
+    /// Only include entries whose target contains this string.
+    target: Option<String>,
+    /// Only include entries attributed to this agent.
+    agent_id: Option<String>,
+}
+
+impl LogFilter {
+    fn min_level(&self) -> Level {
+        self.level
+            .as_deref()
+            .and_then(|level| level.parse::<Level>().ok())
+            .unwrap_or(Level::INFO)
+    }
+
+    fn matches(&self, entry: &LogEntry) -> bool {
+        let level_ok = entry
+            .level
+            .parse::<Level>()
+            .is_ok_and(|level| level <= self.min_level());
+        let target_ok = self
+            .target
+            .as_deref()
+            .is_none_or(|target| entry.target.contains(target));
+        let agent_ok = self
+            .agent_id
+            .as_deref()
+            .is_none_or(|agent_id| entry.agent_id.as_deref() == Some(agent_id));
+        level_ok && target_ok && agent_ok
+    }
+}
+
+#[derive(Deserialize, utoipa::ToSchema, utoipa::IntoParams)]
+pub(super) struct LogHistoryQuery {
+    #[serde(default = "default_limit")]
+    limit: usize,
+}
+
+fn default_limit() -> usize {
+    LOG_BUFFER_CAPACITY
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct LogHistoryResponse {
+    entries: Vec<LogEntry>,
+}
+
+/// Recent log entries from the in-memory buffer, oldest first.
+#[utoipa::path(
+    get,
+    path = "/logs",
+    params(
+        ("limit" = Option<usize>, Query, description = "Maximum number of entries to return (default 1000)"),
+        ("level" = Option<String>, Query, description = "Minimum level: info, warn or error"),
+        ("target" = Option<String>, Query, description = "Substring the tracing target must contain"),
+        ("agent_id" = Option<String>, Query, description = "Only entries attributed to this agent"),
+    ),
+    responses(
+        (status = 200, body = LogHistoryResponse),
+    ),
+    tag = "system",
+)]
+pub(super) async fn list_logs(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<LogHistoryQuery>,
+    Query(filter): Query<LogFilter>,
+) -> Json<LogHistoryResponse> {
+    let entries = state
+        .log_buffer
+        .as_ref()
+        .map(|buffer| buffer.snapshot(query.limit.min(LOG_BUFFER_CAPACITY), &filter))
+        .unwrap_or_default();
+    Json(LogHistoryResponse { entries })
+}
+
+/// SSE endpoint streaming new log entries as they are captured.
+#[utoipa::path(
+    get,
+    path = "/logs/stream",
+    params(
+        ("level" = Option<String>, Query, description = "Minimum level: info, warn or error"),
+        ("target" = Option<String>, Query, description = "Substring the tracing target must contain"),
+        ("agent_id" = Option<String>, Query, description = "Only entries attributed to this agent"),
+    ),
+    responses(
+        (status = 200, description = "SSE log stream", content_type = "text/event-stream"),
+    ),
+    tag = "system",
+)]
+pub(super) async fn logs_sse(
+    State(state): State<Arc<ApiState>>,
+    Query(filter): Query<LogFilter>,
+) -> Sse<impl Stream<Item = Result<axum::response::sse::Event, Infallible>>> {
+    let mut rx = state.log_buffer.as_ref().map(LogBuffer::subscribe);
+
+    let stream = async_stream::stream! {
+        let Some(rx) = rx.as_mut() else {
+            // No buffer installed: keep the connection open on keep-alives only.
+            std::future::pending::<()>().await;
+            return;
+        };
+        loop {
+            match crate::classify_broadcast_recv_result(rx.recv().await) {
+                crate::BroadcastRecvResult::Event(entry) => {
+                    if !filter.matches(&entry) {
+                        continue;
+                    }
+                    if let Ok(json) = serde_json::to_string(&entry) {
+                        yield Ok(axum::response::sse::Event::default()
+                            .event("log")
+                            .data(json));
+                    }
+                }
+                crate::BroadcastRecvResult::Lagged(count) => {
+                    yield Ok(axum::response::sse::Event::default()
+                        .event("lagged")
+                        .data(format!("{{\"skipped\":{count}}}")));
+                }
+                crate::BroadcastRecvResult::Closed => break,
+            }
+        }
+    };
+
+    Sse::new(stream).keep_alive(
+        axum::response::sse::KeepAlive::new()
+            .interval(std::time::Duration::from_secs(15))
+            .text("ping"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt as _;
+
+    fn capture(buffer: &LogBuffer, f: impl FnOnce()) {
+        let subscriber = tracing_subscriber::registry().with(buffer.layer());
+        tracing::subscriber::with_default(subscriber, f);
+    }
+
+    #[test]
+    fn captures_info_and_above_with_fields() {
+        let buffer = LogBuffer::new();
+        capture(&buffer, || {
+            tracing::debug!("too noisy");
+            tracing::info!(worker_id = 7, "worker started");
+            tracing::error!("boom");
+        });
+
+        let entries = buffer.snapshot(10, &LogFilter::default());
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].level, "INFO");
+        assert_eq!(entries[0].message, "worker started worker_id=7");
+        assert_eq!(entries[1].level, "ERROR");
+    }
+
+    #[test]
+    fn ring_buffer_drops_oldest() {
+        let buffer = LogBuffer::with_capacity(3);
+        capture(&buffer, || {
+            for index in 0..5 {
+                tracing::info!("entry {index}");
+            }
+        });
+
+        let messages = buffer
+            .snapshot(10, &LogFilter::default())
+            .into_iter()
+            .map(|entry| entry.message)
+            .collect::<Vec<_>>();
+        assert_eq!(messages, ["entry 2", "entry 3", "entry 4"]);
+    }
+
+    #[test]
+    fn scrubs_leaked_secrets() {
+        let buffer = LogBuffer::new();
+        capture(&buffer, || {
+            tracing::warn!(
+                "request failed with key sk-ant-REDACTED"
+            );
+        });
+
+        let entries = buffer.snapshot(10, &LogFilter::default());
+        assert!(!entries[0].message.contains("sk-ant-api03"));
+        assert!(entries[0].message.contains("[LEAKED_SECRET_REDACTED]"));
+    }
+
+    #[test]
+    fn filters_by_level_target_and_agent() {
+        let buffer = LogBuffer::new();
+        capture(&buffer, || {
+            let span = tracing::info_span!("channel", agent_id = "alpha");
+            span.in_scope(|| tracing::info!("inside span"));
+            tracing::warn!(agent_id = "beta", "direct field");
+            tracing::error!(target: "spacebot::llm", "model error");
+        });
+
+        let alpha = LogFilter {
+            agent_id: Some("alpha".into()),
+            ..Default::default()
+        };
+        let entries = buffer.snapshot(10, &alpha);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].message, "inside span");
+
+        let beta = LogFilter {
+            agent_id: Some("beta".into()),
+            ..Default::default()
+        };
+        assert_eq!(buffer.snapshot(10, &beta).len(), 1);
+
+        let warn = LogFilter {
+            level: Some("warn".into()),
+            ..Default::default()
+        };
+        assert_eq!(buffer.snapshot(10, &warn).len(), 2);
+
+        let llm = LogFilter {
+            target: Some("llm".into()),
+            ..Default::default()
+        };
+        let entries = buffer.snapshot(10, &llm);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].target, "spacebot::llm");
+    }
+}
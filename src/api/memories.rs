@@ -44,6 +44,8 @@ pub(super) struct MemoriesListQuery {
     memory_type: Option<String>,
     #[serde(default = "default_memories_sort")]
     sort: String,
+    #[serde(default)]
+    user_id: Option<String>,
 }
 
 fn default_memories_limit() -> i64 {
@@ -84,6 +86,8 @@ pub(super) struct MemoriesSearchQuery {
     limit: usize,
     #[serde(default)]
     memory_type: Option<String>,
+    #[serde(default)]
+    user_id: Option<String>,
 }
 
 fn default_search_limit() -> usize {
@@ -132,6 +136,7 @@ fn default_neighbor_depth() -> u32 {
         ("offset" = usize, Query, description = "Number of results to skip for pagination"),
         ("memory_type" = Option<String>, Query, description = "Filter by memory type (fact, preference, decision, identity, event, observation, goal, todo)"),
         ("sort" = String, Query, description = "Sort order: recent, importance, most_accessed (default: recent)"),
+        ("user_id" = Option<String>, Query, description = "Only return this user's memories plus global memories"),
     ),
     responses(
         (status = 200, body = MemoriesListResponse),
@@ -154,7 +159,7 @@ pub(super) async fn list_memories(
 
     let fetch_limit = limit + query.offset as i64;
     let all = store
-        .get_sorted(sort, fetch_limit, memory_type)
+        .get_sorted(sort, fetch_limit, memory_type, query.user_id.as_deref())
         .await
         .map_err(|error| {
             tracing::warn!(%error, agent_id = %query.agent_id, "failed to list memories");
@@ -176,6 +181,7 @@ pub(super) async fn list_memories(
         ("q" = String, Query, description = "Search query string"),
         ("limit" = usize, Query, description = "Maximum number of results to return (default 20, max 100)"),
         ("memory_type" = Option<String>, Query, description = "Filter by memory type"),
+        ("user_id" = Option<String>, Query, description = "Only return this user's memories plus global memories"),
     ),
     responses(
         (status = 200, body = MemoriesSearchResponse),
@@ -195,6 +201,7 @@ pub(super) async fn search_memories(
         mode: SearchMode::Hybrid,
         memory_type: query.memory_type.as_deref().and_then(parse_memory_type),
         max_results: query.limit.min(100),
+        user_id: query.user_id.clone(),
         ..SearchConfig::default()
     };
 
@@ -240,7 +247,7 @@ pub(super) async fn memory_graph(
 
     let fetch_limit = limit + query.offset as i64;
     let all = store
-        .get_sorted(sort, fetch_limit, memory_type)
+        .get_sorted(sort, fetch_limit, memory_type, None)
         .await
         .map_err(|error| {
             tracing::warn!(%error, agent_id = %query.agent_id, "failed to load graph nodes");
@@ -24,6 +24,8 @@ pub(super) struct AdapterInstanceStatus {
     configured: bool,
     enabled: bool,
     binding_count: usize,
+    /// Live connection state from the running adapter, when it reports one.
+    health: Option<crate::messaging::traits::AdapterHealth>,
 }
 
 #[derive(Serialize, utoipa::ToSchema)]
@@ -35,6 +37,9 @@ pub(super) struct MessagingStatusResponse {
     webhook: PlatformStatus,
     twitch: PlatformStatus,
     mattermost: PlatformStatus,
+    matrix: PlatformStatus,
+    irc: PlatformStatus,
+    teams: PlatformStatus,
     signal: PlatformStatus,
     instances: Vec<AdapterInstanceStatus>,
 }
@@ -105,6 +110,36 @@ pub(super) struct InstanceCredentials {
     mattermost_base_url: Option<String>,
     #[serde(default)]
     mattermost_token: Option<String>,
+    // Matrix credentials
+    #[serde(default)]
+    matrix_homeserver_url: Option<String>,
+    #[serde(default)]
+    matrix_access_token: Option<String>,
+    // IRC credentials
+    #[serde(default)]
+    irc_server: Option<String>,
+    #[serde(default)]
+    irc_port: Option<u16>,
+    #[serde(default)]
+    irc_tls: Option<bool>,
+    #[serde(default)]
+    irc_nickname: Option<String>,
+    #[serde(default)]
+    irc_sasl_password: Option<String>,
+    /// Comma-separated channel list (e.g. `#rust,#spacebot`).
+    #[serde(default)]
+    irc_channels: Option<String>,
+    // Teams credentials
+    #[serde(default)]
+    teams_app_id: Option<String>,
+    #[serde(default)]
+    teams_app_password: Option<String>,
+    #[serde(default)]
+    teams_tenant_id: Option<String>,
+    #[serde(default)]
+    teams_bind: Option<String>,
+    #[serde(default)]
+    teams_port: Option<u16>,
     // Signal credentials
     #[serde(default)]
     signal_http_url: Option<String>,
@@ -194,9 +229,82 @@ fn push_instance_status(
         configured,
         enabled,
         binding_count,
+        health: None,
     });
 }
 
+/// Write IRC connection credentials into a platform or instance table.
+/// Only fields present in the request are written.
+fn write_irc_credentials(
+    credentials: &InstanceCredentials,
+    table: &mut toml_edit::Table,
+) -> Result<(), MessagingInstanceActionResponse> {
+    if let Some(server) = &credentials.irc_server {
+        let server = server.trim();
+        if server.is_empty() || server.contains(['/', ':', ' ']) {
+            return Err(MessagingInstanceActionResponse {
+                success: false,
+                message: "invalid irc server: must be a hostname (e.g. irc.libera.chat)"
+                    .to_string(),
+            });
+        }
+        table["server"] = toml_edit::value(server);
+    }
+    if let Some(port) = credentials.irc_port {
+        table["port"] = toml_edit::value(i64::from(port));
+    }
+    if let Some(tls) = credentials.irc_tls {
+        table["tls"] = toml_edit::value(tls);
+    }
+    if let Some(nickname) = &credentials.irc_nickname {
+        table["nickname"] = toml_edit::value(nickname.trim());
+    }
+    if let Some(password) = &credentials.irc_sasl_password
+        && !password.is_empty()
+    {
+        table["sasl_password"] = toml_edit::value(password.as_str());
+    }
+    if let Some(channels) = &credentials.irc_channels {
+        let mut array = toml_edit::Array::new();
+        for channel in channels
+            .split(',')
+            .map(str::trim)
+            .filter(|channel| !channel.is_empty())
+        {
+            array.push(channel);
+        }
+        table["channels"] = toml_edit::value(array);
+    }
+    Ok(())
+}
+
+/// Write Teams Bot Framework credentials into a platform or instance table.
+/// Only fields present in the request are written.
+fn write_teams_credentials(credentials: &InstanceCredentials, table: &mut toml_edit::Table) {
+    if let Some(app_id) = &credentials.teams_app_id {
+        table["app_id"] = toml_edit::value(app_id.trim());
+    }
+    if let Some(password) = &credentials.teams_app_password
+        && !password.is_empty()
+    {
+        table["app_password"] = toml_edit::value(password.as_str());
+    }
+    if let Some(tenant_id) = &credentials.teams_tenant_id {
+        let tenant_id = tenant_id.trim();
+        if tenant_id.is_empty() {
+            table.remove("tenant_id");
+        } else {
+            table["tenant_id"] = toml_edit::value(tenant_id);
+        }
+    }
+    if let Some(bind) = &credentials.teams_bind {
+        table["bind"] = toml_edit::value(bind.trim());
+    }
+    if let Some(port) = credentials.teams_port {
+        table["port"] = toml_edit::value(i64::from(port));
+    }
+}
+
 /// Merge incoming Signal credentials with existing TOML values for patch-style updates.
 /// Fields omitted from the request are filled from the existing platform table,
 /// so callers can update individual fields without resubmitting every credential.
@@ -363,541 +471,714 @@ pub(super) async fn messaging_status(
 ) -> Result<Json<MessagingStatusResponse>, StatusCode> {
     let config_path = state.config_path.read().await.clone();
 
-    let (discord, slack, telegram, email, webhook, twitch, mattermost, signal, instances) =
-        if config_path.exists() {
-            let content = tokio::fs::read_to_string(&config_path)
-                .await
-                .map_err(|error| {
-                    tracing::warn!(%error, "failed to read config.toml for messaging status");
-                    StatusCode::INTERNAL_SERVER_ERROR
-                })?;
-            let doc: toml_edit::DocumentMut = content.parse().map_err(|error| {
-                tracing::warn!(%error, "failed to parse config.toml for messaging status");
+    let (
+        discord,
+        slack,
+        telegram,
+        email,
+        webhook,
+        twitch,
+        mattermost,
+        matrix,
+        irc,
+        teams,
+        signal,
+        mut instances,
+    ) = if config_path.exists() {
+        let content = tokio::fs::read_to_string(&config_path)
+            .await
+            .map_err(|error| {
+                tracing::warn!(%error, "failed to read config.toml for messaging status");
                 StatusCode::INTERNAL_SERVER_ERROR
             })?;
+        let doc: toml_edit::DocumentMut = content.parse().map_err(|error| {
+            tracing::warn!(%error, "failed to parse config.toml for messaging status");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
 
-            let mut instances: Vec<AdapterInstanceStatus> = Vec::new();
-            let bindings = doc
-                .get("bindings")
-                .and_then(|value| value.as_array_of_tables());
-
-            let discord_status = doc
-                .get("messaging")
-                .and_then(|m| m.get("discord"))
-                .map(|d| {
-                    let has_token = d
-                        .get("token")
-                        .and_then(|v| v.as_str())
-                        .is_some_and(|s| !s.is_empty());
-                    let enabled = d.get("enabled").and_then(|v| v.as_bool()).unwrap_or(false);
-
-                    if has_token {
-                        push_instance_status(
-                            &mut instances,
-                            bindings,
-                            "discord",
-                            None,
-                            true,
-                            enabled,
-                        );
-                    }
+        let mut instances: Vec<AdapterInstanceStatus> = Vec::new();
+        let bindings = doc
+            .get("bindings")
+            .and_then(|value| value.as_array_of_tables());
+
+        let discord_status = doc
+            .get("messaging")
+            .and_then(|m| m.get("discord"))
+            .map(|d| {
+                let has_token = d
+                    .get("token")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|s| !s.is_empty());
+                let enabled = d.get("enabled").and_then(|v| v.as_bool()).unwrap_or(false);
 
-                    if let Some(named_instances) = d
-                        .get("instances")
-                        .and_then(|value| value.as_array_of_tables())
-                    {
-                        for instance in named_instances {
-                            let instance_name = normalize_adapter_selector(
-                                instance.get("name").and_then(|value| value.as_str()),
+                if has_token {
+                    push_instance_status(&mut instances, bindings, "discord", None, true, enabled);
+                }
+
+                if let Some(named_instances) = d
+                    .get("instances")
+                    .and_then(|value| value.as_array_of_tables())
+                {
+                    for instance in named_instances {
+                        let instance_name = normalize_adapter_selector(
+                            instance.get("name").and_then(|value| value.as_str()),
+                        );
+                        let instance_enabled = instance
+                            .get("enabled")
+                            .and_then(|value| value.as_bool())
+                            .unwrap_or(true)
+                            && enabled;
+                        let instance_configured = instance
+                            .get("token")
+                            .and_then(|value| value.as_str())
+                            .is_some_and(|token| !token.is_empty());
+
+                        if let Some(instance_name) = instance_name
+                            && instance_configured
+                        {
+                            push_instance_status(
+                                &mut instances,
+                                bindings,
+                                "discord",
+                                Some(instance_name),
+                                true,
+                                instance_enabled,
                             );
-                            let instance_enabled = instance
-                                .get("enabled")
-                                .and_then(|value| value.as_bool())
-                                .unwrap_or(true)
-                                && enabled;
-                            let instance_configured = instance
-                                .get("token")
-                                .and_then(|value| value.as_str())
-                                .is_some_and(|token| !token.is_empty());
-
-                            if let Some(instance_name) = instance_name
-                                && instance_configured
-                            {
-                                push_instance_status(
-                                    &mut instances,
-                                    bindings,
-                                    "discord",
-                                    Some(instance_name),
-                                    true,
-                                    instance_enabled,
-                                );
-                            }
                         }
                     }
+                }
 
-                    PlatformStatus {
-                        configured: has_token,
-                        enabled: has_token && enabled,
-                    }
-                })
-                .unwrap_or(PlatformStatus {
-                    configured: false,
-                    enabled: false,
-                });
+                PlatformStatus {
+                    configured: has_token,
+                    enabled: has_token && enabled,
+                }
+            })
+            .unwrap_or(PlatformStatus {
+                configured: false,
+                enabled: false,
+            });
+
+        let slack_status = doc
+            .get("messaging")
+            .and_then(|m| m.get("slack"))
+            .map(|s| {
+                let has_bot_token = s
+                    .get("bot_token")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|t| !t.is_empty());
+                let has_app_token = s
+                    .get("app_token")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|t| !t.is_empty());
+                let enabled = s.get("enabled").and_then(|v| v.as_bool()).unwrap_or(false);
+
+                if has_bot_token && has_app_token {
+                    push_instance_status(&mut instances, bindings, "slack", None, true, enabled);
+                }
 
-            let slack_status = doc
-                .get("messaging")
-                .and_then(|m| m.get("slack"))
-                .map(|s| {
-                    let has_bot_token = s
-                        .get("bot_token")
-                        .and_then(|v| v.as_str())
-                        .is_some_and(|t| !t.is_empty());
-                    let has_app_token = s
-                        .get("app_token")
-                        .and_then(|v| v.as_str())
-                        .is_some_and(|t| !t.is_empty());
-                    let enabled = s.get("enabled").and_then(|v| v.as_bool()).unwrap_or(false);
-
-                    if has_bot_token && has_app_token {
-                        push_instance_status(
-                            &mut instances,
-                            bindings,
-                            "slack",
-                            None,
-                            true,
-                            enabled,
+                if let Some(named_instances) = s
+                    .get("instances")
+                    .and_then(|value| value.as_array_of_tables())
+                {
+                    for instance in named_instances {
+                        let instance_name = normalize_adapter_selector(
+                            instance.get("name").and_then(|value| value.as_str()),
                         );
-                    }
+                        let has_instance_bot = instance
+                            .get("bot_token")
+                            .and_then(|value| value.as_str())
+                            .is_some_and(|value| !value.is_empty());
+                        let has_instance_app = instance
+                            .get("app_token")
+                            .and_then(|value| value.as_str())
+                            .is_some_and(|value| !value.is_empty());
+                        let instance_enabled = instance
+                            .get("enabled")
+                            .and_then(|value| value.as_bool())
+                            .unwrap_or(true)
+                            && enabled;
 
-                    if let Some(named_instances) = s
-                        .get("instances")
-                        .and_then(|value| value.as_array_of_tables())
-                    {
-                        for instance in named_instances {
-                            let instance_name = normalize_adapter_selector(
-                                instance.get("name").and_then(|value| value.as_str()),
+                        if let Some(instance_name) = instance_name
+                            && has_instance_bot
+                            && has_instance_app
+                        {
+                            push_instance_status(
+                                &mut instances,
+                                bindings,
+                                "slack",
+                                Some(instance_name),
+                                true,
+                                instance_enabled,
                             );
-                            let has_instance_bot = instance
-                                .get("bot_token")
-                                .and_then(|value| value.as_str())
-                                .is_some_and(|value| !value.is_empty());
-                            let has_instance_app = instance
-                                .get("app_token")
-                                .and_then(|value| value.as_str())
-                                .is_some_and(|value| !value.is_empty());
-                            let instance_enabled = instance
-                                .get("enabled")
-                                .and_then(|value| value.as_bool())
-                                .unwrap_or(true)
-                                && enabled;
-
-                            if let Some(instance_name) = instance_name
-                                && has_instance_bot
-                                && has_instance_app
-                            {
-                                push_instance_status(
-                                    &mut instances,
-                                    bindings,
-                                    "slack",
-                                    Some(instance_name),
-                                    true,
-                                    instance_enabled,
-                                );
-                            }
                         }
                     }
+                }
 
-                    PlatformStatus {
-                        configured: has_bot_token && has_app_token,
-                        enabled: has_bot_token && has_app_token && enabled,
-                    }
-                })
-                .unwrap_or(PlatformStatus {
-                    configured: false,
-                    enabled: false,
-                });
+                PlatformStatus {
+                    configured: has_bot_token && has_app_token,
+                    enabled: has_bot_token && has_app_token && enabled,
+                }
+            })
+            .unwrap_or(PlatformStatus {
+                configured: false,
+                enabled: false,
+            });
 
-            let webhook_status = doc
-                .get("messaging")
-                .and_then(|m| m.get("webhook"))
-                .map(|w| {
-                    let enabled = w.get("enabled").and_then(|v| v.as_bool()).unwrap_or(false);
+        let webhook_status = doc
+            .get("messaging")
+            .and_then(|m| m.get("webhook"))
+            .map(|w| {
+                let enabled = w.get("enabled").and_then(|v| v.as_bool()).unwrap_or(false);
 
-                    push_instance_status(&mut instances, bindings, "webhook", None, true, enabled);
+                push_instance_status(&mut instances, bindings, "webhook", None, true, enabled);
 
-                    PlatformStatus {
-                        configured: true,
-                        enabled,
-                    }
-                })
-                .unwrap_or(PlatformStatus {
-                    configured: false,
-                    enabled: false,
-                });
+                PlatformStatus {
+                    configured: true,
+                    enabled,
+                }
+            })
+            .unwrap_or(PlatformStatus {
+                configured: false,
+                enabled: false,
+            });
 
-            let email_status = doc
-                .get("messaging")
-                .and_then(|m| m.get("email"))
-                .map(|email| {
-                    let has_imap_host = email
-                        .get("imap_host")
-                        .and_then(|v| v.as_str())
-                        .is_some_and(|s| !s.is_empty());
-                    let has_imap_username = email
-                        .get("imap_username")
-                        .and_then(|v| v.as_str())
-                        .is_some_and(|s| !s.is_empty());
-                    let has_imap_password = email
-                        .get("imap_password")
-                        .and_then(|v| v.as_str())
-                        .is_some_and(|s| !s.is_empty());
-                    let has_smtp_host = email
-                        .get("smtp_host")
-                        .and_then(|v| v.as_str())
-                        .is_some_and(|s| !s.is_empty());
-
-                    let configured =
-                        has_imap_host && has_imap_username && has_imap_password && has_smtp_host;
-
-                    let enabled = email
-                        .get("enabled")
-                        .and_then(|v| v.as_bool())
-                        .unwrap_or(false);
-
-                    if configured {
-                        push_instance_status(
-                            &mut instances,
-                            bindings,
-                            "email",
-                            None,
-                            true,
-                            enabled,
+        let email_status = doc
+            .get("messaging")
+            .and_then(|m| m.get("email"))
+            .map(|email| {
+                let has_imap_host = email
+                    .get("imap_host")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|s| !s.is_empty());
+                let has_imap_username = email
+                    .get("imap_username")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|s| !s.is_empty());
+                let has_imap_password = email
+                    .get("imap_password")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|s| !s.is_empty());
+                let has_smtp_host = email
+                    .get("smtp_host")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|s| !s.is_empty());
+
+                let configured =
+                    has_imap_host && has_imap_username && has_imap_password && has_smtp_host;
+
+                let enabled = email
+                    .get("enabled")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
+
+                if configured {
+                    push_instance_status(&mut instances, bindings, "email", None, true, enabled);
+                }
+
+                PlatformStatus {
+                    configured,
+                    enabled: configured && enabled,
+                }
+            })
+            .unwrap_or(PlatformStatus {
+                configured: false,
+                enabled: false,
+            });
+
+        let telegram_status = doc
+            .get("messaging")
+            .and_then(|m| m.get("telegram"))
+            .map(|t| {
+                let has_token = t
+                    .get("token")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|s| !s.is_empty());
+                let enabled = t.get("enabled").and_then(|v| v.as_bool()).unwrap_or(false);
+
+                if has_token {
+                    push_instance_status(&mut instances, bindings, "telegram", None, true, enabled);
+                }
+
+                if let Some(named_instances) = t
+                    .get("instances")
+                    .and_then(|value| value.as_array_of_tables())
+                {
+                    for instance in named_instances {
+                        let instance_name = normalize_adapter_selector(
+                            instance.get("name").and_then(|value| value.as_str()),
                         );
+                        let instance_enabled = instance
+                            .get("enabled")
+                            .and_then(|value| value.as_bool())
+                            .unwrap_or(true)
+                            && enabled;
+                        let instance_configured = instance
+                            .get("token")
+                            .and_then(|value| value.as_str())
+                            .is_some_and(|value| !value.is_empty());
+
+                        if let Some(instance_name) = instance_name
+                            && instance_configured
+                        {
+                            push_instance_status(
+                                &mut instances,
+                                bindings,
+                                "telegram",
+                                Some(instance_name),
+                                true,
+                                instance_enabled,
+                            );
+                        }
                     }
+                }
 
-                    PlatformStatus {
-                        configured,
-                        enabled: configured && enabled,
-                    }
-                })
-                .unwrap_or(PlatformStatus {
-                    configured: false,
-                    enabled: false,
-                });
+                PlatformStatus {
+                    configured: has_token,
+                    enabled: has_token && enabled,
+                }
+            })
+            .unwrap_or(PlatformStatus {
+                configured: false,
+                enabled: false,
+            });
+
+        let twitch_status = doc
+            .get("messaging")
+            .and_then(|m| m.get("twitch"))
+            .map(|t| {
+                let has_username = t
+                    .get("username")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|s| !s.is_empty());
+                let has_token = t
+                    .get("oauth_token")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|s| !s.is_empty());
+                let enabled = t.get("enabled").and_then(|v| v.as_bool()).unwrap_or(false);
 
-            let telegram_status = doc
-                .get("messaging")
-                .and_then(|m| m.get("telegram"))
-                .map(|t| {
-                    let has_token = t
-                        .get("token")
-                        .and_then(|v| v.as_str())
-                        .is_some_and(|s| !s.is_empty());
-                    let enabled = t.get("enabled").and_then(|v| v.as_bool()).unwrap_or(false);
-
-                    if has_token {
-                        push_instance_status(
-                            &mut instances,
-                            bindings,
-                            "telegram",
-                            None,
-                            true,
-                            enabled,
+                if has_username && has_token {
+                    push_instance_status(&mut instances, bindings, "twitch", None, true, enabled);
+                }
+
+                if let Some(named_instances) = t
+                    .get("instances")
+                    .and_then(|value| value.as_array_of_tables())
+                {
+                    for instance in named_instances {
+                        let instance_name = normalize_adapter_selector(
+                            instance.get("name").and_then(|value| value.as_str()),
                         );
+                        let instance_enabled = instance
+                            .get("enabled")
+                            .and_then(|value| value.as_bool())
+                            .unwrap_or(true)
+                            && enabled;
+                        let has_instance_username = instance
+                            .get("username")
+                            .and_then(|value| value.as_str())
+                            .is_some_and(|value| !value.is_empty());
+                        let has_instance_token = instance
+                            .get("oauth_token")
+                            .and_then(|value| value.as_str())
+                            .is_some_and(|value| !value.is_empty());
+
+                        if let Some(instance_name) = instance_name
+                            && has_instance_username
+                            && has_instance_token
+                        {
+                            push_instance_status(
+                                &mut instances,
+                                bindings,
+                                "twitch",
+                                Some(instance_name),
+                                true,
+                                instance_enabled,
+                            );
+                        }
                     }
+                }
 
-                    if let Some(named_instances) = t
-                        .get("instances")
-                        .and_then(|value| value.as_array_of_tables())
-                    {
-                        for instance in named_instances {
-                            let instance_name = normalize_adapter_selector(
-                                instance.get("name").and_then(|value| value.as_str()),
-                            );
-                            let instance_enabled = instance
-                                .get("enabled")
-                                .and_then(|value| value.as_bool())
-                                .unwrap_or(true)
-                                && enabled;
-                            let instance_configured = instance
+                PlatformStatus {
+                    configured: has_username && has_token,
+                    enabled: has_username && has_token && enabled,
+                }
+            })
+            .unwrap_or(PlatformStatus {
+                configured: false,
+                enabled: false,
+            });
+
+        // Populate instances for Mattermost (not in the legacy per-platform status fields)
+        let mattermost_status = doc
+            .get("messaging")
+            .and_then(|m| m.get("mattermost"))
+            .map(|mm| {
+                let has_url = mm
+                    .get("base_url")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|s| !s.is_empty());
+                let has_token = mm
+                    .get("token")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|s| !s.is_empty());
+                let enabled = mm.get("enabled").and_then(|v| v.as_bool()).unwrap_or(false);
+
+                if has_url && has_token {
+                    push_instance_status(
+                        &mut instances,
+                        bindings,
+                        "mattermost",
+                        None,
+                        true,
+                        enabled,
+                    );
+                }
+
+                if let Some(named_instances) = mm
+                    .get("instances")
+                    .and_then(|value| value.as_array_of_tables())
+                {
+                    for instance in named_instances {
+                        let instance_name = normalize_adapter_selector(
+                            instance.get("name").and_then(|value| value.as_str()),
+                        );
+                        let instance_enabled = instance
+                            .get("enabled")
+                            .and_then(|value| value.as_bool())
+                            .unwrap_or(true)
+                            && enabled;
+                        let instance_configured = instance
+                            .get("base_url")
+                            .and_then(|value| value.as_str())
+                            .is_some_and(|value| !value.is_empty())
+                            && instance
                                 .get("token")
                                 .and_then(|value| value.as_str())
                                 .is_some_and(|value| !value.is_empty());
 
-                            if let Some(instance_name) = instance_name
-                                && instance_configured
-                            {
-                                push_instance_status(
-                                    &mut instances,
-                                    bindings,
-                                    "telegram",
-                                    Some(instance_name),
-                                    true,
-                                    instance_enabled,
-                                );
-                            }
+                        if let Some(instance_name) = instance_name
+                            && instance_configured
+                        {
+                            push_instance_status(
+                                &mut instances,
+                                bindings,
+                                "mattermost",
+                                Some(instance_name),
+                                true,
+                                instance_enabled,
+                            );
                         }
                     }
+                }
 
-                    PlatformStatus {
-                        configured: has_token,
-                        enabled: has_token && enabled,
-                    }
-                })
-                .unwrap_or(PlatformStatus {
-                    configured: false,
-                    enabled: false,
-                });
+                PlatformStatus {
+                    configured: has_url && has_token,
+                    enabled: has_url && has_token && enabled,
+                }
+            })
+            .unwrap_or(PlatformStatus {
+                configured: false,
+                enabled: false,
+            });
 
-            let twitch_status = doc
-                .get("messaging")
-                .and_then(|m| m.get("twitch"))
-                .map(|t| {
-                    let has_username = t
-                        .get("username")
-                        .and_then(|v| v.as_str())
-                        .is_some_and(|s| !s.is_empty());
-                    let has_token = t
-                        .get("oauth_token")
-                        .and_then(|v| v.as_str())
-                        .is_some_and(|s| !s.is_empty());
-                    let enabled = t.get("enabled").and_then(|v| v.as_bool()).unwrap_or(false);
-
-                    if has_username && has_token {
-                        push_instance_status(
-                            &mut instances,
-                            bindings,
-                            "twitch",
-                            None,
-                            true,
-                            enabled,
-                        );
-                    }
+        // Matrix status and instances
+        let matrix_status = doc
+            .get("messaging")
+            .and_then(|m| m.get("matrix"))
+            .map(|mx| {
+                let has_url = mx
+                    .get("homeserver_url")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|s| !s.is_empty());
+                let has_token = mx
+                    .get("access_token")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|s| !s.is_empty());
+                let enabled = mx.get("enabled").and_then(|v| v.as_bool()).unwrap_or(false);
 
-                    if let Some(named_instances) = t
-                        .get("instances")
-                        .and_then(|value| value.as_array_of_tables())
-                    {
-                        for instance in named_instances {
-                            let instance_name = normalize_adapter_selector(
-                                instance.get("name").and_then(|value| value.as_str()),
-                            );
-                            let instance_enabled = instance
-                                .get("enabled")
-                                .and_then(|value| value.as_bool())
-                                .unwrap_or(true)
-                                && enabled;
-                            let has_instance_username = instance
-                                .get("username")
-                                .and_then(|value| value.as_str())
-                                .is_some_and(|value| !value.is_empty());
-                            let has_instance_token = instance
-                                .get("oauth_token")
+                if has_url && has_token {
+                    push_instance_status(&mut instances, bindings, "matrix", None, true, enabled);
+                }
+
+                if let Some(named_instances) = mx
+                    .get("instances")
+                    .and_then(|value| value.as_array_of_tables())
+                {
+                    for instance in named_instances {
+                        let instance_name = normalize_adapter_selector(
+                            instance.get("name").and_then(|value| value.as_str()),
+                        );
+                        let instance_enabled = instance
+                            .get("enabled")
+                            .and_then(|value| value.as_bool())
+                            .unwrap_or(true)
+                            && enabled;
+                        let instance_configured = instance
+                            .get("homeserver_url")
+                            .and_then(|value| value.as_str())
+                            .is_some_and(|value| !value.is_empty())
+                            && instance
+                                .get("access_token")
                                 .and_then(|value| value.as_str())
                                 .is_some_and(|value| !value.is_empty());
 
-                            if let Some(instance_name) = instance_name
-                                && has_instance_username
-                                && has_instance_token
-                            {
-                                push_instance_status(
-                                    &mut instances,
-                                    bindings,
-                                    "twitch",
-                                    Some(instance_name),
-                                    true,
-                                    instance_enabled,
-                                );
-                            }
+                        if let Some(instance_name) = instance_name
+                            && instance_configured
+                        {
+                            push_instance_status(
+                                &mut instances,
+                                bindings,
+                                "matrix",
+                                Some(instance_name),
+                                true,
+                                instance_enabled,
+                            );
                         }
                     }
+                }
 
-                    PlatformStatus {
-                        configured: has_username && has_token,
-                        enabled: has_username && has_token && enabled,
-                    }
-                })
-                .unwrap_or(PlatformStatus {
-                    configured: false,
-                    enabled: false,
-                });
+                PlatformStatus {
+                    configured: has_url && has_token,
+                    enabled: has_url && has_token && enabled,
+                }
+            })
+            .unwrap_or(PlatformStatus {
+                configured: false,
+                enabled: false,
+            });
 
-            // Populate instances for Mattermost (not in the legacy per-platform status fields)
-            let mattermost_status = doc
-                .get("messaging")
-                .and_then(|m| m.get("mattermost"))
-                .map(|mm| {
-                    let has_url = mm
-                        .get("base_url")
-                        .and_then(|v| v.as_str())
-                        .is_some_and(|s| !s.is_empty());
-                    let has_token = mm
-                        .get("token")
-                        .and_then(|v| v.as_str())
-                        .is_some_and(|s| !s.is_empty());
-                    let enabled = mm.get("enabled").and_then(|v| v.as_bool()).unwrap_or(false);
-
-                    if has_url && has_token {
-                        push_instance_status(
-                            &mut instances,
-                            bindings,
-                            "mattermost",
-                            None,
-                            true,
-                            enabled,
+        // IRC status and instances
+        let irc_status = doc
+            .get("messaging")
+            .and_then(|m| m.get("irc"))
+            .map(|mx| {
+                let has_server = mx
+                    .get("server")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|s| !s.is_empty());
+                let has_nickname = mx
+                    .get("nickname")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|s| !s.is_empty());
+                let enabled = mx.get("enabled").and_then(|v| v.as_bool()).unwrap_or(false);
+
+                if has_server && has_nickname {
+                    push_instance_status(&mut instances, bindings, "irc", None, true, enabled);
+                }
+
+                if let Some(named_instances) = mx
+                    .get("instances")
+                    .and_then(|value| value.as_array_of_tables())
+                {
+                    for instance in named_instances {
+                        let instance_name = normalize_adapter_selector(
+                            instance.get("name").and_then(|value| value.as_str()),
                         );
-                    }
+                        let instance_enabled = instance
+                            .get("enabled")
+                            .and_then(|value| value.as_bool())
+                            .unwrap_or(true)
+                            && enabled;
+                        let instance_configured = instance
+                            .get("server")
+                            .and_then(|value| value.as_str())
+                            .is_some_and(|value| !value.is_empty())
+                            && instance
+                                .get("nickname")
+                                .and_then(|value| value.as_str())
+                                .is_some_and(|value| !value.is_empty());
 
-                    if let Some(named_instances) = mm
-                        .get("instances")
-                        .and_then(|value| value.as_array_of_tables())
-                    {
-                        for instance in named_instances {
-                            let instance_name = normalize_adapter_selector(
-                                instance.get("name").and_then(|value| value.as_str()),
+                        if let Some(instance_name) = instance_name
+                            && instance_configured
+                        {
+                            push_instance_status(
+                                &mut instances,
+                                bindings,
+                                "irc",
+                                Some(instance_name),
+                                true,
+                                instance_enabled,
                             );
-                            let instance_enabled = instance
-                                .get("enabled")
-                                .and_then(|value| value.as_bool())
-                                .unwrap_or(true)
-                                && enabled;
-                            let instance_configured = instance
-                                .get("base_url")
-                                .and_then(|value| value.as_str())
-                                .is_some_and(|value| !value.is_empty())
-                                && instance
-                                    .get("token")
-                                    .and_then(|value| value.as_str())
-                                    .is_some_and(|value| !value.is_empty());
-
-                            if let Some(instance_name) = instance_name
-                                && instance_configured
-                            {
-                                push_instance_status(
-                                    &mut instances,
-                                    bindings,
-                                    "mattermost",
-                                    Some(instance_name),
-                                    true,
-                                    instance_enabled,
-                                );
-                            }
                         }
                     }
+                }
 
-                    PlatformStatus {
-                        configured: has_url && has_token,
-                        enabled: has_url && has_token && enabled,
-                    }
-                })
-                .unwrap_or(PlatformStatus {
-                    configured: false,
-                    enabled: false,
-                });
+                PlatformStatus {
+                    configured: has_server && has_nickname,
+                    enabled: has_server && has_nickname && enabled,
+                }
+            })
+            .unwrap_or(PlatformStatus {
+                configured: false,
+                enabled: false,
+            });
+
+        // Teams status and instances
+        let teams_status = doc
+            .get("messaging")
+            .and_then(|m| m.get("teams"))
+            .map(|tm| {
+                let has_credentials = |table: &dyn toml_edit::TableLike| {
+                    ["app_id", "app_password"].iter().all(|key| {
+                        table
+                            .get(key)
+                            .and_then(|v| v.as_str())
+                            .is_some_and(|s| !s.is_empty())
+                    })
+                };
+                let configured = tm.as_table_like().is_some_and(has_credentials);
+                let enabled = tm.get("enabled").and_then(|v| v.as_bool()).unwrap_or(false);
+
+                if configured {
+                    push_instance_status(&mut instances, bindings, "teams", None, true, enabled);
+                }
 
-            // Signal status and instances
-            let signal_status = doc
-                .get("messaging")
-                .and_then(|m| m.get("signal"))
-                .map(|s| {
-                    let has_http_url = s
-                        .get("http_url")
-                        .and_then(|v| v.as_str())
-                        .is_some_and(|s| !s.is_empty());
-                    let has_account = s
-                        .get("account")
-                        .and_then(|v| v.as_str())
-                        .is_some_and(|s| !s.is_empty());
-                    let enabled = s.get("enabled").and_then(|v| v.as_bool()).unwrap_or(false);
-
-                    if has_http_url && has_account {
-                        push_instance_status(
-                            &mut instances,
-                            bindings,
-                            "signal",
-                            None,
-                            true,
-                            enabled,
+                if let Some(named_instances) = tm
+                    .get("instances")
+                    .and_then(|value| value.as_array_of_tables())
+                {
+                    for instance in named_instances {
+                        let instance_name = normalize_adapter_selector(
+                            instance.get("name").and_then(|value| value.as_str()),
                         );
-                    }
+                        let instance_enabled = instance
+                            .get("enabled")
+                            .and_then(|value| value.as_bool())
+                            .unwrap_or(true)
+                            && enabled;
 
-                    if let Some(named_instances) = s
-                        .get("instances")
-                        .and_then(|value| value.as_array_of_tables())
-                    {
-                        for instance in named_instances {
-                            let instance_name = normalize_adapter_selector(
-                                instance.get("name").and_then(|value| value.as_str()),
+                        if let Some(instance_name) = instance_name
+                            && has_credentials(instance)
+                        {
+                            push_instance_status(
+                                &mut instances,
+                                bindings,
+                                "teams",
+                                Some(instance_name),
+                                true,
+                                instance_enabled,
                             );
-                            // Named instances are enabled based only on their own flag (independent of root enabled)
-                            let instance_enabled = instance
-                                .get("enabled")
-                                .and_then(|value| value.as_bool())
-                                .unwrap_or(true);
-                            let instance_has_http_url = instance
-                                .get("http_url")
-                                .and_then(|value| value.as_str())
-                                .is_some_and(|value| !value.is_empty());
-                            let instance_has_account = instance
-                                .get("account")
-                                .and_then(|value| value.as_str())
-                                .is_some_and(|value| !value.is_empty());
-
-                            if let Some(instance_name) = instance_name
-                                && instance_has_http_url
-                                && instance_has_account
-                            {
-                                push_instance_status(
-                                    &mut instances,
-                                    bindings,
-                                    "signal",
-                                    Some(instance_name),
-                                    true,
-                                    instance_enabled,
-                                );
-                            }
                         }
                     }
+                }
+
+                PlatformStatus {
+                    configured,
+                    enabled: configured && enabled,
+                }
+            })
+            .unwrap_or(PlatformStatus {
+                configured: false,
+                enabled: false,
+            });
+
+        // Signal status and instances
+        let signal_status = doc
+            .get("messaging")
+            .and_then(|m| m.get("signal"))
+            .map(|s| {
+                let has_http_url = s
+                    .get("http_url")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|s| !s.is_empty());
+                let has_account = s
+                    .get("account")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|s| !s.is_empty());
+                let enabled = s.get("enabled").and_then(|v| v.as_bool()).unwrap_or(false);
 
-                    PlatformStatus {
-                        configured: has_http_url && has_account,
-                        enabled: has_http_url && has_account && enabled,
+                if has_http_url && has_account {
+                    push_instance_status(&mut instances, bindings, "signal", None, true, enabled);
+                }
+
+                if let Some(named_instances) = s
+                    .get("instances")
+                    .and_then(|value| value.as_array_of_tables())
+                {
+                    for instance in named_instances {
+                        let instance_name = normalize_adapter_selector(
+                            instance.get("name").and_then(|value| value.as_str()),
+                        );
+                        // Named instances are enabled based only on their own flag (independent of root enabled)
+                        let instance_enabled = instance
+                            .get("enabled")
+                            .and_then(|value| value.as_bool())
+                            .unwrap_or(true);
+                        let instance_has_http_url = instance
+                            .get("http_url")
+                            .and_then(|value| value.as_str())
+                            .is_some_and(|value| !value.is_empty());
+                        let instance_has_account = instance
+                            .get("account")
+                            .and_then(|value| value.as_str())
+                            .is_some_and(|value| !value.is_empty());
+
+                        if let Some(instance_name) = instance_name
+                            && instance_has_http_url
+                            && instance_has_account
+                        {
+                            push_instance_status(
+                                &mut instances,
+                                bindings,
+                                "signal",
+                                Some(instance_name),
+                                true,
+                                instance_enabled,
+                            );
+                        }
                     }
-                })
-                .unwrap_or(PlatformStatus {
-                    configured: false,
-                    enabled: false,
-                });
+                }
 
-            (
-                discord_status,
-                slack_status,
-                telegram_status,
-                email_status,
-                webhook_status,
-                twitch_status,
-                mattermost_status,
-                signal_status,
-                instances,
-            )
-        } else {
-            let default = PlatformStatus {
+                PlatformStatus {
+                    configured: has_http_url && has_account,
+                    enabled: has_http_url && has_account && enabled,
+                }
+            })
+            .unwrap_or(PlatformStatus {
                 configured: false,
                 enabled: false,
-            };
-            (
-                default.clone(),
-                default.clone(),
-                default.clone(),
-                default.clone(),
-                default.clone(),
-                default.clone(),
-                default.clone(),
-                default.clone(),
-                Vec::new(),
-            )
+            });
+
+        (
+            discord_status,
+            slack_status,
+            telegram_status,
+            email_status,
+            webhook_status,
+            twitch_status,
+            mattermost_status,
+            matrix_status,
+            irc_status,
+            teams_status,
+            signal_status,
+            instances,
+        )
+    } else {
+        let default = PlatformStatus {
+            configured: false,
+            enabled: false,
         };
+        (
+            default.clone(),
+            default.clone(),
+            default.clone(),
+            default.clone(),
+            default.clone(),
+            default.clone(),
+            default.clone(),
+            default.clone(),
+            default.clone(),
+            default.clone(),
+            default.clone(),
+            Vec::new(),
+        )
+    };
+
+    if let Some(manager) = state.messaging_manager.read().await.as_ref() {
+        for instance in &mut instances {
+            instance.health = manager.connection_health(&instance.runtime_key).await;
+        }
+    }
 
     Ok(Json(MessagingStatusResponse {
         discord,
@@ -907,6 +1188,9 @@ pub(super) async fn messaging_status(
         webhook,
         twitch,
         mattermost,
+        matrix,
+        irc,
+        teams,
         signal,
         instances,
     }))
@@ -1393,7 +1677,8 @@ pub(super) async fn toggle_platform(
                             webhook_config.port,
                             &webhook_config.bind,
                             webhook_config.auth_token.clone(),
-                        );
+                        )
+                        .with_signing_secret(webhook_config.signing_secret.clone());
                         if let Err(error) = manager.register_and_start(adapter).await {
                             tracing::error!(%error, "failed to start webhook adapter on toggle");
                         }
@@ -1627,7 +1912,17 @@ pub(super) async fn create_messaging_instance(
 
     if !matches!(
         platform.as_str(),
-        "discord" | "slack" | "telegram" | "twitch" | "email" | "webhook" | "mattermost" | "signal"
+        "discord"
+            | "slack"
+            | "telegram"
+            | "twitch"
+            | "email"
+            | "webhook"
+            | "mattermost"
+            | "matrix"
+            | "irc"
+            | "teams"
+            | "signal"
     ) {
         return Ok(Json(MessagingInstanceActionResponse {
             success: false,
@@ -1807,6 +2102,34 @@ pub(super) async fn create_messaging_instance(
                         platform_table["token"] = toml_edit::value(token.as_str());
                     }
                 }
+                "matrix" => {
+                    if let Some(url) = &credentials.matrix_homeserver_url {
+                        if url::Url::parse(url)
+                            .map(|u| {
+                                !matches!(u.scheme(), "http" | "https")
+                                    || u.query().is_some()
+                                    || u.fragment().is_some()
+                            })
+                            .unwrap_or(true)
+                        {
+                            return Ok(Json(MessagingInstanceActionResponse {
+                                success: false,
+                                message: "invalid matrix homeserver_url: must be an http(s) URL (e.g. https://matrix.example.org)"
+                                    .to_string(),
+                            }));
+                        }
+                        platform_table["homeserver_url"] = toml_edit::value(url.as_str());
+                    }
+                    if let Some(token) = &credentials.matrix_access_token {
+                        platform_table["access_token"] = toml_edit::value(token.as_str());
+                    }
+                }
+                "irc" => {
+                    if let Err(response) = write_irc_credentials(credentials, platform_table) {
+                        return Ok(Json(response));
+                    }
+                }
+                "teams" => write_teams_credentials(credentials, platform_table),
                 "signal" => {
                     // Merge incoming credentials with existing TOML values for patch-style updates.
                     // Fields omitted from the request are filled from the current table,
@@ -1972,6 +2295,34 @@ pub(super) async fn create_messaging_instance(
                         instance_table["token"] = toml_edit::value(token.as_str());
                     }
                 }
+                "matrix" => {
+                    if let Some(url) = &credentials.matrix_homeserver_url {
+                        if url::Url::parse(url)
+                            .map(|u| {
+                                !matches!(u.scheme(), "http" | "https")
+                                    || u.query().is_some()
+                                    || u.fragment().is_some()
+                            })
+                            .unwrap_or(true)
+                        {
+                            return Ok(Json(MessagingInstanceActionResponse {
+                                success: false,
+                                message: "invalid matrix homeserver_url: must be an http(s) URL (e.g. https://matrix.example.org)"
+                                    .to_string(),
+                            }));
+                        }
+                        instance_table["homeserver_url"] = toml_edit::value(url.as_str());
+                    }
+                    if let Some(token) = &credentials.matrix_access_token {
+                        instance_table["access_token"] = toml_edit::value(token.as_str());
+                    }
+                }
+                "irc" => {
+                    if let Err(response) = write_irc_credentials(credentials, &mut instance_table) {
+                        return Ok(Json(response));
+                    }
+                }
+                "teams" => write_teams_credentials(credentials, &mut instance_table),
                 "signal" => {
                     // New instance — no existing values to merge, validate directly.
                     let (http_url, account, dm_users) = match parse_signal_credentials(credentials)
@@ -2080,7 +2431,17 @@ pub(super) async fn delete_messaging_instance(
 
     if !matches!(
         platform.as_str(),
-        "discord" | "slack" | "telegram" | "twitch" | "email" | "webhook" | "mattermost" | "signal"
+        "discord"
+            | "slack"
+            | "telegram"
+            | "twitch"
+            | "email"
+            | "webhook"
+            | "mattermost"
+            | "matrix"
+            | "irc"
+            | "teams"
+            | "signal"
     ) {
         return Ok(Json(MessagingInstanceActionResponse {
             success: false,
@@ -2184,6 +2545,34 @@ pub(super) async fn delete_messaging_instance(
                     table.remove("dm_allowed_users");
                     table.remove("max_attachment_bytes");
                 }
+                "matrix" => {
+                    table.remove("homeserver_url");
+                    table.remove("access_token");
+                    table.remove("dm_allowed_users");
+                    table.remove("invite_allowed_users");
+                    table.remove("max_attachment_bytes");
+                }
+                "irc" => {
+                    table.remove("server");
+                    table.remove("port");
+                    table.remove("tls");
+                    table.remove("nickname");
+                    table.remove("username");
+                    table.remove("realname");
+                    table.remove("server_password");
+                    table.remove("sasl_username");
+                    table.remove("sasl_password");
+                    table.remove("channels");
+                    table.remove("dm_allowed_users");
+                }
+                "teams" => {
+                    table.remove("app_id");
+                    table.remove("app_password");
+                    table.remove("tenant_id");
+                    table.remove("bind");
+                    table.remove("port");
+                    table.remove("dm_allowed_users");
+                }
                 "signal" => {
                     table.remove("http_url");
                     table.remove("account");
@@ -0,0 +1,815 @@
+//! OpenID Connect single sign-on for the web interface.
+//!
+//! Runs the authorization code flow with PKCE against any provider that
+//! publishes discovery metadata. ID tokens are verified locally against the
+//! provider's JWKS (RS256 or ES256), and the user's groups are mapped to API
+//! scopes through `api.oidc.group_scopes`.
+
+use super::access::ApiScope;
+use crate::config::OidcConfig;
+
+use anyhow::{Context as _, Result};
+use base64::Engine as _;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use rand::RngCore as _;
+use serde::Deserialize;
+use serde_json::Value;
+use tokio::sync::Mutex;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How long a user has to finish logging in at the provider.
+pub(super) const PENDING_LOGIN_TTL: Duration = Duration::from_secs(600);
+/// Cap on unfinished logins, since starting one needs no credentials.
+const MAX_PENDING_LOGINS: usize = 1024;
+/// Allowed clock skew when checking `exp`.
+const CLOCK_SKEW_SECS: i64 = 60;
+/// Minimum time between JWKS fetches when an ID token names an unknown key.
+const JWKS_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The parts of the provider's discovery document we use.
+#[derive(Debug, Deserialize)]
+struct ProviderMetadata {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    jwks_uri: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Jwk {
+    kty: String,
+    #[serde(default)]
+    kid: Option<String>,
+    #[serde(default)]
+    alg: Option<String>,
+    #[serde(default, rename = "use")]
+    key_use: Option<String>,
+    #[serde(default)]
+    n: Option<String>,
+    #[serde(default)]
+    e: Option<String>,
+    #[serde(default)]
+    crv: Option<String>,
+    #[serde(default)]
+    x: Option<String>,
+    #[serde(default)]
+    y: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JwkSet {
+    keys: Vec<Jwk>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JwtHeader {
+    alg: String,
+    #[serde(default)]
+    kid: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    id_token: Option<String>,
+}
+
+struct PendingLogin {
+    nonce: String,
+    code_verifier: String,
+    return_to: String,
+    started_at: Instant,
+}
+
+/// A login started by [`OidcClient::begin_login`].
+#[derive(Debug)]
+pub struct LoginRedirect {
+    /// Where to send the browser.
+    pub authorize_url: String,
+    /// Comes back on the callback. Also set as a cookie, so a callback is
+    /// only accepted in the browser that started the login.
+    pub state: String,
+}
+
+/// The user from a verified ID token.
+#[derive(Debug, Clone)]
+pub struct OidcIdentity {
+    pub subject: String,
+    pub email: Option<String>,
+    pub name: Option<String>,
+    pub groups: Vec<String>,
+    /// Interface path to land on after login.
+    pub return_to: String,
+}
+
+pub struct OidcClient {
+    config: OidcConfig,
+    http: reqwest::Client,
+    metadata: Mutex<Option<Arc<ProviderMetadata>>>,
+    jwks: Mutex<Option<(Instant, JwkSet)>>,
+    pending: Mutex<HashMap<String, PendingLogin>>,
+}
+
+impl OidcClient {
+    pub fn new(config: OidcConfig) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .context("failed to build OIDC HTTP client")?;
+        Ok(Self {
+            config,
+            http,
+            metadata: Mutex::new(None),
+            jwks: Mutex::new(None),
+            pending: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Session cookies are marked `Secure` when the interface is served over HTTPS.
+    pub fn secure_cookies(&self) -> bool {
+        self.config.redirect_url.starts_with("https://")
+    }
+
+    pub fn session_ttl(&self) -> chrono::Duration {
+        chrono::Duration::hours(self.config.session_ttl_hours as i64)
+    }
+
+    /// Union of the scopes mapped to `groups`, in [`ApiScope::ALL`] order.
+    pub fn scopes_for_groups(&self, groups: &[String]) -> Vec<ApiScope> {
+        ApiScope::ALL
+            .into_iter()
+            .filter(|scope| {
+                groups.iter().any(|group| {
+                    self.config
+                        .group_scopes
+                        .get(group)
+                        .is_some_and(|scopes| scopes.contains(scope))
+                })
+            })
+            .collect()
+    }
+
+    /// Start a login and return the provider URL to redirect to.
+    pub async fn begin_login(&self, return_to: Option<&str>) -> Result<LoginRedirect> {
+        let metadata = self.metadata().await?;
+        let state = random_token();
+        let nonce = random_token();
+        let pkce = crate::auth::generate_pkce();
+
+        let mut scope = vec!["openid"];
+        scope.extend(
+            self.config
+                .scopes
+                .iter()
+                .map(String::as_str)
+                .filter(|requested| *requested != "openid"),
+        );
+
+        let mut url = reqwest::Url::parse(&metadata.authorization_endpoint)
+            .context("provider returned an invalid authorization_endpoint")?;
+        url.query_pairs_mut()
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &self.config.client_id)
+            .append_pair("redirect_uri", &self.config.redirect_url)
+            .append_pair("scope", &scope.join(" "))
+            .append_pair("state", &state)
+            .append_pair("nonce", &nonce)
+            .append_pair("code_challenge", &pkce.challenge)
+            .append_pair("code_challenge_method", "S256");
+
+        let mut pending = self.pending.lock().await;
+        pending.retain(|_, login| login.started_at.elapsed() < PENDING_LOGIN_TTL);
+        anyhow::ensure!(
+            pending.len() < MAX_PENDING_LOGINS,
+            "too many logins in progress, try again shortly"
+        );
+        pending.insert(
+            state.clone(),
+            PendingLogin {
+                nonce,
+                code_verifier: pkce.verifier,
+                return_to: sanitize_return_to(return_to),
+                started_at: Instant::now(),
+            },
+        );
+
+        Ok(LoginRedirect {
+            authorize_url: url.into(),
+            state,
+        })
+    }
+
+    /// Finish a login: redeem the code, verify the ID token, and return the
+    /// user. Each `state` can only be completed once.
+    pub async fn complete_login(&self, code: &str, state: &str) -> Result<OidcIdentity> {
+        let pending = self
+            .pending
+            .lock()
+            .await
+            .remove(state)
+            .filter(|login| login.started_at.elapsed() < PENDING_LOGIN_TTL)
+            .context("login expired or was already completed, start again")?;
+
+        let metadata = self.metadata().await?;
+        let id_token = self
+            .exchange_code(&metadata, code, &pending.code_verifier)
+            .await?;
+        let claims = self.verify_id_token(&id_token).await?;
+        validate_claims(
+            &claims,
+            &metadata.issuer,
+            &self.config.client_id,
+            &pending.nonce,
+            chrono::Utc::now().timestamp(),
+        )?;
+
+        let string_claim =
+            |name: &str| claims.get(name).and_then(Value::as_str).map(str::to_string);
+        Ok(OidcIdentity {
+            subject: string_claim("sub").context("ID token has no subject")?,
+            email: string_claim("email"),
+            name: string_claim("name").or_else(|| string_claim("preferred_username")),
+            groups: claim_at_path(&claims, &self.config.groups_claim)
+                .map(groups_from_claim)
+                .unwrap_or_default(),
+            return_to: pending.return_to,
+        })
+    }
+
+    /// Discovery metadata, fetched once and kept. A failed fetch is retried
+    /// on the next login.
+    async fn metadata(&self) -> Result<Arc<ProviderMetadata>> {
+        let mut cached = self.metadata.lock().await;
+        if let Some(metadata) = cached.as_ref() {
+            return Ok(metadata.clone());
+        }
+
+        let url = format!("{}/.well-known/openid-configuration", self.config.issuer);
+        let metadata: ProviderMetadata = self
+            .http
+            .get(&url)
+            .send()
+            .await
+            .with_context(|| format!("failed to fetch {url}"))?
+            .error_for_status()
+            .with_context(|| format!("failed to fetch {url}"))?
+            .json()
+            .await
+            .context("invalid OIDC discovery document")?;
+        anyhow::ensure!(
+            metadata.issuer.trim_end_matches('/') == self.config.issuer,
+            "discovery document is for issuer '{}', expected '{}'",
+            metadata.issuer,
+            self.config.issuer
+        );
+
+        let metadata = Arc::new(metadata);
+        *cached = Some(metadata.clone());
+        Ok(metadata)
+    }
+
+    async fn exchange_code(
+        &self,
+        metadata: &ProviderMetadata,
+        code: &str,
+        code_verifier: &str,
+    ) -> Result<String> {
+        let mut request = self.http.post(&metadata.token_endpoint).form(&[
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", self.config.redirect_url.as_str()),
+            ("client_id", self.config.client_id.as_str()),
+            ("code_verifier", code_verifier),
+        ]);
+        if let Some(secret) = &self.config.client_secret {
+            request = request.basic_auth(&self.config.client_id, Some(secret));
+        }
+
+        let response = request
+            .send()
+            .await
+            .context("failed to reach the token endpoint")?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("token endpoint returned {status}: {body}");
+        }
+        let tokens: TokenResponse = response
+            .json()
+            .await
+            .context("invalid token endpoint response")?;
+        tokens
+            .id_token
+            .context("token endpoint returned no id_token; is the 'openid' scope allowed?")
+    }
+
+    /// Check the ID token's signature and return its claims.
+    async fn verify_id_token(&self, id_token: &str) -> Result<serde_json::Map<String, Value>> {
+        let mut parts = id_token.split('.');
+        let (Some(header), Some(payload), Some(signature), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            anyhow::bail!("ID token is not a JWT");
+        };
+
+        let header_json: JwtHeader = serde_json::from_slice(
+            &URL_SAFE_NO_PAD
+                .decode(header)
+                .context("invalid ID token header")?,
+        )
+        .context("invalid ID token header")?;
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature)
+            .context("invalid ID token signature")?;
+        let key = self
+            .signing_key(header_json.kid.as_deref(), &header_json.alg)
+            .await?;
+        verify_signature(
+            &key,
+            &header_json.alg,
+            format!("{header}.{payload}").as_bytes(),
+            &signature,
+        )?;
+
+        serde_json::from_slice(
+            &URL_SAFE_NO_PAD
+                .decode(payload)
+                .context("invalid ID token payload")?,
+        )
+        .context("invalid ID token payload")
+    }
+
+    /// The JWKS key for `kid`. An unknown key triggers a refetch, since the
+    /// provider may have rotated its keys.
+    async fn signing_key(&self, kid: Option<&str>, alg: &str) -> Result<Jwk> {
+        let mut cached = self.jwks.lock().await;
+        if let Some((fetched_at, keys)) = cached.as_ref() {
+            if let Some(key) = find_key(keys, kid, alg) {
+                return Ok(key.clone());
+            }
+            if fetched_at.elapsed() < JWKS_REFRESH_INTERVAL {
+                anyhow::bail!("no {alg} signing key matches the ID token (kid {kid:?})");
+            }
+        }
+
+        let metadata = self.metadata().await?;
+        let keys: JwkSet = self
+            .http
+            .get(&metadata.jwks_uri)
+            .send()
+            .await
+            .context("failed to fetch the provider's JWKS")?
+            .error_for_status()
+            .context("failed to fetch the provider's JWKS")?
+            .json()
+            .await
+            .context("invalid JWKS document")?;
+        let key = find_key(&keys, kid, alg).cloned();
+        *cached = Some((Instant::now(), keys));
+        key.with_context(|| format!("no {alg} signing key matches the ID token (kid {kid:?})"))
+    }
+}
+
+fn find_key<'a>(keys: &'a JwkSet, kid: Option<&str>, alg: &str) -> Option<&'a Jwk> {
+    let kty = match alg {
+        "RS256" => "RSA",
+        "ES256" => "EC",
+        _ => return None,
+    };
+    keys.keys.iter().find(|key| {
+        key.kty == kty
+            && key
+                .key_use
+                .as_deref()
+                .is_none_or(|key_use| key_use == "sig")
+            && key.alg.as_deref().is_none_or(|key_alg| key_alg == alg)
+            && (kid.is_none() || key.kid.as_deref() == kid)
+    })
+}
+
+fn verify_signature(key: &Jwk, alg: &str, message: &[u8], signature: &[u8]) -> Result<()> {
+    use ring::signature::{
+        ECDSA_P256_SHA256_FIXED, RSA_PKCS1_2048_8192_SHA256, RsaPublicKeyComponents,
+        UnparsedPublicKey,
+    };
+
+    let component = |value: &Option<String>, name: &str| -> Result<Vec<u8>> {
+        let value = value
+            .as_deref()
+            .with_context(|| format!("signing key has no '{name}'"))?;
+        URL_SAFE_NO_PAD
+            .decode(value)
+            .with_context(|| format!("signing key '{name}' is not base64url"))
+    };
+
+    let verified = match alg {
+        "RS256" => {
+            let modulus = component(&key.n, "n")?;
+            let exponent = component(&key.e, "e")?;
+            // ring rejects a modulus with leading zero bytes, which some JWKS include.
+            let first_nonzero = modulus
+                .iter()
+                .position(|byte| *byte != 0)
+                .unwrap_or(modulus.len());
+            RsaPublicKeyComponents {
+                n: &modulus[first_nonzero..],
+                e: exponent.as_slice(),
+            }
+            .verify(&RSA_PKCS1_2048_8192_SHA256, message, signature)
+        }
+        "ES256" => {
+            anyhow::ensure!(
+                key.crv.as_deref() == Some("P-256"),
+                "ES256 signing key is not on P-256"
+            );
+            let mut point = vec![0x04];
+            point.extend(component(&key.x, "x")?);
+            point.extend(component(&key.y, "y")?);
+            UnparsedPublicKey::new(&ECDSA_P256_SHA256_FIXED, point).verify(message, signature)
+        }
+        other => anyhow::bail!("unsupported ID token algorithm '{other}'"),
+    };
+    verified.map_err(|_| anyhow::anyhow!("ID token signature is invalid"))
+}
+
+/// Check issuer, audience, expiry and nonce.
+fn validate_claims(
+    claims: &serde_json::Map<String, Value>,
+    issuer: &str,
+    client_id: &str,
+    nonce: &str,
+    now: i64,
+) -> Result<()> {
+    anyhow::ensure!(
+        claims.get("iss").and_then(Value::as_str) == Some(issuer),
+        "ID token was issued by someone else"
+    );
+
+    let audience_matches = match claims.get("aud") {
+        Some(Value::String(audience)) => audience == client_id,
+        Some(Value::Array(audiences)) => audiences
+            .iter()
+            .any(|audience| audience.as_str() == Some(client_id)),
+        _ => false,
+    };
+    anyhow::ensure!(audience_matches, "ID token is for another client");
+    if let Some(authorized_party) = claims.get("azp").and_then(Value::as_str) {
+        anyhow::ensure!(
+            authorized_party == client_id,
+            "ID token is for another client"
+        );
+    }
+
+    let expires_at = claims
+        .get("exp")
+        .and_then(Value::as_i64)
+        .context("ID token has no expiry")?;
+    anyhow::ensure!(expires_at + CLOCK_SKEW_SECS > now, "ID token has expired");
+
+    anyhow::ensure!(
+        claims.get("nonce").and_then(Value::as_str) == Some(nonce),
+        "ID token nonce does not match this login"
+    );
+    Ok(())
+}
+
+/// Look up a claim by dotted path, e.g. `realm_access.roles`. A claim whose
+/// own name contains dots is matched first.
+fn claim_at_path<'a>(claims: &'a serde_json::Map<String, Value>, path: &str) -> Option<&'a Value> {
+    if let Some(value) = claims.get(path) {
+        return Some(value);
+    }
+    let mut segments = path.split('.');
+    let mut value = claims.get(segments.next()?)?;
+    for segment in segments {
+        value = value.get(segment)?;
+    }
+    Some(value)
+}
+
+/// Groups come as an array of strings, or a single string from some providers.
+fn groups_from_claim(value: &Value) -> Vec<String> {
+    match value {
+        Value::String(group) => vec![group.clone()],
+        Value::Array(groups) => groups
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Only same-site paths are allowed, so the login can't be used as an open redirect.
+fn sanitize_return_to(return_to: Option<&str>) -> String {
+    match return_to {
+        Some(path) if path.starts_with('/') && !path.starts_with("//") && !path.contains('\\') => {
+            path.to_string()
+        }
+        _ => "/".to_string(),
+    }
+}
+
+fn random_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::rng().fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::extract::{Form, Query, State};
+    use axum::response::{IntoResponse, Redirect, Response};
+    use axum::routing::{get, post};
+    use ring::rand::SystemRandom;
+    use ring::signature::{ECDSA_P256_SHA256_FIXED_SIGNING, EcdsaKeyPair, KeyPair as _};
+    use sha2::{Digest as _, Sha256};
+
+    const CLIENT_ID: &str = "spacebot";
+
+    /// A minimal OIDC provider: discovery, JWKS, an authorize endpoint that
+    /// logs everyone in as `user-1`, and a token endpoint that checks PKCE.
+    struct MockProvider {
+        issuer: String,
+        key_pair: EcdsaKeyPair,
+        /// code -> (nonce, code_challenge)
+        codes: std::sync::Mutex<HashMap<String, (String, String)>>,
+    }
+
+    impl MockProvider {
+        fn sign(&self, claims: Value) -> String {
+            let header = URL_SAFE_NO_PAD.encode(
+                serde_json::json!({"alg": "ES256", "kid": "test-key", "typ": "JWT"}).to_string(),
+            );
+            let payload = URL_SAFE_NO_PAD.encode(claims.to_string());
+            let message = format!("{header}.{payload}");
+            let signature = self
+                .key_pair
+                .sign(&SystemRandom::new(), message.as_bytes())
+                .expect("signing should succeed");
+            format!("{message}.{}", URL_SAFE_NO_PAD.encode(signature.as_ref()))
+        }
+    }
+
+    async fn discovery(State(provider): State<Arc<MockProvider>>) -> Response {
+        axum::Json(serde_json::json!({
+            "issuer": provider.issuer,
+            "authorization_endpoint": format!("{}/authorize", provider.issuer),
+            "token_endpoint": format!("{}/token", provider.issuer),
+            "jwks_uri": format!("{}/jwks", provider.issuer),
+        }))
+        .into_response()
+    }
+
+    async fn jwks(State(provider): State<Arc<MockProvider>>) -> Response {
+        let point = provider.key_pair.public_key().as_ref();
+        axum::Json(serde_json::json!({"keys": [{
+            "kty": "EC",
+            "crv": "P-256",
+            "kid": "test-key",
+            "use": "sig",
+            "alg": "ES256",
+            "x": URL_SAFE_NO_PAD.encode(&point[1..33]),
+            "y": URL_SAFE_NO_PAD.encode(&point[33..65]),
+        }]}))
+        .into_response()
+    }
+
+    async fn authorize(
+        State(provider): State<Arc<MockProvider>>,
+        Query(params): Query<HashMap<String, String>>,
+    ) -> Response {
+        assert_eq!(params["client_id"], CLIENT_ID);
+        assert_eq!(params["code_challenge_method"], "S256");
+        assert!(params["scope"].split(' ').any(|scope| scope == "openid"));
+        let code = random_token();
+        provider.codes.lock().unwrap().insert(
+            code.clone(),
+            (params["nonce"].clone(), params["code_challenge"].clone()),
+        );
+        let mut location = reqwest::Url::parse(&params["redirect_uri"]).unwrap();
+        location
+            .query_pairs_mut()
+            .append_pair("code", &code)
+            .append_pair("state", &params["state"]);
+        Redirect::to(location.as_str()).into_response()
+    }
+
+    async fn token(
+        State(provider): State<Arc<MockProvider>>,
+        Form(params): Form<HashMap<String, String>>,
+    ) -> Response {
+        let Some((nonce, challenge)) = provider.codes.lock().unwrap().remove(&params["code"])
+        else {
+            return axum::http::StatusCode::BAD_REQUEST.into_response();
+        };
+        let verifier_hash =
+            URL_SAFE_NO_PAD.encode(Sha256::digest(params["code_verifier"].as_bytes()));
+        if verifier_hash != challenge {
+            return axum::http::StatusCode::BAD_REQUEST.into_response();
+        }
+        let now = chrono::Utc::now().timestamp();
+        let id_token = provider.sign(serde_json::json!({
+            "iss": provider.issuer,
+            "aud": CLIENT_ID,
+            "sub": "user-1",
+            "email": "ada@example.com",
+            "name": "Ada",
+            "groups": ["ops", "staff"],
+            "nonce": nonce,
+            "iat": now,
+            "exp": now + 300,
+        }));
+        axum::Json(serde_json::json!({
+            "access_token": "unused",
+            "token_type": "Bearer",
+            "id_token": id_token,
+        }))
+        .into_response()
+    }
+
+    async fn start_mock_provider() -> Arc<MockProvider> {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let rng = SystemRandom::new();
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng).unwrap();
+        let provider = Arc::new(MockProvider {
+            issuer: format!("http://{}", listener.local_addr().unwrap()),
+            key_pair: EcdsaKeyPair::from_pkcs8(
+                &ECDSA_P256_SHA256_FIXED_SIGNING,
+                pkcs8.as_ref(),
+                &rng,
+            )
+            .unwrap(),
+            codes: std::sync::Mutex::new(HashMap::new()),
+        });
+        let app = axum::Router::new()
+            .route("/.well-known/openid-configuration", get(discovery))
+            .route("/jwks", get(jwks))
+            .route("/authorize", get(authorize))
+            .route("/token", post(token))
+            .with_state(provider.clone());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        provider
+    }
+
+    fn client_for(provider: &MockProvider) -> OidcClient {
+        OidcClient::new(OidcConfig {
+            issuer: provider.issuer.clone(),
+            client_id: CLIENT_ID.into(),
+            client_secret: None,
+            redirect_url: "http://127.0.0.1:19898/api/auth/oidc/callback".into(),
+            scopes: vec!["email".into()],
+            groups_claim: "groups".into(),
+            group_scopes: HashMap::from([
+                ("ops".to_string(), vec![ApiScope::Messaging]),
+                ("admins".to_string(), vec![ApiScope::Admin]),
+            ]),
+            session_ttl_hours: 12,
+        })
+        .unwrap()
+    }
+
+    /// Follow the authorize redirect the way a browser would, stopping at the callback.
+    async fn authorize_at_provider(authorize_url: &str) -> HashMap<String, String> {
+        let browser = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .unwrap();
+        let response = browser.get(authorize_url).send().await.unwrap();
+        let location = response.headers()[reqwest::header::LOCATION]
+            .to_str()
+            .unwrap()
+            .to_string();
+        assert!(location.starts_with("http://127.0.0.1:19898/api/auth/oidc/callback?"));
+        reqwest::Url::parse(&location)
+            .unwrap()
+            .query_pairs()
+            .into_owned()
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn login_against_mock_provider() {
+        let provider = start_mock_provider().await;
+        let client = client_for(&provider);
+
+        let login = client.begin_login(Some("/agents/main")).await.unwrap();
+        let callback = authorize_at_provider(&login.authorize_url).await;
+        assert_eq!(callback["state"], login.state);
+
+        let identity = client
+            .complete_login(&callback["code"], &callback["state"])
+            .await
+            .unwrap();
+        assert_eq!(identity.subject, "user-1");
+        assert_eq!(identity.email.as_deref(), Some("ada@example.com"));
+        assert_eq!(
+            identity.groups,
+            vec!["ops".to_string(), "staff".to_string()]
+        );
+        assert_eq!(identity.return_to, "/agents/main");
+        assert_eq!(
+            client.scopes_for_groups(&identity.groups),
+            vec![ApiScope::Messaging]
+        );
+
+        // A state can only be completed once.
+        assert!(
+            client
+                .complete_login(&callback["code"], &callback["state"])
+                .await
+                .is_err()
+        );
+        // A state this client never issued is rejected before the code is redeemed.
+        let other = client.begin_login(None).await.unwrap();
+        let callback = authorize_at_provider(&other.authorize_url).await;
+        assert!(
+            client
+                .complete_login(&callback["code"], "forged-state")
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn tampered_id_token_is_rejected() {
+        let provider = start_mock_provider().await;
+        let client = client_for(&provider);
+        let signed = provider.sign(serde_json::json!({"sub": "user-1"}));
+        assert!(client.verify_id_token(&signed).await.is_ok());
+
+        let forged_payload = URL_SAFE_NO_PAD.encode(r#"{"sub":"admin"}"#);
+        let mut parts: Vec<&str> = signed.split('.').collect();
+        parts[1] = &forged_payload;
+        assert!(client.verify_id_token(&parts.join(".")).await.is_err());
+
+        let unsigned = format!(
+            "{}.{}.",
+            URL_SAFE_NO_PAD.encode(r#"{"alg":"none"}"#),
+            forged_payload
+        );
+        assert!(client.verify_id_token(&unsigned).await.is_err());
+    }
+
+    #[test]
+    fn claims_are_validated() {
+        let now = 1_800_000_000;
+        let claims = |overrides: Value| {
+            let mut claims = serde_json::json!({
+                "iss": "https://id.example.com",
+                "aud": ["spacebot", "other"],
+                "exp": now + 60,
+                "nonce": "n-1",
+            });
+            for (key, value) in overrides.as_object().unwrap() {
+                claims[key] = value.clone();
+            }
+            claims.as_object().unwrap().clone()
+        };
+        let check = |claims: serde_json::Map<String, Value>| {
+            validate_claims(&claims, "https://id.example.com", "spacebot", "n-1", now)
+        };
+
+        assert!(check(claims(serde_json::json!({}))).is_ok());
+        assert!(
+            check(claims(
+                serde_json::json!({"iss": "https://evil.example.com"})
+            ))
+            .is_err()
+        );
+        assert!(check(claims(serde_json::json!({"aud": "other"}))).is_err());
+        assert!(check(claims(serde_json::json!({"azp": "other"}))).is_err());
+        assert!(check(claims(serde_json::json!({"exp": now - 120}))).is_err());
+        assert!(check(claims(serde_json::json!({"nonce": "n-2"}))).is_err());
+    }
+
+    #[test]
+    fn groups_and_return_paths() {
+        let claims = serde_json::json!({
+            "groups": "ops",
+            "realm_access": {"roles": ["admins", 7]},
+        });
+        let claims = claims.as_object().unwrap();
+        assert_eq!(
+            claim_at_path(claims, "groups").map(groups_from_claim),
+            Some(vec!["ops".to_string()])
+        );
+        assert_eq!(
+            claim_at_path(claims, "realm_access.roles").map(groups_from_claim),
+            Some(vec!["admins".to_string()])
+        );
+        assert!(claim_at_path(claims, "missing.path").is_none());
+
+        assert_eq!(
+            sanitize_return_to(Some("/agents?tab=cron")),
+            "/agents?tab=cron"
+        );
+        assert_eq!(sanitize_return_to(Some("//evil.example.com")), "/");
+        assert_eq!(sanitize_return_to(Some("https://evil.example.com")), "/");
+        assert_eq!(sanitize_return_to(Some("/\\evil.example.com")), "/");
+        assert_eq!(sanitize_return_to(None), "/");
+    }
+}
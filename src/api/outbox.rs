@@ -0,0 +1,240 @@
+use super::state::ApiState;
+
+use crate::messaging::outbox::{OutboxEntry, OutboxStatus, OutboxStore};
+
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+// ---------------------------------------------------------------------------
+// Request / response types
+// ---------------------------------------------------------------------------
+
+#[derive(Deserialize, utoipa::ToSchema, utoipa::IntoParams)]
+pub(super) struct OutboxListQuery {
+    /// Filter by status: `pending` or `dead_letter`. Optional.
+    #[serde(default)]
+    status: Option<String>,
+    #[serde(default = "default_outbox_limit")]
+    limit: i64,
+}
+
+#[derive(Deserialize, utoipa::ToSchema, utoipa::IntoParams)]
+pub(super) struct OutboxPurgeQuery {
+    /// Only purge rows with this status. Purges everything when omitted.
+    #[serde(default)]
+    status: Option<String>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct OutboxListResponse {
+    entries: Vec<OutboxEntry>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct OutboxActionResponse {
+    success: bool,
+    message: String,
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn default_outbox_limit() -> i64 {
+    100
+}
+
+/// Extract the outbox store, returning 503 if not yet initialized.
+fn get_outbox_store(state: &ApiState) -> Result<Arc<OutboxStore>, StatusCode> {
+    state
+        .outbox_store
+        .load()
+        .as_ref()
+        .clone()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)
+}
+
+fn parse_status(value: Option<&str>) -> Result<Option<OutboxStatus>, StatusCode> {
+    match value {
+        None => Ok(None),
+        Some(value) => Ok(Some(
+            OutboxStatus::parse(value).ok_or(StatusCode::BAD_REQUEST)?,
+        )),
+    }
+}
+
+/// Nudge the running manager's outbox worker so replays go out immediately
+/// instead of on the next poll.
+async fn wake_outbox_worker(state: &ApiState) {
+    if let Some(manager) = state.messaging_manager.read().await.as_ref() {
+        manager.wake_outbox();
+    }
+}
+
+fn internal_error(error: crate::Error, action: &str) -> StatusCode {
+    tracing::warn!(%error, action, "outbox operation failed");
+    StatusCode::INTERNAL_SERVER_ERROR
+}
+
+// ---------------------------------------------------------------------------
+// Handlers
+// ---------------------------------------------------------------------------
+
+/// `GET /messaging/outbox` — list queued and dead-lettered proactive messages.
+#[utoipa::path(
+    get,
+    path = "/messaging/outbox",
+    params(OutboxListQuery),
+    responses(
+        (status = 200, body = OutboxListResponse),
+        (status = 400, description = "Invalid status filter"),
+        (status = 503, description = "Outbox not initialized"),
+    ),
+    tag = "messaging",
+)]
+pub(super) async fn list_outbox(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<OutboxListQuery>,
+) -> Result<Json<OutboxListResponse>, StatusCode> {
+    let store = get_outbox_store(&state)?;
+    let status = parse_status(query.status.as_deref())?;
+
+    let entries = store
+        .list(status, query.limit.clamp(1, 500))
+        .await
+        .map_err(|error| internal_error(error, "list"))?;
+
+    Ok(Json(OutboxListResponse { entries }))
+}
+
+/// `DELETE /messaging/outbox` — purge messages, optionally only one status.
+#[utoipa::path(
+    delete,
+    path = "/messaging/outbox",
+    params(OutboxPurgeQuery),
+    responses(
+        (status = 200, body = OutboxActionResponse),
+        (status = 400, description = "Invalid status filter"),
+        (status = 503, description = "Outbox not initialized"),
+    ),
+    tag = "messaging",
+)]
+pub(super) async fn purge_outbox(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<OutboxPurgeQuery>,
+) -> Result<Json<OutboxActionResponse>, StatusCode> {
+    let store = get_outbox_store(&state)?;
+    let status = parse_status(query.status.as_deref())?;
+
+    let purged = store
+        .purge(status)
+        .await
+        .map_err(|error| internal_error(error, "purge"))?;
+
+    Ok(Json(OutboxActionResponse {
+        success: true,
+        message: format!("purged {purged} outbox message(s)"),
+    }))
+}
+
+/// `POST /messaging/outbox/replay` — requeue every dead-lettered message.
+#[utoipa::path(
+    post,
+    path = "/messaging/outbox/replay",
+    responses(
+        (status = 200, body = OutboxActionResponse),
+        (status = 503, description = "Outbox not initialized"),
+    ),
+    tag = "messaging",
+)]
+pub(super) async fn replay_dead_letters(
+    State(state): State<Arc<ApiState>>,
+) -> Result<Json<OutboxActionResponse>, StatusCode> {
+    let store = get_outbox_store(&state)?;
+
+    let replayed = store
+        .replay_dead_letters()
+        .await
+        .map_err(|error| internal_error(error, "replay_all"))?;
+    if replayed > 0 {
+        wake_outbox_worker(&state).await;
+    }
+
+    Ok(Json(OutboxActionResponse {
+        success: true,
+        message: format!("requeued {replayed} dead-lettered message(s)"),
+    }))
+}
+
+/// `POST /messaging/outbox/{id}/replay` — retry one dead-lettered message now
+/// with a fresh attempt budget.
+#[utoipa::path(
+    post,
+    path = "/messaging/outbox/{id}/replay",
+    params(
+        ("id" = String, Path, description = "Outbox message ID"),
+    ),
+    responses(
+        (status = 200, body = OutboxActionResponse),
+        (status = 404, description = "No dead-lettered message with this ID"),
+        (status = 503, description = "Outbox not initialized"),
+    ),
+    tag = "messaging",
+)]
+pub(super) async fn replay_outbox_message(
+    State(state): State<Arc<ApiState>>,
+    Path(id): Path<String>,
+) -> Result<Json<OutboxActionResponse>, StatusCode> {
+    let store = get_outbox_store(&state)?;
+
+    if !store
+        .replay(&id)
+        .await
+        .map_err(|error| internal_error(error, "replay"))?
+    {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    wake_outbox_worker(&state).await;
+
+    Ok(Json(OutboxActionResponse {
+        success: true,
+        message: format!("outbox message {id} requeued"),
+    }))
+}
+
+/// `DELETE /messaging/outbox/{id}` — drop one message without delivering it.
+#[utoipa::path(
+    delete,
+    path = "/messaging/outbox/{id}",
+    params(
+        ("id" = String, Path, description = "Outbox message ID"),
+    ),
+    responses(
+        (status = 200, body = OutboxActionResponse),
+        (status = 404, description = "Message not found"),
+        (status = 503, description = "Outbox not initialized"),
+    ),
+    tag = "messaging",
+)]
+pub(super) async fn delete_outbox_message(
+    State(state): State<Arc<ApiState>>,
+    Path(id): Path<String>,
+) -> Result<Json<OutboxActionResponse>, StatusCode> {
+    let store = get_outbox_store(&state)?;
+
+    if !store
+        .delete(&id)
+        .await
+        .map_err(|error| internal_error(error, "delete"))?
+    {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    Ok(Json(OutboxActionResponse {
+        success: true,
+        message: format!("outbox message {id} deleted"),
+    }))
+}
@@ -1381,6 +1381,8 @@ pub(super) async fn test_provider_model(
                         extra_headers: Vec::new(),
                         api_version: Some(api_version.trim().to_string()),
                         deployment: Some(deployment.trim().to_string()),
+                        bedrock: None,
+                        cassette: None,
                     },
                 );
                 providers
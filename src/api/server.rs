@@ -1,16 +1,20 @@
 //! HTTP server setup: router, static file serving, and API route wiring.
 
+use super::access::{
+    ApiAccessStore, ApiActor, ApiScope, agent_id_from_path, route_is_agent_scoped,
+};
 use super::state::ApiState;
 use super::{
-    agents, bindings, channels, config, cortex, cron, factory, ingest, links, mcp, memories,
-    messaging, models, opencode_proxy, portal, projects, providers, secrets, settings, skills, ssh,
-    system, tasks, tools, workers,
+    agents, auth, bindings, channels, config, cortex, cron, factory, ingest, links, logs, mcp,
+    memories, messaging, models, opencode_proxy, outbox, portal, projects, providers, secrets,
+    settings, skills, spend, ssh, system, tasks, tokens, tools, workers,
 };
 
 use axum::Json;
 use axum::Router;
-use axum::extract::{DefaultBodyLimit, Request, State};
-use axum::http::{StatusCode, Uri, header};
+use axum::body::Body;
+use axum::extract::{DefaultBodyLimit, Query, Request, State};
+use axum::http::{HeaderMap, Method, StatusCode, Uri, header};
 use axum::middleware::{self, Next};
 use axum::response::{Html, IntoResponse, Response};
 use axum::routing::any;
@@ -20,9 +24,13 @@ use tower_http::cors::CorsLayer;
 use utoipa::OpenApi;
 use utoipa_axum::{router::OpenApiRouter, routes};
 
+use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
 
+/// Request bodies larger than this are never buffered to look for an `agent_id`.
+const MAX_INSPECTED_BODY_BYTES: usize = 10 * 1024 * 1024;
+
 /// Embedded frontend assets from the Vite build output.
 #[derive(Embed)]
 #[folder = "interface/dist/"]
@@ -55,6 +63,8 @@ pub fn api_router() -> OpenApiRouter<Arc<ApiState>> {
         .routes(routes!(system::backup_export))
         .routes(routes!(system::backup_restore))
         .routes(routes!(system::events_sse))
+        .routes(routes!(logs::list_logs))
+        .routes(routes!(logs::logs_sse))
         // Agent routes
         .routes(routes!(agents::instance_overview))
         .routes(routes!(agents::list_agents))
@@ -99,6 +109,8 @@ pub fn api_router() -> OpenApiRouter<Arc<ApiState>> {
         // Worker routes
         .routes(routes!(workers::list_workers))
         .routes(routes!(workers::worker_detail))
+        .routes(routes!(workers::list_tool_approvals))
+        .routes(routes!(workers::resolve_tool_approval))
         // Memory routes
         .routes(routes!(memories::list_memories))
         .routes(routes!(memories::search_memories))
@@ -124,6 +136,9 @@ pub fn api_router() -> OpenApiRouter<Arc<ApiState>> {
         .routes(routes!(cron::cron_executions))
         .routes(routes!(cron::trigger_cron))
         .routes(routes!(cron::toggle_cron))
+        // Spend routes
+        .routes(routes!(spend::agent_spend))
+        .routes(routes!(spend::agent_spend_history))
         // Task routes
         .routes(routes!(tasks::list_tasks, tasks::create_task))
         .routes(routes!(
@@ -197,6 +212,10 @@ pub fn api_router() -> OpenApiRouter<Arc<ApiState>> {
             messaging::create_messaging_instance,
             messaging::delete_messaging_instance
         ))
+        .routes(routes!(outbox::list_outbox, outbox::purge_outbox))
+        .routes(routes!(outbox::replay_dead_letters))
+        .routes(routes!(outbox::replay_outbox_message))
+        .routes(routes!(outbox::delete_outbox_message))
         // Binding routes
         .routes(routes!(
             bindings::list_bindings,
@@ -239,6 +258,15 @@ pub fn api_router() -> OpenApiRouter<Arc<ApiState>> {
         // Factory routes
         .routes(routes!(factory::list_presets))
         .routes(routes!(factory::get_preset))
+        // Login routes
+        .routes(routes!(auth::auth_session))
+        .routes(routes!(auth::oidc_login))
+        .routes(routes!(auth::oidc_callback))
+        .routes(routes!(auth::logout))
+        // Token and audit routes
+        .routes(routes!(tokens::list_tokens, tokens::create_token))
+        .routes(routes!(tokens::revoke_token))
+        .routes(routes!(tokens::list_audit))
 }
 
 /// Start the HTTP server on the given address.
@@ -250,10 +278,10 @@ pub async fn start_http_server(
     state: Arc<ApiState>,
     shutdown_rx: tokio::sync::watch::Receiver<bool>,
 ) -> anyhow::Result<tokio::task::JoinHandle<()>> {
-    // Note: credentials are intentionally disabled. The API uses Bearer
-    // token auth (Authorization header), not cookies. Enabling credentials
-    // with mirror_request origin would allow any site to make credentialed
-    // cross-origin requests if cookie-based auth were ever added.
+    // Note: credentials are intentionally disabled. Scripts use Bearer token
+    // auth, and the SSO session cookie is only meant for the same-origin
+    // interface. Enabling credentials with mirror_request origin would let
+    // any site make cross-origin requests as the logged-in user.
     let cors = CorsLayer::new()
         .allow_origin(tower_http::cors::AllowOrigin::mirror_request())
         .allow_methods([
@@ -323,33 +351,194 @@ async fn api_auth_middleware(
     request: Request,
     next: Next,
 ) -> Response {
-    let Some(expected_token) = state.auth_token.as_deref() else {
+    let path = request.uri().path();
+    // Login routes authenticate on their own.
+    if path == "/api/health" || path == "/health" || path.starts_with("/api/auth/") {
         return next.run(request).await;
+    }
+
+    let access_store = state.api_access_store.load().as_ref().clone();
+    let actor = if state.auth_token.is_none() && state.oidc.is_none() {
+        ApiActor::Anonymous
+    } else {
+        match authenticate(&state, request.headers(), access_store.as_deref()).await {
+            Ok(Some(actor)) => actor,
+            Ok(None) => return unauthorized(),
+            Err(error) => {
+                tracing::warn!(%error, "api credential lookup failed");
+                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            }
+        }
     };
 
-    let path = request.uri().path();
-    if path == "/api/health" || path == "/health" {
-        return next.run(request).await;
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let mutating = !matches!(method, Method::GET | Method::HEAD | Method::OPTIONS);
+    let agent_restricted = matches!(&actor, ApiActor::Token(token) if !token.agent_ids.is_empty());
+    let (request, agent_ids) = if mutating || agent_restricted {
+        match request_agent_ids(request, mutating).await {
+            Ok(found) => found,
+            Err(response) => return response,
+        }
+    } else {
+        (request, Vec::new())
+    };
+    let agent_id = agent_ids.first().cloned();
+    let conflicting_agents = agent_ids.iter().any(|id| Some(id) != agent_id.as_ref());
+
+    let denied = match &actor {
+        ApiActor::Token(token) => {
+            let required = ApiScope::required_for(&method, &path);
+            if !token.allows(required) {
+                Some(format!(
+                    "token '{}' needs the '{required}' scope for this request",
+                    token.name
+                ))
+            } else if conflicting_agents {
+                Some(format!(
+                    "request names more than one agent ({}); name exactly one",
+                    agent_ids.join(", ")
+                ))
+            } else if !token.agent_ids.is_empty() && !route_is_agent_scoped(&method, &path) {
+                Some(format!(
+                    "token '{}' is limited to agents {}; this route is not scoped to one agent",
+                    token.name,
+                    token.agent_ids.join(", ")
+                ))
+            } else if !token.allows_agent(agent_id.as_deref()) {
+                Some(format!(
+                    "token '{}' is limited to agents {}; name one with agent_id",
+                    token.name,
+                    token.agent_ids.join(", ")
+                ))
+            } else {
+                None
+            }
+        }
+        ApiActor::Session(session) => {
+            let required = ApiScope::required_for(&method, &path);
+            let csrf_token = request
+                .headers()
+                .get(auth::CSRF_HEADER)
+                .and_then(|value| value.to_str().ok());
+            if mutating && !session.csrf_matches(csrf_token) {
+                Some("missing or invalid CSRF token".to_string())
+            } else if !session.allows(required) {
+                Some(format!(
+                    "{} needs the '{required}' scope for this request",
+                    session.user()
+                ))
+            } else {
+                None
+            }
+        }
+        ApiActor::Root | ApiActor::Anonymous => None,
+    };
+
+    let response = match denied {
+        Some(message) => (StatusCode::FORBIDDEN, Json(json!({"error": message}))).into_response(),
+        None => next.run(request).await,
+    };
+
+    if mutating
+        && let Some(store) = &access_store
+        && let Err(error) = store
+            .record_audit(
+                &actor,
+                method.as_str(),
+                &path,
+                agent_id.as_deref(),
+                response.status().as_u16(),
+            )
+            .await
+    {
+        tracing::warn!(%error, %path, "failed to write audit log entry");
     }
 
-    let is_authorized = request
-        .headers()
+    response
+}
+
+/// Resolve the caller from a bearer token, or else from an SSO session
+/// cookie. `None` means the presented credential is unknown or missing.
+async fn authenticate(
+    state: &ApiState,
+    headers: &HeaderMap,
+    access_store: Option<&ApiAccessStore>,
+) -> crate::error::Result<Option<ApiActor>> {
+    let bearer = headers
         .get(header::AUTHORIZATION)
         .and_then(|value| value.to_str().ok())
-        .and_then(|value| value.strip_prefix("Bearer "))
-        .is_some_and(|token| token == expected_token);
+        .and_then(|value| value.strip_prefix("Bearer "));
 
-    if is_authorized {
-        next.run(request).await
-    } else {
-        (
-            StatusCode::UNAUTHORIZED,
-            Json(json!({"error": "unauthorized"})),
-        )
-            .into_response()
+    if let Some(token) = bearer {
+        if state.auth_token.as_deref() == Some(token) {
+            return Ok(Some(ApiActor::Root));
+        }
+        return match access_store {
+            Some(store) => Ok(store.authenticate(token).await?.map(ApiActor::Token)),
+            None => Ok(None),
+        };
+    }
+
+    match access_store {
+        Some(store) if state.oidc.is_some() => Ok(auth::current_session(store, headers)
+            .await?
+            .map(ApiActor::Session)),
+        _ => Ok(None),
     }
 }
 
+fn unauthorized() -> Response {
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(json!({"error": "unauthorized"})),
+    )
+        .into_response()
+}
+
+/// Every agent a request names: the `agent_id` query parameter, an agent ID
+/// in the path, and (when `inspect_body` is set) a top-level `agent_id` in a
+/// JSON body. The body is buffered and handed back on the returned request.
+///
+/// All of them are returned so the caller can refuse requests that name one
+/// agent to pass the check and act on another.
+async fn request_agent_ids(
+    request: Request,
+    inspect_body: bool,
+) -> Result<(Request, Vec<String>), Response> {
+    let mut agent_ids = Vec::new();
+    if let Some(agent_id) = Query::<HashMap<String, String>>::try_from_uri(request.uri())
+        .ok()
+        .and_then(|Query(mut query)| query.remove("agent_id"))
+    {
+        agent_ids.push(agent_id);
+    }
+    if let Some(agent_id) = agent_id_from_path(request.uri().path()) {
+        agent_ids.push(agent_id.to_string());
+    }
+
+    let is_json = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
+    if !inspect_body || !is_json {
+        return Ok((request, agent_ids));
+    }
+
+    let (parts, body) = request.into_parts();
+    let bytes = axum::body::to_bytes(body, MAX_INSPECTED_BODY_BYTES)
+        .await
+        .map_err(|_| StatusCode::PAYLOAD_TOO_LARGE.into_response())?;
+    if let Some(agent_id) = serde_json::from_slice::<serde_json::Value>(&bytes)
+        .ok()
+        .and_then(|value| value.get("agent_id")?.as_str().map(str::to_string))
+    {
+        agent_ids.push(agent_id);
+    }
+    Ok((Request::from_parts(parts, Body::from(bytes)), agent_ids))
+}
+
 #[cfg(feature = "metrics")]
 async fn metrics_middleware(request: Request, next: Next) -> Response {
     let method = request.method().to_string();
@@ -459,3 +648,149 @@ async fn static_handler(uri: Uri) -> Response {
 
     (StatusCode::NOT_FOUND, "not found").into_response()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::routing::{get, post};
+
+    /// Serve a stub API behind the auth middleware, with one token limited
+    /// to agent `main` for each scope set in `scopes`.
+    async fn spawn_api(scopes: &[&[ApiScope]]) -> (String, Vec<String>) {
+        let (provider_setup_tx, _provider_setup_rx) = tokio::sync::mpsc::channel(1);
+        let (agent_tx, _agent_rx) = tokio::sync::mpsc::channel(1);
+        let (agent_remove_tx, _agent_remove_rx) = tokio::sync::mpsc::channel(1);
+        let (injection_tx, _injection_rx) = tokio::sync::mpsc::channel(1);
+        let mut state = ApiState::new_with_provider_sender(
+            provider_setup_tx,
+            agent_tx,
+            agent_remove_tx,
+            injection_tx,
+        );
+        state.auth_token = Some("root-token".into());
+
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("in-memory sqlite should connect");
+        sqlx::migrate!("./migrations/api")
+            .run(&pool)
+            .await
+            .expect("api migrations should apply");
+        let store = Arc::new(ApiAccessStore::new(pool));
+        let mut secrets = Vec::new();
+        for (index, scopes) in scopes.iter().enumerate() {
+            let created = store
+                .create_token(&format!("token-{index}"), scopes, &["main".into()], None)
+                .await
+                .unwrap();
+            secrets.push(created.secret);
+        }
+        state.set_api_access_store(store);
+        let state = Arc::new(state);
+
+        let app = Router::new()
+            .route("/api/agents/cron", post(|| async { StatusCode::OK }))
+            .route(
+                "/api/agents/{agent_id}/spend",
+                get(|| async { StatusCode::OK }),
+            )
+            .route("/api/messaging/outbox", get(|| async { StatusCode::OK }))
+            .route(
+                "/api/messaging/outbox/{id}/replay",
+                post(|| async { StatusCode::OK }),
+            )
+            .route(
+                "/api/tasks/{number}/approve",
+                post(|| async { StatusCode::OK }),
+            )
+            .route(
+                "/api/agents/workers/approvals/{approval_id}",
+                post(|| async { StatusCode::OK }),
+            )
+            .layer(middleware::from_fn_with_state(state, api_auth_middleware));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.ok();
+        });
+        (format!("http://{address}"), secrets)
+    }
+
+    async fn post_cron(base: &str, secret: &str, query_agent: &str, body_agent: &str) -> u16 {
+        reqwest::Client::new()
+            .post(format!("{base}/api/agents/cron?agent_id={query_agent}"))
+            .bearer_auth(secret)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(format!(r#"{{"agent_id":"{body_agent}","id":"nightly"}}"#))
+            .send()
+            .await
+            .unwrap()
+            .status()
+            .as_u16()
+    }
+
+    #[tokio::test]
+    async fn agent_limited_tokens_cannot_reach_other_agents() {
+        let (base, secrets) = spawn_api(&[&[ApiScope::Read], &[ApiScope::Agents]]).await;
+        let (read_only, agents) = (&secrets[0], &secrets[1]);
+
+        assert_eq!(post_cron(&base, read_only, "main", "main").await, 403);
+        assert_eq!(post_cron(&base, agents, "main", "other").await, 403);
+        assert_eq!(post_cron(&base, agents, "main", "main").await, 200);
+
+        let spend = |path: &str| {
+            reqwest::Client::new()
+                .get(format!("{base}{path}"))
+                .bearer_auth(agents)
+                .send()
+        };
+        let status = spend("/api/agents/other/spend?agent_id=main")
+            .await
+            .unwrap();
+        assert_eq!(status.status().as_u16(), 403);
+        let status = spend("/api/agents/main/spend").await.unwrap();
+        assert_eq!(status.status().as_u16(), 200);
+    }
+
+    #[tokio::test]
+    async fn agent_limited_tokens_cannot_use_instance_wide_routes() {
+        let (base, secrets) = spawn_api(&[&[ApiScope::Agents, ApiScope::Messaging]]).await;
+        let (client, secret) = (reqwest::Client::new(), &secrets[0]);
+        let send = |request: reqwest::RequestBuilder| async move {
+            request
+                .bearer_auth(secret)
+                .header(header::CONTENT_TYPE, "application/json")
+                .send()
+                .await
+                .unwrap()
+                .status()
+                .as_u16()
+        };
+
+        // Outbox rows and task numbers are instance-wide, so naming an allowed
+        // agent does not limit what these routes touch.
+        let outbox = client.get(format!("{base}/api/messaging/outbox?agent_id=main"));
+        assert_eq!(send(outbox).await, 403);
+        let replay = client.post(format!(
+            "{base}/api/messaging/outbox/42/replay?agent_id=main"
+        ));
+        assert_eq!(send(replay).await, 403);
+        let approve = client
+            .post(format!("{base}/api/tasks/7/approve"))
+            .body(r#"{"agent_id":"main"}"#);
+        assert_eq!(send(approve).await, 403);
+
+        // Tool approvals live in a per-agent registry.
+        let approval = format!("{base}/api/agents/workers/approvals/abc");
+        let other = client
+            .post(&approval)
+            .body(r#"{"agent_id":"other","decision":"approved"}"#);
+        assert_eq!(send(other).await, 403);
+        let own = client
+            .post(&approval)
+            .body(r#"{"agent_id":"main","decision":"approved"}"#);
+        assert_eq!(send(own).await, 200);
+    }
+}
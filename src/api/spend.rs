@@ -0,0 +1,163 @@
+//! Spend API endpoints: current budget status and daily spend history.
+
+use super::state::ApiState;
+
+use crate::config::BudgetLimits;
+use crate::llm::budget::{
+    BreachSeverity, BudgetPeriod, ChannelSpend, DailySpend, SpendTracker, evaluate_limits,
+};
+
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct SpendSummaryResponse {
+    agent_id: String,
+    /// Current day in the agent's timezone (`YYYY-MM-DD`).
+    day: String,
+    daily_spent_usd: f64,
+    monthly_spent_usd: f64,
+    daily_limit_usd: Option<f64>,
+    monthly_limit_usd: Option<f64>,
+    /// `ok`, `soft_limit`, or `hard_limit` for the agent-wide budget.
+    status: String,
+    /// Period of the limit driving `status`, if any.
+    limiting_period: Option<BudgetPeriod>,
+    /// Month-to-date spend per channel, most expensive first.
+    channels: Vec<ChannelSpend>,
+}
+
+#[derive(Deserialize, utoipa::ToSchema, utoipa::IntoParams)]
+pub(super) struct SpendHistoryQuery {
+    #[serde(default = "default_history_days")]
+    days: i64,
+}
+
+fn default_history_days() -> i64 {
+    30
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct SpendHistoryResponse {
+    agent_id: String,
+    days: Vec<DailySpend>,
+}
+
+async fn spend_tracker(state: &ApiState, agent_id: &str) -> Result<Arc<SpendTracker>, StatusCode> {
+    let manager_guard = state.llm_manager.read().await;
+    let manager = manager_guard
+        .as_ref()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    manager.spend_tracker(agent_id).ok_or(StatusCode::NOT_FOUND)
+}
+
+/// Get today's and this month's spend for an agent against its budget.
+#[utoipa::path(
+    get,
+    path = "/agents/{agent_id}/spend",
+    params(
+        ("agent_id" = String, Path, description = "Agent ID"),
+    ),
+    responses(
+        (status = 200, body = SpendSummaryResponse),
+        (status = 404, description = "Agent not found"),
+        (status = 500, description = "Internal server error"),
+        (status = 503, description = "LLM manager not initialized"),
+    ),
+    tag = "spend",
+)]
+pub(super) async fn agent_spend(
+    State(state): State<Arc<ApiState>>,
+    Path(agent_id): Path<String>,
+) -> Result<Json<SpendSummaryResponse>, StatusCode> {
+    let tracker = spend_tracker(&state, &agent_id).await?;
+    let config = tracker.config();
+    let day = tracker.today();
+    let month_start = tracker.month_start();
+
+    let ledger_error = |error: crate::error::Error| {
+        tracing::warn!(%error, %agent_id, "failed to read spend ledger");
+        StatusCode::INTERNAL_SERVER_ERROR
+    };
+    let daily_spent_usd = tracker
+        .spent_since(&day, None)
+        .await
+        .map_err(ledger_error)?;
+    let monthly_spent_usd = tracker
+        .spent_since(&month_start, None)
+        .await
+        .map_err(ledger_error)?;
+    let channels = tracker
+        .channel_totals(&month_start)
+        .await
+        .map_err(ledger_error)?;
+
+    let BudgetLimits {
+        daily_usd,
+        monthly_usd,
+    } = config.limits;
+    let breach = evaluate_limits(
+        config.limits,
+        daily_spent_usd,
+        monthly_spent_usd,
+        config.soft_limit_ratio,
+    );
+    let status = match breach.map(|breach| breach.severity) {
+        None => "ok",
+        Some(BreachSeverity::Soft) => "soft_limit",
+        Some(BreachSeverity::Hard) => "hard_limit",
+    };
+
+    Ok(Json(SpendSummaryResponse {
+        agent_id,
+        day,
+        daily_spent_usd,
+        monthly_spent_usd,
+        daily_limit_usd: daily_usd,
+        monthly_limit_usd: monthly_usd,
+        status: status.to_string(),
+        limiting_period: breach.map(|breach| breach.period),
+        channels,
+    }))
+}
+
+/// Get per-day spend totals for an agent.
+#[utoipa::path(
+    get,
+    path = "/agents/{agent_id}/spend/history",
+    params(
+        ("agent_id" = String, Path, description = "Agent ID"),
+        ("days" = i64, Query, description = "Number of days to include, counting today (default 30, max 365)"),
+    ),
+    responses(
+        (status = 200, body = SpendHistoryResponse),
+        (status = 404, description = "Agent not found"),
+        (status = 500, description = "Internal server error"),
+        (status = 503, description = "LLM manager not initialized"),
+    ),
+    tag = "spend",
+)]
+pub(super) async fn agent_spend_history(
+    State(state): State<Arc<ApiState>>,
+    Path(agent_id): Path<String>,
+    Query(query): Query<SpendHistoryQuery>,
+) -> Result<Json<SpendHistoryResponse>, StatusCode> {
+    let tracker = spend_tracker(&state, &agent_id).await?;
+
+    let today = NaiveDate::parse_from_str(&tracker.today(), "%Y-%m-%d")
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let since = today - Duration::days(query.days.clamp(1, 365) - 1);
+    let days = tracker
+        .daily_totals(&since.format("%Y-%m-%d").to_string())
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, %agent_id, "failed to read spend history");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(SpendHistoryResponse { agent_id, days }))
+}
@@ -3,6 +3,8 @@
 use crate::agent::channel::ChannelState;
 use crate::agent::cortex_chat::CortexChatSession;
 use crate::agent::status::StatusBlock;
+use crate::api::access::ApiAccessStore;
+use crate::api::oidc::OidcClient;
 use crate::config::{
     Binding, DefaultsConfig, DiscordPermissions, RuntimeConfig, SignalPermissions, SlackPermissions,
 };
@@ -12,6 +14,7 @@ use crate::llm::LlmManager;
 use crate::mcp::McpManager;
 use crate::memory::{EmbeddingModel, MemorySearch};
 use crate::messaging::MessagingManager;
+use crate::messaging::outbox::OutboxStore;
 use crate::messaging::portal::PortalAdapter;
 use crate::projects::ProjectStore;
 use crate::prompts::PromptEngine;
@@ -51,6 +54,8 @@ pub struct AgentInfo {
 pub struct ApiState {
     pub started_at: Instant,
     pub auth_token: Option<String>,
+    /// Single sign-on for the web interface, when `api.oidc` is configured.
+    pub oidc: Option<Arc<OidcClient>>,
     /// Aggregated event stream from all agents. SSE clients subscribe here.
     pub event_tx: broadcast::Sender<ApiEvent>,
     /// Per-agent SQLite pools for querying channel/conversation data.
@@ -84,6 +89,10 @@ pub struct ApiState {
     pub cron_schedulers: arc_swap::ArcSwap<HashMap<String, Arc<Scheduler>>>,
     /// Instance-level global task store shared across all agents.
     pub task_store: ArcSwap<Option<Arc<TaskStore>>>,
+    /// Instance-level outbox of queued and dead-lettered proactive messages.
+    pub outbox_store: ArcSwap<Option<Arc<OutboxStore>>>,
+    /// Named API tokens and the audit log. `None` leaves only the root token.
+    pub api_access_store: ArcSwap<Option<Arc<ApiAccessStore>>>,
     /// Per-agent project stores for project/repo/worktree CRUD operations.
     pub project_stores: arc_swap::ArcSwap<HashMap<String, Arc<ProjectStore>>>,
     /// Per-agent RuntimeConfig for reading live hot-reloaded configuration.
@@ -92,6 +101,9 @@ pub struct ApiState {
     pub mcp_managers: ArcSwap<HashMap<String, Arc<McpManager>>>,
     /// Per-agent sandbox instances for process containment.
     pub sandboxes: ArcSwap<HashMap<String, Arc<crate::sandbox::Sandbox>>>,
+    /// Per-agent registries of worker tool calls waiting for approval.
+    pub tool_approvals:
+        ArcSwap<HashMap<String, Arc<crate::agent::tool_approval::ToolApprovalRegistry>>>,
     /// Instance-level secrets store (shared across all agents).
     pub secrets_store: ArcSwap<Option<Arc<crate::secrets::store::SecretsStore>>>,
     /// Shared reference to the Discord permissions ArcSwap (same instance used by the adapter and file watcher).
@@ -140,6 +152,9 @@ pub struct ApiState {
     /// Serializes SSH daemon enable/disable transitions to prevent races
     /// between overlapping toggle requests.
     pub ssh_mutex: tokio::sync::Mutex<()>,
+    /// In-memory tracing ring buffer behind `/api/logs`. `None` when tracing
+    /// was initialized without the buffer layer.
+    pub log_buffer: Option<crate::api::LogBuffer>,
 }
 
 /// Events sent to SSE clients. Wraps ProcessEvents with agent context.
@@ -235,6 +250,16 @@ pub enum ApiEvent {
         tool_name: String,
         result: String,
     },
+    /// A line of live output from a running tool call.
+    ToolOutput {
+        agent_id: String,
+        channel_id: Option<String>,
+        process_type: String,
+        process_id: String,
+        tool_name: String,
+        line: String,
+        stream: String,
+    },
     /// Configuration was reloaded (skills, identity, etc.).
     ConfigReloaded,
     /// A message was sent from one agent to another.
@@ -271,6 +296,26 @@ pub enum ApiEvent {
         worker_id: String,
         text: String,
     },
+    /// A worker tool call is paused until someone approves or denies it.
+    ToolApprovalRequested {
+        agent_id: String,
+        worker_id: String,
+        channel_id: Option<String>,
+        approval_id: String,
+        tool_name: String,
+        args: String,
+        timeout_secs: u64,
+    },
+    /// A paused worker tool call was approved, denied, or timed out.
+    ToolApprovalResolved {
+        agent_id: String,
+        worker_id: String,
+        channel_id: Option<String>,
+        approval_id: String,
+        tool_name: String,
+        decision: crate::agent::tool_approval::ToolApprovalDecision,
+        resolved_by: Option<String>,
+    },
     /// A cortex chat auto-triggered response (e.g. after a worker result was
     /// delivered). The frontend appends this as a new assistant message.
     CortexChatMessage {
@@ -292,6 +337,7 @@ impl ApiState {
         Self {
             started_at: Instant::now(),
             auth_token: None,
+            oidc: None,
             event_tx,
             agent_pools: arc_swap::ArcSwap::from_pointee(HashMap::new()),
             agent_configs: arc_swap::ArcSwap::from_pointee(Vec::new()),
@@ -307,10 +353,13 @@ impl ApiState {
             cron_stores: arc_swap::ArcSwap::from_pointee(HashMap::new()),
             cron_schedulers: arc_swap::ArcSwap::from_pointee(HashMap::new()),
             task_store: ArcSwap::from_pointee(None),
+            outbox_store: ArcSwap::from_pointee(None),
+            api_access_store: ArcSwap::from_pointee(None),
             project_stores: arc_swap::ArcSwap::from_pointee(HashMap::new()),
             runtime_configs: ArcSwap::from_pointee(HashMap::new()),
             mcp_managers: ArcSwap::from_pointee(HashMap::new()),
             sandboxes: ArcSwap::from_pointee(HashMap::new()),
+            tool_approvals: ArcSwap::from_pointee(HashMap::new()),
             secrets_store: ArcSwap::from_pointee(None),
             discord_permissions: RwLock::new(None),
             slack_permissions: RwLock::new(None),
@@ -333,6 +382,7 @@ impl ApiState {
             agent_humans: ArcSwap::from_pointee(Vec::new()),
             live_worker_transcripts: Arc::new(RwLock::new(HashMap::new())),
             ssh_mutex: tokio::sync::Mutex::new(()),
+            log_buffer: None,
         }
     }
 
@@ -555,6 +605,29 @@ impl ApiState {
                                     })
                                     .ok();
                             }
+                            ProcessEvent::ToolOutput {
+                                process_id,
+                                channel_id,
+                                tool_name,
+                                line,
+                                stream,
+                                ..
+                            } => {
+                                // Not accumulated into the live transcript; the
+                                // full output arrives with ToolCompleted.
+                                let (process_type, id_str) = process_id_info(process_id);
+                                api_tx
+                                    .send(ApiEvent::ToolOutput {
+                                        agent_id: agent_id.clone(),
+                                        channel_id: channel_id.as_deref().map(|s| s.to_string()),
+                                        process_type,
+                                        process_id: id_str,
+                                        tool_name: tool_name.clone(),
+                                        line: line.clone(),
+                                        stream: stream.clone(),
+                                    })
+                                    .ok();
+                            }
                             ProcessEvent::AgentMessageSent {
                                 from_agent_id,
                                 to_agent_id,
@@ -649,6 +722,60 @@ impl ApiState {
                                     })
                                     .ok();
                             }
+                            ProcessEvent::ToolApprovalRequested {
+                                worker_id,
+                                channel_id,
+                                approval_id,
+                                tool_name,
+                                args,
+                                timeout_secs,
+                                ..
+                            } => {
+                                api_tx
+                                    .send(ApiEvent::ToolApprovalRequested {
+                                        agent_id: agent_id.clone(),
+                                        worker_id: worker_id.to_string(),
+                                        channel_id: channel_id.as_deref().map(|s| s.to_string()),
+                                        approval_id: approval_id.clone(),
+                                        tool_name: tool_name.clone(),
+                                        args: args.clone(),
+                                        timeout_secs: *timeout_secs,
+                                    })
+                                    .ok();
+                            }
+                            ProcessEvent::ToolApprovalResolved {
+                                worker_id,
+                                channel_id,
+                                approval_id,
+                                tool_name,
+                                decision,
+                                resolved_by,
+                                ..
+                            } => {
+                                let step = TranscriptStep::ToolApproval {
+                                    call_id: String::new(),
+                                    name: tool_name.clone(),
+                                    decision: *decision,
+                                    resolved_by: resolved_by.clone(),
+                                };
+                                let mut guard = live_transcripts.write().await;
+                                if let Some(steps) = guard.get_mut(&worker_id.to_string()) {
+                                    steps.push(step);
+                                }
+                                drop(guard);
+
+                                api_tx
+                                    .send(ApiEvent::ToolApprovalResolved {
+                                        agent_id: agent_id.clone(),
+                                        worker_id: worker_id.to_string(),
+                                        channel_id: channel_id.as_deref().map(|s| s.to_string()),
+                                        approval_id: approval_id.clone(),
+                                        tool_name: tool_name.clone(),
+                                        decision: *decision,
+                                        resolved_by: resolved_by.clone(),
+                                    })
+                                    .ok();
+                            }
                             ProcessEvent::CortexChatUpdate {
                                 thread_id,
                                 content,
@@ -749,6 +876,16 @@ impl ApiState {
         self.task_store.store(Arc::new(Some(store)));
     }
 
+    /// Set the messaging outbox store.
+    pub fn set_outbox_store(&self, store: Arc<OutboxStore>) {
+        self.outbox_store.store(Arc::new(Some(store)));
+    }
+
+    /// Set the API token and audit log store.
+    pub fn set_api_access_store(&self, store: Arc<ApiAccessStore>) {
+        self.api_access_store.store(Arc::new(Some(store)));
+    }
+
     /// Set the project stores for all agents.
     pub fn set_project_stores(&self, stores: HashMap<String, Arc<ProjectStore>>) {
         self.project_stores.store(Arc::new(stores));
@@ -769,6 +906,14 @@ impl ApiState {
         self.sandboxes.store(Arc::new(sandboxes));
     }
 
+    /// Set the tool approval registries for all agents.
+    pub fn set_tool_approvals(
+        &self,
+        registries: HashMap<String, Arc<crate::agent::tool_approval::ToolApprovalRegistry>>,
+    ) {
+        self.tool_approvals.store(Arc::new(registries));
+    }
+
     /// Set the instance-level secrets store.
     pub fn set_secrets_store(&self, store: Arc<crate::secrets::store::SecretsStore>) {
         self.secrets_store.store(Arc::new(Some(store)));
@@ -126,12 +126,15 @@ pub(super) async fn events_sse(
                             ApiEvent::BranchCompleted { .. } => "branch_completed",
                             ApiEvent::ToolStarted { .. } => "tool_started",
                             ApiEvent::ToolCompleted { .. } => "tool_completed",
+                            ApiEvent::ToolOutput { .. } => "tool_output",
                             ApiEvent::ConfigReloaded => "config_reloaded",
                             ApiEvent::AgentMessageSent { .. } => "agent_message_sent",
                             ApiEvent::AgentMessageReceived { .. } => "agent_message_received",
                             ApiEvent::TaskUpdated { .. } => "task_updated",
                             ApiEvent::OpenCodePartUpdated { .. } => "opencode_part_updated",
                             ApiEvent::WorkerText { .. } => "worker_text",
+                            ApiEvent::ToolApprovalRequested { .. } => "tool_approval_requested",
+                            ApiEvent::ToolApprovalResolved { .. } => "tool_approval_resolved",
                             ApiEvent::CortexChatMessage { .. } => "cortex_chat_message",
                         };
                         yield Ok(axum::response::sse::Event::default()
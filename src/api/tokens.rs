@@ -0,0 +1,267 @@
+use super::access::{ApiAccessStore, ApiScope, ApiToken, AuditEntry, CreatedApiToken};
+use super::state::ApiState;
+
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Longest token name accepted.
+const MAX_TOKEN_NAME_LENGTH: usize = 64;
+
+// ---------------------------------------------------------------------------
+// Request / response types
+// ---------------------------------------------------------------------------
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub(super) struct CreateTokenRequest {
+    name: String,
+    scopes: Vec<ApiScope>,
+    /// Agents the token may act on. Omit for every agent.
+    #[serde(default)]
+    agent_ids: Vec<String>,
+    /// Days until the token expires. Omit for a token that never expires.
+    #[serde(default)]
+    expires_in_days: Option<u32>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct TokenListResponse {
+    tokens: Vec<ApiToken>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct TokenActionResponse {
+    success: bool,
+    message: String,
+}
+
+#[derive(Deserialize, utoipa::ToSchema, utoipa::IntoParams)]
+pub(super) struct AuditQuery {
+    /// Only calls made with this token.
+    #[serde(default)]
+    token_id: Option<String>,
+    /// Only calls that named this agent.
+    #[serde(default)]
+    agent_id: Option<String>,
+    /// Only entries older than this ID, for paging.
+    #[serde(default)]
+    before: Option<i64>,
+    #[serde(default = "default_audit_limit")]
+    limit: i64,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct AuditResponse {
+    entries: Vec<AuditEntry>,
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn default_audit_limit() -> i64 {
+    100
+}
+
+/// Extract the access store, returning 503 if not yet initialized.
+fn get_access_store(state: &ApiState) -> Result<Arc<ApiAccessStore>, Response> {
+    state
+        .api_access_store
+        .load()
+        .as_ref()
+        .clone()
+        .ok_or_else(|| {
+            error_response(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "token store not initialized",
+            )
+        })
+}
+
+fn error_response(status: StatusCode, message: impl Into<String>) -> Response {
+    (status, Json(serde_json::json!({"error": message.into()}))).into_response()
+}
+
+fn internal_error(error: crate::Error, action: &str) -> Response {
+    tracing::warn!(%error, action, "api token operation failed");
+    error_response(StatusCode::INTERNAL_SERVER_ERROR, error.to_string())
+}
+
+// ---------------------------------------------------------------------------
+// Handlers
+// ---------------------------------------------------------------------------
+
+/// `GET /tokens` — list named API tokens. Secrets are never returned.
+#[utoipa::path(
+    get,
+    path = "/tokens",
+    responses(
+        (status = 200, body = TokenListResponse),
+        (status = 503, description = "Token store not initialized"),
+    ),
+    tag = "tokens",
+)]
+pub(super) async fn list_tokens(State(state): State<Arc<ApiState>>) -> Response {
+    let store = match get_access_store(&state) {
+        Ok(store) => store,
+        Err(response) => return response,
+    };
+
+    match store.list_tokens().await {
+        Ok(tokens) => Json(TokenListResponse { tokens }).into_response(),
+        Err(error) => internal_error(error, "list"),
+    }
+}
+
+/// `POST /tokens` — create a named token. The secret is only returned here.
+#[utoipa::path(
+    post,
+    path = "/tokens",
+    request_body = CreateTokenRequest,
+    responses(
+        (status = 200, body = CreatedApiToken),
+        (status = 400, description = "Invalid name, scopes or agents, or no root token configured"),
+        (status = 503, description = "Token store not initialized"),
+    ),
+    tag = "tokens",
+)]
+pub(super) async fn create_token(
+    State(state): State<Arc<ApiState>>,
+    Json(request): Json<CreateTokenRequest>,
+) -> Response {
+    let store = match get_access_store(&state) {
+        Ok(store) => store,
+        Err(response) => return response,
+    };
+
+    // Without a root token or SSO the API is open, and named tokens would
+    // restrict nothing.
+    if state.auth_token.is_none() && state.oidc.is_none() {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            "set api.auth_token or api.oidc before creating named tokens",
+        );
+    }
+
+    let name = request.name.trim();
+    if name.is_empty() || name.len() > MAX_TOKEN_NAME_LENGTH {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            format!("token name must be 1-{MAX_TOKEN_NAME_LENGTH} characters"),
+        );
+    }
+    if request.scopes.is_empty() {
+        return error_response(StatusCode::BAD_REQUEST, "at least one scope is required");
+    }
+    let mut scopes = request.scopes;
+    scopes.sort_by_key(|scope| ApiScope::ALL.iter().position(|known| known == scope));
+    scopes.dedup();
+
+    let known_agents = state.agent_configs.load();
+    if let Some(unknown) = request
+        .agent_ids
+        .iter()
+        .find(|agent_id| !known_agents.iter().any(|agent| &agent.id == *agent_id))
+    {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            format!("unknown agent '{unknown}'"),
+        );
+    }
+
+    let expires_at = request
+        .expires_in_days
+        .map(|days| chrono::Utc::now() + chrono::Duration::days(i64::from(days)));
+
+    match store
+        .create_token(name, &scopes, &request.agent_ids, expires_at)
+        .await
+    {
+        Ok(created) => {
+            tracing::info!(
+                token_id = %created.token.id,
+                name = %created.token.name,
+                "api token created"
+            );
+            Json(created).into_response()
+        }
+        Err(error) => internal_error(error, "create"),
+    }
+}
+
+/// `DELETE /tokens/{id}` — revoke a token. It stays listed for the audit trail.
+#[utoipa::path(
+    delete,
+    path = "/tokens/{id}",
+    params(
+        ("id" = String, Path, description = "Token ID"),
+    ),
+    responses(
+        (status = 200, body = TokenActionResponse),
+        (status = 404, description = "Token not found or already revoked"),
+        (status = 503, description = "Token store not initialized"),
+    ),
+    tag = "tokens",
+)]
+pub(super) async fn revoke_token(
+    State(state): State<Arc<ApiState>>,
+    Path(id): Path<String>,
+) -> Response {
+    let store = match get_access_store(&state) {
+        Ok(store) => store,
+        Err(response) => return response,
+    };
+
+    match store.revoke_token(&id).await {
+        Ok(true) => {
+            tracing::info!(token_id = %id, "api token revoked");
+            Json(TokenActionResponse {
+                success: true,
+                message: format!("token {id} revoked"),
+            })
+            .into_response()
+        }
+        Ok(false) => error_response(
+            StatusCode::NOT_FOUND,
+            format!("no active token with ID {id}"),
+        ),
+        Err(error) => internal_error(error, "revoke"),
+    }
+}
+
+/// `GET /audit` — mutating API calls, newest first.
+#[utoipa::path(
+    get,
+    path = "/audit",
+    params(AuditQuery),
+    responses(
+        (status = 200, body = AuditResponse),
+        (status = 503, description = "Token store not initialized"),
+    ),
+    tag = "tokens",
+)]
+pub(super) async fn list_audit(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<AuditQuery>,
+) -> Response {
+    let store = match get_access_store(&state) {
+        Ok(store) => store,
+        Err(response) => return response,
+    };
+
+    match store
+        .list_audit(
+            query.token_id.as_deref(),
+            query.agent_id.as_deref(),
+            query.before,
+            query.limit.clamp(1, 500),
+        )
+        .await
+    {
+        Ok(entries) => Json(AuditResponse { entries }).into_response(),
+        Err(error) => internal_error(error, "audit"),
+    }
+}
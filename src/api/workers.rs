@@ -1,12 +1,14 @@
-//! Workers API endpoints: list and detail views for worker runs.
+//! Workers API endpoints: list and detail views for worker runs, and
+//! decisions on tool calls paused for approval.
 
 use super::state::ApiState;
 
+use crate::agent::tool_approval::{PendingToolApproval, ToolApprovalDecision};
 use crate::conversation::history::ProcessRunLogger;
 use crate::conversation::worker_transcript;
 
 use axum::Json;
-use axum::extract::{Query, State};
+use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -243,3 +245,84 @@ pub(super) async fn worker_detail(
         directory: detail.directory,
     }))
 }
+
+#[derive(Deserialize, utoipa::ToSchema, utoipa::IntoParams)]
+pub(super) struct ToolApprovalListQuery {
+    agent_id: String,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub(super) struct ToolApprovalListResponse {
+    approvals: Vec<PendingToolApproval>,
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub(super) struct ToolApprovalDecisionRequest {
+    agent_id: String,
+    /// `approved` or `denied`.
+    decision: ToolApprovalDecision,
+    /// Shown in the channel and the worker transcript. Defaults to "dashboard".
+    resolved_by: Option<String>,
+}
+
+/// List worker tool calls currently waiting for approval.
+#[utoipa::path(
+    get,
+    path = "/agents/workers/approvals",
+    params(
+        ("agent_id" = String, Query, description = "Agent ID"),
+    ),
+    responses(
+        (status = 200, body = ToolApprovalListResponse),
+        (status = 404, description = "Agent not found"),
+    ),
+    tag = "workers",
+)]
+pub(super) async fn list_tool_approvals(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<ToolApprovalListQuery>,
+) -> Result<Json<ToolApprovalListResponse>, StatusCode> {
+    let registries = state.tool_approvals.load();
+    let registry = registries
+        .get(&query.agent_id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+    Ok(Json(ToolApprovalListResponse {
+        approvals: registry.pending(),
+    }))
+}
+
+/// Approve or deny a paused worker tool call.
+#[utoipa::path(
+    post,
+    path = "/agents/workers/approvals/{approval_id}",
+    params(
+        ("approval_id" = String, Path, description = "Approval ID"),
+    ),
+    request_body = ToolApprovalDecisionRequest,
+    responses(
+        (status = 200, body = PendingToolApproval),
+        (status = 400, description = "Decision must be approved or denied"),
+        (status = 404, description = "Agent not found or approval no longer pending"),
+    ),
+    tag = "workers",
+)]
+pub(super) async fn resolve_tool_approval(
+    State(state): State<Arc<ApiState>>,
+    Path(approval_id): Path<String>,
+    Json(request): Json<ToolApprovalDecisionRequest>,
+) -> Result<Json<PendingToolApproval>, StatusCode> {
+    if request.decision == ToolApprovalDecision::TimedOut {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let registries = state.tool_approvals.load();
+    let registry = registries
+        .get(&request.agent_id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let resolved_by = request
+        .resolved_by
+        .unwrap_or_else(|| "dashboard".to_string());
+    registry
+        .resolve(&approval_id, request.decision, Some(resolved_by))
+        .map(Json)
+        .map_err(|_| StatusCode::NOT_FOUND)
+}
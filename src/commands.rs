@@ -0,0 +1,502 @@
+//! Chat command registry.
+//!
+//! Built-in commands (`/status`, `/help`, response modes) are handled directly
+//! by the channel. User-defined commands come from `[[defaults.commands]]`,
+//! `[[agents.commands]]`, and skills that declare a `command` in their
+//! frontmatter. The registry resolves invocations, binds positional
+//! arguments, renders templates, and produces the command specs messaging
+//! adapters register with their platforms (Discord application commands,
+//! Telegram bot commands, Slack slash commands).
+
+use crate::config::{AccessRole, CommandAction, CommandArgDef, CommandDef, RuntimeConfig};
+use crate::skills::SkillSet;
+
+use std::collections::HashMap;
+
+/// A command the channel handles itself.
+#[derive(Debug, Clone, Copy)]
+pub struct BuiltinCommand {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Whether the command is listed in `/help` and registered with platforms.
+    pub listed: bool,
+}
+
+/// Built-in commands, in `/help` order.
+pub const BUILTIN_COMMANDS: &[BuiltinCommand] = &[
+    BuiltinCommand {
+        name: "status",
+        description: "current mode, models, binding snapshot",
+        listed: true,
+    },
+    BuiltinCommand {
+        name: "today",
+        description: "in-progress + ready task snapshot",
+        listed: true,
+    },
+    BuiltinCommand {
+        name: "tasks",
+        description: "ready task list",
+        listed: true,
+    },
+    BuiltinCommand {
+        name: "digest",
+        description: "one-shot day digest (00:00 -> now)",
+        listed: true,
+    },
+    BuiltinCommand {
+        name: "observe",
+        description: "learn from conversation, never respond",
+        listed: true,
+    },
+    BuiltinCommand {
+        name: "quiet",
+        description: "alias for /observe",
+        listed: false,
+    },
+    BuiltinCommand {
+        name: "mention-only",
+        description: "only respond when @mentioned, replied to, or given a command",
+        listed: true,
+    },
+    BuiltinCommand {
+        name: "active",
+        description: "normal reply mode",
+        listed: true,
+    },
+    BuiltinCommand {
+        name: "agent-id",
+        description: "runtime agent id",
+        listed: true,
+    },
+    BuiltinCommand {
+        name: "help",
+        description: "list available commands",
+        listed: true,
+    },
+];
+
+/// Maximum command and argument name length accepted by Discord and Telegram.
+const MAX_NAME_LENGTH: usize = 32;
+
+/// Errors from resolving a command invocation.
+#[derive(Debug, thiserror::Error)]
+pub enum CommandError {
+    #[error("missing argument `{arg}`. usage: {usage}")]
+    MissingArgument { arg: String, usage: String },
+    #[error("unterminated quote in arguments. usage: {usage}")]
+    UnterminatedQuote { usage: String },
+}
+
+/// Whether `name` is handled by the channel itself.
+pub fn is_builtin(name: &str) -> bool {
+    BUILTIN_COMMANDS.iter().any(|builtin| builtin.name == name)
+}
+
+/// Command names are lowercase letters, digits, `-` and `_`, up to 32 characters.
+pub fn is_valid_command_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= MAX_NAME_LENGTH
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+}
+
+/// Argument names are lowercase letters, digits and `_`, up to 32 characters.
+/// `args` is reserved for the full argument text.
+pub fn is_valid_arg_name(name: &str) -> bool {
+    !name.is_empty()
+        && name != "args"
+        && name.len() <= MAX_NAME_LENGTH
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// What a platform needs to register a command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandSpec {
+    pub name: String,
+    pub description: String,
+    pub args: Vec<CommandArgDef>,
+}
+
+/// A parsed `/name rest` invocation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Invocation<'a> {
+    /// Lowercased command name with any Telegram `@botname` suffix removed.
+    pub name: String,
+    /// Argument text after the command name, trimmed.
+    pub args: &'a str,
+}
+
+/// Parse `/name args...` from message text. Returns `None` for anything that
+/// is not a slash command.
+pub fn parse_invocation(text: &str) -> Option<Invocation<'_>> {
+    let text = text.trim().strip_prefix('/')?;
+    let (head, args) = match text.find(char::is_whitespace) {
+        Some(index) => (&text[..index], text[index..].trim()),
+        None => (text, ""),
+    };
+    let name = head.split('@').next().unwrap_or(head).to_lowercase();
+    is_valid_command_name(&name).then_some(Invocation { name, args })
+}
+
+/// User-defined commands available to one agent.
+#[derive(Debug, Clone, Default)]
+pub struct CommandRegistry {
+    commands: Vec<CommandDef>,
+}
+
+impl CommandRegistry {
+    /// Build from config commands and skill-declared commands. Config
+    /// commands win on name conflicts; built-in names are never overridden.
+    pub fn new(config_commands: &[CommandDef], skills: &SkillSet) -> Self {
+        let mut commands: Vec<CommandDef> = Vec::new();
+        let declared = config_commands
+            .iter()
+            .chain(skills.iter().filter_map(|skill| skill.command.as_ref()));
+        for command in declared {
+            if is_builtin(&command.name) || commands.iter().any(|c| c.name == command.name) {
+                continue;
+            }
+            commands.push(command.clone());
+        }
+        commands.sort_by(|a, b| a.name.cmp(&b.name));
+        Self { commands }
+    }
+
+    /// Snapshot the commands currently configured for an agent.
+    pub fn from_runtime(runtime_config: &RuntimeConfig) -> Self {
+        Self::new(
+            &runtime_config.commands.load(),
+            &runtime_config.skills.load(),
+        )
+    }
+
+    pub fn get(&self, name: &str) -> Option<&CommandDef> {
+        self.commands.iter().find(|command| command.name == name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &CommandDef> {
+        self.commands.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Built-in and user-defined commands, for platform registration.
+    pub fn specs(&self) -> Vec<CommandSpec> {
+        let builtins = BUILTIN_COMMANDS
+            .iter()
+            .filter(|builtin| builtin.listed)
+            .map(|builtin| CommandSpec {
+                name: builtin.name.to_string(),
+                description: builtin.description.to_string(),
+                args: Vec::new(),
+            });
+        let custom = self.commands.iter().map(|command| CommandSpec {
+            name: command.name.clone(),
+            description: command_description(command),
+            args: command.args.clone(),
+        });
+        builtins.chain(custom).collect()
+    }
+
+    /// Generated `/help` text. Commands above `role` are left out.
+    pub fn help_text(&self, role: AccessRole) -> String {
+        let mut lines = vec!["commands:".to_string()];
+        for builtin in BUILTIN_COMMANDS.iter().filter(|builtin| builtin.listed) {
+            lines.push(format!("- /{}: {}", builtin.name, builtin.description));
+        }
+        for command in self.commands.iter().filter(|command| role >= command.role) {
+            lines.push(format!(
+                "- {}: {}",
+                usage(command),
+                command_description(command)
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Union of command specs across agents, keyed by agent ID. Agents are
+/// visited in ID order and the first definition of a name wins. Adapters are
+/// shared between agents, so they register every agent's commands.
+pub fn agent_command_specs<'a>(
+    runtime_configs: impl IntoIterator<Item = (&'a str, &'a RuntimeConfig)>,
+) -> Vec<CommandSpec> {
+    let mut runtime_configs: Vec<_> = runtime_configs.into_iter().collect();
+    runtime_configs.sort_by_key(|(agent_id, _)| *agent_id);
+
+    let mut merged: Vec<CommandSpec> = Vec::new();
+    for (_, runtime_config) in runtime_configs {
+        for spec in CommandRegistry::from_runtime(runtime_config).specs() {
+            if !merged.iter().any(|existing| existing.name == spec.name) {
+                merged.push(spec);
+            }
+        }
+    }
+    merged
+}
+
+fn command_description(command: &CommandDef) -> String {
+    if command.description.trim().is_empty() {
+        match &command.action {
+            CommandAction::Prompt(_) => "custom command".to_string(),
+            CommandAction::Skill { skill, .. } => format!("run the {skill} skill"),
+            CommandAction::Cron(cron_id) => format!("run the {cron_id} cron job now"),
+            CommandAction::Task { .. } => "create a task".to_string(),
+        }
+    } else {
+        command.description.clone()
+    }
+}
+
+/// Usage line, e.g. `/standup <team> [notes]`.
+pub fn usage(command: &CommandDef) -> String {
+    let mut usage = format!("/{}", command.name);
+    for arg in &command.args {
+        if arg.required {
+            usage.push_str(&format!(" <{}>", arg.name));
+        } else {
+            usage.push_str(&format!(" [{}]", arg.name));
+        }
+    }
+    usage
+}
+
+/// Bind positional arguments. Words are split on whitespace, double quotes
+/// group words, and the last declared argument captures the remaining text.
+pub fn bind_args(
+    command: &CommandDef,
+    text: &str,
+) -> std::result::Result<HashMap<String, String>, CommandError> {
+    let tokens = tokenize(text).ok_or_else(|| CommandError::UnterminatedQuote {
+        usage: usage(command),
+    })?;
+
+    let mut bound = HashMap::new();
+    for (index, arg) in command.args.iter().enumerate() {
+        let is_last = index + 1 == command.args.len();
+        let value = match tokens.get(index) {
+            Some(token) if is_last && index + 1 < tokens.len() => text[token.start..].trim().into(),
+            Some(token) => token.value.clone(),
+            None => String::new(),
+        };
+        if value.is_empty() && arg.required {
+            return Err(CommandError::MissingArgument {
+                arg: arg.name.clone(),
+                usage: usage(command),
+            });
+        }
+        bound.insert(arg.name.clone(), value);
+    }
+    bound.insert("args".to_string(), text.trim().to_string());
+    Ok(bound)
+}
+
+/// Substitute `{name}` placeholders in one left-to-right pass. Inserted
+/// values are never rescanned, so an argument containing `{args}` stays
+/// literal. Unknown placeholders are left as-is.
+pub fn render_template(template: &str, bound: &HashMap<String, String>) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        rendered.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        match after
+            .find(['{', '}'])
+            .filter(|&close| after.as_bytes()[close] == b'}')
+            .and_then(|close| Some((close, bound.get(&after[..close])?)))
+        {
+            Some((close, value)) => {
+                rendered.push_str(value);
+                rest = &after[close + 1..];
+            }
+            None => {
+                rendered.push('{');
+                rest = after;
+            }
+        }
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+/// Rebuild invocation text from structured option values (Discord). Values
+/// containing whitespace are quoted so they bind to the same argument, and
+/// skipped optional arguments are passed as `""` to keep later ones in place.
+pub fn invocation_text(name: &str, values: &[String]) -> String {
+    let provided = values
+        .iter()
+        .rposition(|value| !value.is_empty())
+        .map_or(0, |index| index + 1);
+    let mut text = format!("/{name}");
+    for value in &values[..provided] {
+        text.push(' ');
+        if value.is_empty() || (value.chars().any(char::is_whitespace) && !value.contains('"')) {
+            text.push('"');
+            text.push_str(value);
+            text.push('"');
+        } else {
+            text.push_str(value);
+        }
+    }
+    text
+}
+
+struct Token {
+    /// Byte offset where the token starts in the argument text.
+    start: usize,
+    value: String,
+}
+
+fn tokenize(text: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        let mut value = String::new();
+        if c == '"' {
+            chars.next();
+            let mut closed = false;
+            for (_, c) in chars.by_ref() {
+                if c == '"' {
+                    closed = true;
+                    break;
+                }
+                value.push(c);
+            }
+            if !closed {
+                return None;
+            }
+        } else {
+            while let Some(&(_, c)) = chars.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+                value.push(c);
+                chars.next();
+            }
+        }
+        tokens.push(Token { start, value });
+    }
+    Some(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(name: &str, args: &[(&str, bool)]) -> CommandDef {
+        CommandDef {
+            name: name.into(),
+            description: String::new(),
+            args: args
+                .iter()
+                .map(|(name, required)| CommandArgDef {
+                    name: (*name).into(),
+                    description: String::new(),
+                    required: *required,
+                })
+                .collect(),
+            role: AccessRole::Member,
+            action: CommandAction::Prompt("summarize {topic} for {team}".into()),
+        }
+    }
+
+    #[test]
+    fn parse_invocation_strips_bot_suffix() {
+        let invocation = parse_invocation("/Standup@spacebot_bot  eng notes ").unwrap();
+        assert_eq!(invocation.name, "standup");
+        assert_eq!(invocation.args, "eng notes");
+        assert!(parse_invocation("hello /standup").is_none());
+        assert!(parse_invocation("/").is_none());
+    }
+
+    #[test]
+    fn bind_args_last_argument_takes_rest() {
+        let def = command("standup", &[("team", true), ("topic", false)]);
+        let bound = bind_args(&def, "\"core infra\" the   deploy plan").unwrap();
+        assert_eq!(bound["team"], "core infra");
+        assert_eq!(bound["topic"], "the   deploy plan");
+        assert_eq!(
+            render_template("summarize {topic} for {team}", &bound),
+            "summarize the   deploy plan for core infra"
+        );
+
+        let bound = bind_args(&def, "eng").unwrap();
+        assert_eq!(bound["topic"], "");
+    }
+
+    #[test]
+    fn render_template_does_not_expand_inserted_values() {
+        let def = command("standup", &[("team", true), ("topic", false)]);
+        let bound = bind_args(&def, "{args} {team}").unwrap();
+        assert_eq!(
+            render_template("team={team} topic={topic} {unknown} {{team}", &bound),
+            "team={args} topic={team} {unknown} {{args}"
+        );
+    }
+
+    #[test]
+    fn bind_args_reports_missing_and_unterminated() {
+        let def = command("standup", &[("team", true)]);
+        let error = bind_args(&def, "").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "missing argument `team`. usage: /standup <team>"
+        );
+        assert!(matches!(
+            bind_args(&def, "\"open"),
+            Err(CommandError::UnterminatedQuote { .. })
+        ));
+    }
+
+    #[test]
+    fn registry_skips_builtins_and_filters_help_by_role() {
+        let mut admin_only = command("deploy", &[]);
+        admin_only.role = AccessRole::Admin;
+        let registry = CommandRegistry::new(
+            &[
+                command("status", &[]),
+                command("standup", &[("team", true)]),
+                admin_only,
+            ],
+            &SkillSet::default(),
+        );
+        assert!(registry.get("status").is_none());
+        assert!(registry.get("deploy").is_some());
+
+        let help = registry.help_text(AccessRole::Member);
+        assert!(help.contains("- /status: "));
+        assert!(help.contains("- /standup <team>: custom command"));
+        assert!(!help.contains("/deploy"));
+        assert!(registry.help_text(AccessRole::Owner).contains("/deploy"));
+    }
+
+    #[test]
+    fn invocation_text_round_trips_through_bind_args() {
+        let def = command("standup", &[("team", true), ("topic", true)]);
+        let text = invocation_text("standup", &["core infra".into(), "deploy plan".into()]);
+        let invocation = parse_invocation(&text).unwrap();
+        let bound = bind_args(&def, invocation.args).unwrap();
+        assert_eq!(bound["team"], "core infra");
+        assert_eq!(bound["topic"], "deploy plan");
+
+        let def = command(
+            "standup",
+            &[("team", false), ("topic", false), ("notes", false)],
+        );
+        let text = invocation_text("standup", &[String::new(), "deploys".into(), String::new()]);
+        assert_eq!(text, "/standup \"\" deploys");
+        let bound = bind_args(&def, parse_invocation(&text).unwrap().args).unwrap();
+        assert_eq!(bound["team"], "");
+        assert_eq!(bound["topic"], "deploys");
+    }
+}
@@ -15,8 +15,8 @@ pub(crate) use load::resolve_env_value;
 pub use load::set_resolve_secrets_store;
 pub use onboarding::run_onboarding;
 pub use permissions::{
-    DiscordPermissions, MattermostPermissions, SignalPermissions, SlackPermissions,
-    TelegramPermissions, TwitchPermissions,
+    DiscordPermissions, IrcPermissions, MatrixPermissions, MattermostPermissions,
+    SignalPermissions, SlackPermissions, TeamsPermissions, TelegramPermissions, TwitchPermissions,
 };
 pub(crate) use providers::default_provider_config;
 pub use runtime::RuntimeConfig;
@@ -180,6 +180,25 @@ api_key = "test-key"
         let result5: StdResult<TomlProviderConfig, toml::de::Error> = toml::from_str(toml5);
         assert!(result5.is_ok(), "Error: {:?}", result5.err());
         assert_eq!(result5.unwrap().api_type, ApiType::Anthropic);
+
+        let toml6 = r#"
+api_type = "vertex"
+base_url = "https://us-central1-aiplatform.googleapis.com/v1/projects/my-project/locations/us-central1"
+api_key = "/etc/spacebot/vertex-key.json"
+"#;
+        let result6: StdResult<TomlProviderConfig, toml::de::Error> = toml::from_str(toml6);
+        assert!(result6.is_ok(), "Error: {:?}", result6.err());
+        assert_eq!(result6.unwrap().api_type, ApiType::Vertex);
+
+        let toml7 = r#"
+api_type = "bedrock"
+base_url = "https://bedrock-runtime.us-west-2.amazonaws.com"
+api_key = "secret:AWS_SECRET_ACCESS_KEY"
+access_key_id = "secret:AWS_ACCESS_KEY_ID"
+"#;
+        let result7: StdResult<TomlProviderConfig, toml::de::Error> = toml::from_str(toml7);
+        assert!(result7.is_ok(), "Error: {:?}", result7.err());
+        assert_eq!(result7.unwrap().api_type, ApiType::Bedrock);
     }
 
     #[test]
@@ -275,6 +294,76 @@ api_key = "static-provider-key"
         assert_eq!(second_provider.api_key, "static-provider-key");
     }
 
+    #[test]
+    fn test_bedrock_provider_resolves_signing_settings() {
+        let _lock = env_test_lock().lock();
+        let _env = EnvGuard::new();
+
+        let toml = r#"
+[llm.provider.bedrock]
+api_type = "bedrock"
+base_url = "https://bedrock-runtime.eu-central-1.amazonaws.com"
+api_key = "secret-access-key"
+access_key_id = "AKIDEXAMPLE"
+session_token = "session"
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+
+        let provider = config
+            .llm
+            .providers
+            .get("bedrock")
+            .expect("bedrock provider missing");
+        let bedrock = provider.bedrock.as_ref().expect("bedrock settings missing");
+        assert_eq!(provider.api_key, "secret-access-key");
+        assert_eq!(bedrock.access_key_id, "AKIDEXAMPLE");
+        assert_eq!(bedrock.session_token.as_deref(), Some("session"));
+        // Region falls back to the runtime endpoint's host.
+        assert_eq!(bedrock.region, "eu-central-1");
+        assert!(!format!("{provider:?}").contains("session\""));
+
+        let missing_key_id = r#"
+[llm.provider.bedrock]
+api_type = "bedrock"
+base_url = "https://bedrock-runtime.eu-central-1.amazonaws.com"
+api_key = "secret-access-key"
+"#;
+        let parsed: TomlConfig = toml::from_str(missing_key_id).expect("failed to parse test TOML");
+        let error = Config::from_toml(parsed, PathBuf::from(".")).unwrap_err();
+        assert!(error.to_string().contains("requires access_key_id"));
+    }
+
+    #[test]
+    fn test_custom_provider_parses_cassette_settings() {
+        let _lock = env_test_lock().lock();
+        let _env = EnvGuard::new();
+
+        let toml = r#"
+[llm.provider.standin]
+api_type = "anthropic"
+base_url = "http://localhost:8080"
+api_key = "test-key"
+cassette = { mode = "replay", dir = "tests/cassettes" }
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+
+        let provider = config
+            .llm
+            .providers
+            .get("standin")
+            .expect("standin provider missing");
+        let cassette = provider
+            .cassette
+            .as_ref()
+            .expect("cassette settings missing");
+        assert_eq!(cassette.mode, CassetteMode::Replay);
+        assert_eq!(cassette.dir, PathBuf::from("tests/cassettes"));
+    }
+
     #[test]
     fn test_legacy_llm_keys_auto_migrate_to_providers() {
         let _lock = env_test_lock().lock();
@@ -583,6 +672,7 @@ bind = "127.0.0.1"
             require_mention: false,
             dm_allowed_users,
             settings: None,
+            access: BindingAccess::default(),
         }
     }
 
@@ -1065,6 +1155,59 @@ maintenance_decay_rate = 0.33
         assert_eq!(resolved.cortex.association_max_per_pass, 55);
     }
 
+    #[test]
+    fn test_cortex_autonomy_settings_resolution() {
+        let toml = r#"
+[defaults.cortex]
+autonomy_interval_secs = 900
+autonomy_max_workers = 1
+autonomy_active_start_hour = 22
+autonomy_active_end_hour = 6
+
+[[agents]]
+id = "main"
+
+[agents.cortex]
+autonomy_enabled = false
+autonomy_tasks_require_approval = false
+"#;
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+        let resolved = config.agents[0].resolve(&config.instance_dir, &config.defaults);
+
+        assert!(config.defaults.cortex.autonomy_enabled);
+        assert_eq!(config.defaults.cortex.autonomy_active_hours, Some((22, 6)));
+        assert!(!resolved.cortex.autonomy_enabled);
+        assert!(!resolved.cortex.autonomy_tasks_require_approval);
+        assert_eq!(resolved.cortex.autonomy_interval_secs, 900);
+        assert_eq!(resolved.cortex.autonomy_max_workers, 1);
+        assert_eq!(resolved.cortex.autonomy_max_turns, 15);
+        assert_eq!(resolved.cortex.autonomy_active_hours, Some((22, 6)));
+
+        let too_frequent = r#"
+[defaults.cortex]
+autonomy_interval_secs = 60
+"#;
+        let parsed: TomlConfig =
+            toml::from_str(too_frequent).expect("failed to parse short interval TOML");
+        assert!(
+            Config::from_toml(parsed, PathBuf::from(".")).is_err(),
+            "expected autonomy_interval_secs below 300 to be rejected"
+        );
+
+        let invalid_hours = r#"
+[defaults.cortex]
+autonomy_active_start_hour = 9
+autonomy_active_end_hour = 25
+"#;
+        let parsed: TomlConfig =
+            toml::from_str(invalid_hours).expect("failed to parse invalid hours TOML");
+        assert!(
+            Config::from_toml(parsed, PathBuf::from(".")).is_err(),
+            "expected out-of-range autonomy active hours to be rejected"
+        );
+    }
+
     #[test]
     fn test_cortex_maintenance_config_rejects_invalid_ranges() {
         let invalid_threshold = r#"
@@ -1441,6 +1584,7 @@ maintenance_merge_similarity_threshold = 1.1
             require_mention: false,
             dm_allowed_users: vec![],
             settings: None,
+            access: BindingAccess::default(),
         };
         assert_eq!(binding.runtime_adapter_key(), "telegram:sales");
     }
@@ -1459,6 +1603,7 @@ maintenance_merge_similarity_threshold = 1.1
             require_mention: false,
             dm_allowed_users: vec![],
             settings: None,
+            access: BindingAccess::default(),
         };
         assert!(binding.uses_default_adapter());
     }
@@ -1492,6 +1637,7 @@ maintenance_merge_similarity_threshold = 1.1
             require_mention: false,
             dm_allowed_users: vec![],
             settings: None,
+            access: BindingAccess::default(),
         };
         let message = test_inbound_message("telegram", None);
         assert!(binding_adapter_matches(&binding, &message));
@@ -1511,6 +1657,7 @@ maintenance_merge_similarity_threshold = 1.1
             require_mention: false,
             dm_allowed_users: vec![],
             settings: None,
+            access: BindingAccess::default(),
         };
         let message = test_inbound_message("telegram", Some("telegram:support"));
         assert!(binding_adapter_matches(&binding, &message));
@@ -1530,6 +1677,7 @@ maintenance_merge_similarity_threshold = 1.1
             require_mention: false,
             dm_allowed_users: vec![],
             settings: None,
+            access: BindingAccess::default(),
         };
         let message = test_inbound_message("telegram", None);
         assert!(!binding_adapter_matches(&binding, &message));
@@ -1549,6 +1697,7 @@ maintenance_merge_similarity_threshold = 1.1
             require_mention: false,
             dm_allowed_users: vec![],
             settings: None,
+            access: BindingAccess::default(),
         };
         let message = test_inbound_message("telegram", Some("telegram:support"));
         assert!(!binding_adapter_matches(&binding, &message));
@@ -1568,6 +1717,7 @@ maintenance_merge_similarity_threshold = 1.1
             require_mention: false,
             dm_allowed_users: vec![],
             settings: None,
+            access: BindingAccess::default(),
         };
         let message = test_inbound_message("telegram", Some("telegram:sales"));
         assert!(!binding_adapter_matches(&binding, &message));
@@ -1594,6 +1744,9 @@ maintenance_merge_similarity_threshold = 1.1
             twitch: None,
             signal: None,
             mattermost: None,
+            matrix: None,
+            irc: None,
+            teams: None,
         };
         let bindings = vec![
             Binding {
@@ -1608,6 +1761,7 @@ maintenance_merge_similarity_threshold = 1.1
                 require_mention: false,
                 dm_allowed_users: vec![],
                 settings: None,
+                access: BindingAccess::default(),
             },
             Binding {
                 agent_id: "support-agent".into(),
@@ -1621,6 +1775,7 @@ maintenance_merge_similarity_threshold = 1.1
                 require_mention: false,
                 dm_allowed_users: vec![],
                 settings: None,
+                access: BindingAccess::default(),
             },
         ];
         let result = validate_named_messaging_adapters(&messaging, bindings, false)
@@ -1644,6 +1799,9 @@ maintenance_merge_similarity_threshold = 1.1
             twitch: None,
             signal: None,
             mattermost: None,
+            matrix: None,
+            irc: None,
+            teams: None,
         };
         let bindings = vec![Binding {
             agent_id: "main".into(),
@@ -1657,6 +1815,7 @@ maintenance_merge_similarity_threshold = 1.1
             require_mention: false,
             dm_allowed_users: vec![],
             settings: None,
+            access: BindingAccess::default(),
         }];
         let result = validate_named_messaging_adapters(&messaging, bindings, false)
             .expect("bindings should be resolvable");
@@ -1702,6 +1861,7 @@ maintenance_merge_similarity_threshold = 1.1
                 from_address: "bot@test.com".into(),
                 from_name: None,
                 poll_interval_secs: 60,
+                idle: true,
                 folders: vec![],
                 allowed_senders: vec![],
                 max_body_bytes: 1_000_000,
@@ -1712,6 +1872,9 @@ maintenance_merge_similarity_threshold = 1.1
             twitch: None,
             signal: None,
             mattermost: None,
+            matrix: None,
+            irc: None,
+            teams: None,
         };
         let bindings = vec![Binding {
             agent_id: "main".into(),
@@ -1725,6 +1888,7 @@ maintenance_merge_similarity_threshold = 1.1
             require_mention: false,
             dm_allowed_users: vec![],
             settings: None,
+            access: BindingAccess::default(),
         }];
         let result = validate_named_messaging_adapters(&messaging, bindings, false)
             .expect("bindings should be resolvable");
@@ -1755,6 +1919,9 @@ maintenance_merge_similarity_threshold = 1.1
             twitch: None,
             signal: None,
             mattermost: None,
+            matrix: None,
+            irc: None,
+            teams: None,
         };
         // Binding targets default adapter, but no default credentials exist
         let bindings = vec![Binding {
@@ -1769,6 +1936,7 @@ maintenance_merge_similarity_threshold = 1.1
             require_mention: false,
             dm_allowed_users: vec![],
             settings: None,
+            access: BindingAccess::default(),
         }];
         let result = validate_named_messaging_adapters(&messaging, bindings, false)
             .expect("bindings should be resolvable");
@@ -1799,6 +1967,9 @@ maintenance_merge_similarity_threshold = 1.1
             twitch: None,
             signal: None,
             mattermost: None,
+            matrix: None,
+            irc: None,
+            teams: None,
         };
         let bindings = vec![
             // Valid: default adapter with credentials
@@ -1814,6 +1985,7 @@ maintenance_merge_similarity_threshold = 1.1
                 require_mention: false,
                 dm_allowed_users: vec![],
                 settings: None,
+                access: BindingAccess::default(),
             },
             // Invalid: references a non-existent named adapter
             Binding {
@@ -1828,6 +2000,7 @@ maintenance_merge_similarity_threshold = 1.1
                 require_mention: false,
                 dm_allowed_users: vec![],
                 settings: None,
+                access: BindingAccess::default(),
             },
             // Valid: references an existing named adapter
             Binding {
@@ -1842,6 +2015,7 @@ maintenance_merge_similarity_threshold = 1.1
                 require_mention: false,
                 dm_allowed_users: vec![],
                 settings: None,
+                access: BindingAccess::default(),
             },
             // Invalid: no discord config at all
             Binding {
@@ -1856,6 +2030,7 @@ maintenance_merge_similarity_threshold = 1.1
                 require_mention: false,
                 dm_allowed_users: vec![],
                 settings: None,
+                access: BindingAccess::default(),
             },
         ];
         let result = validate_named_messaging_adapters(&messaging, bindings, false)
@@ -1880,6 +2055,9 @@ maintenance_merge_similarity_threshold = 1.1
             twitch: None,
             signal: None,
             mattermost: None,
+            matrix: None,
+            irc: None,
+            teams: None,
         };
         let bindings = vec![Binding {
             agent_id: "main".into(),
@@ -1893,6 +2071,7 @@ maintenance_merge_similarity_threshold = 1.1
             require_mention: false,
             dm_allowed_users: vec![],
             settings: None,
+            access: BindingAccess::default(),
         }];
         let result = validate_named_messaging_adapters(&messaging, bindings, false)
             .expect("bindings should be resolvable");
@@ -1913,6 +2092,9 @@ maintenance_merge_similarity_threshold = 1.1
             twitch: None,
             signal: None,
             mattermost: None,
+            matrix: None,
+            irc: None,
+            teams: None,
         };
         let bindings = vec![Binding {
             agent_id: "main".into(),
@@ -1926,6 +2108,7 @@ maintenance_merge_similarity_threshold = 1.1
             require_mention: false,
             dm_allowed_users: vec![],
             settings: None,
+            access: BindingAccess::default(),
         }];
         let result = validate_named_messaging_adapters(&messaging, bindings, true);
         assert!(
@@ -1955,6 +2138,9 @@ maintenance_merge_similarity_threshold = 1.1
             twitch: None,
             signal: None,
             mattermost: None,
+            matrix: None,
+            irc: None,
+            teams: None,
         };
         let bindings = vec![Binding {
             agent_id: "main".into(),
@@ -1968,6 +2154,7 @@ maintenance_merge_similarity_threshold = 1.1
             require_mention: false,
             dm_allowed_users: vec![],
             settings: None,
+            access: BindingAccess::default(),
         }];
         let result = validate_named_messaging_adapters(&messaging, bindings, false)
             .expect("bindings should be resolvable");
@@ -1993,6 +2180,9 @@ maintenance_merge_similarity_threshold = 1.1
             twitch: None,
             signal: None,
             mattermost: None,
+            matrix: None,
+            irc: None,
+            teams: None,
         };
         let bindings = vec![Binding {
             agent_id: "main".into(),
@@ -2006,6 +2196,7 @@ maintenance_merge_similarity_threshold = 1.1
             require_mention: false,
             dm_allowed_users: vec![],
             settings: None,
+            access: BindingAccess::default(),
         }];
         let result = validate_named_messaging_adapters(&messaging, bindings, false)
             .expect("bindings should be resolvable");
@@ -2103,6 +2294,252 @@ guild_id = "123456"
         assert_eq!(config.bindings[0].guild_id.as_deref(), Some("123456"));
     }
 
+    #[test]
+    fn matrix_instances_and_room_bindings_load() {
+        let _guard = env_test_lock().lock();
+        let guard = EnvGuard::new();
+
+        let toml_content = r#"
+[messaging.matrix]
+enabled = true
+homeserver_url = "https://matrix.example.org"
+access_token = "syt_default"
+dm_allowed_users = ["@alice:example.org"]
+
+[[messaging.matrix.instances]]
+name = "ops"
+enabled = true
+homeserver_url = "http://localhost:8009"
+access_token = "syt_ops"
+
+[[bindings]]
+agent_id = "main"
+channel = "matrix"
+channel_ids = ["!general:example.org"]
+
+[[bindings]]
+agent_id = "ops-bot"
+channel = "matrix"
+adapter = "ops"
+channel_ids = ["!incidents:example.org"]
+"#;
+        let config_path = guard.test_dir.join("config.toml");
+        std::fs::write(&config_path, toml_content).unwrap();
+
+        let config = Config::load_from_path(&config_path).unwrap();
+        let matrix = config.messaging.matrix.as_ref().unwrap();
+        assert_eq!(matrix.access_token, "syt_default");
+        assert_eq!(matrix.instances[0].homeserver_url, "http://localhost:8009");
+
+        let default = MatrixPermissions::from_config(matrix, &config.bindings);
+        assert!(default.allows_room("!general:example.org"));
+        assert!(!default.allows_room("!incidents:example.org"));
+        assert_eq!(default.dm_allowed_users, vec!["@alice:example.org"]);
+
+        let ops = MatrixPermissions::from_instance_config(&matrix.instances[0], &config.bindings);
+        assert!(ops.allows_room("!incidents:example.org"));
+        assert!(!ops.allows_room("!general:example.org"));
+    }
+
+    #[test]
+    fn matrix_homeserver_must_use_https_off_loopback() {
+        let _guard = env_test_lock().lock();
+        let guard = EnvGuard::new();
+
+        let toml_content = r#"
+[messaging.matrix]
+enabled = true
+homeserver_url = "http://matrix.example.org"
+access_token = "syt_default"
+"#;
+        let config_path = guard.test_dir.join("config.toml");
+        std::fs::write(&config_path, toml_content).unwrap();
+
+        assert!(Config::load_from_path(&config_path).is_err());
+    }
+
+    #[test]
+    fn irc_instances_and_channel_bindings_load() {
+        let _guard = env_test_lock().lock();
+        let guard = EnvGuard::new();
+
+        let toml_content = r##"
+[messaging.irc]
+enabled = true
+server = "irc.libera.chat"
+nickname = "spacebot"
+sasl_password = "hunter2"
+channels = ["#spacebot"]
+dm_allowed_users = ["alice"]
+
+[[messaging.irc.instances]]
+name = "oftc"
+enabled = true
+server = "irc.oftc.net"
+nickname = "spacebot"
+tls = false
+port = 6667
+
+[[messaging.irc.instances]]
+name = "broken"
+enabled = true
+server = "irc.example.org"
+
+[[bindings]]
+agent_id = "main"
+channel = "irc"
+channel_ids = ["#Spacebot"]
+
+[[bindings]]
+agent_id = "dev-bot"
+channel = "irc"
+adapter = "oftc"
+channel_ids = ["#spacebot-dev"]
+dm_allowed_users = ["bob"]
+"##;
+        let config_path = guard.test_dir.join("config.toml");
+        std::fs::write(&config_path, toml_content).unwrap();
+
+        let config = Config::load_from_path(&config_path).unwrap();
+        let irc = config.messaging.irc.as_ref().unwrap();
+        assert_eq!(irc.port, 6697);
+        assert!(irc.tls);
+        assert_eq!(irc.sasl_password.as_deref(), Some("hunter2"));
+        assert_eq!(irc.instances[0].port, 6667);
+        assert!(!irc.instances[0].tls);
+        // Instances without a nickname are disabled rather than failing the load.
+        assert!(!irc.instances[1].enabled);
+
+        let default = IrcPermissions::from_config(irc, &config.bindings);
+        assert!(default.allows_channel("#spacebot"));
+        assert!(!default.allows_channel("#spacebot-dev"));
+        assert!(default.allows_dm("Alice", None));
+        assert!(!default.allows_dm("alice", Some("mallory")));
+
+        let oftc = IrcPermissions::from_instance_config(&irc.instances[0], &config.bindings);
+        assert!(oftc.allows_channel("#SPACEBOT-DEV"));
+        assert!(!oftc.allows_channel("#spacebot"));
+        assert!(oftc.allows_dm("bob", None));
+        assert!(!oftc.allows_dm("alice", None));
+    }
+
+    #[test]
+    fn irc_rejects_invalid_nicknames_and_channels() {
+        let _guard = env_test_lock().lock();
+        let guard = EnvGuard::new();
+        let config_path = guard.test_dir.join("config.toml");
+
+        for (nickname, channels) in [
+            ("space bot", r##"["#spacebot"]"##),
+            ("1spacebot", r##"["#spacebot"]"##),
+            ("spacebot", r##"["spacebot"]"##),
+            ("spacebot", r##"["#a,#b"]"##),
+        ] {
+            let toml_content = format!(
+                r#"
+[messaging.irc]
+enabled = true
+server = "irc.libera.chat"
+nickname = "{nickname}"
+channels = {channels}
+"#
+            );
+            std::fs::write(&config_path, toml_content).unwrap();
+            assert!(
+                Config::load_from_path(&config_path).is_err(),
+                "{nickname} {channels} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn teams_instances_and_team_bindings_load() {
+        let _guard = env_test_lock().lock();
+        let guard = EnvGuard::new();
+
+        let toml_content = r##"
+[messaging.teams]
+enabled = true
+app_id = "00000000-0000-0000-0000-000000000001"
+app_password = "hunter2"
+dm_allowed_users = ["aad-alice"]
+
+[[messaging.teams.instances]]
+name = "support"
+enabled = true
+app_id = "00000000-0000-0000-0000-000000000002"
+app_password = "hunter3"
+tenant_id = "tenant-1"
+port = 3979
+
+[[messaging.teams.instances]]
+name = "broken"
+enabled = true
+app_id = "00000000-0000-0000-0000-000000000003"
+port = 3980
+
+[[bindings]]
+agent_id = "main"
+channel = "teams"
+team_id = "team-eng"
+
+[[bindings]]
+agent_id = "support-bot"
+channel = "teams"
+adapter = "support"
+channel_ids = ["19:help@thread.tacv2"]
+dm_allowed_users = ["aad-bob"]
+"##;
+        let config_path = guard.test_dir.join("config.toml");
+        std::fs::write(&config_path, toml_content).unwrap();
+
+        let config = Config::load_from_path(&config_path).unwrap();
+        let teams = config.messaging.teams.as_ref().unwrap();
+        assert_eq!(teams.port, 3978);
+        assert_eq!(teams.bind, "127.0.0.1");
+        assert_eq!(teams.tenant_id, None);
+        assert_eq!(teams.instances[0].tenant_id.as_deref(), Some("tenant-1"));
+        // Instances without an app password are disabled rather than failing the load.
+        assert!(!teams.instances[1].enabled);
+
+        let default = TeamsPermissions::from_config(teams, &config.bindings);
+        assert!(default.allows_conversation(Some("team-eng"), "19:general@thread.tacv2"));
+        assert!(!default.allows_conversation(Some("team-ops"), "19:general@thread.tacv2"));
+        assert!(!default.allows_conversation(None, "19:chat@thread.v2"));
+        assert!(default.allows_dm(Some("AAD-ALICE")));
+        assert!(!default.allows_dm(None));
+
+        let support = TeamsPermissions::from_instance_config(&teams.instances[0], &config.bindings);
+        assert!(support.allows_conversation(Some("team-ops"), "19:help@thread.tacv2"));
+        assert!(!support.allows_conversation(Some("team-ops"), "19:general@thread.tacv2"));
+        assert!(support.allows_dm(Some("aad-bob")));
+        assert!(!support.allows_dm(Some("aad-alice")));
+    }
+
+    #[test]
+    fn teams_rejects_shared_listener_ports() {
+        let _guard = env_test_lock().lock();
+        let guard = EnvGuard::new();
+
+        let toml_content = r#"
+[messaging.teams]
+enabled = true
+app_id = "00000000-0000-0000-0000-000000000001"
+app_password = "hunter2"
+
+[[messaging.teams.instances]]
+name = "support"
+enabled = true
+app_id = "00000000-0000-0000-0000-000000000002"
+app_password = "hunter3"
+"#;
+        let config_path = guard.test_dir.join("config.toml");
+        std::fs::write(&config_path, toml_content).unwrap();
+
+        let error = Config::load_from_path(&config_path).unwrap_err();
+        assert!(error.to_string().contains("both listen on"), "{error}");
+    }
+
     #[test]
     fn normalize_adapter_trims_and_clears_empty() {
         assert_eq!(normalize_adapter(None), None);
@@ -2213,4 +2650,485 @@ tool_use_enforcement = ["gemini", "deepseek"]
                 .should_inject("anthropic/claude-sonnet-4")
         );
     }
+
+    #[test]
+    fn prompt_routing_parses_and_agent_profile_overrides() {
+        let toml = r#"
+[defaults.routing]
+channel = "anthropic/claude-sonnet-4"
+
+[defaults.routing.prompt_routing]
+enabled = true
+process_types = ["channel"]
+
+[defaults.routing.prompt_routing.tiers]
+light = "anthropic/claude-haiku-4.5"
+heavy = "anthropic/claude-opus-4"
+
+[defaults.routing.prompt_routing.boundaries]
+standard_heavy = 0.5
+
+[[agents]]
+id = "main"
+
+[[agents]]
+id = "budget"
+routing_profile = "eco"
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+
+        let prompt_routing = &config.defaults.routing.prompt_routing;
+        assert!(prompt_routing.enabled);
+        assert_eq!(
+            prompt_routing.process_types,
+            vec![crate::ProcessType::Channel]
+        );
+        assert_eq!(
+            prompt_routing.tiers.light.as_deref(),
+            Some("anthropic/claude-haiku-4.5")
+        );
+        assert_eq!(prompt_routing.tiers.standard, None);
+        assert_eq!(prompt_routing.boundaries.light_standard, 0.0);
+        assert_eq!(prompt_routing.boundaries.standard_heavy, 0.5);
+
+        let resolved = config.resolve_agents();
+        assert_eq!(
+            resolved[0].routing.prompt_routing.profile,
+            crate::llm::routing::RoutingProfile::Balanced
+        );
+        assert_eq!(
+            resolved[1].routing.prompt_routing.profile,
+            crate::llm::routing::RoutingProfile::Eco
+        );
+        assert!(resolved[1].routing.prompt_routing.enabled);
+    }
+
+    #[test]
+    fn budget_parses_scoped_limits_and_agent_overrides() {
+        let toml = r#"
+[defaults.budget]
+daily_limit_usd = 10.0
+monthly_limit_usd = 200.0
+warning_target = "discord:123456789"
+
+[defaults.budget.channels."discord:42"]
+daily_limit_usd = 2.0
+
+[defaults.budget.cron.digest]
+monthly_limit_usd = 5.0
+
+[[agents]]
+id = "main"
+
+[[agents]]
+id = "frugal"
+
+[agents.budget]
+daily_limit_usd = 1.0
+soft_limit_ratio = 0.5
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+
+        let budget = &config.defaults.budget;
+        assert!(budget.is_enabled());
+        assert_eq!(budget.limits.daily_usd, Some(10.0));
+        assert_eq!(budget.soft_limit_ratio, 0.8);
+        assert!(budget.downgrade_on_soft_limit);
+        assert!(budget.scoped_limits("discord:42").is_some());
+        assert!(budget.scoped_limits("cron:digest").is_some());
+        assert!(budget.scoped_limits("discord:7").is_none());
+
+        let resolved = config.resolve_agents();
+        assert_eq!(resolved[0].budget, *budget);
+        let frugal = &resolved[1].budget;
+        assert_eq!(frugal.limits.daily_usd, Some(1.0));
+        assert_eq!(frugal.limits.monthly_usd, Some(200.0));
+        assert_eq!(frugal.soft_limit_ratio, 0.5);
+        assert_eq!(frugal.cron.len(), 1);
+    }
+
+    #[test]
+    fn budget_rejects_invalid_values() {
+        for toml in [
+            "[defaults.budget]\nsoft_limit_ratio = 1.5\n",
+            "[defaults.budget]\ndaily_limit_usd = -1.0\n",
+        ] {
+            let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+            assert!(Config::from_toml(parsed, PathBuf::from(".")).is_err());
+        }
+    }
+
+    #[test]
+    fn response_cache_parses_defaults_and_agent_overrides() {
+        let toml = r#"
+[defaults.response_cache]
+enabled = true
+similarity_threshold = 0.97
+
+[[agents]]
+id = "main"
+
+[[agents]]
+id = "cortex-only"
+
+[agents.response_cache]
+process_types = ["cortex"]
+ttl_secs = 3600
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+
+        let cache = &config.defaults.response_cache;
+        assert!(cache.caches("cortex"));
+        assert!(cache.caches("compactor"));
+        assert!(!cache.caches("channel"));
+        assert_eq!(cache.similarity_threshold, Some(0.97));
+
+        let resolved = config.resolve_agents();
+        assert_eq!(resolved[0].response_cache, *cache);
+        let cortex_only = &resolved[1].response_cache;
+        assert!(cortex_only.caches("cortex"));
+        assert!(!cortex_only.caches("compactor"));
+        assert_eq!(cortex_only.ttl_secs, 3600);
+        assert_eq!(cortex_only.max_entries, 1000);
+    }
+
+    #[test]
+    fn response_cache_rejects_channel_workers_and_bad_thresholds() {
+        for toml in [
+            "[defaults.response_cache]\nprocess_types = [\"cortex\", \"channel\"]\n",
+            "[defaults.response_cache]\nprocess_types = [\"worker\"]\n",
+            "[defaults.response_cache]\nsimilarity_threshold = 1.5\n",
+        ] {
+            let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+            assert!(Config::from_toml(parsed, PathBuf::from(".")).is_err());
+        }
+    }
+
+    #[test]
+    fn tool_approval_rules_match_tool_args_and_sandbox_mode() {
+        use crate::sandbox::SandboxMode;
+
+        let toml = r#"
+[defaults.tool_approval]
+timeout_secs = 120
+
+[[defaults.tool_approval.rules]]
+tool = "shell"
+args_pattern = "rm\\s+-rf"
+
+[[defaults.tool_approval.rules]]
+tool = "*"
+sandbox = "disabled"
+
+[[agents]]
+id = "main"
+
+[[agents]]
+id = "secrets-only"
+
+[agents.tool_approval]
+rules = [{ tool = "secret_set" }]
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+
+        let policy = &config.defaults.tool_approval;
+        assert_eq!(policy.timeout_secs, 120);
+        assert!(
+            policy
+                .matching_rule(
+                    "shell",
+                    r#"{"command":"rm -rf /tmp/x"}"#,
+                    SandboxMode::Enabled
+                )
+                .is_some()
+        );
+        assert!(
+            policy
+                .matching_rule("shell", r#"{"command":"ls"}"#, SandboxMode::Enabled)
+                .is_none()
+        );
+        assert!(
+            policy
+                .matching_rule("file_write", "{}", SandboxMode::Disabled)
+                .is_some()
+        );
+
+        let resolved = config.resolve_agents();
+        assert_eq!(resolved[0].tool_approval.rules.len(), 2);
+        let secrets_only = &resolved[1].tool_approval;
+        assert_eq!(secrets_only.timeout_secs, 120);
+        assert!(
+            secrets_only
+                .matching_rule("secret_set", "{}", SandboxMode::Enabled)
+                .is_some()
+        );
+        assert!(
+            secrets_only
+                .matching_rule("file_write", "{}", SandboxMode::Disabled)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn tool_approval_rejects_bad_patterns_and_zero_timeout() {
+        for toml in [
+            "[[defaults.tool_approval.rules]]\ntool = \"shell\"\nargs_pattern = \"(\"\n",
+            "[defaults.tool_approval]\ntimeout_secs = 0\n",
+        ] {
+            let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+            assert!(Config::from_toml(parsed, PathBuf::from(".")).is_err());
+        }
+    }
+
+    #[test]
+    fn access_roles_resolve_from_humans_and_bindings() {
+        let toml = r#"
+[defaults.access]
+enabled = true
+default_role = "guest"
+
+[[agents]]
+id = "main"
+
+[agents.access]
+cron = "member"
+
+[[humans]]
+id = "alice"
+discord_id = "1"
+access_role = "owner"
+
+[messaging.discord]
+enabled = true
+token = "discord-token"
+
+[[bindings]]
+agent_id = "main"
+channel = "discord"
+guild_id = "100"
+
+[bindings.access]
+default_role = "member"
+users = { "2" = "admin" }
+groups = { "42" = "admin" }
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+
+        let discord_message = |sender_id: &str, guild_id: u64, group_ids: &[&str]| {
+            let mut message = test_inbound_message("discord", None);
+            message.sender_id = sender_id.into();
+            message.agent_id = Some("main".into());
+            message
+                .metadata
+                .insert("discord_guild_id".into(), guild_id.into());
+            message.metadata.insert(
+                crate::metadata_keys::SENDER_GROUP_IDS.into(),
+                serde_json::json!(group_ids),
+            );
+            message
+        };
+        let role = |message: &crate::InboundMessage| {
+            resolve_sender_role(&config.bindings, &config.humans, message)
+        };
+
+        assert_eq!(
+            role(&discord_message("1", 999, &[])),
+            Some(AccessRole::Owner)
+        );
+        assert_eq!(
+            role(&discord_message("2", 100, &[])),
+            Some(AccessRole::Admin)
+        );
+        assert_eq!(
+            role(&discord_message("3", 100, &["42"])),
+            Some(AccessRole::Admin)
+        );
+        assert_eq!(
+            role(&discord_message("3", 100, &["7"])),
+            Some(AccessRole::Member)
+        );
+        // Outside the bound guild nothing assigns a role.
+        assert_eq!(role(&discord_message("3", 999, &["42"])), None);
+
+        let resolved = config.resolve_agents();
+        let access = &resolved[0].access;
+        assert_eq!(access.cron, AccessRole::Member);
+        assert_eq!(access.secret_set, AccessRole::Admin);
+
+        let mut message = discord_message("3", 999, &[]);
+        assert_eq!(access.role_for(&message), AccessRole::Guest);
+        message.metadata.insert(
+            crate::metadata_keys::SENDER_ROLE.into(),
+            serde_json::json!(AccessRole::Member),
+        );
+        assert_eq!(access.role_for(&message), AccessRole::Member);
+        assert!(
+            access
+                .check(AccessRole::Member, AccessCapability::Cron)
+                .is_ok()
+        );
+        let denied = access
+            .check(AccessRole::Member, AccessCapability::SecretSet)
+            .expect_err("members cannot set secrets");
+        assert_eq!(
+            denied.to_string(),
+            "changing secrets requires the admin role or higher; your role is member"
+        );
+        assert_eq!(
+            access.role_for(&test_inbound_message("portal", None)),
+            AccessRole::Owner
+        );
+    }
+
+    #[test]
+    fn access_disabled_allows_everything() {
+        let access = AccessConfig::default();
+        let message = test_inbound_message("discord", None);
+        assert_eq!(access.role_for(&message), AccessRole::Owner);
+        assert!(
+            access
+                .check(AccessRole::Guest, AccessCapability::SecretSet)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn commands_merge_defaults_with_agent_overrides() {
+        let toml = r#"
+[[defaults.commands]]
+name = "standup"
+description = "Summarize a team's standup"
+prompt = "summarize today's standup for {team}"
+
+[[defaults.commands.args]]
+name = "team"
+
+[[defaults.commands]]
+name = "/bug"
+role = "guest"
+
+[defaults.commands.task]
+title = "{args}"
+priority = "high"
+
+[[agents]]
+id = "main"
+
+[[agents.commands]]
+name = "standup"
+skill = "standup-report"
+role = "admin"
+
+[[agents.commands]]
+name = "nightly"
+cron = "nightly-report"
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+        let resolved = config.agents[0].resolve(&config.instance_dir, &config.defaults);
+
+        let names: Vec<&str> = resolved
+            .commands
+            .iter()
+            .map(|command| command.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["standup", "bug", "nightly"]);
+
+        let standup = &resolved.commands[0];
+        assert_eq!(standup.role, AccessRole::Admin);
+        assert!(standup.args.is_empty());
+        assert_eq!(
+            standup.action,
+            CommandAction::Skill {
+                skill: "standup-report".into(),
+                task: None,
+            }
+        );
+
+        let bug = &resolved.commands[1];
+        assert_eq!(bug.role, AccessRole::Guest);
+        assert_eq!(
+            bug.action,
+            CommandAction::Task {
+                title: "{args}".into(),
+                description: None,
+                priority: crate::tasks::TaskPriority::High,
+            }
+        );
+        assert_eq!(
+            resolved.commands[2].action,
+            CommandAction::Cron("nightly-report".into())
+        );
+    }
+
+    #[test]
+    fn commands_reject_invalid_definitions() {
+        let build = |commands: &str| {
+            let parsed: TomlConfig = toml::from_str(commands).expect("failed to parse test TOML");
+            Config::from_toml(parsed, PathBuf::from(".")).map(|_| ())
+        };
+
+        assert!(build("[[defaults.commands]]\nname = \"ok\"\nprompt = \"hi\"\n").is_ok());
+        assert!(build("[[defaults.commands]]\nname = \"status\"\nprompt = \"hi\"\n").is_err());
+        assert!(build("[[defaults.commands]]\nname = \"Bad Name\"\nprompt = \"hi\"\n").is_err());
+        assert!(
+            build("[[defaults.commands]]\nname = \"both\"\nprompt = \"hi\"\ncron = \"x\"\n")
+                .is_err()
+        );
+        assert!(build("[[defaults.commands]]\nname = \"none\"\n").is_err());
+        assert!(
+            build(
+                "[[defaults.commands]]\nname = \"order\"\nprompt = \"hi\"\n\
+                 [[defaults.commands.args]]\nname = \"a\"\nrequired = false\n\
+                 [[defaults.commands.args]]\nname = \"b\"\n"
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn api_oidc_parses_group_scopes() {
+        let build = |api: &str| {
+            let parsed: TomlConfig = toml::from_str(api).expect("failed to parse test TOML");
+            Config::from_toml(parsed, PathBuf::from("."))
+        };
+        let oidc_section = "[api.oidc]\nissuer = \"https://id.example.com/\"\n\
+                            client_id = \"spacebot\"\n\
+                            redirect_url = \"https://bot.example.com/api/auth/oidc/callback\"\n";
+
+        let config = build(&format!(
+            "{oidc_section}[api.oidc.group_scopes]\nops = [\"read\", \"messaging\"]\n"
+        ))
+        .expect("valid oidc config");
+        let oidc = config.api.oidc.expect("oidc should be set");
+        assert_eq!(oidc.issuer, "https://id.example.com");
+        assert_eq!(oidc.groups_claim, "groups");
+        assert_eq!(oidc.session_ttl_hours, 12);
+        assert_eq!(
+            oidc.group_scopes["ops"],
+            vec![
+                crate::api::access::ApiScope::Read,
+                crate::api::access::ApiScope::Messaging
+            ]
+        );
+
+        assert!(
+            build(&format!(
+                "{oidc_section}[api.oidc.group_scopes]\nops = [\"everything\"]\n"
+            ))
+            .is_err()
+        );
+        assert!(build(&oidc_section.replace("https://id.example.com/", "not a url")).is_err());
+    }
 }
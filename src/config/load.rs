@@ -7,17 +7,21 @@ use super::providers::{
     OPENCODE_ZEN_PROVIDER_BASE_URL, OPENROUTER_PROVIDER_BASE_URL, TOGETHER_PROVIDER_BASE_URL,
     XAI_PROVIDER_BASE_URL, ZAI_CODING_PLAN_BASE_URL, ZHIPU_PROVIDER_BASE_URL,
     add_shorthand_provider, infer_routing_from_providers, openrouter_extra_headers,
-    resolve_routing,
+    parse_routing_profile, resolve_routing,
 };
 use super::toml_schema::*;
 use super::{
-    AgentConfig, ApiConfig, ApiType, Binding, BrowserConfig, ChannelConfig, ClosePolicy,
-    CoalesceConfig, CompactionConfig, Config, CortexConfig, CronDef, DefaultsConfig, DiscordConfig,
-    DiscordInstanceConfig, EmailConfig, EmailInstanceConfig, GroupDef, HumanDef, IngestionConfig,
-    LinkDef, LlmConfig, MattermostConfig, MattermostInstanceConfig, McpServerConfig, McpTransport,
-    MemoryPersistenceConfig, MessagingConfig, MetricsConfig, OpenCodeConfig, ProjectsConfig,
-    ProviderConfig, SignalConfig, SignalInstanceConfig, SlackCommandConfig, SlackConfig,
-    SlackInstanceConfig, TelegramConfig, TelegramInstanceConfig, TelemetryConfig, TwitchConfig,
+    AccessConfig, AccessRole, AgentConfig, ApiConfig, ApiType, BedrockConfig, Binding,
+    BindingAccess, BrowserConfig, BudgetConfig, BudgetLimits, ChannelConfig, ClosePolicy,
+    CoalesceConfig, CommandAction, CommandArgDef, CommandDef, CompactionConfig, Config,
+    CortexConfig, CronDef, DefaultsConfig, DiscordConfig, DiscordInstanceConfig, EmailConfig,
+    EmailInstanceConfig, GroupDef, HumanDef, IngestionConfig, IrcConfig, IrcInstanceConfig,
+    LinkDef, LlmConfig, MatrixConfig, MatrixInstanceConfig, MattermostConfig,
+    MattermostInstanceConfig, McpServerConfig, McpTransport, MemoryPersistenceConfig,
+    MessagingConfig, MetricsConfig, OidcConfig, OpenCodeConfig, ParticipantConfig, ProjectsConfig,
+    ProviderConfig, ResponseCacheConfig, SignalConfig, SignalInstanceConfig, SlackCommandConfig,
+    SlackConfig, SlackInstanceConfig, TeamsConfig, TeamsInstanceConfig, TelegramConfig,
+    TelegramInstanceConfig, TelemetryConfig, ToolApprovalConfig, ToolApprovalRule, TwitchConfig,
     TwitchInstanceConfig, WarmupConfig, WebhookConfig, normalize_adapter,
     validate_named_messaging_adapters,
 };
@@ -54,6 +58,51 @@ pub(crate) fn resolve_env_value(value: &str) -> Option<String> {
     }
 }
 
+/// Resolve the AWS signing settings for a Bedrock provider.
+///
+/// The access key ID and session token accept `secret:`/`env:` references
+/// like `api_key`. The region falls back to the one in a
+/// `bedrock-runtime.<region>.amazonaws.com` base URL.
+fn resolve_bedrock_config(
+    provider_id: &str,
+    config: &TomlProviderConfig,
+) -> anyhow::Result<Option<BedrockConfig>> {
+    if config.api_type != ApiType::Bedrock {
+        return Ok(None);
+    }
+
+    let access_key_id = config
+        .access_key_id
+        .as_deref()
+        .and_then(resolve_env_value)
+        .ok_or_else(|| {
+            anyhow::anyhow!("bedrock provider '{provider_id}' requires access_key_id")
+        })?;
+    let session_token = config.session_token.as_deref().and_then(resolve_env_value);
+    let region = config
+        .region
+        .clone()
+        .or_else(|| {
+            reqwest::Url::parse(&config.base_url)
+                .ok()?
+                .host_str()?
+                .strip_prefix("bedrock-runtime.")?
+                .strip_suffix(".amazonaws.com")
+                .map(ToString::to_string)
+        })
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "bedrock provider '{provider_id}' requires region (or a bedrock-runtime.<region>.amazonaws.com base_url)"
+            )
+        })?;
+
+    Ok(Some(BedrockConfig {
+        region,
+        access_key_id,
+        session_token,
+    }))
+}
+
 /// Process-wide reference to the secrets store for use during config resolution.
 ///
 /// Uses `ArcSwap` so it is accessible from any thread (file watcher, API
@@ -175,6 +224,31 @@ fn resolve_close_policy(
     })
 }
 
+impl ParticipantConfig {
+    fn resolve(overrides: TomlParticipantConfig, defaults: ParticipantConfig) -> ParticipantConfig {
+        ParticipantConfig {
+            enabled: overrides.enabled.unwrap_or(defaults.enabled),
+            summary_interval_secs: overrides
+                .summary_interval_secs
+                .unwrap_or(defaults.summary_interval_secs)
+                .max(1),
+            summary_max_words: overrides
+                .summary_max_words
+                .unwrap_or(defaults.summary_max_words),
+            summary_stale_after_secs: overrides
+                .summary_stale_after_secs
+                .unwrap_or(defaults.summary_stale_after_secs),
+            min_participants: overrides
+                .min_participants
+                .unwrap_or(defaults.min_participants)
+                .max(1),
+            max_summaries_per_pass: overrides
+                .max_summaries_per_pass
+                .unwrap_or(defaults.max_summaries_per_pass),
+        }
+    }
+}
+
 impl CortexConfig {
     fn resolve(overrides: TomlCortexConfig, defaults: CortexConfig) -> Result<CortexConfig> {
         let maintenance_interval_secs = overrides
@@ -186,6 +260,33 @@ impl CortexConfig {
             );
         }
 
+        let autonomy_interval_secs = overrides
+            .autonomy_interval_secs
+            .unwrap_or(defaults.autonomy_interval_secs);
+        if autonomy_interval_secs < 300 {
+            return Err(ConfigError::Invalid(format!(
+                "autonomy_interval_secs must be >= 300, got {autonomy_interval_secs}"
+            ))
+            .into());
+        }
+
+        let autonomy_active_hours = match (
+            overrides.autonomy_active_start_hour,
+            overrides.autonomy_active_end_hour,
+        ) {
+            (Some(start), Some(end)) => {
+                if start > 23 || end > 23 {
+                    return Err(ConfigError::Invalid(format!(
+                        "autonomy active hours must be between 0 and 23, got {start}-{end}"
+                    ))
+                    .into());
+                }
+                // An empty window (start == end) means always active.
+                (start != end).then_some((start, end))
+            }
+            _ => defaults.autonomy_active_hours,
+        };
+
         let config = CortexConfig {
             tick_interval_secs: overrides
                 .tick_interval_secs
@@ -227,6 +328,12 @@ impl CortexConfig {
             maintenance_merge_similarity_threshold: overrides
                 .maintenance_merge_similarity_threshold
                 .unwrap_or(defaults.maintenance_merge_similarity_threshold),
+            recent_tier_ttl_days: overrides
+                .recent_tier_ttl_days
+                .unwrap_or(defaults.recent_tier_ttl_days),
+            recent_tier_max: overrides
+                .recent_tier_max
+                .unwrap_or(defaults.recent_tier_max),
             association_interval_secs: overrides
                 .association_interval_secs
                 .unwrap_or(defaults.association_interval_secs),
@@ -245,12 +352,223 @@ impl CortexConfig {
             knowledge_synthesis_debounce_secs: overrides
                 .knowledge_synthesis_debounce_secs
                 .unwrap_or(defaults.knowledge_synthesis_debounce_secs),
+            autonomy_enabled: overrides
+                .autonomy_enabled
+                .unwrap_or(defaults.autonomy_enabled),
+            autonomy_interval_secs,
+            autonomy_max_turns: overrides
+                .autonomy_max_turns
+                .unwrap_or(defaults.autonomy_max_turns)
+                .max(1),
+            autonomy_max_workers: overrides
+                .autonomy_max_workers
+                .unwrap_or(defaults.autonomy_max_workers),
+            autonomy_tasks_require_approval: overrides
+                .autonomy_tasks_require_approval
+                .unwrap_or(defaults.autonomy_tasks_require_approval),
+            autonomy_active_hours,
         };
         config.validate_maintenance_bounds()?;
         Ok(config)
     }
 }
 
+impl BudgetConfig {
+    /// Overlay TOML budget settings on `defaults`. Channel and cron limits
+    /// merge by key, with the overrides winning.
+    fn resolve(overrides: TomlBudgetConfig, defaults: &BudgetConfig) -> Result<BudgetConfig> {
+        let soft_limit_ratio = overrides
+            .soft_limit_ratio
+            .unwrap_or(defaults.soft_limit_ratio);
+        if !(soft_limit_ratio > 0.0 && soft_limit_ratio <= 1.0) {
+            return Err(ConfigError::Invalid(format!(
+                "budget soft_limit_ratio must be in (0, 1], got {soft_limit_ratio}"
+            ))
+            .into());
+        }
+
+        let limits = resolve_budget_limits(
+            "agent",
+            overrides.daily_limit_usd,
+            overrides.monthly_limit_usd,
+            defaults.limits,
+        )?;
+        let mut channels = defaults.channels.clone();
+        for (channel_id, scoped) in overrides.channels {
+            let scoped = resolve_budget_limits(
+                &channel_id,
+                scoped.daily_limit_usd,
+                scoped.monthly_limit_usd,
+                BudgetLimits::default(),
+            )?;
+            channels.insert(channel_id, scoped);
+        }
+        let mut cron = defaults.cron.clone();
+        for (cron_id, scoped) in overrides.cron {
+            let scoped = resolve_budget_limits(
+                &cron_id,
+                scoped.daily_limit_usd,
+                scoped.monthly_limit_usd,
+                BudgetLimits::default(),
+            )?;
+            cron.insert(cron_id, scoped);
+        }
+
+        let warning_target = overrides
+            .warning_target
+            .or_else(|| defaults.warning_target.clone());
+        if let Some(target) = warning_target.as_deref()
+            && crate::messaging::target::parse_delivery_target(target).is_none()
+        {
+            tracing::warn!(
+                warning_target = target,
+                "invalid budget warning_target, soft-limit warnings will only be logged"
+            );
+        }
+
+        Ok(BudgetConfig {
+            limits,
+            soft_limit_ratio,
+            warning_target,
+            downgrade_on_soft_limit: overrides
+                .downgrade_on_soft_limit
+                .unwrap_or(defaults.downgrade_on_soft_limit),
+            downgrade_model: overrides
+                .downgrade_model
+                .or_else(|| defaults.downgrade_model.clone()),
+            channels,
+            cron,
+        })
+    }
+}
+
+impl ResponseCacheConfig {
+    /// Overlay TOML response cache settings on `defaults`.
+    fn resolve(
+        overrides: TomlResponseCacheConfig,
+        defaults: &ResponseCacheConfig,
+    ) -> Result<ResponseCacheConfig> {
+        let process_types = overrides
+            .process_types
+            .unwrap_or_else(|| defaults.process_types.clone());
+        if let Some(process_type) = process_types.iter().find(|process_type| {
+            matches!(
+                process_type,
+                crate::ProcessType::Channel | crate::ProcessType::Worker
+            )
+        }) {
+            return Err(ConfigError::Invalid(format!(
+                "response_cache cannot cache {process_type} completions"
+            ))
+            .into());
+        }
+
+        let similarity_threshold = overrides
+            .similarity_threshold
+            .or(defaults.similarity_threshold);
+        if let Some(threshold) = similarity_threshold
+            && !(threshold > 0.0 && threshold <= 1.0)
+        {
+            return Err(ConfigError::Invalid(format!(
+                "response_cache similarity_threshold must be in (0, 1], got {threshold}"
+            ))
+            .into());
+        }
+
+        Ok(ResponseCacheConfig {
+            enabled: overrides.enabled.unwrap_or(defaults.enabled),
+            process_types,
+            ttl_secs: overrides.ttl_secs.unwrap_or(defaults.ttl_secs),
+            max_entries: overrides.max_entries.unwrap_or(defaults.max_entries),
+            similarity_threshold,
+        })
+    }
+}
+
+impl ToolApprovalConfig {
+    /// Overlay TOML tool approval settings on `defaults`. Rules replace the
+    /// inherited list as a whole rather than appending to it.
+    fn resolve(
+        overrides: TomlToolApprovalConfig,
+        defaults: &ToolApprovalConfig,
+    ) -> Result<ToolApprovalConfig> {
+        let rules = match overrides.rules {
+            Some(rules) => rules
+                .into_iter()
+                .map(|rule| {
+                    let args_pattern = rule
+                        .args_pattern
+                        .map(|pattern| {
+                            regex::Regex::new(&pattern).map_err(|error| {
+                                ConfigError::Invalid(format!(
+                                    "tool_approval rule for '{}' has an invalid args_pattern: {error}",
+                                    rule.tool
+                                ))
+                            })
+                        })
+                        .transpose()?;
+                    Ok(ToolApprovalRule {
+                        tool: rule.tool,
+                        args_pattern,
+                        sandbox: rule.sandbox,
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?,
+            None => defaults.rules.clone(),
+        };
+
+        let timeout_secs = overrides.timeout_secs.unwrap_or(defaults.timeout_secs);
+        if timeout_secs == 0 {
+            return Err(ConfigError::Invalid(
+                "tool_approval timeout_secs must be greater than 0".into(),
+            )
+            .into());
+        }
+
+        Ok(ToolApprovalConfig {
+            rules,
+            timeout_secs,
+        })
+    }
+}
+
+impl AccessConfig {
+    fn resolve(overrides: TomlAccessConfig, defaults: &AccessConfig) -> AccessConfig {
+        AccessConfig {
+            enabled: overrides.enabled.unwrap_or(defaults.enabled),
+            default_role: overrides.default_role.unwrap_or(defaults.default_role),
+            commands: overrides.commands.unwrap_or(defaults.commands),
+            spawn_worker: overrides.spawn_worker.unwrap_or(defaults.spawn_worker),
+            secret_set: overrides.secret_set.unwrap_or(defaults.secret_set),
+            cron: overrides.cron.unwrap_or(defaults.cron),
+            task_approval: overrides.task_approval.unwrap_or(defaults.task_approval),
+            tool_approval: overrides.tool_approval.unwrap_or(defaults.tool_approval),
+        }
+    }
+}
+
+fn resolve_budget_limits(
+    scope: &str,
+    daily_usd: Option<f64>,
+    monthly_usd: Option<f64>,
+    defaults: BudgetLimits,
+) -> Result<BudgetLimits> {
+    for (period, value) in [("daily", daily_usd), ("monthly", monthly_usd)] {
+        if let Some(value) = value
+            && !(value.is_finite() && value >= 0.0)
+        {
+            return Err(ConfigError::Invalid(format!(
+                "budget {period}_limit_usd for {scope} must be a non-negative number, got {value}"
+            ))
+            .into());
+        }
+    }
+    Ok(BudgetLimits {
+        daily_usd: daily_usd.or(defaults.daily_usd),
+        monthly_usd: monthly_usd.or(defaults.monthly_usd),
+    })
+}
+
 fn parse_otlp_headers(value: Option<String>) -> Result<HashMap<String, String>> {
     let Some(raw) = value else {
         return Ok(HashMap::new());
@@ -285,6 +603,152 @@ fn parse_otlp_headers(value: Option<String>) -> Result<HashMap<String, String>>
     Ok(headers)
 }
 
+fn parse_oidc_config(raw: TomlOidcConfig) -> Result<OidcConfig> {
+    let issuer = raw.issuer.trim().trim_end_matches('/').to_string();
+    for (key, value) in [("issuer", &issuer), ("redirect_url", &raw.redirect_url)] {
+        if let Err(error) = reqwest::Url::parse(value) {
+            return Err(ConfigError::Invalid(format!(
+                "api.oidc.{key} '{value}' is not a valid URL: {error}"
+            ))
+            .into());
+        }
+    }
+    if raw.client_id.trim().is_empty() {
+        return Err(ConfigError::Invalid("api.oidc.client_id must not be empty".into()).into());
+    }
+
+    let mut group_scopes = HashMap::with_capacity(raw.group_scopes.len());
+    for (group, scope_names) in raw.group_scopes {
+        let mut scopes = Vec::with_capacity(scope_names.len());
+        for name in &scope_names {
+            let scope = crate::api::access::ApiScope::parse(name).ok_or_else(|| {
+                ConfigError::Invalid(format!(
+                    "api.oidc.group_scopes.{group}: unknown scope '{name}'"
+                ))
+            })?;
+            scopes.push(scope);
+        }
+        group_scopes.insert(group, scopes);
+    }
+    if group_scopes.is_empty() {
+        tracing::warn!("api.oidc.group_scopes is empty, so no one can log in with single sign-on");
+    }
+
+    Ok(OidcConfig {
+        issuer,
+        client_id: raw.client_id.trim().to_string(),
+        client_secret: raw.client_secret.as_deref().and_then(resolve_env_value),
+        redirect_url: raw.redirect_url,
+        scopes: raw.scopes,
+        groups_claim: raw.groups_claim,
+        group_scopes,
+        session_ttl_hours: raw.session_ttl_hours.max(1),
+    })
+}
+
+fn parse_command_defs(raw: Vec<TomlCommandDef>) -> Result<Vec<CommandDef>> {
+    let mut commands: Vec<CommandDef> = Vec::with_capacity(raw.len());
+    for raw_command in raw {
+        let command = parse_command_def(raw_command)?;
+        if commands
+            .iter()
+            .any(|existing| existing.name == command.name)
+        {
+            return Err(ConfigError::Invalid(format!(
+                "command '/{}' is declared more than once",
+                command.name
+            ))
+            .into());
+        }
+        commands.push(command);
+    }
+    Ok(commands)
+}
+
+fn parse_command_def(raw: TomlCommandDef) -> Result<CommandDef> {
+    let name = raw.name.trim().trim_start_matches('/').to_string();
+    if !crate::commands::is_valid_command_name(&name) {
+        return Err(ConfigError::Invalid(format!(
+            "command name '{}' must be 1-32 lowercase letters, digits, '-' or '_'",
+            raw.name
+        ))
+        .into());
+    }
+    if crate::commands::is_builtin(&name) {
+        return Err(
+            ConfigError::Invalid(format!("command '/{name}' conflicts with a built-in")).into(),
+        );
+    }
+
+    let mut args: Vec<CommandArgDef> = Vec::with_capacity(raw.args.len());
+    for arg in raw.args {
+        if !crate::commands::is_valid_arg_name(&arg.name) {
+            return Err(ConfigError::Invalid(format!(
+                "command '/{name}' argument '{}' must be 1-32 lowercase letters, digits or '_' \
+                 (and not 'args')",
+                arg.name
+            ))
+            .into());
+        }
+        if args.iter().any(|existing| existing.name == arg.name) {
+            return Err(ConfigError::Invalid(format!(
+                "command '/{name}' declares argument '{}' twice",
+                arg.name
+            ))
+            .into());
+        }
+        if arg.required && args.iter().any(|existing| !existing.required) {
+            return Err(ConfigError::Invalid(format!(
+                "command '/{name}' has required argument '{}' after an optional one",
+                arg.name
+            ))
+            .into());
+        }
+        args.push(CommandArgDef {
+            name: arg.name,
+            description: arg.description,
+            required: arg.required,
+        });
+    }
+
+    let action = match (raw.prompt, raw.skill, raw.cron, raw.task) {
+        (Some(template), None, None, None) => CommandAction::Prompt(template),
+        (task, Some(skill), None, None) => CommandAction::Skill { skill, task },
+        (None, None, Some(cron_id), None) => CommandAction::Cron(cron_id),
+        (None, None, None, Some(task)) => {
+            let priority = match task.priority.as_deref() {
+                None => crate::tasks::TaskPriority::Medium,
+                Some(value) => crate::tasks::TaskPriority::parse(value).ok_or_else(|| {
+                    ConfigError::Invalid(format!(
+                        "command '/{name}' has invalid task priority '{value}', expected \
+                         critical, high, medium or low"
+                    ))
+                })?,
+            };
+            CommandAction::Task {
+                title: task.title,
+                description: task.description,
+                priority,
+            }
+        }
+        _ => {
+            return Err(ConfigError::Invalid(format!(
+                "command '/{name}' must set exactly one of 'prompt', 'skill', 'cron' or 'task' \
+                 ('prompt' may accompany 'skill' as the worker task)"
+            ))
+            .into());
+        }
+    };
+
+    Ok(CommandDef {
+        name,
+        description: raw.description,
+        args,
+        role: raw.role.unwrap_or(AccessRole::Member),
+        action,
+    })
+}
+
 fn parse_mcp_server_config(raw: TomlMcpServerConfig) -> Result<McpServerConfig> {
     if raw.name.trim().is_empty() {
         return Err(ConfigError::Invalid("mcp server name cannot be empty".into()).into());
@@ -489,6 +953,8 @@ impl Config {
                     extra_headers: vec![],
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -504,6 +970,8 @@ impl Config {
                     extra_headers: openrouter_extra_headers(),
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -577,6 +1045,8 @@ impl Config {
                     extra_headers: vec![],
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -592,6 +1062,8 @@ impl Config {
                     extra_headers: vec![],
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -607,6 +1079,8 @@ impl Config {
                     extra_headers: vec![],
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -622,6 +1096,8 @@ impl Config {
                     extra_headers: openrouter_extra_headers(),
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -665,6 +1141,8 @@ impl Config {
                     extra_headers: vec![],
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -680,6 +1158,8 @@ impl Config {
                     extra_headers: vec![],
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -695,6 +1175,8 @@ impl Config {
                     extra_headers: vec![],
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -710,6 +1192,8 @@ impl Config {
                     extra_headers: vec![],
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -725,6 +1209,8 @@ impl Config {
                     extra_headers: vec![],
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -740,6 +1226,8 @@ impl Config {
                     extra_headers: vec![],
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -755,6 +1243,8 @@ impl Config {
                     extra_headers: vec![],
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -770,6 +1260,8 @@ impl Config {
                     extra_headers: vec![],
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -785,6 +1277,8 @@ impl Config {
                     extra_headers: vec![],
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -800,6 +1294,8 @@ impl Config {
                     extra_headers: vec![],
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -815,6 +1311,8 @@ impl Config {
                     extra_headers: vec![],
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -830,6 +1328,8 @@ impl Config {
                     extra_headers: vec![],
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -845,6 +1345,8 @@ impl Config {
                     extra_headers: vec![],
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -863,6 +1365,8 @@ impl Config {
                     extra_headers: vec![],
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -923,9 +1427,15 @@ impl Config {
             coalesce: None,
             ingestion: None,
             cortex: None,
+            participants: None,
             warmup: None,
             browser: None,
             channel: None,
+            budget: None,
+            response_cache: None,
+            tool_approval: None,
+            access: None,
+            commands: None,
             mcp: None,
             brave_search_key: None,
             cron_timezone: None,
@@ -963,6 +1473,7 @@ impl Config {
                 telegram_id: None,
                 slack_id: None,
                 email: None,
+                access_role: None,
             }],
             messaging: MessagingConfig::default(),
             bindings: Vec::new(),
@@ -1175,6 +1686,7 @@ impl Config {
                     let api_key = resolve_env_value(&config.api_key).ok_or_else(|| {
                         anyhow::anyhow!("failed to resolve API key for provider '{}'", provider_id)
                     })?;
+                    let bedrock = resolve_bedrock_config(&provider_id, &config)?;
                     let normalized_id = provider_id.to_lowercase();
                     let extra_headers = if normalized_id == "openrouter" {
                         openrouter_extra_headers()
@@ -1192,6 +1704,8 @@ impl Config {
                             extra_headers,
                             api_version: config.api_version,
                             deployment: config.deployment,
+                            bedrock,
+                            cassette: config.cassette,
                         },
                     ))
                 })
@@ -1220,6 +1734,8 @@ impl Config {
                     extra_headers: vec![],
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -1235,6 +1751,8 @@ impl Config {
                     extra_headers: vec![],
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -1250,6 +1768,8 @@ impl Config {
                     extra_headers: openrouter_extra_headers(),
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -1323,6 +1843,8 @@ impl Config {
                     extra_headers: vec![],
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -1338,6 +1860,8 @@ impl Config {
                     extra_headers: vec![],
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -1353,6 +1877,8 @@ impl Config {
                     extra_headers: vec![],
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -1368,6 +1894,8 @@ impl Config {
                     extra_headers: vec![],
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -1383,6 +1911,8 @@ impl Config {
                     extra_headers: vec![],
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -1398,6 +1928,8 @@ impl Config {
                     extra_headers: vec![],
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -1413,6 +1945,8 @@ impl Config {
                     extra_headers: vec![],
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -1428,6 +1962,8 @@ impl Config {
                     extra_headers: vec![],
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -1443,6 +1979,8 @@ impl Config {
                     extra_headers: vec![],
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -1458,6 +1996,8 @@ impl Config {
                     extra_headers: vec![],
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -1473,6 +2013,8 @@ impl Config {
                     extra_headers: vec![],
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -1491,6 +2033,8 @@ impl Config {
                     extra_headers: vec![],
                     api_version: None,
                     deployment: None,
+                    bedrock: None,
+                    cassette: None,
                 });
         }
 
@@ -1503,6 +2047,7 @@ impl Config {
             .into_iter()
             .map(parse_mcp_server_config)
             .collect::<Result<Vec<_>>>()?;
+        let default_commands = parse_command_defs(toml.defaults.commands)?;
 
         let base_defaults = DefaultsConfig::default();
         // When `[defaults.routing]` is absent, infer sane routing from the
@@ -1597,6 +2142,11 @@ impl Config {
                 .map(|c| CortexConfig::resolve(c, base_defaults.cortex))
                 .transpose()?
                 .unwrap_or(base_defaults.cortex),
+            participants: toml
+                .defaults
+                .participants
+                .map(|p| ParticipantConfig::resolve(p, base_defaults.participants))
+                .unwrap_or(base_defaults.participants),
             warmup: toml
                 .defaults
                 .warmup
@@ -1661,6 +2211,30 @@ impl Config {
                     }
                 })
                 .unwrap_or(base_defaults.channel),
+            budget: toml
+                .defaults
+                .budget
+                .map(|b| BudgetConfig::resolve(b, &base_defaults.budget))
+                .transpose()?
+                .unwrap_or_else(|| base_defaults.budget.clone()),
+            response_cache: toml
+                .defaults
+                .response_cache
+                .map(|c| ResponseCacheConfig::resolve(c, &base_defaults.response_cache))
+                .transpose()?
+                .unwrap_or_else(|| base_defaults.response_cache.clone()),
+            tool_approval: toml
+                .defaults
+                .tool_approval
+                .map(|c| ToolApprovalConfig::resolve(c, &base_defaults.tool_approval))
+                .transpose()?
+                .unwrap_or_else(|| base_defaults.tool_approval.clone()),
+            access: toml
+                .defaults
+                .access
+                .map(|c| AccessConfig::resolve(c, &base_defaults.access))
+                .unwrap_or_else(|| base_defaults.access.clone()),
+            commands: default_commands,
             mcp: default_mcp,
             brave_search_key: toml
                 .defaults
@@ -1749,10 +2323,19 @@ impl Config {
             .into_iter()
             .map(|a| -> Result<AgentConfig> {
                 // Per-agent routing resolves against instance defaults
-                let agent_routing = a
+                let mut agent_routing = a
                     .routing
                     .map(|r| resolve_routing(Some(r), &defaults.routing));
 
+                // `routing_profile` is a per-agent shorthand for
+                // `routing.prompt_routing.profile`.
+                if let Some(profile) = parse_routing_profile(a.routing_profile.as_deref()) {
+                    agent_routing
+                        .get_or_insert_with(|| defaults.routing.clone())
+                        .prompt_routing
+                        .profile = profile;
+                }
+
                 let cron = a
                     .cron
                     .into_iter()
@@ -1768,6 +2351,7 @@ impl Config {
                         },
                         enabled: h.enabled,
                         run_once: h.run_once,
+                        stateful: h.stateful,
                         timeout_secs: h.timeout_secs,
                     })
                     .collect();
@@ -1824,6 +2408,9 @@ impl Config {
                         .cortex
                         .map(|c| CortexConfig::resolve(c, defaults.cortex))
                         .transpose()?,
+                    participants: a
+                        .participants
+                        .map(|p| ParticipantConfig::resolve(p, defaults.participants)),
                     warmup: a.warmup.map(|w| WarmupConfig {
                         enabled: w.enabled.unwrap_or(defaults.warmup.enabled),
                         eager_embedding_load: w
@@ -1874,6 +2461,20 @@ impl Config {
                                 .unwrap_or(defaults.channel.save_attachments),
                         }
                     }),
+                    budget: a
+                        .budget
+                        .map(|b| BudgetConfig::resolve(b, &defaults.budget))
+                        .transpose()?,
+                    response_cache: a
+                        .response_cache
+                        .map(|c| ResponseCacheConfig::resolve(c, &defaults.response_cache))
+                        .transpose()?,
+                    tool_approval: a
+                        .tool_approval
+                        .map(|c| ToolApprovalConfig::resolve(c, &defaults.tool_approval))
+                        .transpose()?,
+                    access: a.access.map(|c| AccessConfig::resolve(c, &defaults.access)),
+                    commands: a.commands.map(parse_command_defs).transpose()?,
                     mcp: match a.mcp {
                         Some(mcp_servers) => Some(
                             mcp_servers
@@ -1934,9 +2535,15 @@ impl Config {
                 coalesce: None,
                 ingestion: None,
                 cortex: None,
+                participants: None,
                 warmup: None,
                 browser: None,
                 channel: None,
+                budget: None,
+                response_cache: None,
+                tool_approval: None,
+                access: None,
+                commands: None,
                 mcp: None,
                 brave_search_key: None,
                 cron_timezone: None,
@@ -2153,6 +2760,7 @@ impl Config {
                             from_address,
                             from_name,
                             poll_interval_secs: instance.poll_interval_secs,
+                            idle: instance.idle,
                             folders: if instance.folders.is_empty() {
                                 vec!["INBOX".to_string()]
                             } else {
@@ -2223,6 +2831,7 @@ impl Config {
                     from_address,
                     from_name,
                     poll_interval_secs: email.poll_interval_secs,
+                    idle: email.idle,
                     folders: if email.folders.is_empty() {
                         vec!["INBOX".to_string()]
                     } else {
@@ -2239,6 +2848,7 @@ impl Config {
                 port: w.port,
                 bind: w.bind,
                 auth_token: w.auth_token.as_deref().and_then(resolve_env_value),
+                signing_secret: w.signing_secret.as_deref().and_then(resolve_env_value),
             }),
             twitch: toml.messaging.twitch.and_then(|t| {
                 let instances = t
@@ -2415,6 +3025,173 @@ impl Config {
                     max_attachment_bytes: mm.max_attachment_bytes,
                 })
             }),
+            matrix: toml.messaging.matrix.and_then(|mx| {
+                let instances = mx
+                    .instances
+                    .into_iter()
+                    .map(|instance| {
+                        let access_token =
+                            instance.access_token.as_deref().and_then(resolve_env_value);
+                        let homeserver_url =
+                            instance.homeserver_url.as_deref().and_then(resolve_env_value);
+                        let has_credentials = access_token.is_some() && homeserver_url.is_some();
+                        if instance.enabled && !has_credentials {
+                            tracing::warn!(
+                                adapter = %instance.name,
+                                "matrix instance is enabled but credentials are missing/unresolvable — disabling"
+                            );
+                        }
+                        MatrixInstanceConfig {
+                            name: instance.name,
+                            enabled: instance.enabled && has_credentials,
+                            homeserver_url: homeserver_url.unwrap_or_default(),
+                            access_token: access_token.unwrap_or_default(),
+                            dm_allowed_users: instance.dm_allowed_users,
+                            invite_allowed_users: instance.invite_allowed_users,
+                            max_attachment_bytes: instance.max_attachment_bytes,
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                let access_token = std::env::var("MATRIX_ACCESS_TOKEN")
+                    .ok()
+                    .or_else(|| mx.access_token.as_deref().and_then(resolve_env_value));
+                let homeserver_url = std::env::var("MATRIX_HOMESERVER_URL")
+                    .ok()
+                    .or_else(|| mx.homeserver_url.as_deref().and_then(resolve_env_value));
+
+                if (access_token.is_none() || homeserver_url.is_none()) && instances.is_empty() {
+                    tracing::warn!("matrix config present but no credentials found");
+                    return None;
+                }
+
+                Some(MatrixConfig {
+                    enabled: mx.enabled,
+                    homeserver_url: homeserver_url.unwrap_or_default(),
+                    access_token: access_token.unwrap_or_default(),
+                    instances,
+                    dm_allowed_users: mx.dm_allowed_users,
+                    invite_allowed_users: mx.invite_allowed_users,
+                    max_attachment_bytes: mx.max_attachment_bytes,
+                })
+            }),
+            irc: toml.messaging.irc.and_then(|irc| {
+                let resolve = |value: Option<String>| value.as_deref().and_then(resolve_env_value);
+                let instances = irc
+                    .instances
+                    .into_iter()
+                    .map(|instance| {
+                        let server = resolve(instance.server);
+                        let nickname = resolve(instance.nickname);
+                        let has_credentials = server.is_some() && nickname.is_some();
+                        if instance.enabled && !has_credentials {
+                            tracing::warn!(
+                                adapter = %instance.name,
+                                "irc instance is enabled but server/nickname are missing/unresolvable — disabling"
+                            );
+                        }
+                        IrcInstanceConfig {
+                            name: instance.name,
+                            enabled: instance.enabled && has_credentials,
+                            server: server.unwrap_or_default(),
+                            port: instance.port,
+                            tls: instance.tls,
+                            nickname: nickname.unwrap_or_default(),
+                            username: instance.username,
+                            realname: instance.realname,
+                            server_password: resolve(instance.server_password),
+                            sasl_username: resolve(instance.sasl_username),
+                            sasl_password: resolve(instance.sasl_password),
+                            channels: instance.channels,
+                            dm_allowed_users: instance.dm_allowed_users,
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                let server = std::env::var("IRC_SERVER")
+                    .ok()
+                    .or_else(|| resolve(irc.server));
+                let nickname = std::env::var("IRC_NICKNAME")
+                    .ok()
+                    .or_else(|| resolve(irc.nickname));
+                let sasl_password = std::env::var("IRC_SASL_PASSWORD")
+                    .ok()
+                    .or_else(|| resolve(irc.sasl_password));
+
+                if (server.is_none() || nickname.is_none()) && instances.is_empty() {
+                    tracing::warn!("irc config present but no server/nickname found");
+                    return None;
+                }
+
+                Some(IrcConfig {
+                    enabled: irc.enabled,
+                    server: server.unwrap_or_default(),
+                    port: irc.port,
+                    tls: irc.tls,
+                    nickname: nickname.unwrap_or_default(),
+                    username: irc.username,
+                    realname: irc.realname,
+                    server_password: resolve(irc.server_password),
+                    sasl_username: resolve(irc.sasl_username),
+                    sasl_password,
+                    channels: irc.channels,
+                    instances,
+                    dm_allowed_users: irc.dm_allowed_users,
+                })
+            }),
+            teams: toml.messaging.teams.and_then(|teams| {
+                let resolve = |value: Option<String>| value.as_deref().and_then(resolve_env_value);
+                let instances = teams
+                    .instances
+                    .into_iter()
+                    .map(|instance| {
+                        let app_id = resolve(instance.app_id);
+                        let app_password = resolve(instance.app_password);
+                        let has_credentials = app_id.is_some() && app_password.is_some();
+                        if instance.enabled && !has_credentials {
+                            tracing::warn!(
+                                adapter = %instance.name,
+                                "teams instance is enabled but app_id/app_password are missing/unresolvable — disabling"
+                            );
+                        }
+                        TeamsInstanceConfig {
+                            name: instance.name,
+                            enabled: instance.enabled && has_credentials,
+                            app_id: app_id.unwrap_or_default(),
+                            app_password: app_password.unwrap_or_default(),
+                            tenant_id: resolve(instance.tenant_id),
+                            bind: instance.bind,
+                            port: instance.port,
+                            dm_allowed_users: instance.dm_allowed_users,
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                let app_id = std::env::var("TEAMS_APP_ID")
+                    .ok()
+                    .or_else(|| resolve(teams.app_id));
+                let app_password = std::env::var("TEAMS_APP_PASSWORD")
+                    .ok()
+                    .or_else(|| resolve(teams.app_password));
+
+                if (app_id.is_none() || app_password.is_none()) && instances.is_empty() {
+                    tracing::warn!("teams config present but no app_id/app_password found");
+                    return None;
+                }
+
+                Some(TeamsConfig {
+                    enabled: teams.enabled,
+                    app_id: app_id.unwrap_or_default(),
+                    app_password: app_password.unwrap_or_default(),
+                    tenant_id: std::env::var("TEAMS_TENANT_ID")
+                        .ok()
+                        .or_else(|| resolve(teams.tenant_id)),
+                    bind: teams.bind,
+                    port: teams.port,
+                    instances,
+                    dm_allowed_users: teams.dm_allowed_users,
+                })
+            }),
         };
 
         let bindings: Vec<Binding> = toml
@@ -2426,6 +3203,7 @@ impl Config {
                     let mut cs = ConversationSettings {
                         model: s.model,
                         save_attachments: s.save_attachments,
+                        streaming: s.streaming,
                         ..Default::default()
                     };
                     // Only override enum fields when explicitly set in TOML,
@@ -2476,6 +3254,11 @@ impl Config {
                     require_mention: b.require_mention,
                     dm_allowed_users: b.dm_allowed_users,
                     settings,
+                    access: BindingAccess {
+                        default_role: b.access.default_role,
+                        users: b.access.users,
+                        groups: b.access.groups,
+                    },
                 }
             })
             .collect();
@@ -2487,6 +3270,7 @@ impl Config {
             port: toml.api.port,
             bind: hosted_api_bind(toml.api.bind),
             auth_token: toml.api.auth_token.as_deref().and_then(resolve_env_value),
+            oidc: toml.api.oidc.map(parse_oidc_config).transpose()?,
         };
 
         let metrics = MetricsConfig {
@@ -2563,6 +3347,7 @@ impl Config {
                     telegram_id: h.telegram_id,
                     slack_id: h.slack_id,
                     email: h.email,
+                    access_role: h.access_role,
                 }
             })
             .collect();
@@ -2580,6 +3365,7 @@ impl Config {
                 telegram_id: None,
                 slack_id: None,
                 email: None,
+                access_role: None,
             });
 
             // Link the default admin to the default agent so the agent sees
@@ -1,7 +1,8 @@
 use super::{
-    Binding, DiscordConfig, DiscordInstanceConfig, MattermostConfig, MattermostInstanceConfig,
-    SignalConfig, SignalInstanceConfig, SlackConfig, SlackInstanceConfig, TelegramConfig,
-    TelegramInstanceConfig, TwitchConfig, TwitchInstanceConfig,
+    Binding, DiscordConfig, DiscordInstanceConfig, IrcConfig, IrcInstanceConfig, MatrixConfig,
+    MatrixInstanceConfig, MattermostConfig, MattermostInstanceConfig, SignalConfig,
+    SignalInstanceConfig, SlackConfig, SlackInstanceConfig, TeamsConfig, TeamsInstanceConfig,
+    TelegramConfig, TelegramInstanceConfig, TwitchConfig, TwitchInstanceConfig,
 };
 use std::collections::HashMap;
 
@@ -531,6 +532,279 @@ impl MattermostPermissions {
     }
 }
 
+/// Per-adapter permissions for the Matrix platform.
+///
+/// Room IDs come from `channel_ids` on `channel = "matrix"` bindings.
+#[derive(Debug, Clone, Default)]
+pub struct MatrixPermissions {
+    /// Rooms the adapter listens in. `None` means any joined room.
+    pub room_filter: Option<Vec<String>>,
+    pub dm_allowed_users: Vec<String>,
+    pub invite_allowed_users: Vec<String>,
+}
+
+impl MatrixPermissions {
+    pub fn from_config(config: &MatrixConfig, bindings: &[Binding]) -> Self {
+        Self::from_bindings_for_adapter(
+            config.dm_allowed_users.clone(),
+            config.invite_allowed_users.clone(),
+            bindings,
+            None,
+        )
+    }
+
+    pub fn from_instance_config(instance: &MatrixInstanceConfig, bindings: &[Binding]) -> Self {
+        Self::from_bindings_for_adapter(
+            instance.dm_allowed_users.clone(),
+            instance.invite_allowed_users.clone(),
+            bindings,
+            Some(instance.name.as_str()),
+        )
+    }
+
+    fn from_bindings_for_adapter(
+        seed_dm_allowed_users: Vec<String>,
+        invite_allowed_users: Vec<String>,
+        bindings: &[Binding],
+        adapter_selector: Option<&str>,
+    ) -> Self {
+        let matrix_bindings: Vec<&Binding> = bindings
+            .iter()
+            .filter(|b| {
+                b.channel == "matrix" && binding_adapter_selector_matches(b, adapter_selector)
+            })
+            .collect();
+
+        let room_filter = {
+            let room_ids: Vec<String> = matrix_bindings
+                .iter()
+                .flat_map(|b| b.channel_ids.clone())
+                .collect();
+            if room_ids.is_empty() {
+                None
+            } else {
+                Some(room_ids)
+            }
+        };
+
+        let mut dm_allowed_users = seed_dm_allowed_users;
+        for binding in &matrix_bindings {
+            for id in &binding.dm_allowed_users {
+                if !dm_allowed_users.contains(id) {
+                    dm_allowed_users.push(id.clone());
+                }
+            }
+        }
+
+        Self {
+            room_filter,
+            dm_allowed_users,
+            invite_allowed_users,
+        }
+    }
+
+    /// Whether the adapter should act on messages from `room_id`.
+    pub fn allows_room(&self, room_id: &str) -> bool {
+        self.room_filter
+            .as_ref()
+            .is_none_or(|rooms| rooms.iter().any(|room| room == room_id))
+    }
+}
+
+/// Per-adapter permissions for the IRC platform.
+///
+/// Channel names come from `channel_ids` on `channel = "irc"` bindings and
+/// are compared case-insensitively.
+#[derive(Debug, Clone, Default)]
+pub struct IrcPermissions {
+    /// Channels the adapter responds in. `None` means every joined channel.
+    pub channel_filter: Option<Vec<String>>,
+    /// Nicks (or services account names) allowed to message the bot directly.
+    pub dm_allowed_users: Vec<String>,
+}
+
+impl IrcPermissions {
+    pub fn from_config(config: &IrcConfig, bindings: &[Binding]) -> Self {
+        Self::from_bindings_for_adapter(config.dm_allowed_users.clone(), bindings, None)
+    }
+
+    pub fn from_instance_config(instance: &IrcInstanceConfig, bindings: &[Binding]) -> Self {
+        Self::from_bindings_for_adapter(
+            instance.dm_allowed_users.clone(),
+            bindings,
+            Some(instance.name.as_str()),
+        )
+    }
+
+    fn from_bindings_for_adapter(
+        seed_dm_allowed_users: Vec<String>,
+        bindings: &[Binding],
+        adapter_selector: Option<&str>,
+    ) -> Self {
+        let irc_bindings: Vec<&Binding> = bindings
+            .iter()
+            .filter(|b| b.channel == "irc" && binding_adapter_selector_matches(b, adapter_selector))
+            .collect();
+
+        let channel_filter = {
+            let channels: Vec<String> = irc_bindings
+                .iter()
+                .flat_map(|b| b.channel_ids.iter().map(|id| id.to_ascii_lowercase()))
+                .collect();
+            if channels.is_empty() {
+                None
+            } else {
+                Some(channels)
+            }
+        };
+
+        let mut dm_allowed_users = seed_dm_allowed_users;
+        for binding in &irc_bindings {
+            for id in &binding.dm_allowed_users {
+                if !dm_allowed_users
+                    .iter()
+                    .any(|existing| existing.eq_ignore_ascii_case(id))
+                {
+                    dm_allowed_users.push(id.clone());
+                }
+            }
+        }
+
+        Self {
+            channel_filter,
+            dm_allowed_users,
+        }
+    }
+
+    /// Whether the adapter should act on messages in `channel`.
+    pub fn allows_channel(&self, channel: &str) -> bool {
+        self.channel_filter.as_ref().is_none_or(|channels| {
+            channels
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(channel))
+        })
+    }
+
+    /// Whether a direct message from `nick` may reach the agent. When the
+    /// server reports the sender's services account, that is matched instead
+    /// of the (unauthenticated) nick. Empty list = nobody (fail-closed).
+    pub fn allows_dm(&self, nick: &str, account: Option<&str>) -> bool {
+        let identity = account.unwrap_or(nick);
+        self.dm_allowed_users
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(identity))
+    }
+}
+
+/// Per-adapter permissions for the Microsoft Teams platform.
+///
+/// Team IDs come from `team_id` and channel/chat IDs from `channel_ids` on
+/// `channel = "teams"` bindings.
+#[derive(Debug, Clone, Default)]
+pub struct TeamsPermissions {
+    /// Teams the adapter responds in. `None` means every team it is installed in.
+    pub team_filter: Option<Vec<String>>,
+    /// Channel or group chat IDs the adapter responds in. `None` means all.
+    pub channel_filter: Option<Vec<String>>,
+    /// Azure AD object IDs allowed to message the bot in personal chats.
+    pub dm_allowed_users: Vec<String>,
+}
+
+impl TeamsPermissions {
+    pub fn from_config(config: &TeamsConfig, bindings: &[Binding]) -> Self {
+        Self::from_bindings_for_adapter(config.dm_allowed_users.clone(), bindings, None)
+    }
+
+    pub fn from_instance_config(instance: &TeamsInstanceConfig, bindings: &[Binding]) -> Self {
+        Self::from_bindings_for_adapter(
+            instance.dm_allowed_users.clone(),
+            bindings,
+            Some(instance.name.as_str()),
+        )
+    }
+
+    fn from_bindings_for_adapter(
+        seed_dm_allowed_users: Vec<String>,
+        bindings: &[Binding],
+        adapter_selector: Option<&str>,
+    ) -> Self {
+        let teams_bindings: Vec<&Binding> = bindings
+            .iter()
+            .filter(|b| {
+                b.channel == "teams" && binding_adapter_selector_matches(b, adapter_selector)
+            })
+            .collect();
+
+        let team_filter = {
+            let team_ids: Vec<String> = teams_bindings
+                .iter()
+                .filter_map(|b| b.team_id.clone())
+                .collect();
+            if team_ids.is_empty() {
+                None
+            } else {
+                Some(team_ids)
+            }
+        };
+
+        let channel_filter = {
+            let channel_ids: Vec<String> = teams_bindings
+                .iter()
+                .flat_map(|b| b.channel_ids.clone())
+                .collect();
+            if channel_ids.is_empty() {
+                None
+            } else {
+                Some(channel_ids)
+            }
+        };
+
+        let mut dm_allowed_users = seed_dm_allowed_users;
+        for binding in &teams_bindings {
+            for id in &binding.dm_allowed_users {
+                if !dm_allowed_users.contains(id) {
+                    dm_allowed_users.push(id.clone());
+                }
+            }
+        }
+
+        Self {
+            team_filter,
+            channel_filter,
+            dm_allowed_users,
+        }
+    }
+
+    /// Whether the adapter should act on a team channel or group chat.
+    ///
+    /// With a team filter, group chats (which belong to no team) are only
+    /// allowed when their ID is listed in `channel_ids`.
+    pub fn allows_conversation(&self, team_id: Option<&str>, channel_id: &str) -> bool {
+        let channel_listed = self
+            .channel_filter
+            .as_ref()
+            .map(|channels| channels.iter().any(|allowed| allowed == channel_id));
+        if channel_listed == Some(false) {
+            return false;
+        }
+        match (&self.team_filter, team_id) {
+            (Some(teams), Some(team_id)) => teams.iter().any(|allowed| allowed == team_id),
+            (Some(_), None) => channel_listed == Some(true),
+            (None, _) => true,
+        }
+    }
+
+    /// Whether a personal-chat message from `aad_object_id` may reach the
+    /// agent. Empty list = nobody (fail-closed).
+    pub fn allows_dm(&self, aad_object_id: Option<&str>) -> bool {
+        aad_object_id.is_some_and(|id| {
+            self.dm_allowed_users
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(id))
+        })
+    }
+}
+
 fn binding_adapter_selector_matches(binding: &Binding, adapter_selector: Option<&str>) -> bool {
     match (binding.adapter.as_deref(), adapter_selector) {
         (None, None) => true,
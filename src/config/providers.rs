@@ -1,6 +1,10 @@
-use super::toml_schema::TomlRoutingConfig;
+use super::toml_schema::{TomlPromptRoutingConfig, TomlRoutingConfig};
 use super::{ApiType, ProviderConfig};
-use crate::llm::routing::RoutingConfig;
+use crate::ProcessType;
+use crate::llm::routing::{
+    DimensionWeights, PromptRoutingConfig, PromptTiers, RoutingConfig, RoutingProfile,
+    TierBoundaries,
+};
 
 use std::collections::HashMap;
 
@@ -25,7 +29,7 @@ pub(super) const MISTRAL_PROVIDER_BASE_URL: &str = "https://api.mistral.ai";
 pub(super) const NVIDIA_PROVIDER_BASE_URL: &str = "https://integrate.api.nvidia.com";
 pub(super) const FIREWORKS_PROVIDER_BASE_URL: &str = "https://api.fireworks.ai/inference";
 pub(crate) const GEMINI_PROVIDER_BASE_URL: &str =
-    "https://generativelanguage.googleapis.com/v1beta/openai";
+    "https://generativelanguage.googleapis.com/v1beta";
 pub(super) const GITHUB_COPILOT_DEFAULT_BASE_URL: &str = "https://api.individual.githubcopilot.com";
 
 /// App attribution headers sent with every OpenRouter API request.
@@ -63,6 +67,8 @@ pub(crate) fn default_provider_config(
             extra_headers: vec![],
             api_version: None,
             deployment: None,
+            bedrock: None,
+            cassette: None,
         },
         "openai" => ProviderConfig {
             api_type: ApiType::OpenAiCompletions,
@@ -73,6 +79,8 @@ pub(crate) fn default_provider_config(
             extra_headers: vec![],
             api_version: None,
             deployment: None,
+            bedrock: None,
+            cassette: None,
         },
         "openrouter" => ProviderConfig {
             api_type: ApiType::OpenAiCompletions,
@@ -83,6 +91,8 @@ pub(crate) fn default_provider_config(
             extra_headers: openrouter_extra_headers(),
             api_version: None,
             deployment: None,
+            bedrock: None,
+            cassette: None,
         },
         "kilo" => ProviderConfig {
             api_type: ApiType::KiloGateway,
@@ -93,6 +103,8 @@ pub(crate) fn default_provider_config(
             extra_headers: vec![],
             api_version: None,
             deployment: None,
+            bedrock: None,
+            cassette: None,
         },
         "zhipu" => ProviderConfig {
             api_type: ApiType::OpenAiChatCompletions,
@@ -103,6 +115,8 @@ pub(crate) fn default_provider_config(
             extra_headers: vec![],
             api_version: None,
             deployment: None,
+            bedrock: None,
+            cassette: None,
         },
         "groq" => ProviderConfig {
             api_type: ApiType::OpenAiCompletions,
@@ -113,6 +127,8 @@ pub(crate) fn default_provider_config(
             extra_headers: vec![],
             api_version: None,
             deployment: None,
+            bedrock: None,
+            cassette: None,
         },
         "together" => ProviderConfig {
             api_type: ApiType::OpenAiCompletions,
@@ -123,6 +139,8 @@ pub(crate) fn default_provider_config(
             extra_headers: vec![],
             api_version: None,
             deployment: None,
+            bedrock: None,
+            cassette: None,
         },
         "fireworks" => ProviderConfig {
             api_type: ApiType::OpenAiCompletions,
@@ -133,6 +151,8 @@ pub(crate) fn default_provider_config(
             extra_headers: vec![],
             api_version: None,
             deployment: None,
+            bedrock: None,
+            cassette: None,
         },
         "deepseek" => ProviderConfig {
             api_type: ApiType::OpenAiCompletions,
@@ -143,6 +163,8 @@ pub(crate) fn default_provider_config(
             extra_headers: vec![],
             api_version: None,
             deployment: None,
+            bedrock: None,
+            cassette: None,
         },
         "xai" => ProviderConfig {
             api_type: ApiType::OpenAiCompletions,
@@ -153,6 +175,8 @@ pub(crate) fn default_provider_config(
             extra_headers: vec![],
             api_version: None,
             deployment: None,
+            bedrock: None,
+            cassette: None,
         },
         "mistral" => ProviderConfig {
             api_type: ApiType::OpenAiCompletions,
@@ -163,6 +187,8 @@ pub(crate) fn default_provider_config(
             extra_headers: vec![],
             api_version: None,
             deployment: None,
+            bedrock: None,
+            cassette: None,
         },
         "gemini" => ProviderConfig {
             api_type: ApiType::Gemini,
@@ -173,6 +199,8 @@ pub(crate) fn default_provider_config(
             extra_headers: vec![],
             api_version: None,
             deployment: None,
+            bedrock: None,
+            cassette: None,
         },
         "ollama" => ProviderConfig {
             api_type: ApiType::OpenAiCompletions,
@@ -183,6 +211,8 @@ pub(crate) fn default_provider_config(
             extra_headers: vec![],
             api_version: None,
             deployment: None,
+            bedrock: None,
+            cassette: None,
         },
         "opencode-zen" => ProviderConfig {
             api_type: ApiType::OpenAiCompletions,
@@ -193,6 +223,8 @@ pub(crate) fn default_provider_config(
             extra_headers: vec![],
             api_version: None,
             deployment: None,
+            bedrock: None,
+            cassette: None,
         },
         "opencode-go" => ProviderConfig {
             api_type: ApiType::OpenAiCompletions,
@@ -203,6 +235,8 @@ pub(crate) fn default_provider_config(
             extra_headers: vec![],
             api_version: None,
             deployment: None,
+            bedrock: None,
+            cassette: None,
         },
         "nvidia" => ProviderConfig {
             api_type: ApiType::OpenAiCompletions,
@@ -213,6 +247,8 @@ pub(crate) fn default_provider_config(
             extra_headers: vec![],
             api_version: None,
             deployment: None,
+            bedrock: None,
+            cassette: None,
         },
         "minimax" => ProviderConfig {
             api_type: ApiType::Anthropic,
@@ -223,6 +259,8 @@ pub(crate) fn default_provider_config(
             extra_headers: vec![],
             api_version: None,
             deployment: None,
+            bedrock: None,
+            cassette: None,
         },
         "minimax-cn" => ProviderConfig {
             api_type: ApiType::Anthropic,
@@ -233,6 +271,8 @@ pub(crate) fn default_provider_config(
             extra_headers: vec![],
             api_version: None,
             deployment: None,
+            bedrock: None,
+            cassette: None,
         },
         "moonshot" => ProviderConfig {
             api_type: ApiType::OpenAiCompletions,
@@ -243,6 +283,8 @@ pub(crate) fn default_provider_config(
             extra_headers: vec![],
             api_version: None,
             deployment: None,
+            bedrock: None,
+            cassette: None,
         },
         "zai-coding-plan" => ProviderConfig {
             api_type: ApiType::OpenAiChatCompletions,
@@ -253,6 +295,8 @@ pub(crate) fn default_provider_config(
             extra_headers: vec![],
             api_version: None,
             deployment: None,
+            bedrock: None,
+            cassette: None,
         },
         // GitHub Copilot requires token exchange and dynamic base URL derivation.
         // The test path should use LlmManager::get_github_copilot_provider() instead.
@@ -282,6 +326,8 @@ pub(super) fn add_shorthand_provider(
                 extra_headers: vec![],
                 api_version: None,
                 deployment: None,
+                bedrock: None,
+                cassette: None,
             });
     }
 }
@@ -378,5 +424,98 @@ pub(super) fn resolve_routing(
         cortex_thinking_effort: t
             .cortex_thinking_effort
             .unwrap_or_else(|| base.cortex_thinking_effort.clone()),
+        prompt_routing: resolve_prompt_routing(t.prompt_routing, &base.prompt_routing),
+    }
+}
+
+/// Resolve a TomlPromptRoutingConfig against a base PromptRoutingConfig.
+fn resolve_prompt_routing(
+    toml: Option<TomlPromptRoutingConfig>,
+    base: &PromptRoutingConfig,
+) -> PromptRoutingConfig {
+    let Some(t) = toml else { return base.clone() };
+
+    let process_types = match t.process_types {
+        Some(names) => names
+            .iter()
+            .filter_map(|name| {
+                let process_type = parse_process_type(name);
+                if process_type.is_none() {
+                    tracing::warn!(
+                        process_type = %name,
+                        "unknown prompt_routing process type, ignoring"
+                    );
+                }
+                process_type
+            })
+            .collect(),
+        None => base.process_types.clone(),
+    };
+
+    let tiers = match t.tiers {
+        Some(tiers) => PromptTiers {
+            light: tiers.light.or_else(|| base.tiers.light.clone()),
+            standard: tiers.standard.or_else(|| base.tiers.standard.clone()),
+            heavy: tiers.heavy.or_else(|| base.tiers.heavy.clone()),
+        },
+        None => base.tiers.clone(),
+    };
+
+    let boundaries = match t.boundaries {
+        Some(boundaries) => TierBoundaries {
+            light_standard: boundaries
+                .light_standard
+                .unwrap_or(base.boundaries.light_standard),
+            standard_heavy: boundaries
+                .standard_heavy
+                .unwrap_or(base.boundaries.standard_heavy),
+        },
+        None => base.boundaries,
+    };
+
+    let weights = match t.weights {
+        Some(weights) => DimensionWeights {
+            token_count: weights.token_count.unwrap_or(base.weights.token_count),
+            code_presence: weights.code_presence.unwrap_or(base.weights.code_presence),
+            reasoning: weights.reasoning.unwrap_or(base.weights.reasoning),
+            simple: weights.simple.unwrap_or(base.weights.simple),
+            technical: weights.technical.unwrap_or(base.weights.technical),
+            multi_step: weights.multi_step.unwrap_or(base.weights.multi_step),
+            constraints: weights.constraints.unwrap_or(base.weights.constraints),
+        },
+        None => base.weights,
+    };
+
+    PromptRoutingConfig {
+        enabled: t.enabled.unwrap_or(base.enabled),
+        process_types,
+        tiers,
+        boundaries,
+        weights,
+        profile: parse_routing_profile(t.profile.as_deref()).unwrap_or(base.profile),
+    }
+}
+
+/// Parse a routing profile name, warning on unknown values.
+pub(super) fn parse_routing_profile(value: Option<&str>) -> Option<RoutingProfile> {
+    let value = value?;
+    let profile = RoutingProfile::parse(value);
+    if profile.is_none() {
+        tracing::warn!(
+            value,
+            "unknown routing profile, expected one of: eco, balanced, premium"
+        );
+    }
+    profile
+}
+
+fn parse_process_type(value: &str) -> Option<ProcessType> {
+    match value {
+        "channel" => Some(ProcessType::Channel),
+        "branch" => Some(ProcessType::Branch),
+        "worker" => Some(ProcessType::Worker),
+        "compactor" => Some(ProcessType::Compactor),
+        "cortex" => Some(ProcessType::Cortex),
+        _ => None,
     }
 }
@@ -4,10 +4,11 @@ use std::sync::Arc;
 use arc_swap::ArcSwap;
 
 use super::{
-    BrowserConfig, ChannelConfig, CoalesceConfig, CompactionConfig, Config, CortexConfig,
-    DefaultsConfig, IngestionConfig, McpServerConfig, MemoryPersistenceConfig, OpenCodeConfig,
-    ResolvedAgentConfig, ToolUseEnforcement, WarmupConfig, WarmupStatus, WorkReadiness,
-    evaluate_work_readiness,
+    AccessConfig, BrowserConfig, BudgetConfig, ChannelConfig, CoalesceConfig, CommandDef,
+    CompactionConfig, Config, CortexConfig, DefaultsConfig, IngestionConfig, McpServerConfig,
+    MemoryPersistenceConfig, OpenCodeConfig, ParticipantConfig, ResolvedAgentConfig,
+    ResponseCacheConfig, ToolApprovalConfig, ToolUseEnforcement, WarmupConfig, WarmupStatus,
+    WorkReadiness, evaluate_work_readiness,
 };
 use crate::llm::routing::RoutingConfig;
 use crate::tools::browser::SharedBrowserHandle;
@@ -31,6 +32,8 @@ pub struct RuntimeConfig {
     pub memory_persistence: ArcSwap<MemoryPersistenceConfig>,
     pub coalesce: ArcSwap<CoalesceConfig>,
     pub ingestion: ArcSwap<IngestionConfig>,
+    /// Participant summary loop and `## Participant Info` settings.
+    pub participants: ArcSwap<ParticipantConfig>,
     pub channel_config: ArcSwap<ChannelConfig>,
     pub max_turns: ArcSwap<usize>,
     pub branch_max_turns: ArcSwap<usize>,
@@ -46,6 +49,15 @@ pub struct RuntimeConfig {
     pub user_timezone: ArcSwap<Option<String>>,
     pub cortex: ArcSwap<CortexConfig>,
     pub warmup: ArcSwap<WarmupConfig>,
+    /// LLM spend limits enforced by the agent's `SpendTracker`.
+    pub budget: ArcSwap<BudgetConfig>,
+    /// Completion caching for background processes, applied by the agent's `ResponseCache`.
+    pub response_cache: ArcSwap<ResponseCacheConfig>,
+    /// Which worker tool calls wait for a human decision before running.
+    pub tool_approval: ArcSwap<ToolApprovalConfig>,
+    pub access: ArcSwap<AccessConfig>,
+    /// Config-declared chat commands. Skill commands are read from `skills`.
+    pub commands: ArcSwap<Vec<CommandDef>>,
     /// Current warmup lifecycle status for API and observability.
     pub warmup_status: ArcSwap<WarmupStatus>,
     /// Synchronizes warmup passes so periodic and API-triggered runs don't overlap.
@@ -123,6 +135,7 @@ impl RuntimeConfig {
             memory_persistence: ArcSwap::from_pointee(agent_config.memory_persistence),
             coalesce: ArcSwap::from_pointee(agent_config.coalesce),
             ingestion: ArcSwap::from_pointee(agent_config.ingestion),
+            participants: ArcSwap::from_pointee(agent_config.participants),
             channel_config: ArcSwap::from_pointee(agent_config.channel),
             max_turns: ArcSwap::from_pointee(agent_config.max_turns),
             branch_max_turns: ArcSwap::from_pointee(agent_config.branch_max_turns),
@@ -138,6 +151,11 @@ impl RuntimeConfig {
             user_timezone: ArcSwap::from_pointee(agent_config.user_timezone.clone()),
             cortex: ArcSwap::from_pointee(agent_config.cortex),
             warmup: ArcSwap::from_pointee(agent_config.warmup),
+            budget: ArcSwap::from_pointee(agent_config.budget.clone()),
+            response_cache: ArcSwap::from_pointee(agent_config.response_cache.clone()),
+            tool_approval: ArcSwap::from_pointee(agent_config.tool_approval.clone()),
+            access: ArcSwap::from_pointee(agent_config.access.clone()),
+            commands: ArcSwap::from_pointee(agent_config.commands.clone()),
             warmup_status: ArcSwap::from_pointee(WarmupStatus::default()),
             warmup_lock: Arc::new(tokio::sync::Mutex::new(())),
             memory_bulletin: ArcSwap::from_pointee(String::new()),
@@ -245,6 +263,7 @@ impl RuntimeConfig {
             .store(Arc::new(resolved.memory_persistence));
         self.coalesce.store(Arc::new(resolved.coalesce));
         self.ingestion.store(Arc::new(resolved.ingestion));
+        self.participants.store(Arc::new(resolved.participants));
         let resolved_channel = resolved.channel;
         self.channel_config.store(Arc::new(resolved_channel));
         self.max_turns.store(Arc::new(resolved.max_turns));
@@ -277,6 +296,11 @@ impl RuntimeConfig {
         self.user_timezone.store(Arc::new(resolved.user_timezone));
         self.cortex.store(Arc::new(resolved.cortex));
         self.warmup.store(Arc::new(resolved.warmup));
+        self.budget.store(Arc::new(resolved.budget));
+        self.response_cache.store(Arc::new(resolved.response_cache));
+        self.tool_approval.store(Arc::new(resolved.tool_approval));
+        self.access.store(Arc::new(resolved.access));
+        self.commands.store(Arc::new(resolved.commands));
         // Preserve project_paths from the current sandbox config when
         // reloading — the resolved config only has user-configured paths.
         let existing_project_paths = self.sandbox.load().project_paths.clone();
@@ -1,6 +1,6 @@
 // -- TOML deserialization types --
 
-use super::types::ToolUseEnforcement;
+use super::types::{AccessRole, ToolUseEnforcement};
 
 use serde::{Deserialize, Deserializer};
 use std::collections::HashMap;
@@ -70,6 +70,7 @@ pub(super) struct TomlHumanDef {
     pub(super) telegram_id: Option<String>,
     pub(super) slack_id: Option<String>,
     pub(super) email: Option<String>,
+    pub(super) access_role: Option<AccessRole>,
 }
 
 #[derive(Deserialize, Default)]
@@ -90,6 +91,8 @@ pub(super) struct TomlApiConfig {
     pub(super) bind: String,
     #[serde(default)]
     pub(super) auth_token: Option<String>,
+    #[serde(default)]
+    pub(super) oidc: Option<TomlOidcConfig>,
 }
 
 impl Default for TomlApiConfig {
@@ -99,10 +102,28 @@ impl Default for TomlApiConfig {
             port: default_api_port(),
             bind: default_api_bind(),
             auth_token: None,
+            oidc: None,
         }
     }
 }
 
+#[derive(Deserialize)]
+pub(super) struct TomlOidcConfig {
+    pub(super) issuer: String,
+    pub(super) client_id: String,
+    #[serde(default)]
+    pub(super) client_secret: Option<String>,
+    pub(super) redirect_url: String,
+    #[serde(default = "default_oidc_scopes")]
+    pub(super) scopes: Vec<String>,
+    #[serde(default = "default_oidc_groups_claim")]
+    pub(super) groups_claim: String,
+    #[serde(default)]
+    pub(super) group_scopes: HashMap<String, Vec<String>>,
+    #[serde(default = "default_oidc_session_ttl_hours")]
+    pub(super) session_ttl_hours: u64,
+}
+
 pub(super) fn default_api_enabled() -> bool {
     true
 }
@@ -112,6 +133,15 @@ pub(super) fn default_api_port() -> u16 {
 pub(super) fn default_api_bind() -> String {
     "127.0.0.1".into()
 }
+pub(super) fn default_oidc_scopes() -> Vec<String> {
+    vec!["profile".into(), "email".into()]
+}
+pub(super) fn default_oidc_groups_claim() -> String {
+    "groups".into()
+}
+pub(super) fn default_oidc_session_ttl_hours() -> u64 {
+    12
+}
 
 pub(super) fn hosted_api_bind(bind: String) -> String {
     match std::env::var("SPACEBOT_DEPLOYMENT") {
@@ -158,6 +188,19 @@ pub(super) struct TomlProviderConfig {
     pub(super) api_version: Option<String>,
     #[serde(default)]
     pub(super) deployment: Option<String>,
+    /// AWS region for Bedrock providers. Derived from a
+    /// `bedrock-runtime.<region>.amazonaws.com` base URL when omitted.
+    #[serde(default)]
+    pub(super) region: Option<String>,
+    /// AWS access key ID for Bedrock providers (`api_key` is the secret key).
+    #[serde(default)]
+    pub(super) access_key_id: Option<String>,
+    /// AWS session token for Bedrock providers using temporary credentials.
+    #[serde(default)]
+    pub(super) session_token: Option<String>,
+    /// Record/replay cassettes for this provider's completions.
+    #[serde(default)]
+    pub(super) cassette: Option<super::CassetteConfig>,
 }
 
 #[derive(Deserialize, Default)]
@@ -290,9 +333,16 @@ pub(super) struct TomlDefaultsConfig {
     pub(super) coalesce: Option<TomlCoalesceConfig>,
     pub(super) ingestion: Option<TomlIngestionConfig>,
     pub(super) cortex: Option<TomlCortexConfig>,
+    pub(super) participants: Option<TomlParticipantConfig>,
     pub(super) warmup: Option<TomlWarmupConfig>,
     pub(super) browser: Option<TomlBrowserConfig>,
     pub(super) channel: Option<TomlChannelConfig>,
+    pub(super) budget: Option<TomlBudgetConfig>,
+    pub(super) response_cache: Option<TomlResponseCacheConfig>,
+    pub(super) tool_approval: Option<TomlToolApprovalConfig>,
+    pub(super) access: Option<TomlAccessConfig>,
+    #[serde(default)]
+    pub(super) commands: Vec<TomlCommandDef>,
     #[serde(default)]
     pub(super) mcp: Vec<TomlMcpServerConfig>,
     pub(super) brave_search_key: Option<String>,
@@ -320,6 +370,41 @@ pub(super) struct TomlRoutingConfig {
     #[serde(default)]
     pub(super) task_overrides: HashMap<String, String>,
     pub(super) fallbacks: Option<HashMap<String, Vec<String>>>,
+    pub(super) prompt_routing: Option<TomlPromptRoutingConfig>,
+}
+
+#[derive(Deserialize, Default)]
+pub(super) struct TomlPromptRoutingConfig {
+    pub(super) enabled: Option<bool>,
+    pub(super) process_types: Option<Vec<String>>,
+    pub(super) profile: Option<String>,
+    pub(super) tiers: Option<TomlPromptTiers>,
+    pub(super) boundaries: Option<TomlTierBoundaries>,
+    pub(super) weights: Option<TomlDimensionWeights>,
+}
+
+#[derive(Deserialize, Default)]
+pub(super) struct TomlPromptTiers {
+    pub(super) light: Option<String>,
+    pub(super) standard: Option<String>,
+    pub(super) heavy: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+pub(super) struct TomlTierBoundaries {
+    pub(super) light_standard: Option<f64>,
+    pub(super) standard_heavy: Option<f64>,
+}
+
+#[derive(Deserialize, Default)]
+pub(super) struct TomlDimensionWeights {
+    pub(super) token_count: Option<f64>,
+    pub(super) code_presence: Option<f64>,
+    pub(super) reasoning: Option<f64>,
+    pub(super) simple: Option<f64>,
+    pub(super) technical: Option<f64>,
+    pub(super) multi_step: Option<f64>,
+    pub(super) constraints: Option<f64>,
 }
 
 #[derive(Deserialize)]
@@ -351,6 +436,16 @@ pub(super) struct TomlCompactionConfig {
     pub(super) emergency_threshold: Option<f32>,
 }
 
+#[derive(Deserialize)]
+pub(super) struct TomlParticipantConfig {
+    pub(super) enabled: Option<bool>,
+    pub(super) summary_interval_secs: Option<u64>,
+    pub(super) summary_max_words: Option<usize>,
+    pub(super) summary_stale_after_secs: Option<u64>,
+    pub(super) min_participants: Option<usize>,
+    pub(super) max_summaries_per_pass: Option<usize>,
+}
+
 #[derive(Deserialize)]
 pub(super) struct TomlCortexConfig {
     pub(super) tick_interval_secs: Option<u64>,
@@ -367,12 +462,21 @@ pub(super) struct TomlCortexConfig {
     pub(super) maintenance_prune_threshold: Option<f32>,
     pub(super) maintenance_min_age_days: Option<i64>,
     pub(super) maintenance_merge_similarity_threshold: Option<f32>,
+    pub(super) recent_tier_ttl_days: Option<i64>,
+    pub(super) recent_tier_max: Option<usize>,
     pub(super) association_interval_secs: Option<u64>,
     pub(super) association_similarity_threshold: Option<f32>,
     pub(super) association_updates_threshold: Option<f32>,
     pub(super) association_max_per_pass: Option<usize>,
     pub(super) knowledge_synthesis_max_words: Option<usize>,
     pub(super) knowledge_synthesis_debounce_secs: Option<u64>,
+    pub(super) autonomy_enabled: Option<bool>,
+    pub(super) autonomy_interval_secs: Option<u64>,
+    pub(super) autonomy_max_turns: Option<usize>,
+    pub(super) autonomy_max_workers: Option<usize>,
+    pub(super) autonomy_tasks_require_approval: Option<bool>,
+    pub(super) autonomy_active_start_hour: Option<u8>,
+    pub(super) autonomy_active_end_hour: Option<u8>,
 }
 
 #[derive(Deserialize)]
@@ -401,6 +505,103 @@ pub(super) struct TomlChannelConfig {
     pub(super) save_attachments: Option<bool>,
 }
 
+#[derive(Deserialize)]
+pub(super) struct TomlBudgetConfig {
+    pub(super) daily_limit_usd: Option<f64>,
+    pub(super) monthly_limit_usd: Option<f64>,
+    pub(super) soft_limit_ratio: Option<f64>,
+    pub(super) warning_target: Option<String>,
+    pub(super) downgrade_on_soft_limit: Option<bool>,
+    pub(super) downgrade_model: Option<String>,
+    #[serde(default)]
+    pub(super) channels: HashMap<String, TomlBudgetLimits>,
+    #[serde(default)]
+    pub(super) cron: HashMap<String, TomlBudgetLimits>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlBudgetLimits {
+    pub(super) daily_limit_usd: Option<f64>,
+    pub(super) monthly_limit_usd: Option<f64>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlResponseCacheConfig {
+    pub(super) enabled: Option<bool>,
+    pub(super) process_types: Option<Vec<crate::ProcessType>>,
+    pub(super) ttl_secs: Option<u64>,
+    pub(super) max_entries: Option<usize>,
+    pub(super) similarity_threshold: Option<f32>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlToolApprovalConfig {
+    pub(super) rules: Option<Vec<TomlToolApprovalRule>>,
+    pub(super) timeout_secs: Option<u64>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlToolApprovalRule {
+    pub(super) tool: String,
+    pub(super) args_pattern: Option<String>,
+    pub(super) sandbox: Option<crate::sandbox::SandboxMode>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlAccessConfig {
+    pub(super) enabled: Option<bool>,
+    pub(super) default_role: Option<AccessRole>,
+    pub(super) commands: Option<AccessRole>,
+    pub(super) spawn_worker: Option<AccessRole>,
+    pub(super) secret_set: Option<AccessRole>,
+    pub(super) cron: Option<AccessRole>,
+    pub(super) task_approval: Option<AccessRole>,
+    pub(super) tool_approval: Option<AccessRole>,
+}
+
+#[derive(Deserialize, Clone)]
+pub(super) struct TomlCommandDef {
+    pub(super) name: String,
+    #[serde(default)]
+    pub(super) description: String,
+    #[serde(default)]
+    pub(super) args: Vec<TomlCommandArgDef>,
+    pub(super) role: Option<AccessRole>,
+    pub(super) prompt: Option<String>,
+    pub(super) skill: Option<String>,
+    pub(super) cron: Option<String>,
+    pub(super) task: Option<TomlCommandTask>,
+}
+
+#[derive(Deserialize, Clone)]
+pub(super) struct TomlCommandArgDef {
+    pub(super) name: String,
+    #[serde(default)]
+    pub(super) description: String,
+    #[serde(default = "default_command_arg_required")]
+    pub(super) required: bool,
+}
+
+pub(super) fn default_command_arg_required() -> bool {
+    true
+}
+
+#[derive(Deserialize, Clone)]
+pub(super) struct TomlCommandTask {
+    pub(super) title: String,
+    pub(super) description: Option<String>,
+    pub(super) priority: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+pub(super) struct TomlBindingAccess {
+    pub(super) default_role: Option<AccessRole>,
+    #[serde(default)]
+    pub(super) users: HashMap<String, AccessRole>,
+    #[serde(default)]
+    pub(super) groups: HashMap<String, AccessRole>,
+}
+
 #[derive(Deserialize)]
 pub(super) struct TomlOpenCodeConfig {
     pub(super) enabled: Option<bool>,
@@ -459,6 +660,7 @@ pub(super) struct TomlAgentConfig {
     pub(super) gradient_end: Option<String>,
     pub(super) workspace: Option<String>,
     pub(super) routing: Option<TomlRoutingConfig>,
+    pub(super) routing_profile: Option<String>,
     pub(super) max_concurrent_branches: Option<usize>,
     pub(super) max_concurrent_workers: Option<usize>,
     pub(super) max_turns: Option<usize>,
@@ -470,9 +672,15 @@ pub(super) struct TomlAgentConfig {
     pub(super) coalesce: Option<TomlCoalesceConfig>,
     pub(super) ingestion: Option<TomlIngestionConfig>,
     pub(super) cortex: Option<TomlCortexConfig>,
+    pub(super) participants: Option<TomlParticipantConfig>,
     pub(super) warmup: Option<TomlWarmupConfig>,
     pub(super) browser: Option<TomlBrowserConfig>,
     pub(super) channel: Option<TomlChannelConfig>,
+    pub(super) budget: Option<TomlBudgetConfig>,
+    pub(super) response_cache: Option<TomlResponseCacheConfig>,
+    pub(super) tool_approval: Option<TomlToolApprovalConfig>,
+    pub(super) access: Option<TomlAccessConfig>,
+    pub(super) commands: Option<Vec<TomlCommandDef>>,
     pub(super) mcp: Option<Vec<TomlMcpServerConfig>>,
     pub(super) brave_search_key: Option<String>,
     pub(super) cron_timezone: Option<String>,
@@ -496,6 +704,8 @@ pub(super) struct TomlCronDef {
     pub(super) enabled: bool,
     #[serde(default)]
     pub(super) run_once: bool,
+    #[serde(default)]
+    pub(super) stateful: bool,
     pub(super) timeout_secs: Option<u64>,
 }
 
@@ -514,6 +724,12 @@ pub(super) struct TomlMessagingConfig {
     pub(super) signal: Option<TomlSignalConfig>,
     #[serde(default)]
     pub(super) mattermost: Option<TomlMattermostConfig>,
+    #[serde(default)]
+    pub(super) matrix: Option<TomlMatrixConfig>,
+    #[serde(default)]
+    pub(super) irc: Option<TomlIrcConfig>,
+    #[serde(default)]
+    pub(super) teams: Option<TomlTeamsConfig>,
 }
 
 #[derive(Deserialize)]
@@ -618,6 +834,8 @@ pub(super) struct TomlEmailConfig {
     pub(super) from_name: Option<String>,
     #[serde(default = "default_email_poll_interval_secs")]
     pub(super) poll_interval_secs: u64,
+    #[serde(default = "default_email_idle")]
+    pub(super) idle: bool,
     #[serde(default = "default_email_folders")]
     pub(super) folders: Vec<String>,
     #[serde(default)]
@@ -653,6 +871,8 @@ pub(super) struct TomlEmailInstanceConfig {
     pub(super) from_name: Option<String>,
     #[serde(default = "default_email_poll_interval_secs")]
     pub(super) poll_interval_secs: u64,
+    #[serde(default = "default_email_idle")]
+    pub(super) idle: bool,
     #[serde(default = "default_email_folders")]
     pub(super) folders: Vec<String>,
     #[serde(default)]
@@ -672,6 +892,7 @@ pub(super) struct TomlWebhookConfig {
     #[serde(default = "default_webhook_bind")]
     pub(super) bind: String,
     pub(super) auth_token: Option<String>,
+    pub(super) signing_secret: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -771,6 +992,10 @@ pub(super) fn default_email_poll_interval_secs() -> u64 {
     30
 }
 
+pub(super) fn default_email_idle() -> bool {
+    true
+}
+
 pub(super) fn default_email_folders() -> Vec<String> {
     vec!["INBOX".to_string()]
 }
@@ -791,6 +1016,7 @@ pub(super) struct TomlConversationSettings {
     pub(super) delegation: Option<String>,
     pub(super) response_mode: Option<String>,
     pub(super) save_attachments: Option<bool>,
+    pub(super) streaming: Option<bool>,
 }
 
 #[derive(Deserialize)]
@@ -812,6 +1038,8 @@ pub(super) struct TomlBinding {
     pub(super) dm_allowed_users: Vec<String>,
     #[serde(default)]
     pub(super) settings: Option<TomlConversationSettings>,
+    #[serde(default)]
+    pub(super) access: TomlBindingAccess,
 }
 
 #[derive(Deserialize)]
@@ -846,3 +1074,132 @@ pub(super) struct TomlMattermostInstanceConfig {
 pub(super) fn default_mattermost_max_attachment_bytes() -> usize {
     10 * 1024 * 1024
 }
+
+#[derive(Deserialize)]
+pub(super) struct TomlMatrixConfig {
+    #[serde(default)]
+    pub(super) enabled: bool,
+    pub(super) homeserver_url: Option<String>,
+    pub(super) access_token: Option<String>,
+    #[serde(default)]
+    pub(super) instances: Vec<TomlMatrixInstanceConfig>,
+    #[serde(default)]
+    pub(super) dm_allowed_users: Vec<String>,
+    #[serde(default)]
+    pub(super) invite_allowed_users: Vec<String>,
+    #[serde(default = "default_matrix_max_attachment_bytes")]
+    pub(super) max_attachment_bytes: usize,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlMatrixInstanceConfig {
+    pub(super) name: String,
+    #[serde(default)]
+    pub(super) enabled: bool,
+    pub(super) homeserver_url: Option<String>,
+    pub(super) access_token: Option<String>,
+    #[serde(default)]
+    pub(super) dm_allowed_users: Vec<String>,
+    #[serde(default)]
+    pub(super) invite_allowed_users: Vec<String>,
+    #[serde(default = "default_matrix_max_attachment_bytes")]
+    pub(super) max_attachment_bytes: usize,
+}
+
+pub(super) fn default_matrix_max_attachment_bytes() -> usize {
+    50 * 1024 * 1024
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlIrcConfig {
+    #[serde(default)]
+    pub(super) enabled: bool,
+    pub(super) server: Option<String>,
+    #[serde(default = "default_irc_port")]
+    pub(super) port: u16,
+    #[serde(default = "default_irc_tls")]
+    pub(super) tls: bool,
+    pub(super) nickname: Option<String>,
+    pub(super) username: Option<String>,
+    pub(super) realname: Option<String>,
+    pub(super) server_password: Option<String>,
+    pub(super) sasl_username: Option<String>,
+    pub(super) sasl_password: Option<String>,
+    #[serde(default)]
+    pub(super) channels: Vec<String>,
+    #[serde(default)]
+    pub(super) instances: Vec<TomlIrcInstanceConfig>,
+    #[serde(default)]
+    pub(super) dm_allowed_users: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlIrcInstanceConfig {
+    pub(super) name: String,
+    #[serde(default)]
+    pub(super) enabled: bool,
+    pub(super) server: Option<String>,
+    #[serde(default = "default_irc_port")]
+    pub(super) port: u16,
+    #[serde(default = "default_irc_tls")]
+    pub(super) tls: bool,
+    pub(super) nickname: Option<String>,
+    pub(super) username: Option<String>,
+    pub(super) realname: Option<String>,
+    pub(super) server_password: Option<String>,
+    pub(super) sasl_username: Option<String>,
+    pub(super) sasl_password: Option<String>,
+    #[serde(default)]
+    pub(super) channels: Vec<String>,
+    #[serde(default)]
+    pub(super) dm_allowed_users: Vec<String>,
+}
+
+pub(super) fn default_irc_port() -> u16 {
+    6697
+}
+
+pub(super) fn default_irc_tls() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlTeamsConfig {
+    #[serde(default)]
+    pub(super) enabled: bool,
+    pub(super) app_id: Option<String>,
+    pub(super) app_password: Option<String>,
+    pub(super) tenant_id: Option<String>,
+    #[serde(default = "default_teams_bind")]
+    pub(super) bind: String,
+    #[serde(default = "default_teams_port")]
+    pub(super) port: u16,
+    #[serde(default)]
+    pub(super) instances: Vec<TomlTeamsInstanceConfig>,
+    #[serde(default)]
+    pub(super) dm_allowed_users: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlTeamsInstanceConfig {
+    pub(super) name: String,
+    #[serde(default)]
+    pub(super) enabled: bool,
+    pub(super) app_id: Option<String>,
+    pub(super) app_password: Option<String>,
+    pub(super) tenant_id: Option<String>,
+    #[serde(default = "default_teams_bind")]
+    pub(super) bind: String,
+    #[serde(default = "default_teams_port")]
+    pub(super) port: u16,
+    #[serde(default)]
+    pub(super) dm_allowed_users: Vec<String>,
+}
+
+pub(super) fn default_teams_port() -> u16 {
+    3978
+}
+
+pub(super) fn default_teams_bind() -> String {
+    "127.0.0.1".into()
+}
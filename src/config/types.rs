@@ -117,6 +117,9 @@ pub struct HumanDef {
     pub slack_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub email: Option<String>,
+    /// Access role granted to this person on every platform ID above.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_role: Option<AccessRole>,
 }
 
 /// A visual group definition for the topology UI.
@@ -138,6 +141,8 @@ pub struct ApiConfig {
     /// Address to bind the HTTP server on.
     pub bind: String,
     pub auth_token: Option<String>,
+    /// OpenID Connect single sign-on for the web interface.
+    pub oidc: Option<OidcConfig>,
 }
 
 impl Default for ApiConfig {
@@ -147,10 +152,35 @@ impl Default for ApiConfig {
             port: 19898,
             bind: "127.0.0.1".into(),
             auth_token: None,
+            oidc: None,
         }
     }
 }
 
+/// OpenID Connect login for the web interface (authorization code + PKCE).
+#[derive(Debug, Clone)]
+pub struct OidcConfig {
+    /// Issuer URL. Endpoints are discovered from
+    /// `{issuer}/.well-known/openid-configuration`.
+    pub issuer: String,
+    pub client_id: String,
+    /// Omit for public clients, which rely on PKCE alone.
+    pub client_secret: Option<String>,
+    /// Callback URL registered with the provider, ending in
+    /// `/api/auth/oidc/callback`.
+    pub redirect_url: String,
+    /// Scopes requested alongside `openid`.
+    pub scopes: Vec<String>,
+    /// ID token claim holding the user's groups. Dotted paths reach into
+    /// nested claims, e.g. `realm_access.roles`.
+    pub groups_claim: String,
+    /// API scopes granted to members of each group. Users get the union over
+    /// their groups; users in no mapped group cannot log in.
+    pub group_scopes: HashMap<String, Vec<crate::api::access::ApiScope>>,
+    /// How long a login session lasts.
+    pub session_ttl_hours: u64,
+}
+
 /// Prometheus metrics endpoint configuration.
 #[derive(Debug, Clone)]
 pub struct MetricsConfig {
@@ -185,10 +215,16 @@ pub enum ApiType {
     OpenAiResponses,
     /// Anthropic Messages API (https://api.anthropic.com/v1/messages)
     Anthropic,
-    /// Google Gemini API (https://generativelanguage.googleapis.com/v1beta/openai/chat/completions)
+    /// Google Gemini API (https://generativelanguage.googleapis.com/v1beta/models/{model}:generateContent)
     Gemini,
+    /// Google Vertex AI Gemini API (`{base_url}/models/{model}:generateContent`), authenticated
+    /// with a service-account key
+    Vertex,
     /// Azure OpenAI API (https://{resource}.openai.azure.com/openai/deployments/{deployment}/chat/completions?api-version={version})
     Azure,
+    /// AWS Bedrock Converse API (https://bedrock-runtime.{region}.amazonaws.com/model/{model}/converse),
+    /// signed with SigV4
+    Bedrock,
 }
 
 impl<'de> serde::Deserialize<'de> for ApiType {
@@ -203,10 +239,12 @@ impl<'de> serde::Deserialize<'de> for ApiType {
             "openai_responses" => Ok(Self::OpenAiResponses),
             "anthropic" => Ok(Self::Anthropic),
             "gemini" => Ok(Self::Gemini),
+            "vertex" => Ok(Self::Vertex),
             "azure" => Ok(Self::Azure),
+            "bedrock" => Ok(Self::Bedrock),
             other => Err(serde::de::Error::invalid_value(
                 serde::de::Unexpected::Str(other),
-                &"one of \"openai_completions\", \"openai_chat_completions\", \"kilo_gateway\", \"openai_responses\", \"anthropic\", \"gemini\", or \"azure\"",
+                &"one of \"openai_completions\", \"openai_chat_completions\", \"kilo_gateway\", \"openai_responses\", \"anthropic\", \"gemini\", \"vertex\", \"azure\", or \"bedrock\"",
             )),
         }
     }
@@ -306,6 +344,55 @@ pub struct ProviderConfig {
     /// Azure deployment name (e.g., "gpt-4o"). Required for Azure providers.
     #[serde(default)]
     pub deployment: Option<String>,
+    /// AWS region and access key for Bedrock providers. The secret access
+    /// key is carried in `api_key`.
+    #[serde(default)]
+    pub bedrock: Option<BedrockConfig>,
+    /// Record or replay this provider's completions from cassette files.
+    #[serde(default)]
+    pub cassette: Option<CassetteConfig>,
+}
+
+/// Whether LLM calls are captured to cassettes or served from them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CassetteMode {
+    /// Call the provider and write each request/response pair to disk.
+    Record,
+    /// Serve responses from disk without touching the network.
+    Replay,
+}
+
+/// Cassette settings for a provider, from `cassette = { mode, dir }`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CassetteConfig {
+    pub mode: CassetteMode,
+    /// Directory holding the cassette files, relative to the working
+    /// directory unless absolute.
+    pub dir: PathBuf,
+}
+
+/// AWS signing settings for a Bedrock provider.
+#[derive(Clone, serde::Deserialize)]
+pub struct BedrockConfig {
+    pub region: String,
+    pub access_key_id: String,
+    /// Session token for temporary (STS) credentials.
+    #[serde(default)]
+    pub session_token: Option<String>,
+}
+
+impl std::fmt::Debug for BedrockConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BedrockConfig")
+            .field("region", &self.region)
+            .field("access_key_id", &self.access_key_id)
+            .field(
+                "session_token",
+                &self.session_token.as_ref().map(|_| "[REDACTED]"),
+            )
+            .finish()
+    }
 }
 
 impl std::fmt::Debug for ProviderConfig {
@@ -324,6 +411,8 @@ impl std::fmt::Debug for ProviderConfig {
                     .map(|(key, _)| key.as_str())
                     .collect::<Vec<_>>(),
             )
+            .field("bedrock", &self.bedrock)
+            .field("cassette", &self.cassette)
             .finish()
     }
 }
@@ -616,9 +705,15 @@ pub struct DefaultsConfig {
     pub coalesce: CoalesceConfig,
     pub ingestion: IngestionConfig,
     pub cortex: CortexConfig,
+    pub participants: ParticipantConfig,
     pub warmup: WarmupConfig,
     pub browser: BrowserConfig,
     pub channel: ChannelConfig,
+    pub budget: BudgetConfig,
+    pub response_cache: ResponseCacheConfig,
+    pub tool_approval: ToolApprovalConfig,
+    pub access: AccessConfig,
+    pub commands: Vec<CommandDef>,
     pub mcp: Vec<McpServerConfig>,
     /// Brave Search API key for web search tool. Supports "env:VAR_NAME" references.
     pub brave_search_key: Option<String>,
@@ -652,9 +747,15 @@ impl std::fmt::Debug for DefaultsConfig {
             .field("coalesce", &self.coalesce)
             .field("ingestion", &self.ingestion)
             .field("cortex", &self.cortex)
+            .field("participants", &self.participants)
             .field("warmup", &self.warmup)
             .field("browser", &self.browser)
             .field("channel", &self.channel)
+            .field("budget", &self.budget)
+            .field("response_cache", &self.response_cache)
+            .field("tool_approval", &self.tool_approval)
+            .field("access", &self.access)
+            .field("commands", &self.commands)
             .field("mcp", &self.mcp)
             .field(
                 "brave_search_key",
@@ -845,6 +946,39 @@ impl Default for CoalesceConfig {
     }
 }
 
+/// Participant awareness: cached per-person summaries injected into channel
+/// prompts as `## Participant Info`.
+#[derive(Debug, Clone, Copy)]
+pub struct ParticipantConfig {
+    /// Whether the summary loop runs and channels inject participant info.
+    pub enabled: bool,
+    /// How often the cortex looks for stale summaries, in seconds.
+    pub summary_interval_secs: u64,
+    /// Target summary length in words.
+    pub summary_max_words: usize,
+    /// Regenerate a summary once the person has been seen this long after it
+    /// was written, in seconds.
+    pub summary_stale_after_secs: u64,
+    /// Minimum distinct active senders before a channel shows participant
+    /// info. 2 skips DMs; 1 includes them.
+    pub min_participants: usize,
+    /// Cap on summaries generated per pass.
+    pub max_summaries_per_pass: usize,
+}
+
+impl Default for ParticipantConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            summary_interval_secs: 300,
+            summary_max_words: 100,
+            summary_stale_after_secs: 3600,
+            min_participants: 2,
+            max_summaries_per_pass: 10,
+        }
+    }
+}
+
 /// File-based memory ingestion configuration.
 ///
 /// Watches a directory in the agent workspace for text files, chunks them, and
@@ -1008,6 +1142,10 @@ pub struct CortexConfig {
     pub maintenance_min_age_days: i64,
     /// Similarity threshold above which memories are merged as near-duplicates.
     pub maintenance_merge_similarity_threshold: f32,
+    /// Days without access before a recent-tier memory is demoted to long-term.
+    pub recent_tier_ttl_days: i64,
+    /// Maximum number of recent-tier memories kept hot at once.
+    pub recent_tier_max: usize,
     /// Interval in seconds between association passes.
     pub association_interval_secs: u64,
     /// Minimum cosine similarity to create a RelatedTo edge.
@@ -1020,6 +1158,19 @@ pub struct CortexConfig {
     pub knowledge_synthesis_max_words: usize,
     /// Debounce seconds after last memory change before regenerating knowledge synthesis.
     pub knowledge_synthesis_debounce_secs: u64,
+    /// Kill switch for the autonomy loop. Checked before every cycle.
+    pub autonomy_enabled: bool,
+    /// Interval in seconds between autonomy cycles (minimum 300).
+    pub autonomy_interval_secs: u64,
+    /// Max LLM turns per autonomy cycle.
+    pub autonomy_max_turns: usize,
+    /// Max workers the autonomy loop may spawn per cycle.
+    pub autonomy_max_workers: usize,
+    /// File tasks created by the autonomy loop as `pending_approval`.
+    pub autonomy_tasks_require_approval: bool,
+    /// Local-time window `(start_hour, end_hour)` in which autonomy cycles
+    /// may run. `None` runs around the clock.
+    pub autonomy_active_hours: Option<(u8, u8)>,
 }
 
 impl Default for CortexConfig {
@@ -1039,12 +1190,20 @@ impl Default for CortexConfig {
             maintenance_prune_threshold: 0.1,
             maintenance_min_age_days: 30,
             maintenance_merge_similarity_threshold: 0.95,
+            recent_tier_ttl_days: 3,
+            recent_tier_max: 64,
             association_interval_secs: 300,
             association_similarity_threshold: 0.85,
             association_updates_threshold: 0.95,
             association_max_per_pass: 100,
             knowledge_synthesis_max_words: 500,
             knowledge_synthesis_debounce_secs: 60,
+            autonomy_enabled: true,
+            autonomy_interval_secs: 1800,
+            autonomy_max_turns: 15,
+            autonomy_max_workers: 3,
+            autonomy_tasks_require_approval: true,
+            autonomy_active_hours: None,
         }
     }
 }
@@ -1068,6 +1227,13 @@ impl CortexConfig {
             ))
             .into());
         }
+        if self.recent_tier_ttl_days < 0 {
+            return Err(ConfigError::Invalid(format!(
+                "recent_tier_ttl_days must be >= 0, got {}",
+                self.recent_tier_ttl_days
+            ))
+            .into());
+        }
         if self.maintenance_interval_secs == 0 {
             return Err(
                 ConfigError::Invalid("maintenance_interval_secs must be >= 1".to_string()).into(),
@@ -1111,6 +1277,391 @@ impl Default for WarmupConfig {
     }
 }
 
+/// LLM spend limits in USD. `None` leaves that period unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BudgetLimits {
+    pub daily_usd: Option<f64>,
+    pub monthly_usd: Option<f64>,
+}
+
+impl BudgetLimits {
+    pub fn is_empty(&self) -> bool {
+        self.daily_usd.is_none() && self.monthly_usd.is_none()
+    }
+}
+
+/// LLM spend budget configuration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetConfig {
+    /// Limits across every process of the agent.
+    pub limits: BudgetLimits,
+    /// Fraction of a limit at which the soft-limit warning (and downgrade) kicks in.
+    pub soft_limit_ratio: f64,
+    /// Delivery target for soft-limit warnings in "adapter:target" format.
+    pub warning_target: Option<String>,
+    /// Switch to a cheaper model once a soft limit is crossed.
+    pub downgrade_on_soft_limit: bool,
+    /// Model used after a soft limit. Falls back to the light prompt-routing tier.
+    pub downgrade_model: Option<String>,
+    /// Per-channel limits, keyed by channel ID.
+    pub channels: HashMap<String, BudgetLimits>,
+    /// Per-cron-job limits, keyed by cron job ID.
+    pub cron: HashMap<String, BudgetLimits>,
+}
+
+impl Default for BudgetConfig {
+    fn default() -> Self {
+        Self {
+            limits: BudgetLimits::default(),
+            soft_limit_ratio: 0.8,
+            warning_target: None,
+            downgrade_on_soft_limit: true,
+            downgrade_model: None,
+            channels: HashMap::new(),
+            cron: HashMap::new(),
+        }
+    }
+}
+
+impl BudgetConfig {
+    /// True when any limit is configured.
+    pub fn is_enabled(&self) -> bool {
+        !self.limits.is_empty()
+            || self.channels.values().any(|limits| !limits.is_empty())
+            || self.cron.values().any(|limits| !limits.is_empty())
+    }
+
+    /// Channel- or cron-scoped limits that apply to `channel_id`.
+    ///
+    /// Cron jobs run in channels named `cron:{job_id}`.
+    pub fn scoped_limits(
+        &self,
+        channel_id: &str,
+    ) -> Option<(crate::llm::budget::BudgetScope, BudgetLimits)> {
+        use crate::llm::budget::BudgetScope;
+
+        if let Some(limits) = self.channels.get(channel_id) {
+            return Some((BudgetScope::Channel(channel_id.to_string()), *limits));
+        }
+        let cron_id = channel_id.strip_prefix("cron:")?;
+        self.cron
+            .get(cron_id)
+            .map(|limits| (BudgetScope::Cron(cron_id.to_string()), *limits))
+    }
+}
+
+/// Opt-in cache for completions made by background processes.
+///
+/// Channel and worker calls are never cached, and neither is any request
+/// that offers tools, whatever `process_types` says.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResponseCacheConfig {
+    pub enabled: bool,
+    /// Process types whose completions may be served from the cache.
+    pub process_types: Vec<crate::ProcessType>,
+    /// How long a cached response stays valid.
+    pub ttl_secs: u64,
+    /// Maximum cached responses per agent. The least recently used are evicted first.
+    pub max_entries: usize,
+    /// Minimum cosine similarity for a near-identical prompt to reuse a
+    /// cached response. `None` keeps the cache exact-match only.
+    pub similarity_threshold: Option<f32>,
+}
+
+impl Default for ResponseCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            process_types: vec![crate::ProcessType::Cortex, crate::ProcessType::Compactor],
+            ttl_secs: 24 * 60 * 60,
+            max_entries: 1000,
+            similarity_threshold: None,
+        }
+    }
+}
+
+impl ResponseCacheConfig {
+    /// Whether completions for `process_type` may use the cache.
+    pub fn caches(&self, process_type: &str) -> bool {
+        self.enabled
+            && self
+                .process_types
+                .iter()
+                .any(|cached| cached.to_string() == process_type)
+    }
+}
+
+/// Human-in-the-loop approval policy for built-in worker tool calls.
+///
+/// A call matching any rule is paused until someone approves or denies it
+/// from the originating channel or the dashboard. No rules means no gating.
+#[derive(Debug, Clone)]
+pub struct ToolApprovalConfig {
+    pub rules: Vec<ToolApprovalRule>,
+    /// How long a paused call waits for a decision before it is denied.
+    pub timeout_secs: u64,
+}
+
+impl Default for ToolApprovalConfig {
+    fn default() -> Self {
+        Self {
+            rules: Vec::new(),
+            timeout_secs: 300,
+        }
+    }
+}
+
+impl ToolApprovalConfig {
+    /// The first rule that gates this call, if any.
+    pub fn matching_rule(
+        &self,
+        tool_name: &str,
+        args: &str,
+        sandbox_mode: crate::sandbox::SandboxMode,
+    ) -> Option<&ToolApprovalRule> {
+        self.rules
+            .iter()
+            .find(|rule| rule.matches(tool_name, args, sandbox_mode))
+    }
+}
+
+/// One approval rule. Every condition that is set must hold for a match.
+#[derive(Debug, Clone)]
+pub struct ToolApprovalRule {
+    /// Tool name, or `*` for every tool.
+    pub tool: String,
+    /// Regex tested against the raw JSON arguments of the call.
+    pub args_pattern: Option<regex::Regex>,
+    /// Only gate calls while the agent sandbox is in this mode.
+    pub sandbox: Option<crate::sandbox::SandboxMode>,
+}
+
+impl ToolApprovalRule {
+    pub fn matches(
+        &self,
+        tool_name: &str,
+        args: &str,
+        sandbox_mode: crate::sandbox::SandboxMode,
+    ) -> bool {
+        (self.tool == "*" || self.tool == tool_name)
+            && self
+                .args_pattern
+                .as_ref()
+                .is_none_or(|pattern| pattern.is_match(args))
+            && self.sandbox.is_none_or(|mode| mode == sandbox_mode)
+    }
+}
+
+/// Who may do what in a conversation, ordered from least to most trusted.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+    utoipa::ToSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum AccessRole {
+    Guest,
+    Member,
+    Admin,
+    Owner,
+}
+
+impl std::fmt::Display for AccessRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Guest => write!(f, "guest"),
+            Self::Member => write!(f, "member"),
+            Self::Admin => write!(f, "admin"),
+            Self::Owner => write!(f, "owner"),
+        }
+    }
+}
+
+/// An action gated by the `[access]` policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessCapability {
+    /// Built-in chat commands that change channel behaviour (`/observe`, ...).
+    Commands,
+    SpawnWorker,
+    SecretSet,
+    Cron,
+    TaskApproval,
+    /// Approving or denying a worker tool call paused by `[tool_approval]`.
+    ToolApproval,
+}
+
+impl std::fmt::Display for AccessCapability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Commands => write!(f, "running this command"),
+            Self::SpawnWorker => write!(f, "spawning workers"),
+            Self::SecretSet => write!(f, "changing secrets"),
+            Self::Cron => write!(f, "creating or deleting cron jobs"),
+            Self::TaskApproval => write!(f, "approving tasks"),
+            Self::ToolApproval => write!(f, "approving tool calls"),
+        }
+    }
+}
+
+/// Role-based access policy for people talking to an agent.
+///
+/// Roles are assigned to platform identities through `[[humans]]` entries
+/// and binding `access` tables. Senders without an assignment get
+/// `default_role`. Disabled means every sender is treated as an owner.
+#[derive(Debug, Clone)]
+pub struct AccessConfig {
+    pub enabled: bool,
+    pub default_role: AccessRole,
+    pub commands: AccessRole,
+    pub spawn_worker: AccessRole,
+    pub secret_set: AccessRole,
+    pub cron: AccessRole,
+    pub task_approval: AccessRole,
+    pub tool_approval: AccessRole,
+}
+
+impl Default for AccessConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            default_role: AccessRole::Member,
+            commands: AccessRole::Admin,
+            spawn_worker: AccessRole::Member,
+            secret_set: AccessRole::Admin,
+            cron: AccessRole::Admin,
+            task_approval: AccessRole::Admin,
+            tool_approval: AccessRole::Admin,
+        }
+    }
+}
+
+impl AccessConfig {
+    /// Minimum role needed for `capability`.
+    pub fn required_role(&self, capability: AccessCapability) -> AccessRole {
+        match capability {
+            AccessCapability::Commands => self.commands,
+            AccessCapability::SpawnWorker => self.spawn_worker,
+            AccessCapability::SecretSet => self.secret_set,
+            AccessCapability::Cron => self.cron,
+            AccessCapability::TaskApproval => self.task_approval,
+            AccessCapability::ToolApproval => self.tool_approval,
+        }
+    }
+
+    /// Effective role of the sender of `message`.
+    ///
+    /// Internal sources (the dashboard portal and system retriggers) act as
+    /// owners. Everyone else gets the role stamped by the router, falling
+    /// back to `default_role`.
+    pub fn role_for(&self, message: &crate::InboundMessage) -> AccessRole {
+        if !self.enabled || matches!(message.source.as_str(), "portal" | "system") {
+            return AccessRole::Owner;
+        }
+        message
+            .metadata
+            .get(crate::metadata_keys::SENDER_ROLE)
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+            .unwrap_or(self.default_role)
+    }
+
+    pub fn check(
+        &self,
+        role: AccessRole,
+        capability: AccessCapability,
+    ) -> std::result::Result<(), AccessDenied> {
+        let required = self.required_role(capability);
+        if !self.enabled || role >= required {
+            return Ok(());
+        }
+        Err(AccessDenied {
+            capability,
+            required,
+            role,
+        })
+    }
+}
+
+/// A refused action, phrased for the person who asked.
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("{capability} requires the {required} role or higher; your role is {role}")]
+pub struct AccessDenied {
+    pub capability: AccessCapability,
+    pub required: AccessRole,
+    pub role: AccessRole,
+}
+
+/// The role a process acts with, captured when its tools are built.
+///
+/// Tools hold one of these instead of the live channel state so that work
+/// started on behalf of one sender keeps that sender's permissions.
+#[derive(Debug, Clone)]
+pub struct AccessGrant {
+    pub role: AccessRole,
+    policy: std::sync::Arc<AccessConfig>,
+}
+
+impl AccessGrant {
+    pub fn new(policy: std::sync::Arc<AccessConfig>, role: AccessRole) -> Self {
+        Self { role, policy }
+    }
+
+    pub fn check(&self, capability: AccessCapability) -> std::result::Result<(), AccessDenied> {
+        self.policy.check(self.role, capability)
+    }
+}
+
+/// A chat command declared under `[[defaults.commands]]`, `[[agents.commands]]`,
+/// or by a skill's `command` frontmatter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandDef {
+    /// Command name without the leading slash, e.g. `standup`.
+    pub name: String,
+    /// One-line description shown in `/help` and platform autocomplete.
+    pub description: String,
+    /// Positional arguments. The last argument captures the remaining text.
+    pub args: Vec<CommandArgDef>,
+    /// Minimum sender role allowed to run the command.
+    pub role: AccessRole,
+    pub action: CommandAction,
+}
+
+/// A positional argument of a chat command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandArgDef {
+    pub name: String,
+    pub description: String,
+    pub required: bool,
+}
+
+/// What a chat command does when invoked.
+///
+/// Templates substitute `{arg_name}` with the bound argument and `{args}`
+/// with the full argument text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandAction {
+    /// Run the rendered template as the user's turn.
+    Prompt(String),
+    /// Spawn a worker with the named skill. The rendered template, or the raw
+    /// argument text when no template is set, becomes the worker task.
+    Skill { skill: String, task: Option<String> },
+    /// Trigger a configured cron job immediately.
+    Cron(String),
+    /// Create a backlog task from the rendered templates.
+    Task {
+        title: String,
+        description: Option<String>,
+        priority: crate::tasks::TaskPriority,
+    },
+}
+
 /// Projects configuration — agent-level defaults for project workspace management.
 #[derive(Debug, Clone)]
 pub struct ProjectsConfig {
@@ -1278,9 +1829,20 @@ pub struct AgentConfig {
     pub coalesce: Option<CoalesceConfig>,
     pub ingestion: Option<IngestionConfig>,
     pub cortex: Option<CortexConfig>,
+    pub participants: Option<ParticipantConfig>,
     pub warmup: Option<WarmupConfig>,
     pub browser: Option<BrowserConfig>,
     pub channel: Option<ChannelConfig>,
+    /// Per-agent spend budget. None inherits from defaults.
+    pub budget: Option<BudgetConfig>,
+    /// Per-agent response cache settings. None inherits from defaults.
+    pub response_cache: Option<ResponseCacheConfig>,
+    /// Per-agent tool approval policy. None inherits from defaults.
+    pub tool_approval: Option<ToolApprovalConfig>,
+    /// Per-agent access policy. None inherits from defaults.
+    pub access: Option<AccessConfig>,
+    /// Per-agent chat commands, merged over the defaults by name.
+    pub commands: Option<Vec<CommandDef>>,
     pub mcp: Option<Vec<McpServerConfig>>,
     /// Per-agent Brave Search API key override. None inherits from defaults.
     pub brave_search_key: Option<String>,
@@ -1311,6 +1873,8 @@ pub struct CronDef {
     pub active_hours: Option<(u8, u8)>,
     pub enabled: bool,
     pub run_once: bool,
+    /// Carry a rolling state document and recent results across runs.
+    pub stateful: bool,
     /// Maximum wall-clock seconds to wait for the job to complete.
     /// `None` uses the default of 120 seconds.
     pub timeout_secs: Option<u64>,
@@ -1341,9 +1905,15 @@ pub struct ResolvedAgentConfig {
     pub coalesce: CoalesceConfig,
     pub ingestion: IngestionConfig,
     pub cortex: CortexConfig,
+    pub participants: ParticipantConfig,
     pub warmup: WarmupConfig,
     pub browser: BrowserConfig,
     pub channel: ChannelConfig,
+    pub budget: BudgetConfig,
+    pub response_cache: ResponseCacheConfig,
+    pub tool_approval: ToolApprovalConfig,
+    pub access: AccessConfig,
+    pub commands: Vec<CommandDef>,
     pub mcp: Vec<McpServerConfig>,
     pub brave_search_key: Option<String>,
     pub cron_timezone: Option<String>,
@@ -1373,9 +1943,15 @@ impl Default for DefaultsConfig {
             coalesce: CoalesceConfig::default(),
             ingestion: IngestionConfig::default(),
             cortex: CortexConfig::default(),
+            participants: ParticipantConfig::default(),
             warmup: WarmupConfig::default(),
             browser: BrowserConfig::default(),
             channel: ChannelConfig::default(),
+            budget: BudgetConfig::default(),
+            response_cache: ResponseCacheConfig::default(),
+            tool_approval: ToolApprovalConfig::default(),
+            access: AccessConfig::default(),
+            commands: Vec::new(),
             mcp: Vec::new(),
             brave_search_key: None,
             cron_timezone: None,
@@ -1439,12 +2015,30 @@ impl AgentConfig {
             coalesce: self.coalesce.unwrap_or(defaults.coalesce),
             ingestion: self.ingestion.unwrap_or(defaults.ingestion),
             cortex: self.cortex.unwrap_or(defaults.cortex),
+            participants: self.participants.unwrap_or(defaults.participants),
             warmup: self.warmup.unwrap_or(defaults.warmup),
             browser: self
                 .browser
                 .clone()
                 .unwrap_or_else(|| defaults.browser.clone()),
             channel: self.channel.unwrap_or(defaults.channel),
+            budget: self
+                .budget
+                .clone()
+                .unwrap_or_else(|| defaults.budget.clone()),
+            response_cache: self
+                .response_cache
+                .clone()
+                .unwrap_or_else(|| defaults.response_cache.clone()),
+            tool_approval: self
+                .tool_approval
+                .clone()
+                .unwrap_or_else(|| defaults.tool_approval.clone()),
+            access: self
+                .access
+                .clone()
+                .unwrap_or_else(|| defaults.access.clone()),
+            commands: resolve_command_defs(&defaults.commands, self.commands.as_deref()),
             mcp: resolve_mcp_configs(&defaults.mcp, self.mcp.as_deref()),
             brave_search_key: self
                 .brave_search_key
@@ -1589,6 +2183,28 @@ fn resolve_user_timezone(
     None
 }
 
+fn resolve_command_defs(
+    default_commands: &[CommandDef],
+    agent_commands: Option<&[CommandDef]>,
+) -> Vec<CommandDef> {
+    let mut merged = default_commands.to_vec();
+
+    if let Some(agent_commands) = agent_commands {
+        for agent_command in agent_commands {
+            if let Some(existing_index) = merged
+                .iter()
+                .position(|existing| existing.name == agent_command.name)
+            {
+                merged[existing_index] = agent_command.clone();
+            } else {
+                merged.push(agent_command.clone());
+            }
+        }
+    }
+
+    merged
+}
+
 fn resolve_mcp_configs(
     default_configs: &[McpServerConfig],
     agent_configs: Option<&[McpServerConfig]>,
@@ -1634,7 +2250,7 @@ pub struct Binding {
     pub guild_id: Option<String>,
     pub workspace_id: Option<String>, // Slack workspace (team) ID
     pub chat_id: Option<String>,      // Telegram group ID
-    pub team_id: Option<String>,      // Mattermost team ID
+    pub team_id: Option<String>,      // Mattermost or Teams team ID
     /// Channel IDs this binding applies to. If empty, all channels in the guild/workspace are allowed.
     pub channel_ids: Vec<String>,
     /// Require explicit @mention (or reply-to-bot) for inbound messages.
@@ -1648,6 +2264,44 @@ pub struct Binding {
     pub dm_allowed_users: Vec<String>,
     /// Default conversation settings for channels matched by this binding.
     pub settings: Option<crate::conversation::ConversationSettings>,
+    /// Access roles for senders in conversations matched by this binding.
+    pub access: BindingAccess,
+}
+
+/// Per-binding role assignments.
+///
+/// `groups` keys are platform group IDs: Discord role IDs or Slack user
+/// group IDs, as reported by the adapter in `sender_group_ids`.
+#[derive(Debug, Clone, Default)]
+pub struct BindingAccess {
+    /// Role for any sender in this binding without a more specific match.
+    pub default_role: Option<AccessRole>,
+    /// Platform user ID → role.
+    pub users: HashMap<String, AccessRole>,
+    /// Platform group ID → role.
+    pub groups: HashMap<String, AccessRole>,
+}
+
+impl BindingAccess {
+    /// Highest role this binding grants the sender of `message`, if any.
+    fn role_for(&self, message: &crate::InboundMessage) -> Option<AccessRole> {
+        let group_ids = message
+            .metadata
+            .get(crate::metadata_keys::SENDER_GROUP_IDS)
+            .and_then(|value| value.as_array());
+        let group_roles = group_ids
+            .into_iter()
+            .flatten()
+            .filter_map(|id| id.as_str())
+            .filter_map(|id| self.groups.get(id).copied());
+        self.users
+            .get(&message.sender_id)
+            .copied()
+            .into_iter()
+            .chain(group_roles)
+            .chain(self.default_role)
+            .max()
+    }
 }
 
 impl Binding {
@@ -1735,13 +2389,30 @@ impl Binding {
                 .metadata
                 .get("mattermost_channel_id")
                 .and_then(|v| v.as_str());
+            let matrix_room = message
+                .metadata
+                .get("matrix_room_id")
+                .and_then(|v| v.as_str());
+            let irc_channel = message.metadata.get("irc_channel").and_then(|v| v.as_str());
+            let teams_channel = message
+                .metadata
+                .get("teams_channel_id")
+                .and_then(|v| v.as_str());
 
             let direct_match = message_channel
                 .as_ref()
                 .is_some_and(|id| self.channel_ids.contains(id))
                 || slack_channel.is_some_and(|id| self.channel_ids.contains(&id.to_string()))
                 || twitch_channel.is_some_and(|id| self.channel_ids.contains(&id.to_string()))
-                || mattermost_channel.is_some_and(|id| self.channel_ids.contains(&id.to_string()));
+                || mattermost_channel.is_some_and(|id| self.channel_ids.contains(&id.to_string()))
+                || matrix_room.is_some_and(|id| self.channel_ids.contains(&id.to_string()))
+                || teams_channel.is_some_and(|id| self.channel_ids.contains(&id.to_string()))
+                // IRC channel names are case-insensitive.
+                || irc_channel.is_some_and(|name| {
+                    self.channel_ids
+                        .iter()
+                        .any(|id| id.eq_ignore_ascii_case(name))
+                });
             let parent_match = parent_channel
                 .as_ref()
                 .is_some_and(|id| self.channel_ids.contains(id));
@@ -1763,14 +2434,16 @@ impl Binding {
             }
         }
 
-        // Mattermost team filter
+        // Mattermost / Teams team filter
         if let Some(team_id) = &self.team_id
-            && self.channel == "mattermost"
+            && matches!(self.channel.as_str(), "mattermost" | "teams")
         {
-            let message_team = message
-                .metadata
-                .get("mattermost_team_id")
-                .and_then(|v| v.as_str());
+            let team_key = if self.channel == "teams" {
+                "teams_team_id"
+            } else {
+                "mattermost_team_id"
+            };
+            let message_team = message.metadata.get(team_key).and_then(|v| v.as_str());
             if message_team != Some(team_id.as_str()) {
                 return false;
             }
@@ -1804,6 +2477,11 @@ impl Binding {
                     .and_then(|v| v.as_str())
                     == Some("private")
             }
+            "teams" => message
+                .metadata
+                .get("teams_is_dm")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
             _ => false,
         };
         if is_dm {
@@ -1818,6 +2496,9 @@ impl Binding {
             "twitch" => "twitch_mentions_or_replies_to_bot",
             "telegram" => "telegram_mentions_or_replies_to_bot",
             "mattermost" => "mattermost_mentions_or_replies_to_bot",
+            "matrix" => "matrix_mentions_or_replies_to_bot",
+            "irc" => "irc_mentions_or_replies_to_bot",
+            "teams" => "teams_mentions_or_replies_to_bot",
             // Unknown platforms: if require_mention is set, default to
             // requiring a mention (safe default).
             _ => return false,
@@ -1859,7 +2540,16 @@ pub(super) struct AdapterValidationState {
 pub(super) fn is_named_adapter_platform(platform: &str) -> bool {
     matches!(
         platform,
-        "discord" | "slack" | "telegram" | "twitch" | "email" | "signal" | "mattermost"
+        "discord"
+            | "slack"
+            | "telegram"
+            | "twitch"
+            | "email"
+            | "signal"
+            | "mattermost"
+            | "matrix"
+            | "irc"
+            | "teams"
     )
 }
 
@@ -2161,6 +2851,108 @@ pub(super) fn build_adapter_validation_states(
         );
     }
 
+    if let Some(matrix) = &messaging.matrix {
+        validate_instance_names(
+            "matrix",
+            matrix
+                .instances
+                .iter()
+                .map(|instance| instance.name.as_str()),
+        )?;
+        let named_instances: std::collections::HashSet<String> = matrix
+            .instances
+            .iter()
+            .filter(|i| i.enabled)
+            .map(|i| i.name.clone())
+            .collect();
+        let default_present = matrix.enabled
+            && !matrix.homeserver_url.trim().is_empty()
+            && !matrix.access_token.trim().is_empty();
+        validate_runtime_keys("matrix", default_present, &named_instances)?;
+        if default_present {
+            validate_matrix_homeserver_url(&matrix.homeserver_url)?;
+        }
+        for instance in &matrix.instances {
+            if instance.enabled && !instance.homeserver_url.is_empty() {
+                validate_matrix_homeserver_url(&instance.homeserver_url)?;
+            }
+        }
+        states.insert(
+            "matrix",
+            AdapterValidationState {
+                default_present,
+                named_instances,
+            },
+        );
+    }
+
+    if let Some(irc) = &messaging.irc {
+        validate_instance_names(
+            "irc",
+            irc.instances.iter().map(|instance| instance.name.as_str()),
+        )?;
+        let named_instances: std::collections::HashSet<String> = irc
+            .instances
+            .iter()
+            .filter(|i| i.enabled)
+            .map(|i| i.name.clone())
+            .collect();
+        let default_present =
+            irc.enabled && !irc.server.trim().is_empty() && !irc.nickname.trim().is_empty();
+        validate_runtime_keys("irc", default_present, &named_instances)?;
+        if default_present {
+            validate_irc_identity(&irc.nickname, &irc.channels)?;
+        }
+        for instance in irc.instances.iter().filter(|i| i.enabled) {
+            validate_irc_identity(&instance.nickname, &instance.channels)?;
+        }
+        states.insert(
+            "irc",
+            AdapterValidationState {
+                default_present,
+                named_instances,
+            },
+        );
+    }
+
+    if let Some(teams) = &messaging.teams {
+        validate_instance_names(
+            "teams",
+            teams
+                .instances
+                .iter()
+                .map(|instance| instance.name.as_str()),
+        )?;
+        let named_instances: std::collections::HashSet<String> = teams
+            .instances
+            .iter()
+            .filter(|i| i.enabled)
+            .map(|i| i.name.clone())
+            .collect();
+        let default_present = teams.enabled
+            && !teams.app_id.trim().is_empty()
+            && !teams.app_password.trim().is_empty();
+        validate_runtime_keys("teams", default_present, &named_instances)?;
+        let listeners = default_present
+            .then_some(("default", teams.bind.as_str(), teams.port))
+            .into_iter()
+            .chain(
+                teams
+                    .instances
+                    .iter()
+                    .filter(|i| i.enabled)
+                    .map(|i| (i.name.as_str(), i.bind.as_str(), i.port)),
+            );
+        validate_teams_listeners(listeners)?;
+        states.insert(
+            "teams",
+            AdapterValidationState {
+                default_present,
+                named_instances,
+            },
+        );
+    }
+
     Ok(states)
 }
 
@@ -2224,6 +3016,93 @@ fn validate_mattermost_url(url: &str) -> Result<()> {
     Ok(())
 }
 
+/// Unlike Mattermost, a homeserver's client API may live under a path prefix
+/// (e.g. behind a reverse proxy), so only credentials, query strings, and
+/// fragments are rejected.
+fn validate_matrix_homeserver_url(url: &str) -> Result<()> {
+    let parsed = url::Url::parse(url)
+        .map_err(|e| ConfigError::Invalid(format!("invalid matrix homeserver_url '{url}': {e}")))?;
+
+    if !parsed.username().is_empty() || parsed.password().is_some() {
+        return Err(ConfigError::Invalid(
+            "matrix homeserver_url must not contain credentials".to_string(),
+        )
+        .into());
+    }
+    if parsed.query().is_some() || parsed.fragment().is_some() {
+        return Err(ConfigError::Invalid(
+            "matrix homeserver_url must not contain a query string or fragment".to_string(),
+        )
+        .into());
+    }
+
+    match parsed.scheme() {
+        "https" => {}
+        "http" => {
+            let is_local = match parsed.host() {
+                Some(url::Host::Domain(h)) => h.eq_ignore_ascii_case("localhost"),
+                Some(url::Host::Ipv4(addr)) => addr.is_loopback(),
+                Some(url::Host::Ipv6(addr)) => addr.is_loopback(),
+                None => false,
+            };
+            // Local http is how E2EE proxies like Pantalaimon are usually run.
+            if !is_local {
+                return Err(ConfigError::Invalid(
+                    "matrix homeserver_url must use https for non-localhost hosts".to_string(),
+                )
+                .into());
+            }
+        }
+        scheme => {
+            return Err(ConfigError::Invalid(format!(
+                "matrix homeserver_url must use http or https, got: {scheme}"
+            ))
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Reject nicknames and channel names the server would refuse or that would
+/// break the line protocol (spaces, commas, control characters).
+fn validate_irc_identity(nickname: &str, channels: &[String]) -> Result<()> {
+    let bad_char = |c: char| c.is_whitespace() || c.is_control() || c == ',';
+
+    if nickname.is_empty()
+        || nickname.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '#')
+        || nickname.contains(|c: char| bad_char(c) || matches!(c, '!' | '@' | ':'))
+    {
+        return Err(ConfigError::Invalid(format!("invalid irc nickname '{nickname}'")).into());
+    }
+
+    for channel in channels {
+        if !channel.starts_with(['#', '&']) || channel.len() < 2 || channel.contains(bad_char) {
+            return Err(ConfigError::Invalid(format!(
+                "invalid irc channel '{channel}': must start with # or & and contain no spaces or commas"
+            ))
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Each Teams adapter binds its own messaging endpoint, so two enabled
+/// adapters on the same address would fail to start.
+fn validate_teams_listeners<'a>(
+    listeners: impl Iterator<Item = (&'a str, &'a str, u16)>,
+) -> Result<()> {
+    let mut seen: HashMap<(&str, u16), &str> = HashMap::new();
+    for (name, bind, port) in listeners {
+        if let Some(existing) = seen.insert((bind, port), name) {
+            return Err(ConfigError::Invalid(format!(
+                "teams adapters '{existing}' and '{name}' both listen on {bind}:{port}; give each a distinct port"
+            ))
+            .into());
+        }
+    }
+    Ok(())
+}
+
 pub(super) fn validate_instance_names<'a>(
     platform: &str,
     names: impl Iterator<Item = &'a str>,
@@ -2337,6 +3216,33 @@ pub fn resolve_agent_for_message(
     Some((std::sync::Arc::from(default_agent_id), None))
 }
 
+/// Resolve the access role assigned to the sender of a routed message.
+///
+/// Takes the highest role granted by a matching `[[humans]]` entry or by the
+/// `access` table of any binding for the message's agent that matches on
+/// routing. Returns `None` when nothing assigns a role, leaving the agent's
+/// `default_role` to apply.
+pub fn resolve_sender_role(
+    bindings: &[Binding],
+    humans: &[HumanDef],
+    message: &crate::InboundMessage,
+) -> Option<AccessRole> {
+    let human_role =
+        crate::conversation::humans::match_human_def(humans, &message.source, &message.sender_id)
+            .and_then(|human| human.access_role);
+    let binding_roles = bindings
+        .iter()
+        .filter(|binding| {
+            message
+                .agent_id
+                .as_deref()
+                .is_none_or(|agent_id| agent_id == binding.agent_id)
+        })
+        .filter(|binding| binding.matches_route(message))
+        .filter_map(|binding| binding.access.role_for(message));
+    human_role.into_iter().chain(binding_roles).max()
+}
+
 // ---------------------------------------------------------------------------
 // Messaging platform configs
 // ---------------------------------------------------------------------------
@@ -2352,6 +3258,9 @@ pub struct MessagingConfig {
     pub twitch: Option<TwitchConfig>,
     pub signal: Option<SignalConfig>,
     pub mattermost: Option<MattermostConfig>,
+    pub matrix: Option<MatrixConfig>,
+    pub irc: Option<IrcConfig>,
+    pub teams: Option<TeamsConfig>,
 }
 
 #[derive(Clone)]
@@ -2596,6 +3505,9 @@ pub struct EmailConfig {
     pub from_address: String,
     pub from_name: Option<String>,
     pub poll_interval_secs: u64,
+    /// Use IMAP IDLE for push delivery when the server supports it, polling
+    /// otherwise.
+    pub idle: bool,
     pub folders: Vec<String>,
     pub allowed_senders: Vec<String>,
     pub max_body_bytes: usize,
@@ -2621,6 +3533,9 @@ pub struct EmailInstanceConfig {
     pub from_address: String,
     pub from_name: Option<String>,
     pub poll_interval_secs: u64,
+    /// Use IMAP IDLE for push delivery when the server supports it, polling
+    /// otherwise.
+    pub idle: bool,
     pub folders: Vec<String>,
     pub allowed_senders: Vec<String>,
     pub max_body_bytes: usize,
@@ -2645,6 +3560,7 @@ impl std::fmt::Debug for EmailInstanceConfig {
             .field("from_address", &"[REDACTED]")
             .field("from_name", &self.from_name)
             .field("poll_interval_secs", &self.poll_interval_secs)
+            .field("idle", &self.idle)
             .field("folders", &self.folders)
             .field("allowed_senders", &"[REDACTED]")
             .field("max_body_bytes", &self.max_body_bytes)
@@ -2670,6 +3586,7 @@ impl std::fmt::Debug for EmailConfig {
             .field("from_address", &"[REDACTED]")
             .field("from_name", &self.from_name)
             .field("poll_interval_secs", &self.poll_interval_secs)
+            .field("idle", &self.idle)
             .field("folders", &self.folders)
             .field("allowed_senders", &"[REDACTED]")
             .field("max_body_bytes", &self.max_body_bytes)
@@ -2844,6 +3761,8 @@ pub struct WebhookConfig {
     pub port: u16,
     pub bind: String,
     pub auth_token: Option<String>,
+    /// HMAC key for signing callback POSTs. Falls back to `auth_token`.
+    pub signing_secret: Option<String>,
 }
 
 /// Signal messaging via signal-cli JSON-RPC daemon.
@@ -3026,6 +3945,306 @@ impl std::fmt::Debug for MattermostInstanceConfig {
     }
 }
 
+#[derive(Clone)]
+pub struct MatrixConfig {
+    pub enabled: bool,
+    /// Client-Server API base URL of the homeserver (or of an E2EE proxy such
+    /// as Pantalaimon sitting in front of it).
+    pub homeserver_url: String,
+    pub access_token: String,
+    pub instances: Vec<MatrixInstanceConfig>,
+    pub dm_allowed_users: Vec<String>,
+    /// Accept room invites from these users (in addition to DM-allowed users
+    /// and invites into rooms named in bindings).
+    pub invite_allowed_users: Vec<String>,
+    pub max_attachment_bytes: usize,
+}
+
+impl std::fmt::Debug for MatrixConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MatrixConfig")
+            .field("enabled", &self.enabled)
+            .field("homeserver_url", &self.homeserver_url)
+            .field("access_token", &"[REDACTED]")
+            .field("instances", &self.instances)
+            .field("dm_allowed_users", &self.dm_allowed_users)
+            .field("invite_allowed_users", &self.invite_allowed_users)
+            .field("max_attachment_bytes", &self.max_attachment_bytes)
+            .finish()
+    }
+}
+
+#[derive(Clone)]
+pub struct MatrixInstanceConfig {
+    pub name: String,
+    pub enabled: bool,
+    pub homeserver_url: String,
+    pub access_token: String,
+    pub dm_allowed_users: Vec<String>,
+    pub invite_allowed_users: Vec<String>,
+    pub max_attachment_bytes: usize,
+}
+
+impl std::fmt::Debug for MatrixInstanceConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MatrixInstanceConfig")
+            .field("name", &self.name)
+            .field("enabled", &self.enabled)
+            .field("homeserver_url", &self.homeserver_url)
+            .field("access_token", &"[REDACTED]")
+            .field("dm_allowed_users", &self.dm_allowed_users)
+            .field("invite_allowed_users", &self.invite_allowed_users)
+            .field("max_attachment_bytes", &self.max_attachment_bytes)
+            .finish()
+    }
+}
+
+impl SystemSecrets for MatrixConfig {
+    fn section() -> &'static str {
+        "matrix"
+    }
+
+    fn is_messaging_adapter() -> bool {
+        true
+    }
+
+    fn secret_fields() -> &'static [SecretField] {
+        &[
+            SecretField {
+                toml_key: "access_token",
+                secret_name: "MATRIX_ACCESS_TOKEN",
+                instance_pattern: Some(InstancePattern {
+                    platform_prefix: "MATRIX",
+                    field_suffix: "ACCESS_TOKEN",
+                }),
+            },
+            SecretField {
+                toml_key: "homeserver_url",
+                secret_name: "MATRIX_HOMESERVER_URL",
+                instance_pattern: Some(InstancePattern {
+                    platform_prefix: "MATRIX",
+                    field_suffix: "HOMESERVER_URL",
+                }),
+            },
+        ]
+    }
+}
+
+#[derive(Clone)]
+pub struct IrcConfig {
+    pub enabled: bool,
+    /// Server hostname (e.g. `irc.libera.chat`).
+    pub server: String,
+    pub port: u16,
+    pub tls: bool,
+    pub nickname: String,
+    /// Username (ident) sent in `USER`. Defaults to the nickname.
+    pub username: Option<String>,
+    pub realname: Option<String>,
+    /// Connection password sent with `PASS` (bouncers, private servers).
+    pub server_password: Option<String>,
+    /// SASL PLAIN account. Defaults to the nickname when only a password is set.
+    pub sasl_username: Option<String>,
+    pub sasl_password: Option<String>,
+    pub channels: Vec<String>,
+    pub instances: Vec<IrcInstanceConfig>,
+    pub dm_allowed_users: Vec<String>,
+}
+
+impl std::fmt::Debug for IrcConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IrcConfig")
+            .field("enabled", &self.enabled)
+            .field("server", &self.server)
+            .field("port", &self.port)
+            .field("tls", &self.tls)
+            .field("nickname", &self.nickname)
+            .field("username", &self.username)
+            .field("realname", &self.realname)
+            .field(
+                "server_password",
+                &self.server_password.as_ref().map(|_| "[REDACTED]"),
+            )
+            .field("sasl_username", &self.sasl_username)
+            .field(
+                "sasl_password",
+                &self.sasl_password.as_ref().map(|_| "[REDACTED]"),
+            )
+            .field("channels", &self.channels)
+            .field("instances", &self.instances)
+            .field("dm_allowed_users", &self.dm_allowed_users)
+            .finish()
+    }
+}
+
+#[derive(Clone)]
+pub struct IrcInstanceConfig {
+    pub name: String,
+    pub enabled: bool,
+    pub server: String,
+    pub port: u16,
+    pub tls: bool,
+    pub nickname: String,
+    pub username: Option<String>,
+    pub realname: Option<String>,
+    pub server_password: Option<String>,
+    pub sasl_username: Option<String>,
+    pub sasl_password: Option<String>,
+    pub channels: Vec<String>,
+    pub dm_allowed_users: Vec<String>,
+}
+
+impl std::fmt::Debug for IrcInstanceConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IrcInstanceConfig")
+            .field("name", &self.name)
+            .field("enabled", &self.enabled)
+            .field("server", &self.server)
+            .field("port", &self.port)
+            .field("tls", &self.tls)
+            .field("nickname", &self.nickname)
+            .field("username", &self.username)
+            .field("realname", &self.realname)
+            .field(
+                "server_password",
+                &self.server_password.as_ref().map(|_| "[REDACTED]"),
+            )
+            .field("sasl_username", &self.sasl_username)
+            .field(
+                "sasl_password",
+                &self.sasl_password.as_ref().map(|_| "[REDACTED]"),
+            )
+            .field("channels", &self.channels)
+            .field("dm_allowed_users", &self.dm_allowed_users)
+            .finish()
+    }
+}
+
+impl SystemSecrets for IrcConfig {
+    fn section() -> &'static str {
+        "irc"
+    }
+
+    fn is_messaging_adapter() -> bool {
+        true
+    }
+
+    fn secret_fields() -> &'static [SecretField] {
+        &[
+            SecretField {
+                toml_key: "sasl_password",
+                secret_name: "IRC_SASL_PASSWORD",
+                instance_pattern: Some(InstancePattern {
+                    platform_prefix: "IRC",
+                    field_suffix: "SASL_PASSWORD",
+                }),
+            },
+            SecretField {
+                toml_key: "server_password",
+                secret_name: "IRC_SERVER_PASSWORD",
+                instance_pattern: Some(InstancePattern {
+                    platform_prefix: "IRC",
+                    field_suffix: "SERVER_PASSWORD",
+                }),
+            },
+        ]
+    }
+}
+
+/// Microsoft Teams via the Bot Framework.
+///
+/// The adapter binds its own HTTP server for the bot's messaging endpoint
+/// (`/api/messages`) and replies through the Bot Framework connector API.
+#[derive(Clone)]
+pub struct TeamsConfig {
+    pub enabled: bool,
+    /// Microsoft App ID of the Azure Bot registration.
+    pub app_id: String,
+    /// Client secret for the app registration.
+    pub app_password: String,
+    /// Directory (tenant) ID. Set for single-tenant bots; also restricts
+    /// inbound activities to this tenant.
+    pub tenant_id: Option<String>,
+    pub bind: String,
+    pub port: u16,
+    pub instances: Vec<TeamsInstanceConfig>,
+    /// Azure AD object IDs allowed to message the bot in personal chats.
+    pub dm_allowed_users: Vec<String>,
+}
+
+impl std::fmt::Debug for TeamsConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TeamsConfig")
+            .field("enabled", &self.enabled)
+            .field("app_id", &self.app_id)
+            .field("app_password", &"[REDACTED]")
+            .field("tenant_id", &self.tenant_id)
+            .field("bind", &self.bind)
+            .field("port", &self.port)
+            .field("instances", &self.instances)
+            .field("dm_allowed_users", &self.dm_allowed_users)
+            .finish()
+    }
+}
+
+#[derive(Clone)]
+pub struct TeamsInstanceConfig {
+    pub name: String,
+    pub enabled: bool,
+    pub app_id: String,
+    pub app_password: String,
+    pub tenant_id: Option<String>,
+    pub bind: String,
+    pub port: u16,
+    pub dm_allowed_users: Vec<String>,
+}
+
+impl std::fmt::Debug for TeamsInstanceConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TeamsInstanceConfig")
+            .field("name", &self.name)
+            .field("enabled", &self.enabled)
+            .field("app_id", &self.app_id)
+            .field("app_password", &"[REDACTED]")
+            .field("tenant_id", &self.tenant_id)
+            .field("bind", &self.bind)
+            .field("port", &self.port)
+            .field("dm_allowed_users", &self.dm_allowed_users)
+            .finish()
+    }
+}
+
+impl SystemSecrets for TeamsConfig {
+    fn section() -> &'static str {
+        "teams"
+    }
+
+    fn is_messaging_adapter() -> bool {
+        true
+    }
+
+    fn secret_fields() -> &'static [SecretField] {
+        &[
+            SecretField {
+                toml_key: "app_password",
+                secret_name: "TEAMS_APP_PASSWORD",
+                instance_pattern: Some(InstancePattern {
+                    platform_prefix: "TEAMS",
+                    field_suffix: "APP_PASSWORD",
+                }),
+            },
+            SecretField {
+                toml_key: "app_id",
+                secret_name: "TEAMS_APP_ID",
+                instance_pattern: Some(InstancePattern {
+                    platform_prefix: "TEAMS",
+                    field_suffix: "APP_ID",
+                }),
+            },
+        ]
+    }
+}
+
 #[cfg(test)]
 mod mattermost_url_tests {
     use super::validate_mattermost_url;
@@ -2,8 +2,9 @@ use std::path::PathBuf;
 use std::sync::Arc;
 
 use super::{
-    Binding, Config, DiscordPermissions, MattermostPermissions, RuntimeConfig, SignalPermissions,
-    SlackPermissions, TelegramPermissions, TwitchPermissions, binding_runtime_adapter_key,
+    Binding, Config, DiscordPermissions, IrcPermissions, MatrixPermissions, MattermostPermissions,
+    RuntimeConfig, SignalPermissions, SlackPermissions, TeamsPermissions, TelegramPermissions,
+    TwitchPermissions, binding_runtime_adapter_key,
 };
 
 /// Per-agent context needed by the file watcher: (id, prompt_dir, identity_dir,
@@ -32,6 +33,9 @@ pub fn spawn_file_watcher(
     telegram_permissions: Option<Arc<arc_swap::ArcSwap<TelegramPermissions>>>,
     twitch_permissions: Option<Arc<arc_swap::ArcSwap<TwitchPermissions>>>,
     mattermost_permissions: Option<Arc<arc_swap::ArcSwap<MattermostPermissions>>>,
+    matrix_permissions: Option<Arc<arc_swap::ArcSwap<MatrixPermissions>>>,
+    irc_permissions: Option<Arc<arc_swap::ArcSwap<IrcPermissions>>>,
+    teams_permissions: Option<Arc<arc_swap::ArcSwap<TeamsPermissions>>>,
     signal_permissions: Option<Arc<arc_swap::ArcSwap<SignalPermissions>>>,
     bindings: Arc<arc_swap::ArcSwap<Vec<Binding>>>,
     messaging_manager: Option<Arc<crate::messaging::MessagingManager>>,
@@ -251,6 +255,30 @@ pub fn spawn_file_watcher(
                     tracing::info!("mattermost permissions reloaded");
                 }
 
+                if let Some(ref perms) = matrix_permissions
+                    && let Some(matrix_config) = &config.messaging.matrix
+                {
+                    let new_perms = MatrixPermissions::from_config(matrix_config, &config.bindings);
+                    perms.store(Arc::new(new_perms));
+                    tracing::info!("matrix permissions reloaded");
+                }
+
+                if let Some(ref perms) = irc_permissions
+                    && let Some(irc_config) = &config.messaging.irc
+                {
+                    let new_perms = IrcPermissions::from_config(irc_config, &config.bindings);
+                    perms.store(Arc::new(new_perms));
+                    tracing::info!("irc permissions reloaded");
+                }
+
+                if let Some(ref perms) = teams_permissions
+                    && let Some(teams_config) = &config.messaging.teams
+                {
+                    let new_perms = TeamsPermissions::from_config(teams_config, &config.bindings);
+                    perms.store(Arc::new(new_perms));
+                    tracing::info!("teams permissions reloaded");
+                }
+
                 if let Some(ref perms) = signal_permissions
                     && let Some(signal_config) = &config.messaging.signal
                 {
@@ -269,6 +297,9 @@ pub fn spawn_file_watcher(
                     let telegram_permissions = telegram_permissions.clone();
                     let twitch_permissions = twitch_permissions.clone();
                     let mattermost_permissions = mattermost_permissions.clone();
+                    let matrix_permissions = matrix_permissions.clone();
+                    let irc_permissions = irc_permissions.clone();
+                    let teams_permissions = teams_permissions.clone();
                     let signal_permissions = signal_permissions.clone();
                     let instance_dir = instance_dir.clone();
 
@@ -684,6 +715,192 @@ pub fn spawn_file_watcher(
                                     }
                                 }
                             }
+
+                        // Matrix: start default + named instances that are enabled and not already running.
+                        if let Some(matrix_config) = &config.messaging.matrix
+                            && matrix_config.enabled {
+                                if !matrix_config.homeserver_url.is_empty()
+                                    && !matrix_config.access_token.is_empty()
+                                    && !manager.has_adapter("matrix").await
+                                {
+                                    let permissions = match matrix_permissions {
+                                        Some(ref existing) => existing.clone(),
+                                        None => {
+                                            let permissions = MatrixPermissions::from_config(matrix_config, &config.bindings);
+                                            Arc::new(arc_swap::ArcSwap::from_pointee(permissions))
+                                        }
+                                    };
+                                    match crate::messaging::matrix::MatrixAdapter::new(
+                                        "matrix",
+                                        &matrix_config.homeserver_url,
+                                        matrix_config.access_token.as_str(),
+                                        matrix_config.max_attachment_bytes,
+                                        permissions,
+                                    ) {
+                                        Ok(adapter) => {
+                                            if let Err(error) = manager.register_and_start(adapter).await {
+                                                tracing::error!(%error, "failed to hot-start matrix adapter from config change");
+                                            }
+                                        }
+                                        Err(error) => {
+                                            tracing::error!(%error, "failed to build matrix adapter from config change");
+                                        }
+                                    }
+                                }
+
+                                for instance in matrix_config.instances.iter().filter(|instance| instance.enabled) {
+                                    let runtime_key = binding_runtime_adapter_key(
+                                        "matrix",
+                                        Some(instance.name.as_str()),
+                                    );
+                                    if manager.has_adapter(runtime_key.as_str()).await {
+                                        continue;
+                                    }
+
+                                    let permissions = Arc::new(arc_swap::ArcSwap::from_pointee(
+                                        MatrixPermissions::from_instance_config(instance, &config.bindings),
+                                    ));
+                                    match crate::messaging::matrix::MatrixAdapter::new(
+                                        runtime_key,
+                                        &instance.homeserver_url,
+                                        instance.access_token.as_str(),
+                                        instance.max_attachment_bytes,
+                                        permissions,
+                                    ) {
+                                        Ok(adapter) => {
+                                            if let Err(error) = manager.register_and_start(adapter).await {
+                                                tracing::error!(%error, adapter = %instance.name, "failed to hot-start named matrix adapter from config change");
+                                            }
+                                        }
+                                        Err(error) => {
+                                            tracing::error!(%error, adapter = %instance.name, "failed to build named matrix adapter from config change");
+                                        }
+                                    }
+                                }
+                            }
+
+                        // IRC: start default + named instances that are enabled and not already running.
+                        if let Some(irc_config) = &config.messaging.irc
+                            && irc_config.enabled {
+                                if !irc_config.server.is_empty()
+                                    && !irc_config.nickname.is_empty()
+                                    && !manager.has_adapter("irc").await
+                                {
+                                    let permissions = match irc_permissions {
+                                        Some(ref existing) => existing.clone(),
+                                        None => {
+                                            let permissions = IrcPermissions::from_config(irc_config, &config.bindings);
+                                            Arc::new(arc_swap::ArcSwap::from_pointee(permissions))
+                                        }
+                                    };
+                                    match crate::messaging::irc::IrcAdapter::new(
+                                        "irc",
+                                        crate::messaging::irc::IrcConnectionSettings::from_config(irc_config),
+                                        permissions,
+                                    ) {
+                                        Ok(adapter) => {
+                                            if let Err(error) = manager.register_and_start(adapter).await {
+                                                tracing::error!(%error, "failed to hot-start irc adapter from config change");
+                                            }
+                                        }
+                                        Err(error) => {
+                                            tracing::error!(%error, "failed to build irc adapter from config change");
+                                        }
+                                    }
+                                }
+
+                                for instance in irc_config.instances.iter().filter(|instance| instance.enabled) {
+                                    let runtime_key = binding_runtime_adapter_key(
+                                        "irc",
+                                        Some(instance.name.as_str()),
+                                    );
+                                    if manager.has_adapter(runtime_key.as_str()).await {
+                                        continue;
+                                    }
+
+                                    let permissions = Arc::new(arc_swap::ArcSwap::from_pointee(
+                                        IrcPermissions::from_instance_config(instance, &config.bindings),
+                                    ));
+                                    match crate::messaging::irc::IrcAdapter::new(
+                                        runtime_key,
+                                        crate::messaging::irc::IrcConnectionSettings::from_instance_config(instance),
+                                        permissions,
+                                    ) {
+                                        Ok(adapter) => {
+                                            if let Err(error) = manager.register_and_start(adapter).await {
+                                                tracing::error!(%error, adapter = %instance.name, "failed to hot-start named irc adapter from config change");
+                                            }
+                                        }
+                                        Err(error) => {
+                                            tracing::error!(%error, adapter = %instance.name, "failed to build named irc adapter from config change");
+                                        }
+                                    }
+                                }
+                            }
+
+                        // Teams: start default + named instances that are enabled and not already running.
+                        if let Some(teams_config) = &config.messaging.teams
+                            && teams_config.enabled {
+                                if !teams_config.app_id.is_empty()
+                                    && !teams_config.app_password.is_empty()
+                                    && !manager.has_adapter("teams").await
+                                {
+                                    let permissions = match teams_permissions {
+                                        Some(ref existing) => existing.clone(),
+                                        None => {
+                                            let permissions = TeamsPermissions::from_config(teams_config, &config.bindings);
+                                            Arc::new(arc_swap::ArcSwap::from_pointee(permissions))
+                                        }
+                                    };
+                                    match crate::messaging::teams::TeamsAdapter::new(
+                                        "teams",
+                                        crate::messaging::teams::TeamsConnectionSettings::from_config(teams_config),
+                                        Some(crate::messaging::teams::conversation_store_path(&instance_dir, None)),
+                                        permissions,
+                                    ) {
+                                        Ok(adapter) => {
+                                            if let Err(error) = manager.register_and_start(adapter).await {
+                                                tracing::error!(%error, "failed to hot-start teams adapter from config change");
+                                            }
+                                        }
+                                        Err(error) => {
+                                            tracing::error!(%error, "failed to build teams adapter from config change");
+                                        }
+                                    }
+                                }
+
+                                for instance in teams_config.instances.iter().filter(|instance| instance.enabled) {
+                                    let runtime_key = binding_runtime_adapter_key(
+                                        "teams",
+                                        Some(instance.name.as_str()),
+                                    );
+                                    if manager.has_adapter(runtime_key.as_str()).await {
+                                        continue;
+                                    }
+
+                                    let permissions = Arc::new(arc_swap::ArcSwap::from_pointee(
+                                        TeamsPermissions::from_instance_config(instance, &config.bindings),
+                                    ));
+                                    match crate::messaging::teams::TeamsAdapter::new(
+                                        runtime_key,
+                                        crate::messaging::teams::TeamsConnectionSettings::from_instance_config(instance),
+                                        Some(crate::messaging::teams::conversation_store_path(
+                                            &instance_dir,
+                                            Some(instance.name.as_str()),
+                                        )),
+                                        permissions,
+                                    ) {
+                                        Ok(adapter) => {
+                                            if let Err(error) = manager.register_and_start(adapter).await {
+                                                tracing::error!(%error, adapter = %instance.name, "failed to hot-start named teams adapter from config change");
+                                            }
+                                        }
+                                        Err(error) => {
+                                            tracing::error!(%error, adapter = %instance.name, "failed to build named teams adapter from config change");
+                                        }
+                                    }
+                                }
+                            }
                     });
                 }
             }
@@ -4,6 +4,7 @@ pub mod channel_settings;
 pub mod channels;
 pub mod context;
 pub mod history;
+pub mod humans;
 pub mod portal;
 pub mod settings;
 pub mod worker_transcript;
@@ -13,6 +14,7 @@ pub use channels::ChannelStore;
 pub use history::{
     ConversationLogger, ProcessRunLogger, TimelineItem, WorkerDetailRow, WorkerRunRow,
 };
+pub use humans::{Human, HumanStore};
 pub use portal::{PortalConversation, PortalConversationStore, PortalConversationSummary};
 pub use settings::{
     ConversationDefaultsResponse, ConversationSettings, DelegationMode, MemoryMode, ModelOption,
@@ -315,6 +315,27 @@ fn extract_platform_meta(
                 }
             }
         }
+        "irc" => {
+            for key in ["irc_target", "irc_is_dm"] {
+                if let Some(value) = metadata.get(key) {
+                    meta.insert(key.to_string(), value.clone());
+                }
+            }
+        }
+        "teams" => {
+            for key in ["teams_conversation_id", "teams_is_dm"] {
+                if let Some(value) = metadata.get(key) {
+                    meta.insert(key.to_string(), value.clone());
+                }
+            }
+        }
+        "matrix" => {
+            for key in ["matrix_room_id", "matrix_is_direct"] {
+                if let Some(value) = metadata.get(key) {
+                    meta.insert(key.to_string(), value.clone());
+                }
+            }
+        }
         "twitch" => {
             if let Some(value) = metadata.get("twitch_channel") {
                 meta.insert("twitch_channel".to_string(), value.clone());
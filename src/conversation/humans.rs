@@ -0,0 +1,365 @@
+//! Known humans (SQLite): one row per platform sender identity.
+//!
+//! Rows are upserted from the inbound message pipeline and carry a
+//! cortex-generated summary that channels inject as participant info.
+
+use crate::config::HumanDef;
+use crate::error::Result;
+use crate::memory::types::user_scope_id;
+
+use sqlx::{Row as _, SqlitePool};
+
+/// A person the agent has heard from on some platform.
+#[derive(Debug, Clone)]
+pub struct Human {
+    pub id: String,
+    pub display_name: String,
+    pub platform: String,
+    pub platform_user_id: String,
+    /// ID of the `[[humans]]` config entry this identity maps to, if any.
+    pub human_def_id: Option<String>,
+    pub summary: Option<String>,
+    pub last_seen_at: chrono::DateTime<chrono::Utc>,
+    pub last_summary_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl Human {
+    /// Memory scope ID (`platform:sender_id`) used for user-scoped recall.
+    pub fn user_scope_id(&self) -> Option<String> {
+        user_scope_id(&self.platform, &self.platform_user_id)
+    }
+}
+
+/// Persistence for the `humans` table.
+///
+/// `upsert` is fire-and-forget like `ChannelStore::upsert`, so the message
+/// pipeline never waits on it.
+#[derive(Debug, Clone)]
+pub struct HumanStore {
+    pool: SqlitePool,
+}
+
+const HUMAN_COLUMNS: &str = "id, display_name, platform, platform_user_id, human_def_id, summary, \
+     last_seen_at, last_summary_at, created_at";
+
+impl HumanStore {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Record that a human was seen, refreshing their display name and config
+    /// link. Fire-and-forget.
+    pub fn upsert(
+        &self,
+        platform: &str,
+        platform_user_id: &str,
+        display_name: &str,
+        human_def_id: Option<&str>,
+    ) {
+        let store = self.clone();
+        let platform = platform.to_string();
+        let platform_user_id = platform_user_id.to_string();
+        let display_name = display_name.to_string();
+        let human_def_id = human_def_id.map(str::to_string);
+
+        tokio::spawn(async move {
+            if let Err(error) = store
+                .upsert_now(
+                    &platform,
+                    &platform_user_id,
+                    &display_name,
+                    human_def_id.as_deref(),
+                )
+                .await
+            {
+                tracing::warn!(%error, %platform, %platform_user_id, "failed to upsert human");
+            }
+        });
+    }
+
+    async fn upsert_now(
+        &self,
+        platform: &str,
+        platform_user_id: &str,
+        display_name: &str,
+        human_def_id: Option<&str>,
+    ) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO humans (id, display_name, platform, platform_user_id, human_def_id, last_seen_at) \
+             VALUES (?, ?, ?, ?, ?, CURRENT_TIMESTAMP) \
+             ON CONFLICT(platform, platform_user_id) DO UPDATE SET \
+                 display_name = excluded.display_name, \
+                 human_def_id = excluded.human_def_id, \
+                 last_seen_at = CURRENT_TIMESTAMP",
+        )
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(display_name)
+        .bind(platform)
+        .bind(platform_user_id)
+        .bind(human_def_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|error| anyhow::anyhow!(error))?;
+        Ok(())
+    }
+
+    /// Look up a human by platform identity.
+    pub async fn get_by_platform_id(
+        &self,
+        platform: &str,
+        platform_user_id: &str,
+    ) -> Result<Option<Human>> {
+        let row = sqlx::query(&format!(
+            "SELECT {HUMAN_COLUMNS} FROM humans WHERE platform = ? AND platform_user_id = ?"
+        ))
+        .bind(platform)
+        .bind(platform_user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|error| anyhow::anyhow!(error))?;
+
+        Ok(row.map(row_to_human))
+    }
+
+    /// Batch lookup by `(platform, platform_user_id)` pairs. Unknown
+    /// identities are skipped.
+    pub async fn get_by_platform_ids(&self, identities: &[(String, String)]) -> Result<Vec<Human>> {
+        if identities.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let clauses =
+            vec!["(platform = ? AND platform_user_id = ?)"; identities.len()].join(" OR ");
+        let sql = format!("SELECT {HUMAN_COLUMNS} FROM humans WHERE {clauses}");
+        let mut query = sqlx::query(&sql);
+        for (platform, platform_user_id) in identities {
+            query = query.bind(platform).bind(platform_user_id);
+        }
+
+        let rows = query
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|error| anyhow::anyhow!(error))?;
+
+        Ok(rows.into_iter().map(row_to_human).collect())
+    }
+
+    /// Store a freshly generated summary.
+    pub async fn update_summary(&self, id: &str, summary: &str) -> Result<()> {
+        sqlx::query(
+            "UPDATE humans SET summary = ?, last_summary_at = CURRENT_TIMESTAMP WHERE id = ?",
+        )
+        .bind(summary)
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(|error| anyhow::anyhow!(error))?;
+        Ok(())
+    }
+
+    /// Humans whose summary needs regenerating: never summarized, or seen
+    /// more than `stale_after_secs` after the last summary. Most recently
+    /// seen first.
+    pub async fn get_stale_summaries(
+        &self,
+        stale_after_secs: u64,
+        limit: usize,
+    ) -> Result<Vec<Human>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {HUMAN_COLUMNS} FROM humans \
+             WHERE summary IS NULL OR last_summary_at IS NULL \
+                OR (julianday(last_seen_at) - julianday(last_summary_at)) * 86400 > ? \
+             ORDER BY last_seen_at DESC \
+             LIMIT ?"
+        ))
+        .bind(stale_after_secs as i64)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|error| anyhow::anyhow!(error))?;
+
+        Ok(rows.into_iter().map(row_to_human).collect())
+    }
+
+    /// Recent messages this person sent, newest first, across all channels.
+    pub async fn recent_messages(
+        &self,
+        human: &Human,
+        limit: i64,
+    ) -> Result<Vec<(String, chrono::DateTime<chrono::Utc>)>> {
+        let rows = sqlx::query(
+            "SELECT content, created_at FROM conversation_messages \
+             WHERE role = 'user' AND sender_id = ? AND channel_id LIKE ? \
+             ORDER BY created_at DESC \
+             LIMIT ?",
+        )
+        .bind(&human.platform_user_id)
+        .bind(format!("{}:%", human.platform))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|error| anyhow::anyhow!(error))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                (
+                    row.try_get("content").unwrap_or_default(),
+                    row.try_get("created_at")
+                        .unwrap_or_else(|_| chrono::Utc::now()),
+                )
+            })
+            .collect())
+    }
+}
+
+/// Find the `[[humans]]` config entry whose platform ID matches a sender.
+pub fn match_human_def<'a>(
+    humans: &'a [HumanDef],
+    platform: &str,
+    platform_user_id: &str,
+) -> Option<&'a HumanDef> {
+    humans.iter().find(|human| {
+        let configured = match platform {
+            "discord" => human.discord_id.as_deref(),
+            "telegram" => human.telegram_id.as_deref(),
+            "slack" => human.slack_id.as_deref(),
+            "email" => {
+                return human
+                    .email
+                    .as_deref()
+                    .is_some_and(|email| email.eq_ignore_ascii_case(platform_user_id));
+            }
+            _ => None,
+        };
+        configured == Some(platform_user_id)
+    })
+}
+
+fn row_to_human(row: sqlx::sqlite::SqliteRow) -> Human {
+    Human {
+        id: row.try_get("id").unwrap_or_default(),
+        display_name: row.try_get("display_name").unwrap_or_default(),
+        platform: row.try_get("platform").unwrap_or_default(),
+        platform_user_id: row.try_get("platform_user_id").unwrap_or_default(),
+        human_def_id: row.try_get("human_def_id").ok().flatten(),
+        summary: row.try_get("summary").ok().flatten(),
+        last_seen_at: row
+            .try_get("last_seen_at")
+            .unwrap_or_else(|_| chrono::Utc::now()),
+        last_summary_at: row.try_get("last_summary_at").ok().flatten(),
+        created_at: row
+            .try_get("created_at")
+            .unwrap_or_else(|_| chrono::Utc::now()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_store() -> HumanStore {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("in-memory sqlite should connect");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("migrations should apply");
+        HumanStore::new(pool)
+    }
+
+    #[tokio::test]
+    async fn upsert_keeps_one_row_per_identity() {
+        let store = setup_store().await;
+        store
+            .upsert_now("discord", "123", "jamie", None)
+            .await
+            .unwrap();
+        let first = store
+            .get_by_platform_id("discord", "123")
+            .await
+            .unwrap()
+            .expect("human should exist");
+
+        store
+            .upsert_now("discord", "123", "Jamie R", Some("jamie"))
+            .await
+            .unwrap();
+        let second = store
+            .get_by_platform_id("discord", "123")
+            .await
+            .unwrap()
+            .expect("human should exist");
+
+        assert_eq!(first.id, second.id);
+        assert_eq!(second.display_name, "Jamie R");
+        assert_eq!(second.human_def_id.as_deref(), Some("jamie"));
+        assert_eq!(second.user_scope_id().as_deref(), Some("discord:123"));
+
+        let batch = store
+            .get_by_platform_ids(&[
+                ("discord".into(), "123".into()),
+                ("slack".into(), "U404".into()),
+            ])
+            .await
+            .unwrap();
+        assert_eq!(batch.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn stale_summaries_skip_fresh_ones() {
+        let store = setup_store().await;
+        store
+            .upsert_now("discord", "1", "alex", None)
+            .await
+            .unwrap();
+        store.upsert_now("discord", "2", "sam", None).await.unwrap();
+
+        let stale = store.get_stale_summaries(3600, 10).await.unwrap();
+        assert_eq!(stale.len(), 2);
+
+        let alex = store
+            .get_by_platform_id("discord", "1")
+            .await
+            .unwrap()
+            .unwrap();
+        store
+            .update_summary(&alex.id, "Backend engineer.")
+            .await
+            .unwrap();
+
+        let stale = store.get_stale_summaries(3600, 10).await.unwrap();
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].display_name, "sam");
+    }
+
+    #[test]
+    fn matches_configured_humans_by_platform_id() {
+        let humans = vec![HumanDef {
+            id: "jamie".into(),
+            display_name: Some("Jamie".into()),
+            role: None,
+            bio: None,
+            description: None,
+            discord_id: Some("123".into()),
+            telegram_id: None,
+            slack_id: Some("U1".into()),
+            email: Some("Jamie@Example.com".into()),
+            access_role: None,
+        }];
+
+        assert_eq!(
+            match_human_def(&humans, "discord", "123").map(|h| h.id.as_str()),
+            Some("jamie")
+        );
+        assert!(match_human_def(&humans, "slack", "U1").is_some());
+        assert!(match_human_def(&humans, "email", "jamie@example.com").is_some());
+        assert!(match_human_def(&humans, "telegram", "123").is_none());
+        assert!(match_human_def(&humans, "discord", "999").is_none());
+    }
+}
@@ -200,6 +200,10 @@ pub struct ConversationSettings {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub save_attachments: Option<bool>,
 
+    /// Whether replies stream into the chat as they are generated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub streaming: Option<bool>,
+
     /// What context workers spawned from this conversation receive.
     #[serde(default)]
     pub worker_context: WorkerContextMode,
@@ -221,6 +225,8 @@ pub struct ResolvedConversationSettings {
     pub response_mode: ResponseMode,
     /// Whether file attachments are saved.
     pub save_attachments: bool,
+    /// Whether replies stream into the chat as they are generated.
+    pub streaming: bool,
     /// The resolved worker context settings.
     pub worker_context: WorkerContextMode,
 }
@@ -264,6 +270,9 @@ impl ResolvedConversationSettings {
             if let Some(sa) = default.save_attachments {
                 resolved.save_attachments = sa;
             }
+            if let Some(streaming) = default.streaming {
+                resolved.streaming = streaming;
+            }
             resolved.worker_context = default.worker_context.clone();
         }
 
@@ -282,6 +291,9 @@ impl ResolvedConversationSettings {
             if let Some(sa) = channel_settings.save_attachments {
                 resolved.save_attachments = sa;
             }
+            if let Some(streaming) = channel_settings.streaming {
+                resolved.streaming = streaming;
+            }
             resolved.worker_context = channel_settings.worker_context.clone();
         }
 
@@ -300,6 +312,9 @@ impl ResolvedConversationSettings {
             if let Some(sa) = conv_settings.save_attachments {
                 resolved.save_attachments = sa;
             }
+            if let Some(streaming) = conv_settings.streaming {
+                resolved.streaming = streaming;
+            }
             resolved.worker_context = conv_settings.worker_context.clone();
         }
 
@@ -332,6 +347,7 @@ impl Default for ResolvedConversationSettings {
             delegation: DelegationMode::Standard,
             response_mode: ResponseMode::Active,
             save_attachments: false,
+            streaming: false,
             worker_context: WorkerContextMode::default(),
         }
     }
@@ -458,5 +474,33 @@ mod tests {
         assert_eq!(resolved.delegation, DelegationMode::Standard);
         assert_eq!(resolved.worker_context.history, WorkerHistoryMode::None);
         assert_eq!(resolved.worker_context.memory, WorkerMemoryMode::None);
+        assert!(!resolved.streaming);
+    }
+
+    #[test]
+    fn test_streaming_inherits_until_overridden() {
+        let agent_default = ConversationSettings {
+            streaming: Some(true),
+            ..Default::default()
+        };
+        let channel_settings = ConversationSettings::default();
+
+        let resolved = ResolvedConversationSettings::resolve(
+            None,
+            Some(&channel_settings),
+            Some(&agent_default),
+        );
+        assert!(resolved.streaming);
+
+        let conversation_settings = ConversationSettings {
+            streaming: Some(false),
+            ..Default::default()
+        };
+        let resolved = ResolvedConversationSettings::resolve(
+            Some(&conversation_settings),
+            Some(&channel_settings),
+            Some(&agent_default),
+        );
+        assert!(!resolved.streaming);
     }
 }
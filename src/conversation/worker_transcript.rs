@@ -3,6 +3,7 @@
 //! Converts a Rig `Vec<Message>` history into a flat `Vec<TranscriptStep>`,
 //! then serializes to gzipped JSON for compact storage on the `worker_runs` row.
 
+use crate::agent::tool_approval::{ToolApprovalDecision, ToolApprovalRecord};
 use crate::tools::{MAX_TOOL_OUTPUT_BYTES, truncate_output};
 
 use flate2::Compression;
@@ -37,6 +38,17 @@ pub enum TranscriptStep {
         name: String,
         text: String,
     },
+    /// Human decision on a tool call gated by the approval policy.
+    ///
+    /// Placed right after the call it gated. Denied and timed-out calls also
+    /// carry the refusal as their `ToolResult`. Dropped by
+    /// `transcript_to_history()` since the model never saw it.
+    ToolApproval {
+        call_id: String,
+        name: String,
+        decision: ToolApprovalDecision,
+        resolved_by: Option<String>,
+    },
 }
 
 /// Content within an action step.
@@ -59,6 +71,68 @@ pub fn serialize_transcript(history: &[rig::message::Message]) -> Vec<u8> {
     serialize_steps(&steps)
 }
 
+/// Like [`serialize_transcript`], with a `ToolApproval` step after each
+/// tool call a human decided on.
+pub fn serialize_transcript_with_approvals(
+    history: &[rig::message::Message],
+    approvals: &[ToolApprovalRecord],
+) -> Vec<u8> {
+    let mut steps = convert_history(history);
+    insert_approval_steps(&mut steps, approvals);
+    serialize_steps(&steps)
+}
+
+/// Place approval steps after the calls they gated.
+///
+/// Calls are matched in order by call ID or, since providers differ in which
+/// ID they hand the hook, by tool name and arguments. Approvals whose call is
+/// no longer in the history (e.g. compacted away) are appended at the end.
+fn insert_approval_steps(steps: &mut Vec<TranscriptStep>, approvals: &[ToolApprovalRecord]) {
+    let mut search_from = 0;
+    for approval in approvals {
+        let args = truncate_output(&approval.args, MAX_TOOL_ARGS_BYTES);
+        let matched = steps
+            .iter()
+            .enumerate()
+            .skip(search_from)
+            .find_map(|(index, step)| {
+                let TranscriptStep::Action { content } = step else {
+                    return None;
+                };
+                content.iter().find_map(|item| match item {
+                    ActionContent::ToolCall {
+                        id,
+                        name,
+                        args: call_args,
+                    } if *name == approval.tool_name
+                        && (approval.call_id.as_deref() == Some(id.as_str())
+                            || *call_args == args) =>
+                    {
+                        Some((index, id.clone()))
+                    }
+                    _ => None,
+                })
+            });
+
+        let (position, call_id) = match matched {
+            Some((index, id)) => {
+                search_from = index + 2;
+                (index + 1, id)
+            }
+            None => (steps.len(), approval.call_id.clone().unwrap_or_default()),
+        };
+        steps.insert(
+            position,
+            TranscriptStep::ToolApproval {
+                call_id,
+                name: approval.tool_name.clone(),
+                decision: approval.decision,
+                resolved_by: approval.resolved_by.clone(),
+            },
+        );
+    }
+}
+
 /// Serialize pre-built transcript steps as gzipped JSON.
 ///
 /// Used by OpenCode workers that build transcript steps directly from SSE
@@ -389,6 +463,7 @@ pub fn transcript_to_history(steps: &[TranscriptStep]) -> Vec<rig::message::Mess
                     content: OneOrMany::one(UserContent::ToolResult(result)),
                 });
             }
+            TranscriptStep::ToolApproval { .. } => {}
         }
     }
 
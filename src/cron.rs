@@ -4,4 +4,4 @@ pub mod scheduler;
 pub mod store;
 
 pub use scheduler::{CronConfig, CronContext, Scheduler};
-pub use store::{CronExecutionEntry, CronExecutionStats, CronStore};
+pub use store::{CronExecutionEntry, CronExecutionStats, CronJobState, CronStore};
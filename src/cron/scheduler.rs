@@ -8,8 +8,9 @@
 use crate::agent::channel::Channel;
 use crate::cron::store::{CronExecutionRecord, CronStore};
 use crate::error::Result;
-use crate::messaging::MessagingManager;
 use crate::messaging::target::{BroadcastTarget, parse_delivery_target};
+use crate::messaging::{MessagingManager, ProactiveDelivery};
+use crate::prompts::engine::CronRunContext;
 use crate::{AgentDeps, InboundMessage, MessageContent, OutboundResponse, RoutedResponse};
 use chrono::Timelike;
 use chrono_tz::Tz;
@@ -32,6 +33,9 @@ pub struct CronJob {
     pub active_hours: Option<(u8, u8)>,
     pub enabled: bool,
     pub run_once: bool,
+    /// Inject the job's state document and recent results into each run and
+    /// store the state block the run writes back.
+    pub stateful: bool,
     pub consecutive_failures: u32,
     pub next_run_at: Option<chrono::DateTime<chrono::Utc>>,
     /// Maximum wall-clock seconds to wait for the job to complete.
@@ -55,6 +59,9 @@ pub struct CronConfig {
     pub enabled: bool,
     #[serde(default)]
     pub run_once: bool,
+    /// Carry a rolling state document and recent results across runs.
+    #[serde(default)]
+    pub stateful: bool,
     #[serde(default)]
     pub next_run_at: Option<String>,
     /// Maximum wall-clock seconds to wait for the job to complete.
@@ -86,6 +93,18 @@ pub struct CronContext {
 
 const MAX_CONSECUTIVE_FAILURES: u32 = 3;
 
+/// Upper bound on the state document a stateful job can write back.
+const MAX_CRON_STATE_CHARS: usize = 4000;
+
+/// Number of previous results injected into a stateful run.
+const CRON_STATE_PREVIOUS_RUNS: i64 = 3;
+
+/// Per-result cap when injecting previous results.
+const CRON_STATE_RESULT_CHARS: usize = 1500;
+
+const CRON_STATE_OPEN_TAG: &str = "<cron_state>";
+const CRON_STATE_CLOSE_TAG: &str = "</cron_state>";
+
 /// RAII guard that clears an `AtomicBool` on drop, ensuring the flag is
 /// released even if the holding task panics.
 struct ExecutionGuard(Arc<std::sync::atomic::AtomicBool>);
@@ -712,6 +731,20 @@ impl Scheduler {
         }
     }
 
+    /// Trigger a cron job without waiting for the run to finish.
+    ///
+    /// Channels use this for command-triggered runs; a cron run drives its
+    /// own channel, so awaiting it from a channel turn would block that turn.
+    pub fn trigger_in_background(self: &Arc<Self>, job_id: impl Into<String>) {
+        let scheduler = self.clone();
+        let job_id = job_id.into();
+        tokio::spawn(async move {
+            if let Err(error) = scheduler.trigger_now(&job_id).await {
+                tracing::warn!(%error, cron_id = %job_id, "background cron trigger failed");
+            }
+        });
+    }
+
     /// Update a job's enabled state and manage its timer accordingly.
     ///
     /// Handles three cases:
@@ -819,6 +852,7 @@ fn cron_job_from_config(config: &CronConfig) -> Result<CronJob> {
         active_hours: normalize_active_hours(config.active_hours),
         enabled: config.enabled,
         run_once: config.run_once,
+        stateful: config.stateful,
         consecutive_failures: 0,
         next_run_at: config.next_run_at.as_deref().and_then(parse_cron_timestamp),
         timeout_secs: config.timeout_secs,
@@ -887,7 +921,7 @@ fn current_hour_and_timezone(context: &CronContext, cron_id: &str) -> (u8, Strin
     }
 }
 
-fn hour_in_active_window(current_hour: u8, start_hour: u8, end_hour: u8) -> bool {
+pub(crate) fn hour_in_active_window(current_hour: u8, start_hour: u8, end_hour: u8) -> bool {
     if start_hour == end_hour {
         return true;
     }
@@ -1300,6 +1334,11 @@ async fn run_cron_job(
         .split(':')
         .next()
         .unwrap_or("cron");
+    let prompt = if job.stateful {
+        build_stateful_cron_prompt(job, context).await
+    } else {
+        job.prompt.clone()
+    };
     let message = InboundMessage {
         id: uuid::Uuid::new_v4().to_string(),
         source: source_adapter.into(),
@@ -1307,7 +1346,7 @@ async fn run_cron_job(
         conversation_id: format!("cron:{}", job.id),
         sender_id: "system".into(),
         agent_id: Some(context.deps.agent_id.clone()),
-        content: MessageContent::Text(job.prompt.clone()),
+        content: MessageContent::Text(prompt),
         timestamp: chrono::Utc::now(),
         metadata: HashMap::new(),
         formatted_author: None,
@@ -1441,18 +1480,54 @@ async fn run_cron_job(
         }
     };
 
+    // Stateful jobs write their state back in a tagged block that is never delivered.
+    let delivery_response = if job.stateful {
+        delivery_response.and_then(|response| {
+            let (response, state) = extract_cron_state(response);
+            if let Some(state) = state {
+                persist_cron_state(context, &job.id, state);
+            }
+            response
+        })
+    } else {
+        delivery_response
+    };
+
     // Deliver result to target (only if there's something to say)
     if let Some(response) = delivery_response {
         let summary = cron_response_summary(&response);
-        if let Err(error) = context
+        let delivery = context
             .messaging_manager
             .broadcast_proactive(
                 &job.delivery_target.adapter,
                 &job.delivery_target.target,
                 response,
             )
-            .await
-        {
+            .await;
+        if let Ok(ProactiveDelivery::Queued { outbox_id }) = &delivery {
+            // The run itself succeeded and the outbox owns delivery from here,
+            // so this doesn't count against the circuit breaker.
+            tracing::warn!(
+                cron_id = %job.id,
+                target = %job.delivery_target,
+                %outbox_id,
+                "cron result queued in outbox for retry"
+            );
+            persist_cron_execution(
+                context,
+                &job.id,
+                CronExecutionRecord {
+                    execution_succeeded: true,
+                    delivery_attempted: true,
+                    delivery_succeeded: Some(false),
+                    result_summary: summary,
+                    execution_error: None,
+                    delivery_error: Some(format!("queued in outbox {outbox_id} for retry")),
+                },
+            );
+            return Ok(());
+        }
+        if let Err(error) = delivery {
             tracing::error!(
                 cron_id = %job.id,
                 target = %job.delivery_target,
@@ -1510,6 +1585,167 @@ async fn run_cron_job(
     Ok(())
 }
 
+/// Wrap a stateful job's prompt with its saved state and recent results.
+///
+/// Falls back to the bare prompt if the state cannot be loaded or rendered so
+/// the run still happens.
+async fn build_stateful_cron_prompt(job: &CronJob, context: &CronContext) -> String {
+    let state = match context.store.load_state(&job.id).await {
+        Ok(state) => state,
+        Err(error) => {
+            tracing::warn!(cron_id = %job.id, %error, "failed to load cron job state");
+            None
+        }
+    };
+    let previous_runs = match context
+        .store
+        .load_executions(&job.id, CRON_STATE_PREVIOUS_RUNS)
+        .await
+    {
+        Ok(entries) => entries
+            .into_iter()
+            .rev()
+            .map(cron_run_context)
+            .collect::<Vec<_>>(),
+        Err(error) => {
+            tracing::warn!(cron_id = %job.id, %error, "failed to load previous cron results");
+            Vec::new()
+        }
+    };
+
+    let prompt_engine = context.deps.runtime_config.prompts.load();
+    match prompt_engine.render_system_cron_state(
+        &job.prompt,
+        state.as_ref().map(|state| state.state.as_str()),
+        state.as_ref().map(|state| state.updated_at.as_str()),
+        &previous_runs,
+        MAX_CRON_STATE_CHARS,
+    ) {
+        Ok(prompt) => prompt,
+        Err(error) => {
+            tracing::warn!(cron_id = %job.id, %error, "failed to render stateful cron prompt");
+            job.prompt.clone()
+        }
+    }
+}
+
+fn cron_run_context(entry: crate::cron::CronExecutionEntry) -> CronRunContext {
+    let (outcome, summary) = if !entry.execution_succeeded {
+        ("failed", entry.execution_error)
+    } else if !entry.delivery_attempted {
+        ("no output", None)
+    } else if entry.delivery_succeeded == Some(true) {
+        ("delivered", entry.result_summary)
+    } else {
+        ("delivery failed", entry.result_summary)
+    };
+
+    CronRunContext {
+        executed_at: entry.executed_at,
+        outcome: outcome.to_string(),
+        summary: summary
+            .map(|summary| truncate_chars(&summary, CRON_STATE_RESULT_CHARS))
+            .unwrap_or_default(),
+    }
+}
+
+/// Split the `<cron_state>` block out of a stateful job's response.
+///
+/// Returns the response with the block removed (`None` if nothing is left to
+/// deliver) and the block's trimmed contents, if present.
+fn extract_cron_state(response: OutboundResponse) -> (Option<OutboundResponse>, Option<String>) {
+    match response {
+        OutboundResponse::Text(text) => {
+            let (text, state) = split_cron_state(&text);
+            (
+                normalize_cron_delivery_response(OutboundResponse::Text(text)),
+                state,
+            )
+        }
+        OutboundResponse::RichMessage {
+            text,
+            blocks,
+            cards,
+            interactive_elements,
+            poll,
+        } => {
+            let (text, state) = split_cron_state(&text);
+            let response = normalize_cron_delivery_response(OutboundResponse::RichMessage {
+                text,
+                blocks,
+                cards,
+                interactive_elements,
+                poll,
+            });
+            (response, state)
+        }
+        OutboundResponse::File {
+            filename,
+            data,
+            mime_type,
+            caption,
+        } => {
+            let (caption, state) = match caption {
+                Some(caption) => {
+                    let (caption, state) = split_cron_state(&caption);
+                    (Some(caption).filter(|caption| !caption.is_empty()), state)
+                }
+                None => (None, None),
+            };
+            let response = OutboundResponse::File {
+                filename,
+                data,
+                mime_type,
+                caption,
+            };
+            (Some(response), state)
+        }
+        other => (Some(other), None),
+    }
+}
+
+/// Remove the last `<cron_state>` block from `text`. An unclosed block runs to
+/// the end of the text.
+fn split_cron_state(text: &str) -> (String, Option<String>) {
+    let Some(start) = text.rfind(CRON_STATE_OPEN_TAG) else {
+        return (text.to_string(), None);
+    };
+    let body_start = start + CRON_STATE_OPEN_TAG.len();
+    let (body, rest) = match text[body_start..].find(CRON_STATE_CLOSE_TAG) {
+        Some(end) => (
+            &text[body_start..body_start + end],
+            &text[body_start + end + CRON_STATE_CLOSE_TAG.len()..],
+        ),
+        None => (&text[body_start..], ""),
+    };
+
+    let remaining = [text[..start].trim(), rest.trim()]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    let state = body.trim();
+    let state = (!state.is_empty()).then(|| truncate_chars(state, MAX_CRON_STATE_CHARS));
+    (remaining, state)
+}
+
+fn truncate_chars(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((index, _)) => text[..index].to_string(),
+        None => text.to_string(),
+    }
+}
+
+fn persist_cron_state(context: &CronContext, cron_id: &str, state: String) {
+    let store = context.store.clone();
+    let cron_id = cron_id.to_string();
+    tokio::spawn(async move {
+        if let Err(error) = store.save_state(&cron_id, &state).await {
+            tracing::warn!(cron_id = %cron_id, %error, "failed to save cron job state");
+        }
+    });
+}
+
 fn persist_cron_execution(context: &CronContext, cron_id: &str, record: CronExecutionRecord) {
     #[cfg(feature = "metrics")]
     record_cron_metrics(&context.deps.agent_id, cron_id, &record);
@@ -1691,7 +1927,7 @@ fn cron_response_summary(response: &OutboundResponse) -> Option<String> {
 mod tests {
     use super::{
         CronConfig, CronJob, CronResponseWaitOutcome, CronRunError, await_cron_delivery_response,
-        cron_response_summary, hour_in_active_window, normalize_active_hours,
+        cron_response_summary, extract_cron_state, hour_in_active_window, normalize_active_hours,
         normalize_cron_delivery_response, set_job_enabled_state, sync_job_from_store,
     };
     use crate::cron::store::CronStore;
@@ -1890,6 +2126,7 @@ mod tests {
             active_hours: None,
             enabled: true,
             run_once: false,
+            stateful: false,
             consecutive_failures,
             next_run_at,
             timeout_secs: None,
@@ -1916,6 +2153,7 @@ mod tests {
                 active_hours: None,
                 enabled: true,
                 run_once: false,
+                stateful: false,
                 next_run_at: Some(expected_text.clone()),
                 timeout_secs: None,
             })
@@ -1958,6 +2196,42 @@ mod tests {
         assert_eq!(execution_error.failure_class(), "execution_error");
     }
 
+    #[test]
+    fn cron_state_block_is_stripped_from_delivery() {
+        let (response, state) = extract_cron_state(OutboundResponse::Text(
+            "2 new issues since yesterday.\n\n<cron_state>\n- #423 open\n- #424 open\n</cron_state>"
+                .to_string(),
+        ));
+
+        assert!(matches!(
+            response,
+            Some(OutboundResponse::Text(ref text)) if text == "2 new issues since yesterday."
+        ));
+        assert_eq!(state.as_deref(), Some("- #423 open\n- #424 open"));
+    }
+
+    #[test]
+    fn cron_state_only_response_skips_delivery() {
+        let (response, state) = extract_cron_state(OutboundResponse::Text(
+            "<cron_state>nothing changed</cron_state>".to_string(),
+        ));
+
+        assert!(response.is_none());
+        assert_eq!(state.as_deref(), Some("nothing changed"));
+    }
+
+    #[test]
+    fn cron_state_missing_block_leaves_response_untouched() {
+        let (response, state) =
+            extract_cron_state(OutboundResponse::Text("plain report".to_string()));
+
+        assert!(matches!(
+            response,
+            Some(OutboundResponse::Text(ref text)) if text == "plain report"
+        ));
+        assert!(state.is_none());
+    }
+
     #[tokio::test]
     async fn set_job_enabled_state_updates_in_memory_flag() {
         let jobs = Arc::new(RwLock::new(HashMap::from([(
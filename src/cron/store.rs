@@ -72,6 +72,10 @@ fn row_to_cron_config(row: SqliteRow) -> Result<CronConfig> {
             .try_get::<i64, _>("run_once")
             .context("decode cron_jobs.run_once")?
             != 0,
+        stateful: row
+            .try_get::<i64, _>("stateful")
+            .context("decode cron_jobs.stateful")?
+            != 0,
         next_run_at: row
             .try_get::<Option<String>, _>("next_run_at")
             .ok()
@@ -148,8 +152,8 @@ impl CronStore {
 
         sqlx::query(
             r#"
-            INSERT INTO cron_jobs (id, prompt, cron_expr, interval_secs, delivery_target, active_start_hour, active_end_hour, enabled, run_once, next_run_at, timeout_secs)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO cron_jobs (id, prompt, cron_expr, interval_secs, delivery_target, active_start_hour, active_end_hour, enabled, run_once, stateful, next_run_at, timeout_secs)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             ON CONFLICT(id) DO UPDATE SET
                 prompt = excluded.prompt,
                 cron_expr = excluded.cron_expr,
@@ -159,6 +163,7 @@ impl CronStore {
                 active_end_hour = excluded.active_end_hour,
                 enabled = excluded.enabled,
                 run_once = excluded.run_once,
+                stateful = excluded.stateful,
                 next_run_at = CASE
                     WHEN NOT (cron_expr IS excluded.cron_expr)
                         OR interval_secs != excluded.interval_secs
@@ -177,6 +182,7 @@ impl CronStore {
         .bind(active_end)
         .bind(config.enabled as i64)
         .bind(config.run_once as i64)
+        .bind(config.stateful as i64)
         .bind(normalized_next_run_at.as_deref())
         .bind(config.timeout_secs.map(|t| t as i64))
         .execute(&self.pool)
@@ -190,7 +196,7 @@ impl CronStore {
     pub async fn load_all(&self) -> Result<Vec<CronConfig>> {
         let rows = sqlx::query(
             r#"
-            SELECT id, prompt, cron_expr, interval_secs, delivery_target, active_start_hour, active_end_hour, enabled, run_once, next_run_at, timeout_secs
+            SELECT id, prompt, cron_expr, interval_secs, delivery_target, active_start_hour, active_end_hour, enabled, run_once, stateful, next_run_at, timeout_secs
             FROM cron_jobs
             WHERE enabled = 1
             ORDER BY created_at ASC
@@ -223,7 +229,7 @@ impl CronStore {
     pub async fn load(&self, id: &str) -> Result<Option<CronConfig>> {
         let row = sqlx::query(
             r#"
-            SELECT id, prompt, cron_expr, interval_secs, delivery_target, active_start_hour, active_end_hour, enabled, run_once, next_run_at, timeout_secs
+            SELECT id, prompt, cron_expr, interval_secs, delivery_target, active_start_hour, active_end_hour, enabled, run_once, stateful, next_run_at, timeout_secs
             FROM cron_jobs
             WHERE id = ?
             "#,
@@ -337,11 +343,45 @@ impl CronStore {
         Ok(())
     }
 
+    /// Load the state document a stateful job last wrote back.
+    pub async fn load_state(&self, cron_id: &str) -> Result<Option<CronJobState>> {
+        let row = sqlx::query("SELECT state, updated_at FROM cron_job_state WHERE cron_id = ?")
+            .bind(cron_id)
+            .fetch_optional(&self.pool)
+            .await
+            .context("failed to load cron job state")?;
+
+        Ok(row.map(|row| CronJobState {
+            state: row.try_get("state").unwrap_or_default(),
+            updated_at: row.try_get("updated_at").unwrap_or_default(),
+        }))
+    }
+
+    /// Replace the state document for a stateful job.
+    pub async fn save_state(&self, cron_id: &str, state: &str) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO cron_job_state (cron_id, state, updated_at)
+            VALUES (?, ?, CURRENT_TIMESTAMP)
+            ON CONFLICT(cron_id) DO UPDATE SET
+                state = excluded.state,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(cron_id)
+        .bind(state)
+        .execute(&self.pool)
+        .await
+        .context("failed to save cron job state")?;
+
+        Ok(())
+    }
+
     /// Load all cron job configurations (including disabled).
     pub async fn load_all_unfiltered(&self) -> Result<Vec<CronConfig>> {
         let rows = sqlx::query(
             r#"
-            SELECT id, prompt, cron_expr, interval_secs, delivery_target, active_start_hour, active_end_hour, enabled, run_once, next_run_at, timeout_secs
+            SELECT id, prompt, cron_expr, interval_secs, delivery_target, active_start_hour, active_end_hour, enabled, run_once, stateful, next_run_at, timeout_secs
             FROM cron_jobs
             ORDER BY created_at ASC
             "#,
@@ -519,6 +559,13 @@ pub struct CronExecutionEntry {
     pub delivery_error: Option<String>,
 }
 
+/// Rolling state document persisted between runs of a stateful cron job.
+#[derive(Debug, Clone, serde::Serialize, utoipa::ToSchema)]
+pub struct CronJobState {
+    pub state: String,
+    pub updated_at: String,
+}
+
 /// Execution statistics for a cron job.
 #[derive(Debug, Clone, serde::Serialize, Default)]
 pub struct CronExecutionStats {
@@ -559,6 +606,7 @@ mod tests {
                 active_hours: None,
                 enabled: true,
                 run_once: false,
+                stateful: false,
                 next_run_at: None,
                 timeout_secs: None,
             })
@@ -681,6 +729,69 @@ mod tests {
         assert_eq!(execution.cron_id.as_deref(), Some("daily-digest"));
     }
 
+    #[tokio::test]
+    async fn stateful_jobs_round_trip_flag_and_state() {
+        let store = setup_store().await;
+        store
+            .save(&CronConfig {
+                id: "issue-tracker".to_string(),
+                prompt: "track the open issues".to_string(),
+                cron_expr: Some("0 9 * * *".to_string()),
+                interval_secs: 3600,
+                delivery_target: "discord:123456789".to_string(),
+                active_hours: None,
+                enabled: true,
+                run_once: false,
+                stateful: true,
+                next_run_at: None,
+                timeout_secs: None,
+            })
+            .await
+            .expect("save stateful cron job");
+
+        let loaded = store
+            .load("issue-tracker")
+            .await
+            .expect("load cron job")
+            .expect("cron job exists");
+        assert!(loaded.stateful);
+        assert!(
+            store
+                .load_state("issue-tracker")
+                .await
+                .expect("load empty state")
+                .is_none()
+        );
+
+        store
+            .save_state("issue-tracker", "- #423 open")
+            .await
+            .expect("save state");
+        store
+            .save_state("issue-tracker", "- #423 closed")
+            .await
+            .expect("replace state");
+
+        let state = store
+            .load_state("issue-tracker")
+            .await
+            .expect("load state")
+            .expect("state exists");
+        assert_eq!(state.state, "- #423 closed");
+
+        store
+            .delete("issue-tracker")
+            .await
+            .expect("delete cron job");
+        assert!(
+            store
+                .load_state("issue-tracker")
+                .await
+                .expect("load state after delete")
+                .is_none()
+        );
+    }
+
     #[tokio::test]
     async fn save_normalizes_next_run_at_to_canonical_rfc3339() {
         let store = setup_store().await;
@@ -696,6 +807,7 @@ mod tests {
                 active_hours: None,
                 enabled: true,
                 run_once: false,
+                stateful: false,
                 next_run_at: Some(next_run_at.to_string()),
                 timeout_secs: None,
             })
@@ -725,6 +837,7 @@ mod tests {
                 active_hours: None,
                 enabled: true,
                 run_once: false,
+                stateful: false,
                 next_run_at: Some("not-a-timestamp".to_string()),
                 timeout_secs: None,
             })
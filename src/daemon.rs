@@ -1,5 +1,6 @@
 //! Process daemonization and IPC for background operation.
 
+use crate::api::LogBuffer;
 use crate::config::{Config, TelemetryConfig};
 
 use anyhow::{Context as _, anyhow};
@@ -128,11 +129,13 @@ pub fn daemonize(paths: &DaemonPaths) -> anyhow::Result<()> {
 ///
 /// Returns an `SdkTracerProvider` if OTLP export is configured. The caller must
 /// hold onto it for the process lifetime and call `.shutdown()` before exit so
-/// the batch exporter flushes buffered spans.
+/// the batch exporter flushes buffered spans. Info and above are also captured
+/// into `log_buffer` for the live log viewer.
 pub fn init_background_tracing(
     paths: &DaemonPaths,
     debug: bool,
     telemetry: &TelemetryConfig,
+    log_buffer: &LogBuffer,
 ) -> Option<SdkTracerProvider> {
     let file_appender = tracing_appender::rolling::daily(&paths.log_dir, "spacebot.log");
     let (non_blocking, _guard) = tracing_appender::non_blocking(file_appender);
@@ -175,6 +178,7 @@ pub fn init_background_tracing(
             tracing_subscriber::registry()
                 .with(filter)
                 .with(fmt_layer)
+                .with(log_buffer.layer())
                 .with(tracing_opentelemetry::layer().with_tracer(tracer))
                 .init();
             Some(provider)
@@ -183,6 +187,7 @@ pub fn init_background_tracing(
             tracing_subscriber::registry()
                 .with(filter)
                 .with(fmt_layer)
+                .with(log_buffer.layer())
                 .init();
             None
         }
@@ -191,10 +196,12 @@ pub fn init_background_tracing(
 
 /// Initialize tracing for foreground (terminal) mode.
 ///
-/// Returns an `SdkTracerProvider` if OTLP export is configured.
+/// Returns an `SdkTracerProvider` if OTLP export is configured. Info and above
+/// are also captured into `log_buffer` for the live log viewer.
 pub fn init_foreground_tracing(
     debug: bool,
     telemetry: &TelemetryConfig,
+    log_buffer: &LogBuffer,
 ) -> Option<SdkTracerProvider> {
     let field_formatter = format::debug_fn(|writer, field, value| {
         let field_name = field.name();
@@ -230,6 +237,7 @@ pub fn init_foreground_tracing(
             tracing_subscriber::registry()
                 .with(filter)
                 .with(fmt_layer)
+                .with(log_buffer.layer())
                 .with(tracing_opentelemetry::layer().with_tracer(tracer))
                 .init();
             Some(provider)
@@ -238,6 +246,7 @@ pub fn init_foreground_tracing(
             tracing_subscriber::registry()
                 .with(filter)
                 .with(fmt_layer)
+                .with(log_buffer.layer())
                 .init();
             None
         }
@@ -94,3 +94,55 @@ pub async fn connect_global_tasks(data_dir: &Path) -> Result<SqlitePool> {
 
     Ok(pool)
 }
+
+/// Connect to the instance-level messaging outbox database and run its migrations.
+///
+/// The outbox lives at `{instance_dir}/data/outbox.db` and persists proactive
+/// sends until they are delivered, so they survive restarts and outages.
+pub async fn connect_outbox(data_dir: &Path) -> Result<SqlitePool> {
+    std::fs::create_dir_all(data_dir)
+        .with_context(|| format!("failed to create data directory: {}", data_dir.display()))?;
+
+    let db_path = data_dir.join("outbox.db");
+    let url = format!("sqlite:{}?mode=rwc", db_path.display());
+
+    let pool = SqlitePool::connect(&url).await.with_context(|| {
+        format!(
+            "failed to connect to outbox database: {}",
+            db_path.display()
+        )
+    })?;
+
+    sqlx::migrate!("./migrations/outbox")
+        .run(&pool)
+        .await
+        .with_context(|| "failed to run outbox database migrations")?;
+
+    Ok(pool)
+}
+
+/// Connect to the instance-level API access database and run its migrations.
+///
+/// The database lives at `{instance_dir}/data/api.db` and holds named API
+/// tokens and the append-only audit log of mutating API calls.
+pub async fn connect_api_access(data_dir: &Path) -> Result<SqlitePool> {
+    std::fs::create_dir_all(data_dir)
+        .with_context(|| format!("failed to create data directory: {}", data_dir.display()))?;
+
+    let db_path = data_dir.join("api.db");
+    let url = format!("sqlite:{}?mode=rwc", db_path.display());
+
+    let pool = SqlitePool::connect(&url).await.with_context(|| {
+        format!(
+            "failed to connect to API access database: {}",
+            db_path.display()
+        )
+    })?;
+
+    sqlx::migrate!("./migrations/api")
+        .run(&pool)
+        .await
+        .with_context(|| "failed to run API access database migrations")?;
+
+    Ok(pool)
+}
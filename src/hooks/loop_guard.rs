@@ -5,7 +5,7 @@ use crate::ProcessType;
 use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet, VecDeque};
 
-const POLL_TOOLS: &[&str] = &["shell"];
+const POLL_TOOLS: &[&str] = &["shell", "shell_input"];
 const HISTORY_CAPACITY: usize = 30;
 
 /// Tools that read changing state and are expected to be called repeatedly with
@@ -1,6 +1,12 @@
 //! SpacebotHook: Prompt hook for channels, branches, and workers.
 
+use crate::agent::tool_approval::{
+    ToolApprovalDecision, ToolApprovalRecord, ToolApprovalRegistry, ToolApprovalResolution,
+    redact_args,
+};
+use crate::config::RuntimeConfig;
 use crate::hooks::loop_guard::{LoopGuard, LoopGuardConfig, LoopGuardVerdict};
+use crate::messaging::streaming::ReplyStream;
 use crate::tools::{MemoryPersistenceContractState, MemoryPersistenceTerminalOutcome};
 use crate::{AgentId, ChannelId, ProcessEvent, ProcessId, ProcessType};
 use futures::StreamExt;
@@ -68,8 +74,30 @@ pub struct SpacebotHook {
     memory_persistence_contract: Option<Arc<MemoryPersistenceContractState>>,
     reply_tool_delta_state:
         std::sync::Arc<std::sync::Mutex<std::collections::HashMap<String, ReplyToolDeltaState>>>,
+    /// Set by the channel for turns with streaming enabled. Partial `reply`
+    /// content is previewed through it as the model generates it.
+    reply_stream: std::sync::Arc<std::sync::Mutex<Option<ReplyStream>>>,
+    /// Set for built-in workers. Pauses calls matching the agent's
+    /// `[tool_approval]` policy until someone decides on them.
+    tool_approval: Option<ToolApprovalGate>,
+    /// Approval decisions made during this run, kept for the worker transcript.
+    tool_approval_records: std::sync::Arc<std::sync::Mutex<Vec<ToolApprovalRecord>>>,
+}
+
+#[derive(Clone)]
+struct ToolApprovalGate {
+    registry: Arc<ToolApprovalRegistry>,
+    runtime_config: Arc<RuntimeConfig>,
 }
 
+/// `reply` arguments that turn the reply into something other than plain text.
+const REPLY_NON_TEXT_ARGS: &[&str] = &[
+    "\"thread_name\"",
+    "\"cards\"",
+    "\"interactive_elements\"",
+    "\"poll\"",
+];
+
 #[derive(Clone, Debug, Default)]
 struct ReplyToolDeltaState {
     tool_name: Option<String>,
@@ -132,6 +160,9 @@ impl SpacebotHook {
             reply_tool_delta_state: std::sync::Arc::new(std::sync::Mutex::new(
                 std::collections::HashMap::new(),
             )),
+            reply_stream: std::sync::Arc::new(std::sync::Mutex::new(None)),
+            tool_approval: None,
+            tool_approval_records: std::sync::Arc::new(std::sync::Mutex::new(Vec::new())),
         }
     }
 
@@ -159,6 +190,35 @@ impl SpacebotHook {
         self
     }
 
+    /// Gate tool calls on the approval policy in `runtime_config`, parking
+    /// matching calls in `registry` until they are resolved or time out.
+    pub fn with_tool_approval(
+        mut self,
+        registry: Arc<ToolApprovalRegistry>,
+        runtime_config: Arc<RuntimeConfig>,
+    ) -> Self {
+        self.tool_approval = Some(ToolApprovalGate {
+            registry,
+            runtime_config,
+        });
+        self
+    }
+
+    /// Approval decisions made so far, oldest first.
+    pub fn tool_approval_records(&self) -> Vec<ToolApprovalRecord> {
+        self.tool_approval_records
+            .lock()
+            .map(|records| records.clone())
+            .unwrap_or_default()
+    }
+
+    /// Preview `reply` tool content through `stream` until cleared with `None`.
+    pub fn set_reply_stream(&self, stream: Option<ReplyStream>) {
+        if let Ok(mut slot) = self.reply_stream.lock() {
+            *slot = stream;
+        }
+    }
+
     /// Return the current tool nudge policy for this hook.
     pub fn tool_nudge_policy(&self) -> ToolNudgePolicy {
         self.tool_nudge_policy
@@ -718,6 +778,110 @@ impl SpacebotHook {
         }
     }
 
+    /// Pause a call that matches the approval policy until it is decided.
+    ///
+    /// Returns the skip reason for denied and timed-out calls, or `None` when
+    /// the call may run (approved, or not gated at all).
+    async fn await_tool_approval(
+        &self,
+        tool_name: &str,
+        tool_call_id: Option<String>,
+        args: &str,
+    ) -> Option<String> {
+        let gate = self.tool_approval.as_ref()?;
+        let ProcessId::Worker(worker_id) = self.process_id else {
+            return None;
+        };
+        let policy = gate.runtime_config.tool_approval.load();
+        let sandbox_mode = gate.runtime_config.sandbox.load().mode;
+        policy.matching_rule(tool_name, args, sandbox_mode)?;
+
+        // Redact once so the prompt, the conversation log, the dashboard, and
+        // the transcript record all see the same masked arguments.
+        let redacted_args = redact_args(tool_name, args);
+        let capped_args = crate::tools::truncate_output(&redacted_args, 2_000);
+        let (request, mut decision_rx) =
+            gate.registry
+                .request(worker_id, self.channel_id.as_ref(), tool_name, &capped_args);
+        tracing::info!(
+            process_id = %self.process_id,
+            tool_name = %tool_name,
+            approval_id = %request.approval_id,
+            "tool call waiting for approval"
+        );
+        self.event_tx
+            .send(ProcessEvent::ToolApprovalRequested {
+                agent_id: self.agent_id.clone(),
+                worker_id,
+                channel_id: self.channel_id.clone(),
+                approval_id: request.approval_id.clone(),
+                tool_name: tool_name.to_string(),
+                args: capped_args,
+                timeout_secs: policy.timeout_secs,
+            })
+            .ok();
+
+        let timeout = std::time::Duration::from_secs(policy.timeout_secs);
+        let resolution = match tokio::time::timeout(timeout, &mut decision_rx).await {
+            Ok(Ok(resolution)) => resolution,
+            _ => {
+                // A decision can land between the timeout firing and the
+                // entry being expired; honour it if so.
+                gate.registry.expire(&request.approval_id);
+                decision_rx.try_recv().unwrap_or(ToolApprovalResolution {
+                    decision: ToolApprovalDecision::TimedOut,
+                    resolved_by: None,
+                })
+            }
+        };
+
+        tracing::info!(
+            process_id = %self.process_id,
+            tool_name = %tool_name,
+            approval_id = %request.approval_id,
+            decision = %resolution.decision,
+            "tool approval resolved"
+        );
+        self.event_tx
+            .send(ProcessEvent::ToolApprovalResolved {
+                agent_id: self.agent_id.clone(),
+                worker_id,
+                channel_id: self.channel_id.clone(),
+                approval_id: request.approval_id,
+                tool_name: tool_name.to_string(),
+                decision: resolution.decision,
+                resolved_by: resolution.resolved_by.clone(),
+            })
+            .ok();
+        if let Ok(mut records) = self.tool_approval_records.lock() {
+            records.push(ToolApprovalRecord {
+                call_id: tool_call_id,
+                tool_name: tool_name.to_string(),
+                args: redacted_args,
+                decision: resolution.decision,
+                resolved_by: resolution.resolved_by.clone(),
+            });
+        }
+
+        let by = resolution
+            .resolved_by
+            .map(|name| format!(" by {name}"))
+            .unwrap_or_default();
+        match resolution.decision {
+            ToolApprovalDecision::Approved => None,
+            ToolApprovalDecision::Denied => Some(format!(
+                "The {tool_name} call was denied{by} and did not run. Do not retry it; \
+                 continue another way or report that you were blocked."
+            )),
+            ToolApprovalDecision::TimedOut => Some(format!(
+                "The {tool_name} call needed approval and nobody responded within {}s, \
+                 so it did not run. Do not retry it; continue another way or report \
+                 that you were blocked.",
+                policy.timeout_secs
+            )),
+        }
+    }
+
     /// Scan content for potential secret leaks, including encoded forms.
     ///
     /// Delegates to the shared implementation in `secrets::scrub`.
@@ -1109,48 +1273,63 @@ where
             return HookAction::Continue;
         };
 
-        let mut guard = match self.reply_tool_delta_state.lock() {
-            Ok(guard) => guard,
-            Err(_) => return HookAction::Continue,
-        };
+        // Keep the lock scoped so it is released before any await below.
+        let (delta, content, plain_reply) = {
+            let mut guard = match self.reply_tool_delta_state.lock() {
+                Ok(guard) => guard,
+                Err(_) => return HookAction::Continue,
+            };
 
-        let state = guard
-            .entry(internal_call_id.to_string())
-            .or_insert_with(ReplyToolDeltaState::default);
+            let state = guard
+                .entry(internal_call_id.to_string())
+                .or_insert_with(ReplyToolDeltaState::default);
 
-        if let Some(tool_name) = tool_name {
-            state.tool_name = Some(tool_name.to_string());
-        }
+            if let Some(tool_name) = tool_name {
+                state.tool_name = Some(tool_name.to_string());
+            }
 
-        if state.tool_name.as_deref() != Some("reply") {
-            return HookAction::Continue;
-        }
+            if state.tool_name.as_deref() != Some("reply") {
+                return HookAction::Continue;
+            }
 
-        state.raw_args.push_str(tool_call_delta);
-        let Some(content) = Self::extract_partial_reply_content(&state.raw_args) else {
-            return HookAction::Continue;
-        };
+            state.raw_args.push_str(tool_call_delta);
+            let Some(content) = Self::extract_partial_reply_content(&state.raw_args) else {
+                return HookAction::Continue;
+            };
 
-        if !content.starts_with(&state.emitted_content) {
-            return HookAction::Continue;
-        }
+            if !content.starts_with(&state.emitted_content) {
+                return HookAction::Continue;
+            }
 
-        let delta = &content[state.emitted_content.len()..];
-        if delta.is_empty() {
-            return HookAction::Continue;
-        }
+            let delta = content[state.emitted_content.len()..].to_string();
+            if delta.is_empty() {
+                return HookAction::Continue;
+            }
+
+            state.emitted_content = content.clone();
+            // Cards, polls and threads can't be previewed as plain text; leave
+            // those replies to the tool.
+            let plain_reply = !REPLY_NON_TEXT_ARGS
+                .iter()
+                .any(|key| state.raw_args.contains(key));
+            (delta, content, plain_reply)
+        };
 
-        state.emitted_content = content.clone();
         self.event_tx
             .send(ProcessEvent::TextDelta {
                 agent_id: self.agent_id.clone(),
                 process_id: self.process_id.clone(),
                 channel_id: Some(channel_id),
-                text_delta: delta.to_string(),
-                aggregated_text: content,
+                text_delta: delta,
+                aggregated_text: content.clone(),
             })
             .ok();
 
+        let reply_stream = self.reply_stream.lock().ok().and_then(|slot| slot.clone());
+        if plain_reply && let Some(reply_stream) = reply_stream {
+            reply_stream.update(internal_call_id, &content).await;
+        }
+
         HookAction::Continue
     }
 
@@ -1165,7 +1344,7 @@ where
     async fn on_tool_call(
         &self,
         tool_name: &str,
-        _tool_call_id: Option<String>,
+        tool_call_id: Option<String>,
         _internal_call_id: &str,
         args: &str,
     ) -> ToolCallHookAction {
@@ -1216,6 +1395,13 @@ where
             };
         }
 
+        if let Some(reason) = self
+            .await_tool_approval(tool_name, tool_call_id, args)
+            .await
+        {
+            return ToolCallHookAction::Skip { reason };
+        }
+
         // Send event without blocking. Truncate args to keep broadcast payloads bounded.
         let capped_args = crate::tools::truncate_output(args, 2_000);
         let event = ProcessEvent::ToolStarted {
@@ -1446,6 +1632,75 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn tool_approval_never_exposes_secret_values() {
+        use crate::agent::tool_approval::{ToolApprovalDecision, ToolApprovalRegistry};
+        use crate::config::{RuntimeConfig, ToolApprovalConfig, ToolApprovalRule};
+
+        let instance_dir = tempfile::tempdir().unwrap();
+        let config = crate::config::Config::load_from_env(instance_dir.path()).unwrap();
+        let resolved = config.resolve_agents().into_iter().next().unwrap();
+        let runtime_config = Arc::new(RuntimeConfig::new(
+            instance_dir.path(),
+            &resolved,
+            &config.defaults,
+            crate::prompts::PromptEngine::new("en").unwrap(),
+            crate::identity::Identity::default(),
+            crate::skills::SkillSet::default(),
+        ));
+        runtime_config
+            .tool_approval
+            .store(Arc::new(ToolApprovalConfig {
+                rules: vec![ToolApprovalRule {
+                    tool: "secret_set".into(),
+                    args_pattern: None,
+                    sandbox: None,
+                }],
+                timeout_secs: 5,
+            }));
+
+        let registry = Arc::new(ToolApprovalRegistry::new());
+        let (event_tx, mut event_rx) = tokio::sync::broadcast::channel(8);
+        let hook = SpacebotHook::new(
+            std::sync::Arc::<str>::from("agent"),
+            ProcessId::Worker(uuid::Uuid::new_v4()),
+            ProcessType::Worker,
+            None,
+            event_tx,
+        )
+        .with_tool_approval(registry.clone(), runtime_config);
+
+        let approver = tokio::spawn(async move {
+            loop {
+                if let Ok(ProcessEvent::ToolApprovalRequested {
+                    approval_id, args, ..
+                }) = event_rx.recv().await
+                {
+                    registry
+                        .resolve(&approval_id, ToolApprovalDecision::Approved, None)
+                        .unwrap();
+                    return args;
+                }
+            }
+        });
+
+        let skip = hook
+            .await_tool_approval(
+                "secret_set",
+                Some("call_1".into()),
+                r#"{"name":"GH_TOKEN","value":"hunter2-very-secret"}"#,
+            )
+            .await;
+        assert!(skip.is_none());
+
+        let prompt_args = approver.await.unwrap();
+        assert!(prompt_args.contains("GH_TOKEN"));
+        assert!(!prompt_args.contains("hunter2"));
+        let records = hook.tool_approval_records();
+        assert_eq!(records.len(), 1);
+        assert!(!records[0].args.contains("hunter2"));
+    }
+
     #[tokio::test]
     async fn nudges_on_every_text_only_response_without_outcome() {
         let hook = make_hook().with_tool_nudge_policy(ToolNudgePolicy::Enabled);
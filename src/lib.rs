@@ -3,6 +3,7 @@
 pub mod agent;
 pub mod api;
 pub mod auth;
+pub mod commands;
 pub mod config;
 pub mod conversation;
 pub mod cron;
@@ -217,6 +218,17 @@ pub enum ProcessEvent {
         tool_name: String,
         result: String,
     },
+    /// One line of live output from a running tool (shell). Ephemeral: the
+    /// full output still arrives in `ToolCompleted`.
+    ToolOutput {
+        agent_id: AgentId,
+        process_id: ProcessId,
+        channel_id: Option<ChannelId>,
+        tool_name: String,
+        line: String,
+        /// "stdout" or "stderr".
+        stream: String,
+    },
     MemorySaved {
         agent_id: AgentId,
         memory_id: String,
@@ -243,6 +255,26 @@ pub enum ProcessEvent {
         description: String,
         patterns: Vec<String>,
     },
+    /// A built-in worker tool call is paused until someone approves or denies it.
+    ToolApprovalRequested {
+        agent_id: AgentId,
+        worker_id: WorkerId,
+        channel_id: Option<ChannelId>,
+        approval_id: String,
+        tool_name: String,
+        args: String,
+        timeout_secs: u64,
+    },
+    /// A paused tool call was approved, denied, or timed out.
+    ToolApprovalResolved {
+        agent_id: AgentId,
+        worker_id: WorkerId,
+        channel_id: Option<ChannelId>,
+        approval_id: String,
+        tool_name: String,
+        decision: agent::tool_approval::ToolApprovalDecision,
+        resolved_by: Option<String>,
+    },
     WorkerQuestion {
         agent_id: AgentId,
         worker_id: WorkerId,
@@ -414,6 +446,8 @@ pub struct AgentDeps {
     /// to surface human display names, roles, and descriptions in agent prompts.
     pub humans: Arc<arc_swap::ArcSwap<Vec<config::HumanDef>>>,
     pub process_control_registry: Arc<agent::process_control::ProcessControlRegistry>,
+    /// Worker tool calls waiting on a human approve/deny decision.
+    pub tool_approvals: Arc<agent::tool_approval::ToolApprovalRegistry>,
     /// Sender for injecting messages into channels from outside the normal
     /// inbound message flow (e.g. cross-agent task completion notifications).
     pub injection_tx: tokio::sync::mpsc::Sender<ChannelInjection>,
@@ -459,6 +493,12 @@ pub mod metadata_keys {
     pub const REPLY_TO_MESSAGE_ID: &str = "reply_to_message_id";
     /// Quoted reply text preview from the message being replied to.
     pub const REPLY_TO_TEXT: &str = "reply_to_text";
+    /// Platform group IDs the sender belongs to (Discord role IDs, Slack user
+    /// group IDs), as a JSON array of strings. Used for access role mapping.
+    pub const SENDER_GROUP_IDS: &str = "sender_group_ids";
+    /// Access role of the sender. Stamped by the router after binding
+    /// resolution; any adapter-supplied value is discarded.
+    pub const SENDER_ROLE: &str = "sender_role";
 }
 
 /// Inbound message from any messaging platform.
@@ -700,8 +740,12 @@ pub enum OutboundResponse {
         /// Unix epoch seconds when the message should be delivered.
         post_at: i64,
     },
+    /// Open a streamed reply preview. See [`messaging::streaming`].
     StreamStart,
+    /// The full reply text generated so far (a snapshot, not a delta). An
+    /// empty last chunk withdraws the reply.
     StreamChunk(String),
+    /// Finalize the preview with the text of the last chunk.
     StreamEnd,
     Status(StatusUpdate),
 }
@@ -1,10 +1,15 @@
 //! LLM provider management and routing.
 
 pub mod anthropic;
+pub mod bedrock;
+pub mod budget;
+pub mod cassette;
+pub mod gemini;
 pub mod manager;
 pub mod model;
 pub mod pricing;
 pub mod providers;
+pub mod response_cache;
 pub mod routing;
 
 pub use manager::LlmManager;
@@ -0,0 +1,13 @@
+//! Native AWS Bedrock integration: SigV4 signing, Converse request building,
+//! and response parsing for both `Converse` and the binary event stream of
+//! `ConverseStream`.
+
+pub mod event_stream;
+pub mod params;
+pub mod response;
+pub mod sigv4;
+
+pub use event_stream::{EventStreamDecoder, EventStreamMessage};
+pub use params::{build_converse_body, converse_url};
+pub use response::{BedrockStreamState, parse_converse_response, process_bedrock_stream_message};
+pub use sigv4::{AwsCredentials, SigningParams, sign_request};
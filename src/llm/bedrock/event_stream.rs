@@ -0,0 +1,199 @@
+//! Decoder for the binary `application/vnd.amazon.eventstream` framing used
+//! by ConverseStream.
+//!
+//! Each message is a 12-byte prelude (total length, headers length, prelude
+//! CRC), typed headers, a payload, and a trailing CRC of everything before it.
+//! Only string headers are kept; they carry the `:event-type`,
+//! `:message-type`, and `:exception-type` that identify a message.
+
+use std::collections::HashMap;
+
+const PRELUDE_LEN: usize = 12;
+const TRAILER_LEN: usize = 4;
+
+/// One decoded event-stream message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventStreamMessage {
+    pub headers: HashMap<String, String>,
+    pub payload: Vec<u8>,
+}
+
+impl EventStreamMessage {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).map(String::as_str)
+    }
+}
+
+/// Incremental decoder fed with response body chunks.
+#[derive(Debug, Default)]
+pub struct EventStreamDecoder {
+    buffer: Vec<u8>,
+}
+
+impl EventStreamDecoder {
+    pub fn push(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Whether undecoded bytes are left over, e.g. from a truncated stream.
+    pub fn has_remaining(&self) -> bool {
+        !self.buffer.is_empty()
+    }
+
+    /// Decode the next complete message, or `None` if more bytes are needed.
+    pub fn next_message(&mut self) -> Result<Option<EventStreamMessage>, String> {
+        if self.buffer.len() < PRELUDE_LEN {
+            return Ok(None);
+        }
+
+        let total_len = read_u32(&self.buffer[0..4]) as usize;
+        let headers_len = read_u32(&self.buffer[4..8]) as usize;
+        if read_u32(&self.buffer[8..12]) != crc32(&self.buffer[0..8]) {
+            return Err("event stream prelude checksum mismatch".into());
+        }
+        if total_len < PRELUDE_LEN + headers_len + TRAILER_LEN {
+            return Err(format!("invalid event stream message length {total_len}"));
+        }
+        if self.buffer.len() < total_len {
+            return Ok(None);
+        }
+
+        let message: Vec<u8> = self.buffer.drain(..total_len).collect();
+        let body_end = total_len - TRAILER_LEN;
+        if read_u32(&message[body_end..]) != crc32(&message[..body_end]) {
+            return Err("event stream message checksum mismatch".into());
+        }
+
+        let headers_end = PRELUDE_LEN + headers_len;
+        let headers = parse_headers(&message[PRELUDE_LEN..headers_end])?;
+        Ok(Some(EventStreamMessage {
+            headers,
+            payload: message[headers_end..body_end].to_vec(),
+        }))
+    }
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+fn parse_headers(mut bytes: &[u8]) -> Result<HashMap<String, String>, String> {
+    let truncated = || "truncated event stream header".to_string();
+    let mut headers = HashMap::new();
+
+    while !bytes.is_empty() {
+        let name_len = bytes[0] as usize;
+        let name = bytes.get(1..1 + name_len).ok_or_else(truncated)?;
+        let name = String::from_utf8_lossy(name).to_string();
+        let value_type = *bytes.get(1 + name_len).ok_or_else(truncated)?;
+        bytes = &bytes[2 + name_len..];
+
+        // Value sizes per type: bool (0/1) carry no bytes, then byte, short,
+        // int, long, length-prefixed bytes/string, timestamp, and UUID.
+        let value_len = match value_type {
+            0 | 1 => 0,
+            2 => 1,
+            3 => 2,
+            4 => 4,
+            5 | 8 => 8,
+            6 | 7 => {
+                let prefix = bytes.get(..2).ok_or_else(truncated)?;
+                2 + u16::from_be_bytes([prefix[0], prefix[1]]) as usize
+            }
+            9 => 16,
+            other => return Err(format!("unknown event stream header type {other}")),
+        };
+        let value = bytes.get(..value_len).ok_or_else(truncated)?;
+        if value_type == 7 {
+            headers.insert(name, String::from_utf8_lossy(&value[2..]).to_string());
+        }
+        bytes = &bytes[value_len..];
+    }
+
+    Ok(headers)
+}
+
+/// CRC-32 (IEEE), as used for event-stream checksums.
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFF_u32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// Encode a message with string headers. Used by tests and local stand-ins.
+#[cfg(test)]
+pub(crate) fn encode_message(headers: &[(&str, &str)], payload: &[u8]) -> Vec<u8> {
+    let mut header_bytes = Vec::new();
+    for (name, value) in headers {
+        header_bytes.push(name.len() as u8);
+        header_bytes.extend_from_slice(name.as_bytes());
+        header_bytes.push(7);
+        header_bytes.extend_from_slice(&(value.len() as u16).to_be_bytes());
+        header_bytes.extend_from_slice(value.as_bytes());
+    }
+
+    let total_len = PRELUDE_LEN + header_bytes.len() + payload.len() + TRAILER_LEN;
+    let mut message = Vec::with_capacity(total_len);
+    message.extend_from_slice(&(total_len as u32).to_be_bytes());
+    message.extend_from_slice(&(header_bytes.len() as u32).to_be_bytes());
+    message.extend_from_slice(&crc32(&message).to_be_bytes());
+    message.extend_from_slice(&header_bytes);
+    message.extend_from_slice(payload);
+    message.extend_from_slice(&crc32(&message).to_be_bytes());
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crc32_matches_reference_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn decodes_messages_split_across_chunks() {
+        let first = encode_message(
+            &[
+                (":event-type", "contentBlockDelta"),
+                (":message-type", "event"),
+            ],
+            br#"{"delta":{"text":"Hi"}}"#,
+        );
+        let second = encode_message(&[(":event-type", "messageStop")], b"{}");
+        let bytes: Vec<u8> = first.iter().chain(second.iter()).copied().collect();
+
+        let mut decoder = EventStreamDecoder::default();
+        decoder.push(&bytes[..7]);
+        assert_eq!(decoder.next_message().unwrap(), None);
+        decoder.push(&bytes[7..first.len() + 3]);
+
+        let message = decoder.next_message().unwrap().unwrap();
+        assert_eq!(message.header(":event-type"), Some("contentBlockDelta"));
+        assert_eq!(message.payload, br#"{"delta":{"text":"Hi"}}"#);
+        assert_eq!(decoder.next_message().unwrap(), None);
+
+        decoder.push(&bytes[first.len() + 3..]);
+        let message = decoder.next_message().unwrap().unwrap();
+        assert_eq!(message.header(":event-type"), Some("messageStop"));
+        assert!(!decoder.has_remaining());
+    }
+
+    #[test]
+    fn rejects_corrupted_messages() {
+        let mut bytes = encode_message(&[(":event-type", "messageStop")], b"{}");
+        let last = bytes.len() - 5;
+        bytes[last] ^= 0xFF;
+
+        let mut decoder = EventStreamDecoder::default();
+        decoder.push(&bytes);
+        assert!(decoder.next_message().is_err());
+    }
+}
@@ -0,0 +1,317 @@
+//! Build Bedrock Converse request bodies from a CompletionRequest.
+
+use base64::Engine as _;
+use rig::completion::CompletionRequest;
+use rig::message::{
+    AssistantContent, DocumentSourceKind, Message, MimeType, ToolChoice, UserContent,
+};
+use rig::one_or_many::OneOrMany;
+
+/// Construct the `Converse` (or `ConverseStream`) URL for a model.
+///
+/// `base_url` is the Bedrock runtime endpoint, e.g.
+/// `https://bedrock-runtime.us-east-1.amazonaws.com`. Model IDs and inference
+/// profile ARNs contain `:` and `/`, so the ID is percent-encoded as one
+/// path segment.
+pub fn converse_url(base_url: &str, model_id: &str, stream: bool) -> String {
+    let base = base_url.trim_end_matches('/');
+    let action = if stream {
+        "converse-stream"
+    } else {
+        "converse"
+    };
+    format!("{base}/model/{}/{action}", urlencoding::encode(model_id))
+}
+
+/// Build the JSON body for a Bedrock `Converse` request.
+///
+/// Keys in the request's `additional_params` are passed through as
+/// `additionalModelRequestFields`, which is how model-specific options such
+/// as Anthropic's `thinking` or `top_k` reach the model.
+pub fn build_converse_body(request: &CompletionRequest) -> serde_json::Value {
+    let mut body = serde_json::json!({
+        "messages": convert_messages_to_bedrock(&request.chat_history),
+    });
+
+    if let Some(preamble) = request.preamble.as_deref().filter(|p| !p.trim().is_empty()) {
+        body["system"] = serde_json::json!([{ "text": preamble }]);
+    }
+
+    let mut inference_config = serde_json::Map::new();
+    if let Some(max_tokens) = request.max_tokens {
+        inference_config.insert("maxTokens".into(), serde_json::json!(max_tokens));
+    }
+    if let Some(temperature) = request.temperature {
+        inference_config.insert("temperature".into(), serde_json::json!(temperature));
+    }
+    if !inference_config.is_empty() {
+        body["inferenceConfig"] = serde_json::Value::Object(inference_config);
+    }
+
+    // Bedrock has no "none" tool choice; the tools are left out instead.
+    if !request.tools.is_empty() && !matches!(request.tool_choice, Some(ToolChoice::None)) {
+        let tools: Vec<serde_json::Value> = request
+            .tools
+            .iter()
+            .map(|tool| {
+                serde_json::json!({
+                    "toolSpec": {
+                        "name": tool.name,
+                        "description": tool.description,
+                        "inputSchema": { "json": tool.parameters },
+                    }
+                })
+            })
+            .collect();
+        body["toolConfig"] = serde_json::json!({ "tools": tools });
+
+        if let Some(tool_choice) = request.tool_choice.as_ref().and_then(tool_choice) {
+            body["toolConfig"]["toolChoice"] = tool_choice;
+        }
+    }
+
+    if let Some(extra @ serde_json::Value::Object(_)) = &request.additional_params {
+        body["additionalModelRequestFields"] = extra.clone();
+    }
+
+    body
+}
+
+fn tool_choice(tool_choice: &ToolChoice) -> Option<serde_json::Value> {
+    match tool_choice {
+        ToolChoice::Auto => Some(serde_json::json!({ "auto": {} })),
+        ToolChoice::None => None,
+        ToolChoice::Required => Some(serde_json::json!({ "any": {} })),
+        ToolChoice::Specific { function_names } => match function_names.as_slice() {
+            [name] => Some(serde_json::json!({ "tool": { "name": name } })),
+            _ => Some(serde_json::json!({ "any": {} })),
+        },
+    }
+}
+
+/// Convert rig chat history into Converse `messages`.
+///
+/// Converse requires alternating roles, so consecutive turns with the same
+/// role are merged; system messages in the history are sent as user text.
+pub(crate) fn convert_messages_to_bedrock(messages: &OneOrMany<Message>) -> Vec<serde_json::Value> {
+    let mut converted: Vec<serde_json::Value> = Vec::new();
+
+    for message in messages.iter() {
+        let (role, content) = match message {
+            Message::User { content } => {
+                let content: Vec<serde_json::Value> =
+                    content.iter().filter_map(convert_user_content).collect();
+                ("user", content)
+            }
+            Message::Assistant { content, .. } => {
+                let content: Vec<serde_json::Value> = content
+                    .iter()
+                    .filter_map(|item| match item {
+                        AssistantContent::Text(text) => (!text.text.trim().is_empty())
+                            .then(|| serde_json::json!({ "text": text.text })),
+                        AssistantContent::ToolCall(tool_call) => Some(serde_json::json!({
+                            "toolUse": {
+                                "toolUseId": tool_call.id,
+                                "name": tool_call.function.name,
+                                "input": tool_call.function.arguments,
+                            }
+                        })),
+                        _ => None,
+                    })
+                    .collect();
+                ("assistant", content)
+            }
+            Message::System { content } => {
+                let content = if content.trim().is_empty() {
+                    Vec::new()
+                } else {
+                    vec![serde_json::json!({ "text": content })]
+                };
+                ("user", content)
+            }
+        };
+
+        if content.is_empty() {
+            continue;
+        }
+
+        if let Some(last) = converted.last_mut()
+            && last["role"] == role
+            && let Some(existing) = last["content"].as_array_mut()
+        {
+            existing.extend(content);
+            continue;
+        }
+        converted.push(serde_json::json!({ "role": role, "content": content }));
+    }
+
+    converted
+}
+
+fn convert_user_content(content: &UserContent) -> Option<serde_json::Value> {
+    match content {
+        UserContent::Text(text) => {
+            (!text.text.trim().is_empty()).then(|| serde_json::json!({ "text": text.text }))
+        }
+        UserContent::ToolResult(result) => Some(serde_json::json!({
+            "toolResult": {
+                "toolUseId": result.id,
+                "content": [{
+                    "text": crate::llm::model::tool_result_content_to_string(&result.content),
+                }],
+            }
+        })),
+        UserContent::Image(image) => {
+            let format = image
+                .media_type
+                .as_ref()
+                .map(|media_type| media_type.to_mime_type())
+                .and_then(|mime| mime.strip_prefix("image/"))
+                .unwrap_or("jpeg");
+            let bytes = media_bytes(&image.data)?;
+            Some(serde_json::json!({
+                "image": { "format": format, "source": { "bytes": bytes } }
+            }))
+        }
+        UserContent::Document(document) => {
+            if let DocumentSourceKind::String(text) = &document.data {
+                return (!text.trim().is_empty()).then(|| serde_json::json!({ "text": text }));
+            }
+            let format = match document
+                .media_type
+                .as_ref()
+                .map(|media_type| media_type.to_mime_type())
+            {
+                Some("text/plain") => "txt",
+                Some("text/html") => "html",
+                Some("text/markdown") => "md",
+                Some("text/csv") => "csv",
+                _ => "pdf",
+            };
+            let bytes = media_bytes(&document.data)?;
+            // Document names are restricted to alphanumerics, spaces, hyphens,
+            // parentheses, and brackets, and must be unique per request.
+            let name = format!(
+                "document-{}",
+                &uuid::Uuid::new_v4().simple().to_string()[..8]
+            );
+            Some(serde_json::json!({
+                "document": { "format": format, "name": name, "source": { "bytes": bytes } }
+            }))
+        }
+        _ => None,
+    }
+}
+
+/// Converse takes media inline as base64; URLs aren't supported.
+fn media_bytes(data: &DocumentSourceKind) -> Option<String> {
+    match data {
+        DocumentSourceKind::Base64(data) => Some(data.clone()),
+        DocumentSourceKind::Raw(bytes) => {
+            Some(base64::engine::general_purpose::STANDARD.encode(bytes))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rig::completion::ToolDefinition;
+    use rig::message::{Text, ToolCall, ToolFunction, ToolResult, ToolResultContent};
+
+    fn request(chat_history: Vec<Message>) -> CompletionRequest {
+        CompletionRequest {
+            model: None,
+            preamble: Some("be brief".into()),
+            chat_history: OneOrMany::many(chat_history).unwrap(),
+            documents: Vec::new(),
+            tools: vec![ToolDefinition {
+                name: "reply".into(),
+                description: "Send a reply".into(),
+                parameters: serde_json::json!({"type": "object"}),
+            }],
+            temperature: Some(0.2),
+            max_tokens: Some(1024),
+            tool_choice: Some(ToolChoice::Required),
+            additional_params: Some(serde_json::json!({"top_k": 40})),
+            output_schema: None,
+        }
+    }
+
+    #[test]
+    fn converse_url_encodes_model_ids() {
+        assert_eq!(
+            converse_url(
+                "https://bedrock-runtime.us-east-1.amazonaws.com/",
+                "anthropic.claude-3-5-sonnet-20240620-v1:0",
+                false
+            ),
+            "https://bedrock-runtime.us-east-1.amazonaws.com/model/anthropic.claude-3-5-sonnet-20240620-v1%3A0/converse"
+        );
+        assert!(converse_url("http://localhost", "m", true).ends_with("/model/m/converse-stream"));
+    }
+
+    #[test]
+    fn build_body_maps_tool_use_and_results() {
+        let tool_call = ToolCall {
+            id: "tooluse_1".into(),
+            call_id: None,
+            function: ToolFunction {
+                name: "reply".into(),
+                arguments: serde_json::json!({"content": "hi"}),
+            },
+            signature: None,
+            additional_params: None,
+        };
+        let body = build_converse_body(&request(vec![
+            Message::user("hello"),
+            Message::Assistant {
+                id: None,
+                content: OneOrMany::one(AssistantContent::ToolCall(tool_call)),
+            },
+            Message::User {
+                content: OneOrMany::one(UserContent::ToolResult(ToolResult {
+                    id: "tooluse_1".into(),
+                    call_id: None,
+                    content: OneOrMany::one(ToolResultContent::Text(Text {
+                        text: "sent".into(),
+                    })),
+                })),
+            },
+            Message::user("thanks"),
+        ]));
+
+        assert_eq!(body["system"][0]["text"], "be brief");
+        assert_eq!(body["messages"][1]["role"], "assistant");
+        assert_eq!(
+            body["messages"][1]["content"][0]["toolUse"]["toolUseId"],
+            "tooluse_1"
+        );
+        assert_eq!(
+            body["messages"][2]["content"][0]["toolResult"]["content"][0]["text"],
+            "sent"
+        );
+        // The tool result and the following user text share one turn.
+        assert_eq!(body["messages"][2]["content"][1]["text"], "thanks");
+        assert_eq!(body["messages"].as_array().unwrap().len(), 3);
+        assert_eq!(
+            body["toolConfig"]["tools"][0]["toolSpec"]["inputSchema"]["json"]["type"],
+            "object"
+        );
+        assert_eq!(
+            body["toolConfig"]["toolChoice"],
+            serde_json::json!({"any": {}})
+        );
+        assert_eq!(body["inferenceConfig"]["maxTokens"], 1024);
+        assert_eq!(body["additionalModelRequestFields"]["top_k"], 40);
+    }
+
+    #[test]
+    fn tool_choice_none_omits_tools() {
+        let mut request = request(vec![Message::user("hi")]);
+        request.tool_choice = Some(ToolChoice::None);
+        let body = build_converse_body(&request);
+        assert!(body.get("toolConfig").is_none());
+    }
+}
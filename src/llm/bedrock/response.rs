@@ -0,0 +1,467 @@
+//! Parse Bedrock `Converse` responses and `ConverseStream` events.
+
+use super::event_stream::EventStreamMessage;
+use crate::llm::model::{RawResponse, RawStreamingResponse};
+
+use rig::completion::{self, CompletionError};
+use rig::message::{AssistantContent, Reasoning, Text, ToolCall, ToolFunction};
+use rig::one_or_many::OneOrMany;
+use rig::streaming::{RawStreamingChoice, RawStreamingToolCall};
+use std::collections::BTreeMap;
+
+/// Token usage from a response's `usage` object.
+///
+/// Bedrock reports cache reads and writes separately from `inputTokens`, so
+/// both are folded back in to get the full prompt size.
+pub fn bedrock_usage(body: &serde_json::Value) -> completion::Usage {
+    let usage = &body["usage"];
+    let cached_input_tokens = usage["cacheReadInputTokens"].as_u64().unwrap_or(0);
+    let input_tokens = usage["inputTokens"].as_u64().unwrap_or(0)
+        + cached_input_tokens
+        + usage["cacheWriteInputTokens"].as_u64().unwrap_or(0);
+    let output_tokens = usage["outputTokens"].as_u64().unwrap_or(0);
+
+    completion::Usage {
+        input_tokens,
+        output_tokens,
+        total_tokens: input_tokens + output_tokens,
+        cached_input_tokens,
+    }
+}
+
+fn tool_call_from_block(block: &serde_json::Value) -> Option<ToolCall> {
+    let tool_use = block.get("toolUse")?;
+    let arguments = match &tool_use["input"] {
+        serde_json::Value::Null => serde_json::json!({}),
+        input => input.clone(),
+    };
+
+    Some(ToolCall {
+        id: tool_use["toolUseId"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+        call_id: None,
+        function: ToolFunction {
+            name: tool_use["name"].as_str().unwrap_or("").trim().to_string(),
+            arguments,
+        },
+        signature: None,
+        additional_params: None,
+    })
+}
+
+fn empty_response_error(body: &serde_json::Value) -> CompletionError {
+    let stop_reason = body["stopReason"].as_str().unwrap_or("unknown");
+    tracing::warn!(stop_reason, "unexpected empty response from Bedrock");
+    CompletionError::ResponseError(format!(
+        "empty response from Bedrock (stopReason: {stop_reason})"
+    ))
+}
+
+fn finished_normally(body: &serde_json::Value) -> bool {
+    matches!(body["stopReason"].as_str(), Some("end_turn") | None)
+}
+
+/// Parse a non-streaming `Converse` response.
+pub fn parse_converse_response(
+    body: serde_json::Value,
+) -> Result<completion::CompletionResponse<RawResponse>, CompletionError> {
+    let blocks = body["output"]["message"]["content"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+    let mut assistant_content = Vec::new();
+    for block in blocks {
+        if let Some(tool_call) = tool_call_from_block(block) {
+            assistant_content.push(AssistantContent::ToolCall(tool_call));
+        } else if let Some(text) = block["text"].as_str() {
+            if !text.trim().is_empty() {
+                assistant_content.push(AssistantContent::Text(Text {
+                    text: text.to_string(),
+                }));
+            }
+        } else if let Some(reasoning) = block["reasoningContent"]["reasoningText"]["text"].as_str()
+        {
+            assistant_content.push(AssistantContent::Reasoning(Reasoning::new(reasoning)));
+        }
+    }
+
+    let has_output = assistant_content
+        .iter()
+        .any(|content| !matches!(content, AssistantContent::Reasoning(_)));
+    if !has_output {
+        if !finished_normally(&body) {
+            return Err(empty_response_error(&body));
+        }
+        // Like Anthropic's empty end_turn: the model had nothing further to
+        // say after a side-effect-only tool call.
+        assistant_content.push(AssistantContent::Text(Text {
+            text: " ".to_string(),
+        }));
+    }
+
+    let choice = OneOrMany::many(assistant_content).map_err(|_| empty_response_error(&body))?;
+
+    Ok(completion::CompletionResponse {
+        choice,
+        usage: bedrock_usage(&body),
+        message_id: None,
+        raw_response: RawResponse { body },
+    })
+}
+
+#[derive(Debug)]
+enum StreamBlock {
+    Text(String),
+    Reasoning(String),
+    ToolUse {
+        id: String,
+        internal_call_id: String,
+        name: String,
+        input: String,
+    },
+}
+
+impl StreamBlock {
+    fn to_json(&self) -> serde_json::Value {
+        match self {
+            StreamBlock::Text(text) => serde_json::json!({ "text": text }),
+            StreamBlock::Reasoning(text) => {
+                serde_json::json!({ "reasoningContent": { "reasoningText": { "text": text } } })
+            }
+            StreamBlock::ToolUse {
+                id, name, input, ..
+            } => serde_json::json!({
+                "toolUse": {
+                    "toolUseId": id,
+                    "name": name,
+                    "input": parse_tool_input(input),
+                }
+            }),
+        }
+    }
+}
+
+fn parse_tool_input(input: &str) -> serde_json::Value {
+    if input.trim().is_empty() {
+        return serde_json::json!({});
+    }
+    serde_json::from_str(input).unwrap_or_else(|_| serde_json::Value::String(input.to_string()))
+}
+
+/// Accumulates `ConverseStream` events into one `Converse`-shaped body.
+#[derive(Debug, Default)]
+pub struct BedrockStreamState {
+    blocks: BTreeMap<u64, StreamBlock>,
+    stop_reason: Option<String>,
+    usage: Option<serde_json::Value>,
+}
+
+impl BedrockStreamState {
+    /// Build the final response event once the stream is exhausted.
+    pub fn finish(self) -> Result<RawStreamingChoice<RawStreamingResponse>, CompletionError> {
+        let content: Vec<serde_json::Value> =
+            self.blocks.values().map(StreamBlock::to_json).collect();
+        let has_output = self
+            .blocks
+            .values()
+            .any(|block| !matches!(block, StreamBlock::Reasoning(_)));
+
+        let mut body = serde_json::json!({
+            "output": { "message": { "role": "assistant", "content": content } },
+        });
+        if let Some(stop_reason) = self.stop_reason {
+            body["stopReason"] = stop_reason.into();
+        }
+        if let Some(usage) = self.usage {
+            body["usage"] = usage;
+        }
+
+        if !has_output && !finished_normally(&body) {
+            return Err(empty_response_error(&body));
+        }
+
+        Ok(RawStreamingChoice::FinalResponse(RawStreamingResponse {
+            usage: Some(bedrock_usage(&body)),
+            body,
+        }))
+    }
+}
+
+/// Convert one decoded stream message into streaming events, recording it
+/// in `state`. Exception messages become provider errors so their type
+/// (e.g. `throttlingException`) reaches retry classification.
+pub fn process_bedrock_stream_message(
+    message: &EventStreamMessage,
+    state: &mut BedrockStreamState,
+) -> Result<Vec<RawStreamingChoice<RawStreamingResponse>>, CompletionError> {
+    let payload: serde_json::Value = serde_json::from_slice(&message.payload).map_err(|error| {
+        CompletionError::ProviderError(format!("invalid Bedrock stream payload: {error}"))
+    })?;
+
+    if message.header(":message-type") != Some("event") {
+        let error_type = message
+            .header(":exception-type")
+            .or(message.header(":error-code"))
+            .unwrap_or("unknown");
+        let error_message = payload["message"]
+            .as_str()
+            .or(message.header(":error-message"))
+            .unwrap_or_default();
+        return Err(CompletionError::ProviderError(format!(
+            "Bedrock stream error: {error_type}: {error_message}"
+        )));
+    }
+
+    let index = payload["contentBlockIndex"].as_u64().unwrap_or(0);
+    let mut events = Vec::new();
+
+    match message.header(":event-type").unwrap_or_default() {
+        "contentBlockStart" => {
+            if let Some(tool_use) = payload["start"].get("toolUse") {
+                let id = tool_use["toolUseId"].as_str().unwrap_or_default();
+                let name = tool_use["name"].as_str().unwrap_or_default().trim();
+                let internal_call_id = uuid::Uuid::new_v4().to_string();
+                events.push(RawStreamingChoice::ToolCallDelta {
+                    id: id.to_string(),
+                    internal_call_id: internal_call_id.clone(),
+                    content: rig::streaming::ToolCallDeltaContent::Name(name.to_string()),
+                });
+                state.blocks.insert(
+                    index,
+                    StreamBlock::ToolUse {
+                        id: id.to_string(),
+                        internal_call_id,
+                        name: name.to_string(),
+                        input: String::new(),
+                    },
+                );
+            }
+        }
+        "contentBlockDelta" => {
+            let delta = &payload["delta"];
+            if let Some(text) = delta["text"].as_str() {
+                events.push(RawStreamingChoice::Message(text.to_string()));
+                match state
+                    .blocks
+                    .entry(index)
+                    .or_insert(StreamBlock::Text(String::new()))
+                {
+                    StreamBlock::Text(existing) => existing.push_str(text),
+                    other => tracing::debug!(?other, "Bedrock text delta for non-text block"),
+                }
+            } else if let Some(input) = delta["toolUse"]["input"].as_str() {
+                if let Some(StreamBlock::ToolUse {
+                    id,
+                    internal_call_id,
+                    input: existing,
+                    ..
+                }) = state.blocks.get_mut(&index)
+                {
+                    existing.push_str(input);
+                    events.push(RawStreamingChoice::ToolCallDelta {
+                        id: id.clone(),
+                        internal_call_id: internal_call_id.clone(),
+                        content: rig::streaming::ToolCallDeltaContent::Delta(input.to_string()),
+                    });
+                }
+            } else if let Some(reasoning) = delta["reasoningContent"]["text"].as_str() {
+                events.push(RawStreamingChoice::ReasoningDelta {
+                    id: None,
+                    reasoning: reasoning.to_string(),
+                });
+                if let StreamBlock::Reasoning(existing) = state
+                    .blocks
+                    .entry(index)
+                    .or_insert(StreamBlock::Reasoning(String::new()))
+                {
+                    existing.push_str(reasoning);
+                }
+            }
+        }
+        "contentBlockStop" => {
+            if let Some(StreamBlock::ToolUse {
+                id,
+                internal_call_id,
+                name,
+                input,
+            }) = state.blocks.get(&index)
+            {
+                events.push(RawStreamingChoice::ToolCall(RawStreamingToolCall {
+                    id: id.clone(),
+                    internal_call_id: internal_call_id.clone(),
+                    call_id: None,
+                    name: name.clone(),
+                    arguments: parse_tool_input(input),
+                    signature: None,
+                    additional_params: None,
+                }));
+            }
+        }
+        "messageStop" => {
+            state.stop_reason = payload["stopReason"].as_str().map(ToString::to_string);
+        }
+        "metadata" => {
+            if payload.get("usage").is_some() {
+                state.usage = Some(payload["usage"].clone());
+            }
+        }
+        _ => {}
+    }
+
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::bedrock::event_stream::{EventStreamDecoder, encode_message};
+
+    fn event(event_type: &str, payload: serde_json::Value) -> EventStreamMessage {
+        let bytes = encode_message(
+            &[(":event-type", event_type), (":message-type", "event")],
+            payload.to_string().as_bytes(),
+        );
+        let mut decoder = EventStreamDecoder::default();
+        decoder.push(&bytes);
+        decoder.next_message().unwrap().unwrap()
+    }
+
+    #[test]
+    fn parse_response_maps_text_tool_use_and_usage() {
+        let body = serde_json::json!({
+            "output": {"message": {"role": "assistant", "content": [
+                {"reasoningContent": {"reasoningText": {"text": "pondering"}}},
+                {"text": "Checking."},
+                {"toolUse": {"toolUseId": "tooluse_1", "name": "reply", "input": {"content": "hi"}}}
+            ]}},
+            "stopReason": "tool_use",
+            "usage": {
+                "inputTokens": 60,
+                "outputTokens": 20,
+                "cacheReadInputTokens": 30,
+                "cacheWriteInputTokens": 10
+            }
+        });
+
+        let response = parse_converse_response(body).unwrap();
+        let choice: Vec<_> = response.choice.into_iter().collect();
+        assert!(matches!(choice[0], AssistantContent::Reasoning(_)));
+        assert!(matches!(&choice[1], AssistantContent::Text(text) if text.text == "Checking."));
+        let AssistantContent::ToolCall(tool_call) = &choice[2] else {
+            panic!("expected a tool call");
+        };
+        assert_eq!(tool_call.id, "tooluse_1");
+        assert_eq!(tool_call.function.arguments["content"], "hi");
+
+        assert_eq!(response.usage.input_tokens, 100);
+        assert_eq!(response.usage.output_tokens, 20);
+        assert_eq!(response.usage.cached_input_tokens, 30);
+    }
+
+    #[test]
+    fn empty_responses_error_unless_end_turn() {
+        let truncated = serde_json::json!({
+            "output": {"message": {"role": "assistant", "content": []}},
+            "stopReason": "max_tokens"
+        });
+        let error = parse_converse_response(truncated).unwrap_err();
+        assert!(error.to_string().contains("stopReason: max_tokens"));
+
+        let end_turn = serde_json::json!({
+            "output": {"message": {"role": "assistant", "content": []}},
+            "stopReason": "end_turn"
+        });
+        assert!(parse_converse_response(end_turn).is_ok());
+    }
+
+    #[test]
+    fn stream_events_accumulate_into_final_response() {
+        let mut state = BedrockStreamState::default();
+        let messages = [
+            event("messageStart", serde_json::json!({"role": "assistant"})),
+            event(
+                "contentBlockDelta",
+                serde_json::json!({"contentBlockIndex": 0, "delta": {"text": "Hi"}}),
+            ),
+            event(
+                "contentBlockStop",
+                serde_json::json!({"contentBlockIndex": 0}),
+            ),
+            event(
+                "contentBlockStart",
+                serde_json::json!({"contentBlockIndex": 1, "start": {"toolUse": {"toolUseId": "t1", "name": "react"}}}),
+            ),
+            event(
+                "contentBlockDelta",
+                serde_json::json!({"contentBlockIndex": 1, "delta": {"toolUse": {"input": "{\"emoji\":"}}}),
+            ),
+            event(
+                "contentBlockDelta",
+                serde_json::json!({"contentBlockIndex": 1, "delta": {"toolUse": {"input": "\"👍\"}"}}}),
+            ),
+            event(
+                "contentBlockStop",
+                serde_json::json!({"contentBlockIndex": 1}),
+            ),
+            event("messageStop", serde_json::json!({"stopReason": "tool_use"})),
+            event(
+                "metadata",
+                serde_json::json!({"usage": {"inputTokens": 10, "outputTokens": 5}}),
+            ),
+        ];
+
+        let events: Vec<_> = messages
+            .iter()
+            .flat_map(|message| process_bedrock_stream_message(message, &mut state).unwrap())
+            .collect();
+
+        assert!(matches!(&events[0], RawStreamingChoice::Message(text) if text == "Hi"));
+        let RawStreamingChoice::ToolCallDelta {
+            internal_call_id: delta_call_id,
+            ..
+        } = &events[1]
+        else {
+            panic!("expected a tool call name delta");
+        };
+        let RawStreamingChoice::ToolCall(tool_call) = &events[4] else {
+            panic!("expected a tool call");
+        };
+        assert_eq!(&tool_call.internal_call_id, delta_call_id);
+        assert_eq!(tool_call.id, "t1");
+        assert_eq!(tool_call.arguments["emoji"], "👍");
+
+        let RawStreamingChoice::FinalResponse(response) = state.finish().unwrap() else {
+            panic!("expected a final response");
+        };
+        assert_eq!(response.usage.unwrap().output_tokens, 5);
+        assert_eq!(
+            response.body["output"]["message"]["content"][1]["toolUse"]["input"]["emoji"],
+            "👍"
+        );
+    }
+
+    #[test]
+    fn stream_exceptions_become_provider_errors() {
+        let bytes = encode_message(
+            &[
+                (":message-type", "exception"),
+                (":exception-type", "throttlingException"),
+            ],
+            br#"{"message":"Too many requests"}"#,
+        );
+        let mut decoder = EventStreamDecoder::default();
+        decoder.push(&bytes);
+        let message = decoder.next_message().unwrap().unwrap();
+
+        let error = process_bedrock_stream_message(&message, &mut BedrockStreamState::default())
+            .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Bedrock stream error: throttlingException: Too many requests")
+        );
+    }
+}
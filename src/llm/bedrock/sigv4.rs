@@ -0,0 +1,237 @@
+//! AWS Signature Version 4 request signing.
+//!
+//! Only what Bedrock needs: header-based signing of a request with a fully
+//! buffered payload. Paths are double-encoded as required for every service
+//! except S3, so a model ID like `anthropic.claude-v2:1` (sent as `%3A`) is
+//! canonicalized as `%253A`.
+
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+type HmacSha256 = Hmac<Sha256>;
+
+const ALGORITHM: &str = "AWS4-HMAC-SHA256";
+
+/// AWS credentials used to sign a request.
+pub struct AwsCredentials<'a> {
+    pub access_key_id: &'a str,
+    pub secret_access_key: &'a str,
+    /// Session token for temporary (STS) credentials.
+    pub session_token: Option<&'a str>,
+}
+
+/// Credentials and scope for one signature.
+pub struct SigningParams<'a> {
+    pub credentials: AwsCredentials<'a>,
+    pub region: &'a str,
+    /// Signing name of the service, e.g. `bedrock`.
+    pub service: &'a str,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+/// Compute the SigV4 headers for a request.
+///
+/// `headers` are extra headers to sign (e.g. `content-type`); `host` is taken
+/// from the URL. Returns the headers to attach: `x-amz-date`,
+/// `x-amz-security-token` when a session token is set, and `authorization`.
+pub fn sign_request(
+    method: &str,
+    url: &reqwest::Url,
+    headers: &[(&str, &str)],
+    payload: &[u8],
+    params: &SigningParams<'_>,
+) -> Vec<(&'static str, String)> {
+    let SigningParams {
+        credentials,
+        region,
+        service,
+        timestamp,
+    } = params;
+    let amz_date = timestamp.format("%Y%m%dT%H%M%SZ").to_string();
+    let date = timestamp.format("%Y%m%d").to_string();
+
+    let mut host = url.host_str().unwrap_or_default().to_string();
+    if let Some(port) = url.port() {
+        host = format!("{host}:{port}");
+    }
+
+    let mut signed: Vec<(String, String)> = headers
+        .iter()
+        .map(|(name, value)| (name.to_ascii_lowercase(), value.trim().to_string()))
+        .collect();
+    signed.push(("host".into(), host));
+    signed.push(("x-amz-date".into(), amz_date.clone()));
+    if let Some(token) = credentials.session_token {
+        signed.push(("x-amz-security-token".into(), token.to_string()));
+    }
+    signed.sort();
+
+    let canonical_headers: String = signed
+        .iter()
+        .map(|(name, value)| format!("{name}:{value}\n"))
+        .collect();
+    let signed_headers = signed
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>()
+        .join(";");
+
+    let canonical_request = format!(
+        "{method}\n{}\n{}\n{canonical_headers}\n{signed_headers}\n{}",
+        canonical_uri(url),
+        canonical_query(url),
+        hex::encode(Sha256::digest(payload)),
+    );
+
+    let scope = format!("{date}/{region}/{service}/aws4_request");
+    let string_to_sign = format!(
+        "{ALGORITHM}\n{amz_date}\n{scope}\n{}",
+        hex::encode(Sha256::digest(canonical_request.as_bytes()))
+    );
+
+    let signing_key = [*region, *service, "aws4_request"].iter().fold(
+        hmac_sha256(
+            format!("AWS4{}", credentials.secret_access_key).as_bytes(),
+            date.as_bytes(),
+        ),
+        |key, part| hmac_sha256(&key, part.as_bytes()),
+    );
+    let signature = hex::encode(hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+    let mut result = vec![("x-amz-date", amz_date)];
+    if let Some(token) = credentials.session_token {
+        result.push(("x-amz-security-token", token.to_string()));
+    }
+    result.push((
+        "authorization",
+        format!(
+            "{ALGORITHM} Credential={}/{scope}, SignedHeaders={signed_headers}, Signature={signature}",
+            credentials.access_key_id
+        ),
+    ));
+    result
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Percent-encode everything except RFC 3986 unreserved characters.
+fn uri_encode(value: &str, encode_slash: bool) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            b'/' if !encode_slash => encoded.push('/'),
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+fn canonical_uri(url: &reqwest::Url) -> String {
+    let path = url.path();
+    if path.is_empty() {
+        return "/".into();
+    }
+    path.split('/')
+        .map(|segment| uri_encode(segment, true))
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn canonical_query(url: &reqwest::Url) -> String {
+    let mut pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(key, value)| (uri_encode(&key, true), uri_encode(&value, true)))
+        .collect();
+    pairs.sort();
+    pairs
+        .iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone as _;
+
+    fn example_credentials() -> AwsCredentials<'static> {
+        AwsCredentials {
+            access_key_id: "AKIDEXAMPLE",
+            secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            session_token: None,
+        }
+    }
+
+    #[test]
+    fn matches_aws_get_vanilla_test_vector() {
+        let url = reqwest::Url::parse("https://example.amazonaws.com/").unwrap();
+        let timestamp = chrono::Utc
+            .with_ymd_and_hms(2015, 8, 30, 12, 36, 0)
+            .unwrap();
+
+        let headers = sign_request(
+            "GET",
+            &url,
+            &[],
+            b"",
+            &SigningParams {
+                credentials: example_credentials(),
+                region: "us-east-1",
+                service: "service",
+                timestamp,
+            },
+        );
+
+        assert_eq!(headers[0], ("x-amz-date", "20150830T123600Z".to_string()));
+        assert_eq!(
+            headers[1].1,
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, \
+             SignedHeaders=host;x-amz-date, \
+             Signature=5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31"
+        );
+    }
+
+    #[test]
+    fn session_token_is_signed_and_returned() {
+        let url = reqwest::Url::parse("http://127.0.0.1:8080/model/m/converse").unwrap();
+        let headers = sign_request(
+            "POST",
+            &url,
+            &[("content-type", "application/json")],
+            b"{}",
+            &SigningParams {
+                credentials: AwsCredentials {
+                    session_token: Some("token"),
+                    ..example_credentials()
+                },
+                region: "us-west-2",
+                service: "bedrock",
+                timestamp: chrono::Utc::now(),
+            },
+        );
+
+        assert_eq!(headers[1], ("x-amz-security-token", "token".to_string()));
+        assert!(
+            headers[2]
+                .1
+                .contains("SignedHeaders=content-type;host;x-amz-date;x-amz-security-token")
+        );
+    }
+
+    #[test]
+    fn canonical_uri_double_encodes_escaped_segments() {
+        let url = reqwest::Url::parse(
+            "https://bedrock-runtime.us-east-1.amazonaws.com/model/a.b-v1%3A0/converse",
+        )
+        .unwrap();
+        assert_eq!(canonical_uri(&url), "/model/a.b-v1%253A0/converse");
+    }
+}
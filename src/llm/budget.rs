@@ -0,0 +1,770 @@
+//! LLM spend budgets: persisted spend ledger, limit evaluation, and enforcement.
+//!
+//! Every completion that reports token usage is priced with
+//! `pricing::estimate_cost` and appended to the per-agent `llm_spend` table.
+//! Before dispatch, `SpacebotModel` asks the agent's `SpendTracker` whether the
+//! call may proceed. Crossing the soft limit sends a one-time warning to the
+//! configured delivery target and optionally downgrades to a cheaper model;
+//! crossing the hard limit refuses the call outright.
+//!
+//! Periods are calendar days and months in the agent's timezone, matching the
+//! `day` column used by working memory.
+
+use crate::config::{BudgetConfig, BudgetLimits, RuntimeConfig};
+use crate::error::Result;
+use crate::messaging::MessagingManager;
+use crate::messaging::target::parse_delivery_target;
+use crate::{OutboundResponse, llm::pricing};
+
+use arc_swap::ArcSwap;
+use chrono::{Datelike as _, Utc};
+use chrono_tz::Tz;
+use serde::Serialize;
+use sqlx::{Row as _, SqlitePool};
+
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// Calendar period a limit applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetPeriod {
+    Daily,
+    Monthly,
+}
+
+impl BudgetPeriod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Daily => "daily",
+            Self::Monthly => "monthly",
+        }
+    }
+}
+
+impl fmt::Display for BudgetPeriod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// What a set of limits is scoped to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BudgetScope {
+    Agent,
+    Channel(String),
+    Cron(String),
+}
+
+impl BudgetScope {
+    /// Channel ID the scope's spend is filtered by, or `None` for agent-wide.
+    fn channel_id(&self) -> Option<String> {
+        match self {
+            Self::Agent => None,
+            Self::Channel(channel_id) => Some(channel_id.clone()),
+            Self::Cron(cron_id) => Some(format!("cron:{cron_id}")),
+        }
+    }
+}
+
+impl fmt::Display for BudgetScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Agent => f.write_str("agent"),
+            Self::Channel(channel_id) => write!(f, "channel {channel_id}"),
+            Self::Cron(cron_id) => write!(f, "cron job {cron_id}"),
+        }
+    }
+}
+
+/// How far past a limit the current spend is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BreachSeverity {
+    Soft,
+    Hard,
+}
+
+/// A single limit that current spend has reached.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LimitBreach {
+    pub period: BudgetPeriod,
+    pub severity: BreachSeverity,
+    pub spent_usd: f64,
+    pub limit_usd: f64,
+}
+
+impl LimitBreach {
+    fn usage_ratio(&self) -> f64 {
+        self.spent_usd / self.limit_usd
+    }
+}
+
+/// A hard limit was reached; the call is refused.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetExceeded {
+    pub scope: BudgetScope,
+    pub period: BudgetPeriod,
+    pub spent_usd: f64,
+    pub limit_usd: f64,
+}
+
+impl BudgetExceeded {
+    /// Short explanation suitable for sending to the conversation.
+    pub fn user_message(&self) -> String {
+        let resets = match self.period {
+            BudgetPeriod::Daily => "tomorrow",
+            BudgetPeriod::Monthly => "next month",
+        };
+        format!(
+            "I've hit my {} spending limit (${:.2}), so I'm pausing until it resets {resets}.",
+            self.period, self.limit_usd
+        )
+    }
+}
+
+impl fmt::Display for BudgetExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} budget exceeded for {}: spent ${:.2} of ${:.2}",
+            self.period, self.scope, self.spent_usd, self.limit_usd
+        )
+    }
+}
+
+/// Outcome of a pre-dispatch budget check.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BudgetDecision {
+    Allow,
+    /// A soft limit was crossed; dispatch to this cheaper model instead.
+    Downgrade {
+        model: String,
+    },
+    Stop(BudgetExceeded),
+}
+
+/// Compare spend against a set of limits and return the most severe breach.
+///
+/// Hard breaches win over soft ones; within the same severity the limit
+/// closest to (or furthest past) exhaustion wins.
+pub fn evaluate_limits(
+    limits: BudgetLimits,
+    daily_spent_usd: f64,
+    monthly_spent_usd: f64,
+    soft_limit_ratio: f64,
+) -> Option<LimitBreach> {
+    [
+        (BudgetPeriod::Daily, limits.daily_usd, daily_spent_usd),
+        (BudgetPeriod::Monthly, limits.monthly_usd, monthly_spent_usd),
+    ]
+    .into_iter()
+    .filter_map(|(period, limit, spent)| {
+        let limit = limit?;
+        let severity = if spent >= limit {
+            BreachSeverity::Hard
+        } else if spent >= limit * soft_limit_ratio {
+            BreachSeverity::Soft
+        } else {
+            return None;
+        };
+        Some(LimitBreach {
+            period,
+            severity,
+            spent_usd: spent,
+            limit_usd: limit,
+        })
+    })
+    .max_by(|left, right| {
+        left.severity
+            .cmp(&right.severity)
+            .then(left.usage_ratio().total_cmp(&right.usage_ratio()))
+    })
+}
+
+/// Token usage for one completion, attributed to the process that made it.
+#[derive(Debug, Clone)]
+pub struct SpendRecord {
+    pub channel_id: Option<String>,
+    pub process_type: String,
+    pub model: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cached_input_tokens: u64,
+}
+
+/// Spend aggregated over one day.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct DailySpend {
+    pub day: String,
+    pub cost_usd: f64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub requests: i64,
+}
+
+/// Spend aggregated per channel over a period.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct ChannelSpend {
+    pub channel_id: String,
+    pub cost_usd: f64,
+    pub requests: i64,
+}
+
+/// Per-agent spend ledger and budget enforcer.
+pub struct SpendTracker {
+    agent_id: String,
+    pool: SqlitePool,
+    timezone: Tz,
+    runtime_config: Arc<RuntimeConfig>,
+    /// Set once adapters are started; warnings before then are only logged.
+    messaging_manager: ArcSwap<Option<Arc<MessagingManager>>>,
+    /// Soft-limit warnings already sent, keyed by scope, period, and period
+    /// start so each crossing is announced once.
+    warned: Mutex<HashSet<String>>,
+}
+
+impl fmt::Debug for SpendTracker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpendTracker")
+            .field("agent_id", &self.agent_id)
+            .field("timezone", &self.timezone)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SpendTracker {
+    pub fn new(
+        agent_id: impl Into<String>,
+        pool: SqlitePool,
+        timezone: Tz,
+        runtime_config: Arc<RuntimeConfig>,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            agent_id: agent_id.into(),
+            pool,
+            timezone,
+            runtime_config,
+            messaging_manager: ArcSwap::from_pointee(None),
+            warned: Mutex::new(HashSet::new()),
+        })
+    }
+
+    pub fn agent_id(&self) -> &str {
+        &self.agent_id
+    }
+
+    /// Set the messaging manager used to deliver soft-limit warnings.
+    pub fn set_messaging_manager(&self, messaging_manager: Arc<MessagingManager>) {
+        self.messaging_manager
+            .store(Arc::new(Some(messaging_manager)));
+    }
+
+    /// Current budget configuration snapshot.
+    pub fn config(&self) -> Arc<BudgetConfig> {
+        self.runtime_config.budget.load_full()
+    }
+
+    /// Today's date string in the agent's timezone.
+    pub fn today(&self) -> String {
+        Utc::now()
+            .with_timezone(&self.timezone)
+            .format("%Y-%m-%d")
+            .to_string()
+    }
+
+    /// First day of the current month in the agent's timezone.
+    pub fn month_start(&self) -> String {
+        let today = Utc::now().with_timezone(&self.timezone).date_naive();
+        format!("{:04}-{:02}-01", today.year(), today.month())
+    }
+
+    /// Decide whether a completion for `channel_id` may be dispatched.
+    ///
+    /// Ledger read failures fail open: losing budget bookkeeping for a moment
+    /// is better than taking the agent offline.
+    pub async fn check(&self, channel_id: Option<&str>) -> BudgetDecision {
+        let config = self.config();
+        if !config.is_enabled() {
+            return BudgetDecision::Allow;
+        }
+
+        let mut scopes = vec![(BudgetScope::Agent, config.limits)];
+        if let Some(channel_id) = channel_id {
+            scopes.extend(config.scoped_limits(channel_id));
+        }
+
+        let mut soft_breach = false;
+        for (scope, limits) in scopes {
+            if limits.is_empty() {
+                continue;
+            }
+            let breach = match self.evaluate_scope(&scope, limits, &config).await {
+                Ok(breach) => breach,
+                Err(error) => {
+                    tracing::warn!(
+                        agent_id = %self.agent_id,
+                        %scope,
+                        %error,
+                        "failed to read spend ledger, skipping budget check"
+                    );
+                    continue;
+                }
+            };
+            let Some(breach) = breach else {
+                continue;
+            };
+
+            match breach.severity {
+                BreachSeverity::Hard => {
+                    return BudgetDecision::Stop(BudgetExceeded {
+                        scope,
+                        period: breach.period,
+                        spent_usd: breach.spent_usd,
+                        limit_usd: breach.limit_usd,
+                    });
+                }
+                BreachSeverity::Soft => {
+                    soft_breach = true;
+                    self.warn_once(&config, &scope, breach);
+                }
+            }
+        }
+
+        if soft_breach
+            && config.downgrade_on_soft_limit
+            && let Some(model) = config.downgrade_model.clone().or_else(|| {
+                self.runtime_config
+                    .routing
+                    .load()
+                    .prompt_routing
+                    .tiers
+                    .light
+                    .clone()
+            })
+        {
+            return BudgetDecision::Downgrade { model };
+        }
+
+        BudgetDecision::Allow
+    }
+
+    async fn evaluate_scope(
+        &self,
+        scope: &BudgetScope,
+        limits: BudgetLimits,
+        config: &BudgetConfig,
+    ) -> Result<Option<LimitBreach>> {
+        let channel_id = scope.channel_id();
+        let daily = match limits.daily_usd {
+            Some(_) => {
+                self.spent_since(&self.today(), channel_id.as_deref())
+                    .await?
+            }
+            None => 0.0,
+        };
+        let monthly = match limits.monthly_usd {
+            Some(_) => {
+                self.spent_since(&self.month_start(), channel_id.as_deref())
+                    .await?
+            }
+            None => 0.0,
+        };
+        Ok(evaluate_limits(
+            limits,
+            daily,
+            monthly,
+            config.soft_limit_ratio,
+        ))
+    }
+
+    /// Announce a soft-limit crossing once per scope and period.
+    fn warn_once(&self, config: &BudgetConfig, scope: &BudgetScope, breach: LimitBreach) {
+        let period_start = match breach.period {
+            BudgetPeriod::Daily => self.today(),
+            BudgetPeriod::Monthly => self.month_start(),
+        };
+        let key = format!("{scope}:{}:{period_start}", breach.period);
+        let first_crossing = self
+            .warned
+            .lock()
+            .map(|mut warned| warned.insert(key))
+            .unwrap_or(false);
+        if !first_crossing {
+            return;
+        }
+
+        tracing::warn!(
+            agent_id = %self.agent_id,
+            %scope,
+            period = %breach.period,
+            spent_usd = breach.spent_usd,
+            limit_usd = breach.limit_usd,
+            "soft spend limit reached"
+        );
+
+        let Some(raw_target) = config.warning_target.as_deref() else {
+            return;
+        };
+        let Some(target) = parse_delivery_target(raw_target) else {
+            tracing::warn!(
+                agent_id = %self.agent_id,
+                warning_target = raw_target,
+                "invalid budget warning target"
+            );
+            return;
+        };
+        let Some(messaging_manager) = self.messaging_manager.load().as_ref().clone() else {
+            return;
+        };
+
+        let text = format!(
+            "Budget warning for agent `{}`: {} {} spend is ${:.2} of ${:.2} ({:.0}%).",
+            self.agent_id,
+            scope,
+            breach.period,
+            breach.spent_usd,
+            breach.limit_usd,
+            breach.usage_ratio() * 100.0
+        );
+        let agent_id = self.agent_id.clone();
+        tokio::spawn(async move {
+            if let Err(error) = messaging_manager
+                .broadcast_proactive(
+                    &target.adapter,
+                    &target.target,
+                    OutboundResponse::Text(text),
+                )
+                .await
+            {
+                tracing::warn!(%agent_id, %target, %error, "failed to deliver budget warning");
+            }
+        });
+    }
+
+    /// Fire-and-forget ledger write. Spawns a task, never blocks the caller.
+    pub fn record(&self, record: SpendRecord) {
+        let cost_usd = pricing::estimate_cost(
+            &record.model,
+            record.input_tokens,
+            record.output_tokens,
+            record.cached_input_tokens,
+        );
+        if cost_usd <= 0.0 {
+            return;
+        }
+
+        let pool = self.pool.clone();
+        let day = self.today();
+        let agent_id = self.agent_id.clone();
+        tokio::spawn(async move {
+            if let Err(error) = insert_spend(&pool, &day, &record, cost_usd).await {
+                tracing::warn!(%agent_id, %error, "failed to record LLM spend");
+            }
+        });
+    }
+
+    /// Total spend since `since_day` (inclusive), optionally for one channel.
+    pub async fn spent_since(&self, since_day: &str, channel_id: Option<&str>) -> Result<f64> {
+        let total: f64 = match channel_id {
+            Some(channel_id) => {
+                sqlx::query_scalar(
+                    "SELECT COALESCE(SUM(cost_usd), 0.0) FROM llm_spend \
+                     WHERE day >= ? AND channel_id = ?",
+                )
+                .bind(since_day)
+                .bind(channel_id)
+                .fetch_one(&self.pool)
+                .await?
+            }
+            None => {
+                sqlx::query_scalar(
+                    "SELECT COALESCE(SUM(cost_usd), 0.0) FROM llm_spend WHERE day >= ?",
+                )
+                .bind(since_day)
+                .fetch_one(&self.pool)
+                .await?
+            }
+        };
+        Ok(total)
+    }
+
+    /// Per-day totals since `since_day` (inclusive), oldest first.
+    pub async fn daily_totals(&self, since_day: &str) -> Result<Vec<DailySpend>> {
+        let rows = sqlx::query(
+            "SELECT day, SUM(cost_usd) AS cost_usd, SUM(input_tokens) AS input_tokens, \
+             SUM(output_tokens) AS output_tokens, COUNT(*) AS requests \
+             FROM llm_spend WHERE day >= ? GROUP BY day ORDER BY day ASC",
+        )
+        .bind(since_day)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| DailySpend {
+                day: row.get("day"),
+                cost_usd: row.get("cost_usd"),
+                input_tokens: row.get("input_tokens"),
+                output_tokens: row.get("output_tokens"),
+                requests: row.get("requests"),
+            })
+            .collect())
+    }
+
+    /// Per-channel totals since `since_day` (inclusive), most expensive first.
+    pub async fn channel_totals(&self, since_day: &str) -> Result<Vec<ChannelSpend>> {
+        let rows = sqlx::query(
+            "SELECT channel_id, SUM(cost_usd) AS cost_usd, COUNT(*) AS requests \
+             FROM llm_spend WHERE day >= ? AND channel_id IS NOT NULL \
+             GROUP BY channel_id ORDER BY cost_usd DESC",
+        )
+        .bind(since_day)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| ChannelSpend {
+                channel_id: row.get("channel_id"),
+                cost_usd: row.get("cost_usd"),
+                requests: row.get("requests"),
+            })
+            .collect())
+    }
+}
+
+async fn insert_spend(
+    pool: &SqlitePool,
+    day: &str,
+    record: &SpendRecord,
+    cost_usd: f64,
+) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO llm_spend \
+         (day, channel_id, process_type, model, input_tokens, output_tokens, cached_input_tokens, cost_usd) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(day)
+    .bind(record.channel_id.as_deref())
+    .bind(&record.process_type)
+    .bind(&record.model)
+    .bind(record.input_tokens as i64)
+    .bind(record.output_tokens as i64)
+    .bind(record.cached_input_tokens as i64)
+    .bind(cost_usd)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Attribution carried by a `SpacebotModel` so completions can be billed to
+/// the right agent, channel, and process.
+#[derive(Debug, Clone)]
+pub struct SpendRecorder {
+    tracker: Arc<SpendTracker>,
+    channel_id: Option<String>,
+    process_type: String,
+}
+
+impl SpendRecorder {
+    pub fn new(
+        tracker: Arc<SpendTracker>,
+        channel_id: Option<String>,
+        process_type: impl Into<String>,
+    ) -> Self {
+        Self {
+            tracker,
+            channel_id,
+            process_type: process_type.into(),
+        }
+    }
+
+    pub fn record(&self, model: &str, usage: &rig::completion::Usage) {
+        if usage.input_tokens == 0 && usage.output_tokens == 0 {
+            return;
+        }
+        self.tracker.record(SpendRecord {
+            channel_id: self.channel_id.clone(),
+            process_type: self.process_type.clone(),
+            model: model.to_string(),
+            input_tokens: usage.input_tokens,
+            output_tokens: usage.output_tokens,
+            cached_input_tokens: usage.cached_input_tokens,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(daily_usd: Option<f64>, monthly_usd: Option<f64>) -> BudgetLimits {
+        BudgetLimits {
+            daily_usd,
+            monthly_usd,
+        }
+    }
+
+    #[test]
+    fn evaluate_limits_ignores_spend_below_soft_ratio() {
+        assert_eq!(
+            evaluate_limits(limits(Some(10.0), Some(100.0)), 5.0, 50.0, 0.8),
+            None
+        );
+    }
+
+    #[test]
+    fn evaluate_limits_reports_soft_breach() {
+        let breach = evaluate_limits(limits(Some(10.0), None), 8.5, 0.0, 0.8).unwrap();
+        assert_eq!(breach.period, BudgetPeriod::Daily);
+        assert_eq!(breach.severity, BreachSeverity::Soft);
+    }
+
+    #[test]
+    fn evaluate_limits_prefers_hard_breach() {
+        // Daily is only soft, monthly is exhausted.
+        let breach = evaluate_limits(limits(Some(10.0), Some(100.0)), 9.0, 100.5, 0.8).unwrap();
+        assert_eq!(breach.period, BudgetPeriod::Monthly);
+        assert_eq!(breach.severity, BreachSeverity::Hard);
+    }
+
+    #[test]
+    fn evaluate_limits_picks_closest_soft_breach() {
+        let breach = evaluate_limits(limits(Some(10.0), Some(100.0)), 8.0, 95.0, 0.8).unwrap();
+        assert_eq!(breach.period, BudgetPeriod::Monthly);
+        assert_eq!(breach.severity, BreachSeverity::Soft);
+    }
+
+    #[test]
+    fn budget_exceeded_user_message_mentions_reset() {
+        let exceeded = BudgetExceeded {
+            scope: BudgetScope::Agent,
+            period: BudgetPeriod::Daily,
+            spent_usd: 5.2,
+            limit_usd: 5.0,
+        };
+        assert!(exceeded.user_message().contains("$5.00"));
+        assert!(exceeded.user_message().contains("tomorrow"));
+        assert_eq!(
+            exceeded.to_string(),
+            "daily budget exceeded for agent: spent $5.20 of $5.00"
+        );
+    }
+
+    async fn test_tracker(budget: BudgetConfig) -> (Arc<SpendTracker>, tempfile::TempDir) {
+        let instance_dir = tempfile::tempdir().unwrap();
+        let config = crate::config::Config::load_from_env(instance_dir.path()).unwrap();
+        let resolved = config.resolve_agents().into_iter().next().unwrap();
+        let runtime_config = Arc::new(RuntimeConfig::new(
+            instance_dir.path(),
+            &resolved,
+            &config.defaults,
+            crate::prompts::PromptEngine::new("en").unwrap(),
+            crate::identity::Identity::default(),
+            crate::skills::SkillSet::default(),
+        ));
+        runtime_config.budget.store(Arc::new(budget));
+
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let tracker = SpendTracker::new("main", pool, Tz::UTC, runtime_config);
+        (tracker, instance_dir)
+    }
+
+    fn record(channel_id: Option<&str>) -> SpendRecord {
+        SpendRecord {
+            channel_id: channel_id.map(str::to_string),
+            process_type: "channel".to_string(),
+            model: "anthropic/claude-sonnet-4-20250514".to_string(),
+            input_tokens: 1000,
+            output_tokens: 500,
+            cached_input_tokens: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn ledger_totals_filter_by_day_and_channel() {
+        let (tracker, _instance_dir) = test_tracker(BudgetConfig::default()).await;
+        let pool = &tracker.pool;
+        insert_spend(pool, "2026-03-01", &record(Some("discord:1")), 1.0)
+            .await
+            .unwrap();
+        insert_spend(pool, "2026-03-02", &record(Some("discord:1")), 2.0)
+            .await
+            .unwrap();
+        insert_spend(pool, "2026-03-02", &record(None), 4.0)
+            .await
+            .unwrap();
+
+        let channel = tracker
+            .spent_since("2026-03-02", Some("discord:1"))
+            .await
+            .unwrap();
+        assert!((channel - 2.0).abs() < 1e-9);
+        let all = tracker.spent_since("2026-03-01", None).await.unwrap();
+        assert!((all - 7.0).abs() < 1e-9);
+
+        let days = tracker.daily_totals("2026-03-01").await.unwrap();
+        assert_eq!(days.len(), 2);
+        assert!((days[1].cost_usd - 6.0).abs() < 1e-9);
+        let channels = tracker.channel_totals("2026-03-01").await.unwrap();
+        assert_eq!(channels.len(), 1);
+        assert_eq!(channels[0].requests, 2);
+    }
+
+    #[tokio::test]
+    async fn check_downgrades_then_stops() {
+        let budget = BudgetConfig {
+            limits: limits(Some(10.0), None),
+            downgrade_model: Some("anthropic/claude-haiku-4.5".to_string()),
+            ..BudgetConfig::default()
+        };
+        let (tracker, _instance_dir) = test_tracker(budget).await;
+        let today = tracker.today();
+
+        assert_eq!(tracker.check(None).await, BudgetDecision::Allow);
+
+        insert_spend(&tracker.pool, &today, &record(None), 8.5)
+            .await
+            .unwrap();
+        assert_eq!(
+            tracker.check(None).await,
+            BudgetDecision::Downgrade {
+                model: "anthropic/claude-haiku-4.5".to_string()
+            }
+        );
+
+        insert_spend(&tracker.pool, &today, &record(None), 2.0)
+            .await
+            .unwrap();
+        let BudgetDecision::Stop(exceeded) = tracker.check(None).await else {
+            panic!("expected hard stop");
+        };
+        assert_eq!(exceeded.scope, BudgetScope::Agent);
+        assert_eq!(exceeded.period, BudgetPeriod::Daily);
+    }
+
+    #[tokio::test]
+    async fn check_applies_cron_limits_to_cron_channels() {
+        let mut budget = BudgetConfig::default();
+        budget
+            .cron
+            .insert("digest".to_string(), limits(Some(1.0), None));
+        let (tracker, _instance_dir) = test_tracker(budget).await;
+        let today = tracker.today();
+
+        insert_spend(&tracker.pool, &today, &record(Some("cron:digest")), 1.5)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            tracker.check(Some("discord:1")).await,
+            BudgetDecision::Allow
+        );
+        let BudgetDecision::Stop(exceeded) = tracker.check(Some("cron:digest")).await else {
+            panic!("expected cron job to be stopped");
+        };
+        assert_eq!(exceeded.scope, BudgetScope::Cron("digest".to_string()));
+    }
+}
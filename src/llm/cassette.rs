@@ -0,0 +1,733 @@
+//! Record/replay cassettes for LLM calls.
+//!
+//! In record mode every provider call made by `SpacebotModel` is written to a
+//! JSON cassette file, including each streamed event and any error. In replay
+//! mode the same calls are served from those files without touching the
+//! network, so tests can drive whole conversations, tool loops, retries, and
+//! fallbacks offline.
+//!
+//! Cassettes are keyed by a hash of the model name, whether the call streamed,
+//! and the request itself. UUIDs and date-times in the request are masked
+//! first, so prompts that embed the current time or generated IDs still hit
+//! the same cassette. A cassette holds every call made with its key, in
+//! order, so a retry that failed and then succeeded replays the same way.
+//! Enable per provider with
+//! `cassette = { mode = "replay", dir = "tests/cassettes" }`, or for every
+//! provider with `SPACEBOT_LLM_CASSETTE=record|replay|off` (and optionally
+//! `SPACEBOT_LLM_CASSETTE_DIR`).
+
+use crate::config::{CassetteConfig, CassetteMode};
+use crate::llm::model::{RawResponse, RawStreamingResponse};
+
+use futures::StreamExt as _;
+use regex::Regex;
+use rig::completion::{self, CompletionError, CompletionRequest};
+use rig::message::{AssistantContent, Reasoning, ToolCall};
+use rig::one_or_many::OneOrMany;
+use rig::streaming::{
+    RawStreamingChoice, RawStreamingToolCall, StreamedAssistantContent,
+    StreamingCompletionResponse, ToolCallDeltaContent,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest as _, Sha256};
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+
+/// Overrides the cassette mode for every provider: `record`, `replay`, or `off`.
+pub const CASSETTE_MODE_ENV: &str = "SPACEBOT_LLM_CASSETTE";
+/// Overrides the cassette directory for every provider.
+pub const CASSETTE_DIR_ENV: &str = "SPACEBOT_LLM_CASSETTE_DIR";
+
+const DEFAULT_CASSETTE_DIR: &str = "tests/cassettes";
+const CASSETTE_VERSION: u32 = 2;
+
+/// Calls made so far with each cassette file in this process, per mode. The
+/// count is the sequence index of the next call with that key.
+static SEQUENCES: LazyLock<Mutex<HashMap<(bool, PathBuf), usize>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+/// Serializes the read-modify-write of cassette files while recording.
+static RECORD_LOCK: LazyLock<tokio::sync::Mutex<()>> =
+    LazyLock::new(|| tokio::sync::Mutex::new(()));
+
+static UUID_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}\b")
+        .expect("hardcoded regex")
+});
+static DATETIME_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"\b\d{4}-\d{2}-\d{2}[T ]\d{2}:\d{2}(?::\d{2}(?:\.\d+)?)?(?:Z|[+-]\d{2}:?\d{2}|\s[A-Z]{2,5}\b)?",
+    )
+    .expect("hardcoded regex")
+});
+
+/// A resolved cassette store for one provider call.
+#[derive(Debug, Clone)]
+pub struct Cassette {
+    pub mode: CassetteMode,
+    pub dir: PathBuf,
+}
+
+/// Identifies one recorded call.
+#[derive(Debug, Clone)]
+pub struct CassetteKey {
+    pub hash: String,
+    model: String,
+    request: serde_json::Value,
+    /// Position of this call among the calls made with the same hash.
+    sequence: usize,
+}
+
+/// On-disk cassette format.
+#[derive(Debug, Serialize, Deserialize)]
+struct CassetteFile {
+    version: u32,
+    model: String,
+    /// The normalized request, kept for reviewing diffs.
+    request: serde_json::Value,
+    /// Every call made with this key, in call order.
+    interactions: Vec<Interaction>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Interaction {
+    Completion {
+        choice: Box<OneOrMany<AssistantContent>>,
+        usage: completion::Usage,
+        message_id: Option<String>,
+        raw_response: serde_json::Value,
+    },
+    Stream {
+        events: Vec<StreamEvent>,
+    },
+    Error {
+        message: String,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum StreamEvent {
+    Text {
+        text: String,
+    },
+    ToolCall {
+        tool_call: ToolCall,
+        internal_call_id: String,
+    },
+    ToolCallDelta {
+        id: String,
+        internal_call_id: String,
+        content: ToolCallDeltaContent,
+    },
+    Reasoning {
+        reasoning: Reasoning,
+    },
+    ReasoningDelta {
+        id: Option<String>,
+        reasoning: String,
+    },
+    MessageId {
+        id: String,
+    },
+    Final {
+        response: RawStreamingResponse,
+    },
+    Error {
+        message: String,
+    },
+}
+
+impl StreamEvent {
+    fn from_streamed(content: StreamedAssistantContent<RawStreamingResponse>) -> Self {
+        match content {
+            StreamedAssistantContent::Text(text) => Self::Text { text: text.text },
+            StreamedAssistantContent::ToolCall {
+                tool_call,
+                internal_call_id,
+            } => Self::ToolCall {
+                tool_call,
+                internal_call_id,
+            },
+            StreamedAssistantContent::ToolCallDelta {
+                id,
+                internal_call_id,
+                content,
+            } => Self::ToolCallDelta {
+                id,
+                internal_call_id,
+                content,
+            },
+            StreamedAssistantContent::Reasoning(reasoning) => Self::Reasoning { reasoning },
+            StreamedAssistantContent::ReasoningDelta { id, reasoning } => {
+                Self::ReasoningDelta { id, reasoning }
+            }
+            StreamedAssistantContent::Final(response) => Self::Final { response },
+        }
+    }
+
+    /// The raw events that reproduce this one when fed back through rig.
+    fn to_raw(&self) -> Vec<Result<RawStreamingChoice<RawStreamingResponse>, CompletionError>> {
+        let choice = match self {
+            Self::Text { text } => RawStreamingChoice::Message(text.clone()),
+            Self::ToolCall {
+                tool_call,
+                internal_call_id,
+            } => RawStreamingChoice::ToolCall(RawStreamingToolCall {
+                id: tool_call.id.clone(),
+                internal_call_id: internal_call_id.clone(),
+                call_id: tool_call.call_id.clone(),
+                name: tool_call.function.name.clone(),
+                arguments: tool_call.function.arguments.clone(),
+                signature: tool_call.signature.clone(),
+                additional_params: tool_call.additional_params.clone(),
+            }),
+            Self::ToolCallDelta {
+                id,
+                internal_call_id,
+                content,
+            } => RawStreamingChoice::ToolCallDelta {
+                id: id.clone(),
+                internal_call_id: internal_call_id.clone(),
+                content: content.clone(),
+            },
+            Self::Reasoning { reasoning } => {
+                return reasoning
+                    .content
+                    .iter()
+                    .map(|content| {
+                        Ok(RawStreamingChoice::Reasoning {
+                            id: reasoning.id.clone(),
+                            content: content.clone(),
+                        })
+                    })
+                    .collect();
+            }
+            Self::ReasoningDelta { id, reasoning } => RawStreamingChoice::ReasoningDelta {
+                id: id.clone(),
+                reasoning: reasoning.clone(),
+            },
+            Self::MessageId { id } => RawStreamingChoice::MessageId(id.clone()),
+            Self::Final { response } => RawStreamingChoice::FinalResponse(response.clone()),
+            Self::Error { message } => {
+                return vec![Err(CompletionError::ProviderError(message.clone()))];
+            }
+        };
+        vec![Ok(choice)]
+    }
+}
+
+impl Cassette {
+    /// Resolve the cassette for a provider. The environment overrides the
+    /// provider's own settings; `None` means calls go straight to the provider.
+    pub fn resolve(config: Option<&CassetteConfig>) -> Result<Option<Self>, CompletionError> {
+        let env_dir = std::env::var_os(CASSETTE_DIR_ENV).map(PathBuf::from);
+        let mode = match std::env::var(CASSETTE_MODE_ENV).ok().as_deref() {
+            None | Some("") => config.map(|config| config.mode),
+            Some("off") => None,
+            Some("record") => Some(CassetteMode::Record),
+            Some("replay") => Some(CassetteMode::Replay),
+            Some(other) => {
+                return Err(CompletionError::ProviderError(format!(
+                    "invalid {CASSETTE_MODE_ENV} value '{other}' (expected record, replay, or off)"
+                )));
+            }
+        };
+
+        Ok(mode.map(|mode| Self {
+            mode,
+            dir: env_dir
+                .or_else(|| config.map(|config| config.dir.clone()))
+                .unwrap_or_else(|| PathBuf::from(DEFAULT_CASSETTE_DIR)),
+        }))
+    }
+
+    /// Compute the key for a call to `model` with `request`. Each call takes
+    /// the next sequence index for its key, so only call this once per call.
+    pub fn key(&self, model: &str, request: &CompletionRequest, stream: bool) -> CassetteKey {
+        let request = normalize_request(request);
+        let mut hasher = Sha256::new();
+        hasher.update(model.as_bytes());
+        hasher.update([u8::from(stream)]);
+        hasher.update(request.to_string().as_bytes());
+
+        let mut key = CassetteKey {
+            hash: hex::encode(hasher.finalize()),
+            model: model.to_string(),
+            request,
+            sequence: 0,
+        };
+        let mut sequences = SEQUENCES.lock().expect("cassette sequence lock poisoned");
+        let next = sequences
+            .entry((self.mode == CassetteMode::Record, self.path(&key)))
+            .or_insert(0);
+        key.sequence = *next;
+        *next += 1;
+        key
+    }
+
+    /// Path of the cassette file for `key`.
+    pub fn path(&self, key: &CassetteKey) -> PathBuf {
+        let model: String = key
+            .model
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        self.dir.join(format!("{model}-{}.json", &key.hash[..16]))
+    }
+
+    /// Serve a completion from its cassette.
+    pub async fn replay_completion(
+        &self,
+        key: &CassetteKey,
+    ) -> Result<completion::CompletionResponse<RawResponse>, CompletionError> {
+        match self.load(key).await? {
+            Interaction::Completion {
+                choice,
+                usage,
+                message_id,
+                raw_response,
+            } => Ok(completion::CompletionResponse {
+                choice: *choice,
+                usage,
+                message_id,
+                raw_response: RawResponse { body: raw_response },
+            }),
+            Interaction::Error { message } => Err(CompletionError::ProviderError(message)),
+            Interaction::Stream { .. } => Err(self.kind_mismatch(key, "a stream")),
+        }
+    }
+
+    /// Write a completion result (or error) to its cassette.
+    pub async fn record_completion(
+        &self,
+        key: &CassetteKey,
+        result: &Result<completion::CompletionResponse<RawResponse>, CompletionError>,
+    ) {
+        let interaction = match result {
+            Ok(response) => Interaction::Completion {
+                choice: Box::new(response.choice.clone()),
+                usage: response.usage,
+                message_id: response.message_id.clone(),
+                raw_response: response.raw_response.body.clone(),
+            },
+            Err(error) => Interaction::Error {
+                message: error_message(error),
+            },
+        };
+        self.save(key, interaction).await;
+    }
+
+    /// Serve a streaming call from its cassette, replaying every event in
+    /// its original order.
+    pub async fn replay_stream(
+        &self,
+        key: &CassetteKey,
+    ) -> Result<StreamingCompletionResponse<RawStreamingResponse>, CompletionError> {
+        let events = match self.load(key).await? {
+            Interaction::Stream { events } => events,
+            Interaction::Error { message } => return Err(CompletionError::ProviderError(message)),
+            Interaction::Completion { .. } => return Err(self.kind_mismatch(key, "a completion")),
+        };
+
+        let stream = futures::stream::iter(
+            events
+                .iter()
+                .flat_map(StreamEvent::to_raw)
+                .collect::<Vec<_>>(),
+        );
+        Ok(StreamingCompletionResponse::stream(Box::pin(stream)))
+    }
+
+    /// Pass a provider stream through unchanged, writing its events to the
+    /// cassette once it ends. A stream dropped before the end isn't recorded.
+    pub async fn record_stream(
+        &self,
+        key: CassetteKey,
+        result: Result<StreamingCompletionResponse<RawStreamingResponse>, CompletionError>,
+    ) -> Result<StreamingCompletionResponse<RawStreamingResponse>, CompletionError> {
+        let mut inner = match result {
+            Ok(inner) => inner,
+            Err(error) => {
+                let message = error_message(&error);
+                self.save(&key, Interaction::Error { message }).await;
+                return Err(error);
+            }
+        };
+
+        let cassette = self.clone();
+        let stream = async_stream::stream! {
+            let mut events = Vec::new();
+            while let Some(item) = inner.next().await {
+                match item {
+                    Ok(content) => {
+                        let event = StreamEvent::from_streamed(content);
+                        for raw in event.to_raw() {
+                            yield raw;
+                        }
+                        events.push(event);
+                    }
+                    Err(error) => {
+                        events.push(StreamEvent::Error { message: error_message(&error) });
+                        cassette.save(&key, Interaction::Stream { events }).await;
+                        yield Err(error);
+                        return;
+                    }
+                }
+            }
+            // rig swallows message IDs into the response, so re-emit it.
+            if let Some(id) = inner.message_id.clone() {
+                yield Ok(RawStreamingChoice::MessageId(id.clone()));
+                events.push(StreamEvent::MessageId { id });
+            }
+            cassette.save(&key, Interaction::Stream { events }).await;
+        };
+
+        Ok(StreamingCompletionResponse::stream(Box::pin(stream)))
+    }
+
+    async fn load(&self, key: &CassetteKey) -> Result<Interaction, CompletionError> {
+        let path = self.path(key);
+        let contents = match tokio::fs::read_to_string(&path).await {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                tracing::warn!(
+                    model = %key.model,
+                    path = %path.display(),
+                    request = %key.request,
+                    "no cassette recorded for request"
+                );
+                return Err(CompletionError::ProviderError(format!(
+                    "no cassette recorded for {} in replay mode (expected {})",
+                    key.model,
+                    path.display()
+                )));
+            }
+            Err(error) => {
+                return Err(CompletionError::ProviderError(format!(
+                    "failed to read cassette {}: {error}",
+                    path.display()
+                )));
+            }
+        };
+
+        let mut file: CassetteFile = serde_json::from_str(&contents).map_err(|error| {
+            CompletionError::ProviderError(format!("invalid cassette {}: {error}", path.display()))
+        })?;
+        // Calls past the end of the recording repeat its last interaction.
+        let index = key.sequence.min(file.interactions.len().saturating_sub(1));
+        if index >= file.interactions.len() {
+            return Err(CompletionError::ProviderError(format!(
+                "cassette {} has no recorded calls",
+                path.display()
+            )));
+        }
+        Ok(file.interactions.swap_remove(index))
+    }
+
+    async fn save(&self, key: &CassetteKey, interaction: Interaction) {
+        let path = self.path(key);
+        let _guard = RECORD_LOCK.lock().await;
+
+        // The first call with a key this run starts a fresh recording; later
+        // ones extend it.
+        let mut interactions = Vec::new();
+        if key.sequence > 0
+            && let Ok(contents) = tokio::fs::read_to_string(&path).await
+            && let Ok(file) = serde_json::from_str::<CassetteFile>(&contents)
+        {
+            interactions = file.interactions;
+        }
+        if key.sequence < interactions.len() {
+            interactions[key.sequence] = interaction;
+        } else {
+            interactions.push(interaction);
+        }
+
+        let file = CassetteFile {
+            version: CASSETTE_VERSION,
+            model: key.model.clone(),
+            request: key.request.clone(),
+            interactions,
+        };
+        if let Err(error) = write_cassette(&path, &file).await {
+            tracing::warn!(path = %path.display(), %error, "failed to write cassette");
+        }
+    }
+
+    fn kind_mismatch(&self, key: &CassetteKey, recorded: &str) -> CompletionError {
+        CompletionError::ProviderError(format!(
+            "cassette {} recorded {recorded} for a different kind of call",
+            self.path(key).display()
+        ))
+    }
+}
+
+async fn write_cassette(path: &Path, file: &CassetteFile) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let mut contents = serde_json::to_string_pretty(file)?;
+    contents.push('\n');
+    tokio::fs::write(path, contents).await?;
+    Ok(())
+}
+
+/// The message to replay for an error. Provider errors keep their bare
+/// message so retry classification sees the same text on replay.
+fn error_message(error: &CompletionError) -> String {
+    match error {
+        CompletionError::ProviderError(message) => message.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Serialize the parts of a request that affect the response, with object
+/// keys sorted and volatile values masked.
+fn normalize_request(request: &CompletionRequest) -> serde_json::Value {
+    let mut value = serde_json::json!({
+        "preamble": request.preamble,
+        "chat_history": request.chat_history,
+        "documents": request.documents,
+        "tools": request.tools,
+        "temperature": request.temperature,
+        "max_tokens": request.max_tokens,
+        "tool_choice": request.tool_choice,
+        "additional_params": request.additional_params,
+        "output_schema": request.output_schema,
+    });
+    mask_volatile_values(&mut value);
+    value.sort_all_objects();
+    value
+}
+
+fn mask_volatile_values(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::String(text) => {
+            let masked = UUID_PATTERN.replace_all(text, "<uuid>");
+            let masked = DATETIME_PATTERN.replace_all(&masked, "<datetime>");
+            if masked != text.as_str() {
+                *text = masked.into_owned();
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(mask_volatile_values),
+        serde_json::Value::Object(map) => map.values_mut().for_each(mask_volatile_values),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rig::message::{Message, Text};
+
+    fn request(prompt: &str) -> CompletionRequest {
+        CompletionRequest {
+            model: None,
+            preamble: Some("You are a bot.".into()),
+            chat_history: OneOrMany::one(Message::user(prompt)),
+            documents: Vec::new(),
+            tools: Vec::new(),
+            temperature: None,
+            max_tokens: None,
+            tool_choice: None,
+            additional_params: None,
+            output_schema: None,
+        }
+    }
+
+    fn cassette(mode: CassetteMode, dir: &Path) -> Cassette {
+        Cassette {
+            mode,
+            dir: dir.to_path_buf(),
+        }
+    }
+
+    #[test]
+    fn keys_ignore_volatile_values_but_not_content() {
+        let cassette = cassette(CassetteMode::Replay, Path::new("cassettes"));
+        let first = cassette.key(
+            "anthropic/claude",
+            &request("It is 2026-10-17 14:03:00 PDT, job 0b8e7f3a-1c2d-4e5f-8a9b-0c1d2e3f4a5b"),
+            false,
+        );
+        let second = cassette.key(
+            "anthropic/claude",
+            &request("It is 2026-10-18 09:15:42 UTC, job 9f8e7d6c-5b4a-4392-8170-6f5e4d3c2b1a"),
+            false,
+        );
+        assert_eq!(first.hash, second.hash);
+        assert_eq!(
+            first.request["chat_history"][0]["content"][0]["text"],
+            "It is <datetime>, job <uuid>"
+        );
+
+        let other_prompt = cassette.key("anthropic/claude", &request("hello"), false);
+        let streamed = cassette.key("anthropic/claude", &request("hello"), true);
+        let other_model = cassette.key("openai/gpt-4o", &request("hello"), false);
+        assert_ne!(first.hash, other_prompt.hash);
+        assert_ne!(other_prompt.hash, streamed.hash);
+        assert_ne!(other_prompt.hash, other_model.hash);
+        assert!(
+            cassette
+                .path(&other_model)
+                .to_string_lossy()
+                .starts_with("cassettes/openai_gpt-4o-")
+        );
+    }
+
+    #[tokio::test]
+    async fn completions_and_errors_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let recorder = cassette(CassetteMode::Record, dir.path());
+        let key = recorder.key("anthropic/claude", &request("hi"), false);
+
+        let response = completion::CompletionResponse {
+            choice: OneOrMany::one(AssistantContent::Text(Text {
+                text: "hello".into(),
+            })),
+            usage: completion::Usage {
+                input_tokens: 3,
+                output_tokens: 1,
+                total_tokens: 4,
+                cached_input_tokens: 0,
+            },
+            message_id: Some("msg_1".into()),
+            raw_response: RawResponse {
+                body: serde_json::json!({"id": "msg_1"}),
+            },
+        };
+        recorder.record_completion(&key, &Ok(response)).await;
+
+        let replayed = cassette(CassetteMode::Replay, dir.path())
+            .replay_completion(&key)
+            .await
+            .unwrap();
+        assert!(
+            matches!(replayed.choice.first(), AssistantContent::Text(text) if text.text == "hello")
+        );
+        assert_eq!(replayed.usage.total_tokens, 4);
+        assert_eq!(replayed.message_id.as_deref(), Some("msg_1"));
+
+        recorder
+            .record_completion(
+                &key,
+                &Err(CompletionError::ProviderError(
+                    "503 Service Unavailable".into(),
+                )),
+            )
+            .await;
+        let error = recorder.replay_completion(&key).await.unwrap_err();
+        assert!(
+            matches!(error, CompletionError::ProviderError(message) if message == "503 Service Unavailable")
+        );
+    }
+
+    #[tokio::test]
+    async fn retries_replay_in_recorded_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let recorder = cassette(CassetteMode::Record, dir.path());
+        let failed = recorder.key("anthropic/claude", &request("retry me"), false);
+        recorder
+            .record_completion(
+                &failed,
+                &Err(CompletionError::ProviderError("529 Overloaded".into())),
+            )
+            .await;
+        let succeeded = recorder.key("anthropic/claude", &request("retry me"), false);
+        let response = completion::CompletionResponse {
+            choice: OneOrMany::one(AssistantContent::Text(Text {
+                text: "done".into(),
+            })),
+            usage: completion::Usage::new(),
+            message_id: None,
+            raw_response: RawResponse {
+                body: serde_json::json!({}),
+            },
+        };
+        recorder.record_completion(&succeeded, &Ok(response)).await;
+        assert_eq!(failed.hash, succeeded.hash);
+
+        let player = cassette(CassetteMode::Replay, dir.path());
+        let first = player.key("anthropic/claude", &request("retry me"), false);
+        let error = player.replay_completion(&first).await.unwrap_err();
+        assert!(
+            matches!(error, CompletionError::ProviderError(message) if message == "529 Overloaded")
+        );
+        for _ in 0..2 {
+            let retry = player.key("anthropic/claude", &request("retry me"), false);
+            let replayed = player.replay_completion(&retry).await.unwrap();
+            assert!(
+                matches!(replayed.choice.first(), AssistantContent::Text(text) if text.text == "done")
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn streams_round_trip_through_cassettes() {
+        let dir = tempfile::tempdir().unwrap();
+        let recorder = cassette(CassetteMode::Record, dir.path());
+        let key = recorder.key("anthropic/claude", &request("hi"), true);
+
+        let source = futures::stream::iter(vec![
+            Ok(RawStreamingChoice::Message("Hel".into())),
+            Ok(RawStreamingChoice::Message("lo".into())),
+            Ok(RawStreamingChoice::ToolCall(RawStreamingToolCall {
+                id: "call_1".into(),
+                internal_call_id: "internal_1".into(),
+                call_id: None,
+                name: "reply".into(),
+                arguments: serde_json::json!({"content": "hi"}),
+                signature: None,
+                additional_params: None,
+            })),
+            Ok(RawStreamingChoice::FinalResponse(RawStreamingResponse {
+                body: serde_json::json!({}),
+                usage: None,
+            })),
+        ]);
+        let recorded = recorder
+            .record_stream(
+                key.clone(),
+                Ok(StreamingCompletionResponse::stream(Box::pin(source))),
+            )
+            .await
+            .unwrap();
+        let recorded: Vec<_> = recorded.collect().await;
+        assert_eq!(recorded.len(), 4);
+
+        let mut replayed = cassette(CassetteMode::Replay, dir.path())
+            .replay_stream(&key)
+            .await
+            .unwrap();
+        while let Some(item) = replayed.next().await {
+            item.unwrap();
+        }
+        let choice: Vec<_> = replayed.choice.into_iter().collect();
+        assert!(matches!(&choice[0], AssistantContent::Text(text) if text.text == "Hello"));
+        assert!(
+            matches!(&choice[1], AssistantContent::ToolCall(call) if call.function.name == "reply")
+        );
+    }
+
+    #[tokio::test]
+    async fn replay_without_cassette_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let cassette = cassette(CassetteMode::Replay, dir.path());
+        let key = cassette.key("anthropic/claude", &request("hi"), false);
+        let error = cassette.replay_completion(&key).await.unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("no cassette recorded for anthropic/claude")
+        );
+    }
+}
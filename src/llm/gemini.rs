@@ -0,0 +1,10 @@
+//! Native Google Gemini and Vertex AI integration: request building, response
+//! parsing, and service-account auth for Vertex.
+
+pub mod auth;
+pub mod params;
+pub mod response;
+
+pub use auth::{ServiceAccountKey, VertexAccessToken, exchange_service_account_token};
+pub use params::{build_gemini_body, generate_content_url};
+pub use response::{GeminiStreamState, parse_gemini_response, process_gemini_stream_chunk};
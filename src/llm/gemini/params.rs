@@ -0,0 +1,426 @@
+//! Build native Gemini `generateContent` request bodies from a CompletionRequest.
+
+use base64::Engine as _;
+use rig::completion::CompletionRequest;
+use rig::message::{
+    AssistantContent, DocumentSourceKind, Message, MimeType, ToolChoice, UserContent,
+};
+use rig::one_or_many::OneOrMany;
+use std::collections::HashMap;
+
+/// Construct the `generateContent` (or `streamGenerateContent`) URL for a model.
+///
+/// `base_url` is either the Gemini API root (`.../v1beta`) or a Vertex AI
+/// publisher path (`.../projects/{project}/locations/{location}/publishers/google`).
+/// A trailing `/openai` left over from the OpenAI-compatible endpoint is
+/// dropped, and a Vertex location path gets `/publishers/google` appended.
+pub fn generate_content_url(base_url: &str, model_name: &str, stream: bool) -> String {
+    let mut base = base_url.trim_end_matches('/');
+    base = base.strip_suffix("/openai").unwrap_or(base);
+    let publisher = if base.contains("/projects/") && !base.contains("/publishers/") {
+        "/publishers/google"
+    } else {
+        ""
+    };
+    let model = model_name.strip_prefix("models/").unwrap_or(model_name);
+
+    if stream {
+        format!("{base}{publisher}/models/{model}:streamGenerateContent?alt=sse")
+    } else {
+        format!("{base}{publisher}/models/{model}:generateContent")
+    }
+}
+
+/// Build the JSON body for a Gemini `generateContent` request.
+///
+/// `thinking_effort` is the routing effort for this model ("auto", "max",
+/// "high", "medium", "low"). Keys in the request's `additional_params` are
+/// merged into the top level of the body.
+pub fn build_gemini_body(
+    model_name: &str,
+    request: &CompletionRequest,
+    thinking_effort: &str,
+) -> serde_json::Value {
+    let mut body = serde_json::json!({
+        "contents": convert_messages_to_gemini(&request.chat_history),
+    });
+
+    if let Some(preamble) = request.preamble.as_deref().filter(|p| !p.trim().is_empty()) {
+        body["systemInstruction"] = serde_json::json!({ "parts": [{ "text": preamble }] });
+    }
+
+    if !request.tools.is_empty() {
+        let declarations: Vec<serde_json::Value> = request
+            .tools
+            .iter()
+            .map(|tool| {
+                serde_json::json!({
+                    "name": tool.name,
+                    "description": tool.description,
+                    "parametersJsonSchema": tool.parameters,
+                })
+            })
+            .collect();
+        body["tools"] = serde_json::json!([{ "functionDeclarations": declarations }]);
+
+        if let Some(tool_choice) = &request.tool_choice {
+            body["toolConfig"] = tool_config(tool_choice);
+        }
+    }
+
+    let mut generation_config = serde_json::Map::new();
+    if let Some(max_tokens) = request.max_tokens {
+        generation_config.insert("maxOutputTokens".into(), serde_json::json!(max_tokens));
+    }
+    if let Some(temperature) = request.temperature {
+        generation_config.insert("temperature".into(), serde_json::json!(temperature));
+    }
+    if let Some(thinking) = thinking_config(model_name, thinking_effort) {
+        generation_config.insert("thinkingConfig".into(), thinking);
+    }
+    if !generation_config.is_empty() {
+        body["generationConfig"] = serde_json::Value::Object(generation_config);
+    }
+
+    if let Some(serde_json::Value::Object(extra)) = &request.additional_params {
+        for (key, value) in extra {
+            body[key] = value.clone();
+        }
+    }
+
+    body
+}
+
+fn tool_config(tool_choice: &ToolChoice) -> serde_json::Value {
+    let config = match tool_choice {
+        ToolChoice::Auto => serde_json::json!({ "mode": "AUTO" }),
+        ToolChoice::None => serde_json::json!({ "mode": "NONE" }),
+        ToolChoice::Required => serde_json::json!({ "mode": "ANY" }),
+        ToolChoice::Specific { function_names } => serde_json::json!({
+            "mode": "ANY",
+            "allowedFunctionNames": function_names,
+        }),
+    };
+    serde_json::json!({ "functionCallingConfig": config })
+}
+
+/// Map the routing thinking effort onto Gemini's `thinkingConfig`.
+///
+/// Gemini 3 models take a `thinkingLevel` (only "low" and "high" are
+/// available on every model); Gemini 2.5 models take a token budget, where
+/// `-1` lets the model decide. Older models don't support thinking at all.
+fn thinking_config(model_name: &str, effort: &str) -> Option<serde_json::Value> {
+    let model = model_name.to_lowercase();
+
+    if model.contains("gemini-3") {
+        let mut config = serde_json::json!({ "includeThoughts": true });
+        match effort {
+            "max" | "high" => config["thinkingLevel"] = "high".into(),
+            "medium" | "low" => config["thinkingLevel"] = "low".into(),
+            _ => {}
+        }
+        return Some(config);
+    }
+
+    if model.contains("gemini-2.5") {
+        let budget: i64 = match effort {
+            "max" if model.contains("pro") => 32_768,
+            "max" => 24_576,
+            "high" => 16_384,
+            "medium" => 8_192,
+            "low" => 1_024,
+            _ => -1,
+        };
+        return Some(serde_json::json!({
+            "thinkingBudget": budget,
+            "includeThoughts": true,
+        }));
+    }
+
+    None
+}
+
+/// Convert rig chat history into Gemini `contents`.
+///
+/// Gemini has no tool call IDs on the wire, so function responses are named
+/// after the call they answer, looked up by ID from earlier assistant turns.
+/// Consecutive turns with the same role are merged.
+pub(crate) fn convert_messages_to_gemini(messages: &OneOrMany<Message>) -> Vec<serde_json::Value> {
+    let mut tool_names: HashMap<&str, &str> = HashMap::new();
+    for message in messages.iter() {
+        if let Message::Assistant { content, .. } = message {
+            for item in content.iter() {
+                if let AssistantContent::ToolCall(tool_call) = item {
+                    tool_names.insert(&tool_call.id, &tool_call.function.name);
+                    if let Some(call_id) = &tool_call.call_id {
+                        tool_names.insert(call_id, &tool_call.function.name);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut contents: Vec<serde_json::Value> = Vec::new();
+
+    for message in messages.iter() {
+        let (role, parts) = match message {
+            Message::User { content } => {
+                let parts: Vec<serde_json::Value> = content
+                    .iter()
+                    .filter_map(|item| convert_user_content(item, &tool_names))
+                    .collect();
+                ("user", parts)
+            }
+            Message::Assistant { content, .. } => {
+                let parts: Vec<serde_json::Value> = content
+                    .iter()
+                    .filter_map(|item| match item {
+                        AssistantContent::Text(text) => (!text.text.trim().is_empty())
+                            .then(|| serde_json::json!({ "text": text.text })),
+                        AssistantContent::ToolCall(tool_call) => {
+                            let mut part = serde_json::json!({
+                                "functionCall": {
+                                    "name": tool_call.function.name,
+                                    "args": tool_call.function.arguments,
+                                }
+                            });
+                            if let Some(signature) = &tool_call.signature {
+                                part["thoughtSignature"] = signature.clone().into();
+                            }
+                            Some(part)
+                        }
+                        _ => None,
+                    })
+                    .collect();
+                ("model", parts)
+            }
+            Message::System { content } => {
+                let parts = if content.trim().is_empty() {
+                    Vec::new()
+                } else {
+                    vec![serde_json::json!({ "text": content })]
+                };
+                ("user", parts)
+            }
+        };
+
+        if parts.is_empty() {
+            continue;
+        }
+
+        if let Some(last) = contents.last_mut()
+            && last["role"] == role
+            && let Some(existing) = last["parts"].as_array_mut()
+        {
+            existing.extend(parts);
+            continue;
+        }
+        contents.push(serde_json::json!({ "role": role, "parts": parts }));
+    }
+
+    contents
+}
+
+fn convert_user_content(
+    content: &UserContent,
+    tool_names: &HashMap<&str, &str>,
+) -> Option<serde_json::Value> {
+    match content {
+        UserContent::Text(text) => {
+            (!text.text.trim().is_empty()).then(|| serde_json::json!({ "text": text.text }))
+        }
+        UserContent::ToolResult(result) => {
+            let name = result
+                .call_id
+                .as_deref()
+                .and_then(|call_id| tool_names.get(call_id))
+                .or_else(|| tool_names.get(result.id.as_str()))
+                .copied()
+                .unwrap_or(result.id.as_str());
+            Some(serde_json::json!({
+                "functionResponse": {
+                    "name": name,
+                    "response": {
+                        "content": crate::llm::model::tool_result_content_to_string(&result.content),
+                    },
+                }
+            }))
+        }
+        UserContent::Image(image) => {
+            let media_type = image
+                .media_type
+                .as_ref()
+                .map(|media_type| media_type.to_mime_type())
+                .unwrap_or("image/jpeg");
+            convert_media(&image.data, media_type)
+        }
+        UserContent::Document(document) => {
+            let media_type = document
+                .media_type
+                .as_ref()
+                .map(|media_type| media_type.to_mime_type())
+                .unwrap_or("application/pdf");
+            convert_media(&document.data, media_type)
+        }
+        _ => None,
+    }
+}
+
+/// Inline base64 data goes in `inlineData`; URIs (Files API, `gs://`, or
+/// public URLs) go in `fileData` so the media is never re-uploaded.
+fn convert_media(data: &DocumentSourceKind, media_type: &str) -> Option<serde_json::Value> {
+    match data {
+        DocumentSourceKind::Base64(data) => Some(serde_json::json!({
+            "inlineData": { "mimeType": media_type, "data": data }
+        })),
+        DocumentSourceKind::Raw(bytes) => Some(serde_json::json!({
+            "inlineData": {
+                "mimeType": media_type,
+                "data": base64::engine::general_purpose::STANDARD.encode(bytes),
+            }
+        })),
+        DocumentSourceKind::Url(url) => Some(serde_json::json!({
+            "fileData": { "mimeType": media_type, "fileUri": url }
+        })),
+        DocumentSourceKind::String(text) => {
+            (!text.trim().is_empty()).then(|| serde_json::json!({ "text": text }))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rig::completion::ToolDefinition;
+    use rig::message::{Text, ToolCall, ToolFunction, ToolResult, ToolResultContent};
+
+    fn request(chat_history: Vec<Message>) -> CompletionRequest {
+        CompletionRequest {
+            model: None,
+            preamble: Some("be brief".into()),
+            chat_history: OneOrMany::many(chat_history).unwrap(),
+            documents: Vec::new(),
+            tools: vec![ToolDefinition {
+                name: "reply".into(),
+                description: "Send a reply".into(),
+                parameters: serde_json::json!({"type": "object"}),
+            }],
+            temperature: Some(0.2),
+            max_tokens: Some(1024),
+            tool_choice: None,
+            additional_params: None,
+            output_schema: None,
+        }
+    }
+
+    #[test]
+    fn generate_content_url_handles_gemini_and_vertex_bases() {
+        assert_eq!(
+            generate_content_url(
+                "https://generativelanguage.googleapis.com/v1beta/openai/",
+                "gemini-2.5-pro",
+                false
+            ),
+            "https://generativelanguage.googleapis.com/v1beta/models/gemini-2.5-pro:generateContent"
+        );
+        assert_eq!(
+            generate_content_url(
+                "https://us-central1-aiplatform.googleapis.com/v1/projects/p/locations/us-central1",
+                "gemini-2.5-flash",
+                true
+            ),
+            "https://us-central1-aiplatform.googleapis.com/v1/projects/p/locations/us-central1/publishers/google/models/gemini-2.5-flash:streamGenerateContent?alt=sse"
+        );
+    }
+
+    #[test]
+    fn build_body_maps_tools_and_function_responses() {
+        let tool_call = ToolCall {
+            id: "call_1".into(),
+            call_id: None,
+            function: ToolFunction {
+                name: "reply".into(),
+                arguments: serde_json::json!({"content": "hi"}),
+            },
+            signature: Some("sig".into()),
+            additional_params: None,
+        };
+        let body = build_gemini_body(
+            "gemini-2.0-flash",
+            &request(vec![
+                Message::user("hello"),
+                Message::Assistant {
+                    id: None,
+                    content: OneOrMany::one(AssistantContent::ToolCall(tool_call)),
+                },
+                Message::User {
+                    content: OneOrMany::one(UserContent::ToolResult(ToolResult {
+                        id: "call_1".into(),
+                        call_id: None,
+                        content: OneOrMany::one(ToolResultContent::Text(Text {
+                            text: "sent".into(),
+                        })),
+                    })),
+                },
+            ]),
+            "auto",
+        );
+
+        assert_eq!(body["systemInstruction"]["parts"][0]["text"], "be brief");
+        assert_eq!(body["contents"][1]["role"], "model");
+        assert_eq!(
+            body["contents"][1]["parts"][0]["functionCall"]["name"],
+            "reply"
+        );
+        assert_eq!(body["contents"][1]["parts"][0]["thoughtSignature"], "sig");
+        assert_eq!(
+            body["contents"][2]["parts"][0]["functionResponse"]["name"],
+            "reply"
+        );
+        assert_eq!(
+            body["tools"][0]["functionDeclarations"][0]["parametersJsonSchema"]["type"],
+            "object"
+        );
+        assert_eq!(body["generationConfig"]["maxOutputTokens"], 1024);
+        assert!(body["generationConfig"].get("thinkingConfig").is_none());
+    }
+
+    #[test]
+    fn thinking_effort_maps_to_budget_or_level() {
+        let body = build_gemini_body("gemini-2.5-pro", &request(vec![Message::user("hi")]), "max");
+        assert_eq!(
+            body["generationConfig"]["thinkingConfig"]["thinkingBudget"],
+            32_768
+        );
+
+        let body = build_gemini_body(
+            "gemini-2.5-flash",
+            &request(vec![Message::user("hi")]),
+            "auto",
+        );
+        assert_eq!(
+            body["generationConfig"]["thinkingConfig"]["thinkingBudget"],
+            -1
+        );
+
+        let body = build_gemini_body(
+            "gemini-3-pro-preview",
+            &request(vec![Message::user("hi")]),
+            "low",
+        );
+        assert_eq!(
+            body["generationConfig"]["thinkingConfig"]["thinkingLevel"],
+            "low"
+        );
+    }
+
+    #[test]
+    fn consecutive_user_turns_are_merged() {
+        let contents = convert_messages_to_gemini(
+            &OneOrMany::many(vec![Message::user("one"), Message::user("two")]).unwrap(),
+        );
+        assert_eq!(contents.len(), 1);
+        assert_eq!(contents[0]["parts"].as_array().unwrap().len(), 2);
+    }
+}
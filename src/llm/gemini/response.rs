@@ -0,0 +1,351 @@
+//! Parse Gemini `generateContent` responses and `streamGenerateContent` SSE chunks.
+
+use crate::llm::model::{RawResponse, RawStreamingResponse};
+
+use rig::completion::{self, CompletionError};
+use rig::message::{AssistantContent, Reasoning, Text, ToolCall, ToolFunction};
+use rig::one_or_many::OneOrMany;
+use rig::streaming::{RawStreamingChoice, RawStreamingToolCall};
+
+/// Token usage from a response's `usageMetadata`.
+///
+/// Thinking tokens are billed as output, so they are folded into
+/// `output_tokens`. `promptTokenCount` already includes cached tokens.
+pub fn gemini_usage(body: &serde_json::Value) -> completion::Usage {
+    let usage = &body["usageMetadata"];
+    let input_tokens = usage["promptTokenCount"].as_u64().unwrap_or(0);
+    let output_tokens = usage["candidatesTokenCount"].as_u64().unwrap_or(0)
+        + usage["thoughtsTokenCount"].as_u64().unwrap_or(0);
+    let cached_input_tokens = usage["cachedContentTokenCount"].as_u64().unwrap_or(0);
+
+    completion::Usage {
+        input_tokens,
+        output_tokens,
+        total_tokens: input_tokens + output_tokens,
+        cached_input_tokens,
+    }
+}
+
+/// Fail with the block reason when Gemini refused the prompt outright.
+fn check_prompt_feedback(
+    body: &serde_json::Value,
+    provider_label: &str,
+) -> Result<(), CompletionError> {
+    match body["promptFeedback"]["blockReason"].as_str() {
+        Some(reason) => Err(CompletionError::ResponseError(format!(
+            "{provider_label} blocked the prompt (blockReason: {reason})"
+        ))),
+        None => Ok(()),
+    }
+}
+
+fn candidate_parts(body: &serde_json::Value) -> &[serde_json::Value] {
+    body["candidates"][0]["content"]["parts"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+fn function_call_from_part(part: &serde_json::Value) -> Option<ToolCall> {
+    let function_call = part.get("functionCall")?;
+    let id = function_call["id"]
+        .as_str()
+        .map(ToString::to_string)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let arguments = match &function_call["args"] {
+        serde_json::Value::Null => serde_json::json!({}),
+        args => args.clone(),
+    };
+
+    Some(ToolCall {
+        id,
+        call_id: None,
+        function: ToolFunction {
+            name: function_call["name"]
+                .as_str()
+                .unwrap_or("")
+                .trim()
+                .to_string(),
+            arguments,
+        },
+        signature: part["thoughtSignature"].as_str().map(ToString::to_string),
+        additional_params: None,
+    })
+}
+
+fn is_thought(part: &serde_json::Value) -> bool {
+    part["thought"].as_bool().unwrap_or(false)
+}
+
+/// Error for a candidate that ended without any usable content.
+fn empty_response_error(body: &serde_json::Value, provider_label: &str) -> CompletionError {
+    let finish_reason = body["candidates"][0]["finishReason"]
+        .as_str()
+        .unwrap_or("unknown");
+    tracing::warn!(
+        provider = provider_label,
+        finish_reason,
+        "unexpected empty response from Gemini"
+    );
+    CompletionError::ResponseError(format!(
+        "empty response from {provider_label} (finishReason: {finish_reason})"
+    ))
+}
+
+fn finished_normally(body: &serde_json::Value) -> bool {
+    matches!(
+        body["candidates"][0]["finishReason"].as_str(),
+        Some("STOP") | None
+    )
+}
+
+/// Parse a non-streaming `generateContent` response.
+pub fn parse_gemini_response(
+    body: serde_json::Value,
+    provider_label: &str,
+) -> Result<completion::CompletionResponse<RawResponse>, CompletionError> {
+    check_prompt_feedback(&body, provider_label)?;
+
+    let mut assistant_content = Vec::new();
+    for part in candidate_parts(&body) {
+        if let Some(tool_call) = function_call_from_part(part) {
+            assistant_content.push(AssistantContent::ToolCall(tool_call));
+        } else if let Some(text) = part["text"].as_str() {
+            if text.trim().is_empty() {
+                continue;
+            }
+            if is_thought(part) {
+                assistant_content.push(AssistantContent::Reasoning(Reasoning::new(text)));
+            } else {
+                assistant_content.push(AssistantContent::Text(Text {
+                    text: text.to_string(),
+                }));
+            }
+        }
+    }
+
+    let has_output = assistant_content
+        .iter()
+        .any(|content| !matches!(content, AssistantContent::Reasoning(_)));
+    if !has_output {
+        if !finished_normally(&body) {
+            return Err(empty_response_error(&body, provider_label));
+        }
+        // Like Anthropic's empty end_turn: the model had nothing further to
+        // say after a side-effect-only tool call.
+        assistant_content.push(AssistantContent::Text(Text {
+            text: " ".to_string(),
+        }));
+    }
+
+    let choice = OneOrMany::many(assistant_content)
+        .map_err(|_| empty_response_error(&body, provider_label))?;
+
+    Ok(completion::CompletionResponse {
+        choice,
+        usage: gemini_usage(&body),
+        message_id: body["responseId"].as_str().map(ToString::to_string),
+        raw_response: RawResponse { body },
+    })
+}
+
+/// Accumulates `streamGenerateContent` chunks into one response body.
+#[derive(Debug, Default)]
+pub struct GeminiStreamState {
+    parts: Vec<serde_json::Value>,
+    finish_reason: Option<String>,
+    usage_metadata: Option<serde_json::Value>,
+    response_id: Option<String>,
+    emitted_message_id: bool,
+}
+
+impl GeminiStreamState {
+    /// Build the final response event once the stream is exhausted.
+    pub fn finish(
+        self,
+        provider_label: &str,
+    ) -> Result<RawStreamingChoice<RawStreamingResponse>, CompletionError> {
+        let mut candidate = serde_json::json!({
+            "content": { "role": "model", "parts": self.parts },
+        });
+        if let Some(finish_reason) = &self.finish_reason {
+            candidate["finishReason"] = finish_reason.clone().into();
+        }
+        let mut body = serde_json::json!({ "candidates": [candidate] });
+        if let Some(usage_metadata) = self.usage_metadata {
+            body["usageMetadata"] = usage_metadata;
+        }
+        if let Some(response_id) = self.response_id {
+            body["responseId"] = response_id.into();
+        }
+
+        let has_output = candidate_parts(&body).iter().any(|part| {
+            !is_thought(part) && (part.get("functionCall").is_some() || part.get("text").is_some())
+        });
+        if !has_output && !finished_normally(&body) {
+            return Err(empty_response_error(&body, provider_label));
+        }
+
+        Ok(RawStreamingChoice::FinalResponse(RawStreamingResponse {
+            usage: Some(gemini_usage(&body)),
+            body,
+        }))
+    }
+}
+
+/// Convert one SSE chunk into streaming events, recording it in `state`.
+pub fn process_gemini_stream_chunk(
+    chunk: &serde_json::Value,
+    state: &mut GeminiStreamState,
+    provider_label: &str,
+) -> Result<Vec<RawStreamingChoice<RawStreamingResponse>>, CompletionError> {
+    check_prompt_feedback(chunk, provider_label)?;
+
+    let mut events = Vec::new();
+
+    if let Some(response_id) = chunk["responseId"].as_str() {
+        state.response_id = Some(response_id.to_string());
+        if !state.emitted_message_id {
+            state.emitted_message_id = true;
+            events.push(RawStreamingChoice::MessageId(response_id.to_string()));
+        }
+    }
+    if let Some(finish_reason) = chunk["candidates"][0]["finishReason"].as_str() {
+        state.finish_reason = Some(finish_reason.to_string());
+    }
+    if chunk.get("usageMetadata").is_some() {
+        state.usage_metadata = Some(chunk["usageMetadata"].clone());
+    }
+
+    for part in candidate_parts(chunk) {
+        let mut part = part.clone();
+        if let Some(tool_call) = function_call_from_part(&part) {
+            part["functionCall"]["id"] = tool_call.id.clone().into();
+            events.push(RawStreamingChoice::ToolCall(RawStreamingToolCall {
+                internal_call_id: tool_call.id.clone(),
+                id: tool_call.id,
+                call_id: None,
+                name: tool_call.function.name,
+                arguments: tool_call.function.arguments,
+                signature: tool_call.signature,
+                additional_params: None,
+            }));
+        } else if let Some(text) = part["text"].as_str().filter(|text| !text.is_empty()) {
+            if is_thought(&part) {
+                events.push(RawStreamingChoice::ReasoningDelta {
+                    id: None,
+                    reasoning: text.to_string(),
+                });
+            } else {
+                events.push(RawStreamingChoice::Message(text.to_string()));
+            }
+        }
+        state.parts.push(part);
+    }
+
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_response_maps_text_tool_calls_and_usage() {
+        let body = serde_json::json!({
+            "candidates": [{
+                "content": {
+                    "role": "model",
+                    "parts": [
+                        {"text": "pondering", "thought": true},
+                        {"text": "Checking."},
+                        {"functionCall": {"name": "reply", "args": {"content": "hi"}}, "thoughtSignature": "abc"}
+                    ]
+                },
+                "finishReason": "STOP"
+            }],
+            "usageMetadata": {
+                "promptTokenCount": 100,
+                "candidatesTokenCount": 20,
+                "thoughtsTokenCount": 30,
+                "cachedContentTokenCount": 40
+            },
+            "responseId": "resp-1"
+        });
+
+        let response = parse_gemini_response(body, "Google Gemini").unwrap();
+        let choice: Vec<_> = response.choice.into_iter().collect();
+        assert!(matches!(choice[0], AssistantContent::Reasoning(_)));
+        assert!(matches!(&choice[1], AssistantContent::Text(text) if text.text == "Checking."));
+        let AssistantContent::ToolCall(tool_call) = &choice[2] else {
+            panic!("expected a tool call");
+        };
+        assert_eq!(tool_call.function.name, "reply");
+        assert_eq!(tool_call.function.arguments["content"], "hi");
+        assert_eq!(tool_call.signature.as_deref(), Some("abc"));
+        assert!(!tool_call.id.is_empty());
+
+        assert_eq!(response.usage.input_tokens, 100);
+        assert_eq!(response.usage.output_tokens, 50);
+        assert_eq!(response.usage.cached_input_tokens, 40);
+        assert_eq!(response.message_id.as_deref(), Some("resp-1"));
+    }
+
+    #[test]
+    fn blocked_and_empty_responses_are_errors() {
+        let blocked = serde_json::json!({"promptFeedback": {"blockReason": "SAFETY"}});
+        let error = parse_gemini_response(blocked, "Google Gemini").unwrap_err();
+        assert!(error.to_string().contains("blockReason: SAFETY"));
+
+        let empty = serde_json::json!({
+            "candidates": [{"content": {"parts": []}, "finishReason": "RECITATION"}]
+        });
+        let error = parse_gemini_response(empty, "Google Gemini").unwrap_err();
+        assert!(error.to_string().contains("finishReason: RECITATION"));
+
+        let end_turn = serde_json::json!({
+            "candidates": [{"content": {"parts": []}, "finishReason": "STOP"}]
+        });
+        assert!(parse_gemini_response(end_turn, "Google Gemini").is_ok());
+    }
+
+    #[test]
+    fn stream_chunks_accumulate_into_final_response() {
+        let mut state = GeminiStreamState::default();
+        let first = serde_json::json!({
+            "candidates": [{"content": {"role": "model", "parts": [{"text": "Hel"}]}}],
+            "responseId": "resp-1"
+        });
+        let second = serde_json::json!({
+            "candidates": [{
+                "content": {"role": "model", "parts": [
+                    {"text": "lo"},
+                    {"functionCall": {"name": "react", "args": {"emoji": "👍"}}}
+                ]},
+                "finishReason": "STOP"
+            }],
+            "usageMetadata": {"promptTokenCount": 10, "candidatesTokenCount": 5}
+        });
+
+        let events = process_gemini_stream_chunk(&first, &mut state, "Google Gemini").unwrap();
+        assert!(matches!(&events[0], RawStreamingChoice::MessageId(id) if id == "resp-1"));
+        assert!(matches!(&events[1], RawStreamingChoice::Message(text) if text == "Hel"));
+
+        let events = process_gemini_stream_chunk(&second, &mut state, "Google Gemini").unwrap();
+        assert!(matches!(&events[0], RawStreamingChoice::Message(text) if text == "lo"));
+        let RawStreamingChoice::ToolCall(tool_call) = &events[1] else {
+            panic!("expected a tool call");
+        };
+        assert_eq!(tool_call.name, "react");
+
+        let RawStreamingChoice::FinalResponse(response) = state.finish("Google Gemini").unwrap()
+        else {
+            panic!("expected a final response");
+        };
+        assert_eq!(response.usage.unwrap().output_tokens, 5);
+        assert_eq!(
+            response.body["candidates"][0]["content"]["parts"][2]["functionCall"]["id"],
+            tool_call.id.as_str()
+        );
+    }
+}
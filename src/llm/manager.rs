@@ -12,6 +12,9 @@ use crate::auth::OAuthCredentials as AnthropicOAuthCredentials;
 use crate::config::{ApiType, LlmConfig, ProviderConfig};
 use crate::error::{LlmError, Result};
 use crate::github_copilot_auth::CopilotToken;
+use crate::llm::budget::SpendTracker;
+use crate::llm::gemini::{ServiceAccountKey, VertexAccessToken};
+use crate::llm::response_cache::ResponseCache;
 use crate::openai_auth::OAuthCredentials as OpenAiOAuthCredentials;
 
 use anyhow::Context as _;
@@ -44,6 +47,15 @@ pub struct LlmManager {
     openai_oauth_credentials: RwLock<Option<OpenAiOAuthCredentials>>,
     /// Cached GitHub Copilot API token (exchanged from PAT, refreshed lazily).
     copilot_token: RwLock<Option<CopilotToken>>,
+    /// Parsed Vertex AI service-account keys, keyed by the configured value
+    /// (inline JSON or key file path), so the key file is read once.
+    vertex_keys: RwLock<HashMap<String, Arc<ServiceAccountKey>>>,
+    /// Cached Vertex AI access tokens, keyed like `vertex_keys`.
+    vertex_tokens: RwLock<HashMap<String, VertexAccessToken>>,
+    /// Per-agent spend trackers, consulted by `SpacebotModel` before dispatch.
+    spend_trackers: ArcSwap<HashMap<String, Arc<SpendTracker>>>,
+    /// Per-agent completion caches, consulted by `SpacebotModel` before dispatch.
+    response_caches: ArcSwap<HashMap<String, Arc<ResponseCache>>>,
 }
 
 impl LlmManager {
@@ -62,6 +74,10 @@ impl LlmManager {
             anthropic_oauth_credentials: RwLock::new(None),
             openai_oauth_credentials: RwLock::new(None),
             copilot_token: RwLock::new(None),
+            vertex_keys: RwLock::new(HashMap::new()),
+            vertex_tokens: RwLock::new(HashMap::new()),
+            spend_trackers: ArcSwap::from_pointee(HashMap::new()),
+            response_caches: ArcSwap::from_pointee(HashMap::new()),
         })
     }
 
@@ -142,6 +158,10 @@ impl LlmManager {
             anthropic_oauth_credentials: RwLock::new(anthropic_oauth_credentials),
             openai_oauth_credentials: RwLock::new(openai_oauth_credentials),
             copilot_token: RwLock::new(copilot_token),
+            vertex_keys: RwLock::new(HashMap::new()),
+            vertex_tokens: RwLock::new(HashMap::new()),
+            spend_trackers: ArcSwap::from_pointee(HashMap::new()),
+            response_caches: ArcSwap::from_pointee(HashMap::new()),
         })
     }
 
@@ -226,6 +246,8 @@ impl LlmManager {
                 extra_headers: vec![],
                 api_version: None,
                 deployment: None,
+                bedrock: None,
+                cassette: None,
             }),
             (None, None) => Err(LlmError::UnknownProvider("anthropic".to_string()).into()),
         }
@@ -298,6 +320,8 @@ impl LlmManager {
                 extra_headers: vec![],
                 api_version: None,
                 deployment: None,
+                bedrock: None,
+                cassette: None,
             }),
             None => Err(LlmError::UnknownProvider("openai-chatgpt".to_string()).into()),
         }
@@ -412,6 +436,8 @@ impl LlmManager {
             ],
             api_version: None,
             deployment: None,
+            bedrock: None,
+            cassette: None,
         })
     }
 
@@ -424,6 +450,47 @@ impl LlmManager {
         *self.copilot_token.write().await = None;
     }
 
+    /// Get a valid Vertex AI access token for a service-account key,
+    /// exchanging a fresh one when the cached token is missing or expiring.
+    ///
+    /// `service_account_key` is the provider's `api_key`: the key JSON itself
+    /// or a path to the key file.
+    pub async fn get_vertex_token(&self, service_account_key: &str) -> Result<String> {
+        {
+            let tokens = self.vertex_tokens.read().await;
+            if let Some(cached) = tokens.get(service_account_key)
+                && !cached.is_expired()
+            {
+                return Ok(cached.token.clone());
+            }
+        } // read lock dropped here before network call
+
+        let key = self.vertex_key(service_account_key).await?;
+        tracing::debug!(client_email = %key.client_email, "exchanging Vertex AI access token");
+        let token =
+            crate::llm::gemini::exchange_service_account_token(&self.http_client, &key).await?;
+        let access_token = token.token.clone();
+        self.vertex_tokens
+            .write()
+            .await
+            .insert(service_account_key.to_string(), token);
+        Ok(access_token)
+    }
+
+    /// Parse a Vertex AI service-account key, reading it from disk only the
+    /// first time a configured value is seen.
+    async fn vertex_key(&self, service_account_key: &str) -> Result<Arc<ServiceAccountKey>> {
+        if let Some(key) = self.vertex_keys.read().await.get(service_account_key) {
+            return Ok(key.clone());
+        }
+        let key = Arc::new(ServiceAccountKey::load(service_account_key).await?);
+        self.vertex_keys
+            .write()
+            .await
+            .insert(service_account_key.to_string(), key.clone());
+        Ok(key)
+    }
+
     /// Get the appropriate API key for a provider.
     pub fn get_api_key(&self, provider_id: &str) -> Result<String> {
         let provider = self.get_provider(provider_id)?;
@@ -481,4 +548,32 @@ impl LlmManager {
             .await
             .retain(|_, limited_at| limited_at.elapsed().as_secs() < cooldown_secs);
     }
+
+    /// Register (or replace) the spend tracker for an agent.
+    pub fn register_spend_tracker(&self, tracker: Arc<SpendTracker>) {
+        self.spend_trackers.rcu(|trackers| {
+            let mut trackers = HashMap::clone(trackers);
+            trackers.insert(tracker.agent_id().to_string(), tracker.clone());
+            trackers
+        });
+    }
+
+    /// Spend tracker for an agent, if budgets are wired up for it.
+    pub fn spend_tracker(&self, agent_id: &str) -> Option<Arc<SpendTracker>> {
+        self.spend_trackers.load().get(agent_id).cloned()
+    }
+
+    /// Register (or replace) the response cache for an agent.
+    pub fn register_response_cache(&self, cache: Arc<ResponseCache>) {
+        self.response_caches.rcu(|caches| {
+            let mut caches = HashMap::clone(caches);
+            caches.insert(cache.agent_id().to_string(), cache.clone());
+            caches
+        });
+    }
+
+    /// Response cache for an agent, if one is wired up for it.
+    pub fn response_cache(&self, agent_id: &str) -> Option<Arc<ResponseCache>> {
+        self.response_caches.load().get(agent_id).cloned()
+    }
 }
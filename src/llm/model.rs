@@ -1,9 +1,13 @@
 //! SpacebotModel: Custom CompletionModel implementation that routes through LlmManager.
 
-use crate::config::{ApiType, ProviderConfig};
+use crate::config::{ApiType, CassetteMode, ProviderConfig};
+use crate::llm::budget::{BudgetDecision, SpendRecorder, SpendTracker};
+use crate::llm::cassette::Cassette;
 use crate::llm::manager::LlmManager;
+use crate::llm::response_cache::{CacheLookup, ResponseCache};
 use crate::llm::routing::{
-    self, MAX_FALLBACK_ATTEMPTS, MAX_RETRIES_PER_MODEL, RETRY_BASE_DELAY_MS, RoutingConfig,
+    self, MAX_FALLBACK_ATTEMPTS, MAX_RETRIES_PER_MODEL, PromptTier, RETRY_BASE_DELAY_MS,
+    RoutingConfig,
 };
 
 use futures::StreamExt as _;
@@ -20,7 +24,9 @@ use rig::providers::openai::responses_api::streaming::{
     StreamingCompletionChunk as OpenAiResponsesStreamingCompletionChunk,
     StreamingItemDoneOutput as OpenAiResponsesStreamingItemDoneOutput,
 };
-use rig::streaming::{RawStreamingChoice, RawStreamingToolCall, StreamingCompletionResponse};
+use rig::streaming::{
+    RawStreamingChoice, RawStreamingToolCall, StreamingCompletionResponse, StreamingResult,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::sync::Arc;
@@ -60,6 +66,8 @@ pub struct SpacebotModel {
     agent_id: Option<String>,
     process_type: Option<String>,
     worker_type: Option<String>,
+    prompt_tier: Option<PromptTier>,
+    channel_id: Option<String>,
 }
 
 impl SpacebotModel {
@@ -96,6 +104,113 @@ impl SpacebotModel {
         self
     }
 
+    /// Record the prompt-routing tier this model was selected for, so
+    /// request metrics can attribute calls (and savings) to a tier.
+    pub fn with_prompt_tier(mut self, prompt_tier: Option<PromptTier>) -> Self {
+        self.prompt_tier = prompt_tier;
+        self
+    }
+
+    pub fn prompt_tier(&self) -> Option<PromptTier> {
+        self.prompt_tier
+    }
+
+    /// Attach the channel this model is serving, so spend is attributed to
+    /// it and channel/cron budgets apply.
+    pub fn with_channel(mut self, channel_id: impl Into<String>) -> Self {
+        self.channel_id = Some(channel_id.into());
+        self
+    }
+
+    /// Same routing and attribution, different model. Used for fallbacks and
+    /// budget downgrades.
+    fn for_model(&self, model_name: &str) -> Self {
+        Self {
+            routing: self.routing.clone(),
+            agent_id: self.agent_id.clone(),
+            process_type: self.process_type.clone(),
+            worker_type: self.worker_type.clone(),
+            prompt_tier: self.prompt_tier,
+            channel_id: self.channel_id.clone(),
+            ..SpacebotModel::make(&self.llm_manager, model_name)
+        }
+    }
+
+    fn spend_tracker(&self) -> Option<Arc<SpendTracker>> {
+        let agent_id = self.agent_id.as_deref()?;
+        self.llm_manager.spend_tracker(agent_id)
+    }
+
+    fn spend_recorder(&self) -> Option<SpendRecorder> {
+        let tracker = self.spend_tracker()?;
+        Some(SpendRecorder::new(
+            tracker,
+            self.channel_id.clone(),
+            self.process_type.as_deref().unwrap_or("unknown"),
+        ))
+    }
+
+    /// Bill a provider response to the agent's spend ledger.
+    fn record_spend(&self, usage: &completion::Usage) {
+        if let Some(recorder) = self.spend_recorder() {
+            recorder.record(&self.full_model_name, usage);
+        }
+    }
+
+    /// Bill the usage carried by a stream's final response.
+    fn with_spend_recording(
+        &self,
+        stream: StreamingResult<RawStreamingResponse>,
+    ) -> StreamingResult<RawStreamingResponse> {
+        let Some(recorder) = self.spend_recorder() else {
+            return stream;
+        };
+        let model_name = self.full_model_name.clone();
+        Box::pin(stream.inspect(move |chunk| {
+            if let Ok(RawStreamingChoice::FinalResponse(response)) = chunk
+                && let Some(usage) = &response.usage
+            {
+                recorder.record(&model_name, usage);
+            }
+        }))
+    }
+
+    /// Check the agent's spend budget before dispatch.
+    ///
+    /// Returns a cheaper model to use instead when a soft limit has been
+    /// crossed, or an error when a hard limit has been reached.
+    async fn enforce_budget(&self) -> Result<Option<Self>, CompletionError> {
+        let Some(tracker) = self.spend_tracker() else {
+            return Ok(None);
+        };
+
+        match tracker.check(self.channel_id.as_deref()).await {
+            BudgetDecision::Allow => Ok(None),
+            BudgetDecision::Downgrade { model } => {
+                let downgraded = self.for_model(&model);
+                if downgraded.full_model_name == self.full_model_name {
+                    return Ok(None);
+                }
+                tracing::debug!(
+                    agent_id = tracker.agent_id(),
+                    from = %self.full_model_name,
+                    to = %downgraded.full_model_name,
+                    "soft spend limit reached, downgrading model"
+                );
+                Ok(Some(downgraded))
+            }
+            BudgetDecision::Stop(exceeded) => {
+                tracing::warn!(
+                    agent_id = tracker.agent_id(),
+                    channel_id = ?self.channel_id,
+                    %exceeded,
+                    "refusing LLM call"
+                );
+                Err(CompletionError::ProviderError(exceeded.to_string()))
+            }
+        }
+    }
+
     async fn provider_config_for_current_model(&self) -> Result<ProviderConfig, CompletionError> {
         let provider_id = self
             .full_model_name
@@ -131,13 +246,34 @@ impl SpacebotModel {
         }
     }
 
-    /// Direct call to the provider (no fallback logic).
+    /// Direct call to the provider (no fallback logic), or to its cassette
+    /// when record/replay is enabled.
     async fn attempt_completion(
         &self,
         request: CompletionRequest,
     ) -> Result<completion::CompletionResponse<RawResponse>, CompletionError> {
         let provider_config = self.provider_config_for_current_model().await?;
+        let Some(cassette) = Cassette::resolve(provider_config.cassette.as_ref())? else {
+            return self.call_provider(request, provider_config).await;
+        };
+
+        let key = cassette.key(&self.full_model_name, &request, false);
+        match cassette.mode {
+            CassetteMode::Replay => cassette.replay_completion(&key).await,
+            CassetteMode::Record => {
+                let result = self.call_provider(request, provider_config).await;
+                cassette.record_completion(&key, &result).await;
+                result
+            }
+        }
+    }
 
+    /// Call the provider's completion API.
+    async fn call_provider(
+        &self,
+        request: CompletionRequest,
+        provider_config: ProviderConfig,
+    ) -> Result<completion::CompletionResponse<RawResponse>, CompletionError> {
         match provider_config.api_type {
             ApiType::Anthropic => self.call_anthropic(request, &provider_config).await,
             ApiType::OpenAiCompletions => self.call_openai(request, &provider_config).await,
@@ -259,10 +395,8 @@ impl SpacebotModel {
                 .await
             }
             ApiType::OpenAiResponses => self.call_openai_responses(request, &provider_config).await,
-            ApiType::Gemini => {
-                self.call_openai_compatible(request, "Google Gemini", &provider_config)
-                    .await
-            }
+            ApiType::Gemini | ApiType::Vertex => self.call_gemini(request, &provider_config).await,
+            ApiType::Bedrock => self.call_bedrock(request, &provider_config).await,
         }
     }
 
@@ -280,7 +414,7 @@ impl SpacebotModel {
         let model = if model_name == self.full_model_name {
             self.clone()
         } else {
-            SpacebotModel::make(&self.llm_manager, model_name)
+            self.for_model(model_name)
         };
 
         let mut last_error = None;
@@ -355,12 +489,73 @@ impl CompletionModel for SpacebotModel {
             agent_id: None,
             process_type: None,
             worker_type: None,
+            prompt_tier: None,
+            channel_id: None,
         }
     }
 
     async fn completion(
         &self,
         request: CompletionRequest,
+    ) -> Result<completion::CompletionResponse<RawResponse>, CompletionError> {
+        let Some((cache, process_type)) = self.response_cache_for(&request) else {
+            return self.budgeted_completion(request).await;
+        };
+
+        // A hit is free, so the cache is consulted before the budget.
+        let pending = match cache
+            .lookup(&self.full_model_name, process_type, &request)
+            .await
+        {
+            CacheLookup::Hit(response) => return Ok(response),
+            CacheLookup::Miss(pending) => pending,
+        };
+        let result = self.budgeted_completion(request).await;
+        if let Ok(response) = &result {
+            cache.store(pending, response).await;
+        }
+        result
+    }
+
+    async fn stream(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<StreamingCompletionResponse<RawStreamingResponse>, CompletionError> {
+        match self.enforce_budget().await? {
+            Some(downgraded) => downgraded.dispatch_stream(request).await,
+            None => self.dispatch_stream(request).await,
+        }
+    }
+}
+
+impl SpacebotModel {
+    /// The agent's response cache and this model's process type, when the
+    /// request may be served from the cache.
+    fn response_cache_for(
+        &self,
+        request: &CompletionRequest,
+    ) -> Option<(Arc<ResponseCache>, &str)> {
+        let process_type = self.process_type.as_deref()?;
+        let cache = self.llm_manager.response_cache(self.agent_id.as_deref()?)?;
+        cache
+            .applies_to(process_type, request)
+            .then_some((cache, process_type))
+    }
+
+    async fn budgeted_completion(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<completion::CompletionResponse<RawResponse>, CompletionError> {
+        match self.enforce_budget().await? {
+            Some(downgraded) => downgraded.dispatch_completion(request).await,
+            None => self.dispatch_completion(request).await,
+        }
+    }
+
+    /// Completion with retries and the fallback chain, after budget checks.
+    async fn dispatch_completion(
+        &self,
+        request: CompletionRequest,
     ) -> Result<completion::CompletionResponse<RawResponse>, CompletionError> {
         #[cfg(feature = "metrics")]
         let start = std::time::Instant::now();
@@ -467,10 +662,17 @@ impl CompletionModel for SpacebotModel {
                 None if tier_label == "worker" => "unknown",
                 None => "",
             };
+            let prompt_tier_label = self.prompt_tier.map(|tier| tier.as_str()).unwrap_or("");
             let metrics = crate::telemetry::Metrics::global();
             metrics
                 .llm_requests_total
-                .with_label_values(&[agent_label, &self.full_model_name, tier_label, worker_label])
+                .with_label_values(&[
+                    agent_label,
+                    &self.full_model_name,
+                    tier_label,
+                    worker_label,
+                    prompt_tier_label,
+                ])
                 .inc();
             metrics
                 .llm_request_duration_seconds
@@ -573,12 +775,31 @@ impl CompletionModel for SpacebotModel {
         result
     }
 
-    async fn stream(
+    async fn dispatch_stream(
         &self,
         request: CompletionRequest,
     ) -> Result<StreamingCompletionResponse<RawStreamingResponse>, CompletionError> {
         let provider_config = self.provider_config_for_current_model().await?;
+        let Some(cassette) = Cassette::resolve(provider_config.cassette.as_ref())? else {
+            return self.stream_provider(request, provider_config).await;
+        };
+
+        let key = cassette.key(&self.full_model_name, &request, true);
+        match cassette.mode {
+            CassetteMode::Replay => cassette.replay_stream(&key).await,
+            CassetteMode::Record => {
+                let result = self.stream_provider(request, provider_config).await;
+                cassette.record_stream(key, result).await
+            }
+        }
+    }
 
+    /// Call the provider's streaming API.
+    async fn stream_provider(
+        &self,
+        request: CompletionRequest,
+        provider_config: ProviderConfig,
+    ) -> Result<StreamingCompletionResponse<RawStreamingResponse>, CompletionError> {
         match provider_config.api_type {
             ApiType::OpenAiCompletions => self.stream_openai(request, &provider_config).await,
             ApiType::OpenAiChatCompletions => {
@@ -697,12 +918,12 @@ impl CompletionModel for SpacebotModel {
                 )
                 .await
             }
-            ApiType::Gemini => {
-                self.stream_openai_compatible(request, "Google Gemini", &provider_config)
-                    .await
+            ApiType::Gemini | ApiType::Vertex => {
+                self.stream_gemini(request, &provider_config).await
             }
+            ApiType::Bedrock => self.stream_bedrock(request, &provider_config).await,
             ApiType::Anthropic => {
-                let response = self.attempt_completion(request).await?;
+                let response = self.call_provider(request, provider_config).await?;
                 Ok(stream_from_completion_response(response))
             }
             ApiType::OpenAiResponses => {
@@ -711,9 +932,7 @@ impl CompletionModel for SpacebotModel {
             }
         }
     }
-}
 
-impl SpacebotModel {
     async fn call_anthropic(
         &self,
         request: CompletionRequest,
@@ -724,7 +943,13 @@ impl SpacebotModel {
         let effort = self
             .routing
             .as_ref()
-            .map(|r| r.thinking_effort_for_model(&self.model_name))
+            .map(|r| {
+                r.thinking_effort_for_call(
+                    &self.model_name,
+                    self.process_type.as_deref(),
+                    self.prompt_tier,
+                )
+            })
             .unwrap_or("auto");
         let anthropic_request = crate::llm::anthropic::build_anthropic_request(
             self.llm_manager.http_client(),
@@ -775,6 +1000,7 @@ impl SpacebotModel {
             reverse_map_tool_names(&mut completion, &original_tools);
         }
 
+        self.record_spend(&completion.usage);
         Ok(completion)
     }
 
@@ -1009,7 +1235,9 @@ impl SpacebotModel {
             })?
         };
 
-        parse_openai_responses_response(response_body, &provider_label)
+        let completion = parse_openai_responses_response(response_body, &provider_label)?;
+        self.record_spend(&completion.usage);
+        Ok(completion)
     }
 
     async fn stream_openai_responses(
@@ -1291,113 +1519,383 @@ impl SpacebotModel {
             }));
         };
 
-        Ok(StreamingCompletionResponse::stream(Box::pin(stream)))
+        Ok(StreamingCompletionResponse::stream(
+            self.with_spend_recording(Box::pin(stream)),
+        ))
+    }
+
+    /// Auth header for a native Gemini call: an API key for the Gemini API,
+    /// or an OAuth access token from the service-account key for Vertex AI.
+    async fn gemini_auth_header(
+        &self,
+        provider_config: &ProviderConfig,
+    ) -> Result<(&'static str, String), CompletionError> {
+        match provider_config.api_type {
+            ApiType::Vertex => {
+                let token = self
+                    .llm_manager
+                    .get_vertex_token(&provider_config.api_key)
+                    .await
+                    .map_err(|error| {
+                        CompletionError::ProviderError(format!("Vertex AI auth failed: {error}"))
+                    })?;
+                Ok(("authorization", format!("Bearer {token}")))
+            }
+            _ => Ok(("x-goog-api-key", provider_config.api_key.clone())),
+        }
     }
 
-    /// Generic OpenAI-compatible API call.
-    /// Used by providers that implement the OpenAI chat completions format.
-    #[allow(dead_code)]
-    async fn call_openai_compatible(
+    fn gemini_request_body(&self, request: &CompletionRequest) -> serde_json::Value {
+        let effort = self
+            .routing
+            .as_ref()
+            .map(|r| {
+                r.thinking_effort_for_call(
+                    &self.model_name,
+                    self.process_type.as_deref(),
+                    self.prompt_tier,
+                )
+            })
+            .unwrap_or("auto");
+        crate::llm::gemini::build_gemini_body(&self.model_name, request, effort)
+    }
+
+    /// Native Gemini `generateContent` call (Gemini API or Vertex AI).
+    async fn call_gemini(
         &self,
         request: CompletionRequest,
-        provider_display_name: &str,
         provider_config: &ProviderConfig,
     ) -> Result<completion::CompletionResponse<RawResponse>, CompletionError> {
-        let stream = self
-            .stream_openai_compatible(request, provider_display_name, provider_config)
-            .await?;
-        collect_streaming_completion_response(stream).await
+        let provider_label = gemini_display_name(provider_config);
+        let url = crate::llm::gemini::generate_content_url(
+            &provider_config.base_url,
+            &self.model_name,
+            false,
+        );
+        let body = self.gemini_request_body(&request);
+        let (auth_header, auth_value) = self.gemini_auth_header(provider_config).await?;
+
+        let response = self
+            .llm_manager
+            .http_client()
+            .post(&url)
+            .header(auth_header, auth_value)
+            .header("content-type", "application/json")
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| CompletionError::ProviderError(e.to_string()))?;
+
+        let status = response.status();
+        let response_text = response.text().await.map_err(|e| {
+            CompletionError::ProviderError(format!("failed to read response body: {e}"))
+        })?;
+
+        if !status.is_success() {
+            return Err(CompletionError::ProviderError(format!(
+                "{provider_label} API error ({})",
+                format_api_error_from_response_text(status, &response_text)
+            )));
+        }
+
+        let response_body: serde_json::Value =
+            serde_json::from_str(&response_text).map_err(|e| {
+                CompletionError::ProviderError(format!(
+                    "{provider_label} response ({status}) is not valid JSON: {e}\nBody: {}",
+                    truncate_body(&response_text)
+                ))
+            })?;
+
+        let completion = crate::llm::gemini::parse_gemini_response(response_body, provider_label)?;
+        self.record_spend(&completion.usage);
+        Ok(completion)
     }
 
-    async fn stream_openai_compatible(
+    /// Native Gemini `streamGenerateContent` call over SSE.
+    async fn stream_gemini(
         &self,
         request: CompletionRequest,
-        provider_display_name: &str,
         provider_config: &ProviderConfig,
     ) -> Result<StreamingCompletionResponse<RawStreamingResponse>, CompletionError> {
-        let base_url = provider_config.base_url.trim_end_matches('/');
-        let endpoint_path = match provider_config.api_type {
-            ApiType::OpenAiCompletions | ApiType::OpenAiResponses => "/v1/chat/completions",
-            ApiType::OpenAiChatCompletions | ApiType::Gemini => "/chat/completions",
-            ApiType::Azure => {
-                // Azure handles its own endpoint construction in the call() match
-                // This fallback should not be reached for Azure
-                return Err(CompletionError::ProviderError(
-                    "Azure provider should use the dedicated Azure endpoint construction in call()"
-                        .to_string(),
-                ));
-            }
-            ApiType::Anthropic => {
-                return Err(CompletionError::ProviderError(format!(
-                    "{provider_display_name} is configured with anthropic API type, but this call expects an OpenAI-compatible API"
-                )));
-            }
-            _ => {
-                return Err(CompletionError::ProviderError(format!(
-                    "{provider_display_name} uses API type {:?} which does not support OpenAI-compatible calls",
-                    provider_config.api_type
-                )));
-            }
-        };
-        let endpoint = format!("{base_url}{endpoint_path}");
-        let api_key = provider_config.api_key.as_str();
+        let provider_label = gemini_display_name(provider_config).to_string();
+        let url = crate::llm::gemini::generate_content_url(
+            &provider_config.base_url,
+            &self.model_name,
+            true,
+        );
+        let body = self.gemini_request_body(&request);
+        let (auth_header, auth_value) = self.gemini_auth_header(provider_config).await?;
 
-        let mut messages = Vec::new();
+        let response = self
+            .llm_manager
+            .http_client()
+            .post(&url)
+            .header(auth_header, auth_value)
+            .header("content-type", "application/json")
+            .header("accept-encoding", "identity")
+            .timeout(std::time::Duration::from_secs(STREAM_REQUEST_TIMEOUT_SECS))
+            .json(&body)
+            .send()
+            .await
+            .map_err(|error| CompletionError::ProviderError(error.to_string()))?;
 
-        if let Some(preamble) = &request.preamble {
-            messages.push(serde_json::json!({
-                "role": "system",
-                "content": preamble,
-            }));
+        let status = response.status();
+        if !status.is_success() {
+            let response_text = response
+                .text()
+                .await
+                .unwrap_or_else(|error| format!("failed to read error response body: {error}"));
+
+            return Err(CompletionError::ProviderError(format!(
+                "{provider_label} API error ({})",
+                format_api_error_from_response_text(status, &response_text)
+            )));
         }
 
-        messages.extend(convert_messages_to_openai(&request.chat_history));
+        let stream = async_stream::stream! {
+            let mut stream = response.bytes_stream();
+            let mut block_buffer = String::new();
+            let mut state = crate::llm::gemini::GeminiStreamState::default();
 
-        let mut body = serde_json::json!({
-            "model": self.model_name,
-            "messages": messages,
-        });
+            loop {
+                let chunk = match stream.next().await {
+                    Some(Ok(bytes)) => Some(bytes),
+                    Some(Err(error)) => {
+                        yield Err(CompletionError::ProviderError(format!(
+                            "{provider_label} stream read failed: {error}"
+                        )));
+                        return;
+                    }
+                    None => None,
+                };
+                let stream_done = chunk.is_none();
+                match chunk {
+                    Some(bytes) => block_buffer.push_str(&String::from_utf8_lossy(&bytes)),
+                    // Flush a final event that wasn't terminated by a blank line.
+                    None => block_buffer.push_str("\n\n"),
+                }
 
-        if let Some(max_tokens) = request.max_tokens {
-            body["max_tokens"] = serde_json::json!(max_tokens);
+                while let Some(block) = extract_sse_block(&mut block_buffer) {
+                    let Some(data) = extract_sse_data_payload(&block) else {
+                        continue;
+                    };
+                    let event_body = match serde_json::from_str::<serde_json::Value>(data.trim()) {
+                        Ok(body) => body,
+                        Err(error) => {
+                            tracing::trace!(%error, payload = %data, "failed to parse Gemini SSE chunk");
+                            continue;
+                        }
+                    };
+
+                    match crate::llm::gemini::process_gemini_stream_chunk(
+                        &event_body,
+                        &mut state,
+                        &provider_label,
+                    ) {
+                        Ok(events) => {
+                            for event in events {
+                                yield Ok(event);
+                            }
+                        }
+                        Err(error) => {
+                            yield Err(error);
+                            return;
+                        }
+                    }
+                }
+
+                if stream_done {
+                    break;
+                }
+            }
+
+            yield state.finish(&provider_label);
+        };
+
+        Ok(StreamingCompletionResponse::stream(
+            self.with_spend_recording(Box::pin(stream)),
+        ))
+    }
+
+    /// Send a SigV4-signed Bedrock request. The provider's `api_key` is the
+    /// secret access key; the key ID, region, and session token come from its
+    /// `bedrock` settings.
+    async fn send_bedrock_request(
+        &self,
+        provider_config: &ProviderConfig,
+        request: &CompletionRequest,
+        stream: bool,
+    ) -> Result<reqwest::Response, CompletionError> {
+        let bedrock = provider_config.bedrock.as_ref().ok_or_else(|| {
+            CompletionError::ProviderError(
+                "Bedrock provider is missing its access_key_id and region settings".into(),
+            )
+        })?;
+        let url =
+            crate::llm::bedrock::converse_url(&provider_config.base_url, &self.model_name, stream);
+        let url = reqwest::Url::parse(&url).map_err(|error| {
+            CompletionError::ProviderError(format!("invalid Bedrock endpoint {url}: {error}"))
+        })?;
+        let payload = serde_json::to_vec(&crate::llm::bedrock::build_converse_body(request))
+            .map_err(|error| CompletionError::ProviderError(error.to_string()))?;
+
+        let signed_headers = crate::llm::bedrock::sign_request(
+            "POST",
+            &url,
+            &[("content-type", "application/json")],
+            &payload,
+            &crate::llm::bedrock::SigningParams {
+                credentials: crate::llm::bedrock::AwsCredentials {
+                    access_key_id: &bedrock.access_key_id,
+                    secret_access_key: &provider_config.api_key,
+                    session_token: bedrock.session_token.as_deref(),
+                },
+                region: &bedrock.region,
+                service: "bedrock",
+                timestamp: chrono::Utc::now(),
+            },
+        );
+
+        let mut builder = self
+            .llm_manager
+            .http_client()
+            .post(url)
+            .header("content-type", "application/json");
+        for (name, value) in signed_headers {
+            builder = builder.header(name, value);
+        }
+        if stream {
+            builder = builder
+                .header("accept", "application/vnd.amazon.eventstream")
+                .timeout(std::time::Duration::from_secs(STREAM_REQUEST_TIMEOUT_SECS));
         }
 
-        if let Some(temperature) = request.temperature {
-            body["temperature"] = serde_json::json!(temperature);
+        let response = builder
+            .body(payload)
+            .send()
+            .await
+            .map_err(|error| CompletionError::ProviderError(error.to_string()))?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
         }
 
-        if !request.tools.is_empty() {
-            let tools: Vec<serde_json::Value> = request
-                .tools
-                .iter()
-                .map(|t| {
-                    serde_json::json!({
-                        "type": "function",
-                        "function": {
-                            "name": t.name,
-                            "description": t.description,
-                            "parameters": t.parameters,
+        // The exception name (e.g. `ThrottlingException`) is carried in a
+        // header, optionally followed by `:<documentation URL>`.
+        let error_type = response
+            .headers()
+            .get("x-amzn-errortype")
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.split(':').next().unwrap_or(value).to_string());
+        let response_text = response
+            .text()
+            .await
+            .unwrap_or_else(|error| format!("failed to read error response body: {error}"));
+        let message = serde_json::from_str::<serde_json::Value>(&response_text)
+            .ok()
+            .and_then(|body| {
+                body.get("message")
+                    .or_else(|| body.get("Message"))
+                    .and_then(serde_json::Value::as_str)
+                    .map(ToString::to_string)
+            })
+            .unwrap_or_else(|| truncate_body(&response_text).to_string());
+
+        Err(CompletionError::ProviderError(match error_type {
+            Some(error_type) => format!("Bedrock API error ({status}): {error_type}: {message}"),
+            None => format!("Bedrock API error ({status}): {message}"),
+        }))
+    }
+
+    /// Native Bedrock `Converse` call.
+    async fn call_bedrock(
+        &self,
+        request: CompletionRequest,
+        provider_config: &ProviderConfig,
+    ) -> Result<completion::CompletionResponse<RawResponse>, CompletionError> {
+        let response = self
+            .send_bedrock_request(provider_config, &request, false)
+            .await?;
+
+        let status = response.status();
+        let response_text = response.text().await.map_err(|e| {
+            CompletionError::ProviderError(format!("failed to read response body: {e}"))
+        })?;
+        let response_body: serde_json::Value =
+            serde_json::from_str(&response_text).map_err(|e| {
+                CompletionError::ProviderError(format!(
+                    "Bedrock response ({status}) is not valid JSON: {e}\nBody: {}",
+                    truncate_body(&response_text)
+                ))
+            })?;
+
+        let completion = crate::llm::bedrock::parse_converse_response(response_body)?;
+        self.record_spend(&completion.usage);
+        Ok(completion)
+    }
+
+    /// Native Bedrock `ConverseStream` call over the binary event stream.
+    async fn stream_bedrock(
+        &self,
+        request: CompletionRequest,
+        provider_config: &ProviderConfig,
+    ) -> Result<StreamingCompletionResponse<RawStreamingResponse>, CompletionError> {
+        let response = self
+            .send_bedrock_request(provider_config, &request, true)
+            .await?;
+
+        let stream = async_stream::stream! {
+            let mut stream = response.bytes_stream();
+            let mut decoder = crate::llm::bedrock::EventStreamDecoder::default();
+            let mut state = crate::llm::bedrock::BedrockStreamState::default();
+
+            while let Some(chunk) = stream.next().await {
+                match chunk {
+                    Ok(bytes) => decoder.push(&bytes),
+                    Err(error) => {
+                        yield Err(CompletionError::ProviderError(format!(
+                            "Bedrock stream read failed: {error}"
+                        )));
+                        return;
+                    }
+                }
+
+                loop {
+                    let message = match decoder.next_message() {
+                        Ok(Some(message)) => message,
+                        Ok(None) => break,
+                        Err(error) => {
+                            yield Err(CompletionError::ProviderError(format!(
+                                "Bedrock stream decode failed: {error}"
+                            )));
+                            return;
                         }
-                    })
-                })
-                .collect();
-            body["tools"] = serde_json::json!(tools);
-        }
+                    };
 
-        let http_client = self.llm_manager.http_client().clone();
-        let auth_header = format!("Bearer {api_key}");
-        self.stream_openai_chat_request(
-            move |request_body| {
-                http_client
-                    .post(&endpoint)
-                    .header("authorization", auth_header.clone())
-                    .header("content-type", "application/json")
-                    .json(request_body)
-            },
-            body,
-            provider_display_name,
-        )
-        .await
+                    match crate::llm::bedrock::process_bedrock_stream_message(&message, &mut state) {
+                        Ok(events) => {
+                            for event in events {
+                                yield Ok(event);
+                            }
+                        }
+                        Err(error) => {
+                            yield Err(error);
+                            return;
+                        }
+                    }
+                }
+            }
+
+            if decoder.has_remaining() {
+                tracing::warn!("Bedrock stream ended with a partial event");
+            }
+            yield state.finish();
+        };
+
+        Ok(StreamingCompletionResponse::stream(
+            self.with_spend_recording(Box::pin(stream)),
+        ))
     }
 
     /// Remap model name for providers that require a different format in API calls.
@@ -1689,7 +2187,9 @@ impl SpacebotModel {
             }));
         };
 
-        Ok(StreamingCompletionResponse::stream(Box::pin(stream)))
+        Ok(StreamingCompletionResponse::stream(
+            self.with_spend_recording(Box::pin(stream)),
+        ))
     }
 }
 // --- Helpers ---
@@ -1708,7 +2208,9 @@ fn reverse_map_tool_names(
     }
 }
 
-fn tool_result_content_to_string(content: &OneOrMany<rig::message::ToolResultContent>) -> String {
+pub(crate) fn tool_result_content_to_string(
+    content: &OneOrMany<rig::message::ToolResultContent>,
+) -> String {
     content
         .iter()
         .filter_map(|c| match c {
@@ -3619,6 +4121,16 @@ fn format_api_error(status: reqwest::StatusCode, body: &serde_json::Value) -> St
     }
 }
 
+fn gemini_display_name(provider_config: &ProviderConfig) -> &str {
+    provider_config
+        .name
+        .as_deref()
+        .unwrap_or(match provider_config.api_type {
+            ApiType::Vertex => "Vertex AI",
+            _ => "Google Gemini",
+        })
+}
+
 fn provider_display_name(provider_id: &str) -> String {
     match provider_id {
         "openai" => "OpenAI".to_string(),
@@ -4561,3 +5073,224 @@ mod tests {
         assert!(msg.contains("invalid schema"));
     }
 }
+
+#[cfg(test)]
+mod bedrock_tests {
+    use super::*;
+    use crate::config::{BedrockConfig, LlmConfig};
+    use crate::llm::bedrock::event_stream::encode_message;
+    use axum::Router;
+    use axum::http::{HeaderMap, StatusCode, Uri};
+    use axum::response::IntoResponse as _;
+    use axum::routing::post;
+    use rig::message::Message;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    const MODEL_ID: &str = "anthropic.claude-3-haiku-20240307-v1:0";
+
+    type Recorded = Arc<Mutex<Vec<(String, String)>>>;
+
+    /// Serve canned Converse and ConverseStream responses, recording each
+    /// request's path and `authorization` header. The `throttled` model
+    /// always answers with a ThrottlingException.
+    async fn spawn_bedrock_stand_in() -> (String, Recorded) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let recorded: Recorded = Arc::default();
+        let record = |recorded: &Recorded, uri: &Uri, headers: &HeaderMap| {
+            let authorization = headers
+                .get("authorization")
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or_default()
+                .to_string();
+            recorded
+                .lock()
+                .unwrap()
+                .push((uri.path().to_string(), authorization));
+        };
+
+        let converse_recorded = recorded.clone();
+        let stream_recorded = recorded.clone();
+        let app = Router::new()
+            .route(
+                "/model/{model}/converse",
+                post(move |uri: Uri, headers: HeaderMap| async move {
+                    record(&converse_recorded, &uri, &headers);
+                    if uri.path().contains("throttled") {
+                        return (
+                            StatusCode::TOO_MANY_REQUESTS,
+                            [(
+                                "x-amzn-ErrorType",
+                                "ThrottlingException:http://internal.amazon.com/coral/com.amazon.bedrock/",
+                            )],
+                            axum::Json(serde_json::json!({"message": "Too many requests"})),
+                        )
+                            .into_response();
+                    }
+                    axum::Json(serde_json::json!({
+                        "output": {"message": {"role": "assistant", "content": [
+                            {"text": "Hello from Bedrock."}
+                        ]}},
+                        "stopReason": "end_turn",
+                        "usage": {"inputTokens": 12, "outputTokens": 4, "totalTokens": 16}
+                    }))
+                    .into_response()
+                }),
+            )
+            .route(
+                "/model/{model}/converse-stream",
+                post(move |uri: Uri, headers: HeaderMap| async move {
+                    record(&stream_recorded, &uri, &headers);
+                    let events = [
+                        ("messageStart", serde_json::json!({"role": "assistant"})),
+                        (
+                            "contentBlockStart",
+                            serde_json::json!({"contentBlockIndex": 0, "start": {"toolUse": {"toolUseId": "tooluse_1", "name": "reply"}}}),
+                        ),
+                        (
+                            "contentBlockDelta",
+                            serde_json::json!({"contentBlockIndex": 0, "delta": {"toolUse": {"input": "{\"content\":\"hi\"}"}}}),
+                        ),
+                        ("contentBlockStop", serde_json::json!({"contentBlockIndex": 0})),
+                        ("messageStop", serde_json::json!({"stopReason": "tool_use"})),
+                        (
+                            "metadata",
+                            serde_json::json!({"usage": {"inputTokens": 20, "outputTokens": 7}}),
+                        ),
+                    ];
+                    let body: Vec<u8> = events
+                        .iter()
+                        .flat_map(|(event_type, payload)| {
+                            encode_message(
+                                &[(":event-type", event_type), (":message-type", "event")],
+                                payload.to_string().as_bytes(),
+                            )
+                        })
+                        .collect();
+                    (
+                        [("content-type", "application/vnd.amazon.eventstream")],
+                        body,
+                    )
+                        .into_response()
+                }),
+            );
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        (base_url, recorded)
+    }
+
+    async fn bedrock_model(base_url: &str, model_id: &str) -> SpacebotModel {
+        let provider = ProviderConfig {
+            api_type: ApiType::Bedrock,
+            base_url: base_url.to_string(),
+            api_key: "secret-access-key".into(),
+            name: None,
+            use_bearer_auth: false,
+            extra_headers: Vec::new(),
+            api_version: None,
+            deployment: None,
+            bedrock: Some(BedrockConfig {
+                region: "us-east-1".into(),
+                access_key_id: "AKID".into(),
+                session_token: None,
+            }),
+            cassette: None,
+        };
+        let config = LlmConfig {
+            anthropic_key: None,
+            openai_key: None,
+            openrouter_key: None,
+            kilo_key: None,
+            zhipu_key: None,
+            groq_key: None,
+            together_key: None,
+            fireworks_key: None,
+            deepseek_key: None,
+            xai_key: None,
+            mistral_key: None,
+            gemini_key: None,
+            ollama_key: None,
+            ollama_base_url: None,
+            opencode_zen_key: None,
+            opencode_go_key: None,
+            nvidia_key: None,
+            minimax_key: None,
+            minimax_cn_key: None,
+            moonshot_key: None,
+            zai_coding_plan_key: None,
+            github_copilot_key: None,
+            providers: HashMap::from([("bedrock".to_string(), provider)]),
+        };
+        let manager = Arc::new(LlmManager::new(config).await.unwrap());
+        SpacebotModel::make(&manager, format!("bedrock/{model_id}"))
+    }
+
+    fn request() -> CompletionRequest {
+        CompletionRequest {
+            model: None,
+            preamble: Some("be brief".into()),
+            chat_history: OneOrMany::one(Message::user("hello")),
+            documents: Vec::new(),
+            tools: Vec::new(),
+            temperature: None,
+            max_tokens: Some(256),
+            tool_choice: None,
+            additional_params: None,
+            output_schema: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn converse_requests_are_signed_and_parsed() {
+        let (base_url, recorded) = spawn_bedrock_stand_in().await;
+        let model = bedrock_model(&base_url, MODEL_ID).await;
+
+        let response = model.attempt_completion(request()).await.unwrap();
+        assert!(matches!(
+            response.choice.first(),
+            AssistantContent::Text(text) if text.text == "Hello from Bedrock."
+        ));
+        assert_eq!(response.usage.input_tokens, 12);
+
+        let recorded = recorded.lock().unwrap();
+        let (path, authorization) = &recorded[0];
+        assert_eq!(
+            path,
+            "/model/anthropic.claude-3-haiku-20240307-v1%3A0/converse"
+        );
+        assert!(authorization.starts_with("AWS4-HMAC-SHA256 Credential=AKID/"));
+        assert!(authorization.contains("/us-east-1/bedrock/aws4_request"));
+    }
+
+    #[tokio::test]
+    async fn converse_stream_decodes_tool_use() {
+        let (base_url, _recorded) = spawn_bedrock_stand_in().await;
+        let model = bedrock_model(&base_url, MODEL_ID).await;
+
+        let stream = model.dispatch_stream(request()).await.unwrap();
+        let response = collect_streaming_completion_response(stream).await.unwrap();
+
+        let AssistantContent::ToolCall(tool_call) = response.choice.first() else {
+            panic!("expected a tool call");
+        };
+        assert_eq!(tool_call.id, "tooluse_1");
+        assert_eq!(tool_call.function.name, "reply");
+        assert_eq!(tool_call.function.arguments["content"], "hi");
+        assert_eq!(response.usage.output_tokens, 7);
+    }
+
+    #[tokio::test]
+    async fn throttling_errors_are_classified_as_rate_limits() {
+        let (base_url, _recorded) = spawn_bedrock_stand_in().await;
+        let model = bedrock_model(&base_url, "throttled").await;
+
+        let error = model.attempt_completion(request()).await.unwrap_err();
+        let message = error.to_string();
+        assert!(message.contains("429"));
+        assert!(message.contains("ThrottlingException: Too many requests"));
+        assert!(routing::is_retriable_error(&message));
+        assert!(routing::is_rate_limit_error(&message));
+    }
+}
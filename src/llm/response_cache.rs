@@ -0,0 +1,613 @@
+//! Opt-in completion cache for background LLM calls.
+//!
+//! The cortex regenerates bulletins, profiles, and knowledge syntheses from
+//! mostly unchanged memories, and the compactor re-summarizes overlapping
+//! windows, so many of their prompts repeat. When the agent's
+//! `response_cache` is enabled, `SpacebotModel` asks its `ResponseCache`
+//! before dispatching a completion for a cached process type: first by an
+//! exact hash of the request, then (with a `similarity_threshold`) by
+//! embedding similarity against entries that share the request's model,
+//! preamble, and settings.
+//!
+//! Entries live in the agent's `llm_response_cache` table, expire after
+//! `ttl_secs`, and are evicted least recently used beyond `max_entries`.
+//! Channel and worker calls, and any request that offers tools, always
+//! bypass the cache. Cache failures are logged and treated as misses.
+
+use crate::config::{ResponseCacheConfig, RuntimeConfig};
+use crate::error::Result;
+use crate::llm::model::RawResponse;
+use crate::memory::EmbeddingModel;
+
+use chrono::Utc;
+use rig::completion::{self, CompletionRequest};
+use rig::message::{AssistantContent, Message, UserContent};
+use rig::one_or_many::OneOrMany;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest as _, Sha256};
+use sqlx::{Row as _, SqlitePool};
+
+use std::fmt;
+use std::sync::Arc;
+
+/// Per-agent completion cache backed by the agent's SQLite database.
+pub struct ResponseCache {
+    agent_id: String,
+    pool: SqlitePool,
+    runtime_config: Arc<RuntimeConfig>,
+    embedding_model: Option<Arc<EmbeddingModel>>,
+}
+
+impl fmt::Debug for ResponseCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseCache")
+            .field("agent_id", &self.agent_id)
+            .field("similarity", &self.embedding_model.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Result of a cache lookup.
+pub enum CacheLookup {
+    Hit(completion::CompletionResponse<RawResponse>),
+    /// Carries what `store` needs once the provider has answered.
+    Miss(PendingEntry),
+}
+
+/// A request that missed the cache.
+pub struct PendingEntry {
+    cache_key: String,
+    context_key: String,
+    process_type: String,
+    model: String,
+    embedding: Option<Vec<f32>>,
+}
+
+/// What a cache row stores. Usage is not kept: a hit costs nothing.
+#[derive(Serialize, Deserialize)]
+struct CachedResponse {
+    choice: OneOrMany<AssistantContent>,
+    message_id: Option<String>,
+    raw_response: serde_json::Value,
+}
+
+impl CachedResponse {
+    fn into_response(self) -> completion::CompletionResponse<RawResponse> {
+        completion::CompletionResponse {
+            choice: self.choice,
+            usage: completion::Usage::new(),
+            raw_response: RawResponse {
+                body: self.raw_response,
+            },
+            message_id: self.message_id,
+        }
+    }
+}
+
+impl ResponseCache {
+    pub fn new(
+        agent_id: impl Into<String>,
+        pool: SqlitePool,
+        runtime_config: Arc<RuntimeConfig>,
+        embedding_model: Option<Arc<EmbeddingModel>>,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            agent_id: agent_id.into(),
+            pool,
+            runtime_config,
+            embedding_model,
+        })
+    }
+
+    pub fn agent_id(&self) -> &str {
+        &self.agent_id
+    }
+
+    /// Whether a completion for `process_type` may be served from the cache.
+    pub fn applies_to(&self, process_type: &str, request: &CompletionRequest) -> bool {
+        is_cacheable(
+            &self.runtime_config.response_cache.load(),
+            process_type,
+            request,
+        )
+    }
+
+    /// Look up a cached response for `request`, exact match first.
+    pub async fn lookup(
+        &self,
+        model: &str,
+        process_type: &str,
+        request: &CompletionRequest,
+    ) -> CacheLookup {
+        let config = self.runtime_config.response_cache.load_full();
+        let (cache_key, context_key) = cache_keys(model, process_type, request);
+        let oldest = Utc::now().timestamp() - config.ttl_secs as i64;
+
+        match self.find_exact(&cache_key, oldest).await {
+            Ok(Some(response)) => {
+                self.touch(&cache_key).await;
+                self.record_lookup(process_type, "exact_hit");
+                return CacheLookup::Hit(response.into_response());
+            }
+            Ok(None) => {}
+            Err(error) => {
+                tracing::warn!(agent_id = %self.agent_id, %error, "response cache lookup failed");
+            }
+        }
+
+        let mut embedding = None;
+        if let (Some(threshold), Some(embedding_model)) =
+            (config.similarity_threshold, &self.embedding_model)
+        {
+            match embedding_model.embed_one(&prompt_text(request)).await {
+                Ok(vector) => {
+                    match self
+                        .find_similar(&context_key, oldest, &vector, threshold)
+                        .await
+                    {
+                        Ok(Some((similar_key, response))) => {
+                            self.touch(&similar_key).await;
+                            self.record_lookup(process_type, "similar_hit");
+                            return CacheLookup::Hit(response.into_response());
+                        }
+                        Ok(None) => {}
+                        Err(error) => {
+                            tracing::warn!(
+                                agent_id = %self.agent_id,
+                                %error,
+                                "response cache similarity lookup failed"
+                            );
+                        }
+                    }
+                    embedding = Some(vector);
+                }
+                Err(error) => {
+                    tracing::warn!(
+                        agent_id = %self.agent_id,
+                        %error,
+                        "failed to embed prompt for response cache"
+                    );
+                }
+            }
+        }
+
+        self.record_lookup(process_type, "miss");
+        CacheLookup::Miss(PendingEntry {
+            cache_key,
+            context_key,
+            process_type: process_type.to_string(),
+            model: model.to_string(),
+            embedding,
+        })
+    }
+
+    /// Cache the provider's response to a request that missed.
+    pub async fn store(
+        &self,
+        entry: PendingEntry,
+        response: &completion::CompletionResponse<RawResponse>,
+    ) {
+        let config = self.runtime_config.response_cache.load_full();
+        if let Err(error) = self.insert(&entry, response, &config).await {
+            tracing::warn!(agent_id = %self.agent_id, %error, "failed to store cached response");
+        }
+    }
+
+    async fn find_exact(&self, cache_key: &str, oldest: i64) -> Result<Option<CachedResponse>> {
+        let response: Option<String> = sqlx::query_scalar(
+            "SELECT response FROM llm_response_cache WHERE cache_key = ? AND created_at >= ?",
+        )
+        .bind(cache_key)
+        .bind(oldest)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(response.and_then(|response| serde_json::from_str(&response).ok()))
+    }
+
+    /// Most similar unexpired entry with the same context, if any clears `threshold`.
+    async fn find_similar(
+        &self,
+        context_key: &str,
+        oldest: i64,
+        embedding: &[f32],
+        threshold: f32,
+    ) -> Result<Option<(String, CachedResponse)>> {
+        let rows = sqlx::query(
+            "SELECT cache_key, embedding, response FROM llm_response_cache \
+             WHERE context_key = ? AND created_at >= ? AND embedding IS NOT NULL",
+        )
+        .bind(context_key)
+        .bind(oldest)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let best = rows
+            .iter()
+            .filter_map(|row| {
+                let stored = decode_embedding(&row.get::<Vec<u8>, _>("embedding"));
+                let similarity = cosine_similarity(embedding, &stored);
+                (similarity >= threshold).then_some((similarity, row))
+            })
+            .max_by(|(left, _), (right, _)| left.total_cmp(right));
+
+        Ok(best.and_then(|(_, row)| {
+            let response = serde_json::from_str(&row.get::<String, _>("response")).ok()?;
+            Some((row.get("cache_key"), response))
+        }))
+    }
+
+    async fn touch(&self, cache_key: &str) {
+        let result = sqlx::query(
+            "UPDATE llm_response_cache SET hits = hits + 1, last_used_at = ? WHERE cache_key = ?",
+        )
+        .bind(Utc::now().timestamp())
+        .bind(cache_key)
+        .execute(&self.pool)
+        .await;
+        if let Err(error) = result {
+            tracing::debug!(%error, "failed to update response cache entry");
+        }
+    }
+
+    async fn insert(
+        &self,
+        entry: &PendingEntry,
+        response: &completion::CompletionResponse<RawResponse>,
+        config: &ResponseCacheConfig,
+    ) -> Result<()> {
+        let cached = serde_json::to_string(&CachedResponse {
+            choice: response.choice.clone(),
+            message_id: response.message_id.clone(),
+            raw_response: response.raw_response.body.clone(),
+        })
+        .map_err(|error| anyhow::anyhow!("failed to serialize cached response: {error}"))?;
+        let now = Utc::now().timestamp();
+
+        sqlx::query(
+            "INSERT INTO llm_response_cache \
+             (cache_key, context_key, process_type, model, embedding, response, created_at, last_used_at) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?) \
+             ON CONFLICT(cache_key) DO UPDATE SET \
+             embedding = excluded.embedding, response = excluded.response, hits = 0, \
+             created_at = excluded.created_at, last_used_at = excluded.last_used_at",
+        )
+        .bind(&entry.cache_key)
+        .bind(&entry.context_key)
+        .bind(&entry.process_type)
+        .bind(&entry.model)
+        .bind(entry.embedding.as_deref().map(encode_embedding))
+        .bind(cached)
+        .bind(now)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query("DELETE FROM llm_response_cache WHERE created_at < ?")
+            .bind(now - config.ttl_secs as i64)
+            .execute(&self.pool)
+            .await?;
+        sqlx::query(
+            "DELETE FROM llm_response_cache WHERE cache_key NOT IN \
+             (SELECT cache_key FROM llm_response_cache ORDER BY last_used_at DESC, rowid DESC LIMIT ?)",
+        )
+        .bind(config.max_entries as i64)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    fn record_lookup(&self, process_type: &str, result: &str) {
+        #[cfg(feature = "metrics")]
+        crate::telemetry::Metrics::global()
+            .llm_response_cache_total
+            .with_label_values(&[&self.agent_id, process_type, result])
+            .inc();
+    }
+}
+
+/// Channel and worker replies depend on live conversation state, and tool
+/// calls have side effects, so neither is ever served from the cache.
+fn is_cacheable(
+    config: &ResponseCacheConfig,
+    process_type: &str,
+    request: &CompletionRequest,
+) -> bool {
+    !matches!(process_type, "channel" | "worker")
+        && request.tools.is_empty()
+        && config.caches(process_type)
+}
+
+/// Hash the request twice: the full request, and everything but the
+/// conversation (the context similarity lookups are scoped to).
+fn cache_keys(model: &str, process_type: &str, request: &CompletionRequest) -> (String, String) {
+    let mut context = serde_json::json!({
+        "process_type": process_type,
+        "model": model,
+        "preamble": request.preamble,
+        "documents": request.documents,
+        "temperature": request.temperature,
+        "max_tokens": request.max_tokens,
+        "additional_params": request.additional_params,
+        "output_schema": request.output_schema,
+    });
+    context.sort_all_objects();
+    let context_key = hex::encode(Sha256::digest(context.to_string().as_bytes()));
+
+    let mut chat_history = serde_json::json!(request.chat_history);
+    chat_history.sort_all_objects();
+    let mut hasher = Sha256::new();
+    hasher.update(context_key.as_bytes());
+    hasher.update(chat_history.to_string().as_bytes());
+
+    (hex::encode(hasher.finalize()), context_key)
+}
+
+/// The conversation's text, which similarity lookups embed.
+fn prompt_text(request: &CompletionRequest) -> String {
+    let mut parts = Vec::new();
+    for message in request.chat_history.iter() {
+        match message {
+            Message::User { content } => {
+                parts.extend(content.iter().filter_map(|item| match item {
+                    UserContent::Text(text) => Some(text.text.as_str()),
+                    _ => None,
+                }));
+            }
+            Message::Assistant { content, .. } => {
+                parts.extend(content.iter().filter_map(|item| match item {
+                    AssistantContent::Text(text) => Some(text.text.as_str()),
+                    _ => None,
+                }));
+            }
+            Message::System { content } => parts.push(content.as_str()),
+        }
+    }
+    parts.join("\n")
+}
+
+fn encode_embedding(embedding: &[f32]) -> Vec<u8> {
+    embedding
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect()
+}
+
+fn decode_embedding(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect()
+}
+
+fn cosine_similarity(left: &[f32], right: &[f32]) -> f32 {
+    if left.len() != right.len() || left.is_empty() {
+        return 0.0;
+    }
+    let dot: f32 = left.iter().zip(right).map(|(a, b)| a * b).sum();
+    let left_norm = left.iter().map(|a| a * a).sum::<f32>().sqrt();
+    let right_norm = right.iter().map(|b| b * b).sum::<f32>().sqrt();
+    if left_norm == 0.0 || right_norm == 0.0 {
+        return 0.0;
+    }
+    dot / (left_norm * right_norm)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rig::completion::ToolDefinition;
+    use rig::message::Text;
+
+    fn request(prompt: &str) -> CompletionRequest {
+        CompletionRequest {
+            model: None,
+            preamble: Some("Summarize the memories.".into()),
+            chat_history: OneOrMany::one(Message::user(prompt)),
+            documents: Vec::new(),
+            tools: Vec::new(),
+            temperature: None,
+            max_tokens: None,
+            tool_choice: None,
+            additional_params: None,
+            output_schema: None,
+        }
+    }
+
+    fn response(text: &str) -> completion::CompletionResponse<RawResponse> {
+        completion::CompletionResponse {
+            choice: OneOrMany::one(AssistantContent::Text(Text { text: text.into() })),
+            usage: completion::Usage {
+                input_tokens: 100,
+                output_tokens: 20,
+                total_tokens: 120,
+                cached_input_tokens: 0,
+            },
+            raw_response: RawResponse {
+                body: serde_json::json!({"id": "msg_1"}),
+            },
+            message_id: None,
+        }
+    }
+
+    async fn test_cache(config: ResponseCacheConfig) -> (Arc<ResponseCache>, tempfile::TempDir) {
+        let instance_dir = tempfile::tempdir().unwrap();
+        let config_file = crate::config::Config::load_from_env(instance_dir.path()).unwrap();
+        let resolved = config_file.resolve_agents().into_iter().next().unwrap();
+        let runtime_config = Arc::new(RuntimeConfig::new(
+            instance_dir.path(),
+            &resolved,
+            &config_file.defaults,
+            crate::prompts::PromptEngine::new("en").unwrap(),
+            crate::identity::Identity::default(),
+            crate::skills::SkillSet::default(),
+        ));
+        runtime_config.response_cache.store(Arc::new(config));
+
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        (
+            ResponseCache::new("main", pool, runtime_config, None),
+            instance_dir,
+        )
+    }
+
+    fn enabled() -> ResponseCacheConfig {
+        ResponseCacheConfig {
+            enabled: true,
+            ..ResponseCacheConfig::default()
+        }
+    }
+
+    fn response_text(response: &completion::CompletionResponse<RawResponse>) -> String {
+        match response.choice.first() {
+            AssistantContent::Text(text) => text.text,
+            other => panic!("unexpected content: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn channel_worker_and_tool_calls_bypass_the_cache() {
+        let mut config = enabled();
+        config.process_types = vec![
+            crate::ProcessType::Channel,
+            crate::ProcessType::Worker,
+            crate::ProcessType::Cortex,
+        ];
+        let plain = request("memories");
+        assert!(is_cacheable(&config, "cortex", &plain));
+        assert!(!is_cacheable(&config, "channel", &plain));
+        assert!(!is_cacheable(&config, "worker", &plain));
+        assert!(!is_cacheable(&config, "branch", &plain));
+
+        let mut with_tools = request("memories");
+        with_tools.tools.push(ToolDefinition {
+            name: "memory_recall".into(),
+            description: "Recall memories".into(),
+            parameters: serde_json::json!({"type": "object"}),
+        });
+        assert!(!is_cacheable(&config, "cortex", &with_tools));
+
+        config.enabled = false;
+        assert!(!is_cacheable(&config, "cortex", &plain));
+    }
+
+    #[test]
+    fn cache_keys_scope_by_process_type_and_context() {
+        let (key, context) = cache_keys("anthropic/claude", "cortex", &request("a"));
+        let (other_key, other_context) = cache_keys("anthropic/claude", "cortex", &request("b"));
+        assert_ne!(key, other_key);
+        assert_eq!(context, other_context);
+
+        let (compactor_key, compactor_context) =
+            cache_keys("anthropic/claude", "compactor", &request("a"));
+        assert_ne!(key, compactor_key);
+        assert_ne!(context, compactor_context);
+    }
+
+    #[test]
+    fn embeddings_round_trip_and_compare() {
+        let embedding = vec![0.5, -1.25, 3.0];
+        assert_eq!(decode_embedding(&encode_embedding(&embedding)), embedding);
+        assert!((cosine_similarity(&embedding, &embedding) - 1.0).abs() < 1e-6);
+        assert_eq!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]), 0.0);
+        assert_eq!(cosine_similarity(&[1.0], &[1.0, 0.0]), 0.0);
+    }
+
+    #[tokio::test]
+    async fn exact_hits_are_free_and_scoped() {
+        let (cache, _instance_dir) = test_cache(enabled()).await;
+        let model = "anthropic/claude-haiku-4.5";
+
+        let CacheLookup::Miss(pending) = cache.lookup(model, "cortex", &request("a")).await else {
+            panic!("expected a miss on an empty cache");
+        };
+        cache.store(pending, &response("bulletin")).await;
+
+        let CacheLookup::Hit(hit) = cache.lookup(model, "cortex", &request("a")).await else {
+            panic!("expected an exact hit");
+        };
+        assert_eq!(response_text(&hit), "bulletin");
+        assert_eq!(hit.usage, completion::Usage::new());
+
+        assert!(matches!(
+            cache.lookup(model, "compactor", &request("a")).await,
+            CacheLookup::Miss(_)
+        ));
+        assert!(matches!(
+            cache.lookup(model, "cortex", &request("b")).await,
+            CacheLookup::Miss(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn similar_prompts_hit_above_threshold() {
+        let (cache, _instance_dir) = test_cache(enabled()).await;
+        let (cache_key, context_key) = cache_keys("m", "cortex", &request("a"));
+        let entry = PendingEntry {
+            cache_key,
+            context_key: context_key.clone(),
+            process_type: "cortex".into(),
+            model: "m".into(),
+            embedding: Some(vec![1.0, 0.0, 0.0]),
+        };
+        cache.store(entry, &response("profile")).await;
+
+        let (_, found) = cache
+            .find_similar(&context_key, 0, &[0.99, 0.1, 0.0], 0.95)
+            .await
+            .unwrap()
+            .expect("expected a similar entry");
+        assert_eq!(found.raw_response["id"], "msg_1");
+        assert!(
+            cache
+                .find_similar(&context_key, 0, &[0.0, 1.0, 0.0], 0.95)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn expired_and_excess_entries_are_evicted() {
+        let config = ResponseCacheConfig {
+            max_entries: 2,
+            ..enabled()
+        };
+        let (cache, _instance_dir) = test_cache(config).await;
+        for prompt in ["a", "b", "c"] {
+            let CacheLookup::Miss(pending) = cache.lookup("m", "cortex", &request(prompt)).await
+            else {
+                panic!("expected a miss");
+            };
+            cache.store(pending, &response(prompt)).await;
+        }
+
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM llm_response_cache")
+            .fetch_one(&cache.pool)
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+        assert!(matches!(
+            cache.lookup("m", "cortex", &request("a")).await,
+            CacheLookup::Miss(_)
+        ));
+        assert!(matches!(
+            cache.lookup("m", "cortex", &request("c")).await,
+            CacheLookup::Hit(_)
+        ));
+
+        let (cache_key, _) = cache_keys("m", "cortex", &request("c"));
+        sqlx::query("UPDATE llm_response_cache SET created_at = 0 WHERE cache_key = ?")
+            .bind(&cache_key)
+            .execute(&cache.pool)
+            .await
+            .unwrap();
+        assert!(matches!(
+            cache.lookup("m", "cortex", &request("c")).await,
+            CacheLookup::Miss(_)
+        ));
+    }
+}
@@ -1,6 +1,9 @@
 //! Model routing configuration and resolution.
 
 use crate::ProcessType;
+
+use serde::{Deserialize, Serialize};
+
 use std::collections::HashMap;
 
 /// Model routing configuration. Lives on the agent config (via defaults).
@@ -32,6 +35,11 @@ pub struct RoutingConfig {
     pub worker_thinking_effort: String,
     pub compactor_thinking_effort: String,
     pub cortex_thinking_effort: String,
+
+    /// Prompt-complexity routing. Disabled by default; when enabled it picks
+    /// a light/standard/heavy model per message for the configured process
+    /// types.
+    pub prompt_routing: PromptRoutingConfig,
 }
 
 impl Default for RoutingConfig {
@@ -58,6 +66,7 @@ impl RoutingConfig {
             worker_thinking_effort: "auto".into(),
             compactor_thinking_effort: "auto".into(),
             cortex_thinking_effort: "auto".into(),
+            prompt_routing: PromptRoutingConfig::default(),
         }
     }
 }
@@ -82,6 +91,72 @@ impl RoutingConfig {
         }
     }
 
+    /// Resolve the model for a process, taking the triggering user message
+    /// into account when prompt routing is enabled.
+    ///
+    /// Task-type overrides still win. Returns the tier the message was
+    /// classified into (after the routing profile is applied) so callers can
+    /// label metrics; `None` means prompt routing did not apply.
+    pub fn resolve_for_message(
+        &self,
+        process_type: ProcessType,
+        task_type: Option<&str>,
+        user_message: Option<&str>,
+    ) -> (&str, Option<PromptTier>) {
+        let default_model = self.resolve(process_type, task_type);
+
+        let has_task_override = task_type.is_some_and(|task| {
+            matches!(process_type, ProcessType::Worker | ProcessType::Branch)
+                && self.task_overrides.contains_key(task)
+        });
+        if has_task_override {
+            return (default_model, None);
+        }
+
+        let Some(message) = user_message else {
+            return (default_model, None);
+        };
+        let Some(tier) = self.prompt_routing.classify(message, process_type) else {
+            return (default_model, None);
+        };
+
+        let model = self
+            .prompt_routing
+            .tiers
+            .model_for(tier)
+            .unwrap_or(default_model);
+        (model, Some(tier))
+    }
+
+    /// Thinking effort for a completion call. A model picked by prompt
+    /// routing rarely matches its process's default model, so when a prompt
+    /// tier applied the effort comes from the process the call was routed for.
+    pub fn thinking_effort_for_call(
+        &self,
+        model_name: &str,
+        process_type: Option<&str>,
+        prompt_tier: Option<PromptTier>,
+    ) -> &str {
+        if prompt_tier.is_some()
+            && let Some(effort) =
+                process_type.and_then(|process| self.thinking_effort_for_process(process))
+        {
+            return effort;
+        }
+        self.thinking_effort_for_model(model_name)
+    }
+
+    fn thinking_effort_for_process(&self, process_type: &str) -> Option<&str> {
+        match process_type {
+            "channel" => Some(&self.channel_thinking_effort),
+            "branch" => Some(&self.branch_thinking_effort),
+            "worker" => Some(&self.worker_thinking_effort),
+            "compactor" => Some(&self.compactor_thinking_effort),
+            "cortex" => Some(&self.cortex_thinking_effort),
+            _ => None,
+        }
+    }
+
     pub fn thinking_effort_for_model(&self, model_name: &str) -> &str {
         if self.channel == model_name {
             return &self.channel_thinking_effort;
@@ -110,6 +185,374 @@ impl RoutingConfig {
     }
 }
 
+/// Complexity tier a user message is routed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PromptTier {
+    Light,
+    Standard,
+    Heavy,
+}
+
+impl PromptTier {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PromptTier::Light => "light",
+            PromptTier::Standard => "standard",
+            PromptTier::Heavy => "heavy",
+        }
+    }
+}
+
+impl std::fmt::Display for PromptTier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Per-agent routing profile. Profiles shift which tier model a classified
+/// message lands on; they compose with the scorer rather than replacing it.
+///
+/// | Profile  | light → | standard → | heavy → |
+/// |----------|---------|------------|---------|
+/// | eco      | light   | light      | standard|
+/// | balanced | light   | standard   | heavy   |
+/// | premium  | standard| heavy      | heavy   |
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoutingProfile {
+    Eco,
+    #[default]
+    Balanced,
+    Premium,
+}
+
+impl RoutingProfile {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "eco" => Some(Self::Eco),
+            "balanced" | "auto" => Some(Self::Balanced),
+            "premium" => Some(Self::Premium),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RoutingProfile::Eco => "eco",
+            RoutingProfile::Balanced => "balanced",
+            RoutingProfile::Premium => "premium",
+        }
+    }
+
+    /// Map a scored tier to the tier whose model this profile uses.
+    pub fn apply(&self, tier: PromptTier) -> PromptTier {
+        match (self, tier) {
+            (RoutingProfile::Balanced, tier) => tier,
+            (RoutingProfile::Eco, PromptTier::Heavy) => PromptTier::Standard,
+            (RoutingProfile::Eco, _) => PromptTier::Light,
+            (RoutingProfile::Premium, PromptTier::Light) => PromptTier::Standard,
+            (RoutingProfile::Premium, _) => PromptTier::Heavy,
+        }
+    }
+}
+
+/// Tier → model mapping. Unset tiers fall back to the process-type default.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PromptTiers {
+    pub light: Option<String>,
+    pub standard: Option<String>,
+    pub heavy: Option<String>,
+}
+
+impl PromptTiers {
+    pub fn model_for(&self, tier: PromptTier) -> Option<&str> {
+        match tier {
+            PromptTier::Light => self.light.as_deref(),
+            PromptTier::Standard => self.standard.as_deref(),
+            PromptTier::Heavy => self.heavy.as_deref(),
+        }
+    }
+}
+
+/// Score thresholds separating the tiers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TierBoundaries {
+    /// Scores below this map to the light tier.
+    pub light_standard: f64,
+    /// Scores above this map to the heavy tier.
+    pub standard_heavy: f64,
+}
+
+impl Default for TierBoundaries {
+    fn default() -> Self {
+        Self {
+            light_standard: 0.0,
+            standard_heavy: 0.4,
+        }
+    }
+}
+
+/// Weight of each scoring dimension in the final complexity score.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DimensionWeights {
+    pub token_count: f64,
+    pub code_presence: f64,
+    pub reasoning: f64,
+    /// Applied negatively: simple indicators pull the score down.
+    pub simple: f64,
+    pub technical: f64,
+    pub multi_step: f64,
+    pub constraints: f64,
+}
+
+impl Default for DimensionWeights {
+    fn default() -> Self {
+        Self {
+            token_count: 0.10,
+            code_presence: 0.20,
+            reasoning: 0.20,
+            simple: 0.15,
+            technical: 0.15,
+            multi_step: 0.10,
+            constraints: 0.10,
+        }
+    }
+}
+
+/// Prompt-complexity routing settings (`[defaults.routing.prompt_routing]`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromptRoutingConfig {
+    pub enabled: bool,
+    /// Process types the scorer applies to. Workers, compactors and the
+    /// cortex are fixed-purpose and gain nothing from per-prompt routing.
+    pub process_types: Vec<ProcessType>,
+    pub tiers: PromptTiers,
+    pub boundaries: TierBoundaries,
+    pub weights: DimensionWeights,
+    pub profile: RoutingProfile,
+}
+
+impl Default for PromptRoutingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            process_types: vec![ProcessType::Channel, ProcessType::Branch],
+            tiers: PromptTiers::default(),
+            boundaries: TierBoundaries::default(),
+            weights: DimensionWeights::default(),
+            profile: RoutingProfile::default(),
+        }
+    }
+}
+
+// Keyword lists match on word boundaries (see `contains_term`), so short
+// entries like "ok" don't fire inside "look" or "token".
+const CODE_KEYWORDS: &[&str] = &[
+    "```",
+    "fn ",
+    "function",
+    "class ",
+    "import ",
+    "async",
+    "await",
+    "struct",
+    "impl",
+    "def ",
+    "compile",
+    "stack trace",
+    "traceback",
+    "regex",
+    "sql",
+    "refactor",
+    "segfault",
+];
+
+const REASONING_KEYWORDS: &[&str] = &[
+    "prove",
+    "proof",
+    "theorem",
+    "step by step",
+    "step-by-step",
+    "derive",
+    "reason through",
+    "chain of thought",
+    "trade-off",
+    "tradeoff",
+    "tradeoffs",
+    "explain why",
+    "compare",
+];
+
+const SIMPLE_KEYWORDS: &[&str] = &[
+    "what is",
+    "what's",
+    "who is",
+    "define",
+    "translate",
+    "hello",
+    "hi",
+    "hey",
+    "thanks",
+    "thank you",
+    "ok",
+    "okay",
+    "lol",
+    "good morning",
+    "good night",
+    "yes",
+    "no problem",
+];
+
+const TECHNICAL_KEYWORDS: &[&str] = &[
+    "algorithm",
+    "architecture",
+    "distributed",
+    "kubernetes",
+    "concurrency",
+    "database",
+    "latency",
+    "throughput",
+    "consensus",
+    "scalability",
+    "protocol",
+    "encryption",
+    "migration",
+    "infrastructure",
+    "optimize",
+    "optimization",
+];
+
+const MULTI_STEP_KEYWORDS: &[&str] = &[
+    "first",
+    "then",
+    "after that",
+    "finally",
+    "step 1",
+    "step 2",
+    "next,",
+];
+
+const CONSTRAINT_KEYWORDS: &[&str] = &[
+    "at most",
+    "at least",
+    "within",
+    "maximum",
+    "minimum",
+    "o(n",
+    "o(1)",
+    "o(log",
+    "must not",
+    "no more than",
+    "budget",
+    "deadline",
+];
+
+impl PromptRoutingConfig {
+    /// Classify a user message into a tier, with the routing profile applied.
+    ///
+    /// Returns `None` when prompt routing is disabled, the process type is
+    /// not covered, or the message is empty.
+    pub fn classify(&self, user_message: &str, process_type: ProcessType) -> Option<PromptTier> {
+        if !self.enabled || !self.process_types.contains(&process_type) {
+            return None;
+        }
+        if user_message.trim().is_empty() {
+            return None;
+        }
+
+        let score = self.score(user_message);
+        Some(self.profile.apply(self.tier_for_score(score)))
+    }
+
+    /// Weighted complexity score of a user message. Only the user's text is
+    /// scored — system prompts and bulletins are keyword-rich by nature and
+    /// would bias every dimension upwards.
+    pub fn score(&self, user_message: &str) -> f64 {
+        let lower = user_message.to_lowercase();
+        let weights = &self.weights;
+
+        let mut score = 0.0;
+        score += score_token_count(user_message) * weights.token_count;
+        score += score_keywords(&lower, CODE_KEYWORDS) * weights.code_presence;
+        score += score_keywords(&lower, REASONING_KEYWORDS) * weights.reasoning;
+        score -= score_keywords(&lower, SIMPLE_KEYWORDS) * weights.simple;
+        score += score_keywords(&lower, TECHNICAL_KEYWORDS) * weights.technical;
+        score += score_multi_step(&lower) * weights.multi_step;
+        score += score_keywords(&lower, CONSTRAINT_KEYWORDS) * weights.constraints;
+        score
+    }
+
+    fn tier_for_score(&self, score: f64) -> PromptTier {
+        if score < self.boundaries.light_standard {
+            PromptTier::Light
+        } else if score > self.boundaries.standard_heavy {
+            PromptTier::Heavy
+        } else {
+            PromptTier::Standard
+        }
+    }
+}
+
+/// Short messages score low, long ones high. Range [-1, 1].
+fn score_token_count(text: &str) -> f64 {
+    match text.split_whitespace().count() {
+        0..=8 => -1.0,
+        9..=40 => 0.0,
+        41..=120 => 0.5,
+        _ => 1.0,
+    }
+}
+
+/// Keyword score in [0, 1]. Two distinct hits saturate.
+fn score_keywords(lower: &str, keywords: &[&str]) -> f64 {
+    let hits = keywords
+        .iter()
+        .filter(|keyword| contains_term(lower, keyword))
+        .count();
+    match hits {
+        0 => 0.0,
+        1 => 0.5,
+        _ => 1.0,
+    }
+}
+
+/// Whether `term` occurs in `haystack` delimited by non-alphanumeric
+/// characters. Terms that start or end with punctuation/whitespace only
+/// check the boundary on their alphanumeric side.
+fn contains_term(haystack: &str, term: &str) -> bool {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+    let check_start = term.chars().next().is_some_and(is_word_char);
+    let check_end = term.chars().last().is_some_and(is_word_char);
+
+    haystack.match_indices(term).any(|(start, _)| {
+        let end = start + term.len();
+        let start_ok = !check_start
+            || !haystack[..start]
+                .chars()
+                .next_back()
+                .is_some_and(is_word_char);
+        let end_ok = !check_end || !haystack[end..].chars().next().is_some_and(is_word_char);
+        start_ok && end_ok
+    })
+}
+
+/// Detect sequenced instructions: keyword sequences or numbered lists.
+fn score_multi_step(lower: &str) -> f64 {
+    let numbered_lines = lower
+        .lines()
+        .map(str::trim_start)
+        .filter(|line| {
+            let digits = line.chars().take_while(char::is_ascii_digit).count();
+            digits > 0 && matches!(line[digits..].chars().next(), Some('.') | Some(')'))
+        })
+        .count();
+    if numbered_lines >= 2 {
+        return 1.0;
+    }
+    score_keywords(lower, MULTI_STEP_KEYWORDS)
+}
+
 /// Whether an HTTP status code should trigger a fallback to the next model.
 pub fn is_retriable_status(status: u16) -> bool {
     matches!(status, 429 | 502 | 503 | 504)
@@ -138,6 +581,12 @@ pub fn is_retriable_error(error_message: &str) -> bool {
         || lower.contains("empty response")
         || lower.contains("failed to read response body")
         || lower.contains("error decoding response body")
+        // AWS exception names, which Bedrock stream errors carry without a
+        // status code
+        || lower.contains("throttlingexception")
+        || lower.contains("serviceunavailableexception")
+        || lower.contains("modelnotreadyexception")
+        || lower.contains("modeltimeoutexception")
 }
 
 /// Whether a completion error indicates context window overflow.
@@ -469,13 +918,132 @@ pub const RETRY_BASE_DELAY_MS: u64 = 500;
 /// momentary and shouldn't lock out a model for the full cooldown period.
 pub fn is_rate_limit_error(error_message: &str) -> bool {
     let lower = error_message.to_lowercase();
-    lower.contains("429") || lower.contains("rate limit")
+    lower.contains("429") || lower.contains("rate limit") || lower.contains("throttlingexception")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn prompt_routing_config() -> RoutingConfig {
+        RoutingConfig {
+            prompt_routing: PromptRoutingConfig {
+                enabled: true,
+                tiers: PromptTiers {
+                    light: Some("anthropic/claude-haiku-4.5".into()),
+                    standard: None,
+                    heavy: Some("anthropic/claude-opus-4".into()),
+                },
+                ..PromptRoutingConfig::default()
+            },
+            ..RoutingConfig::default()
+        }
+    }
+
+    #[test]
+    fn prompt_routing_disabled_by_default() {
+        let routing = RoutingConfig::default();
+        let (model, tier) = routing.resolve_for_message(ProcessType::Channel, None, Some("hi"));
+        assert_eq!(model, "anthropic/claude-sonnet-4");
+        assert_eq!(tier, None);
+    }
+
+    #[test]
+    fn prompt_routing_downgrades_simple_messages() {
+        let routing = prompt_routing_config();
+        let (model, tier) =
+            routing.resolve_for_message(ProcessType::Channel, None, Some("hey, thanks!"));
+        assert_eq!(tier, Some(PromptTier::Light));
+        assert_eq!(model, "anthropic/claude-haiku-4.5");
+    }
+
+    #[test]
+    fn prompt_routing_keeps_default_for_standard_messages() {
+        let routing = prompt_routing_config();
+        let (model, tier) = routing.resolve_for_message(
+            ProcessType::Channel,
+            None,
+            Some("can you summarize what the team decided about the release notes yesterday"),
+        );
+        assert_eq!(tier, Some(PromptTier::Standard));
+        assert_eq!(model, "anthropic/claude-sonnet-4");
+    }
+
+    #[test]
+    fn prompt_routing_upgrades_complex_messages() {
+        let routing = prompt_routing_config();
+        let message = "First, refactor the async scheduler so the consensus algorithm \
+            is distributed across nodes. Then prove step by step that the retry loop \
+            terminates within at most O(n log n) rounds.\n```rust\nfn schedule() {}\n```";
+        let (model, tier) = routing.resolve_for_message(ProcessType::Channel, None, Some(message));
+        assert_eq!(tier, Some(PromptTier::Heavy));
+        assert_eq!(model, "anthropic/claude-opus-4");
+    }
+
+    #[test]
+    fn prompt_routing_skips_uncovered_process_types_and_task_overrides() {
+        let mut routing = prompt_routing_config();
+        routing
+            .task_overrides
+            .insert("coding".into(), "anthropic/claude-sonnet-4-coding".into());
+
+        let (model, tier) = routing.resolve_for_message(ProcessType::Worker, None, Some("hi"));
+        assert_eq!(model, "anthropic/claude-sonnet-4");
+        assert_eq!(tier, None);
+
+        let (model, tier) =
+            routing.resolve_for_message(ProcessType::Branch, Some("coding"), Some("hi"));
+        assert_eq!(model, "anthropic/claude-sonnet-4-coding");
+        assert_eq!(tier, None);
+    }
+
+    #[test]
+    fn routing_profiles_shift_tiers() {
+        let mut routing = prompt_routing_config();
+        routing.prompt_routing.profile = RoutingProfile::Eco;
+        let (_, tier) = routing.resolve_for_message(
+            ProcessType::Channel,
+            None,
+            Some("can you summarize what the team decided about the release notes yesterday"),
+        );
+        assert_eq!(tier, Some(PromptTier::Light));
+
+        routing.prompt_routing.profile = RoutingProfile::Premium;
+        let (model, tier) = routing.resolve_for_message(ProcessType::Channel, None, Some("hi"));
+        assert_eq!(tier, Some(PromptTier::Standard));
+        assert_eq!(model, "anthropic/claude-sonnet-4");
+
+        assert_eq!(RoutingProfile::parse("ECO"), Some(RoutingProfile::Eco));
+        assert_eq!(RoutingProfile::parse("turbo"), None);
+    }
+
+    #[test]
+    fn routed_models_use_the_process_thinking_effort() {
+        let mut routing = prompt_routing_config();
+        routing.channel_thinking_effort = "high".into();
+        assert_eq!(
+            routing.thinking_effort_for_call(
+                "claude-opus-4",
+                Some("channel"),
+                Some(PromptTier::Heavy)
+            ),
+            "high"
+        );
+        assert_eq!(
+            routing.thinking_effort_for_call("claude-opus-4", Some("channel"), None),
+            "auto"
+        );
+    }
+
+    #[test]
+    fn keyword_matching_respects_word_boundaries() {
+        assert!(contains_term("ok let's go", "ok"));
+        assert!(!contains_term("look at this", "ok"));
+        assert!(!contains_term("this is fine", "hi"));
+        assert!(contains_term("runs in o(n) time", "o(n"));
+        assert!(contains_term("see ```code```", "```"));
+    }
+
     #[test]
     fn is_retriable_error_catches_network_failures() {
         // DNS/connection failures from reqwest
@@ -524,6 +1092,25 @@ mod tests {
         assert!(is_retriable_error("error decoding response body"));
     }
 
+    #[test]
+    fn is_retriable_error_catches_bedrock_exceptions() {
+        assert!(is_retriable_error(
+            "Bedrock stream error: throttlingException: Too many requests"
+        ));
+        assert!(is_retriable_error(
+            "Bedrock stream error: serviceUnavailableException: try again"
+        ));
+        assert!(is_retriable_error(
+            "Bedrock API error (424 Failed Dependency): ModelNotReadyException: loading"
+        ));
+        assert!(is_retriable_error(
+            "Bedrock stream error: modelTimeoutException: took too long"
+        ));
+        assert!(!is_retriable_error(
+            "Bedrock API error (403 Forbidden): AccessDeniedException: denied"
+        ));
+    }
+
     #[test]
     fn is_retriable_error_rejects_non_retriable_errors() {
         // Auth errors should not be retriable
@@ -543,6 +1130,9 @@ mod tests {
         assert!(is_rate_limit_error("429 Too Many Requests"));
         assert!(is_rate_limit_error("rate limit exceeded"));
         assert!(is_rate_limit_error("RATE LIMIT: too many requests"));
+        assert!(is_rate_limit_error(
+            "Bedrock stream error: throttlingException: slow down"
+        ));
         // Other transient errors should not be rate limited
         assert!(!is_rate_limit_error("503 Service Unavailable"));
         assert!(!is_rate_limit_error("timeout"));
@@ -52,6 +52,9 @@ enum Command {
     /// Manage secrets stored in the running instance
     #[command(subcommand)]
     Secrets(SecretsCommand),
+    /// Manage named API tokens and view the API audit log
+    #[command(subcommand)]
+    Tokens(TokensCommand),
 }
 
 #[derive(Subcommand)]
@@ -176,6 +179,43 @@ enum SecretsCommand {
     },
 }
 
+#[derive(Subcommand)]
+enum TokensCommand {
+    /// List API tokens (secrets are never shown)
+    List,
+    /// Create a token and print its secret once
+    Create {
+        /// Token name (e.g. ci-deploy)
+        name: String,
+        /// Scope to grant: read, agents, messaging, config, secrets or admin (repeatable)
+        #[arg(short, long = "scope", required = true)]
+        scopes: Vec<String>,
+        /// Restrict the token to an agent (repeatable; defaults to every agent)
+        #[arg(short, long = "agent")]
+        agents: Vec<String>,
+        /// Days until the token expires (never, if omitted)
+        #[arg(long)]
+        expires_in_days: Option<u32>,
+    },
+    /// Revoke a token by ID
+    Revoke {
+        /// Token ID
+        id: String,
+    },
+    /// Show recent mutating API calls, newest first
+    Audit {
+        /// Only calls made with this token ID
+        #[arg(long)]
+        token: Option<String>,
+        /// Only calls that named this agent
+        #[arg(short, long)]
+        agent: Option<String>,
+        /// Maximum entries to show
+        #[arg(short, long, default_value_t = 50)]
+        limit: i64,
+    },
+}
+
 /// Tracks an active conversation channel and its message sender.
 struct ActiveChannel {
     message_tx: mpsc::Sender<spacebot::InboundMessage>,
@@ -300,13 +340,31 @@ fn render_conversation_history_backfill(
 }
 
 /// Forward outbound response events to SSE clients for the dashboard.
+///
+/// `streamed_text` holds the latest chunk of an in-flight streamed reply so
+/// the dashboard gets one outbound message when the stream ends.
 fn forward_sse_event(
     api_event_tx: &tokio::sync::broadcast::Sender<spacebot::api::ApiEvent>,
     agent_id: &str,
     channel_id: &str,
     response: &spacebot::OutboundResponse,
+    streamed_text: &mut Option<String>,
 ) {
     match response {
+        spacebot::OutboundResponse::StreamChunk(text) => {
+            *streamed_text = Some(text.clone());
+        }
+        spacebot::OutboundResponse::StreamEnd => {
+            if let Some(text) = streamed_text.take() {
+                api_event_tx
+                    .send(spacebot::api::ApiEvent::OutboundMessage {
+                        agent_id: agent_id.to_string(),
+                        channel_id: channel_id.to_string(),
+                        text,
+                    })
+                    .ok();
+            }
+        }
         spacebot::OutboundResponse::Text(text)
         | spacebot::OutboundResponse::RichMessage { text, .. }
         | spacebot::OutboundResponse::ThreadReply { text, .. } => {
@@ -380,6 +438,7 @@ fn main() -> anyhow::Result<()> {
         Command::Skill(skill_cmd) => cmd_skill(cli.config, skill_cmd),
         Command::Auth(auth_cmd) => cmd_auth(cli.config, auth_cmd),
         Command::Secrets(secrets_cmd) => cmd_secrets(cli.config, secrets_cmd),
+        Command::Tokens(tokens_cmd) => cmd_tokens(cli.config, tokens_cmd),
     }
 }
 
@@ -443,14 +502,22 @@ fn cmd_start(
         .context("failed to build Tokio runtime")?;
 
     runtime.block_on(async {
+        let log_buffer = spacebot::api::LogBuffer::new();
         let otel_provider = if foreground {
-            spacebot::daemon::init_foreground_tracing(debug, &config.telemetry)
+            spacebot::daemon::init_foreground_tracing(debug, &config.telemetry, &log_buffer)
         } else {
             let paths = spacebot::daemon::DaemonPaths::new(&config.instance_dir);
-            spacebot::daemon::init_background_tracing(&paths, debug, &config.telemetry)
+            spacebot::daemon::init_background_tracing(&paths, debug, &config.telemetry, &log_buffer)
         };
 
-        run(config, foreground, otel_provider, bootstrapped_store).await
+        run(
+            config,
+            foreground,
+            otel_provider,
+            log_buffer,
+            bootstrapped_store,
+        )
+        .await
     })
 }
 
@@ -679,7 +746,7 @@ fn cmd_secrets(
         match secrets_cmd {
             SecretsCommand::Status => {
                 let response =
-                    secrets_api_get(&client, &api_base, &auth_token, "secrets/status").await?;
+                    daemon_api_get(&client, &api_base, &auth_token, "secrets/status").await?;
                 let body: serde_json::Value = response.json().await?;
                 eprintln!(
                     "State:          {}",
@@ -707,7 +774,7 @@ fn cmd_secrets(
                 Ok(())
             }
             SecretsCommand::List => {
-                let response = secrets_api_get(&client, &api_base, &auth_token, "secrets").await?;
+                let response = daemon_api_get(&client, &api_base, &auth_token, "secrets").await?;
                 let body: serde_json::Value = response.json().await?;
                 let secrets = body["secrets"].as_array();
                 match secrets {
@@ -753,7 +820,7 @@ fn cmd_secrets(
                     body["category"] = serde_json::json!(cat);
                 }
 
-                let response = secrets_api_put(
+                let response = daemon_api_put(
                     &client,
                     &api_base,
                     &auth_token,
@@ -782,7 +849,7 @@ fn cmd_secrets(
             }
             SecretsCommand::Delete { name } => {
                 let response =
-                    secrets_api_delete(&client, &api_base, &auth_token, &format!("secrets/{name}"))
+                    daemon_api_delete(&client, &api_base, &auth_token, &format!("secrets/{name}"))
                         .await?;
 
                 if response.status().is_success() {
@@ -798,7 +865,7 @@ fn cmd_secrets(
                 Ok(())
             }
             SecretsCommand::Info { name } => {
-                let response = secrets_api_get(
+                let response = daemon_api_get(
                     &client,
                     &api_base,
                     &auth_token,
@@ -819,7 +886,7 @@ fn cmd_secrets(
                 Ok(())
             }
             SecretsCommand::Migrate => {
-                let response = secrets_api_post(
+                let response = daemon_api_post(
                     &client,
                     &api_base,
                     &auth_token,
@@ -851,7 +918,7 @@ fn cmd_secrets(
                 Ok(())
             }
             SecretsCommand::Encrypt => {
-                let response = secrets_api_post(
+                let response = daemon_api_post(
                     &client,
                     &api_base,
                     &auth_token,
@@ -891,7 +958,7 @@ fn cmd_secrets(
                         .context("failed to read master key")?
                 };
 
-                let response = secrets_api_post(
+                let response = daemon_api_post(
                     &client,
                     &api_base,
                     &auth_token,
@@ -910,7 +977,7 @@ fn cmd_secrets(
                 Ok(())
             }
             SecretsCommand::Lock => {
-                let response = secrets_api_post(
+                let response = daemon_api_post(
                     &client,
                     &api_base,
                     &auth_token,
@@ -939,7 +1006,7 @@ fn cmd_secrets(
                     return Ok(());
                 }
 
-                let response = secrets_api_post(
+                let response = daemon_api_post(
                     &client,
                     &api_base,
                     &auth_token,
@@ -964,7 +1031,7 @@ fn cmd_secrets(
                 Ok(())
             }
             SecretsCommand::Export { output } => {
-                let response = secrets_api_post(
+                let response = daemon_api_post(
                     &client,
                     &api_base,
                     &auth_token,
@@ -1019,7 +1086,7 @@ fn cmd_secrets(
 
                 import_data["overwrite"] = serde_json::json!(overwrite);
 
-                let response = secrets_api_post(
+                let response = daemon_api_post(
                     &client,
                     &api_base,
                     &auth_token,
@@ -1054,8 +1121,180 @@ fn cmd_secrets(
     })
 }
 
+fn cmd_tokens(
+    config_path: Option<std::path::PathBuf>,
+    tokens_cmd: TokensCommand,
+) -> anyhow::Result<()> {
+    bootstrap_secrets_store(&config_path);
+
+    let config = load_config(&config_path)?;
+    let api_base = format!("http://{}:{}/api", config.api.bind, config.api.port);
+    let auth_token = config.api.auth_token.clone();
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .context("failed to build tokio runtime")?;
+
+    runtime.block_on(async {
+        let client = reqwest::Client::new();
+
+        match tokens_cmd {
+            TokensCommand::List => {
+                let response = daemon_api_get(&client, &api_base, &auth_token, "tokens").await?;
+                if !response.status().is_success() {
+                    return Err(daemon_api_error(response).await);
+                }
+                let body: serde_json::Value = response.json().await?;
+                match body["tokens"].as_array() {
+                    Some(tokens) if !tokens.is_empty() => {
+                        eprintln!(
+                            "{:<36} {:<20} {:<14} {:<24} {:<17} STATE",
+                            "ID", "NAME", "PREFIX", "SCOPES", "LAST USED"
+                        );
+                        for token in tokens {
+                            let join = |key: &str| {
+                                token[key]
+                                    .as_array()
+                                    .map(|values| {
+                                        values
+                                            .iter()
+                                            .filter_map(|value| value.as_str())
+                                            .collect::<Vec<_>>()
+                                            .join(",")
+                                    })
+                                    .unwrap_or_default()
+                            };
+                            let last_used = token["last_used_at"].as_str().unwrap_or("never");
+                            let state = if token["revoked_at"].is_string() {
+                                "revoked".to_string()
+                            } else if let Some(expires_at) = token["expires_at"].as_str() {
+                                format!("expires {}", &expires_at[..expires_at.len().min(10)])
+                            } else {
+                                "active".to_string()
+                            };
+                            let agents = join("agent_ids");
+                            eprintln!(
+                                "{:<36} {:<20} {:<14} {:<24} {:<17} {}{}",
+                                token["id"].as_str().unwrap_or(""),
+                                token["name"].as_str().unwrap_or(""),
+                                token["prefix"].as_str().unwrap_or(""),
+                                join("scopes"),
+                                &last_used[..last_used.len().min(16)],
+                                state,
+                                if agents.is_empty() {
+                                    String::new()
+                                } else {
+                                    format!(" (agents: {agents})")
+                                }
+                            );
+                        }
+                    }
+                    _ => eprintln!("No API tokens."),
+                }
+                Ok(())
+            }
+            TokensCommand::Create {
+                name,
+                scopes,
+                agents,
+                expires_in_days,
+            } => {
+                if let Some(unknown) = scopes
+                    .iter()
+                    .find(|scope| spacebot::api::access::ApiScope::parse(scope).is_none())
+                {
+                    anyhow::bail!(
+                        "unknown scope '{unknown}' (expected read, agents, messaging, config, \
+                         secrets or admin)"
+                    );
+                }
+                let body = serde_json::json!({
+                    "name": name,
+                    "scopes": scopes,
+                    "agent_ids": agents,
+                    "expires_in_days": expires_in_days,
+                });
+                let response =
+                    daemon_api_post(&client, &api_base, &auth_token, "tokens", &body).await?;
+                if !response.status().is_success() {
+                    return Err(daemon_api_error(response).await);
+                }
+                let created: serde_json::Value = response.json().await?;
+                eprintln!(
+                    "Created token {} ({}).",
+                    created["token"]["name"].as_str().unwrap_or(&name),
+                    created["token"]["id"].as_str().unwrap_or("")
+                );
+                eprintln!("Store this secret now; it won't be shown again:");
+                println!("{}", created["secret"].as_str().unwrap_or(""));
+                Ok(())
+            }
+            TokensCommand::Revoke { id } => {
+                let response =
+                    daemon_api_delete(&client, &api_base, &auth_token, &format!("tokens/{id}"))
+                        .await?;
+                if !response.status().is_success() {
+                    return Err(daemon_api_error(response).await);
+                }
+                eprintln!("Revoked token {id}.");
+                Ok(())
+            }
+            TokensCommand::Audit {
+                token,
+                agent,
+                limit,
+            } => {
+                let mut path = format!("audit?limit={limit}");
+                if let Some(token) = &token {
+                    path.push_str(&format!("&token_id={token}"));
+                }
+                if let Some(agent) = &agent {
+                    path.push_str(&format!("&agent_id={agent}"));
+                }
+                let response = daemon_api_get(&client, &api_base, &auth_token, &path).await?;
+                if !response.status().is_success() {
+                    return Err(daemon_api_error(response).await);
+                }
+                let body: serde_json::Value = response.json().await?;
+                match body["entries"].as_array() {
+                    Some(entries) if !entries.is_empty() => {
+                        eprintln!(
+                            "{:<20} {:<24} {:<7} {:<6} {:<12} PATH",
+                            "TIME", "ACTOR", "METHOD", "STATUS", "AGENT"
+                        );
+                        for entry in entries {
+                            eprintln!(
+                                "{:<20} {:<24} {:<7} {:<6} {:<12} {}",
+                                entry["created_at"].as_str().unwrap_or(""),
+                                entry["actor"].as_str().unwrap_or(""),
+                                entry["method"].as_str().unwrap_or(""),
+                                entry["status"].as_i64().unwrap_or(0),
+                                entry["agent_id"].as_str().unwrap_or("-"),
+                                entry["path"].as_str().unwrap_or(""),
+                            );
+                        }
+                    }
+                    _ => eprintln!("No audit entries."),
+                }
+                Ok(())
+            }
+        }
+    })
+}
+
+/// Turn a failed control API response into an error carrying its message.
+async fn daemon_api_error(response: reqwest::Response) -> anyhow::Error {
+    let status = response.status();
+    let error: serde_json::Value = response.json().await.unwrap_or_default();
+    match error["error"].as_str() {
+        Some(message) => anyhow::anyhow!("{message}"),
+        None => anyhow::anyhow!("request failed with {status}"),
+    }
+}
+
 /// Build an authenticated HTTP request to the control API.
-fn secrets_api_request(
+fn daemon_api_request(
     client: &reqwest::Client,
     method: reqwest::Method,
     api_base: &str,
@@ -1070,27 +1309,27 @@ fn secrets_api_request(
     request
 }
 
-async fn secrets_api_get(
+async fn daemon_api_get(
     client: &reqwest::Client,
     api_base: &str,
     auth_token: &Option<String>,
     path: &str,
 ) -> anyhow::Result<reqwest::Response> {
-    let response = secrets_api_request(client, reqwest::Method::GET, api_base, auth_token, path)
+    let response = daemon_api_request(client, reqwest::Method::GET, api_base, auth_token, path)
         .send()
         .await
         .context("failed to connect to spacebot API — is the daemon running?")?;
     Ok(response)
 }
 
-async fn secrets_api_post(
+async fn daemon_api_post(
     client: &reqwest::Client,
     api_base: &str,
     auth_token: &Option<String>,
     path: &str,
     body: &serde_json::Value,
 ) -> anyhow::Result<reqwest::Response> {
-    let response = secrets_api_request(client, reqwest::Method::POST, api_base, auth_token, path)
+    let response = daemon_api_request(client, reqwest::Method::POST, api_base, auth_token, path)
         .json(body)
         .send()
         .await
@@ -1098,14 +1337,14 @@ async fn secrets_api_post(
     Ok(response)
 }
 
-async fn secrets_api_put(
+async fn daemon_api_put(
     client: &reqwest::Client,
     api_base: &str,
     auth_token: &Option<String>,
     path: &str,
     body: &serde_json::Value,
 ) -> anyhow::Result<reqwest::Response> {
-    let response = secrets_api_request(client, reqwest::Method::PUT, api_base, auth_token, path)
+    let response = daemon_api_request(client, reqwest::Method::PUT, api_base, auth_token, path)
         .json(body)
         .send()
         .await
@@ -1113,13 +1352,13 @@ async fn secrets_api_put(
     Ok(response)
 }
 
-async fn secrets_api_delete(
+async fn daemon_api_delete(
     client: &reqwest::Client,
     api_base: &str,
     auth_token: &Option<String>,
     path: &str,
 ) -> anyhow::Result<reqwest::Response> {
-    let response = secrets_api_request(client, reqwest::Method::DELETE, api_base, auth_token, path)
+    let response = daemon_api_request(client, reqwest::Method::DELETE, api_base, auth_token, path)
         .send()
         .await
         .context("failed to connect to spacebot API — is the daemon running?")?;
@@ -1591,6 +1830,7 @@ async fn run(
     config: spacebot::config::Config,
     foreground: bool,
     otel_provider: Option<opentelemetry_sdk::trace::SdkTracerProvider>,
+    log_buffer: spacebot::api::LogBuffer,
     bootstrapped_store: Option<Arc<spacebot::secrets::store::SecretsStore>>,
 ) -> anyhow::Result<()> {
     let paths = spacebot::daemon::DaemonPaths::new(&config.instance_dir);
@@ -1628,6 +1868,19 @@ async fn run(
 
     let global_task_store = Arc::new(spacebot::tasks::TaskStore::new(global_task_pool));
 
+    // Durable outbox for proactive messages, shared by every messaging manager
+    // built across reloads so queued deliveries survive restarts.
+    let outbox_pool = spacebot::db::connect_outbox(&config.instance_dir.join("data"))
+        .await
+        .context("failed to initialize messaging outbox database")?;
+    let outbox_store = Arc::new(spacebot::messaging::outbox::OutboxStore::new(outbox_pool));
+
+    // Named API tokens and the audit log of mutating API calls.
+    let api_access_pool = spacebot::db::connect_api_access(&config.instance_dir.join("data"))
+        .await
+        .context("failed to initialize API access database")?;
+    let api_access_store = Arc::new(spacebot::api::access::ApiAccessStore::new(api_access_pool));
+
     // Start HTTP API server if enabled
     let mut api_state = spacebot::api::ApiState::new_with_provider_sender(
         provider_tx,
@@ -1636,7 +1889,17 @@ async fn run(
         injection_tx.clone(),
     );
     api_state.auth_token = config.api.auth_token.clone();
+    if let Some(oidc_config) = config.api.oidc.clone() {
+        tracing::info!(issuer = %oidc_config.issuer, "OIDC single sign-on enabled");
+        api_state.oidc = Some(Arc::new(
+            spacebot::api::oidc::OidcClient::new(oidc_config)
+                .context("failed to initialize OIDC single sign-on")?,
+        ));
+    }
+    api_state.log_buffer = Some(log_buffer);
     api_state.set_task_store(global_task_store.clone());
+    api_state.set_outbox_store(outbox_store.clone());
+    api_state.set_api_access_store(api_access_store);
     let api_state = Arc::new(api_state);
 
     // Keep the secrets API available in setup mode so encrypted stores can be
@@ -1779,6 +2042,9 @@ async fn run(
         let mut telegram_permissions = None;
         let mut twitch_permissions = None;
         let mut mattermost_permissions = None;
+        let mut matrix_permissions = None;
+        let mut irc_permissions = None;
+        let mut teams_permissions = None;
         let mut signal_permissions = None;
         initialize_agents(
             &config,
@@ -1798,11 +2064,15 @@ async fn run(
             &mut telegram_permissions,
             &mut twitch_permissions,
             &mut mattermost_permissions,
+            &mut matrix_permissions,
+            &mut irc_permissions,
+            &mut teams_permissions,
             &mut signal_permissions,
             agent_links.clone(),
             agent_humans.clone(),
             injection_tx.clone(),
             global_task_store.clone(),
+            outbox_store.clone(),
             &bootstrapped_store,
         )
         .await?;
@@ -1818,6 +2088,9 @@ async fn run(
             telegram_permissions,
             twitch_permissions,
             mattermost_permissions,
+            matrix_permissions,
+            irc_permissions,
+            teams_permissions,
             signal_permissions,
             bindings.clone(),
             Some(messaging_manager.clone()),
@@ -1836,6 +2109,9 @@ async fn run(
             None, // telegram_permissions
             None, // twitch_permissions
             None, // mattermost_permissions
+            None, // matrix_permissions
+            None, // irc_permissions
+            None, // teams_permissions
             None, // signal_permissions
             bindings.clone(),
             None,
@@ -2132,6 +2408,7 @@ async fn run(
                     let sse_agent_id = agent_id.to_string();
                     let sse_channel_id = conversation_id.clone();
                     let outbound_handle = tokio::spawn(async move {
+                        let mut streamed_text = None;
                         while let Some(routed) = response_rx.recv().await {
                             let spacebot::RoutedResponse { response, target } = routed;
                             forward_sse_event(
@@ -2139,6 +2416,7 @@ async fn run(
                                 &sse_agent_id,
                                 &sse_channel_id,
                                 &response,
+                                &mut streamed_text,
                             );
                             route_outbound(&messaging_for_outbound, &target, response).await;
                         }
@@ -2192,6 +2470,19 @@ async fn run(
                     resolved
                 };
 
+                // Roles only ever come from config; never trust an inbound value.
+                message.metadata.remove(spacebot::metadata_keys::SENDER_ROLE);
+                if let Some(role) = spacebot::config::resolve_sender_role(
+                    &bindings.load(),
+                    &agent_humans.load(),
+                    &message,
+                ) {
+                    message.metadata.insert(
+                        spacebot::metadata_keys::SENDER_ROLE.into(),
+                        serde_json::json!(role),
+                    );
+                }
+
                 let conversation_id = message.conversation_id.clone();
                 let channel_key = ActiveChannelKey::new(agent_id.to_string(), conversation_id.clone());
 
@@ -2377,9 +2668,10 @@ async fn run(
                     let sse_agent_id = agent_id.to_string();
                     let sse_channel_id = conversation_id.clone();
                     let outbound_handle = tokio::spawn(async move {
+                        let mut streamed_text = None;
                         while let Some(routed) = response_rx.recv().await {
                             let spacebot::RoutedResponse { response, target } = routed;
-                            forward_sse_event(&api_event_tx, &sse_agent_id, &sse_channel_id, &response);
+                            forward_sse_event(&api_event_tx, &sse_agent_id, &sse_channel_id, &response, &mut streamed_text);
                             route_outbound(&messaging_for_outbound, &target, response).await;
                         }
                         tracing::debug!(
@@ -2553,6 +2845,9 @@ async fn run(
                                 let mut new_telegram_permissions = None;
                                 let mut new_twitch_permissions = None;
                                 let mut new_mattermost_permissions = None;
+                                let mut new_matrix_permissions = None;
+                                let mut new_irc_permissions = None;
+                                let mut new_teams_permissions = None;
                                 let mut new_signal_permissions = None;
                                 match initialize_agents(
                                     &new_config,
@@ -2572,11 +2867,15 @@ async fn run(
                                     &mut new_telegram_permissions,
                                     &mut new_twitch_permissions,
                                     &mut new_mattermost_permissions,
+                                    &mut new_matrix_permissions,
+                                    &mut new_irc_permissions,
+                                    &mut new_teams_permissions,
                                     &mut new_signal_permissions,
                                     agent_links.clone(),
                                     agent_humans.clone(),
                                     injection_tx.clone(),
                                     global_task_store.clone(),
+                                    outbox_store.clone(),
                                     &bootstrapped_store,
                                 ).await {
                                     Ok(()) => {
@@ -2591,6 +2890,9 @@ async fn run(
                                             new_telegram_permissions,
                                             new_twitch_permissions,
                                             new_mattermost_permissions,
+                                            new_matrix_permissions,
+                                            new_irc_permissions,
+                                            new_teams_permissions,
                                             new_signal_permissions,
                                             bindings.clone(),
                                             Some(messaging_manager.clone()),
@@ -2715,11 +3017,15 @@ async fn initialize_agents(
     telegram_permissions: &mut Option<Arc<ArcSwap<spacebot::config::TelegramPermissions>>>,
     twitch_permissions: &mut Option<Arc<ArcSwap<spacebot::config::TwitchPermissions>>>,
     mattermost_permissions: &mut Option<Arc<ArcSwap<spacebot::config::MattermostPermissions>>>,
+    matrix_permissions: &mut Option<Arc<ArcSwap<spacebot::config::MatrixPermissions>>>,
+    irc_permissions: &mut Option<Arc<ArcSwap<spacebot::config::IrcPermissions>>>,
+    teams_permissions: &mut Option<Arc<ArcSwap<spacebot::config::TeamsPermissions>>>,
     signal_permissions: &mut Option<Arc<ArcSwap<spacebot::config::SignalPermissions>>>,
     agent_links: Arc<ArcSwap<Vec<spacebot::links::AgentLink>>>,
     agent_humans: Arc<ArcSwap<Vec<spacebot::config::HumanDef>>>,
     injection_tx: tokio::sync::mpsc::Sender<spacebot::ChannelInjection>,
     global_task_store: Arc<spacebot::tasks::TaskStore>,
+    outbox_store: Arc<spacebot::messaging::outbox::OutboxStore>,
     bootstrapped_store: &Option<Arc<spacebot::secrets::store::SecretsStore>>,
 ) -> anyhow::Result<()> {
     let resolved_agents = config.resolve_agents();
@@ -2917,6 +3223,20 @@ async fn initialize_agents(
             spacebot::config::set_resolve_secrets_store(secrets_store.clone());
         }
 
+        // Spend ledger and budget enforcement, consulted by SpacebotModel.
+        llm_manager.register_spend_tracker(spacebot::llm::budget::SpendTracker::new(
+            agent_config.id.clone(),
+            db.sqlite.clone(),
+            working_memory_timezone,
+            runtime_config.clone(),
+        ));
+        llm_manager.register_response_cache(spacebot::llm::response_cache::ResponseCache::new(
+            agent_config.id.clone(),
+            db.sqlite.clone(),
+            runtime_config.clone(),
+            Some(embedding_model.clone()),
+        ));
+
         watcher_agents.push((
             agent_config.id.clone(),
             agent_config.workspace.clone(),
@@ -2965,6 +3285,7 @@ async fn initialize_agents(
             process_control_registry: Arc::new(
                 spacebot::agent::process_control::ProcessControlRegistry::new(),
             ),
+            tool_approvals: Arc::new(spacebot::agent::tool_approval::ToolApprovalRegistry::new()),
             injection_tx: injection_tx.clone(),
             working_memory,
         };
@@ -3032,6 +3353,7 @@ async fn initialize_agents(
         let mut agent_data_dirs = std::collections::HashMap::new();
         let mut runtime_configs = std::collections::HashMap::new();
         let mut sandboxes = std::collections::HashMap::new();
+        let mut tool_approvals = std::collections::HashMap::new();
         for (agent_id, agent) in agents.iter() {
             let event_rx = agent.deps.event_tx.subscribe();
             api_state.register_agent_events(agent_id.to_string(), event_rx);
@@ -3044,6 +3366,7 @@ async fn initialize_agents(
             agent_data_dirs.insert(agent_id.to_string(), agent.config.data_dir.clone());
             runtime_configs.insert(agent_id.to_string(), agent.deps.runtime_config.clone());
             sandboxes.insert(agent_id.to_string(), agent.deps.sandbox.clone());
+            tool_approvals.insert(agent_id.to_string(), agent.deps.tool_approvals.clone());
             agent_configs.push(spacebot::api::AgentInfo {
                 id: agent.config.id.clone(),
                 display_name: agent.config.display_name.clone(),
@@ -3067,6 +3390,7 @@ async fn initialize_agents(
         api_state.set_agent_identity_dirs(agent_identity_dirs);
         api_state.set_agent_data_dirs(agent_data_dirs);
         api_state.set_sandboxes(sandboxes);
+        api_state.set_tool_approvals(tool_approvals);
         // Wire the instance-level secrets store into the API state.
         if let Some(store) = &bootstrapped_store {
             api_state.set_secrets_store(store.clone());
@@ -3119,7 +3443,17 @@ async fn initialize_agents(
     }
 
     // Initialize messaging adapters
-    let new_messaging_manager = spacebot::messaging::MessagingManager::new();
+    let new_messaging_manager =
+        spacebot::messaging::MessagingManager::new().with_outbox(outbox_store);
+
+    // Adapters are shared between agents, so platforms with native command
+    // menus get every agent's chat commands. This is a startup snapshot:
+    // reloaded commands reach typed messages but not the platform menus.
+    let chat_commands = spacebot::commands::agent_command_specs(
+        agents
+            .values()
+            .map(|agent| (agent.id.as_ref(), agent.deps.runtime_config.as_ref())),
+    );
 
     // Shared Discord permissions (hot-reloadable via file watcher)
     *discord_permissions = config.messaging.discord.as_ref().map(|discord_config| {
@@ -3141,7 +3475,8 @@ async fn initialize_agents(
                 discord_permissions.clone().ok_or_else(|| {
                     anyhow::anyhow!("discord permissions not initialized when discord is enabled")
                 })?,
-            );
+            )
+            .with_commands(chat_commands.clone());
             new_messaging_manager.register(adapter).await;
         }
 
@@ -3168,7 +3503,8 @@ async fn initialize_agents(
                 runtime_key,
                 &instance.token,
                 perms,
-            );
+            )
+            .with_commands(chat_commands.clone());
             new_messaging_manager.register(adapter).await;
         }
     }
@@ -3196,6 +3532,7 @@ async fn initialize_agents(
                 slack_config.commands.clone(),
             ) {
                 Ok(adapter) => {
+                    let adapter = adapter.with_commands(chat_commands.clone());
                     new_messaging_manager.register(adapter).await;
                 }
                 Err(error) => {
@@ -3231,6 +3568,7 @@ async fn initialize_agents(
                 instance.commands.clone(),
             ) {
                 Ok(adapter) => {
+                    let adapter = adapter.with_commands(chat_commands.clone());
                     new_messaging_manager.register(adapter).await;
                 }
                 Err(error) => {
@@ -3257,7 +3595,8 @@ async fn initialize_agents(
                 telegram_permissions.clone().ok_or_else(|| {
                     anyhow::anyhow!("telegram permissions not initialized when telegram is enabled")
                 })?,
-            );
+            )
+            .with_commands(chat_commands.clone());
             new_messaging_manager.register(adapter).await;
         }
 
@@ -3284,7 +3623,8 @@ async fn initialize_agents(
                 runtime_key,
                 &instance.token,
                 perms,
-            );
+            )
+            .with_commands(chat_commands.clone());
             new_messaging_manager.register(adapter).await;
         }
     }
@@ -3337,7 +3677,8 @@ async fn initialize_agents(
             webhook_config.port,
             &webhook_config.bind,
             webhook_config.auth_token.clone(),
-        );
+        )
+        .with_signing_secret(webhook_config.signing_secret.clone());
         new_messaging_manager.register(adapter).await;
     }
 
@@ -3495,6 +3836,194 @@ async fn initialize_agents(
         }
     }
 
+    // Shared Matrix permissions (hot-reloadable via file watcher)
+    *matrix_permissions = config.messaging.matrix.as_ref().map(|matrix_config| {
+        let perms =
+            spacebot::config::MatrixPermissions::from_config(matrix_config, &config.bindings);
+        Arc::new(ArcSwap::from_pointee(perms))
+    });
+
+    if let Some(matrix_config) = &config.messaging.matrix
+        && matrix_config.enabled
+    {
+        if !matrix_config.homeserver_url.is_empty() && !matrix_config.access_token.is_empty() {
+            match spacebot::messaging::matrix::MatrixAdapter::new(
+                "matrix",
+                &matrix_config.homeserver_url,
+                matrix_config.access_token.as_str(),
+                matrix_config.max_attachment_bytes,
+                matrix_permissions.clone().ok_or_else(|| {
+                    anyhow::anyhow!("matrix permissions not initialized when matrix is enabled")
+                })?,
+            ) {
+                Ok(adapter) => {
+                    new_messaging_manager.register(adapter).await;
+                }
+                Err(error) => {
+                    tracing::error!(%error, "failed to create matrix adapter");
+                }
+            }
+        }
+
+        for instance in matrix_config
+            .instances
+            .iter()
+            .filter(|instance| instance.enabled)
+        {
+            if instance.homeserver_url.is_empty() || instance.access_token.is_empty() {
+                tracing::warn!(adapter = %instance.name, "skipping enabled matrix instance with missing credentials");
+                continue;
+            }
+            let runtime_key = spacebot::config::binding_runtime_adapter_key(
+                "matrix",
+                Some(instance.name.as_str()),
+            );
+            let perms = Arc::new(ArcSwap::from_pointee(
+                spacebot::config::MatrixPermissions::from_instance_config(
+                    instance,
+                    &config.bindings,
+                ),
+            ));
+            match spacebot::messaging::matrix::MatrixAdapter::new(
+                runtime_key,
+                &instance.homeserver_url,
+                instance.access_token.as_str(),
+                instance.max_attachment_bytes,
+                perms,
+            ) {
+                Ok(adapter) => {
+                    new_messaging_manager.register(adapter).await;
+                }
+                Err(error) => {
+                    tracing::error!(%error, adapter = %instance.name, "failed to create named matrix adapter");
+                }
+            }
+        }
+    }
+
+    // Shared IRC permissions (hot-reloadable via file watcher)
+    *irc_permissions = config.messaging.irc.as_ref().map(|irc_config| {
+        let perms = spacebot::config::IrcPermissions::from_config(irc_config, &config.bindings);
+        Arc::new(ArcSwap::from_pointee(perms))
+    });
+
+    if let Some(irc_config) = &config.messaging.irc
+        && irc_config.enabled
+    {
+        if !irc_config.server.is_empty() && !irc_config.nickname.is_empty() {
+            match spacebot::messaging::irc::IrcAdapter::new(
+                "irc",
+                spacebot::messaging::irc::IrcConnectionSettings::from_config(irc_config),
+                irc_permissions.clone().ok_or_else(|| {
+                    anyhow::anyhow!("irc permissions not initialized when irc is enabled")
+                })?,
+            ) {
+                Ok(adapter) => {
+                    new_messaging_manager.register(adapter).await;
+                }
+                Err(error) => {
+                    tracing::error!(%error, "failed to create irc adapter");
+                }
+            }
+        }
+
+        for instance in irc_config
+            .instances
+            .iter()
+            .filter(|instance| instance.enabled)
+        {
+            if instance.server.is_empty() || instance.nickname.is_empty() {
+                tracing::warn!(adapter = %instance.name, "skipping enabled irc instance with missing credentials");
+                continue;
+            }
+            let runtime_key =
+                spacebot::config::binding_runtime_adapter_key("irc", Some(instance.name.as_str()));
+            let perms = Arc::new(ArcSwap::from_pointee(
+                spacebot::config::IrcPermissions::from_instance_config(instance, &config.bindings),
+            ));
+            match spacebot::messaging::irc::IrcAdapter::new(
+                runtime_key,
+                spacebot::messaging::irc::IrcConnectionSettings::from_instance_config(instance),
+                perms,
+            ) {
+                Ok(adapter) => {
+                    new_messaging_manager.register(adapter).await;
+                }
+                Err(error) => {
+                    tracing::error!(%error, adapter = %instance.name, "failed to create named irc adapter");
+                }
+            }
+        }
+    }
+
+    // Shared Teams permissions (hot-reloadable via file watcher)
+    *teams_permissions = config.messaging.teams.as_ref().map(|teams_config| {
+        let perms = spacebot::config::TeamsPermissions::from_config(teams_config, &config.bindings);
+        Arc::new(ArcSwap::from_pointee(perms))
+    });
+
+    if let Some(teams_config) = &config.messaging.teams
+        && teams_config.enabled
+    {
+        if !teams_config.app_id.is_empty() && !teams_config.app_password.is_empty() {
+            match spacebot::messaging::teams::TeamsAdapter::new(
+                "teams",
+                spacebot::messaging::teams::TeamsConnectionSettings::from_config(teams_config),
+                Some(spacebot::messaging::teams::conversation_store_path(
+                    &config.instance_dir,
+                    None,
+                )),
+                teams_permissions.clone().ok_or_else(|| {
+                    anyhow::anyhow!("teams permissions not initialized when teams is enabled")
+                })?,
+            ) {
+                Ok(adapter) => {
+                    new_messaging_manager.register(adapter).await;
+                }
+                Err(error) => {
+                    tracing::error!(%error, "failed to create teams adapter");
+                }
+            }
+        }
+
+        for instance in teams_config
+            .instances
+            .iter()
+            .filter(|instance| instance.enabled)
+        {
+            if instance.app_id.is_empty() || instance.app_password.is_empty() {
+                tracing::warn!(adapter = %instance.name, "skipping enabled teams instance with missing credentials");
+                continue;
+            }
+            let runtime_key = spacebot::config::binding_runtime_adapter_key(
+                "teams",
+                Some(instance.name.as_str()),
+            );
+            let perms = Arc::new(ArcSwap::from_pointee(
+                spacebot::config::TeamsPermissions::from_instance_config(
+                    instance,
+                    &config.bindings,
+                ),
+            ));
+            match spacebot::messaging::teams::TeamsAdapter::new(
+                runtime_key,
+                spacebot::messaging::teams::TeamsConnectionSettings::from_instance_config(instance),
+                Some(spacebot::messaging::teams::conversation_store_path(
+                    &config.instance_dir,
+                    Some(instance.name.as_str()),
+                )),
+                perms,
+            ) {
+                Ok(adapter) => {
+                    new_messaging_manager.register(adapter).await;
+                }
+                Err(error) => {
+                    tracing::error!(%error, adapter = %instance.name, "failed to create named teams adapter");
+                }
+            }
+        }
+    }
+
     // Shared Signal permissions (hot-reloadable via file watcher)
     *signal_permissions = config.messaging.signal.as_ref().map(|signal_config| {
         let perms = spacebot::config::SignalPermissions::from_config(signal_config);
@@ -3582,6 +4111,7 @@ async fn initialize_agents(
         .await
         .context("failed to start messaging adapters")?;
     *inbound_stream = Some(new_inbound);
+    spacebot::messaging::MessagingManager::spawn_outbox_worker(messaging_manager);
 
     tracing::info!("messaging adapters started");
 
@@ -3592,6 +4122,9 @@ async fn initialize_agents(
     for (agent_id, agent) in agents.iter_mut() {
         let store = Arc::new(spacebot::cron::CronStore::new(agent.db.sqlite.clone()));
         agent.deps.messaging_manager = Some(messaging_manager.clone());
+        if let Some(spend_tracker) = agent.deps.llm_manager.spend_tracker(agent_id) {
+            spend_tracker.set_messaging_manager(messaging_manager.clone());
+        }
 
         // Seed cron jobs from config into the database
         for cron_def in &agent.config.cron {
@@ -3604,6 +4137,7 @@ async fn initialize_agents(
                 active_hours: cron_def.active_hours,
                 enabled: cron_def.enabled,
                 run_once: cron_def.run_once,
+                stateful: cron_def.stateful,
                 next_run_at: None,
                 timeout_secs: cron_def.timeout_secs,
             };
@@ -3707,12 +4241,26 @@ async fn initialize_agents(
         cortex_handles.push(association_handle);
         tracing::info!(agent_id = %agent_id, "cortex association loop started");
 
+        let participant_handle = spacebot::agent::cortex::spawn_participant_loop(
+            agent.deps.clone(),
+            spacebot::agent::cortex::CortexLogger::new(agent.db.sqlite.clone()),
+        );
+        cortex_handles.push(participant_handle);
+        tracing::info!(agent_id = %agent_id, "cortex participant loop started");
+
         let ready_task_handle = spacebot::agent::cortex::spawn_ready_task_loop(
             agent.deps.clone(),
             spacebot::agent::cortex::CortexLogger::new(agent.db.sqlite.clone()),
         );
         cortex_handles.push(ready_task_handle);
         tracing::info!(agent_id = %agent_id, "cortex ready-task loop started");
+
+        let autonomy_handle = spacebot::agent::cortex::spawn_autonomy_loop(
+            agent.deps.clone(),
+            spacebot::agent::cortex::CortexLogger::new(agent.db.sqlite.clone()),
+        );
+        cortex_handles.push(autonomy_handle);
+        tracing::info!(agent_id = %agent_id, "cortex autonomy loop started");
     }
 
     // Create cortex chat sessions for each agent
@@ -12,5 +12,5 @@ pub use embedding::EmbeddingModel;
 pub use lance::EmbeddingTable;
 pub use search::{MemorySearch, SearchConfig, SearchMode, SearchSort, curate_results};
 pub use store::MemoryStore;
-pub use types::{Association, Memory, MemoryType, RelationType};
+pub use types::{Association, Memory, MemoryTier, MemoryType, RelationType};
 pub use working::{WorkingMemoryEventType, WorkingMemoryStore};
@@ -32,7 +32,12 @@ impl EmbeddingTable {
     pub async fn open_or_create(connection: &lancedb::Connection) -> Result<Self> {
         // Try to open existing table
         match connection.open_table(TABLE_NAME).execute().await {
-            Ok(table) => return Ok(Self { table }),
+            Ok(table) => match Self::ensure_user_id_column(&table).await {
+                Ok(()) => return Ok(Self { table }),
+                Err(error) => {
+                    tracing::warn!(%error, "embeddings table schema upgrade failed, will rebuild");
+                }
+            },
             Err(error) => {
                 tracing::debug!(%error, "failed to open embeddings table, will create");
             }
@@ -61,6 +66,34 @@ impl EmbeddingTable {
         Ok(Self { table })
     }
 
+    /// Add the nullable `user_id` column to tables created before memories
+    /// were user-scoped. Existing rows become global (`NULL`).
+    async fn ensure_user_id_column(table: &lancedb::Table) -> Result<()> {
+        let schema = table
+            .schema()
+            .await
+            .map_err(|e| DbError::LanceDb(e.to_string()))?;
+        if schema.field_with_name("user_id").is_ok() {
+            return Ok(());
+        }
+
+        let user_id_schema = arrow_schema::Schema::new(vec![arrow_schema::Field::new(
+            "user_id",
+            arrow_schema::DataType::Utf8,
+            true,
+        )]);
+        table
+            .add_columns(
+                lancedb::table::NewColumnTransform::AllNulls(Arc::new(user_id_schema)),
+                None,
+            )
+            .await
+            .map_err(|e| DbError::LanceDb(format!("failed to add user_id column: {e}")))?;
+        tracing::info!("added user_id column to embeddings table");
+
+        Ok(())
+    }
+
     /// Create an empty embeddings table.
     async fn create_empty_table(connection: &lancedb::Connection) -> Result<lancedb::Table> {
         let schema = Self::schema();
@@ -74,8 +107,15 @@ impl EmbeddingTable {
     }
 
     /// Store an embedding with content for a memory.
-    /// The content is stored for FTS search capability.
-    pub async fn store(&self, memory_id: &str, content: &str, embedding: &[f32]) -> Result<()> {
+    /// The content is stored for FTS search capability, and the owning user
+    /// (if any) so searches can be scoped before RRF fusion.
+    pub async fn store(
+        &self,
+        memory_id: &str,
+        content: &str,
+        user_id: Option<&str>,
+        embedding: &[f32],
+    ) -> Result<()> {
         if embedding.len() != EMBEDDING_DIM as usize {
             return Err(DbError::LanceDb(format!(
                 "Embedding dimension mismatch: expected {}, got {}",
@@ -92,6 +132,7 @@ impl EmbeddingTable {
         // Build arrays for the record batch
         let id_array = StringArray::from(vec![memory_id]);
         let content_array = StringArray::from(vec![content]);
+        let user_id_array = StringArray::from(vec![user_id]);
 
         // Convert embedding to FixedSizeListArray
         let embedding_array =
@@ -106,6 +147,7 @@ impl EmbeddingTable {
                 Arc::new(id_array) as arrow_array::ArrayRef,
                 Arc::new(content_array) as arrow_array::ArrayRef,
                 Arc::new(embedding_array) as arrow_array::ArrayRef,
+                Arc::new(user_id_array) as arrow_array::ArrayRef,
             ],
         )
         .map_err(|e| DbError::LanceDb(e.to_string()))?;
@@ -136,10 +178,12 @@ impl EmbeddingTable {
 
     /// Vector similarity search using cosine distance.
     /// Returns (memory_id, distance) pairs sorted by distance (ascending).
+    /// When `user_id` is set, only that user's rows and global rows match.
     pub async fn vector_search(
         &self,
         query_embedding: &[f32],
         limit: usize,
+        user_id: Option<&str>,
     ) -> Result<Vec<(String, f32)>> {
         if query_embedding.len() != EMBEDDING_DIM as usize {
             return Err(DbError::LanceDb(format!(
@@ -153,12 +197,16 @@ impl EmbeddingTable {
         use lancedb::query::{ExecutableQuery, QueryBase};
 
         // Use query() API with nearest_to for vector search
-        let results: Vec<arrow_array::RecordBatch> = self
+        let mut query = self
             .table
             .query()
             .nearest_to(query_embedding)
             .map_err(|e| DbError::LanceDb(e.to_string()))?
-            .limit(limit)
+            .limit(limit);
+        if let Some(user_id) = user_id {
+            query = query.only_if(user_scope_predicate(user_id));
+        }
+        let results: Vec<arrow_array::RecordBatch> = query
             .execute()
             .await
             .map_err(|e| DbError::LanceDb(e.to_string()))?
@@ -236,7 +284,7 @@ impl EmbeddingTable {
 
         // Now search for similar embeddings, fetching extra to account for filtering
         let search_limit = limit + 1;
-        let results = self.vector_search(&embedding, search_limit).await?;
+        let results = self.vector_search(&embedding, search_limit, None).await?;
 
         let mut similar = Vec::new();
         for (id, distance) in results {
@@ -255,18 +303,28 @@ impl EmbeddingTable {
 
     /// Full-text search using Tantivy FTS.
     /// Returns (memory_id, score) pairs sorted by score (descending).
-    pub async fn text_search(&self, query: &str, limit: usize) -> Result<Vec<(String, f32)>> {
+    /// When `user_id` is set, only that user's rows and global rows match.
+    pub async fn text_search(
+        &self,
+        query: &str,
+        limit: usize,
+        user_id: Option<&str>,
+    ) -> Result<Vec<(String, f32)>> {
         use lancedb::query::{ExecutableQuery, QueryBase};
 
         // Use full_text_search on the content column
-        let results: Vec<arrow_array::RecordBatch> = self
+        let mut fts_query = self
             .table
             .query()
             .full_text_search(lance_index::scalar::FullTextSearchQuery::new(
                 query.to_string(),
             ))
             .select(lancedb::query::Select::columns(&["id", "_score"]))
-            .limit(limit)
+            .limit(limit);
+        if let Some(user_id) = user_id {
+            fts_query = fts_query.only_if(user_scope_predicate(user_id));
+        }
+        let results: Vec<arrow_array::RecordBatch> = fts_query
             .execute()
             .await
             .map_err(|e| DbError::LanceDb(e.to_string()))?
@@ -356,6 +414,7 @@ impl EmbeddingTable {
                 ),
                 false,
             ),
+            arrow_schema::Field::new("user_id", arrow_schema::DataType::Utf8, true),
         ])
     }
 
@@ -369,3 +428,98 @@ impl EmbeddingTable {
         Ok(())
     }
 }
+
+/// Filter predicate matching one user's rows plus global rows.
+fn user_scope_predicate(user_id: &str) -> String {
+    let escaped = user_id.replace('\'', "''");
+    format!("(user_id = '{escaped}' OR user_id IS NULL)")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn user_scope_predicate_escapes_quotes() {
+        assert_eq!(
+            user_scope_predicate("discord:o'brien"),
+            "(user_id = 'discord:o''brien' OR user_id IS NULL)"
+        );
+    }
+
+    #[tokio::test]
+    async fn vector_search_returns_own_and_global_rows() {
+        let dir = tempfile::tempdir().unwrap();
+        let connection = lancedb::connect(dir.path().to_str().unwrap())
+            .execute()
+            .await
+            .unwrap();
+        let table = EmbeddingTable::open_or_create(&connection).await.unwrap();
+
+        let global_id = uuid::Uuid::new_v4().to_string();
+        let alice_id = uuid::Uuid::new_v4().to_string();
+        let bob_id = uuid::Uuid::new_v4().to_string();
+        let embedding = vec![1.0; EMBEDDING_DIM as usize];
+        table
+            .store(&global_id, "shared", None, &embedding)
+            .await
+            .unwrap();
+        table
+            .store(&alice_id, "alice", Some("discord:alice"), &embedding)
+            .await
+            .unwrap();
+        table
+            .store(&bob_id, "bob", Some("discord:bob"), &embedding)
+            .await
+            .unwrap();
+
+        let mut scoped: Vec<String> = table
+            .vector_search(&embedding, 10, Some("discord:alice"))
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        scoped.sort();
+        let mut expected = vec![global_id, alice_id];
+        expected.sort();
+        assert_eq!(scoped, expected);
+
+        let unscoped = table.vector_search(&embedding, 10, None).await.unwrap();
+        assert_eq!(unscoped.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn open_or_create_adds_user_id_to_legacy_table() {
+        let dir = tempfile::tempdir().unwrap();
+        let connection = lancedb::connect(dir.path().to_str().unwrap())
+            .execute()
+            .await
+            .unwrap();
+
+        let mut legacy_fields = EmbeddingTable::schema().fields().to_vec();
+        legacy_fields.retain(|field| field.name() != "user_id");
+        let legacy_schema = Arc::new(arrow_schema::Schema::new(legacy_fields));
+        let batches = RecordBatchIterator::new(vec![].into_iter().map(Ok), legacy_schema);
+        connection
+            .create_table(TABLE_NAME, Box::new(batches))
+            .execute()
+            .await
+            .unwrap();
+
+        let table = EmbeddingTable::open_or_create(&connection).await.unwrap();
+        let schema = table.table.schema().await.unwrap();
+        assert!(schema.field_with_name("user_id").is_ok());
+
+        let memory_id = uuid::Uuid::new_v4().to_string();
+        table
+            .store(
+                &memory_id,
+                "after upgrade",
+                Some("discord:alice"),
+                &vec![0.5; EMBEDDING_DIM as usize],
+            )
+            .await
+            .unwrap();
+    }
+}
@@ -1,7 +1,7 @@
-//! Memory maintenance: decay, prune, merge, reindex.
+//! Memory maintenance: tier demotion, decay, prune, merge, reindex.
 
 use crate::error::Result;
-use crate::memory::{EmbeddingModel, EmbeddingTable, Memory, MemoryStore, MemoryType};
+use crate::memory::{EmbeddingModel, EmbeddingTable, Memory, MemoryStore, MemoryTier, MemoryType};
 use anyhow::Context;
 
 use sqlx::Row;
@@ -28,6 +28,10 @@ pub struct MaintenanceConfig {
     pub min_age_days: i64,
     /// Similarity threshold for merging memories (0.0 - 1.0).
     pub merge_similarity_threshold: f32,
+    /// Days without access before a recent-tier memory is demoted.
+    pub recent_tier_ttl_days: i64,
+    /// Maximum recent-tier memories; least-recently-accessed excess is demoted.
+    pub recent_tier_max: usize,
 }
 
 impl Default for MaintenanceConfig {
@@ -37,6 +41,8 @@ impl Default for MaintenanceConfig {
             decay_rate: 0.05,
             min_age_days: 30,
             merge_similarity_threshold: 0.95,
+            recent_tier_ttl_days: 3,
+            recent_tier_max: 64,
         }
     }
 }
@@ -73,10 +79,13 @@ pub async fn run_maintenance_with_cancel(
     check_maintenance_cancellation(&mut maintenance_cancel_rx).await?;
     validate_maintenance_config(config)?;
 
-    // Apply decay to all non-identity memories
+    // Demote aged recent-tier memories first so they decay with the long-term
+    // tier, then apply decay to all non-identity long-term memories.
     // Fields are assigned sequentially because the values are async — can't use struct literal.
     #[allow(clippy::field_reassign_with_default)]
     {
+        report.demoted =
+            demote_recent_memories(memory_store, config, &mut maintenance_cancel_rx).await?;
         report.decayed =
             apply_decay(memory_store, config.decay_rate, &mut maintenance_cancel_rx).await?;
         report.pruned = prune_memories(memory_store, config, &mut maintenance_cancel_rx).await?;
@@ -93,7 +102,32 @@ pub async fn run_maintenance_with_cancel(
     Ok(report)
 }
 
+/// Demote recent-tier memories that went unaccessed for the TTL, then trim
+/// the tier to its capacity.
+async fn demote_recent_memories(
+    memory_store: &MemoryStore,
+    config: &MaintenanceConfig,
+    maintenance_cancel_rx: &mut watch::Receiver<bool>,
+) -> Result<usize> {
+    check_maintenance_cancellation(maintenance_cancel_rx).await?;
+
+    let cutoff = chrono::Utc::now() - chrono::Duration::days(config.recent_tier_ttl_days);
+    let expired = maintenance_cancelable_op(
+        maintenance_cancel_rx,
+        memory_store.demote_recent_before(cutoff),
+    )
+    .await?;
+    let evicted = maintenance_cancelable_op(
+        maintenance_cancel_rx,
+        memory_store.enforce_recent_capacity(config.recent_tier_max),
+    )
+    .await?;
+
+    Ok((expired + evicted) as usize)
+}
+
 /// Apply importance decay based on recency and access patterns.
+/// Recent-tier memories are exempt while they stay hot.
 async fn apply_decay(
     memory_store: &MemoryStore,
     decay_rate: f32,
@@ -119,6 +153,9 @@ async fn apply_decay(
 
         for mut memory in memories {
             check_maintenance_cancellation(maintenance_cancel_rx).await?;
+            if memory.tier == MemoryTier::Recent {
+                continue;
+            }
 
             let now = chrono::Utc::now();
             let days_old = (now - memory.updated_at).num_days();
@@ -161,7 +198,9 @@ async fn prune_memories(
     let min_age = chrono::Duration::days(config.min_age_days);
     let cutoff_date = now - min_age;
 
-    // Get all memories below threshold that are old enough
+    // Get all long-term memories below threshold that are old enough. The
+    // retention clock starts at demotion for memories that passed through
+    // the recent tier.
     let candidates: Vec<SqliteRow> = maintenance_cancelable_op(
         maintenance_cancel_rx,
         sqlx::query(
@@ -169,7 +208,8 @@ async fn prune_memories(
         SELECT id FROM memories
         WHERE importance < ? 
         AND memory_type != 'identity'
-        AND created_at < ?
+        AND tier = 'long_term'
+        AND COALESCE(demoted_at, created_at) < ?
         "#,
         )
         .bind(config.prune_threshold)
@@ -221,7 +261,7 @@ async fn merge_similar_memories(
         else {
             continue;
         };
-        if source_memory.forgotten {
+        if source_memory.forgotten || source_memory.tier == MemoryTier::Recent {
             continue;
         }
         let source_id = source_memory.id.clone();
@@ -267,7 +307,14 @@ async fn merge_similar_memories(
             else {
                 continue;
             };
-            if candidate_memory.forgotten {
+            // Never merge across user scopes: the survivor's scope would leak
+            // the other memory's content to a different user (or make a
+            // global memory private).
+            // Recent-tier memories may still be evolving, so they're left alone.
+            if candidate_memory.forgotten
+                || candidate_memory.tier == MemoryTier::Recent
+                || candidate_memory.user_id != active_survivor.user_id
+            {
                 continue;
             }
 
@@ -372,6 +419,7 @@ async fn merge_pair(
         embedding_table.store(
             &updated_survivor.id,
             &updated_survivor.content,
+            updated_survivor.user_id.as_deref(),
             &updated_survivor_embedding,
         ),
     )
@@ -389,7 +437,7 @@ async fn fetch_candidate_memory_ids(
     let rows: Vec<SqliteRow> = maintenance_cancelable_op(
         maintenance_cancel_rx,
         sqlx::query(
-            "SELECT id FROM memories WHERE forgotten = 0 ORDER BY importance DESC, created_at DESC, id ASC LIMIT ?",
+            "SELECT id FROM memories WHERE forgotten = 0 AND tier = 'long_term' ORDER BY importance DESC, created_at DESC, id ASC LIMIT ?",
         )
         .bind(MAX_MAINTENANCE_MERGE_SOURCE_MEMORIES)
         .fetch_all(memory_store.pool()),
@@ -422,6 +470,13 @@ fn validate_maintenance_config(config: &MaintenanceConfig) -> Result<()> {
         )
         .into());
     }
+    if config.recent_tier_ttl_days < 0 {
+        return Err(anyhow::anyhow!(
+            "maintenance recent_tier_ttl_days must be >= 0, got {}",
+            config.recent_tier_ttl_days
+        )
+        .into());
+    }
     Ok(())
 }
 
@@ -479,6 +534,7 @@ where
 /// Maintenance report.
 #[derive(Debug, Default)]
 pub struct MaintenanceReport {
+    pub demoted: usize,
     pub decayed: usize,
     pub pruned: usize,
     pub merged: usize,
@@ -516,7 +572,7 @@ mod tests {
         store.save(&memory).await.expect("failed to save memory");
 
         embedding_table
-            .store(&memory.id, &memory.content, &embedding)
+            .store(&memory.id, &memory.content, None, &embedding)
             .await
             .expect("failed to store embedding");
 
@@ -589,6 +645,8 @@ mod tests {
             decay_rate: 0.05,
             min_age_days: 30,
             merge_similarity_threshold: 0.95,
+            recent_tier_ttl_days: 3,
+            recent_tier_max: 64,
         };
 
         let embedding_model = shared_embedding_model();
@@ -759,6 +817,8 @@ mod tests {
                 decay_rate: 0.05,
                 min_age_days: 30,
                 merge_similarity_threshold: 0.95,
+                recent_tier_ttl_days: 3,
+                recent_tier_max: 64,
             },
         )
         .await
@@ -872,6 +932,8 @@ mod tests {
             decay_rate: 0.05,
             min_age_days: -1,
             merge_similarity_threshold: 0.95,
+            recent_tier_ttl_days: 3,
+            recent_tier_max: 64,
         };
 
         let embedding_model = shared_embedding_model();
@@ -1,7 +1,7 @@
 //! Memory search: hybrid (vector + FTS + RRF + graph), temporal, importance, and typed queries.
 
 use crate::error::Result;
-use crate::memory::types::{Memory, MemorySearchResult, MemoryType, RelationType};
+use crate::memory::types::{Memory, MemorySearchResult, MemoryTier, MemoryType, RelationType};
 use crate::memory::{EmbeddingModel, EmbeddingTable, MemoryStore};
 
 use std::collections::HashMap;
@@ -132,7 +132,12 @@ impl MemorySearch {
     ) -> Result<Vec<MemorySearchResult>> {
         let memories = self
             .store
-            .get_sorted(sort, config.max_results as i64, config.memory_type)
+            .get_sorted(
+                sort,
+                config.max_results as i64,
+                config.memory_type,
+                config.user_id.as_deref(),
+            )
             .await?;
 
         let total = memories.len();
@@ -159,6 +164,9 @@ impl MemorySearch {
     }
 
     /// Perform hybrid search across all memory sources.
+    ///
+    /// When `config.user_id` is set, every source (FTS, vector, graph) is
+    /// restricted to that user's memories plus global ones before fusion.
     pub async fn hybrid_search(
         &self,
         query: &str,
@@ -168,19 +176,21 @@ impl MemorySearch {
         let mut vector_results = Vec::new();
         let mut fts_results = Vec::new();
         let mut graph_results = Vec::new();
+        let user_id = config.user_id.as_deref();
 
         // 1. Full-text search via LanceDB
         // FTS requires an inverted index. If the index doesn't exist yet (empty
         // table, first run) this will fail — fall back to vector + graph search.
         match self
             .embedding_table
-            .text_search(query, config.max_results_per_source)
+            .text_search(query, config.max_results_per_source, user_id)
             .await
         {
             Ok(fts_matches) => {
                 for (memory_id, score) in fts_matches {
                     if let Some(memory) = self.store.load(&memory_id).await?
                         && !memory.forgotten
+                        && memory.visible_to(user_id)
                     {
                         fts_results.push(ScoredMemory {
                             memory,
@@ -198,7 +208,7 @@ impl MemorySearch {
         let query_embedding = self.embedding_model.embed_one(query).await?;
         match self
             .embedding_table
-            .vector_search(&query_embedding, config.max_results_per_source)
+            .vector_search(&query_embedding, config.max_results_per_source, user_id)
             .await
         {
             Ok(vector_matches) => {
@@ -206,6 +216,7 @@ impl MemorySearch {
                     let similarity = 1.0 - distance;
                     if let Some(memory) = self.store.load(&memory_id).await?
                         && !memory.forgotten
+                        && memory.visible_to(user_id)
                     {
                         vector_results.push(ScoredMemory {
                             memory,
@@ -223,7 +234,10 @@ impl MemorySearch {
         // Get identity and high-importance memories as starting points
         let seed_memories = self.store.get_high_importance(0.8, 20).await?;
 
-        for seed in seed_memories {
+        for seed in seed_memories
+            .into_iter()
+            .filter(|seed| seed.visible_to(user_id))
+        {
             // Check if seed is semantically related to query via simple keyword matching
             if query
                 .to_lowercase()
@@ -236,14 +250,24 @@ impl MemorySearch {
                 });
 
                 // Traverse graph to find related memories
-                self.traverse_graph(&seed.id, config.max_graph_depth, &mut graph_results)
-                    .await?;
+                self.traverse_graph(
+                    &seed.id,
+                    config.max_graph_depth,
+                    user_id,
+                    &mut graph_results,
+                )
+                .await?;
             }
         }
 
         // 4. Merge results using Reciprocal Rank Fusion (RRF)
-        let fused_results =
-            reciprocal_rank_fusion(&vector_results, &fts_results, &graph_results, config.rrf_k);
+        let fused_results = reciprocal_rank_fusion(
+            &vector_results,
+            &fts_results,
+            &graph_results,
+            config.rrf_k,
+            config.recent_tier_boost,
+        );
 
         // Convert to MemorySearchResult with ranks, applying optional type filter
         let results: Vec<MemorySearchResult> = fused_results
@@ -267,10 +291,12 @@ impl MemorySearch {
     }
 
     /// Traverse the memory graph to find related memories (iterative to avoid async recursion).
+    /// Never walks into memories owned by a user other than `user_id`.
     async fn traverse_graph(
         &self,
         start_id: &str,
         max_depth: usize,
+        user_id: Option<&str>,
         results: &mut Vec<ScoredMemory>,
     ) -> Result<()> {
         use std::collections::VecDeque;
@@ -303,7 +329,7 @@ impl MemorySearch {
                 visited.insert(related_id.clone());
 
                 if let Some(memory) = self.store.load(related_id).await? {
-                    if memory.forgotten {
+                    if memory.forgotten || !memory.visible_to(user_id) {
                         continue;
                     }
                     // Score based on relation type and weight
@@ -356,6 +382,12 @@ pub struct SearchConfig {
     pub min_score: f32,
     /// Maximum graph traversal depth. Only used in hybrid mode.
     pub max_graph_depth: usize,
+    /// Multiplier applied to the fused score of recent-tier memories.
+    /// Only used in hybrid mode.
+    pub recent_tier_boost: f64,
+    /// Restrict results to this user's memories plus global memories.
+    /// `None` searches everything.
+    pub user_id: Option<String>,
 }
 
 impl Default for SearchConfig {
@@ -371,6 +403,8 @@ impl Default for SearchConfig {
             // score is ~0.016. Set threshold low enough to not discard everything.
             min_score: 0.0,
             max_graph_depth: 2,
+            recent_tier_boost: 1.5,
+            user_id: None,
         }
     }
 }
@@ -384,11 +418,13 @@ struct ScoredMemory {
 
 /// Reciprocal Rank Fusion to combine results from multiple sources.
 /// RRF score = sum(1 / (k + rank)) for each list where the item appears.
+/// Recent-tier memories have their fused score multiplied by `recent_boost`.
 fn reciprocal_rank_fusion(
     vector_results: &[ScoredMemory],
     fts_results: &[ScoredMemory],
     graph_results: &[ScoredMemory],
     k: f64,
+    recent_boost: f64,
 ) -> Vec<ScoredMemory> {
     // Build a map of memory ID to RRF score
     let mut rrf_scores: HashMap<String, (f64, Memory)> = HashMap::new();
@@ -423,7 +459,14 @@ fn reciprocal_rank_fusion(
     // Convert to vec and sort by RRF score
     let mut fused: Vec<ScoredMemory> = rrf_scores
         .into_iter()
-        .map(|(_, (score, memory))| ScoredMemory { memory, score })
+        .map(|(_, (score, memory))| {
+            let score = if memory.tier == MemoryTier::Recent {
+                score * recent_boost
+            } else {
+                score
+            };
+            ScoredMemory { memory, score }
+        })
         .collect();
 
     fused.sort_by(|a, b| b.score.total_cmp(&a.score));
@@ -454,7 +497,7 @@ mod tests {
     #[test]
     fn test_rrf_single_list() {
         let vector = vec![make_scored("a", 0.9), make_scored("b", 0.7)];
-        let fused = reciprocal_rank_fusion(&vector, &[], &[], 60.0);
+        let fused = reciprocal_rank_fusion(&vector, &[], &[], 60.0, 1.0);
 
         assert_eq!(fused.len(), 2);
         assert_eq!(fused[0].memory.id, "a");
@@ -469,7 +512,7 @@ mod tests {
         let vector = vec![make_scored("a", 0.9)];
         let fts = vec![make_scored("a", 5.0)];
 
-        let fused = reciprocal_rank_fusion(&vector, &fts, &[], 60.0);
+        let fused = reciprocal_rank_fusion(&vector, &fts, &[], 60.0, 1.0);
         assert_eq!(fused.len(), 1);
         // Should be 2 * 1/(60+1)
         let expected = 2.0 / 61.0;
@@ -483,14 +526,27 @@ mod tests {
         let fts = vec![make_scored("a", 5.0)];
         let graph = vec![make_scored("a", 0.8)];
 
-        let fused = reciprocal_rank_fusion(&vector, &fts, &graph, 60.0);
+        let fused = reciprocal_rank_fusion(&vector, &fts, &graph, 60.0, 1.0);
         assert_eq!(fused[0].memory.id, "a");
         assert!(fused[0].score > fused[1].score);
     }
 
+    #[test]
+    fn test_rrf_recent_tier_boost() {
+        // "b" ranks second but sits in the recent tier, so the boost lifts it
+        let mut recent = make_scored("b", 0.5);
+        recent.memory.tier = MemoryTier::Recent;
+        let vector = vec![make_scored("a", 0.9), recent];
+
+        let fused = reciprocal_rank_fusion(&vector, &[], &[], 60.0, 1.5);
+        assert_eq!(fused[0].memory.id, "b");
+        assert!((fused[0].score - 1.5 / 62.0).abs() < 1e-10);
+        assert!((fused[1].score - 1.0 / 61.0).abs() < 1e-10);
+    }
+
     #[test]
     fn test_rrf_empty_lists() {
-        let fused = reciprocal_rank_fusion(&[], &[], &[], 60.0);
+        let fused = reciprocal_rank_fusion(&[], &[], &[], 60.0, 1.0);
         assert!(fused.is_empty());
     }
 
@@ -2,7 +2,7 @@
 
 use crate::error::Result;
 use crate::memory::search::SearchSort;
-use crate::memory::types::{Association, Memory, MemoryType, RelationType};
+use crate::memory::types::{Association, Memory, MemoryTier, MemoryType, RelationType};
 
 use anyhow::Context as _;
 use sqlx::{Row, SqlitePool};
@@ -69,8 +69,9 @@ impl MemoryStore {
         sqlx::query(
             r#"
             INSERT INTO memories (id, content, memory_type, importance, created_at, updated_at,
-                                 last_accessed_at, access_count, source, channel_id, forgotten)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                                 last_accessed_at, access_count, source, channel_id, user_id,
+                                 tier, demoted_at, forgotten)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(&memory.id)
@@ -83,6 +84,9 @@ impl MemoryStore {
         .bind(memory.access_count)
         .bind(&memory.source)
         .bind(memory.channel_id.as_deref())
+        .bind(memory.user_id.as_deref())
+        .bind(memory.tier.to_string())
+        .bind(memory.demoted_at)
         .bind(memory.forgotten)
         .execute(&self.pool)
         .await
@@ -114,7 +118,8 @@ impl MemoryStore {
         let row = sqlx::query(
             r#"
             SELECT id, content, memory_type, importance, created_at, updated_at,
-                   last_accessed_at, access_count, source, channel_id, forgotten
+                   last_accessed_at, access_count, source, channel_id, user_id, tier,
+                   demoted_at, forgotten
             FROM memories
             WHERE id = ?
             "#,
@@ -148,7 +153,7 @@ impl MemoryStore {
             UPDATE memories
             SET content = ?, memory_type = ?, importance = ?, updated_at = ?,
                 last_accessed_at = ?, access_count = ?, source = ?, channel_id = ?,
-                forgotten = ?
+                user_id = ?, tier = ?, demoted_at = ?, forgotten = ?
             WHERE id = ?
             "#,
         )
@@ -160,6 +165,9 @@ impl MemoryStore {
         .bind(memory.access_count)
         .bind(&memory.source)
         .bind(memory.channel_id.as_deref())
+        .bind(memory.user_id.as_deref())
+        .bind(memory.tier.to_string())
+        .bind(memory.demoted_at)
         .bind(memory.forgotten)
         .bind(&memory.id)
         .execute(&self.pool)
@@ -259,6 +267,67 @@ impl MemoryStore {
         Ok(result.rows_affected() > 0)
     }
 
+    /// Move a memory into the recent tier and reset its access clock.
+    pub async fn promote_to_recent(&self, id: &str) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE memories
+            SET tier = 'recent', demoted_at = NULL, last_accessed_at = ?
+            WHERE id = ? AND forgotten = 0 AND memory_type != 'identity'
+            "#,
+        )
+        .bind(chrono::Utc::now())
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .with_context(|| format!("failed to promote memory {}", id))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Demote recent-tier memories not accessed since `cutoff` to the
+    /// long-term tier. Returns the number of memories demoted.
+    pub async fn demote_recent_before(&self, cutoff: chrono::DateTime<chrono::Utc>) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE memories
+            SET tier = 'long_term', demoted_at = ?
+            WHERE tier = 'recent' AND last_accessed_at < ?
+            "#,
+        )
+        .bind(chrono::Utc::now())
+        .bind(cutoff)
+        .execute(&self.pool)
+        .await
+        .context("failed to demote expired recent-tier memories")?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Demote the least-recently-accessed recent-tier memories until at most
+    /// `max` remain. Returns the number of memories demoted.
+    pub async fn enforce_recent_capacity(&self, max: usize) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE memories
+            SET tier = 'long_term', demoted_at = ?
+            WHERE id IN (
+                SELECT id FROM memories
+                WHERE tier = 'recent' AND forgotten = 0
+                ORDER BY last_accessed_at DESC
+                LIMIT -1 OFFSET ?
+            )
+            "#,
+        )
+        .bind(chrono::Utc::now())
+        .bind(max as i64)
+        .execute(&self.pool)
+        .await
+        .context("failed to enforce recent-tier capacity")?;
+
+        Ok(result.rows_affected())
+    }
+
     /// Merge one memory into a survivor with atomic SQLite updates.
     ///
     /// This updates survivor content/metadata, rewires associations, records an
@@ -279,7 +348,7 @@ impl MemoryStore {
             UPDATE memories
             SET content = ?, memory_type = ?, importance = ?, updated_at = ?,
                 last_accessed_at = ?, access_count = ?, source = ?, channel_id = ?,
-                forgotten = ?
+                user_id = ?, tier = ?, demoted_at = ?, forgotten = ?
             WHERE id = ?
             "#,
         )
@@ -291,6 +360,9 @@ impl MemoryStore {
         .bind(updated_survivor.access_count)
         .bind(&updated_survivor.source)
         .bind(updated_survivor.channel_id.as_deref())
+        .bind(updated_survivor.user_id.as_deref())
+        .bind(updated_survivor.tier.to_string())
+        .bind(updated_survivor.demoted_at)
         .bind(updated_survivor.forgotten)
         .bind(&updated_survivor.id)
         .execute(&mut *transaction)
@@ -554,7 +626,8 @@ impl MemoryStore {
         let rows = sqlx::query(
             r#"
             SELECT id, content, memory_type, importance, created_at, updated_at,
-                   last_accessed_at, access_count, source, channel_id, forgotten
+                   last_accessed_at, access_count, source, channel_id, user_id, tier,
+                   demoted_at, forgotten
             FROM memories
             WHERE memory_type = ? AND forgotten = 0
             ORDER BY importance DESC, updated_at DESC
@@ -570,12 +643,35 @@ impl MemoryStore {
         Ok(rows.into_iter().map(|row| row_to_memory(&row)).collect())
     }
 
+    /// Get the most recent memories saved with the given source tag.
+    pub async fn get_by_source(&self, source: &str, limit: i64) -> Result<Vec<Memory>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, content, memory_type, importance, created_at, updated_at,
+                   last_accessed_at, access_count, source, channel_id, user_id, tier,
+                   demoted_at, forgotten
+            FROM memories
+            WHERE source = ? AND forgotten = 0
+            ORDER BY created_at DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(source)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .with_context(|| format!("failed to get memories by source {source}"))?;
+
+        Ok(rows.into_iter().map(|row| row_to_memory(&row)).collect())
+    }
+
     /// Get high-importance memories for injection into context.
     pub async fn get_high_importance(&self, threshold: f32, limit: i64) -> Result<Vec<Memory>> {
         let rows = sqlx::query(
             r#"
             SELECT id, content, memory_type, importance, created_at, updated_at,
-                   last_accessed_at, access_count, source, channel_id, forgotten
+                   last_accessed_at, access_count, source, channel_id, user_id, tier,
+                   demoted_at, forgotten
             FROM memories
             WHERE importance >= ? AND forgotten = 0
             ORDER BY importance DESC, updated_at DESC
@@ -591,15 +687,19 @@ impl MemoryStore {
         Ok(rows.into_iter().map(|row| row_to_memory(&row)).collect())
     }
 
-    /// Get memories sorted by a flexible criterion with optional type filter.
+    /// Get memories sorted by a flexible criterion with optional type and
+    /// user filters.
     ///
     /// Used by non-hybrid search modes (Recent, Important, Typed) to retrieve
-    /// memories directly from SQLite without vector/FTS overhead.
+    /// memories directly from SQLite without vector/FTS overhead. When
+    /// `user_id` is set, only that user's memories and global memories
+    /// (`user_id IS NULL`) are returned.
     pub async fn get_sorted(
         &self,
         sort: SearchSort,
         limit: i64,
         memory_type: Option<MemoryType>,
+        user_id: Option<&str>,
     ) -> Result<Vec<Memory>> {
         let order_clause = match sort {
             SearchSort::Recent => "ORDER BY created_at DESC",
@@ -607,39 +707,35 @@ impl MemoryStore {
             SearchSort::MostAccessed => "ORDER BY access_count DESC, created_at DESC",
         };
 
-        let (query_str, type_filter) = if let Some(ref memory_type) = memory_type {
-            (
-                format!(
-                    "SELECT id, content, memory_type, importance, created_at, updated_at, \
-                     last_accessed_at, access_count, source, channel_id, forgotten \
-                     FROM memories WHERE memory_type = ? AND forgotten = 0 {order_clause} LIMIT ?"
-                ),
-                Some(memory_type.to_string()),
-            )
-        } else {
-            (
-                format!(
-                    "SELECT id, content, memory_type, importance, created_at, updated_at, \
-                     last_accessed_at, access_count, source, channel_id, forgotten \
-                     FROM memories WHERE forgotten = 0 {order_clause} LIMIT ?"
-                ),
-                None,
-            )
-        };
+        let mut conditions = vec!["forgotten = 0"];
+        if memory_type.is_some() {
+            conditions.push("memory_type = ?");
+        }
+        if user_id.is_some() {
+            conditions.push("(user_id = ? OR user_id IS NULL)");
+        }
+        let where_clause = conditions.join(" AND ");
+
+        let query_str = format!(
+            "SELECT id, content, memory_type, importance, created_at, updated_at, \
+             last_accessed_at, access_count, source, channel_id, user_id, tier, \
+             demoted_at, forgotten \
+             FROM memories WHERE {where_clause} {order_clause} LIMIT ?"
+        );
 
-        let rows = if let Some(type_str) = type_filter {
-            sqlx::query(&query_str)
-                .bind(type_str)
-                .bind(limit)
-                .fetch_all(&self.pool)
-                .await
-        } else {
-            sqlx::query(&query_str)
-                .bind(limit)
-                .fetch_all(&self.pool)
-                .await
+        let mut query = sqlx::query(&query_str);
+        if let Some(memory_type) = memory_type {
+            query = query.bind(memory_type.to_string());
+        }
+        if let Some(user_id) = user_id {
+            query = query.bind(user_id);
         }
-        .with_context(|| format!("failed to get sorted memories ({sort:?})"))?;
+
+        let rows = query
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await
+            .with_context(|| format!("failed to get sorted memories ({sort:?})"))?;
 
         Ok(rows.into_iter().map(|row| row_to_memory(&row)).collect())
     }
@@ -677,6 +773,8 @@ fn row_to_memory(row: &sqlx::sqlite::SqliteRow) -> Memory {
     let memory_type = parse_memory_type(&mem_type_str);
 
     let channel_id: Option<String> = row.try_get("channel_id").ok();
+    let user_id: Option<String> = row.try_get("user_id").ok().flatten();
+    let tier: String = row.try_get("tier").unwrap_or_default();
 
     Memory {
         id: row.try_get("id").unwrap_or_default(),
@@ -695,6 +793,9 @@ fn row_to_memory(row: &sqlx::sqlite::SqliteRow) -> Memory {
         access_count: row.try_get("access_count").unwrap_or(0),
         source: row.try_get("source").ok(),
         channel_id,
+        user_id,
+        tier: MemoryTier::parse(&tier),
+        demoted_at: row.try_get("demoted_at").ok().flatten(),
         forgotten: row.try_get::<bool, _>("forgotten").unwrap_or(false),
     }
 }
@@ -800,7 +901,7 @@ mod tests {
         let new = insert_memory_at(&store, "new", MemoryType::Fact, 0.5, now).await;
 
         let results = store
-            .get_sorted(SearchSort::Recent, 10, None)
+            .get_sorted(SearchSort::Recent, 10, None, None)
             .await
             .unwrap();
         assert_eq!(results.len(), 3);
@@ -809,6 +910,25 @@ mod tests {
         assert_eq!(results[2].id, old.id);
     }
 
+    #[tokio::test]
+    async fn test_get_by_source_filters_and_orders() {
+        let store = MemoryStore::connect_in_memory().await;
+        let now = Utc::now();
+
+        let mut older =
+            Memory::new("older finding", MemoryType::Observation).with_source("cortex:autonomy");
+        older.created_at = now - Duration::hours(2);
+        store.save(&older).await.unwrap();
+        let newer = Memory::new("newer finding", MemoryType::Event).with_source("cortex:autonomy");
+        store.save(&newer).await.unwrap();
+        insert_memory_at(&store, "untagged", MemoryType::Fact, 0.5, now).await;
+
+        let results = store.get_by_source("cortex:autonomy", 10).await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].id, newer.id);
+        assert_eq!(results[1].id, older.id);
+    }
+
     #[tokio::test]
     async fn test_get_sorted_importance() {
         let store = MemoryStore::connect_in_memory().await;
@@ -819,7 +939,7 @@ mod tests {
         let medium = insert_memory_at(&store, "medium", MemoryType::Fact, 0.5, now).await;
 
         let results = store
-            .get_sorted(SearchSort::Importance, 10, None)
+            .get_sorted(SearchSort::Importance, 10, None, None)
             .await
             .unwrap();
         assert_eq!(results[0].id, high.id);
@@ -842,7 +962,7 @@ mod tests {
         }
 
         let results = store
-            .get_sorted(SearchSort::MostAccessed, 10, None)
+            .get_sorted(SearchSort::MostAccessed, 10, None, None)
             .await
             .unwrap();
         assert_eq!(results[0].id, b.id);
@@ -860,7 +980,7 @@ mod tests {
         insert_memory_at(&store, "an event", MemoryType::Event, 0.5, now).await;
 
         let results = store
-            .get_sorted(SearchSort::Recent, 10, Some(MemoryType::Decision))
+            .get_sorted(SearchSort::Recent, 10, Some(MemoryType::Decision), None)
             .await
             .unwrap();
         assert_eq!(results.len(), 1);
@@ -883,7 +1003,10 @@ mod tests {
             .await;
         }
 
-        let results = store.get_sorted(SearchSort::Recent, 3, None).await.unwrap();
+        let results = store
+            .get_sorted(SearchSort::Recent, 3, None, None)
+            .await
+            .unwrap();
         assert_eq!(results.len(), 3);
     }
 
@@ -897,10 +1020,119 @@ mod tests {
         store.forget(&forgotten.id).await.unwrap();
 
         let results = store
-            .get_sorted(SearchSort::Recent, 10, None)
+            .get_sorted(SearchSort::Recent, 10, None, None)
             .await
             .unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].id, visible.id);
     }
+
+    #[tokio::test]
+    async fn test_get_sorted_scopes_to_user_and_globals() {
+        let store = MemoryStore::connect_in_memory().await;
+
+        let global = Memory::new("shared decision", MemoryType::Decision);
+        let alice = Memory::new("alice's project", MemoryType::Fact).with_user_id("discord:alice");
+        let bob = Memory::new("bob's project", MemoryType::Fact).with_user_id("discord:bob");
+        for memory in [&global, &alice, &bob] {
+            store.save(memory).await.unwrap();
+        }
+
+        let loaded = store.load(&alice.id).await.unwrap().unwrap();
+        assert_eq!(loaded.user_id.as_deref(), Some("discord:alice"));
+
+        let scoped = store
+            .get_sorted(SearchSort::Recent, 10, None, Some("discord:alice"))
+            .await
+            .unwrap();
+        let mut ids: Vec<_> = scoped.iter().map(|memory| memory.id.clone()).collect();
+        ids.sort();
+        let mut expected = vec![global.id.clone(), alice.id.clone()];
+        expected.sort();
+        assert_eq!(ids, expected);
+
+        let typed = store
+            .get_sorted(
+                SearchSort::Recent,
+                10,
+                Some(MemoryType::Fact),
+                Some("discord:bob"),
+            )
+            .await
+            .unwrap();
+        assert_eq!(typed.len(), 1);
+        assert_eq!(typed[0].id, bob.id);
+
+        let unscoped = store
+            .get_sorted(SearchSort::Recent, 10, None, None)
+            .await
+            .unwrap();
+        assert_eq!(unscoped.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_recent_tier_promotion_and_demotion() {
+        let store = MemoryStore::connect_in_memory().await;
+
+        let stale = Memory::new("stale working note", MemoryType::Observation)
+            .with_tier(MemoryTier::Recent);
+        let fresh = Memory::new("fresh working note", MemoryType::Observation)
+            .with_tier(MemoryTier::Recent);
+        let identity = Memory::new("I am spacebot", MemoryType::Identity);
+        for memory in [&stale, &fresh, &identity] {
+            store.save(memory).await.unwrap();
+        }
+
+        // Identity memories can never be promoted.
+        assert!(!store.promote_to_recent(&identity.id).await.unwrap());
+
+        sqlx::query("UPDATE memories SET last_accessed_at = ? WHERE id = ?")
+            .bind(Utc::now() - Duration::days(10))
+            .bind(&stale.id)
+            .execute(store.pool())
+            .await
+            .unwrap();
+
+        let cutoff = Utc::now() - Duration::days(3);
+        assert_eq!(store.demote_recent_before(cutoff).await.unwrap(), 1);
+
+        let demoted = store.load(&stale.id).await.unwrap().unwrap();
+        assert_eq!(demoted.tier, MemoryTier::LongTerm);
+        assert!(demoted.demoted_at.is_some());
+        assert_eq!(
+            store.load(&fresh.id).await.unwrap().unwrap().tier,
+            MemoryTier::Recent
+        );
+
+        assert!(store.promote_to_recent(&stale.id).await.unwrap());
+        let promoted = store.load(&stale.id).await.unwrap().unwrap();
+        assert_eq!(promoted.tier, MemoryTier::Recent);
+        assert!(promoted.demoted_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_enforce_recent_capacity_demotes_least_recently_accessed() {
+        let store = MemoryStore::connect_in_memory().await;
+
+        let mut ids = Vec::new();
+        for age_hours in [3, 2, 1] {
+            let mut memory = Memory::new(format!("note {age_hours}"), MemoryType::Fact)
+                .with_tier(MemoryTier::Recent);
+            memory.last_accessed_at = Utc::now() - Duration::hours(age_hours);
+            store.save(&memory).await.unwrap();
+            ids.push(memory.id);
+        }
+
+        assert_eq!(store.enforce_recent_capacity(2).await.unwrap(), 1);
+        assert_eq!(store.enforce_recent_capacity(2).await.unwrap(), 0);
+
+        let oldest = store.load(&ids[0]).await.unwrap().unwrap();
+        assert_eq!(oldest.tier, MemoryTier::LongTerm);
+        for id in &ids[1..] {
+            assert_eq!(
+                store.load(id).await.unwrap().unwrap().tier,
+                MemoryTier::Recent
+            );
+        }
+    }
 }
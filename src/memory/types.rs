@@ -16,6 +16,18 @@ pub struct Memory {
     pub access_count: i64,
     pub source: Option<String>,
     pub channel_id: Option<String>,
+    /// Platform-qualified ID of the person this memory is about (for example
+    /// `discord:123456`). `None` means the memory is global and visible to
+    /// every user's recall.
+    #[serde(default)]
+    pub user_id: Option<String>,
+    /// Lifecycle tier. Recent memories get a search boost and skip decay.
+    #[serde(default)]
+    pub tier: MemoryTier,
+    /// When the memory was last demoted from the recent tier. Starts the
+    /// long-term retention clock used by pruning.
+    #[serde(default)]
+    pub demoted_at: Option<chrono::DateTime<chrono::Utc>>,
     /// Soft-delete flag. Forgotten memories are excluded from search and recall
     /// but remain in the database.
     pub forgotten: bool,
@@ -39,6 +51,9 @@ impl Memory {
             access_count: 0,
             source: None,
             channel_id: None,
+            user_id: None,
+            tier: MemoryTier::LongTerm,
+            demoted_at: None,
             forgotten: false,
         }
     }
@@ -61,6 +76,34 @@ impl Memory {
         self
     }
 
+    /// Scope the memory to a specific user.
+    pub fn with_user_id(mut self, user_id: impl Into<String>) -> Self {
+        self.user_id = Some(user_id.into());
+        self
+    }
+
+    /// Set the lifecycle tier. Identity memories are permanent and always
+    /// stay in the long-term tier.
+    pub fn with_tier(mut self, tier: MemoryTier) -> Self {
+        self.tier = if self.memory_type == MemoryType::Identity {
+            MemoryTier::LongTerm
+        } else {
+            tier
+        };
+        self
+    }
+
+    /// Whether this memory is visible to a recall scoped to `user_id`.
+    ///
+    /// Global memories are visible to everyone; user-scoped memories only to
+    /// their owner. An unscoped recall (`None`) sees everything.
+    pub fn visible_to(&self, user_id: Option<&str>) -> bool {
+        match (user_id, self.user_id.as_deref()) {
+            (None, _) | (_, None) => true,
+            (Some(requested), Some(owner)) => requested == owner,
+        }
+    }
+
     /// Identity memories have maximum importance and don't decay.
     pub const fn identity_importance() -> f32 {
         1.0
@@ -72,6 +115,18 @@ impl Memory {
     }
 }
 
+/// Build the memory scope ID for an inbound sender.
+///
+/// Sender IDs are only unique within a platform, so the platform name is
+/// prefixed. System messages (retriggers, cron) have no human sender and
+/// return `None`.
+pub fn user_scope_id(platform: &str, sender_id: &str) -> Option<String> {
+    if platform == "system" || sender_id == "system" || sender_id.trim().is_empty() {
+        return None;
+    }
+    Some(format!("{platform}:{sender_id}"))
+}
+
 impl MemoryType {
     /// Get the default importance for this memory type.
     pub fn default_importance(&self) -> f32 {
@@ -139,6 +194,37 @@ impl std::fmt::Display for MemoryType {
     }
 }
 
+/// Memory lifecycle tiers.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoryTier {
+    /// Recently saved or promoted. Boosted in search, exempt from decay,
+    /// demoted after going unaccessed for the configured TTL.
+    Recent,
+    /// Long-term associative memory subject to decay, pruning, and merging.
+    #[default]
+    LongTerm,
+}
+
+impl MemoryTier {
+    /// Parse a tier name, falling back to long-term for unknown values.
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "recent" => MemoryTier::Recent,
+            _ => MemoryTier::LongTerm,
+        }
+    }
+}
+
+impl std::fmt::Display for MemoryTier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MemoryTier::Recent => write!(f, "recent"),
+            MemoryTier::LongTerm => write!(f, "long_term"),
+        }
+    }
+}
+
 /// Association between memories.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
 pub struct Association {
@@ -232,3 +318,45 @@ pub struct CreateAssociationInput {
     pub relation_type: RelationType,
     pub weight: f32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn user_scope_id_qualifies_platform_and_skips_system() {
+        assert_eq!(
+            user_scope_id("discord", "123456").as_deref(),
+            Some("discord:123456")
+        );
+        assert_eq!(user_scope_id("system", "cortex"), None);
+        assert_eq!(user_scope_id("telegram", "system"), None);
+        assert_eq!(user_scope_id("slack", "  "), None);
+    }
+
+    #[test]
+    fn visible_to_allows_owner_and_globals_only() {
+        let global = Memory::new("shared", MemoryType::Decision);
+        let scoped = Memory::new("alice's project", MemoryType::Fact).with_user_id("discord:alice");
+
+        assert!(global.visible_to(Some("discord:bob")));
+        assert!(scoped.visible_to(Some("discord:alice")));
+        assert!(!scoped.visible_to(Some("discord:bob")));
+        assert!(scoped.visible_to(None));
+    }
+
+    #[test]
+    fn identity_memories_stay_long_term() {
+        let fact = Memory::new("current task", MemoryType::Fact).with_tier(MemoryTier::Recent);
+        let identity =
+            Memory::new("my name is spacebot", MemoryType::Identity).with_tier(MemoryTier::Recent);
+
+        assert_eq!(fact.tier, MemoryTier::Recent);
+        assert_eq!(identity.tier, MemoryTier::LongTerm);
+        assert_eq!(
+            MemoryTier::parse(&MemoryTier::Recent.to_string()),
+            MemoryTier::Recent
+        );
+        assert_eq!(MemoryTier::parse("unknown"), MemoryTier::LongTerm);
+    }
+}
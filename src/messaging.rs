@@ -1,18 +1,23 @@
-//! Messaging adapters (Discord, Slack, Telegram, Twitch, Signal, Email, Webhook, Portal, Mattermost).
+//! Messaging adapters (Discord, Slack, Telegram, Twitch, Signal, Email, Webhook, Portal, Mattermost, Matrix, IRC, Teams).
 
 pub mod discord;
 pub mod email;
+pub mod irc;
 pub mod manager;
+pub mod matrix;
 pub mod mattermost;
+pub mod outbox;
 pub mod portal;
 pub mod signal;
 pub mod slack;
+pub mod streaming;
 pub mod target;
+pub mod teams;
 pub mod telegram;
 pub mod traits;
 pub mod twitch;
 pub mod webhook;
 
-pub use manager::MessagingManager;
+pub use manager::{MessagingManager, ProactiveDelivery};
 pub use traits::Messaging;
 pub use traits::apply_runtime_adapter_to_conversation_id;
@@ -1,7 +1,9 @@
 //! Discord messaging adapter using serenity.
 
+use crate::commands::CommandSpec;
 use crate::config::DiscordPermissions;
 use crate::messaging::apply_runtime_adapter_to_conversation_id;
+use crate::messaging::streaming::StreamRegistry;
 use crate::messaging::traits::{HistoryMessage, InboundStream, Messaging};
 use crate::{InboundMessage, MessageContent, OutboundResponse, StatusUpdate};
 
@@ -9,12 +11,13 @@ use anyhow::Context as _;
 use arc_swap::ArcSwap;
 use async_trait::async_trait;
 use serenity::all::{
-    ButtonStyle, ChannelId, ChannelType, Context, CreateActionRow, CreateAttachment, CreateButton,
+    ButtonStyle, ChannelId, ChannelType, Command, CommandInteraction, CommandOptionType, Context,
+    CreateActionRow, CreateAttachment, CreateButton, CreateCommand, CreateCommandOption,
     CreateEmbed, CreateEmbedAuthor, CreateEmbedFooter, CreateInteractionResponse,
     CreateInteractionResponseMessage, CreateMessage, CreatePoll, CreatePollAnswer,
     CreateSelectMenu, CreateSelectMenuKind, CreateSelectMenuOption, CreateThread, EditMessage,
     EventHandler, GatewayIntents, GetMessages, Http, Interaction, Message, MessageId, ReactionType,
-    Ready, ShardManager, Timestamp, User, UserId,
+    Ready, RoleId, ShardManager, Timestamp, User, UserId,
 };
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -28,10 +31,12 @@ pub struct DiscordAdapter {
     http: Arc<RwLock<Option<Arc<Http>>>>,
     bot_user_id: Arc<RwLock<Option<UserId>>>,
     /// Maps InboundMessage.id to the Discord MessageId being edited during streaming.
-    active_messages: Arc<RwLock<HashMap<String, serenity::all::MessageId>>>,
+    streams: Arc<StreamRegistry<MessageId>>,
     /// Typing handles per message. Typing stops when the handle is dropped.
     typing_tasks: Arc<RwLock<HashMap<String, serenity::http::Typing>>>,
     shard_manager: Arc<RwLock<Option<Arc<ShardManager>>>>,
+    /// Chat commands registered as global application commands on connect.
+    commands: Arc<Vec<CommandSpec>>,
 }
 
 impl DiscordAdapter {
@@ -46,12 +51,19 @@ impl DiscordAdapter {
             permissions,
             http: Arc::new(RwLock::new(None)),
             bot_user_id: Arc::new(RwLock::new(None)),
-            active_messages: Arc::new(RwLock::new(HashMap::new())),
+            streams: Arc::new(StreamRegistry::new(2000)),
             typing_tasks: Arc::new(RwLock::new(HashMap::new())),
             shard_manager: Arc::new(RwLock::new(None)),
+            commands: Arc::new(Vec::new()),
         }
     }
 
+    /// Register these chat commands as application commands when connected.
+    pub fn with_commands(mut self, commands: Vec<CommandSpec>) -> Self {
+        self.commands = Arc::new(commands);
+        self
+    }
+
     async fn get_http(&self) -> anyhow::Result<Arc<Http>> {
         self.http
             .read()
@@ -113,6 +125,7 @@ impl Messaging for DiscordAdapter {
             permissions: self.permissions.clone(),
             http_slot: self.http.clone(),
             bot_user_id_slot: self.bot_user_id.clone(),
+            commands: self.commands.clone(),
         };
 
         let intents = GatewayIntents::GUILD_MESSAGES
@@ -322,28 +335,36 @@ impl Messaging for DiscordAdapter {
                     .await
                     .context("failed to send stream placeholder")?;
 
-                self.active_messages
-                    .write()
-                    .await
-                    .insert(message.id.clone(), placeholder.id);
+                self.streams.start(&message.id, placeholder.id).await;
             }
             OutboundResponse::StreamChunk(text) => {
-                let active = self.active_messages.read().await;
-                if let Some(&message_id) = active.get(&message.id) {
-                    let display_text = if text.len() > 2000 {
-                        let end = text.floor_char_boundary(1997);
-                        format!("{}...", &text[..end])
-                    } else {
-                        text
-                    };
-                    let builder = EditMessage::new().content(display_text);
-                    if let Err(error) = channel_id.edit_message(&*http, message_id, builder).await {
+                if let Some(edit) = self.streams.update(&message.id, text).await {
+                    let builder = EditMessage::new().content(edit.text);
+                    if let Err(error) = channel_id.edit_message(&*http, edit.handle, builder).await
+                    {
                         tracing::warn!(%error, "failed to edit streaming message");
                     }
                 }
             }
             OutboundResponse::StreamEnd => {
-                self.active_messages.write().await.remove(&message.id);
+                if let Some(stream) = self.streams.finish(&message.id).await {
+                    let mut chunks = split_message(&stream.text, 2000).into_iter();
+                    if let Some(first) = chunks.next() {
+                        let builder = EditMessage::new().content(first);
+                        if let Err(error) = channel_id
+                            .edit_message(&*http, stream.handle, builder)
+                            .await
+                        {
+                            tracing::warn!(%error, "failed to finalize streaming message");
+                        }
+                    }
+                    for chunk in chunks {
+                        channel_id
+                            .say(&*http, chunk)
+                            .await
+                            .context("failed to send overflow chunk for streaming message")?;
+                    }
+                }
             }
             OutboundResponse::Status(status) => {
                 self.send_status(message, status).await?;
@@ -569,8 +590,13 @@ impl Messaging for DiscordAdapter {
         Ok(())
     }
 
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
     async fn shutdown(&self) -> crate::Result<()> {
         self.typing_tasks.write().await.clear();
+        self.streams.clear().await;
 
         if let Some(shard_manager) = self.shard_manager.read().await.as_ref() {
             shard_manager.shutdown_all().await;
@@ -589,6 +615,7 @@ struct Handler {
     permissions: Arc<ArcSwap<DiscordPermissions>>,
     http_slot: Arc<RwLock<Option<Arc<Http>>>>,
     bot_user_id_slot: Arc<RwLock<Option<UserId>>>,
+    commands: Arc<Vec<CommandSpec>>,
 }
 
 #[async_trait]
@@ -599,6 +626,25 @@ impl EventHandler for Handler {
         *self.http_slot.write().await = Some(ctx.http.clone());
         *self.bot_user_id_slot.write().await = Some(ready.user.id);
         tracing::info!(guild_count = ready.guilds.len(), "discord guilds available");
+
+        if !self.commands.is_empty() {
+            let builders = self
+                .commands
+                .iter()
+                .map(build_application_command)
+                .collect();
+            match Command::set_global_commands(&ctx.http, builders).await {
+                Ok(registered) => {
+                    tracing::info!(
+                        count = registered.len(),
+                        "discord application commands registered"
+                    );
+                }
+                Err(error) => {
+                    tracing::warn!(%error, "failed to register discord application commands");
+                }
+            }
+        }
     }
 
     async fn message(&self, ctx: Context, message: Message) {
@@ -678,7 +724,11 @@ impl EventHandler for Handler {
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
         let component = match interaction {
             Interaction::Component(c) => c,
-            _ => return, // Only handle component interactions
+            Interaction::Command(command) => {
+                self.command_interaction(&ctx, command).await;
+                return;
+            }
+            _ => return, // Only handle component and command interactions
         };
 
         // Acknowledge the interaction immediately to prevent "This interaction failed" in the UI.
@@ -763,6 +813,9 @@ impl EventHandler for Handler {
             "sender_display_name".into(),
             serde_json::Value::String(formatted_author.clone()),
         );
+        if let Some(member) = &component.member {
+            insert_role_ids(&mut metadata, &member.roles);
+        }
 
         let inbound = InboundMessage {
             id: component.id.to_string(), // Use interaction ID to ensure uniqueness
@@ -786,6 +839,176 @@ impl EventHandler for Handler {
     }
 }
 
+impl Handler {
+    /// Turn an application command into a `/name args` text message so it
+    /// runs through the channel's command handling like a typed command.
+    async fn command_interaction(&self, ctx: &Context, command: CommandInteraction) {
+        let user = &command.user;
+        let permissions = self.permissions.load();
+
+        let dm_denied = command.guild_id.is_none()
+            && (permissions.dm_allowed_users.is_empty()
+                || !permissions.dm_allowed_users.contains(&user.id.get()));
+        let guild_denied = permissions
+            .guild_filter
+            .as_ref()
+            .zip(command.guild_id)
+            .is_some_and(|(filter, guild_id)| !filter.contains(&guild_id.get()));
+        let parent_channel_id = command
+            .channel
+            .as_ref()
+            .and_then(|channel| channel.parent_id);
+        let channel_denied = command
+            .guild_id
+            .and_then(|guild_id| permissions.channel_filter.get(&guild_id.get()))
+            .is_some_and(|allowed| {
+                !allowed.is_empty()
+                    && !allowed.contains(&command.channel_id.get())
+                    && !parent_channel_id.is_some_and(|parent| allowed.contains(&parent.get()))
+            });
+        let denied = dm_denied || guild_denied || channel_denied;
+
+        let acknowledgement = if denied {
+            "commands aren't enabled here.".to_string()
+        } else {
+            format!("running `/{}`...", command.data.name)
+        };
+        if let Err(error) = command
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::new()
+                        .content(acknowledgement)
+                        .ephemeral(true),
+                ),
+            )
+            .await
+        {
+            tracing::warn!(%error, "failed to acknowledge command interaction");
+        }
+        if denied {
+            return;
+        }
+
+        // Order option values by the declared arguments so they bind positionally.
+        let values = self
+            .commands
+            .iter()
+            .find(|spec| spec.name == command.data.name)
+            .map(|spec| {
+                spec.args
+                    .iter()
+                    .map(|arg| {
+                        command
+                            .data
+                            .options
+                            .iter()
+                            .find(|option| option.name == arg.name)
+                            .and_then(|option| option.value.as_str())
+                            .unwrap_or_default()
+                            .to_string()
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        let text = crate::commands::invocation_text(&command.data.name, &values);
+
+        let base_conversation_id = match command.guild_id {
+            Some(guild_id) => format!("discord:{}:{}", guild_id, command.channel_id),
+            None => format!("discord:dm:{}", user.id),
+        };
+        let conversation_id =
+            apply_runtime_adapter_to_conversation_id(&self.runtime_key, base_conversation_id);
+
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "discord_channel_id".into(),
+            serde_json::Value::Number(command.channel_id.get().into()),
+        );
+        if let Some(parent_channel_id) = parent_channel_id {
+            metadata.insert(
+                "discord_parent_channel_id".into(),
+                serde_json::Value::Number(parent_channel_id.get().into()),
+            );
+        }
+        // An application command is an explicit invocation, like a mention.
+        metadata.insert("discord_mentioned_bot".into(), true.into());
+        metadata.insert("discord_reply_to_bot".into(), false.into());
+        metadata.insert("discord_mentions_or_replies_to_bot".into(), true.into());
+        if let Some(guild_id) = command.guild_id {
+            metadata.insert(
+                "discord_guild_id".into(),
+                serde_json::Value::Number(guild_id.get().into()),
+            );
+        }
+
+        let formatted_author = format!("{} (<@{}>)", user.name, user.id);
+        metadata.insert(
+            "discord_user_id".into(),
+            serde_json::Value::Number(user.id.get().into()),
+        );
+        metadata.insert(
+            "sender_display_name".into(),
+            serde_json::Value::String(formatted_author.clone()),
+        );
+        if let Some(member) = &command.member {
+            insert_role_ids(&mut metadata, &member.roles);
+        }
+
+        let inbound = InboundMessage {
+            id: command.id.to_string(),
+            source: "discord".into(),
+            adapter: Some(self.runtime_key.clone()),
+            conversation_id,
+            sender_id: user.id.to_string(),
+            agent_id: None,
+            content: MessageContent::Text(text),
+            timestamp: chrono::Utc::now(),
+            metadata,
+            formatted_author: Some(formatted_author),
+        };
+
+        if let Err(error) = self.inbound_tx.send(inbound).await {
+            tracing::warn!(
+                %error,
+                "failed to send inbound command from Discord (receiver dropped)"
+            );
+        }
+    }
+}
+
+/// Discord allows 1-100 character descriptions on commands and options.
+fn application_command_description(description: &str, fallback: &str) -> String {
+    let description = description.trim();
+    let description = if description.is_empty() {
+        fallback
+    } else {
+        description
+    };
+    description.chars().take(100).collect()
+}
+
+fn build_application_command(spec: &CommandSpec) -> CreateCommand {
+    let options = spec
+        .args
+        .iter()
+        .map(|arg| {
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                &arg.name,
+                application_command_description(&arg.description, &arg.name),
+            )
+            .required(arg.required)
+        })
+        .collect();
+    CreateCommand::new(&spec.name)
+        .description(application_command_description(
+            &spec.description,
+            &spec.name,
+        ))
+        .set_options(options)
+}
+
 fn is_mention_or_reply_to_bot(message: &Message, bot_user_id: Option<UserId>) -> bool {
     is_mention_to_bot(message, bot_user_id) || is_reply_to_bot(message, bot_user_id)
 }
@@ -866,6 +1089,21 @@ fn resolve_mentions(content: &str, mentions: &[User]) -> String {
     resolved
 }
 
+/// Record the sender's guild role IDs for access role mapping.
+fn insert_role_ids(metadata: &mut HashMap<String, serde_json::Value>, roles: &[RoleId]) {
+    if roles.is_empty() {
+        return;
+    }
+    let role_ids = roles
+        .iter()
+        .map(|role| serde_json::Value::String(role.get().to_string()))
+        .collect();
+    metadata.insert(
+        crate::metadata_keys::SENDER_GROUP_IDS.into(),
+        serde_json::Value::Array(role_ids),
+    );
+}
+
 async fn build_metadata(
     ctx: &Context,
     message: &Message,
@@ -913,6 +1151,10 @@ async fn build_metadata(
         metadata.insert("sender_is_bot".into(), true.into());
     }
 
+    if let Some(member) = &message.member {
+        insert_role_ids(&mut metadata, &member.roles);
+    }
+
     if let Some(guild_id) = message.guild_id {
         metadata.insert("discord_guild_id".into(), guild_id.get().into());
 
@@ -1,7 +1,7 @@
 //! Email messaging adapter using IMAP polling and SMTP delivery.
 
 use crate::config::EmailConfig;
-use crate::messaging::traits::{HistoryMessage, InboundStream, Messaging};
+use crate::messaging::traits::{AdapterHealth, HistoryMessage, InboundStream, Messaging};
 use crate::{InboundMessage, MessageContent, OutboundResponse};
 
 use anyhow::Context as _;
@@ -16,11 +16,15 @@ use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
 use std::net::ToSocketAddrs;
 use std::sync::{Arc, OnceLock};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::{RwLock, mpsc, watch};
 use tokio::task::JoinHandle;
 
 const EMAIL_MAX_RETRY_BACKOFF_SECS: u64 = 300;
+/// How long a single IDLE command runs before it is re-issued. RFC 2177 asks
+/// clients to restart IDLE at least every 29 minutes; restarting sooner also
+/// rescans the folder in case a notification was missed.
+const EMAIL_IDLE_REFRESH: Duration = Duration::from_secs(5 * 60);
 
 /// Wraps both TLS and plaintext IMAP sessions behind a common interface.
 ///
@@ -75,6 +79,110 @@ impl ImapSession {
             Self::Plain(session) => session.logout(),
         }
     }
+
+    fn supports_idle(&mut self) -> imap::error::Result<bool> {
+        let capabilities = match self {
+            Self::Tls(session) => session.capabilities()?,
+            Self::Plain(session) => session.capabilities()?,
+        };
+        Ok(capabilities.has_str("IDLE"))
+    }
+
+    /// Block in IDLE on the selected folder until it changes or `timeout`
+    /// passes.
+    fn idle_wait(
+        &mut self,
+        timeout: Duration,
+    ) -> imap::error::Result<imap::extensions::idle::WaitOutcome> {
+        match self {
+            Self::Tls(session) => session.idle()?.wait_with_timeout(timeout),
+            Self::Plain(session) => session.idle()?.wait_with_timeout(timeout),
+        }
+    }
+}
+
+/// How a configured folder is currently being watched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FolderWatchMode {
+    Connecting,
+    /// Push delivery via IMAP IDLE.
+    Idle,
+    /// Polling because IDLE is disabled in config.
+    Polling,
+    /// Polling because the server doesn't advertise IDLE.
+    PollingFallback,
+}
+
+#[derive(Debug, Clone)]
+struct FolderWatchState {
+    mode: FolderWatchMode,
+    /// Most recent failure, cleared once the watch recovers.
+    last_error: Option<String>,
+    last_activity: Instant,
+}
+
+type FolderWatches = Arc<std::sync::Mutex<HashMap<String, FolderWatchState>>>;
+
+fn update_folder_watch(
+    watches: &FolderWatches,
+    folder: &str,
+    mode: FolderWatchMode,
+    last_error: Option<String>,
+) {
+    if let Ok(mut watches) = watches.lock() {
+        watches.insert(
+            folder.to_string(),
+            FolderWatchState {
+                mode,
+                last_error,
+                last_activity: Instant::now(),
+            },
+        );
+    }
+}
+
+/// Summarize folder watches for status reporting. A folder is unhealthy when
+/// its last attempt failed or it has gone quiet for longer than `stale_after`.
+fn summarize_folder_watches(
+    watches: &HashMap<String, FolderWatchState>,
+    folders: &[String],
+    stale_after: Duration,
+    now: Instant,
+) -> AdapterHealth {
+    let mut healthy = true;
+    let mut parts = Vec::with_capacity(folders.len());
+
+    for folder in folders {
+        let Some(state) = watches.get(folder) else {
+            healthy = false;
+            parts.push(format!("{folder}: not watched"));
+            continue;
+        };
+
+        let quiet_for = now.saturating_duration_since(state.last_activity);
+        let part = if let Some(error) = &state.last_error {
+            healthy = false;
+            format!("{folder}: reconnecting after error: {error}")
+        } else if quiet_for > stale_after {
+            healthy = false;
+            format!("{folder}: no response for {}s", quiet_for.as_secs())
+        } else {
+            match state.mode {
+                FolderWatchMode::Connecting => format!("{folder}: connecting"),
+                FolderWatchMode::Idle => format!("{folder}: idle"),
+                FolderWatchMode::Polling => format!("{folder}: polling"),
+                FolderWatchMode::PollingFallback => {
+                    format!("{folder}: polling (server lacks IDLE)")
+                }
+            }
+        };
+        parts.push(part);
+    }
+
+    AdapterHealth {
+        healthy,
+        detail: parts.join("; "),
+    }
 }
 
 #[derive(Clone)]
@@ -139,12 +247,14 @@ pub struct EmailAdapter {
     from_name: Option<String>,
     folders: Vec<String>,
     poll_interval: Duration,
+    idle: bool,
     allowed_senders: Vec<String>,
     max_body_bytes: usize,
     max_attachment_bytes: usize,
     smtp_transport: AsyncSmtpTransport<Tokio1Executor>,
     shutdown_tx: Arc<RwLock<Option<watch::Sender<bool>>>>,
     poll_task: Arc<RwLock<Option<JoinHandle<()>>>>,
+    folder_watches: FolderWatches,
 }
 
 impl std::fmt::Debug for EmailAdapter {
@@ -163,6 +273,7 @@ impl std::fmt::Debug for EmailAdapter {
             .field("from_name", &self.from_name)
             .field("folders", &self.folders)
             .field("poll_interval", &self.poll_interval)
+            .field("idle", &self.idle)
             .field("allowed_senders", &"[REDACTED]")
             .field("max_body_bytes", &self.max_body_bytes)
             .field("max_attachment_bytes", &self.max_attachment_bytes)
@@ -195,6 +306,7 @@ impl EmailAdapter {
             from_address: config.from_address.clone(),
             from_name: config.from_name.clone(),
             poll_interval_secs: config.poll_interval_secs,
+            idle: config.idle,
             folders: config.folders.clone(),
             allowed_senders: config.allowed_senders.clone(),
             max_body_bytes: config.max_body_bytes,
@@ -235,12 +347,14 @@ impl EmailAdapter {
             from_name: config.from_name.clone(),
             folders,
             poll_interval: Duration::from_secs(config.poll_interval_secs.max(5)),
+            idle: config.idle,
             allowed_senders: config.allowed_senders.clone(),
             max_body_bytes: config.max_body_bytes.max(1024),
             max_attachment_bytes: config.max_attachment_bytes.max(1024),
             smtp_transport,
             shutdown_tx: Arc::new(RwLock::new(None)),
             poll_task: Arc::new(RwLock::new(None)),
+            folder_watches: Arc::new(std::sync::Mutex::new(HashMap::new())),
         })
     }
 
@@ -342,69 +456,46 @@ impl Messaging for EmailAdapter {
         }
 
         let (inbound_tx, inbound_rx) = mpsc::channel(256);
-        let (shutdown_tx, mut shutdown_rx) = watch::channel(false);
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
 
         *self.shutdown_tx.write().await = Some(shutdown_tx);
 
         let poll_config = self.poll_config();
+        let watches = self.folder_watches.clone();
+        if let Ok(mut watches) = watches.lock() {
+            watches.clear();
+        }
+        for folder in &poll_config.folders {
+            update_folder_watch(&watches, folder, FolderWatchMode::Connecting, None);
+        }
 
-        let poll_task = tokio::spawn(async move {
-            let mut retry_backoff = Duration::from_secs(5);
-
-            loop {
-                if *shutdown_rx.borrow() {
-                    break;
-                }
-
-                let config = poll_config.clone();
-                let poll_result =
-                    tokio::task::spawn_blocking(move || poll_inbox_once(&config)).await;
-
-                let mut had_error = false;
-
-                match poll_result {
-                    Ok(Ok(messages)) => {
-                        retry_backoff = Duration::from_secs(5);
-                        for message in messages {
-                            if inbound_tx.send(message).await.is_err() {
-                                tracing::warn!(
-                                    "email inbound channel closed, stopping adapter loop"
-                                );
-                                return;
-                            }
-                        }
-                    }
-                    Ok(Err(error)) => {
-                        had_error = true;
-                        tracing::warn!(%error, "email poll cycle failed");
-                    }
-                    Err(error) => {
-                        had_error = true;
-                        tracing::warn!(%error, "email poll task panicked");
-                    }
-                }
-
-                let sleep_duration = if had_error {
-                    let current = retry_backoff;
-                    retry_backoff =
-                        (retry_backoff * 2).min(Duration::from_secs(EMAIL_MAX_RETRY_BACKOFF_SECS));
-                    current
-                } else {
-                    poll_config.poll_interval
-                };
-
-                tokio::select! {
-                    _ = shutdown_rx.changed() => {
-                        if *shutdown_rx.borrow() {
-                            break;
-                        }
-                    }
-                    _ = tokio::time::sleep(sleep_duration) => {}
-                }
-            }
-
-            tracing::info!("email adapter loop stopped");
-        });
+        let poll_task = if self.idle {
+            tokio::spawn(async move {
+                let folder_watches = poll_config.folders.iter().map(|folder| {
+                    watch_folder(
+                        poll_config.clone(),
+                        folder.clone(),
+                        inbound_tx.clone(),
+                        shutdown_rx.clone(),
+                        watches.clone(),
+                    )
+                });
+                futures::future::join_all(folder_watches).await;
+                tracing::info!("email adapter loop stopped");
+            })
+        } else {
+            tokio::spawn(async move {
+                poll_folders(
+                    poll_config,
+                    inbound_tx,
+                    shutdown_rx,
+                    watches,
+                    FolderWatchMode::Polling,
+                )
+                .await;
+                tracing::info!("email adapter loop stopped");
+            })
+        };
 
         *self.poll_task.write().await = Some(poll_task);
 
@@ -628,6 +719,12 @@ impl Messaging for EmailAdapter {
     }
 
     async fn health_check(&self) -> crate::Result<()> {
+        if let Some(health) = self.connection_health()
+            && !health.healthy
+        {
+            return Err(anyhow::anyhow!("email inbox watch unhealthy: {}", health.detail).into());
+        }
+
         let poll_config = self.poll_config();
         tokio::task::spawn_blocking(move || {
             let mut session = open_imap_session(&poll_config)?;
@@ -657,6 +754,20 @@ impl Messaging for EmailAdapter {
         Ok(())
     }
 
+    fn connection_health(&self) -> Option<AdapterHealth> {
+        let watches = self.folder_watches.lock().ok()?;
+        if watches.is_empty() {
+            return None;
+        }
+        let stale_after = EMAIL_IDLE_REFRESH.max(self.poll_interval) * 2;
+        Some(summarize_folder_watches(
+            &watches,
+            &self.folders,
+            stale_after,
+            Instant::now(),
+        ))
+    }
+
     async fn shutdown(&self) -> crate::Result<()> {
         if let Some(shutdown_tx) = self.shutdown_tx.write().await.take() {
             shutdown_tx.send(true).ok();
@@ -669,6 +780,9 @@ impl Messaging for EmailAdapter {
         }
 
         self.smtp_transport.shutdown().await;
+        if let Ok(mut watches) = self.folder_watches.lock() {
+            watches.clear();
+        }
 
         tracing::info!("email adapter shut down");
         Ok(())
@@ -703,6 +817,7 @@ fn build_smtp_transport(config: &EmailConfig) -> crate::Result<AsyncSmtpTranspor
         .build())
 }
 
+/// Poll every configured folder once over a fresh session.
 fn poll_inbox_once(config: &EmailPollConfig) -> anyhow::Result<Vec<InboundMessage>> {
     let mut session = open_imap_session(config)?;
     let mut inbound_messages = Vec::new();
@@ -713,58 +828,292 @@ fn poll_inbox_once(config: &EmailPollConfig) -> anyhow::Result<Vec<InboundMessag
             continue;
         }
 
-        let message_uids = session
-            .uid_search("UNSEEN")
-            .with_context(|| format!("failed to search unseen messages in folder '{folder}'"))?;
+        inbound_messages.extend(fetch_unseen(&mut session, folder, config)?);
+    }
 
-        for uid in message_uids {
-            let uid_sequence = uid.to_string();
+    session.logout().ok();
 
-            let fetches = match session.uid_fetch(&uid_sequence, "(UID RFC822)") {
-                Ok(fetches) => fetches,
+    Ok(inbound_messages)
+}
+
+/// Fetch unseen messages from the selected folder and mark the ones that
+/// were handled as seen.
+fn fetch_unseen(
+    session: &mut ImapSession,
+    folder: &str,
+    config: &EmailPollConfig,
+) -> anyhow::Result<Vec<InboundMessage>> {
+    let mut inbound_messages = Vec::new();
+
+    let message_uids = session
+        .uid_search("UNSEEN")
+        .with_context(|| format!("failed to search unseen messages in folder '{folder}'"))?;
+
+    for uid in message_uids {
+        let uid_sequence = uid.to_string();
+
+        let fetches = match session.uid_fetch(&uid_sequence, "(UID RFC822)") {
+            Ok(fetches) => fetches,
+            Err(error) => {
+                tracing::warn!(folder, uid, %error, "failed to fetch unseen email");
+                continue;
+            }
+        };
+
+        let mut should_mark_seen = !fetches.is_empty();
+
+        for fetch in &fetches {
+            let current_uid = fetch.uid.unwrap_or(uid);
+            let Some(raw_email) = fetch.body() else {
+                should_mark_seen = false;
+                tracing::warn!(
+                    folder,
+                    uid = current_uid,
+                    "email fetch body missing; leaving message unseen for retry"
+                );
+                continue;
+            };
+
+            match parse_inbound_email(raw_email, folder, current_uid, config) {
+                Ok(Some(inbound_message)) => inbound_messages.push(inbound_message),
+                Ok(None) => {}
                 Err(error) => {
-                    tracing::warn!(folder, uid, %error, "failed to fetch unseen email");
-                    continue;
+                    should_mark_seen = false;
+                    tracing::warn!(folder, uid = current_uid, %error, "failed to parse inbound email");
                 }
-            };
+            }
+        }
 
-            let mut should_mark_seen = !fetches.is_empty();
+        if should_mark_seen {
+            if let Err(error) = session.uid_store(&uid_sequence, "+FLAGS (\\Seen)") {
+                tracing::warn!(folder, uid, %error, "failed to mark email as seen");
+            }
+        } else {
+            tracing::debug!(folder, uid, "leaving email unseen for retry");
+        }
+    }
 
-            for fetch in &fetches {
-                let current_uid = fetch.uid.unwrap_or(uid);
-                let Some(raw_email) = fetch.body() else {
-                    should_mark_seen = false;
-                    tracing::warn!(
-                        folder,
-                        uid = current_uid,
-                        "email fetch body missing; leaving message unseen for retry"
-                    );
-                    continue;
-                };
+    Ok(inbound_messages)
+}
 
-                match parse_inbound_email(raw_email, folder, current_uid, config) {
-                    Ok(Some(inbound_message)) => inbound_messages.push(inbound_message),
-                    Ok(None) => {}
-                    Err(error) => {
-                        should_mark_seen = false;
-                        tracing::warn!(folder, uid = current_uid, %error, "failed to parse inbound email");
+/// Resolve once shutdown is requested or the adapter is gone.
+async fn wait_for_shutdown(shutdown_rx: &mut watch::Receiver<bool>) {
+    while !*shutdown_rx.borrow_and_update() {
+        if shutdown_rx.changed().await.is_err() {
+            return;
+        }
+    }
+}
+
+/// Poll `config.folders` on a timer until shutdown, backing off on errors.
+async fn poll_folders(
+    config: EmailPollConfig,
+    inbound_tx: mpsc::Sender<InboundMessage>,
+    mut shutdown_rx: watch::Receiver<bool>,
+    watches: FolderWatches,
+    mode: FolderWatchMode,
+) {
+    let mut retry_backoff = Duration::from_secs(5);
+
+    loop {
+        if *shutdown_rx.borrow() {
+            break;
+        }
+
+        let poll_config = config.clone();
+        let poll_result = tokio::task::spawn_blocking(move || poll_inbox_once(&poll_config)).await;
+
+        let error = match poll_result {
+            Ok(Ok(messages)) => {
+                retry_backoff = Duration::from_secs(5);
+                for folder in &config.folders {
+                    update_folder_watch(&watches, folder, mode, None);
+                }
+                for message in messages {
+                    if inbound_tx.send(message).await.is_err() {
+                        tracing::warn!("email inbound channel closed, stopping adapter loop");
+                        return;
                     }
                 }
+                None
+            }
+            Ok(Err(error)) => {
+                tracing::warn!(%error, "email poll cycle failed");
+                Some(error.to_string())
+            }
+            Err(error) => {
+                tracing::warn!(%error, "email poll task panicked");
+                Some(error.to_string())
             }
+        };
+
+        let sleep_duration = if let Some(error) = error {
+            for folder in &config.folders {
+                update_folder_watch(&watches, folder, mode, Some(error.clone()));
+            }
+            let current = retry_backoff;
+            retry_backoff =
+                (retry_backoff * 2).min(Duration::from_secs(EMAIL_MAX_RETRY_BACKOFF_SECS));
+            current
+        } else {
+            config.poll_interval
+        };
+
+        tokio::select! {
+            _ = wait_for_shutdown(&mut shutdown_rx) => break,
+            _ = tokio::time::sleep(sleep_duration) => {}
+        }
+    }
+}
+
+/// Why a blocking IDLE session ended without an error.
+#[derive(Debug, PartialEq, Eq)]
+enum IdleExit {
+    /// Shutdown was requested or the inbound stream was dropped.
+    Stopped,
+    /// The server doesn't advertise IDLE; the caller should poll instead.
+    Unsupported,
+}
+
+/// Watch one folder with IMAP IDLE until shutdown, reconnecting with
+/// backoff on errors and falling back to polling if the server can't IDLE.
+async fn watch_folder(
+    config: EmailPollConfig,
+    folder: String,
+    inbound_tx: mpsc::Sender<InboundMessage>,
+    mut shutdown_rx: watch::Receiver<bool>,
+    watches: FolderWatches,
+) {
+    let mut retry_backoff = Duration::from_secs(5);
+
+    loop {
+        if *shutdown_rx.borrow() {
+            return;
+        }
+
+        let idle_task = {
+            let config = config.clone();
+            let folder = folder.clone();
+            let inbound_tx = inbound_tx.clone();
+            let shutdown_rx = shutdown_rx.clone();
+            let watches = watches.clone();
+            tokio::task::spawn_blocking(move || {
+                idle_folder(&config, &folder, &inbound_tx, &shutdown_rx, &watches)
+            })
+        };
 
-            if should_mark_seen {
-                if let Err(error) = session.uid_store(&uid_sequence, "+FLAGS (\\Seen)") {
-                    tracing::warn!(folder, uid, %error, "failed to mark email as seen");
+        // The blocking session notices shutdown on its next wake-up; there
+        // is no need to hold the adapter's shutdown until then.
+        let result = tokio::select! {
+            result = idle_task => result,
+            _ = wait_for_shutdown(&mut shutdown_rx) => return,
+        };
+
+        let error = match result {
+            Ok((_, Ok(IdleExit::Stopped))) => return,
+            Ok((_, Ok(IdleExit::Unsupported))) => {
+                tracing::warn!(
+                    folder,
+                    "IMAP server does not support IDLE, falling back to polling"
+                );
+                let mut config = config.clone();
+                config.folders = vec![folder.clone()];
+                poll_folders(
+                    config,
+                    inbound_tx,
+                    shutdown_rx,
+                    watches,
+                    FolderWatchMode::PollingFallback,
+                )
+                .await;
+                return;
+            }
+            Ok((connected, Err(error))) => {
+                if connected {
+                    retry_backoff = Duration::from_secs(5);
                 }
-            } else {
-                tracing::debug!(folder, uid, "leaving email unseen for retry");
+                tracing::warn!(folder, %error, "email IDLE session failed");
+                format!("{error:#}")
             }
+            Err(error) => {
+                tracing::warn!(folder, %error, "email IDLE task panicked");
+                error.to_string()
+            }
+        };
+        update_folder_watch(&watches, &folder, FolderWatchMode::Connecting, Some(error));
+
+        let current = retry_backoff;
+        retry_backoff = (retry_backoff * 2).min(Duration::from_secs(EMAIL_MAX_RETRY_BACKOFF_SECS));
+        tokio::select! {
+            _ = wait_for_shutdown(&mut shutdown_rx) => return,
+            _ = tokio::time::sleep(current) => {}
         }
     }
+}
 
-    session.logout().ok();
+/// Run one IDLE session on `folder`: deliver unseen mail, then IDLE until the
+/// folder changes, repeatedly. Blocks the calling thread. Returns whether the
+/// session got as far as selecting the folder, alongside how it ended.
+fn idle_folder(
+    config: &EmailPollConfig,
+    folder: &str,
+    inbound_tx: &mpsc::Sender<InboundMessage>,
+    shutdown_rx: &watch::Receiver<bool>,
+    watches: &FolderWatches,
+) -> (bool, anyhow::Result<IdleExit>) {
+    let mut session = match open_imap_session(config) {
+        Ok(session) => session,
+        Err(error) => return (false, Err(error)),
+    };
 
-    Ok(inbound_messages)
+    match session.supports_idle() {
+        Ok(true) => {}
+        Ok(false) => {
+            session.logout().ok();
+            return (false, Ok(IdleExit::Unsupported));
+        }
+        Err(error) => {
+            return (
+                false,
+                Err(anyhow::Error::new(error).context("failed to read IMAP capabilities")),
+            );
+        }
+    }
+
+    if let Err(error) = session.select(folder) {
+        return (
+            false,
+            Err(anyhow::Error::new(error)
+                .context(format!("failed to select IMAP folder '{folder}'"))),
+        );
+    }
+    update_folder_watch(watches, folder, FolderWatchMode::Idle, None);
+
+    let result = (|| {
+        loop {
+            for message in fetch_unseen(&mut session, folder, config)? {
+                if inbound_tx.blocking_send(message).is_err() {
+                    return Ok(IdleExit::Stopped);
+                }
+            }
+            if *shutdown_rx.borrow() {
+                return Ok(IdleExit::Stopped);
+            }
+
+            session
+                .idle_wait(EMAIL_IDLE_REFRESH)
+                .with_context(|| format!("IMAP IDLE failed in folder '{folder}'"))?;
+            if *shutdown_rx.borrow() {
+                return Ok(IdleExit::Stopped);
+            }
+            update_folder_watch(watches, folder, FolderWatchMode::Idle, None);
+        }
+    })();
+
+    if matches!(result, Ok(IdleExit::Stopped)) {
+        session.logout().ok();
+    }
+    (true, result)
 }
 
 fn open_imap_session(config: &EmailPollConfig) -> anyhow::Result<ImapSession> {
@@ -1755,10 +2104,17 @@ struct EmailReplyContext {
 #[cfg(test)]
 mod tests {
     use super::{
-        EmailSearchHit, EmailSearchQuery, build_imap_search_criterion, derive_thread_key,
-        extract_message_ids, is_local_mail_host, normalize_email_target, normalize_reply_subject,
-        normalize_search_folders, parse_primary_mailbox, sort_and_limit_search_hits,
+        EmailPollConfig, EmailSearchHit, EmailSearchQuery, FolderWatchMode, FolderWatchState,
+        FolderWatches, IdleExit, build_imap_search_criterion, derive_thread_key,
+        extract_message_ids, idle_folder, is_local_mail_host, normalize_email_target,
+        normalize_reply_subject, normalize_search_folders, parse_primary_mailbox,
+        sort_and_limit_search_hits, summarize_folder_watches,
     };
+    use crate::MessageContent;
+    use std::collections::HashMap;
+    use std::io::{BufRead as _, Write as _};
+    use std::time::{Duration, Instant};
+    use tokio::sync::{mpsc, watch};
 
     #[test]
     fn parse_primary_mailbox_parses_display_name() {
@@ -1917,4 +2273,188 @@ mod tests {
         assert_eq!(results[0].subject, "newest");
         assert_eq!(results[1].subject, "middle");
     }
+
+    const STUB_EMAIL: &str = "From: Alice <alice@example.com>\r\nTo: bot@example.com\r\nSubject: Quarterly report\r\nMessage-ID: <report-1@example.com>\r\n\r\nNumbers are in.\r\n";
+
+    /// Scripted single-connection IMAP server. Mail arrives once the client
+    /// enters IDLE; the connection is dropped when it tries to IDLE again.
+    fn spawn_stub_imap_server(advertise_idle: bool) -> u16 {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = std::io::BufReader::new(stream.try_clone().unwrap());
+            let mut writer = stream;
+            writer.write_all(b"* OK stub ready\r\n").unwrap();
+
+            let mut mail_arrived = false;
+            let mut delivered = false;
+            let mut idle_tag = String::new();
+            let mut line = String::new();
+            loop {
+                line.clear();
+                if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                    return;
+                }
+                let line = line.trim_end();
+                if line == "DONE" {
+                    write!(writer, "{idle_tag} OK IDLE terminated\r\n").unwrap();
+                    continue;
+                }
+
+                let (tag, command) = line.split_once(' ').unwrap();
+                let command = command.to_ascii_uppercase();
+                let reply = if command.starts_with("LOGIN") {
+                    format!("{tag} OK logged in\r\n")
+                } else if command.starts_with("CAPABILITY") {
+                    let idle = if advertise_idle { " IDLE" } else { "" };
+                    format!("* CAPABILITY IMAP4rev1{idle}\r\n{tag} OK done\r\n")
+                } else if command.starts_with("SELECT") {
+                    format!(
+                        "* 1 EXISTS\r\n* 0 RECENT\r\n* FLAGS (\\Seen)\r\n{tag} OK [READ-WRITE] selected\r\n"
+                    )
+                } else if command.starts_with("UID SEARCH") {
+                    let hits = if mail_arrived && !delivered { " 7" } else { "" };
+                    format!("* SEARCH{hits}\r\n{tag} OK done\r\n")
+                } else if command.starts_with("UID FETCH") {
+                    format!(
+                        "* 2 FETCH (UID 7 RFC822 {{{}}}\r\n{STUB_EMAIL})\r\n{tag} OK done\r\n",
+                        STUB_EMAIL.len()
+                    )
+                } else if command.starts_with("UID STORE") {
+                    delivered = true;
+                    format!("{tag} OK stored\r\n")
+                } else if command.starts_with("IDLE") {
+                    if mail_arrived {
+                        return;
+                    }
+                    mail_arrived = true;
+                    idle_tag = tag.to_string();
+                    "+ idling\r\n* 2 EXISTS\r\n".to_string()
+                } else if command.starts_with("LOGOUT") {
+                    write!(writer, "* BYE\r\n{tag} OK bye\r\n").unwrap();
+                    return;
+                } else {
+                    format!("{tag} BAD unexpected\r\n")
+                };
+                writer.write_all(reply.as_bytes()).unwrap();
+            }
+        });
+
+        port
+    }
+
+    fn stub_poll_config(port: u16) -> EmailPollConfig {
+        EmailPollConfig {
+            imap_host: "127.0.0.1".to_string(),
+            imap_port: port,
+            imap_username: "bot".to_string(),
+            imap_password: "secret".to_string(),
+            imap_use_tls: false,
+            from_address: "bot@example.com".to_string(),
+            smtp_username: "bot@example.com".to_string(),
+            folders: vec!["INBOX".to_string()],
+            poll_interval: Duration::from_secs(30),
+            allowed_senders: Vec::new(),
+            max_body_bytes: 65_536,
+            runtime_key: "email".to_string(),
+        }
+    }
+
+    #[test]
+    fn idle_session_delivers_mail_when_the_folder_changes() {
+        let config = stub_poll_config(spawn_stub_imap_server(true));
+        let (inbound_tx, mut inbound_rx) = mpsc::channel(8);
+        let (_shutdown_tx, shutdown_rx) = watch::channel(false);
+        let watches = FolderWatches::default();
+
+        let (connected, result) =
+            idle_folder(&config, "INBOX", &inbound_tx, &shutdown_rx, &watches);
+
+        assert!(connected);
+        assert!(
+            result.is_err(),
+            "stub drops the connection on the second IDLE"
+        );
+        let message = inbound_rx
+            .try_recv()
+            .expect("mail delivered after IDLE wake");
+        assert!(
+            matches!(message.content, MessageContent::Text(ref text) if text.contains("Numbers are in."))
+        );
+        assert!(inbound_rx.try_recv().is_err());
+        assert_eq!(watches.lock().unwrap()["INBOX"].mode, FolderWatchMode::Idle);
+    }
+
+    #[test]
+    fn idle_session_reports_servers_without_idle() {
+        let config = stub_poll_config(spawn_stub_imap_server(false));
+        let (inbound_tx, _inbound_rx) = mpsc::channel(8);
+        let (_shutdown_tx, shutdown_rx) = watch::channel(false);
+
+        let (connected, result) = idle_folder(
+            &config,
+            "INBOX",
+            &inbound_tx,
+            &shutdown_rx,
+            &FolderWatches::default(),
+        );
+
+        assert!(!connected);
+        assert_eq!(result.unwrap(), IdleExit::Unsupported);
+    }
+
+    #[test]
+    fn folder_watch_summary_flags_errors_and_stale_watches() {
+        let now = Instant::now();
+        let folders = vec![
+            "INBOX".to_string(),
+            "Support".to_string(),
+            "Archive".to_string(),
+        ];
+        let state = |mode, last_error: Option<&str>, age_secs| FolderWatchState {
+            mode,
+            last_error: last_error.map(str::to_string),
+            last_activity: now - Duration::from_secs(age_secs),
+        };
+
+        let mut watches = HashMap::new();
+        watches.insert("INBOX".to_string(), state(FolderWatchMode::Idle, None, 10));
+        watches.insert(
+            "Support".to_string(),
+            state(FolderWatchMode::PollingFallback, None, 10),
+        );
+        watches.insert(
+            "Archive".to_string(),
+            state(FolderWatchMode::Polling, None, 10),
+        );
+        let health = summarize_folder_watches(&watches, &folders, Duration::from_secs(600), now);
+        assert!(health.healthy);
+        assert_eq!(
+            health.detail,
+            "INBOX: idle; Support: polling (server lacks IDLE); Archive: polling"
+        );
+
+        watches.insert(
+            "Support".to_string(),
+            state(FolderWatchMode::Connecting, Some("connection refused"), 10),
+        );
+        let health = summarize_folder_watches(&watches, &folders, Duration::from_secs(600), now);
+        assert!(!health.healthy);
+        assert!(
+            health
+                .detail
+                .contains("Support: reconnecting after error: connection refused")
+        );
+
+        watches.insert(
+            "Support".to_string(),
+            state(FolderWatchMode::Idle, None, 10),
+        );
+        watches.insert("INBOX".to_string(), state(FolderWatchMode::Idle, None, 900));
+        let health = summarize_folder_watches(&watches, &folders, Duration::from_secs(600), now);
+        assert!(!health.healthy);
+        assert!(health.detail.contains("INBOX: no response for 900s"));
+    }
 }
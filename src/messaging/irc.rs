@@ -0,0 +1,2001 @@
+//! IRC messaging adapter speaking the client protocol directly.
+//!
+//! One long-lived connection per adapter (plain TCP or TLS), registered with
+//! IRCv3 capability negotiation and optional SASL PLAIN. Channel and direct
+//! messages become inbound messages; replies are split into protocol-sized
+//! lines and paced through a flood limiter. When the server offers
+//! `draft/chathistory`, context backfill uses `CHATHISTORY BEFORE`.
+
+use crate::config::{IrcConfig, IrcInstanceConfig, IrcPermissions};
+use crate::messaging::apply_runtime_adapter_to_conversation_id;
+use crate::messaging::traits::{
+    HistoryMessage, InboundStream, Messaging, ensure_supported_broadcast_response,
+    mark_permanent_broadcast, mark_retryable_broadcast,
+};
+use crate::{InboundMessage, MessageContent, OutboundResponse};
+
+use anyhow::Context as _;
+use arc_swap::ArcSwap;
+use base64::Engine as _;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt as _, AsyncRead, AsyncWrite, AsyncWriteExt as _, BufReader};
+use tokio::net::TcpStream;
+use tokio::sync::{Mutex, RwLock, mpsc, oneshot};
+use tokio::time::Instant;
+use tokio_rustls::rustls;
+
+/// RFC 1459 line limit, including the trailing CRLF.
+const MAX_LINE_BYTES: usize = 512;
+/// Servers prepend `:nick!user@host ` when relaying our lines; reserve room
+/// for it so relayed messages are not truncated.
+const HOSTMASK_RESERVE: usize = 100;
+/// SASL payloads are sent in base64 chunks of at most this many bytes.
+const SASL_CHUNK_BYTES: usize = 400;
+/// Outgoing messages may burst this many lines per window before pacing.
+const FLOOD_BURST: usize = 5;
+const FLOOD_WINDOW: Duration = Duration::from_secs(5);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(20);
+/// Silence after which the adapter pings the server; a second silent period
+/// drops the connection.
+const READ_IDLE_TIMEOUT: Duration = Duration::from_secs(180);
+const RECONNECT_BASE_DELAY: Duration = Duration::from_secs(2);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(120);
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(3);
+const HISTORY_TIMEOUT: Duration = Duration::from_secs(10);
+const HISTORY_MAX_LIMIT: usize = 100;
+const DEFAULT_REALNAME: &str = "Spacebot";
+
+/// Capabilities requested when the server advertises them. `sasl` is only
+/// requested when SASL credentials are configured.
+const REQUESTED_CAPS: &[&str] = &[
+    "sasl",
+    "server-time",
+    "message-tags",
+    "account-tag",
+    "batch",
+    "draft/chathistory",
+    "chathistory",
+];
+
+/// Connection settings for one IRC network.
+#[derive(Clone)]
+pub struct IrcConnectionSettings {
+    pub server: String,
+    pub port: u16,
+    pub tls: bool,
+    pub nickname: String,
+    pub username: Option<String>,
+    pub realname: Option<String>,
+    pub server_password: Option<String>,
+    pub sasl_username: Option<String>,
+    pub sasl_password: Option<String>,
+    pub channels: Vec<String>,
+}
+
+impl std::fmt::Debug for IrcConnectionSettings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IrcConnectionSettings")
+            .field("server", &self.server)
+            .field("port", &self.port)
+            .field("tls", &self.tls)
+            .field("nickname", &self.nickname)
+            .field("username", &self.username)
+            .field("realname", &self.realname)
+            .field(
+                "server_password",
+                &self.server_password.as_ref().map(|_| "[REDACTED]"),
+            )
+            .field("sasl_username", &self.sasl_username)
+            .field(
+                "sasl_password",
+                &self.sasl_password.as_ref().map(|_| "[REDACTED]"),
+            )
+            .field("channels", &self.channels)
+            .finish()
+    }
+}
+
+impl IrcConnectionSettings {
+    pub fn from_config(config: &IrcConfig) -> Self {
+        Self {
+            server: config.server.clone(),
+            port: config.port,
+            tls: config.tls,
+            nickname: config.nickname.clone(),
+            username: config.username.clone(),
+            realname: config.realname.clone(),
+            server_password: config.server_password.clone(),
+            sasl_username: config.sasl_username.clone(),
+            sasl_password: config.sasl_password.clone(),
+            channels: config.channels.clone(),
+        }
+    }
+
+    pub fn from_instance_config(instance: &IrcInstanceConfig) -> Self {
+        Self {
+            server: instance.server.clone(),
+            port: instance.port,
+            tls: instance.tls,
+            nickname: instance.nickname.clone(),
+            username: instance.username.clone(),
+            realname: instance.realname.clone(),
+            server_password: instance.server_password.clone(),
+            sasl_username: instance.sasl_username.clone(),
+            sasl_password: instance.sasl_password.clone(),
+            channels: instance.channels.clone(),
+        }
+    }
+
+    /// `(account, password)` for SASL PLAIN, if a password is configured.
+    fn sasl_credentials(&self) -> Option<(&str, &str)> {
+        let password = self
+            .sasl_password
+            .as_deref()
+            .filter(|password| !password.is_empty())?;
+        let account = self
+            .sasl_username
+            .as_deref()
+            .filter(|account| !account.is_empty())
+            .unwrap_or(&self.nickname);
+        Some((account, password))
+    }
+}
+
+pub struct IrcAdapter {
+    runtime_key: Arc<str>,
+    settings: Arc<IrcConnectionSettings>,
+    permissions: Arc<ArcSwap<IrcPermissions>>,
+    shared: Arc<SharedState>,
+    /// Serializes `fetch_history` calls; only one chathistory batch is
+    /// awaited at a time.
+    history_lock: Mutex<()>,
+    shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
+    connection_task: Arc<RwLock<Option<tokio::task::JoinHandle<()>>>>,
+}
+
+impl std::fmt::Debug for IrcAdapter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IrcAdapter")
+            .field("runtime_key", &self.runtime_key)
+            .field("settings", &self.settings)
+            .finish()
+    }
+}
+
+/// State shared between the adapter and its connection task.
+#[derive(Default)]
+struct SharedState {
+    /// Paced queue for messages; `None` until the connection is registered.
+    outgoing: RwLock<Option<mpsc::Sender<String>>>,
+    session: RwLock<SessionState>,
+    pending_history: Mutex<Option<PendingHistory>>,
+}
+
+impl SharedState {
+    /// Forget the current connection. Returns whether it had registered.
+    async fn reset(&self) -> bool {
+        self.outgoing.write().await.take();
+        self.pending_history.lock().await.take();
+        let mut session = self.session.write().await;
+        let registered = session.registered;
+        *session = SessionState::default();
+        registered
+    }
+}
+
+#[derive(Debug, Default)]
+struct SessionState {
+    registered: bool,
+    nick: String,
+    caps: HashSet<String>,
+    /// `CHATHISTORY` ISUPPORT value; `None` means the server set no limit.
+    chathistory_limit: Option<usize>,
+}
+
+impl SessionState {
+    fn supports_chathistory(&self) -> bool {
+        self.caps.contains("draft/chathistory") || self.caps.contains("chathistory")
+    }
+}
+
+/// A `CHATHISTORY` request waiting for its batch.
+struct PendingHistory {
+    target: String,
+    batch: Option<String>,
+    messages: Vec<HistoryMessage>,
+    done: oneshot::Sender<Vec<HistoryMessage>>,
+}
+
+impl IrcAdapter {
+    /// Create a new [`IrcAdapter`].
+    ///
+    /// `runtime_key` is the adapter's unique identifier within the messaging
+    /// manager (e.g. `"irc"` or `"irc:libera"`).
+    pub fn new(
+        runtime_key: impl Into<Arc<str>>,
+        settings: IrcConnectionSettings,
+        permissions: Arc<ArcSwap<IrcPermissions>>,
+    ) -> anyhow::Result<Self> {
+        if settings.server.is_empty() || settings.nickname.is_empty() {
+            return Err(anyhow::anyhow!("irc server and nickname are required"));
+        }
+        if settings.tls {
+            rustls::pki_types::ServerName::try_from(settings.server.as_str())
+                .with_context(|| format!("invalid irc server name: {}", settings.server))?;
+        }
+
+        Ok(Self {
+            runtime_key: runtime_key.into(),
+            settings: Arc::new(settings),
+            permissions,
+            shared: Arc::new(SharedState::default()),
+            history_lock: Mutex::new(()),
+            shutdown_tx: Arc::new(RwLock::new(None)),
+            connection_task: Arc::new(RwLock::new(None)),
+        })
+    }
+
+    /// Where replies to `message` go: the channel, or the sender's nick for
+    /// direct messages.
+    fn extract_target<'a>(&self, message: &'a InboundMessage) -> crate::Result<&'a str> {
+        Ok(message
+            .metadata
+            .get("irc_target")
+            .and_then(|value| value.as_str())
+            .context("missing irc_target metadata")?)
+    }
+
+    async fn send_raw(&self, line: String) -> anyhow::Result<()> {
+        let sender = self
+            .shared
+            .outgoing
+            .read()
+            .await
+            .clone()
+            .context("irc adapter is not connected")?;
+        sender
+            .send(line)
+            .await
+            .map_err(|_| anyhow::anyhow!("irc connection closed"))
+    }
+
+    /// Send `text` as one `PRIVMSG` per line, splitting long lines. The
+    /// first line carries a `+draft/reply` tag when `reply_to` is set and the
+    /// server accepts client tags.
+    async fn send_text(
+        &self,
+        target: &str,
+        text: &str,
+        reply_to: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let reply_tag = match reply_to {
+            Some(msgid) if self.supports_client_tags().await => {
+                Some(format!("@+draft/reply={} ", escape_tag_value(msgid)))
+            }
+            _ => None,
+        };
+
+        for (index, line) in split_message(text, max_payload_bytes(target))
+            .into_iter()
+            .enumerate()
+        {
+            let tags = reply_tag.as_deref().filter(|_| index == 0).unwrap_or("");
+            self.send_raw(format!("{tags}PRIVMSG {target} :{line}"))
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn supports_client_tags(&self) -> bool {
+        self.shared
+            .session
+            .read()
+            .await
+            .caps
+            .contains("message-tags")
+    }
+}
+
+impl Messaging for IrcAdapter {
+    fn name(&self) -> &str {
+        &self.runtime_key
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        let (inbound_tx, inbound_rx) = mpsc::channel(256);
+        let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
+        *self.shutdown_tx.write().await = Some(shutdown_tx);
+
+        let context = ConnectionContext {
+            runtime_key: self.runtime_key.clone(),
+            settings: self.settings.clone(),
+            permissions: self.permissions.clone(),
+            shared: self.shared.clone(),
+            inbound_tx,
+        };
+
+        let handle = tokio::spawn(async move {
+            let mut retry_delay = RECONNECT_BASE_DELAY;
+
+            loop {
+                let result = context.run_connection(&mut shutdown_rx).await;
+                let was_registered = context.shared.reset().await;
+
+                match result {
+                    Ok(ConnectionEnd::Shutdown) => {
+                        tracing::info!(adapter = %context.runtime_key, "irc connection closed for shutdown");
+                        return;
+                    }
+                    Ok(ConnectionEnd::InboundClosed) => {
+                        tracing::debug!("inbound channel closed");
+                        return;
+                    }
+                    Err(error) => {
+                        if was_registered {
+                            retry_delay = RECONNECT_BASE_DELAY;
+                        }
+                        tracing::error!(
+                            adapter = %context.runtime_key,
+                            error = format!("{error:#}"),
+                            delay_ms = retry_delay.as_millis(),
+                            "irc connection failed, reconnecting"
+                        );
+                    }
+                }
+
+                tokio::select! {
+                    _ = tokio::time::sleep(retry_delay) => {
+                        retry_delay = (retry_delay * 2).min(RECONNECT_MAX_DELAY);
+                    }
+                    _ = shutdown_rx.recv() => {
+                        tracing::info!(adapter = %context.runtime_key, "irc adapter shutting down during retry delay");
+                        return;
+                    }
+                }
+            }
+        });
+
+        *self.connection_task.write().await = Some(handle);
+
+        let stream = tokio_stream::wrappers::ReceiverStream::new(inbound_rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn respond(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        let target = self.extract_target(message)?;
+        let msgid = message
+            .metadata
+            .get("irc_msgid")
+            .and_then(|value| value.as_str());
+
+        match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::Ephemeral { text, .. }
+            | OutboundResponse::ScheduledMessage { text, .. } => {
+                self.send_text(target, &text, None).await?;
+            }
+            OutboundResponse::RichMessage { text, cards, .. } => {
+                let text = if text.trim().is_empty() {
+                    OutboundResponse::text_from_cards(&cards)
+                } else {
+                    text
+                };
+                self.send_text(target, &text, None).await?;
+            }
+            OutboundResponse::ThreadReply { text, .. } => {
+                // IRC has no threads; address the sender by nick in channels
+                // so the reply is visibly attached to their message.
+                let is_dm = message
+                    .metadata
+                    .get("irc_is_dm")
+                    .and_then(|value| value.as_bool())
+                    .unwrap_or(false);
+                let nick = message
+                    .metadata
+                    .get("irc_nick")
+                    .and_then(|value| value.as_str());
+                let text = match nick {
+                    Some(nick) if !is_dm => format!("{nick}: {text}"),
+                    _ => text,
+                };
+                self.send_text(target, &text, msgid).await?;
+            }
+            OutboundResponse::File {
+                filename, caption, ..
+            } => {
+                // IRC has no file transfer outside DCC; announce the file instead.
+                let text = match caption {
+                    Some(caption) => format!("[File: {filename}] {caption}"),
+                    None => format!("[File: {filename}]"),
+                };
+                self.send_text(target, &text, None).await?;
+            }
+            OutboundResponse::Reaction(emoji) => {
+                if let Some(msgid) = msgid
+                    && self.supports_client_tags().await
+                {
+                    self.send_raw(format!(
+                        "@+draft/react={};+draft/reply={} TAGMSG {target}",
+                        escape_tag_value(&emoji),
+                        escape_tag_value(msgid)
+                    ))
+                    .await?;
+                }
+            }
+            // IRC can't edit messages, so streaming is a no-op; the outbound
+            // routing sends the final text after StreamEnd.
+            OutboundResponse::StreamStart
+            | OutboundResponse::StreamChunk(_)
+            | OutboundResponse::StreamEnd => {}
+            OutboundResponse::RemoveReaction(_) | OutboundResponse::Status(_) => {}
+        }
+
+        Ok(())
+    }
+
+    async fn broadcast(&self, target: &str, response: OutboundResponse) -> crate::Result<()> {
+        ensure_supported_broadcast_response("irc", &response, |response| {
+            matches!(
+                response,
+                OutboundResponse::Text(_) | OutboundResponse::RichMessage { .. }
+            )
+        })?;
+
+        let target = target.strip_prefix("dm:").unwrap_or(target);
+        if target.is_empty() || target.contains(char::is_whitespace) {
+            return Err(mark_permanent_broadcast(anyhow::anyhow!(
+                "invalid irc broadcast target: {target:?}"
+            )));
+        }
+
+        let text = match response {
+            OutboundResponse::Text(text) => text,
+            OutboundResponse::RichMessage { text, cards, .. } if text.trim().is_empty() => {
+                OutboundResponse::text_from_cards(&cards)
+            }
+            OutboundResponse::RichMessage { text, .. } => text,
+            _ => unreachable!("unsupported variants are rejected above"),
+        };
+
+        self.send_text(target, &text, None)
+            .await
+            .map_err(mark_retryable_broadcast)
+    }
+
+    async fn fetch_history(
+        &self,
+        message: &InboundMessage,
+        limit: usize,
+    ) -> crate::Result<Vec<HistoryMessage>> {
+        let (supported, server_limit) = {
+            let session = self.shared.session.read().await;
+            (session.supports_chathistory(), session.chathistory_limit)
+        };
+        if !supported || limit == 0 {
+            return Ok(Vec::new());
+        }
+
+        let target = self.extract_target(message)?;
+        let capped_limit = limit
+            .min(HISTORY_MAX_LIMIT)
+            .min(server_limit.unwrap_or(usize::MAX));
+        // Anchor on the trigger's msgid when the server sent one; otherwise
+        // on its server-time timestamp.
+        let anchor = match message
+            .metadata
+            .get("irc_msgid")
+            .and_then(|value| value.as_str())
+        {
+            Some(msgid) => format!("msgid={msgid}"),
+            None => format!(
+                "timestamp={}",
+                message
+                    .timestamp
+                    .to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+            ),
+        };
+
+        let _guard = self.history_lock.lock().await;
+        let (done_tx, done_rx) = oneshot::channel();
+        *self.shared.pending_history.lock().await = Some(PendingHistory {
+            target: target.to_string(),
+            batch: None,
+            messages: Vec::new(),
+            done: done_tx,
+        });
+
+        if let Err(error) = self
+            .send_raw(format!(
+                "CHATHISTORY BEFORE {target} {anchor} {capped_limit}"
+            ))
+            .await
+        {
+            self.shared.pending_history.lock().await.take();
+            return Err(error.into());
+        }
+
+        let result = tokio::time::timeout(HISTORY_TIMEOUT, done_rx).await;
+        self.shared.pending_history.lock().await.take();
+
+        let mut history = match result {
+            Ok(Ok(history)) => history,
+            Ok(Err(_)) => {
+                tracing::debug!(adapter = %self.runtime_key, %target, "irc chathistory request was rejected");
+                Vec::new()
+            }
+            Err(_) => {
+                tracing::warn!(adapter = %self.runtime_key, %target, "timed out waiting for irc chathistory batch");
+                Vec::new()
+            }
+        };
+
+        // Batches are oldest first; keep the newest `limit`.
+        let excess = history.len().saturating_sub(limit);
+        history.drain(..excess);
+        Ok(history)
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        if self.shared.session.read().await.registered {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("irc adapter is not connected").into())
+        }
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        if let Some(tx) = self.shutdown_tx.write().await.take() {
+            tx.send(()).await.ok();
+        }
+
+        // Give the connection a moment to send QUIT before tearing it down.
+        if let Some(mut handle) = self.connection_task.write().await.take()
+            && tokio::time::timeout(SHUTDOWN_GRACE, &mut handle)
+                .await
+                .is_err()
+        {
+            handle.abort();
+        }
+
+        tracing::info!(adapter = %self.runtime_key, "irc adapter shut down");
+        Ok(())
+    }
+}
+
+enum ConnectionEnd {
+    Shutdown,
+    InboundClosed,
+}
+
+trait IrcTransport: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> IrcTransport for T {}
+
+type WriterTask = tokio::task::JoinHandle<anyhow::Result<()>>;
+
+/// Everything the background connection task needs, detached from the adapter.
+struct ConnectionContext {
+    runtime_key: Arc<str>,
+    settings: Arc<IrcConnectionSettings>,
+    permissions: Arc<ArcSwap<IrcPermissions>>,
+    shared: Arc<SharedState>,
+    inbound_tx: mpsc::Sender<InboundMessage>,
+}
+
+/// Registration progress for a single connection.
+struct Registration {
+    nick: String,
+    available_caps: HashSet<String>,
+    cap_negotiation_ended: bool,
+    sasl_authenticated: bool,
+    /// Published to [`SharedState::outgoing`] once the server welcomes us.
+    outgoing_tx: Option<mpsc::Sender<String>>,
+}
+
+impl ConnectionContext {
+    async fn run_connection(
+        &self,
+        shutdown_rx: &mut mpsc::Receiver<()>,
+    ) -> anyhow::Result<ConnectionEnd> {
+        let stream = connect(&self.settings).await?;
+        let (read_half, write_half) = tokio::io::split(stream);
+
+        // Protocol replies (PONG, CAP, AUTHENTICATE) skip the flood limiter so
+        // they are never stuck behind a long reply.
+        let (priority_tx, priority_rx) = mpsc::channel(64);
+        let (outgoing_tx, outgoing_rx) = mpsc::channel(256);
+        let mut writer = tokio::spawn(run_writer(write_half, priority_rx, outgoing_rx));
+
+        let settings = &self.settings;
+        let nickname = settings.nickname.as_str();
+        let username = settings
+            .username
+            .as_deref()
+            .filter(|username| !username.is_empty())
+            .unwrap_or(nickname);
+        let realname = settings
+            .realname
+            .as_deref()
+            .filter(|realname| !realname.is_empty())
+            .unwrap_or(DEFAULT_REALNAME);
+
+        let mut handshake = vec!["CAP LS 302".to_string()];
+        if let Some(password) = settings
+            .server_password
+            .as_deref()
+            .filter(|password| !password.is_empty())
+        {
+            handshake.push(format!("PASS :{password}"));
+        }
+        handshake.push(format!("NICK {nickname}"));
+        handshake.push(format!("USER {username} 0 * :{realname}"));
+        for line in handshake {
+            send_priority(&priority_tx, line).await?;
+        }
+
+        let mut registration = Registration {
+            nick: nickname.to_string(),
+            available_caps: HashSet::new(),
+            cap_negotiation_ended: false,
+            sasl_authenticated: false,
+            outgoing_tx: Some(outgoing_tx),
+        };
+
+        let result = self
+            .read_loop(
+                read_half,
+                &priority_tx,
+                &mut writer,
+                &mut registration,
+                shutdown_rx,
+            )
+            .await;
+
+        if matches!(result, Ok(ConnectionEnd::Shutdown))
+            && priority_tx.send("QUIT :Shutting down".into()).await.is_ok()
+        {
+            drop(priority_tx);
+            let _ = tokio::time::timeout(SHUTDOWN_GRACE, &mut writer).await;
+        }
+        writer.abort();
+
+        result
+    }
+
+    async fn read_loop(
+        &self,
+        read_half: tokio::io::ReadHalf<Box<dyn IrcTransport>>,
+        priority_tx: &mpsc::Sender<String>,
+        writer: &mut WriterTask,
+        registration: &mut Registration,
+        shutdown_rx: &mut mpsc::Receiver<()>,
+    ) -> anyhow::Result<ConnectionEnd> {
+        let mut reader = BufReader::new(read_half);
+        let mut buffer = Vec::new();
+        let mut awaiting_pong = false;
+
+        loop {
+            // `read_until` keeps partial lines in `buffer` if the timeout
+            // fires mid-line, so the buffer is only cleared after a full line.
+            let read = tokio::select! {
+                _ = shutdown_rx.recv() => return Ok(ConnectionEnd::Shutdown),
+                result = &mut *writer => {
+                    return Err(match result {
+                        Ok(Ok(())) => anyhow::anyhow!("irc writer stopped"),
+                        Ok(Err(error)) => error.context("failed to write to irc server"),
+                        Err(error) => anyhow::anyhow!("irc writer task failed: {error}"),
+                    });
+                }
+                read = tokio::time::timeout(READ_IDLE_TIMEOUT, reader.read_until(b'\n', &mut buffer)) => read,
+            };
+
+            match read {
+                Err(_) if awaiting_pong => {
+                    return Err(anyhow::anyhow!("irc server stopped responding"));
+                }
+                Err(_) => {
+                    awaiting_pong = true;
+                    send_priority(priority_tx, "PING :spacebot".into()).await?;
+                    continue;
+                }
+                Ok(Err(error)) => return Err(error).context("failed to read from irc server"),
+                Ok(Ok(0)) => return Err(anyhow::anyhow!("irc server closed the connection")),
+                Ok(Ok(_)) => {}
+            }
+            awaiting_pong = false;
+
+            let line = String::from_utf8_lossy(&buffer).into_owned();
+            buffer.clear();
+            let Some(message) = IrcMessage::parse(&line) else {
+                continue;
+            };
+
+            if !self
+                .handle_message(message, registration, priority_tx)
+                .await?
+            {
+                return Ok(ConnectionEnd::InboundClosed);
+            }
+        }
+    }
+
+    /// Handle one server line. Returns `false` once the inbound stream is gone.
+    async fn handle_message(
+        &self,
+        message: IrcMessage,
+        registration: &mut Registration,
+        priority_tx: &mpsc::Sender<String>,
+    ) -> anyhow::Result<bool> {
+        match message.command.as_str() {
+            "PING" => {
+                let token = message.param(0).unwrap_or_default();
+                send_priority(priority_tx, format!("PONG :{token}")).await?;
+            }
+            "CAP" => self.handle_cap(&message, registration, priority_tx).await?,
+            "AUTHENTICATE" if message.param(0) == Some("+") => {
+                if let Some((account, password)) = self.settings.sasl_credentials() {
+                    for chunk in sasl_plain_chunks(account, password) {
+                        send_priority(priority_tx, format!("AUTHENTICATE {chunk}")).await?;
+                    }
+                }
+            }
+            // RPL_SASLSUCCESS
+            "903" => {
+                registration.sasl_authenticated = true;
+                end_cap_negotiation(registration, priority_tx).await?;
+            }
+            // ERR_NICKLOCKED, ERR_SASLFAIL, ERR_SASLTOOLONG, ERR_SASLABORTED
+            "902" | "904" | "905" | "906" => {
+                return Err(anyhow::anyhow!(
+                    "irc SASL authentication failed: {}",
+                    message
+                        .params
+                        .last()
+                        .map(String::as_str)
+                        .unwrap_or_default()
+                ));
+            }
+            // RPL_WELCOME
+            "001" => {
+                self.handle_welcome(&message, registration, priority_tx)
+                    .await?
+            }
+            // RPL_ISUPPORT
+            "005" => {
+                for token in message.params.iter().skip(1) {
+                    if let Some(value) = token.strip_prefix("CHATHISTORY=") {
+                        self.shared.session.write().await.chathistory_limit =
+                            value.parse().ok().filter(|limit| *limit > 0);
+                    }
+                }
+            }
+            // ERR_ERRONEUSNICKNAME
+            "432" if registration.outgoing_tx.is_some() => {
+                return Err(anyhow::anyhow!(
+                    "irc server rejected nickname {}",
+                    registration.nick
+                ));
+            }
+            // ERR_NICKNAMEINUSE, ERR_UNAVAILRESOURCE
+            "433" | "437" if registration.outgoing_tx.is_some() => {
+                registration.nick.push('_');
+                tracing::warn!(adapter = %self.runtime_key, nick = %registration.nick, "irc nickname in use, retrying");
+                send_priority(priority_tx, format!("NICK {}", registration.nick)).await?;
+            }
+            // Channel join failures (full, invite-only, banned, bad key, needs account).
+            "471" | "473" | "474" | "475" | "477" | "403" | "405" => {
+                tracing::warn!(
+                    adapter = %self.runtime_key,
+                    channel = message.param(1).unwrap_or_default(),
+                    reason = message.params.last().map(String::as_str).unwrap_or_default(),
+                    "failed to join irc channel"
+                );
+            }
+            "NICK" => {
+                let mut session = self.shared.session.write().await;
+                if message
+                    .source_nick()
+                    .is_some_and(|nick| nick.eq_ignore_ascii_case(&session.nick))
+                    && let Some(new_nick) = message.param(0)
+                {
+                    session.nick = new_nick.to_string();
+                }
+            }
+            "ERROR" => {
+                return Err(anyhow::anyhow!(
+                    "irc server closed the link: {}",
+                    message.param(0).unwrap_or_default()
+                ));
+            }
+            "BATCH" => self.handle_batch(&message).await,
+            "FAIL" if message.param(0) == Some("CHATHISTORY") => {
+                // Dropping the pending request resolves `fetch_history` empty.
+                self.shared.pending_history.lock().await.take();
+            }
+            "PRIVMSG" => {
+                if let Some(batch) = message.tags.get("batch")
+                    && self.record_history(batch, &message).await
+                {
+                    return Ok(true);
+                }
+
+                let bot_nick = self.shared.session.read().await.nick.clone();
+                let permissions = self.permissions.load();
+                let context = MessageBuildContext {
+                    runtime_key: &self.runtime_key,
+                    bot_nick: &bot_nick,
+                    permissions: &permissions,
+                };
+                if let Some(inbound) = build_inbound_message(&message, &context)
+                    && self.inbound_tx.send(inbound).await.is_err()
+                {
+                    return Ok(false);
+                }
+            }
+            _ => {}
+        }
+
+        Ok(true)
+    }
+
+    async fn handle_cap(
+        &self,
+        message: &IrcMessage,
+        registration: &mut Registration,
+        priority_tx: &mpsc::Sender<String>,
+    ) -> anyhow::Result<()> {
+        let subcommand = message.param(1).unwrap_or_default().to_ascii_uppercase();
+        let caps = message
+            .params
+            .last()
+            .map(String::as_str)
+            .unwrap_or_default();
+        let wants_sasl = self.settings.sasl_credentials().is_some();
+
+        match subcommand.as_str() {
+            "LS" => {
+                registration
+                    .available_caps
+                    .extend(caps.split_whitespace().map(|cap| {
+                        cap.split_once('=')
+                            .map_or(cap, |(name, _)| name)
+                            .to_string()
+                    }));
+                // `CAP * LS * :...` marks a continuation line.
+                if message.param(2) == Some("*") {
+                    return Ok(());
+                }
+
+                if wants_sasl && !registration.available_caps.contains("sasl") {
+                    return Err(anyhow::anyhow!(
+                        "irc server does not offer SASL; refusing to connect without authentication"
+                    ));
+                }
+
+                let requested: Vec<&str> = REQUESTED_CAPS
+                    .iter()
+                    .copied()
+                    .filter(|cap| registration.available_caps.contains(*cap))
+                    .filter(|cap| *cap != "sasl" || wants_sasl)
+                    .collect();
+                if requested.is_empty() {
+                    end_cap_negotiation(registration, priority_tx).await?;
+                } else {
+                    send_priority(priority_tx, format!("CAP REQ :{}", requested.join(" "))).await?;
+                }
+            }
+            "ACK" => {
+                let acked: Vec<&str> = caps
+                    .split_whitespace()
+                    .filter(|cap| !cap.starts_with('-'))
+                    .collect();
+                self.shared
+                    .session
+                    .write()
+                    .await
+                    .caps
+                    .extend(acked.iter().map(|cap| cap.to_string()));
+
+                if wants_sasl && acked.contains(&"sasl") {
+                    send_priority(priority_tx, "AUTHENTICATE PLAIN".into()).await?;
+                } else {
+                    end_cap_negotiation(registration, priority_tx).await?;
+                }
+            }
+            "NAK" => {
+                if wants_sasl {
+                    return Err(anyhow::anyhow!(
+                        "irc server rejected capability request: {caps}"
+                    ));
+                }
+                end_cap_negotiation(registration, priority_tx).await?;
+            }
+            "DEL" => {
+                let mut session = self.shared.session.write().await;
+                for cap in caps.split_whitespace() {
+                    session.caps.remove(cap);
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    async fn handle_welcome(
+        &self,
+        message: &IrcMessage,
+        registration: &mut Registration,
+        priority_tx: &mpsc::Sender<String>,
+    ) -> anyhow::Result<()> {
+        // Servers without CAP support register us without ever reaching SASL.
+        if self.settings.sasl_credentials().is_some() && !registration.sasl_authenticated {
+            return Err(anyhow::anyhow!(
+                "irc server registered the connection without SASL authentication"
+            ));
+        }
+
+        let nick = message.param(0).unwrap_or(&registration.nick).to_string();
+        {
+            let mut session = self.shared.session.write().await;
+            session.registered = true;
+            session.nick = nick.clone();
+        }
+        if let Some(outgoing_tx) = registration.outgoing_tx.take() {
+            *self.shared.outgoing.write().await = Some(outgoing_tx);
+        }
+
+        for line in join_lines(&self.settings.channels) {
+            send_priority(priority_tx, line).await?;
+        }
+
+        tracing::info!(
+            adapter = %self.runtime_key,
+            server = %self.settings.server,
+            nick = %nick,
+            "irc adapter connected"
+        );
+        Ok(())
+    }
+
+    async fn handle_batch(&self, message: &IrcMessage) {
+        let Some(reference) = message.param(0) else {
+            return;
+        };
+        let mut pending = self.shared.pending_history.lock().await;
+
+        if let Some(id) = reference.strip_prefix('+') {
+            let is_history = message
+                .param(1)
+                .is_some_and(|kind| kind == "chathistory" || kind == "draft/chathistory");
+            if let Some(history) = pending.as_mut()
+                && history.batch.is_none()
+                && is_history
+                && message
+                    .param(2)
+                    .is_some_and(|target| target.eq_ignore_ascii_case(&history.target))
+            {
+                history.batch = Some(id.to_string());
+            }
+        } else if let Some(id) = reference.strip_prefix('-')
+            && pending
+                .as_ref()
+                .is_some_and(|history| history.batch.as_deref() == Some(id))
+            && let Some(history) = pending.take()
+        {
+            let _ = history.done.send(history.messages);
+        }
+    }
+
+    /// Add a line to the pending history batch. Returns `false` if the line
+    /// belongs to some other batch.
+    async fn record_history(&self, batch: &str, message: &IrcMessage) -> bool {
+        let mut pending = self.shared.pending_history.lock().await;
+        let Some(history) = pending
+            .as_mut()
+            .filter(|history| history.batch.as_deref() == Some(batch))
+        else {
+            return false;
+        };
+
+        let bot_nick = self.shared.session.read().await.nick.clone();
+        if let Some(entry) = history_entry(message, &bot_nick) {
+            history.messages.push(entry);
+        }
+        true
+    }
+}
+
+async fn connect(settings: &IrcConnectionSettings) -> anyhow::Result<Box<dyn IrcTransport>> {
+    let address = (settings.server.as_str(), settings.port);
+    let tcp = tokio::time::timeout(CONNECT_TIMEOUT, TcpStream::connect(address))
+        .await
+        .context("timed out connecting to irc server")?
+        .with_context(|| {
+            format!(
+                "failed to connect to irc server {}:{}",
+                settings.server, settings.port
+            )
+        })?;
+    tcp.set_nodelay(true).ok();
+
+    if !settings.tls {
+        return Ok(Box::new(tcp));
+    }
+
+    let mut roots = rustls::RootCertStore::empty();
+    roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+    let config = rustls::ClientConfig::builder_with_provider(Arc::new(
+        rustls::crypto::ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()
+    .context("failed to configure TLS")?
+    .with_root_certificates(roots)
+    .with_no_client_auth();
+
+    let server_name = rustls::pki_types::ServerName::try_from(settings.server.clone())
+        .with_context(|| format!("invalid irc server name: {}", settings.server))?;
+    let connector = tokio_rustls::TlsConnector::from(Arc::new(config));
+    let stream = tokio::time::timeout(CONNECT_TIMEOUT, connector.connect(server_name, tcp))
+        .await
+        .context("timed out during irc TLS handshake")?
+        .context("irc TLS handshake failed")?;
+
+    Ok(Box::new(stream))
+}
+
+/// Write queued lines to the server. Priority lines go out immediately;
+/// regular lines are limited to [`FLOOD_BURST`] per [`FLOOD_WINDOW`].
+async fn run_writer(
+    mut writer: tokio::io::WriteHalf<Box<dyn IrcTransport>>,
+    mut priority_rx: mpsc::Receiver<String>,
+    mut outgoing_rx: mpsc::Receiver<String>,
+) -> anyhow::Result<()> {
+    let mut recent_sends: VecDeque<Instant> = VecDeque::with_capacity(FLOOD_BURST);
+
+    loop {
+        let line = tokio::select! {
+            biased;
+            line = priority_rx.recv() => match line {
+                Some(line) => line,
+                None => return Ok(()),
+            },
+            line = outgoing_rx.recv() => {
+                let Some(line) = line else {
+                    return Ok(());
+                };
+                if recent_sends.len() >= FLOOD_BURST
+                    && let Some(oldest) = recent_sends.pop_front()
+                {
+                    tokio::time::sleep_until(oldest + FLOOD_WINDOW).await;
+                }
+                recent_sends.push_back(Instant::now());
+                line
+            }
+        };
+
+        writer.write_all(line.as_bytes()).await?;
+        writer.write_all(b"\r\n").await?;
+        writer.flush().await?;
+    }
+}
+
+async fn send_priority(priority_tx: &mpsc::Sender<String>, line: String) -> anyhow::Result<()> {
+    priority_tx
+        .send(line)
+        .await
+        .map_err(|_| anyhow::anyhow!("irc writer closed"))
+}
+
+async fn end_cap_negotiation(
+    registration: &mut Registration,
+    priority_tx: &mpsc::Sender<String>,
+) -> anyhow::Result<()> {
+    if !registration.cap_negotiation_ended {
+        registration.cap_negotiation_ended = true;
+        send_priority(priority_tx, "CAP END".into()).await?;
+    }
+    Ok(())
+}
+
+/// A parsed protocol line: `[@tags] [:prefix] COMMAND params... [:trailing]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct IrcMessage {
+    tags: HashMap<String, String>,
+    prefix: Option<String>,
+    command: String,
+    params: Vec<String>,
+}
+
+impl IrcMessage {
+    fn parse(line: &str) -> Option<Self> {
+        let mut rest = line.trim_end_matches(['\r', '\n']);
+
+        let mut tags = HashMap::new();
+        if let Some(stripped) = rest.strip_prefix('@') {
+            let (raw_tags, remainder) = stripped.split_once(' ')?;
+            for tag in raw_tags.split(';').filter(|tag| !tag.is_empty()) {
+                let (key, value) = tag.split_once('=').unwrap_or((tag, ""));
+                tags.insert(key.to_string(), unescape_tag_value(value));
+            }
+            rest = remainder.trim_start_matches(' ');
+        }
+
+        let mut prefix = None;
+        if let Some(stripped) = rest.strip_prefix(':') {
+            let (source, remainder) = stripped.split_once(' ')?;
+            prefix = Some(source.to_string());
+            rest = remainder.trim_start_matches(' ');
+        }
+
+        let (command, mut rest) = rest.split_once(' ').unwrap_or((rest, ""));
+        if command.is_empty() {
+            return None;
+        }
+
+        let mut params = Vec::new();
+        loop {
+            rest = rest.trim_start_matches(' ');
+            if rest.is_empty() {
+                break;
+            }
+            if let Some(trailing) = rest.strip_prefix(':') {
+                params.push(trailing.to_string());
+                break;
+            }
+            match rest.split_once(' ') {
+                Some((param, remainder)) => {
+                    params.push(param.to_string());
+                    rest = remainder;
+                }
+                None => {
+                    params.push(rest.to_string());
+                    break;
+                }
+            }
+        }
+
+        Some(Self {
+            tags,
+            prefix,
+            command: command.to_ascii_uppercase(),
+            params,
+        })
+    }
+
+    fn param(&self, index: usize) -> Option<&str> {
+        self.params.get(index).map(String::as_str)
+    }
+
+    /// Nick part of a `nick!user@host` prefix.
+    fn source_nick(&self) -> Option<&str> {
+        let prefix = self.prefix.as_deref()?;
+        prefix
+            .split(['!', '@'])
+            .next()
+            .filter(|nick| !nick.is_empty())
+    }
+}
+
+fn unescape_tag_value(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(character) = chars.next() {
+        if character != '\\' {
+            unescaped.push(character);
+            continue;
+        }
+        match chars.next() {
+            Some(':') => unescaped.push(';'),
+            Some('s') => unescaped.push(' '),
+            Some('r') => unescaped.push('\r'),
+            Some('n') => unescaped.push('\n'),
+            Some(other) => unescaped.push(other),
+            None => {}
+        }
+    }
+    unescaped
+}
+
+fn escape_tag_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for character in value.chars() {
+        match character {
+            ';' => escaped.push_str("\\:"),
+            ' ' => escaped.push_str("\\s"),
+            '\\' => escaped.push_str("\\\\"),
+            '\r' => escaped.push_str("\\r"),
+            '\n' => escaped.push_str("\\n"),
+            other => escaped.push(other),
+        }
+    }
+    escaped
+}
+
+struct MessageBuildContext<'a> {
+    runtime_key: &'a str,
+    bot_nick: &'a str,
+    permissions: &'a IrcPermissions,
+}
+
+/// Turn a `PRIVMSG` into an [`InboundMessage`], applying channel and DM
+/// permissions. Returns `None` for messages the agent should not see.
+fn build_inbound_message(
+    message: &IrcMessage,
+    context: &MessageBuildContext<'_>,
+) -> Option<InboundMessage> {
+    let nick = message.source_nick()?;
+    if nick.eq_ignore_ascii_case(context.bot_nick) {
+        return None;
+    }
+
+    let target = message.param(0)?;
+    let text = message_text(nick, message.param(1)?)?;
+
+    let is_channel = target.starts_with(['#', '&']);
+    let is_dm = !is_channel && target.eq_ignore_ascii_case(context.bot_nick);
+    if !is_channel && !is_dm {
+        // Status-prefixed (`@#channel`) and other targets aren't conversations.
+        return None;
+    }
+
+    let account = message
+        .tags
+        .get("account")
+        .map(String::as_str)
+        .filter(|account| !account.is_empty() && *account != "*");
+
+    if is_dm {
+        if !context.permissions.allows_dm(nick, account) {
+            tracing::debug!(%nick, "irc direct message from user not in dm_allowed_users");
+            return None;
+        }
+    } else if !context.permissions.allows_channel(target) {
+        return None;
+    }
+
+    let reply_target = if is_dm { nick } else { target };
+    let base_conversation_id = if is_dm {
+        format!("irc:dm:{}", nick.to_ascii_lowercase())
+    } else {
+        format!("irc:{}", target.to_ascii_lowercase())
+    };
+    let conversation_id =
+        apply_runtime_adapter_to_conversation_id(context.runtime_key, base_conversation_id);
+
+    let msgid = message.tags.get("msgid").filter(|msgid| !msgid.is_empty());
+    let id = msgid
+        .cloned()
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    let mentions_bot = is_dm || mentions_nick(&text, context.bot_nick);
+
+    let mut metadata = HashMap::new();
+    metadata.insert(
+        crate::metadata_keys::MESSAGE_ID.into(),
+        serde_json::json!(id),
+    );
+    if let Some(msgid) = msgid {
+        metadata.insert("irc_msgid".into(), serde_json::json!(msgid));
+    }
+    metadata.insert("irc_target".into(), serde_json::json!(reply_target));
+    metadata.insert("irc_nick".into(), serde_json::json!(nick));
+    metadata.insert("irc_is_dm".into(), serde_json::json!(is_dm));
+    if let Some(account) = account {
+        metadata.insert("irc_account".into(), serde_json::json!(account));
+    }
+    if is_channel {
+        metadata.insert("irc_channel".into(), serde_json::json!(target));
+        metadata.insert(
+            crate::metadata_keys::CHANNEL_NAME.into(),
+            serde_json::json!(target),
+        );
+    }
+    metadata.insert("sender_display_name".into(), serde_json::json!(nick));
+    metadata.insert(
+        "irc_mentions_or_replies_to_bot".into(),
+        serde_json::json!(mentions_bot),
+    );
+
+    Some(InboundMessage {
+        id,
+        source: "irc".into(),
+        adapter: Some(context.runtime_key.to_string()),
+        conversation_id,
+        sender_id: nick.to_string(),
+        agent_id: None,
+        content: MessageContent::Text(text),
+        timestamp: server_time(message).unwrap_or_else(chrono::Utc::now),
+        metadata,
+        formatted_author: Some(nick.to_string()),
+    })
+}
+
+fn history_entry(message: &IrcMessage, bot_nick: &str) -> Option<HistoryMessage> {
+    let nick = message.source_nick()?;
+    let content = message_text(nick, message.param(1)?)?;
+    let is_bot = nick.eq_ignore_ascii_case(bot_nick);
+    Some(HistoryMessage {
+        author: if is_bot {
+            "bot".to_string()
+        } else {
+            nick.to_string()
+        },
+        content,
+        is_bot,
+        timestamp: server_time(message),
+    })
+}
+
+fn server_time(message: &IrcMessage) -> Option<chrono::DateTime<chrono::Utc>> {
+    let time = message.tags.get("time")?;
+    chrono::DateTime::parse_from_rfc3339(time)
+        .ok()
+        .map(|time| time.with_timezone(&chrono::Utc))
+}
+
+/// Readable text of a `PRIVMSG` body. CTCP `ACTION` becomes `* nick text`;
+/// other CTCP requests are dropped.
+fn message_text(nick: &str, body: &str) -> Option<String> {
+    let text = match body.strip_prefix('\u{1}') {
+        Some(ctcp) => {
+            let ctcp = ctcp.strip_suffix('\u{1}').unwrap_or(ctcp);
+            let (command, argument) = ctcp.split_once(' ').unwrap_or((ctcp, ""));
+            if !command.eq_ignore_ascii_case("ACTION") {
+                return None;
+            }
+            format!("* {nick} {}", strip_formatting(argument).trim())
+        }
+        None => strip_formatting(body).trim().to_string(),
+    };
+    (!text.is_empty()).then_some(text)
+}
+
+/// Remove mIRC formatting codes (bold, colors, italics, ...).
+fn strip_formatting(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(character) = chars.next() {
+        match character {
+            '\u{2}' | '\u{f}' | '\u{11}' | '\u{16}' | '\u{1d}' | '\u{1e}' | '\u{1f}' => {}
+            // ^C<fg>[,<bg>] with up to two digits each.
+            '\u{3}' => {
+                if skip_color_digits(&mut chars) > 0 && chars.peek() == Some(&',') {
+                    let mut lookahead = chars.clone();
+                    lookahead.next();
+                    if lookahead.peek().is_some_and(char::is_ascii_digit) {
+                        chars.next();
+                        skip_color_digits(&mut chars);
+                    }
+                }
+            }
+            // ^D<rrggbb>[,<rrggbb>]
+            '\u{4}' => {
+                for _ in 0..6 {
+                    if chars.peek().is_some_and(char::is_ascii_hexdigit) {
+                        chars.next();
+                    }
+                }
+            }
+            other => stripped.push(other),
+        }
+    }
+
+    stripped
+}
+
+/// Skip up to two color-code digits, returning how many were skipped.
+fn skip_color_digits(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> usize {
+    let mut count = 0;
+    while count < 2 && chars.peek().is_some_and(char::is_ascii_digit) {
+        chars.next();
+        count += 1;
+    }
+    count
+}
+
+/// Whether `text` addresses `nick` as a whole word (case-insensitive).
+fn mentions_nick(text: &str, nick: &str) -> bool {
+    if nick.is_empty() {
+        return false;
+    }
+    let text = text.to_ascii_lowercase();
+    let nick = nick.to_ascii_lowercase();
+    text.match_indices(&nick).any(|(start, _)| {
+        let end = start + nick.len();
+        let before_ok = text[..start]
+            .chars()
+            .next_back()
+            .is_none_or(|character| !is_nick_char(character));
+        let after_ok = text[end..]
+            .chars()
+            .next()
+            .is_none_or(|character| !is_nick_char(character));
+        before_ok && after_ok
+    })
+}
+
+fn is_nick_char(character: char) -> bool {
+    character.is_ascii_alphanumeric() || "[]\\`_^{|}-".contains(character)
+}
+
+/// Bytes available for message text in `PRIVMSG <target> :<text>\r\n`.
+fn max_payload_bytes(target: &str) -> usize {
+    let overhead = "PRIVMSG  :\r\n".len() + target.len() + HOSTMASK_RESERVE;
+    MAX_LINE_BYTES.saturating_sub(overhead).max(64)
+}
+
+/// Split text into IRC lines: one per input line, with long lines broken at
+/// spaces (or char boundaries) to fit `max_bytes`. Blank lines are dropped.
+fn split_message(text: &str, max_bytes: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+
+    for line in text.lines() {
+        let line = line.replace(['\r', '\0'], " ");
+        let mut remaining = line.trim_end();
+        if remaining.trim().is_empty() {
+            continue;
+        }
+
+        while remaining.len() > max_bytes {
+            let search_end = remaining.floor_char_boundary(max_bytes);
+            let end = remaining[..search_end]
+                .rfind(' ')
+                .filter(|&position| position > 0)
+                .unwrap_or(search_end);
+            chunks.push(remaining[..end].to_string());
+            remaining = remaining[end..].trim_start();
+        }
+        if !remaining.is_empty() {
+            chunks.push(remaining.to_string());
+        }
+    }
+
+    chunks
+}
+
+/// `JOIN` lines for the configured channels, batched to stay under the line limit.
+fn join_lines(channels: &[String]) -> Vec<String> {
+    let budget = MAX_LINE_BYTES - "JOIN \r\n".len();
+    let mut lines = Vec::new();
+    let mut current = String::new();
+
+    for channel in channels.iter().filter(|channel| !channel.is_empty()) {
+        if !current.is_empty() && current.len() + 1 + channel.len() > budget {
+            lines.push(format!("JOIN {current}"));
+            current.clear();
+        }
+        if !current.is_empty() {
+            current.push(',');
+        }
+        current.push_str(channel);
+    }
+    if !current.is_empty() {
+        lines.push(format!("JOIN {current}"));
+    }
+
+    lines
+}
+
+/// Base64 SASL PLAIN payload split into `AUTHENTICATE` chunks. A payload that
+/// is an exact multiple of the chunk size is terminated with `+`.
+fn sasl_plain_chunks(account: &str, password: &str) -> Vec<String> {
+    let payload = base64::engine::general_purpose::STANDARD
+        .encode(format!("{account}\0{account}\0{password}"));
+    let mut chunks: Vec<String> = payload
+        .as_bytes()
+        .chunks(SASL_CHUNK_BYTES)
+        .map(|chunk| String::from_utf8_lossy(chunk).into_owned())
+        .collect();
+    if payload.len() % SASL_CHUNK_BYTES == 0 {
+        chunks.push("+".to_string());
+    }
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt as _;
+    use tokio::net::TcpListener;
+
+    // --- helpers ---
+
+    const BOT: &str = "spacebot";
+
+    fn privmsg(line: &str) -> IrcMessage {
+        IrcMessage::parse(line).unwrap()
+    }
+
+    fn build(line: &str, permissions: &IrcPermissions) -> Option<InboundMessage> {
+        let context = MessageBuildContext {
+            runtime_key: "irc",
+            bot_nick: BOT,
+            permissions,
+        };
+        build_inbound_message(&privmsg(line), &context)
+    }
+
+    fn meta_str<'a>(message: &'a InboundMessage, key: &str) -> Option<&'a str> {
+        message.metadata.get(key).and_then(|v| v.as_str())
+    }
+
+    fn meta_bool(message: &InboundMessage, key: &str) -> Option<bool> {
+        message.metadata.get(key).and_then(|v| v.as_bool())
+    }
+
+    // --- parsing ---
+
+    #[test]
+    fn parses_tags_prefix_and_trailing() {
+        let message = IrcMessage::parse(
+            "@msgid=abc;time=2024-05-01T12:00:00.000Z;+example=a\\sb\\:c :alice!a@host PRIVMSG #Rust :hello :there\r\n",
+        )
+        .unwrap();
+
+        assert_eq!(message.command, "PRIVMSG");
+        assert_eq!(message.source_nick(), Some("alice"));
+        assert_eq!(message.params, vec!["#Rust", "hello :there"]);
+        assert_eq!(message.tags["msgid"], "abc");
+        assert_eq!(message.tags["+example"], "a b;c");
+
+        let ping = IrcMessage::parse("PING :irc.example.org").unwrap();
+        assert_eq!(ping.prefix, None);
+        assert_eq!(ping.param(0), Some("irc.example.org"));
+
+        let isupport =
+            IrcMessage::parse(":srv 005 spacebot CHATHISTORY=50 NETWORK=Test :are supported")
+                .unwrap();
+        assert_eq!(isupport.params.len(), 4);
+
+        assert!(IrcMessage::parse("").is_none());
+        assert_eq!(escape_tag_value("a b;c\\"), "a\\sb\\:c\\\\");
+    }
+
+    // --- build_inbound_message ---
+
+    #[test]
+    fn builds_channel_message_with_metadata() {
+        let message = build(
+            "@msgid=abc;time=2024-05-01T12:00:00.000Z;account=alice :alice!a@host PRIVMSG #Rust :spacebot: status?",
+            &IrcPermissions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(message.id, "abc");
+        assert_eq!(message.source, "irc");
+        assert_eq!(message.conversation_id, "irc:#rust");
+        assert_eq!(message.sender_id, "alice");
+        assert_eq!(message.timestamp.to_rfc3339(), "2024-05-01T12:00:00+00:00");
+        assert_eq!(meta_str(&message, "irc_target"), Some("#Rust"));
+        assert_eq!(meta_str(&message, "irc_channel"), Some("#Rust"));
+        assert_eq!(meta_str(&message, "irc_msgid"), Some("abc"));
+        assert_eq!(meta_str(&message, "irc_account"), Some("alice"));
+        assert_eq!(meta_bool(&message, "irc_is_dm"), Some(false));
+        assert_eq!(
+            meta_bool(&message, "irc_mentions_or_replies_to_bot"),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn named_instance_prefixes_conversation_id() {
+        let context = MessageBuildContext {
+            runtime_key: "irc:libera",
+            bot_nick: BOT,
+            permissions: &IrcPermissions::default(),
+        };
+        let message =
+            build_inbound_message(&privmsg(":alice!a@host PRIVMSG #rust :hi"), &context).unwrap();
+
+        assert_eq!(message.conversation_id, "irc:libera:#rust");
+        assert_eq!(message.adapter.as_deref(), Some("irc:libera"));
+        // Without a server msgid the ID is generated and not exposed as irc_msgid.
+        assert!(meta_str(&message, "irc_msgid").is_none());
+        assert_eq!(
+            meta_bool(&message, "irc_mentions_or_replies_to_bot"),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn direct_messages_fail_closed_and_prefer_account() {
+        let line = "@account=alice-account :alice!a@host PRIVMSG spacebot :hi";
+        assert!(build(line, &IrcPermissions::default()).is_none());
+
+        let by_nick = IrcPermissions {
+            dm_allowed_users: vec!["alice".into()],
+            ..Default::default()
+        };
+        // The services account is authoritative when the server reports one.
+        assert!(build(line, &by_nick).is_none());
+        assert!(build(":alice!a@host PRIVMSG spacebot :hi", &by_nick).is_some());
+
+        let by_account = IrcPermissions {
+            dm_allowed_users: vec!["Alice-Account".into()],
+            ..Default::default()
+        };
+        let message = build(line, &by_account).unwrap();
+        assert_eq!(message.conversation_id, "irc:dm:alice");
+        assert_eq!(meta_str(&message, "irc_target"), Some("alice"));
+        assert_eq!(meta_bool(&message, "irc_is_dm"), Some(true));
+        assert_eq!(
+            meta_bool(&message, "irc_mentions_or_replies_to_bot"),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn channel_filter_and_own_messages() {
+        let permissions = IrcPermissions {
+            channel_filter: Some(vec!["#rust".into()]),
+            ..Default::default()
+        };
+
+        assert!(build(":alice!a@host PRIVMSG #RUST :hi", &permissions).is_some());
+        assert!(build(":alice!a@host PRIVMSG #offtopic :hi", &permissions).is_none());
+        assert!(build(":spacebot!s@host PRIVMSG #rust :echo", &permissions).is_none());
+        assert!(build(":alice!a@host PRIVMSG @#rust :ops only", &permissions).is_none());
+    }
+
+    #[test]
+    fn ctcp_actions_and_formatting() {
+        assert_eq!(
+            message_text("alice", "\u{1}ACTION waves\u{1}").as_deref(),
+            Some("* alice waves")
+        );
+        assert_eq!(message_text("alice", "\u{1}VERSION\u{1}"), None);
+        assert_eq!(
+            message_text(
+                "alice",
+                "\u{2}bold\u{2} \u{3}04,12red\u{3} \u{4}ff0000hex\u{f}"
+            )
+            .as_deref(),
+            Some("bold red hex")
+        );
+        assert_eq!(strip_formatting("\u{3}3,x"), ",x");
+        assert_eq!(message_text("alice", "\u{2}\u{2}  "), None);
+    }
+
+    #[test]
+    fn mentions_require_word_boundaries() {
+        assert!(mentions_nick("spacebot: hi", BOT));
+        assert!(mentions_nick("hey SpaceBot, ping", BOT));
+        assert!(!mentions_nick("spacebot_ is someone else", BOT));
+        assert!(!mentions_nick("myspacebot", BOT));
+        assert!(!mentions_nick("anything", ""));
+    }
+
+    // --- outbound ---
+
+    #[test]
+    fn split_message_respects_byte_budget() {
+        assert_eq!(
+            split_message("one\n\n  \ntwo  ", 100),
+            vec!["one".to_string(), "two".to_string()]
+        );
+
+        let long = "word ".repeat(100);
+        let chunks = split_message(&long, 50);
+        assert!(chunks.iter().all(|chunk| chunk.len() <= 50));
+        assert_eq!(chunks.join(" "), long.trim_end());
+
+        let multibyte = "é".repeat(40);
+        let chunks = split_message(&multibyte, 25);
+        assert!(chunks.iter().all(|chunk| chunk.len() <= 25));
+        assert_eq!(chunks.concat(), multibyte);
+
+        assert_eq!(max_payload_bytes("#rust"), 512 - 12 - 5 - HOSTMASK_RESERVE);
+    }
+
+    #[test]
+    fn join_lines_batch_channels() {
+        assert_eq!(
+            join_lines(&["#a".into(), "".into(), "#b".into()]),
+            vec!["JOIN #a,#b".to_string()]
+        );
+        let many: Vec<String> = (0..100).map(|index| format!("#channel-{index}")).collect();
+        let lines = join_lines(&many);
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|line| line.len() + 2 <= MAX_LINE_BYTES));
+    }
+
+    #[test]
+    fn sasl_plain_payload_is_chunked() {
+        let chunks = sasl_plain_chunks("bot", "secret");
+        assert_eq!(chunks.len(), 1);
+        let decoded = base64::engine::general_purpose::STANDARD
+            .decode(&chunks[0])
+            .unwrap();
+        assert_eq!(decoded, b"bot\0bot\0secret");
+
+        // 298 raw bytes encode to exactly 400 base64 bytes.
+        let password = "p".repeat(298 - "bot\0bot\0".len());
+        let chunks = sasl_plain_chunks("bot", &password);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].len(), 400);
+        assert_eq!(chunks[1], "+");
+    }
+
+    // --- connection against a local server stand-in ---
+
+    struct TestServer {
+        reader: BufReader<tokio::net::tcp::OwnedReadHalf>,
+        writer: tokio::net::tcp::OwnedWriteHalf,
+    }
+
+    impl TestServer {
+        async fn recv(&mut self) -> String {
+            let mut line = String::new();
+            tokio::time::timeout(Duration::from_secs(5), self.reader.read_line(&mut line))
+                .await
+                .expect("timed out waiting for client line")
+                .unwrap();
+            line.trim_end().to_string()
+        }
+
+        async fn send(&mut self, line: &str) {
+            self.writer
+                .write_all(format!("{line}\r\n").as_bytes())
+                .await
+                .unwrap();
+        }
+    }
+
+    fn settings(port: u16) -> IrcConnectionSettings {
+        IrcConnectionSettings {
+            server: "127.0.0.1".into(),
+            port,
+            tls: false,
+            nickname: BOT.into(),
+            username: None,
+            realname: None,
+            server_password: None,
+            sasl_username: Some("bot-account".into()),
+            sasl_password: Some("hunter2".into()),
+            channels: vec!["#rust".into()],
+        }
+    }
+
+    #[tokio::test]
+    async fn registers_with_sasl_delivers_messages_and_backfills_history() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let adapter = Arc::new(
+            IrcAdapter::new(
+                "irc",
+                settings(port),
+                Arc::new(ArcSwap::from_pointee(IrcPermissions::default())),
+            )
+            .unwrap(),
+        );
+        let mut inbound = adapter.start().await.unwrap();
+
+        let (socket, _) = listener.accept().await.unwrap();
+        let (read_half, write_half) = socket.into_split();
+        let mut server = TestServer {
+            reader: BufReader::new(read_half),
+            writer: write_half,
+        };
+
+        // Registration and SASL.
+        assert_eq!(server.recv().await, "CAP LS 302");
+        assert_eq!(server.recv().await, "NICK spacebot");
+        assert_eq!(server.recv().await, "USER spacebot 0 * :Spacebot");
+        server
+            .send(":srv CAP * LS * :sasl=PLAIN,EXTERNAL server-time")
+            .await;
+        server
+            .send(":srv CAP * LS :message-tags batch draft/chathistory unknown-cap")
+            .await;
+        let request = server.recv().await;
+        assert_eq!(
+            request,
+            "CAP REQ :sasl server-time message-tags batch draft/chathistory"
+        );
+        server
+            .send(&format!(
+                ":srv CAP * ACK :{}",
+                request.trim_start_matches("CAP REQ :")
+            ))
+            .await;
+        assert_eq!(server.recv().await, "AUTHENTICATE PLAIN");
+        server.send("AUTHENTICATE +").await;
+        let payload = server.recv().await;
+        let decoded = base64::engine::general_purpose::STANDARD
+            .decode(payload.trim_start_matches("AUTHENTICATE "))
+            .unwrap();
+        assert_eq!(decoded, b"bot-account\0bot-account\0hunter2");
+        server
+            .send(":srv 903 spacebot :SASL authentication successful")
+            .await;
+        assert_eq!(server.recv().await, "CAP END");
+
+        assert!(adapter.health_check().await.is_err());
+        server.send(":srv 001 spacebot :Welcome").await;
+        server
+            .send(":srv 005 spacebot CHATHISTORY=5 :are supported by this server")
+            .await;
+        assert_eq!(server.recv().await, "JOIN #rust");
+        server.send("PING :keepalive").await;
+        assert_eq!(server.recv().await, "PONG :keepalive");
+        assert!(adapter.health_check().await.is_ok());
+
+        // Inbound delivery.
+        server
+            .send("@msgid=m2;time=2024-05-01T12:00:00.000Z :alice!a@host PRIVMSG #rust :spacebot: summarize")
+            .await;
+        let message = tokio::time::timeout(Duration::from_secs(5), inbound.next())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(message.conversation_id, "irc:#rust");
+        assert_eq!(message.id, "m2");
+
+        // Replies: one PRIVMSG per line, thread replies address the sender.
+        adapter
+            .respond(
+                &message,
+                OutboundResponse::Text("first line\n\nsecond line".into()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(server.recv().await, "PRIVMSG #rust :first line");
+        assert_eq!(server.recv().await, "PRIVMSG #rust :second line");
+
+        adapter
+            .respond(
+                &message,
+                OutboundResponse::ThreadReply {
+                    thread_name: "ignored".into(),
+                    text: "done".into(),
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            server.recv().await,
+            "@+draft/reply=m2 PRIVMSG #rust :alice: done"
+        );
+
+        // History backfill via CHATHISTORY, capped by the server's limit.
+        let history_task = {
+            let adapter = adapter.clone();
+            let message = message.clone();
+            tokio::spawn(async move { adapter.fetch_history(&message, 50).await })
+        };
+        assert_eq!(server.recv().await, "CHATHISTORY BEFORE #rust msgid=m2 5");
+        server.send(":srv BATCH +h1 chathistory #rust").await;
+        server
+            .send("@batch=h1;time=2024-05-01T11:58:00.000Z :bob!b@host PRIVMSG #rust :earlier")
+            .await;
+        server
+            .send("@batch=h1;time=2024-05-01T11:59:00.000Z :spacebot!s@host PRIVMSG #rust :\u{1}ACTION replies\u{1}")
+            .await;
+        server.send(":srv BATCH -h1").await;
+
+        let history = history_task.await.unwrap().unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].author, "bob");
+        assert_eq!(history[0].content, "earlier");
+        assert!(!history[0].is_bot);
+        assert!(history[1].is_bot);
+        assert_eq!(history[1].content, "* spacebot replies");
+
+        // History lines never reach the inbound stream.
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), inbound.next())
+                .await
+                .is_err()
+        );
+
+        adapter.shutdown().await.unwrap();
+        assert_eq!(server.recv().await, "QUIT :Shutting down");
+    }
+
+    #[tokio::test]
+    async fn broadcast_targets_channels_and_nicks() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let mut settings = settings(port);
+        settings.sasl_password = None;
+        let adapter = IrcAdapter::new(
+            "irc",
+            settings,
+            Arc::new(ArcSwap::from_pointee(IrcPermissions::default())),
+        )
+        .unwrap();
+        let _inbound = adapter.start().await.unwrap();
+
+        let (socket, _) = listener.accept().await.unwrap();
+        let (read_half, write_half) = socket.into_split();
+        let mut server = TestServer {
+            reader: BufReader::new(read_half),
+            writer: write_half,
+        };
+
+        // Broadcasts before registration fail as retryable.
+        let early = adapter
+            .broadcast("#rust", OutboundResponse::Text("too soon".into()))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            crate::messaging::traits::broadcast_failure_kind(&early),
+            crate::messaging::traits::BroadcastFailureKind::Transient
+        );
+
+        assert_eq!(server.recv().await, "CAP LS 302");
+        assert_eq!(server.recv().await, "NICK spacebot");
+        assert_eq!(server.recv().await, "USER spacebot 0 * :Spacebot");
+        // No capabilities in common: negotiation ends immediately.
+        server.send(":srv CAP * LS :away-notify").await;
+        assert_eq!(server.recv().await, "CAP END");
+        server
+            .send(":srv 433 * spacebot :Nickname is already in use")
+            .await;
+        assert_eq!(server.recv().await, "NICK spacebot_");
+        server.send(":srv 001 spacebot_ :Welcome").await;
+        assert_eq!(server.recv().await, "JOIN #rust");
+
+        adapter
+            .broadcast("dm:alice", OutboundResponse::Text("hello".into()))
+            .await
+            .unwrap();
+        assert_eq!(server.recv().await, "PRIVMSG alice :hello");
+
+        let cards = vec![crate::Card {
+            title: Some("Deploy".into()),
+            description: Some("Finished".into()),
+            ..Default::default()
+        }];
+        adapter
+            .broadcast(
+                "#rust",
+                OutboundResponse::RichMessage {
+                    text: String::new(),
+                    blocks: Vec::new(),
+                    cards,
+                    interactive_elements: Vec::new(),
+                    poll: None,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(server.recv().await, "PRIVMSG #rust :Deploy");
+        assert_eq!(server.recv().await, "PRIVMSG #rust :Finished");
+
+        let unsupported = adapter
+            .broadcast("#rust", OutboundResponse::Reaction("👍".into()))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            crate::messaging::traits::broadcast_failure_kind(&unsupported),
+            crate::messaging::traits::BroadcastFailureKind::Permanent
+        );
+
+        // Without chathistory support, backfill is empty and sends nothing.
+        let mut message = InboundMessage::empty();
+        message
+            .metadata
+            .insert("irc_target".into(), serde_json::json!("#rust"));
+        assert!(
+            adapter
+                .fetch_history(&message, 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        adapter.shutdown().await.unwrap();
+        assert_eq!(server.recv().await, "QUIT :Shutting down");
+    }
+}
@@ -1,8 +1,10 @@
 //! MessagingManager: Fan-in and routing for all adapters.
 
+use crate::messaging::outbox::{OutboxStatus, OutboxStore};
+use crate::messaging::streaming::StreamRegistry;
 use crate::messaging::traits::{
-    BroadcastFailureKind, HistoryMessage, InboundStream, Messaging, MessagingDyn,
-    broadcast_failure_kind,
+    AdapterHealth, BroadcastFailureKind, HistoryMessage, InboundStream, Messaging, MessagingDyn,
+    broadcast_failure_kind, mark_permanent_broadcast,
 };
 use crate::{InboundMessage, OutboundResponse, StatusUpdate};
 
@@ -10,7 +12,17 @@ use anyhow::Context as _;
 use futures::StreamExt as _;
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::{RwLock, mpsc};
+use tokio::sync::{Notify, RwLock, mpsc};
+
+/// Outcome of a proactive send that did not fail outright.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProactiveDelivery {
+    /// The platform accepted the message.
+    Delivered,
+    /// Inline retries ran out on a transient failure; the message is persisted
+    /// in the outbox and will be retried in the background.
+    Queued { outbox_id: String },
+}
 
 /// Manages all messaging adapters with support for runtime addition.
 ///
@@ -22,6 +34,14 @@ pub struct MessagingManager {
     fan_in_tx: mpsc::Sender<InboundMessage>,
     /// Receiver side, taken once by `start()`.
     fan_in_rx: RwLock<Option<mpsc::Receiver<InboundMessage>>>,
+    /// Durable store for proactive sends. Without one, proactive sends only
+    /// get the inline retry budget.
+    outbox: Option<Arc<OutboxStore>>,
+    /// Wakes the outbox worker early, e.g. after a replay from the API.
+    outbox_wake: Arc<Notify>,
+    /// Streams buffered for adapters that can't edit messages, keyed by
+    /// adapter and inbound message ID. Flushed as one `Text` on `StreamEnd`.
+    buffered_streams: StreamRegistry<()>,
 }
 
 impl MessagingManager {
@@ -31,9 +51,29 @@ impl MessagingManager {
             adapters: RwLock::new(HashMap::new()),
             fan_in_tx,
             fan_in_rx: RwLock::new(Some(fan_in_rx)),
+            outbox: None,
+            outbox_wake: Arc::new(Notify::new()),
+            buffered_streams: StreamRegistry::new(usize::MAX)
+                .with_edit_interval(std::time::Duration::MAX),
         }
     }
 
+    /// Persist proactive sends in `outbox` so they survive restarts and
+    /// outages longer than the inline retry budget.
+    pub fn with_outbox(mut self, outbox: Arc<OutboxStore>) -> Self {
+        self.outbox = Some(outbox);
+        self
+    }
+
+    pub fn outbox(&self) -> Option<&Arc<OutboxStore>> {
+        self.outbox.as_ref()
+    }
+
+    /// Ask the outbox worker to look for due messages now.
+    pub fn wake_outbox(&self) {
+        self.outbox_wake.notify_one();
+    }
+
     /// Register an adapter (before start). Use `register_and_start` for runtime addition.
     pub async fn register(&self, adapter: impl Messaging) {
         let name = adapter.name().to_string();
@@ -60,6 +100,10 @@ impl MessagingManager {
     const BROADCAST_MAX_RETRY_DELAY: std::time::Duration = std::time::Duration::from_millis(8);
     #[cfg(not(test))]
     const BROADCAST_MAX_RETRY_DELAY: std::time::Duration = std::time::Duration::from_secs(8);
+    /// How often the outbox worker checks for due messages when not woken.
+    const OUTBOX_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(15);
+    /// Messages claimed from the outbox per round.
+    const OUTBOX_BATCH_SIZE: i64 = 20;
 
     /// Start all registered adapters and return the merged inbound stream.
     ///
@@ -241,6 +285,32 @@ impl MessagingManager {
                 .with_context(|| format!("no messaging adapter named '{}'", adapter_key))?,
         );
         drop(adapters);
+
+        if !adapter.supports_streaming() {
+            let stream_key = format!("{adapter_key}:{}", message.id);
+            match response {
+                OutboundResponse::StreamStart => {
+                    self.buffered_streams.start(&stream_key, ()).await;
+                    return Ok(());
+                }
+                OutboundResponse::StreamChunk(text) => {
+                    self.buffered_streams.update(&stream_key, text).await;
+                    return Ok(());
+                }
+                OutboundResponse::StreamEnd => {
+                    return match self.buffered_streams.finish(&stream_key).await {
+                        Some(stream) if !stream.withdrawn => {
+                            adapter
+                                .respond(message, OutboundResponse::Text(stream.text))
+                                .await
+                        }
+                        _ => Ok(()),
+                    };
+                }
+                response => return adapter.respond(message, response).await,
+            }
+        }
+
         adapter.respond(message, response).await
     }
 
@@ -279,19 +349,99 @@ impl MessagingManager {
     }
 
     /// Send a proactive message through a specific adapter with bounded retry/backoff.
+    ///
+    /// With an outbox configured, the message is persisted first. If the
+    /// inline retries run out on a transient failure, it stays queued for the
+    /// outbox worker and `ProactiveDelivery::Queued` is returned; permanent
+    /// failures are dead-lettered and returned as errors.
     pub async fn broadcast_proactive(
         &self,
         adapter_name: &str,
         target: &str,
         response: OutboundResponse,
-    ) -> crate::Result<()> {
-        let adapter = {
-            let adapters = self.adapters.read().await;
-            adapters
-                .get(adapter_name)
-                .cloned()
-                .with_context(|| format!("no messaging adapter named '{adapter_name}'"))?
+    ) -> crate::Result<ProactiveDelivery> {
+        let queued = match &self.outbox {
+            Some(outbox) => match outbox.enqueue(adapter_name, target, &response).await {
+                Ok(outbox_id) => Some((outbox, outbox_id)),
+                Err(error) => {
+                    tracing::warn!(
+                        adapter = %adapter_name,
+                        target,
+                        %error,
+                        "failed to persist proactive message, sending without outbox"
+                    );
+                    None
+                }
+            },
+            None => None,
+        };
+
+        let result = self
+            .broadcast_with_retry(adapter_name, target, response)
+            .await;
+        let Some((outbox, outbox_id)) = queued else {
+            return result.map(|()| ProactiveDelivery::Delivered);
+        };
+
+        let error = match result {
+            Ok(()) => {
+                if let Err(error) = outbox.mark_delivered(&outbox_id).await {
+                    tracing::warn!(
+                        outbox_id,
+                        %error,
+                        "failed to clear delivered message from outbox, it may be sent again"
+                    );
+                }
+                return Ok(ProactiveDelivery::Delivered);
+            }
+            Err(error) => error,
         };
+
+        match outbox
+            .record_failure(
+                &outbox_id,
+                &error.to_string(),
+                broadcast_failure_kind(&error),
+            )
+            .await
+        {
+            Ok(OutboxStatus::Pending) => {
+                tracing::warn!(
+                    adapter = %adapter_name,
+                    target,
+                    outbox_id,
+                    %error,
+                    "proactive broadcast queued in outbox for retry"
+                );
+                Ok(ProactiveDelivery::Queued { outbox_id })
+            }
+            Ok(OutboxStatus::DeadLetter) => {
+                tracing::warn!(
+                    adapter = %adapter_name,
+                    target,
+                    outbox_id,
+                    "proactive broadcast dead-lettered"
+                );
+                Err(error)
+            }
+            Err(store_error) => {
+                tracing::warn!(
+                    outbox_id,
+                    error = %store_error,
+                    "failed to record proactive broadcast failure in outbox"
+                );
+                Err(error)
+            }
+        }
+    }
+
+    async fn broadcast_with_retry(
+        &self,
+        adapter_name: &str,
+        target: &str,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        let adapter = self.broadcast_adapter(adapter_name).await?;
         let mut delay = Self::BROADCAST_INITIAL_RETRY_DELAY;
 
         for attempt in 1..=Self::MAX_BROADCAST_RETRY_ATTEMPTS {
@@ -344,6 +494,123 @@ impl MessagingManager {
         unreachable!("broadcast retry loop must return on success or terminal error")
     }
 
+    /// Look up an adapter for a proactive send. A missing adapter is a
+    /// permanent failure, so the message is dead-lettered for replay once the
+    /// adapter is configured again instead of retrying for hours.
+    async fn broadcast_adapter(&self, adapter_name: &str) -> crate::Result<Arc<dyn MessagingDyn>> {
+        self.adapters
+            .read()
+            .await
+            .get(adapter_name)
+            .cloned()
+            .ok_or_else(|| {
+                mark_permanent_broadcast(anyhow::anyhow!(
+                    "no messaging adapter named '{adapter_name}'"
+                ))
+            })
+    }
+
+    /// Spawn the background task that retries queued outbox messages.
+    ///
+    /// The task holds a weak reference and exits once the manager is dropped,
+    /// so a manager replaced on reload hands the outbox to its successor.
+    /// Returns `None` when no outbox is configured.
+    pub fn spawn_outbox_worker(manager: &Arc<Self>) -> Option<tokio::task::JoinHandle<()>> {
+        let outbox = manager.outbox.clone()?;
+        let wake = manager.outbox_wake.clone();
+        let manager = Arc::downgrade(manager);
+
+        Some(tokio::spawn(async move {
+            loop {
+                let Some(manager) = manager.upgrade() else {
+                    break;
+                };
+                manager.drain_outbox(&outbox).await;
+                drop(manager);
+
+                tokio::select! {
+                    _ = wake.notified() => {}
+                    _ = tokio::time::sleep(Self::OUTBOX_POLL_INTERVAL) => {}
+                }
+            }
+        }))
+    }
+
+    /// Deliver every due outbox message once.
+    async fn drain_outbox(&self, outbox: &OutboxStore) {
+        loop {
+            let batch = match outbox.claim_due(Self::OUTBOX_BATCH_SIZE).await {
+                Ok(batch) => batch,
+                Err(error) => {
+                    tracing::warn!(%error, "failed to claim due outbox messages");
+                    return;
+                }
+            };
+            if batch.is_empty() {
+                return;
+            }
+
+            for delivery in batch {
+                let result = match self.broadcast_adapter(&delivery.adapter).await {
+                    Ok(adapter) => adapter.broadcast(&delivery.target, delivery.response).await,
+                    Err(error) => Err(error),
+                };
+
+                match result {
+                    Ok(()) => {
+                        tracing::info!(
+                            adapter = %delivery.adapter,
+                            target = %delivery.target,
+                            outbox_id = %delivery.id,
+                            previous_attempts = delivery.attempts,
+                            "outbox message delivered"
+                        );
+                        if let Err(error) = outbox.mark_delivered(&delivery.id).await {
+                            tracing::warn!(
+                                outbox_id = %delivery.id,
+                                %error,
+                                "failed to clear delivered message from outbox, it may be sent again"
+                            );
+                        }
+                    }
+                    Err(error) => {
+                        match outbox
+                            .record_failure(
+                                &delivery.id,
+                                &error.to_string(),
+                                broadcast_failure_kind(&error),
+                            )
+                            .await
+                        {
+                            Ok(status) => tracing::warn!(
+                                adapter = %delivery.adapter,
+                                target = %delivery.target,
+                                outbox_id = %delivery.id,
+                                %status,
+                                %error,
+                                "outbox delivery failed"
+                            ),
+                            Err(store_error) => tracing::warn!(
+                                outbox_id = %delivery.id,
+                                error = %store_error,
+                                "failed to record outbox delivery failure"
+                            ),
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Last known connection state of an adapter, if it tracks one.
+    pub async fn connection_health(&self, adapter_name: &str) -> Option<AdapterHealth> {
+        self.adapters
+            .read()
+            .await
+            .get(adapter_name)
+            .and_then(|adapter| adapter.connection_health())
+    }
+
     /// Fetch recent message history from the platform for context backfill.
     pub async fn fetch_history(
         &self,
@@ -410,9 +677,11 @@ impl Default for MessagingManager {
 
 #[cfg(test)]
 mod tests {
-    use super::MessagingManager;
+    use super::{MessagingManager, ProactiveDelivery};
+    use crate::messaging::outbox::{OutboxStatus, OutboxStore};
     use crate::messaging::traits::{
-        InboundStream, Messaging, mark_permanent_broadcast, mark_retryable_broadcast,
+        BroadcastFailureKind, InboundStream, Messaging, broadcast_failure_kind,
+        mark_permanent_broadcast, mark_retryable_broadcast,
     };
     use crate::{InboundMessage, OutboundResponse, StatusUpdate};
     use std::collections::VecDeque;
@@ -423,6 +692,7 @@ mod tests {
         name: &'static str,
         results: Arc<Mutex<VecDeque<crate::Result<()>>>>,
         attempts: Arc<Mutex<usize>>,
+        responses: Arc<Mutex<Vec<OutboundResponse>>>,
     }
 
     impl TestMessagingAdapter {
@@ -431,6 +701,7 @@ mod tests {
                 name,
                 results: Arc::new(Mutex::new(results.into())),
                 attempts: Arc::new(Mutex::new(0)),
+                responses: Arc::new(Mutex::new(Vec::new())),
             }
         }
 
@@ -451,8 +722,12 @@ mod tests {
         async fn respond(
             &self,
             _message: &InboundMessage,
-            _response: OutboundResponse,
+            response: OutboundResponse,
         ) -> crate::Result<()> {
+            self.responses
+                .lock()
+                .expect("lock responses")
+                .push(response);
             Ok(())
         }
 
@@ -492,7 +767,7 @@ mod tests {
         );
         manager.register(adapter.clone()).await;
 
-        manager
+        let delivery = manager
             .broadcast_proactive(
                 "test",
                 "target",
@@ -501,6 +776,7 @@ mod tests {
             .await
             .expect("retry then success");
 
+        assert_eq!(delivery, ProactiveDelivery::Delivered);
         assert_eq!(adapter.attempts(), 2);
     }
 
@@ -586,4 +862,222 @@ mod tests {
         assert!(error.to_string().contains("invalid broadcast target"));
         assert_eq!(adapter.attempts(), 1);
     }
+
+    async fn setup_outbox() -> Arc<OutboxStore> {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("in-memory sqlite should connect");
+        sqlx::migrate!("./migrations/outbox")
+            .run(&pool)
+            .await
+            .expect("outbox migrations should apply");
+        Arc::new(OutboxStore::new(pool))
+    }
+
+    #[tokio::test]
+    async fn proactive_broadcast_queues_in_outbox_after_retry_budget() {
+        let outbox = setup_outbox().await;
+        let manager = MessagingManager::new().with_outbox(outbox.clone());
+        let adapter = TestMessagingAdapter::new(
+            "test",
+            (0..3)
+                .map(|_| {
+                    Err(mark_retryable_broadcast(anyhow::anyhow!(
+                        "temporary outage"
+                    )))
+                })
+                .collect(),
+        );
+        manager.register(adapter.clone()).await;
+
+        let delivery = manager
+            .broadcast_proactive(
+                "test",
+                "target",
+                OutboundResponse::Text("hello".to_string()),
+            )
+            .await
+            .expect("transient exhaustion should queue");
+
+        let ProactiveDelivery::Queued { outbox_id } = delivery else {
+            panic!("expected queued delivery, got {delivery:?}");
+        };
+        let entries = outbox.list(Some(OutboxStatus::Pending), 10).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].id, outbox_id);
+        assert_eq!(entries[0].attempts, 1);
+        assert_eq!(adapter.attempts(), 3);
+    }
+
+    #[tokio::test]
+    async fn proactive_broadcast_dead_letters_permanent_failures() {
+        let outbox = setup_outbox().await;
+        let manager = MessagingManager::new().with_outbox(outbox.clone());
+        let adapter = TestMessagingAdapter::new(
+            "test",
+            vec![Err(mark_permanent_broadcast(anyhow::anyhow!(
+                "invalid broadcast target"
+            )))],
+        );
+        manager.register(adapter.clone()).await;
+
+        manager
+            .broadcast_proactive(
+                "test",
+                "target",
+                OutboundResponse::Text("hello".to_string()),
+            )
+            .await
+            .expect_err("permanent failures should surface");
+
+        let dead = outbox
+            .list(Some(OutboxStatus::DeadLetter), 10)
+            .await
+            .unwrap();
+        assert_eq!(dead.len(), 1);
+        assert!(
+            dead[0]
+                .last_error
+                .as_deref()
+                .unwrap()
+                .contains("invalid broadcast target")
+        );
+    }
+
+    #[tokio::test]
+    async fn proactive_broadcast_dead_letters_unknown_adapter() {
+        let outbox = setup_outbox().await;
+        let manager = MessagingManager::new().with_outbox(outbox.clone());
+
+        let error = manager
+            .broadcast_proactive(
+                "removed",
+                "target",
+                OutboundResponse::Text("hello".to_string()),
+            )
+            .await
+            .expect_err("unknown adapter should not be retried");
+
+        assert_eq!(
+            broadcast_failure_kind(&error),
+            BroadcastFailureKind::Permanent
+        );
+        let dead = outbox
+            .list(Some(OutboxStatus::DeadLetter), 10)
+            .await
+            .unwrap();
+        assert_eq!(dead.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn proactive_broadcast_clears_outbox_on_success() {
+        let outbox = setup_outbox().await;
+        let manager = MessagingManager::new().with_outbox(outbox.clone());
+        manager
+            .register(TestMessagingAdapter::new("test", vec![Ok(())]))
+            .await;
+
+        let delivery = manager
+            .broadcast_proactive(
+                "test",
+                "target",
+                OutboundResponse::Text("hello".to_string()),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(delivery, ProactiveDelivery::Delivered);
+        assert!(outbox.list(None, 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn outbox_worker_delivers_replayed_messages_after_restart() {
+        let outbox = setup_outbox().await;
+        let first = MessagingManager::new().with_outbox(outbox.clone());
+        first
+            .register(TestMessagingAdapter::new(
+                "test",
+                vec![Err(mark_permanent_broadcast(anyhow::anyhow!(
+                    "bot removed from channel"
+                )))],
+            ))
+            .await;
+        first
+            .broadcast_proactive(
+                "test",
+                "target",
+                OutboundResponse::Text("hello".to_string()),
+            )
+            .await
+            .expect_err("permanent failure");
+        drop(first);
+
+        // A fresh manager over the same database picks the message up once
+        // it has been replayed.
+        let restarted = Arc::new(MessagingManager::new().with_outbox(outbox.clone()));
+        let adapter = TestMessagingAdapter::new("test", vec![Ok(())]);
+        restarted.register(adapter.clone()).await;
+        assert_eq!(outbox.replay_dead_letters().await.unwrap(), 1);
+
+        let worker = MessagingManager::spawn_outbox_worker(&restarted).expect("outbox configured");
+        for _ in 0..100 {
+            if adapter.attempts() > 0 && outbox.list(None, 10).await.unwrap().is_empty() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        assert_eq!(adapter.attempts(), 1);
+        assert!(outbox.list(None, 10).await.unwrap().is_empty());
+        drop(restarted);
+        worker.abort();
+    }
+
+    #[tokio::test]
+    async fn streams_collapse_to_one_text_for_adapters_without_edits() {
+        let manager = MessagingManager::new();
+        let adapter = TestMessagingAdapter::new("test", Vec::new());
+        manager.register(adapter.clone()).await;
+
+        let mut message = InboundMessage::empty();
+        message.id = "inbound-1".into();
+        message.adapter = Some("test".into());
+
+        for response in [
+            OutboundResponse::StreamStart,
+            OutboundResponse::StreamChunk("Hel".into()),
+            OutboundResponse::StreamChunk("Hello".into()),
+            OutboundResponse::StreamEnd,
+        ] {
+            manager.respond(&message, response).await.expect("respond");
+        }
+
+        let responses = adapter.responses.lock().expect("lock responses");
+        assert_eq!(responses.len(), 1);
+        assert!(matches!(&responses[0], OutboundResponse::Text(text) if text == "Hello"));
+    }
+
+    #[tokio::test]
+    async fn withdrawn_streams_send_nothing_to_adapters_without_edits() {
+        let manager = MessagingManager::new();
+        let adapter = TestMessagingAdapter::new("test", Vec::new());
+        manager.register(adapter.clone()).await;
+
+        let mut message = InboundMessage::empty();
+        message.id = "inbound-1".into();
+        message.adapter = Some("test".into());
+
+        for response in [
+            OutboundResponse::StreamStart,
+            OutboundResponse::StreamChunk("Partial".into()),
+            OutboundResponse::StreamChunk(String::new()),
+            OutboundResponse::StreamEnd,
+        ] {
+            manager.respond(&message, response).await.expect("respond");
+        }
+
+        assert!(adapter.responses.lock().expect("lock responses").is_empty());
+    }
 }
@@ -0,0 +1,2102 @@
+//! Matrix messaging adapter using the Client-Server API over HTTP.
+//!
+//! Long-polls `/sync` for room events and talks to the homeserver directly
+//! with an access token. The adapter does not implement end-to-end
+//! encryption. Encrypted rooms only work when `homeserver_url` points at an
+//! E2EE proxy such as Pantalaimon, which decrypts `/sync` and encrypts sends.
+//! Joining an encrypted room logs a warning, and encrypted events that still
+//! reach the adapter are reported once per room and skipped.
+
+use crate::config::MatrixPermissions;
+use crate::messaging::apply_runtime_adapter_to_conversation_id;
+use crate::messaging::streaming::StreamRegistry;
+use crate::messaging::traits::{
+    HistoryMessage, InboundStream, Messaging, ensure_supported_broadcast_response,
+    mark_permanent_broadcast, mark_retryable_broadcast,
+};
+use crate::{Attachment, InboundMessage, MessageContent, OutboundResponse, StatusUpdate};
+
+use anyhow::Context as _;
+use arc_swap::ArcSwap;
+use reqwest::{Client, RequestBuilder};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{OnceCell, RwLock, mpsc};
+use url::Url;
+
+/// Matrix caps events at 65,536 bytes including the envelope; leave room for
+/// relations and the edit fallback.
+const MAX_MESSAGE_LENGTH: usize = 30_000;
+const STREAM_EDIT_THROTTLE: Duration = Duration::from_millis(800);
+const TYPING_TIMEOUT_MS: u64 = 30_000;
+const TYPING_REFRESH_INTERVAL: Duration = Duration::from_secs(20);
+const SYNC_TIMEOUT_MS: u64 = 30_000;
+const SYNC_RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+const SYNC_RETRY_MAX_DELAY: Duration = Duration::from_secs(60);
+const HTTP_TIMEOUT: Duration = Duration::from_secs(30);
+const STREAM_PLACEHOLDER: &str = "…";
+
+pub struct MatrixAdapter {
+    runtime_key: Arc<str>,
+    api: MatrixApi,
+    max_attachment_bytes: usize,
+    permissions: Arc<ArcSwap<MatrixPermissions>>,
+    bot_user_id: OnceCell<Arc<str>>,
+    rooms: Arc<RwLock<RoomDirectory>>,
+    display_name_cache: Arc<RwLock<HashMap<String, String>>>,
+    /// Direct rooms this adapter created or resolved for `dm:` broadcasts.
+    dm_room_cache: Arc<RwLock<HashMap<String, String>>>,
+    streams: Arc<StreamRegistry<StreamTarget>>,
+    /// `(event_id, key)` → reaction event ID, so reactions can be redacted.
+    reaction_events: Arc<RwLock<HashMap<(String, String), String>>>,
+    typing_tasks: Arc<RwLock<HashMap<String, tokio::task::JoinHandle<()>>>>,
+    shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
+    sync_task: Arc<RwLock<Option<tokio::task::JoinHandle<()>>>>,
+}
+
+/// The placeholder event a streamed reply is written into.
+#[derive(Clone)]
+struct StreamTarget {
+    room_id: Arc<str>,
+    event_id: Arc<str>,
+    relation: Option<serde_json::Value>,
+}
+
+/// What the adapter has learned about joined rooms from `/sync`.
+#[derive(Debug, Default)]
+struct RoomDirectory {
+    /// Rooms flagged as direct chats via `m.direct` or a direct invite.
+    direct_rooms: HashSet<String>,
+    /// `m.direct` account data: user ID → direct room IDs.
+    direct_by_user: HashMap<String, Vec<String>>,
+    member_counts: HashMap<String, u64>,
+    room_names: HashMap<String, String>,
+    /// Encrypted rooms we've already warned about receiving ciphertext in.
+    undecryptable_warned: HashSet<String>,
+    /// Invites already acted on or declined, so each is handled once.
+    handled_invites: HashSet<String>,
+}
+
+impl RoomDirectory {
+    /// A room is direct when `m.direct` says so or it has exactly two
+    /// members (the bot and one other person).
+    fn is_direct(&self, room_id: &str) -> bool {
+        self.direct_rooms.contains(room_id) || self.member_counts.get(room_id) == Some(&2)
+    }
+}
+
+struct MessageBuildContext<'a> {
+    runtime_key: &'a str,
+    bot_user_id: &'a str,
+    bot_display_name: Option<&'a str>,
+    permissions: &'a MatrixPermissions,
+    is_direct: bool,
+    api: &'a MatrixApi,
+    display_name: Option<&'a str>,
+    room_name: Option<&'a str>,
+}
+
+impl std::fmt::Debug for MatrixAdapter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MatrixAdapter")
+            .field("runtime_key", &self.runtime_key)
+            .field("homeserver_url", &self.api.homeserver_url)
+            .field("access_token", &"[REDACTED]")
+            .field("max_attachment_bytes", &self.max_attachment_bytes)
+            .finish()
+    }
+}
+
+impl MatrixAdapter {
+    /// Create a new [`MatrixAdapter`].
+    ///
+    /// `homeserver_url` is the Client-Server API base (e.g.
+    /// `https://matrix.example.org`, or `http://localhost:8009` for a local
+    /// Pantalaimon proxy). `runtime_key` is the adapter's unique identifier
+    /// within the messaging manager (e.g. `"matrix"` or `"matrix:ops"`).
+    pub fn new(
+        runtime_key: impl Into<Arc<str>>,
+        homeserver_url: &str,
+        access_token: impl Into<Arc<str>>,
+        max_attachment_bytes: usize,
+        permissions: Arc<ArcSwap<MatrixPermissions>>,
+    ) -> anyhow::Result<Self> {
+        let homeserver_url = Url::parse(homeserver_url).context("invalid matrix homeserver_url")?;
+        if homeserver_url.cannot_be_a_base() {
+            return Err(anyhow::anyhow!(
+                "matrix homeserver_url must be an http(s) base URL (got: {homeserver_url})"
+            ));
+        }
+
+        let client = Client::builder()
+            .timeout(HTTP_TIMEOUT)
+            .pool_idle_timeout(Duration::from_secs(90))
+            .build()
+            .context("failed to build HTTP client")?;
+
+        Ok(Self {
+            runtime_key: runtime_key.into(),
+            api: MatrixApi {
+                client,
+                homeserver_url,
+                access_token: access_token.into(),
+            },
+            max_attachment_bytes,
+            permissions,
+            bot_user_id: OnceCell::new(),
+            rooms: Arc::new(RwLock::new(RoomDirectory::default())),
+            display_name_cache: Arc::new(RwLock::new(HashMap::new())),
+            dm_room_cache: Arc::new(RwLock::new(HashMap::new())),
+            streams: Arc::new(
+                StreamRegistry::new(MAX_MESSAGE_LENGTH).with_edit_interval(STREAM_EDIT_THROTTLE),
+            ),
+            reaction_events: Arc::new(RwLock::new(HashMap::new())),
+            typing_tasks: Arc::new(RwLock::new(HashMap::new())),
+            shutdown_tx: Arc::new(RwLock::new(None)),
+            sync_task: Arc::new(RwLock::new(None)),
+        })
+    }
+
+    fn extract_room_id<'a>(&self, message: &'a InboundMessage) -> crate::Result<&'a str> {
+        message
+            .metadata
+            .get("matrix_room_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("missing matrix_room_id metadata").into())
+    }
+
+    /// Build the `m.relates_to` block for a reply to `message`.
+    ///
+    /// Inside a thread, replies stay in the thread and quote the triggering
+    /// event. Outside one, a `REPLY_TO_MESSAGE_ID` (set by the channel for
+    /// branch/worker results) becomes a rich reply; otherwise no relation.
+    fn reply_relation(message: &InboundMessage) -> Option<serde_json::Value> {
+        let reply_to = message
+            .metadata
+            .get(crate::metadata_keys::REPLY_TO_MESSAGE_ID)
+            .and_then(|v| v.as_str());
+
+        if let Some(thread_root) = message
+            .metadata
+            .get("matrix_thread_root")
+            .and_then(|v| v.as_str())
+        {
+            return Some(thread_relation(
+                thread_root,
+                reply_to.unwrap_or(message.id.as_str()),
+            ));
+        }
+
+        reply_to.map(|event_id| serde_json::json!({ "m.in_reply_to": { "event_id": event_id } }))
+    }
+
+    /// Stop refreshing the typing notification for `room_id` and clear it.
+    async fn stop_typing(&self, room_id: &str) {
+        if let Some(handle) = self.typing_tasks.write().await.remove(room_id) {
+            handle.abort();
+            if let Some(user_id) = self.bot_user_id.get()
+                && let Err(error) = self.api.set_typing(user_id, room_id, false).await
+            {
+                tracing::debug!(%error, room_id, "failed to clear matrix typing notification");
+            }
+        }
+    }
+
+    /// Start a typing notification for `room_id`, refreshed until
+    /// [`stop_typing`](Self::stop_typing) is called.
+    async fn start_typing(&self, room_id: &str) {
+        if let Some(handle) = self.typing_tasks.write().await.remove(room_id) {
+            handle.abort();
+        }
+        let Some(user_id) = self.bot_user_id.get().cloned() else {
+            return;
+        };
+        let api = self.api.clone();
+        let room_id_owned = room_id.to_string();
+
+        let handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(TYPING_REFRESH_INTERVAL);
+            loop {
+                interval.tick().await;
+                if let Err(error) = api.set_typing(&user_id, &room_id_owned, true).await {
+                    tracing::warn!(%error, "matrix typing notification failed");
+                }
+            }
+        });
+
+        self.typing_tasks
+            .write()
+            .await
+            .insert(room_id.to_string(), handle);
+    }
+
+    /// Send `text` as one or more `m.text` events, all carrying `relation`.
+    /// Returns the event ID of the first chunk.
+    async fn send_text(
+        &self,
+        room_id: &str,
+        text: &str,
+        relation: Option<&serde_json::Value>,
+    ) -> crate::Result<String> {
+        let mut first_event_id = None;
+        for chunk in split_message(text, MAX_MESSAGE_LENGTH) {
+            let event_id = self
+                .api
+                .send_event(room_id, "m.room.message", &text_content(&chunk, relation))
+                .await?;
+            first_event_id.get_or_insert(event_id);
+        }
+        Ok(first_event_id.unwrap_or_default())
+    }
+
+    /// Upload a file to the media repository and post it to `room_id`.
+    async fn send_file(
+        &self,
+        room_id: &str,
+        filename: String,
+        data: Vec<u8>,
+        mime_type: String,
+        caption: Option<String>,
+        relation: Option<&serde_json::Value>,
+    ) -> crate::Result<()> {
+        if data.len() > self.max_attachment_bytes {
+            return Err(mark_permanent_broadcast(anyhow::anyhow!(
+                "file too large: {} bytes (max: {})",
+                data.len(),
+                self.max_attachment_bytes
+            )));
+        }
+
+        let size = data.len();
+        let content_uri = self.api.upload(&filename, &mime_type, data).await?;
+        let content = file_content(
+            &filename,
+            &mime_type,
+            size,
+            &content_uri,
+            caption.as_deref(),
+            relation,
+        );
+        self.api
+            .send_event(room_id, "m.room.message", &content)
+            .await?;
+        Ok(())
+    }
+
+    /// Replace the text of an event we sent earlier (`m.replace` edit).
+    async fn edit_text(&self, room_id: &str, event_id: &str, text: &str) -> crate::Result<()> {
+        let content = serde_json::json!({
+            "msgtype": "m.text",
+            "body": format!("* {text}"),
+            "m.new_content": { "msgtype": "m.text", "body": text },
+            "m.relates_to": { "rel_type": "m.replace", "event_id": event_id },
+        });
+        self.api
+            .send_event(room_id, "m.room.message", &content)
+            .await?;
+        Ok(())
+    }
+
+    /// Resolve a normalized broadcast target to a room ID.
+    ///
+    /// `dm:{user_id}` reuses a known direct room (from `m.direct` or an
+    /// earlier broadcast) or creates one; `#alias:server` is resolved through
+    /// the room directory.
+    async fn resolve_target_room(&self, target: &str) -> crate::Result<String> {
+        if let Some(user_id) = target.strip_prefix("dm:") {
+            if let Some(room_id) = self.dm_room_cache.read().await.get(user_id).cloned() {
+                return Ok(room_id);
+            }
+            let known_room = {
+                let rooms = self.rooms.read().await;
+                rooms
+                    .direct_by_user
+                    .get(user_id)
+                    .and_then(|room_ids| room_ids.first().cloned())
+            };
+            let room_id = match known_room {
+                Some(room_id) => room_id,
+                None => self.api.create_direct_room(user_id).await?,
+            };
+            self.rooms
+                .write()
+                .await
+                .direct_rooms
+                .insert(room_id.clone());
+            self.dm_room_cache
+                .write()
+                .await
+                .insert(user_id.to_string(), room_id.clone());
+            return Ok(room_id);
+        }
+
+        if target.starts_with('#') {
+            return self.api.resolve_alias(target).await;
+        }
+
+        Ok(target.to_string())
+    }
+}
+
+impl Messaging for MatrixAdapter {
+    fn name(&self) -> &str {
+        &self.runtime_key
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        let (inbound_tx, inbound_rx) = mpsc::channel(256);
+        let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
+        *self.shutdown_tx.write().await = Some(shutdown_tx);
+
+        let user_id: Arc<str> = self.api.whoami().await?.into();
+        if self.bot_user_id.set(user_id.clone()).is_err() {
+            tracing::warn!(adapter = %self.runtime_key, "bot_user_id already initialized — start() called more than once");
+        }
+        let bot_display_name = self.api.display_name(&user_id).await;
+
+        tracing::info!(
+            adapter = %self.runtime_key,
+            user_id = %user_id,
+            "matrix adapter connected"
+        );
+
+        let context = SyncContext {
+            runtime_key: self.runtime_key.clone(),
+            api: self.api.clone(),
+            permissions: self.permissions.clone(),
+            bot_user_id: user_id,
+            bot_display_name,
+            rooms: self.rooms.clone(),
+            display_name_cache: self.display_name_cache.clone(),
+            inbound_tx,
+        };
+
+        let handle = tokio::spawn(async move {
+            let mut since: Option<String> = None;
+            let mut retry_delay = SYNC_RETRY_BASE_DELAY;
+
+            loop {
+                // The first sync only establishes a position; replaying the
+                // backlog would answer messages from before we started.
+                let initial = since.is_none();
+                let timeout_ms = if initial { 0 } else { SYNC_TIMEOUT_MS };
+
+                let result = tokio::select! {
+                    _ = shutdown_rx.recv() => {
+                        tracing::info!(adapter = %context.runtime_key, "matrix sync loop shutting down");
+                        return;
+                    }
+                    result = context.api.sync(since.as_deref(), timeout_ms) => result,
+                };
+
+                match result {
+                    Ok(response) => {
+                        retry_delay = SYNC_RETRY_BASE_DELAY;
+                        since = Some(response.next_batch.clone());
+                        if !context.handle_sync(response, !initial).await {
+                            tracing::debug!("inbound channel closed");
+                            return;
+                        }
+                        continue;
+                    }
+                    Err(error) => {
+                        tracing::error!(
+                            adapter = %context.runtime_key,
+                            %error,
+                            delay_ms = retry_delay.as_millis(),
+                            "matrix sync failed, retrying"
+                        );
+                    }
+                }
+
+                tokio::select! {
+                    _ = tokio::time::sleep(retry_delay) => {
+                        retry_delay = (retry_delay * 2).min(SYNC_RETRY_MAX_DELAY);
+                    }
+                    _ = shutdown_rx.recv() => {
+                        tracing::info!(adapter = %context.runtime_key, "matrix adapter shutting down during retry delay");
+                        return;
+                    }
+                }
+            }
+        });
+
+        *self.sync_task.write().await = Some(handle);
+
+        let stream = tokio_stream::wrappers::ReceiverStream::new(inbound_rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn respond(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        let room_id = self.extract_room_id(message)?;
+        let relation = Self::reply_relation(message);
+
+        match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::RichMessage { text, .. }
+            | OutboundResponse::Ephemeral { text, .. }
+            | OutboundResponse::ScheduledMessage { text, .. } => {
+                self.stop_typing(room_id).await;
+                self.send_text(room_id, &text, relation.as_ref()).await?;
+            }
+
+            OutboundResponse::ThreadReply { text, .. } => {
+                self.stop_typing(room_id).await;
+                // Matrix threads are unnamed; root a new one on the trigger
+                // unless we're already inside a thread.
+                let relation =
+                    relation.unwrap_or_else(|| thread_relation(&message.id, &message.id));
+                self.send_text(room_id, &text, Some(&relation)).await?;
+            }
+
+            OutboundResponse::File {
+                filename,
+                data,
+                mime_type,
+                caption,
+            } => {
+                self.send_file(
+                    room_id,
+                    filename,
+                    data,
+                    mime_type,
+                    caption,
+                    relation.as_ref(),
+                )
+                .await?;
+            }
+
+            OutboundResponse::Reaction(emoji) => {
+                let key = emoji.trim().to_string();
+                let content = serde_json::json!({
+                    "m.relates_to": {
+                        "rel_type": "m.annotation",
+                        "event_id": message.id,
+                        "key": key,
+                    }
+                });
+                let reaction_event_id =
+                    self.api.send_event(room_id, "m.reaction", &content).await?;
+                self.reaction_events
+                    .write()
+                    .await
+                    .insert((message.id.clone(), key), reaction_event_id);
+            }
+
+            OutboundResponse::RemoveReaction(emoji) => {
+                let reaction_event_id = self
+                    .reaction_events
+                    .write()
+                    .await
+                    .remove(&(message.id.clone(), emoji.trim().to_string()));
+                if let Some(reaction_event_id) = reaction_event_id {
+                    self.api.redact(room_id, &reaction_event_id).await?;
+                }
+            }
+
+            OutboundResponse::StreamStart => {
+                self.start_typing(room_id).await;
+                let event_id = match self
+                    .send_text(room_id, STREAM_PLACEHOLDER, relation.as_ref())
+                    .await
+                {
+                    Ok(event_id) => event_id,
+                    Err(error) => {
+                        self.stop_typing(room_id).await;
+                        return Err(error);
+                    }
+                };
+                self.streams
+                    .start(
+                        &message.id,
+                        StreamTarget {
+                            room_id: room_id.into(),
+                            event_id: event_id.into(),
+                            relation,
+                        },
+                    )
+                    .await;
+            }
+
+            OutboundResponse::StreamChunk(text) => {
+                if let Some(edit) = self.streams.update(&message.id, text).await
+                    && let Err(error) = self
+                        .edit_text(&edit.handle.room_id, &edit.handle.event_id, &edit.text)
+                        .await
+                {
+                    tracing::warn!(%error, "failed to edit streaming matrix message");
+                }
+            }
+
+            OutboundResponse::StreamEnd => {
+                self.stop_typing(room_id).await;
+                if let Some(stream) = self.streams.finish(&message.id).await {
+                    let target = stream.handle;
+                    let mut chunks = split_message(&stream.text, MAX_MESSAGE_LENGTH).into_iter();
+                    if let Some(first) = chunks.next()
+                        && let Err(error) = self
+                            .edit_text(&target.room_id, &target.event_id, &first)
+                            .await
+                    {
+                        tracing::warn!(%error, "failed to finalize streaming matrix message");
+                    }
+                    for chunk in chunks {
+                        if let Err(error) = self
+                            .send_text(&target.room_id, &chunk, target.relation.as_ref())
+                            .await
+                        {
+                            tracing::warn!(%error, "failed to send overflow chunk for streaming matrix message");
+                        }
+                    }
+                }
+            }
+
+            OutboundResponse::Status(status) => self.send_status(message, status).await?,
+        }
+
+        Ok(())
+    }
+
+    async fn send_status(
+        &self,
+        message: &InboundMessage,
+        status: StatusUpdate,
+    ) -> crate::Result<()> {
+        let room_id = self.extract_room_id(message)?;
+
+        match status {
+            StatusUpdate::Thinking => self.start_typing(room_id).await,
+            StatusUpdate::StopTyping => self.stop_typing(room_id).await,
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    async fn broadcast(&self, target: &str, response: OutboundResponse) -> crate::Result<()> {
+        ensure_supported_broadcast_response("matrix", &response, |response| {
+            matches!(
+                response,
+                OutboundResponse::Text(_)
+                    | OutboundResponse::RichMessage { .. }
+                    | OutboundResponse::ThreadReply { .. }
+                    | OutboundResponse::ScheduledMessage { .. }
+                    | OutboundResponse::File { .. }
+            )
+        })?;
+
+        let room_id = self.resolve_target_room(target).await?;
+
+        match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::RichMessage { text, .. }
+            | OutboundResponse::ThreadReply { text, .. }
+            | OutboundResponse::ScheduledMessage { text, .. } => {
+                self.send_text(&room_id, &text, None).await?;
+            }
+            OutboundResponse::File {
+                filename,
+                data,
+                mime_type,
+                caption,
+            } => {
+                self.send_file(&room_id, filename, data, mime_type, caption, None)
+                    .await?;
+            }
+            _ => unreachable!("unsupported variants are rejected above"),
+        }
+
+        Ok(())
+    }
+
+    async fn fetch_history(
+        &self,
+        message: &InboundMessage,
+        limit: usize,
+    ) -> crate::Result<Vec<HistoryMessage>> {
+        let room_id = self.extract_room_id(message)?;
+        // Fetch one extra so the window still holds `limit` messages after
+        // dropping the trigger itself.
+        let capped_limit = limit.min(200) + 1;
+        let events = self.api.messages(room_id, capped_limit).await?;
+
+        // `/messages` with `dir=b` is newest first; drop the trigger and
+        // anything that arrived after it.
+        let older = match events
+            .iter()
+            .position(|event| event.event_id.as_deref() == Some(message.id.as_str()))
+        {
+            Some(index) => &events[index + 1..],
+            None => &events[..],
+        };
+
+        let bot_user_id = self.bot_user_id.get().map(|user_id| user_id.as_ref());
+        let display_names = self.display_name_cache.read().await;
+        let history = older
+            .iter()
+            .rev()
+            .filter(|event| event.event_type == "m.room.message")
+            .filter_map(|event| {
+                let content = message_body(event)?;
+                let is_bot = bot_user_id == Some(event.sender.as_str());
+                Some(HistoryMessage {
+                    author: if is_bot {
+                        "bot".to_string()
+                    } else {
+                        display_names
+                            .get(&event.sender)
+                            .cloned()
+                            .unwrap_or_else(|| event.sender.clone())
+                    },
+                    content,
+                    is_bot,
+                    timestamp: chrono::DateTime::from_timestamp_millis(event.origin_server_ts),
+                })
+            })
+            .take(limit)
+            .collect();
+
+        Ok(history)
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        self.api.whoami().await.map(|_| ())
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        if let Some(tx) = self.shutdown_tx.write().await.take() {
+            tx.send(()).await.ok();
+        }
+
+        if let Some(handle) = self.sync_task.write().await.take() {
+            handle.abort();
+        }
+
+        for (_, handle) in self.typing_tasks.write().await.drain() {
+            handle.abort();
+        }
+        self.streams.clear().await;
+
+        tracing::info!(adapter = %self.runtime_key, "matrix adapter shut down");
+        Ok(())
+    }
+}
+
+/// Everything the background `/sync` loop needs, detached from the adapter.
+struct SyncContext {
+    runtime_key: Arc<str>,
+    api: MatrixApi,
+    permissions: Arc<ArcSwap<MatrixPermissions>>,
+    bot_user_id: Arc<str>,
+    bot_display_name: Option<String>,
+    rooms: Arc<RwLock<RoomDirectory>>,
+    display_name_cache: Arc<RwLock<HashMap<String, String>>>,
+    inbound_tx: mpsc::Sender<InboundMessage>,
+}
+
+impl SyncContext {
+    /// Apply one `/sync` response. Timeline messages are only forwarded when
+    /// `deliver_messages` is set. Returns `false` once the inbound channel is
+    /// closed.
+    async fn handle_sync(&self, response: SyncResponse, deliver_messages: bool) -> bool {
+        for event in &response.account_data.events {
+            if event.event_type == "m.direct" {
+                self.apply_direct_account_data(&event.content).await;
+            }
+        }
+
+        for (room_id, invited) in &response.rooms.invite {
+            self.handle_invite(room_id, invited).await;
+        }
+
+        for (room_id, joined) in response.rooms.join {
+            self.apply_room_state(&room_id, &joined).await;
+
+            if !deliver_messages {
+                continue;
+            }
+            for event in &joined.timeline.events {
+                match event.event_type.as_str() {
+                    "m.room.message" => {
+                        if let Some(message) = self.build_inbound(&room_id, event).await
+                            && self.inbound_tx.send(message).await.is_err()
+                        {
+                            return false;
+                        }
+                    }
+                    "m.room.encrypted" => {
+                        let first_warning = self
+                            .rooms
+                            .write()
+                            .await
+                            .undecryptable_warned
+                            .insert(room_id.clone());
+                        if first_warning {
+                            tracing::warn!(
+                                adapter = %self.runtime_key,
+                                room_id = %room_id,
+                                "received an encrypted event the adapter cannot decrypt; point homeserver_url at an E2EE proxy such as Pantalaimon to use encrypted rooms"
+                            );
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        true
+    }
+
+    async fn apply_direct_account_data(&self, content: &serde_json::Value) {
+        let Ok(direct) = serde_json::from_value::<HashMap<String, Vec<String>>>(content.clone())
+        else {
+            tracing::debug!("failed to parse m.direct account data");
+            return;
+        };
+        let mut rooms = self.rooms.write().await;
+        rooms
+            .direct_rooms
+            .extend(direct.values().flatten().cloned());
+        rooms.direct_by_user = direct;
+    }
+
+    async fn apply_room_state(&self, room_id: &str, joined: &JoinedRoom) {
+        let mut rooms = self.rooms.write().await;
+        if let Some(count) = joined.summary.joined_member_count {
+            rooms.member_counts.insert(room_id.to_string(), count);
+        }
+        for event in joined.state.events.iter().chain(&joined.timeline.events) {
+            if event.event_type == "m.room.name"
+                && let Some(name) = event.content.get("name").and_then(|v| v.as_str())
+            {
+                rooms
+                    .room_names
+                    .insert(room_id.to_string(), name.to_string());
+            }
+        }
+    }
+
+    /// Join rooms we're invited to when the inviter is trusted: anyone in
+    /// `invite_allowed_users`, DM-allowed users inviting to a direct chat, or
+    /// anyone inviting us into a room named in a binding.
+    async fn handle_invite(&self, room_id: &str, invited: &InvitedRoom) {
+        if !self
+            .rooms
+            .write()
+            .await
+            .handled_invites
+            .insert(room_id.to_string())
+        {
+            return;
+        }
+
+        let Some(invite) = invited.invite_state.events.iter().find(|event| {
+            event.event_type == "m.room.member"
+                && event.state_key.as_deref() == Some(self.bot_user_id.as_ref())
+        }) else {
+            return;
+        };
+        let inviter = invite.sender.as_str();
+        let is_direct = invite
+            .content
+            .get("is_direct")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let permissions = self.permissions.load();
+        let trusted = permissions
+            .invite_allowed_users
+            .iter()
+            .any(|user| user == inviter)
+            || (is_direct
+                && permissions
+                    .dm_allowed_users
+                    .iter()
+                    .any(|user| user == inviter))
+            || permissions
+                .room_filter
+                .as_ref()
+                .is_some_and(|rooms| rooms.iter().any(|room| room == room_id));
+        if !trusted {
+            tracing::info!(
+                adapter = %self.runtime_key,
+                room_id,
+                inviter,
+                "ignoring matrix invite from untrusted user"
+            );
+            return;
+        }
+
+        match self.api.join(room_id).await {
+            Ok(()) => {
+                tracing::info!(adapter = %self.runtime_key, room_id, inviter, "joined matrix room");
+                if invited
+                    .invite_state
+                    .events
+                    .iter()
+                    .any(|event| event.event_type == "m.room.encryption")
+                {
+                    tracing::warn!(
+                        adapter = %self.runtime_key,
+                        room_id,
+                        "joined an end-to-end encrypted matrix room; the adapter cannot read or send encrypted messages unless homeserver_url points at an E2EE proxy such as Pantalaimon"
+                    );
+                }
+                if is_direct {
+                    self.rooms
+                        .write()
+                        .await
+                        .direct_rooms
+                        .insert(room_id.to_string());
+                }
+            }
+            Err(error) => {
+                tracing::warn!(adapter = %self.runtime_key, room_id, %error, "failed to join matrix room");
+                // Allow a retry on the next sync.
+                self.rooms.write().await.handled_invites.remove(room_id);
+            }
+        }
+    }
+
+    async fn build_inbound(&self, room_id: &str, event: &RoomEvent) -> Option<InboundMessage> {
+        if event.sender == self.bot_user_id.as_ref() {
+            return None;
+        }
+
+        let (is_direct, room_name) = {
+            let rooms = self.rooms.read().await;
+            (
+                rooms.is_direct(room_id),
+                rooms.room_names.get(room_id).cloned(),
+            )
+        };
+        let display_name = self.resolve_display_name(&event.sender).await;
+        let permissions = self.permissions.load();
+
+        let context = MessageBuildContext {
+            runtime_key: &self.runtime_key,
+            bot_user_id: &self.bot_user_id,
+            bot_display_name: self.bot_display_name.as_deref(),
+            permissions: &permissions,
+            is_direct,
+            api: &self.api,
+            display_name: display_name.as_deref(),
+            room_name: room_name.as_deref(),
+        };
+        let mut message = build_message_from_event(room_id, event, &context)?;
+
+        // Replies and thread messages count as directed at the bot when the
+        // event they point at is the bot's.
+        let already_mentioned = message
+            .metadata
+            .get("matrix_mentions_or_replies_to_bot")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        if !already_mentioned {
+            let related = message
+                .metadata
+                .get("matrix_in_reply_to")
+                .or_else(|| message.metadata.get("matrix_thread_root"))
+                .and_then(|v| v.as_str())
+                .map(String::from);
+            if let Some(related) = related
+                && let Ok(related_event) = self.api.get_event(room_id, &related).await
+                && related_event.sender == self.bot_user_id.as_ref()
+            {
+                message.metadata.insert(
+                    "matrix_mentions_or_replies_to_bot".into(),
+                    serde_json::json!(true),
+                );
+            }
+        }
+
+        Some(message)
+    }
+
+    async fn resolve_display_name(&self, user_id: &str) -> Option<String> {
+        if let Some(name) = self.display_name_cache.read().await.get(user_id).cloned() {
+            return Some(name);
+        }
+        let name = self.api.display_name(user_id).await?;
+        self.display_name_cache
+            .write()
+            .await
+            .insert(user_id.to_string(), name.clone());
+        Some(name)
+    }
+}
+
+/// Convert an `m.room.message` event into an [`InboundMessage`], applying
+/// permission filters.
+///
+/// Returns `None` (message dropped) when:
+/// - The event was sent by the bot, or is an `m.notice` (bot traffic by
+///   convention) or an edit of an earlier message.
+/// - The room is a direct chat and the sender isn't in `dm_allowed_users`
+///   (fail-closed when the list is empty).
+/// - The room isn't direct and a room filter is set that doesn't list it.
+/// - The event has no usable body.
+///
+/// When a message passes, these metadata keys are set: `message_id`,
+/// `matrix_event_id`, `matrix_room_id`, `matrix_is_direct`,
+/// `matrix_thread_root` and `matrix_in_reply_to` (when present),
+/// `sender_display_name`, `matrix_room_name` and `channel_name` (when known),
+/// and `matrix_mentions_or_replies_to_bot`.
+fn build_message_from_event(
+    room_id: &str,
+    event: &RoomEvent,
+    context: &MessageBuildContext<'_>,
+) -> Option<InboundMessage> {
+    if event.sender == context.bot_user_id {
+        return None;
+    }
+    let event_id = event.event_id.as_deref()?;
+    let msgtype = event.content.get("msgtype").and_then(|v| v.as_str())?;
+    if msgtype == "m.notice" {
+        return None;
+    }
+
+    let relates_to = event.content.get("m.relates_to");
+    let rel_type = relates_to
+        .and_then(|r| r.get("rel_type"))
+        .and_then(|v| v.as_str());
+    if rel_type == Some("m.replace") {
+        return None;
+    }
+
+    if context.is_direct {
+        if !context
+            .permissions
+            .dm_allowed_users
+            .iter()
+            .any(|user| user == &event.sender)
+        {
+            return None;
+        }
+    } else if !context.permissions.allows_room(room_id) {
+        return None;
+    }
+
+    let body = message_body(event)?;
+    let content = match media_attachment(event, context.api) {
+        Some(attachment) => {
+            // For media, `body` is the filename unless a caption was given.
+            let caption = (event.content.get("filename").is_some() && body != attachment.filename)
+                .then(|| body.clone());
+            MessageContent::Media {
+                text: caption,
+                attachments: vec![attachment],
+            }
+        }
+        None => MessageContent::Text(body.clone()),
+    };
+
+    let thread_root = (rel_type == Some("m.thread"))
+        .then(|| {
+            relates_to
+                .and_then(|r| r.get("event_id"))
+                .and_then(|v| v.as_str())
+        })
+        .flatten();
+    let in_reply_to = relates_to
+        .and_then(|r| r.get("m.in_reply_to"))
+        .and_then(|r| r.get("event_id"))
+        .and_then(|v| v.as_str())
+        // In threads, clients set `m.in_reply_to` as a fallback pointing at
+        // the latest thread event; only a real reply has `is_falling_back` unset.
+        .filter(|_| {
+            relates_to
+                .and_then(|r| r.get("is_falling_back"))
+                .and_then(|v| v.as_bool())
+                != Some(true)
+        });
+
+    let mut metadata = HashMap::new();
+    metadata.insert(
+        crate::metadata_keys::MESSAGE_ID.into(),
+        serde_json::json!(event_id),
+    );
+    metadata.insert("matrix_event_id".into(), serde_json::json!(event_id));
+    metadata.insert("matrix_room_id".into(), serde_json::json!(room_id));
+    metadata.insert(
+        "matrix_is_direct".into(),
+        serde_json::json!(context.is_direct),
+    );
+    if let Some(root) = thread_root {
+        metadata.insert("matrix_thread_root".into(), serde_json::json!(root));
+    }
+    if let Some(reply_to) = in_reply_to {
+        metadata.insert("matrix_in_reply_to".into(), serde_json::json!(reply_to));
+    }
+    if let Some(display_name) = context.display_name {
+        metadata.insert(
+            "sender_display_name".into(),
+            serde_json::json!(display_name),
+        );
+    }
+    if let Some(room_name) = context.room_name {
+        metadata.insert("matrix_room_name".into(), serde_json::json!(room_name));
+        metadata.insert(
+            crate::metadata_keys::CHANNEL_NAME.into(),
+            serde_json::json!(room_name),
+        );
+    }
+
+    let mentioned_by_intent = event
+        .content
+        .get("m.mentions")
+        .and_then(|m| m.get("user_ids"))
+        .and_then(|v| v.as_array())
+        .is_some_and(|ids| {
+            ids.iter()
+                .any(|id| id.as_str() == Some(context.bot_user_id))
+        });
+    let mentioned_in_body = body.contains(context.bot_user_id)
+        || context
+            .bot_display_name
+            .is_some_and(|name| !name.is_empty() && body.contains(name));
+    metadata.insert(
+        "matrix_mentions_or_replies_to_bot".into(),
+        serde_json::json!(context.is_direct || mentioned_by_intent || mentioned_in_body),
+    );
+
+    let conversation_id =
+        apply_runtime_adapter_to_conversation_id(context.runtime_key, format!("matrix:{room_id}"));
+
+    Some(InboundMessage {
+        id: event_id.to_string(),
+        source: "matrix".into(),
+        adapter: Some(context.runtime_key.to_string()),
+        conversation_id,
+        sender_id: event.sender.clone(),
+        agent_id: None,
+        content,
+        timestamp: chrono::DateTime::from_timestamp_millis(event.origin_server_ts)
+            .unwrap_or_else(chrono::Utc::now),
+        metadata,
+        formatted_author: context.display_name.map(String::from),
+    })
+}
+
+/// Plain-text body of a message event with any rich-reply fallback removed.
+fn message_body(event: &RoomEvent) -> Option<String> {
+    let body = event.content.get("body").and_then(|v| v.as_str())?;
+    let body = strip_reply_fallback(body);
+    let body = match event.content.get("msgtype").and_then(|v| v.as_str()) {
+        Some("m.emote") => format!("* {body}"),
+        _ => body.to_string(),
+    };
+    (!body.trim().is_empty()).then_some(body)
+}
+
+/// Strip the `> <@user:server> quoted text` block that older clients prepend
+/// to replies.
+fn strip_reply_fallback(body: &str) -> &str {
+    if !body.starts_with("> <") {
+        return body;
+    }
+    let mut rest = body;
+    while let Some(line_end) = rest.find('\n') {
+        if !rest.starts_with('>') {
+            break;
+        }
+        rest = &rest[line_end + 1..];
+    }
+    if rest.starts_with('>') {
+        // Quote with no reply text after it.
+        return "";
+    }
+    rest.strip_prefix('\n').unwrap_or(rest)
+}
+
+/// Attachment for `m.image`/`m.file`/`m.video`/`m.audio` events, downloaded
+/// through the authenticated media endpoint.
+fn media_attachment(event: &RoomEvent, api: &MatrixApi) -> Option<Attachment> {
+    let msgtype = event.content.get("msgtype").and_then(|v| v.as_str())?;
+    if !matches!(msgtype, "m.image" | "m.file" | "m.video" | "m.audio") {
+        return None;
+    }
+    let mxc = event.content.get("url").and_then(|v| v.as_str())?;
+    let (server_name, media_id) = parse_mxc_uri(mxc)?;
+    let info = event.content.get("info");
+
+    let filename = event
+        .content
+        .get("filename")
+        .or_else(|| event.content.get("body"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("attachment")
+        .to_string();
+
+    Some(Attachment {
+        filename,
+        mime_type: info
+            .and_then(|i| i.get("mimetype"))
+            .and_then(|v| v.as_str())
+            .unwrap_or("application/octet-stream")
+            .to_string(),
+        url: api
+            .url(
+                &["_matrix", "client", "v1", "media", "download"],
+                &[server_name, media_id],
+            )
+            .to_string(),
+        size_bytes: info.and_then(|i| i.get("size")).and_then(|v| v.as_u64()),
+        auth_header: Some(format!("Bearer {}", api.access_token)),
+    })
+}
+
+/// Split `mxc://server/media_id` into its parts.
+fn parse_mxc_uri(uri: &str) -> Option<(&str, &str)> {
+    let (server_name, media_id) = uri.strip_prefix("mxc://")?.split_once('/')?;
+    (!server_name.is_empty() && !media_id.is_empty() && !media_id.contains('/'))
+        .then_some((server_name, media_id))
+}
+
+fn thread_relation(thread_root: &str, reply_to: &str) -> serde_json::Value {
+    serde_json::json!({
+        "rel_type": "m.thread",
+        "event_id": thread_root,
+        "is_falling_back": true,
+        "m.in_reply_to": { "event_id": reply_to },
+    })
+}
+
+fn text_content(text: &str, relation: Option<&serde_json::Value>) -> serde_json::Value {
+    let mut content = serde_json::json!({ "msgtype": "m.text", "body": text });
+    if let Some(relation) = relation {
+        content["m.relates_to"] = relation.clone();
+    }
+    content
+}
+
+fn file_content(
+    filename: &str,
+    mime_type: &str,
+    size: usize,
+    content_uri: &str,
+    caption: Option<&str>,
+    relation: Option<&serde_json::Value>,
+) -> serde_json::Value {
+    let msgtype = match mime_type.split('/').next() {
+        Some("image") => "m.image",
+        Some("video") => "m.video",
+        Some("audio") => "m.audio",
+        _ => "m.file",
+    };
+    // A body that differs from `filename` is rendered as a caption.
+    let mut content = serde_json::json!({
+        "msgtype": msgtype,
+        "body": caption.filter(|c| !c.trim().is_empty()).unwrap_or(filename),
+        "filename": filename,
+        "url": content_uri,
+        "info": { "mimetype": mime_type, "size": size },
+    });
+    if let Some(relation) = relation {
+        content["m.relates_to"] = relation.clone();
+    }
+    content
+}
+
+// --- Client-Server API ---
+
+/// Thin client for the Matrix Client-Server API.
+///
+/// Errors are classified for broadcast retries: network failures, 429, and
+/// 5xx are transient; other non-success statuses are permanent.
+#[derive(Clone)]
+struct MatrixApi {
+    client: Client,
+    homeserver_url: Url,
+    access_token: Arc<str>,
+}
+
+impl MatrixApi {
+    fn url(&self, prefix: &[&str], segments: &[&str]) -> Url {
+        let mut url = self.homeserver_url.clone();
+        url.path_segments_mut()
+            .expect("homeserver_url is a valid base URL")
+            .pop_if_empty()
+            .extend(prefix)
+            .extend(segments);
+        url
+    }
+
+    fn client_url(&self, segments: &[&str]) -> Url {
+        self.url(&["_matrix", "client", "v3"], segments)
+    }
+
+    async fn execute<T: DeserializeOwned>(
+        &self,
+        request: RequestBuilder,
+        operation: &str,
+    ) -> crate::Result<T> {
+        let response = request
+            .bearer_auth(self.access_token.as_ref())
+            .send()
+            .await
+            .map_err(|error| {
+                mark_retryable_broadcast(
+                    anyhow::Error::new(error).context(format!("matrix {operation} request failed")),
+                )
+            })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let error = anyhow::anyhow!(
+                "matrix {operation} failed with status {}: {body}",
+                status.as_u16()
+            );
+            return Err(
+                if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                    mark_retryable_broadcast(error)
+                } else {
+                    mark_permanent_broadcast(error)
+                },
+            );
+        }
+
+        response
+            .json()
+            .await
+            .with_context(|| format!("failed to parse matrix {operation} response"))
+            .map_err(Into::into)
+    }
+
+    async fn whoami(&self) -> crate::Result<String> {
+        let response: WhoAmIResponse = self
+            .execute(
+                self.client.get(self.client_url(&["account", "whoami"])),
+                "whoami",
+            )
+            .await?;
+        Ok(response.user_id)
+    }
+
+    /// Display name from the user's global profile. `None` on any failure.
+    async fn display_name(&self, user_id: &str) -> Option<String> {
+        let response: Result<ProfileResponse, _> = self
+            .execute(
+                self.client
+                    .get(self.client_url(&["profile", user_id, "displayname"])),
+                "profile",
+            )
+            .await;
+        match response {
+            Ok(profile) => profile.displayname.filter(|name| !name.is_empty()),
+            Err(error) => {
+                tracing::debug!(%error, user_id, "failed to fetch matrix display name");
+                None
+            }
+        }
+    }
+
+    async fn sync(&self, since: Option<&str>, timeout_ms: u64) -> crate::Result<SyncResponse> {
+        let mut url = self.client_url(&["sync"]);
+        {
+            let mut query = url.query_pairs_mut();
+            query.append_pair("timeout", &timeout_ms.to_string());
+            match since {
+                Some(since) => {
+                    query.append_pair("since", since);
+                }
+                // Only the position matters on the first sync.
+                None => {
+                    query.append_pair("filter", r#"{"room":{"timeline":{"limit":1}}}"#);
+                }
+            }
+        }
+        self.execute(
+            self.client
+                .get(url)
+                .timeout(Duration::from_millis(timeout_ms) + HTTP_TIMEOUT),
+            "sync",
+        )
+        .await
+    }
+
+    async fn join(&self, room_id: &str) -> crate::Result<()> {
+        let _: serde_json::Value = self
+            .execute(
+                self.client
+                    .post(self.client_url(&["join", room_id]))
+                    .json(&serde_json::json!({})),
+                "join",
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn send_event(
+        &self,
+        room_id: &str,
+        event_type: &str,
+        content: &serde_json::Value,
+    ) -> crate::Result<String> {
+        let txn_id = uuid::Uuid::new_v4().to_string();
+        let response: EventIdResponse = self
+            .execute(
+                self.client
+                    .put(self.client_url(&["rooms", room_id, "send", event_type, &txn_id]))
+                    .json(content),
+                "send",
+            )
+            .await?;
+        Ok(response.event_id)
+    }
+
+    async fn redact(&self, room_id: &str, event_id: &str) -> crate::Result<()> {
+        let txn_id = uuid::Uuid::new_v4().to_string();
+        let _: EventIdResponse = self
+            .execute(
+                self.client
+                    .put(self.client_url(&["rooms", room_id, "redact", event_id, &txn_id]))
+                    .json(&serde_json::json!({})),
+                "redact",
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn set_typing(&self, user_id: &str, room_id: &str, typing: bool) -> crate::Result<()> {
+        let body = if typing {
+            serde_json::json!({ "typing": true, "timeout": TYPING_TIMEOUT_MS })
+        } else {
+            serde_json::json!({ "typing": false })
+        };
+        let _: serde_json::Value = self
+            .execute(
+                self.client
+                    .put(self.client_url(&["rooms", room_id, "typing", user_id]))
+                    .json(&body),
+                "typing",
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Upload to the media repository and return the `mxc://` content URI.
+    async fn upload(
+        &self,
+        filename: &str,
+        mime_type: &str,
+        data: Vec<u8>,
+    ) -> crate::Result<String> {
+        let mut url = self.url(&["_matrix", "media", "v3", "upload"], &[]);
+        url.query_pairs_mut().append_pair("filename", filename);
+        let response: UploadResponse = self
+            .execute(
+                self.client
+                    .post(url)
+                    .header(reqwest::header::CONTENT_TYPE, mime_type)
+                    .body(data),
+                "upload",
+            )
+            .await?;
+        Ok(response.content_uri)
+    }
+
+    async fn get_event(&self, room_id: &str, event_id: &str) -> crate::Result<RoomEvent> {
+        self.execute(
+            self.client
+                .get(self.client_url(&["rooms", room_id, "event", event_id])),
+            "get event",
+        )
+        .await
+    }
+
+    /// Latest `limit` message events in `room_id`, newest first.
+    async fn messages(&self, room_id: &str, limit: usize) -> crate::Result<Vec<RoomEvent>> {
+        let mut url = self.client_url(&["rooms", room_id, "messages"]);
+        url.query_pairs_mut()
+            .append_pair("dir", "b")
+            .append_pair("limit", &limit.to_string())
+            .append_pair("filter", r#"{"types":["m.room.message"]}"#);
+        let response: MessagesResponse = self.execute(self.client.get(url), "messages").await?;
+        Ok(response.chunk)
+    }
+
+    async fn resolve_alias(&self, alias: &str) -> crate::Result<String> {
+        let response: AliasResponse = self
+            .execute(
+                self.client
+                    .get(self.client_url(&["directory", "room", alias])),
+                "alias lookup",
+            )
+            .await?;
+        Ok(response.room_id)
+    }
+
+    async fn create_direct_room(&self, user_id: &str) -> crate::Result<String> {
+        let response: CreateRoomResponse = self
+            .execute(
+                self.client
+                    .post(self.client_url(&["createRoom"]))
+                    .json(&serde_json::json!({
+                        "is_direct": true,
+                        "invite": [user_id],
+                        "preset": "trusted_private_chat",
+                    })),
+                "createRoom",
+            )
+            .await?;
+        Ok(response.room_id)
+    }
+}
+
+// --- API Types ---
+
+#[derive(Debug, Deserialize)]
+struct WhoAmIResponse {
+    user_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProfileResponse {
+    #[serde(default)]
+    displayname: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EventIdResponse {
+    event_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct UploadResponse {
+    content_uri: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AliasResponse {
+    room_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CreateRoomResponse {
+    room_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessagesResponse {
+    #[serde(default)]
+    chunk: Vec<RoomEvent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SyncResponse {
+    next_batch: String,
+    #[serde(default)]
+    rooms: SyncRooms,
+    #[serde(default)]
+    account_data: EventList,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct SyncRooms {
+    #[serde(default)]
+    join: HashMap<String, JoinedRoom>,
+    #[serde(default)]
+    invite: HashMap<String, InvitedRoom>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct JoinedRoom {
+    #[serde(default)]
+    summary: RoomSummary,
+    #[serde(default)]
+    state: EventList,
+    #[serde(default)]
+    timeline: EventList,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct RoomSummary {
+    #[serde(rename = "m.joined_member_count", default)]
+    joined_member_count: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct InvitedRoom {
+    #[serde(default)]
+    invite_state: EventList,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct EventList {
+    #[serde(default)]
+    events: Vec<RoomEvent>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct RoomEvent {
+    #[serde(rename = "type")]
+    event_type: String,
+    #[serde(default)]
+    event_id: Option<String>,
+    #[serde(default)]
+    sender: String,
+    #[serde(default)]
+    origin_server_ts: i64,
+    #[serde(default)]
+    content: serde_json::Value,
+    #[serde(default)]
+    state_key: Option<String>,
+}
+
+/// Split `text` into chunks no longer than `max_len` bytes.
+///
+/// Splits preferentially on newlines, then on spaces, and falls back to a
+/// hard character-boundary break. Leading whitespace is stripped from each
+/// chunk after a split.
+fn split_message(text: &str, max_len: usize) -> Vec<String> {
+    if text.len() <= max_len {
+        return vec![text.to_string()];
+    }
+
+    let mut chunks = Vec::new();
+    let mut remaining = text;
+
+    while !remaining.is_empty() {
+        if remaining.len() <= max_len {
+            chunks.push(remaining.to_string());
+            break;
+        }
+
+        let search_end = remaining.floor_char_boundary(max_len);
+        let search_region = &remaining[..search_end];
+        let break_point = search_region
+            .rfind('\n')
+            .or_else(|| search_region.rfind(' '))
+            .filter(|&pos| pos > 0)
+            .unwrap_or(search_end);
+
+        let end = remaining.floor_char_boundary(break_point);
+        chunks.push(remaining[..end].to_string());
+        remaining = remaining[end..].trim_start_matches('\n').trim_start();
+    }
+
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::Router;
+    use axum::extract::{Path, State};
+    use axum::routing::{get, post, put};
+    use futures::StreamExt as _;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    // --- helpers ---
+
+    const BOT: &str = "@bot:example.org";
+    const ROOM: &str = "!room:example.org";
+
+    fn api(homeserver_url: &str) -> MatrixApi {
+        MatrixApi {
+            client: Client::new(),
+            homeserver_url: Url::parse(homeserver_url).unwrap(),
+            access_token: Arc::from("token"),
+        }
+    }
+
+    fn text_event(sender: &str, body: &str) -> RoomEvent {
+        RoomEvent {
+            event_type: "m.room.message".into(),
+            event_id: Some("$event1".into()),
+            sender: sender.into(),
+            origin_server_ts: 1_700_000_000_000,
+            content: serde_json::json!({ "msgtype": "m.text", "body": body }),
+            state_key: None,
+        }
+    }
+
+    fn build(
+        event: &RoomEvent,
+        permissions: &MatrixPermissions,
+        is_direct: bool,
+    ) -> Option<InboundMessage> {
+        let api = api("https://matrix.example.org");
+        let context = MessageBuildContext {
+            runtime_key: "matrix",
+            bot_user_id: BOT,
+            bot_display_name: Some("Spacebot"),
+            permissions,
+            is_direct,
+            api: &api,
+            display_name: Some("Alice"),
+            room_name: Some("ops"),
+        };
+        build_message_from_event(ROOM, event, &context)
+    }
+
+    fn meta_bool(message: &InboundMessage, key: &str) -> Option<bool> {
+        message.metadata.get(key).and_then(|v| v.as_bool())
+    }
+
+    // --- build_message_from_event ---
+
+    #[test]
+    fn builds_room_message_with_metadata() {
+        let message = build(
+            &text_event("@alice:example.org", "hello"),
+            &MatrixPermissions::default(),
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(message.id, "$event1");
+        assert_eq!(message.source, "matrix");
+        assert_eq!(message.conversation_id, format!("matrix:{ROOM}"));
+        assert_eq!(message.sender_id, "@alice:example.org");
+        assert_eq!(message.metadata["matrix_room_id"], ROOM);
+        assert_eq!(message.metadata[crate::metadata_keys::CHANNEL_NAME], "ops");
+        assert_eq!(
+            meta_bool(&message, "matrix_mentions_or_replies_to_bot"),
+            Some(false)
+        );
+        assert!(matches!(message.content, MessageContent::Text(ref text) if text == "hello"));
+    }
+
+    #[test]
+    fn drops_own_notices_and_edits() {
+        let permissions = MatrixPermissions::default();
+        assert!(build(&text_event(BOT, "echo"), &permissions, false).is_none());
+
+        let mut notice = text_event("@alice:example.org", "beep");
+        notice.content["msgtype"] = "m.notice".into();
+        assert!(build(&notice, &permissions, false).is_none());
+
+        let mut edit = text_event("@alice:example.org", "* fixed");
+        edit.content["m.relates_to"] =
+            serde_json::json!({ "rel_type": "m.replace", "event_id": "$old" });
+        assert!(build(&edit, &permissions, false).is_none());
+    }
+
+    #[test]
+    fn room_filter_blocks_unlisted_rooms() {
+        let permissions = MatrixPermissions {
+            room_filter: Some(vec!["!other:example.org".into()]),
+            ..Default::default()
+        };
+        assert!(build(&text_event("@alice:example.org", "hi"), &permissions, false).is_none());
+
+        let permissions = MatrixPermissions {
+            room_filter: Some(vec![ROOM.into()]),
+            ..Default::default()
+        };
+        assert!(build(&text_event("@alice:example.org", "hi"), &permissions, false).is_some());
+    }
+
+    #[test]
+    fn direct_messages_fail_closed() {
+        let event = text_event("@alice:example.org", "hi");
+        assert!(build(&event, &MatrixPermissions::default(), true).is_none());
+
+        let permissions = MatrixPermissions {
+            dm_allowed_users: vec!["@alice:example.org".into()],
+            ..Default::default()
+        };
+        let message = build(&event, &permissions, true).unwrap();
+        assert_eq!(meta_bool(&message, "matrix_is_direct"), Some(true));
+        assert_eq!(
+            meta_bool(&message, "matrix_mentions_or_replies_to_bot"),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn detects_mentions_by_intent_and_body() {
+        let permissions = MatrixPermissions::default();
+
+        let mut intent = text_event("@alice:example.org", "hey you");
+        intent.content["m.mentions"] = serde_json::json!({ "user_ids": [BOT] });
+        let message = build(&intent, &permissions, false).unwrap();
+        assert_eq!(
+            meta_bool(&message, "matrix_mentions_or_replies_to_bot"),
+            Some(true)
+        );
+
+        let by_name = text_event("@alice:example.org", "Spacebot: status?");
+        let message = build(&by_name, &permissions, false).unwrap();
+        assert_eq!(
+            meta_bool(&message, "matrix_mentions_or_replies_to_bot"),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn thread_fallback_reply_is_not_a_reply() {
+        let mut event = text_event("@alice:example.org", "in thread");
+        event.content["m.relates_to"] = serde_json::json!({
+            "rel_type": "m.thread",
+            "event_id": "$root",
+            "is_falling_back": true,
+            "m.in_reply_to": { "event_id": "$latest" },
+        });
+        let message = build(&event, &MatrixPermissions::default(), false).unwrap();
+        assert_eq!(message.metadata["matrix_thread_root"], "$root");
+        assert!(!message.metadata.contains_key("matrix_in_reply_to"));
+    }
+
+    #[test]
+    fn media_event_becomes_authenticated_attachment() {
+        let mut event = text_event("@alice:example.org", "look at this");
+        event.content = serde_json::json!({
+            "msgtype": "m.image",
+            "body": "look at this",
+            "filename": "cat.png",
+            "url": "mxc://example.org/abc123",
+            "info": { "mimetype": "image/png", "size": 42 },
+        });
+        let message = build(&event, &MatrixPermissions::default(), false).unwrap();
+
+        let MessageContent::Media { text, attachments } = message.content else {
+            panic!("expected media content");
+        };
+        assert_eq!(text.as_deref(), Some("look at this"));
+        assert_eq!(attachments[0].filename, "cat.png");
+        assert_eq!(attachments[0].mime_type, "image/png");
+        assert_eq!(attachments[0].size_bytes, Some(42));
+        assert_eq!(
+            attachments[0].url,
+            "https://matrix.example.org/_matrix/client/v1/media/download/example.org/abc123"
+        );
+        assert_eq!(attachments[0].auth_header.as_deref(), Some("Bearer token"));
+    }
+
+    // --- pure helpers ---
+
+    #[test]
+    fn strips_reply_fallback() {
+        assert_eq!(
+            strip_reply_fallback("> <@alice:example.org> original\n> more\n\nmy reply"),
+            "my reply"
+        );
+        assert_eq!(strip_reply_fallback("> just a quote"), "> just a quote");
+        assert_eq!(strip_reply_fallback("plain"), "plain");
+    }
+
+    #[test]
+    fn parses_mxc_uris() {
+        assert_eq!(
+            parse_mxc_uri("mxc://example.org/abc"),
+            Some(("example.org", "abc"))
+        );
+        assert_eq!(parse_mxc_uri("https://example.org/abc"), None);
+        assert_eq!(parse_mxc_uri("mxc://example.org/"), None);
+        assert_eq!(parse_mxc_uri("mxc://example.org/a/b"), None);
+    }
+
+    #[test]
+    fn file_content_picks_msgtype_from_mime() {
+        let content = file_content("a.png", "image/png", 3, "mxc://s/id", None, None);
+        assert_eq!(content["msgtype"], "m.image");
+        assert_eq!(content["body"], "a.png");
+
+        let content = file_content(
+            "a.pdf",
+            "application/pdf",
+            3,
+            "mxc://s/id",
+            Some("notes"),
+            None,
+        );
+        assert_eq!(content["msgtype"], "m.file");
+        assert_eq!(content["body"], "notes");
+        assert_eq!(content["filename"], "a.pdf");
+    }
+
+    #[test]
+    fn client_urls_keep_homeserver_path_prefix() {
+        let api = api("http://127.0.0.1:8009/proxy/");
+        assert_eq!(
+            api.client_url(&["rooms", ROOM, "send", "m.room.message", "txn"])
+                .as_str(),
+            "http://127.0.0.1:8009/proxy/_matrix/client/v3/rooms/!room:example.org/send/m.room.message/txn"
+        );
+    }
+
+    // --- homeserver stand-in ---
+
+    #[derive(Default)]
+    struct Homeserver {
+        sync_calls: AtomicUsize,
+        sent: std::sync::Mutex<Vec<(String, String, serde_json::Value)>>,
+        uploads: std::sync::Mutex<Vec<(Option<String>, usize)>>,
+        created_rooms: std::sync::Mutex<Vec<serde_json::Value>>,
+    }
+
+    /// Serve the slice of the Client-Server API the adapter uses. The first
+    /// `/sync` returns only a position, the second returns `timeline`, and
+    /// later ones idle briefly with nothing new.
+    async fn spawn_homeserver(timeline: Vec<serde_json::Value>) -> (String, Arc<Homeserver>) {
+        let state = Arc::new(Homeserver::default());
+        let timeline = Arc::new(timeline);
+
+        let app = Router::new()
+            .route(
+                "/_matrix/client/v3/account/whoami",
+                get(|| async { axum::Json(serde_json::json!({ "user_id": BOT })) }),
+            )
+            .route(
+                "/_matrix/client/v3/profile/{user_id}/displayname",
+                get(|Path(user_id): Path<String>| async move {
+                    let name = if user_id == BOT { "Spacebot" } else { "Alice" };
+                    axum::Json(serde_json::json!({ "displayname": name }))
+                }),
+            )
+            .route(
+                "/_matrix/client/v3/sync",
+                get(move |State(state): State<Arc<Homeserver>>| {
+                    let timeline = timeline.clone();
+                    async move {
+                        let call = state.sync_calls.fetch_add(1, Ordering::SeqCst);
+                        if call == 1 {
+                            return axum::Json(serde_json::json!({
+                                "next_batch": "s2",
+                                "rooms": { "join": { ROOM: {
+                                    "summary": { "m.joined_member_count": 5 },
+                                    "timeline": { "events": *timeline },
+                                } } },
+                            }));
+                        }
+                        if call > 1 {
+                            tokio::time::sleep(Duration::from_millis(50)).await;
+                        }
+                        axum::Json(serde_json::json!({ "next_batch": format!("s{}", call + 1) }))
+                    }
+                }),
+            )
+            .route(
+                "/_matrix/client/v3/rooms/{room_id}/send/{event_type}/{txn_id}",
+                put(
+                    |State(state): State<Arc<Homeserver>>,
+                     Path((room_id, event_type, _txn_id)): Path<(String, String, String)>,
+                     axum::Json(body): axum::Json<serde_json::Value>| async move {
+                        let mut sent = state.sent.lock().unwrap();
+                        sent.push((room_id, event_type, body));
+                        axum::Json(
+                            serde_json::json!({ "event_id": format!("$sent{}", sent.len()) }),
+                        )
+                    },
+                ),
+            )
+            .route(
+                "/_matrix/client/v3/rooms/{room_id}/event/{event_id}",
+                get(
+                    |Path((_room_id, event_id)): Path<(String, String)>| async move {
+                        let sender = if event_id == "$bot-message" {
+                            BOT
+                        } else {
+                            "@alice:example.org"
+                        };
+                        axum::Json(serde_json::json!({
+                            "type": "m.room.message",
+                            "event_id": event_id,
+                            "sender": sender,
+                            "content": { "msgtype": "m.text", "body": "earlier" },
+                        }))
+                    },
+                ),
+            )
+            .route(
+                "/_matrix/media/v3/upload",
+                post(
+                    |State(state): State<Arc<Homeserver>>,
+                     axum::extract::Query(query): axum::extract::Query<HashMap<String, String>>,
+                     body: axum::body::Bytes| async move {
+                        state
+                            .uploads
+                            .lock()
+                            .unwrap()
+                            .push((query.get("filename").cloned(), body.len()));
+                        axum::Json(serde_json::json!({ "content_uri": "mxc://example.org/up1" }))
+                    },
+                ),
+            )
+            .route(
+                "/_matrix/client/v3/createRoom",
+                post(
+                    |State(state): State<Arc<Homeserver>>,
+                     axum::Json(body): axum::Json<serde_json::Value>| async move {
+                        state.created_rooms.lock().unwrap().push(body);
+                        axum::Json(serde_json::json!({ "room_id": "!dm:example.org" }))
+                    },
+                ),
+            )
+            .with_state(state.clone());
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.ok();
+        });
+        (format!("http://{address}"), state)
+    }
+
+    fn adapter(homeserver_url: &str, permissions: MatrixPermissions) -> MatrixAdapter {
+        MatrixAdapter::new(
+            "matrix",
+            homeserver_url,
+            "token",
+            1024,
+            Arc::new(ArcSwap::from_pointee(permissions)),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn sync_delivers_new_messages_and_replies_in_threads() {
+        let (url, homeserver) = spawn_homeserver(vec![
+            serde_json::json!({
+                "type": "m.room.message",
+                "event_id": "$question",
+                "sender": "@alice:example.org",
+                "origin_server_ts": 1_700_000_000_000_i64,
+                "content": {
+                    "msgtype": "m.text",
+                    "body": "what's the status?",
+                    "m.relates_to": {
+                        "rel_type": "m.thread",
+                        "event_id": "$bot-message",
+                        "is_falling_back": true,
+                        "m.in_reply_to": { "event_id": "$bot-message" },
+                    },
+                },
+            }),
+            serde_json::json!({
+                "type": "m.room.encrypted",
+                "event_id": "$cipher",
+                "sender": "@alice:example.org",
+                "content": { "algorithm": "m.megolm.v1.aes-sha2" },
+            }),
+        ])
+        .await;
+        let adapter = adapter(&url, MatrixPermissions::default());
+
+        let mut stream = adapter.start().await.unwrap();
+        let message = tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(message.id, "$question");
+        assert_eq!(message.metadata["matrix_thread_root"], "$bot-message");
+        assert_eq!(message.formatted_author.as_deref(), Some("Alice"));
+        // The thread root is the bot's own message.
+        assert_eq!(
+            meta_bool(&message, "matrix_mentions_or_replies_to_bot"),
+            Some(true)
+        );
+
+        adapter
+            .respond(&message, OutboundResponse::Text("all green".into()))
+            .await
+            .unwrap();
+        adapter.shutdown().await.unwrap();
+
+        let sent = homeserver.sent.lock().unwrap();
+        let (room_id, event_type, body) = sent.last().unwrap();
+        assert_eq!(room_id, ROOM);
+        assert_eq!(event_type, "m.room.message");
+        assert_eq!(body["body"], "all green");
+        assert_eq!(body["m.relates_to"]["rel_type"], "m.thread");
+        assert_eq!(body["m.relates_to"]["event_id"], "$bot-message");
+        assert_eq!(
+            body["m.relates_to"]["m.in_reply_to"]["event_id"],
+            "$question"
+        );
+    }
+
+    #[tokio::test]
+    async fn file_responses_upload_then_send() {
+        let (url, homeserver) = spawn_homeserver(Vec::new()).await;
+        let adapter = adapter(&url, MatrixPermissions::default());
+
+        let mut message = InboundMessage::empty();
+        message.id = "$trigger".into();
+        message
+            .metadata
+            .insert("matrix_room_id".into(), serde_json::json!(ROOM));
+
+        adapter
+            .respond(
+                &message,
+                OutboundResponse::File {
+                    filename: "report.txt".into(),
+                    data: b"hello".to_vec(),
+                    mime_type: "text/plain".into(),
+                    caption: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let too_large = adapter
+            .respond(
+                &message,
+                OutboundResponse::File {
+                    filename: "big.bin".into(),
+                    data: vec![0; 2048],
+                    mime_type: "application/octet-stream".into(),
+                    caption: None,
+                },
+            )
+            .await;
+        assert!(too_large.is_err());
+
+        assert_eq!(
+            *homeserver.uploads.lock().unwrap(),
+            vec![(Some("report.txt".to_string()), 5)]
+        );
+        let sent = homeserver.sent.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        let (_, _, body) = &sent[0];
+        assert_eq!(body["msgtype"], "m.file");
+        assert_eq!(body["url"], "mxc://example.org/up1");
+        assert_eq!(body["info"]["size"], 5);
+    }
+
+    #[tokio::test]
+    async fn broadcast_to_user_creates_and_reuses_direct_room() {
+        let (url, homeserver) = spawn_homeserver(Vec::new()).await;
+        let adapter = adapter(&url, MatrixPermissions::default());
+
+        for text in ["first", "second"] {
+            adapter
+                .broadcast("dm:@alice:example.org", OutboundResponse::Text(text.into()))
+                .await
+                .unwrap();
+        }
+
+        let unsupported = adapter
+            .broadcast(ROOM, OutboundResponse::Reaction("👍".into()))
+            .await;
+        assert!(unsupported.is_err());
+
+        let created = homeserver.created_rooms.lock().unwrap();
+        assert_eq!(created.len(), 1);
+        assert_eq!(created[0]["is_direct"], true);
+        assert_eq!(created[0]["invite"][0], "@alice:example.org");
+
+        let sent = homeserver.sent.lock().unwrap();
+        assert_eq!(sent.len(), 2);
+        assert!(
+            sent.iter()
+                .all(|(room_id, _, _)| room_id == "!dm:example.org")
+        );
+    }
+}
@@ -2,6 +2,7 @@
 
 use crate::config::MattermostPermissions;
 use crate::messaging::apply_runtime_adapter_to_conversation_id;
+use crate::messaging::streaming::StreamRegistry;
 use crate::messaging::traits::{HistoryMessage, InboundStream, Messaging};
 use crate::{InboundMessage, MessageContent, OutboundResponse, StatusUpdate};
 
@@ -12,7 +13,7 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::Duration;
 use tokio::sync::{OnceCell, RwLock, mpsc};
 use tokio_tungstenite::{connect_async, tungstenite::Message as WsMessage};
 use url::Url;
@@ -37,20 +38,13 @@ pub struct MattermostAdapter {
     user_identity_cache: Arc<RwLock<HashMap<String, String>>>,
     channel_name_cache: Arc<RwLock<HashMap<String, String>>>,
     dm_channel_cache: Arc<RwLock<HashMap<String, String>>>,
-    active_messages: Arc<RwLock<HashMap<String, ActiveStream>>>,
+    /// Maps InboundMessage.id to the post ID being edited during streaming.
+    streams: Arc<StreamRegistry<Arc<str>>>,
     typing_tasks: Arc<RwLock<HashMap<String, tokio::task::JoinHandle<()>>>>,
     shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
     ws_task: Arc<RwLock<Option<tokio::task::JoinHandle<()>>>>,
 }
 
-struct ActiveStream {
-    post_id: Arc<str>,
-    #[allow(dead_code)]
-    channel_id: Arc<str>,
-    last_edit: Instant,
-    accumulated_text: String,
-}
-
 struct MessageBuildContext<'a> {
     runtime_key: &'a str,
     bot_user_id: &'a str,
@@ -120,7 +114,9 @@ impl MattermostAdapter {
             user_identity_cache: Arc::new(RwLock::new(HashMap::new())),
             channel_name_cache: Arc::new(RwLock::new(HashMap::new())),
             dm_channel_cache: Arc::new(RwLock::new(HashMap::new())),
-            active_messages: Arc::new(RwLock::new(HashMap::new())),
+            streams: Arc::new(
+                StreamRegistry::new(MAX_MESSAGE_LENGTH).with_edit_interval(STREAM_EDIT_THROTTLE),
+            ),
             typing_tasks: Arc::new(RwLock::new(HashMap::new())),
             shutdown_tx: Arc::new(RwLock::new(None)),
             ws_task: Arc::new(RwLock::new(None)),
@@ -697,44 +693,12 @@ impl Messaging for MattermostAdapter {
                         return Err(error);
                     }
                 };
-                self.active_messages.write().await.insert(
-                    message.id.clone(),
-                    ActiveStream {
-                        post_id: post.id.into(),
-                        channel_id: channel_id.to_string().into(),
-                        last_edit: Instant::now(),
-                        accumulated_text: String::new(),
-                    },
-                );
+                self.streams.start(&message.id, post.id.into()).await;
             }
 
-            OutboundResponse::StreamChunk(chunk) => {
-                let pending_edit = {
-                    let mut active_messages = self.active_messages.write().await;
-                    if let Some(active) = active_messages.get_mut(&message.id) {
-                        active.accumulated_text.push_str(&chunk);
-
-                        if active.last_edit.elapsed() > STREAM_EDIT_THROTTLE {
-                            let display_text = if active.accumulated_text.len() > MAX_MESSAGE_LENGTH
-                            {
-                                let end = active
-                                    .accumulated_text
-                                    .floor_char_boundary(MAX_MESSAGE_LENGTH - 3);
-                                format!("{}...", &active.accumulated_text[..end])
-                            } else {
-                                active.accumulated_text.clone()
-                            };
-                            active.last_edit = Instant::now();
-                            Some((active.post_id.clone(), display_text))
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                };
-                if let Some((post_id, display_text)) = pending_edit
-                    && let Err(error) = self.edit_post(&post_id, &display_text).await
+            OutboundResponse::StreamChunk(text) => {
+                if let Some(edit) = self.streams.update(&message.id, text).await
+                    && let Err(error) = self.edit_post(&edit.handle, &edit.text).await
                 {
                     tracing::warn!(%error, "failed to edit streaming message");
                 }
@@ -752,13 +716,13 @@ impl Messaging for MattermostAdapter {
                             .get(crate::metadata_keys::REPLY_TO_MESSAGE_ID)
                             .and_then(|v| v.as_str())
                     });
-                if let Some(active) = self.active_messages.write().await.remove(&message.id) {
-                    let chunks = split_message(&active.accumulated_text, MAX_MESSAGE_LENGTH);
+                if let Some(stream) = self.streams.finish(&message.id).await {
+                    let chunks = split_message(&stream.text, MAX_MESSAGE_LENGTH);
                     let mut first = true;
                     for chunk in chunks {
                         if first {
                             first = false;
-                            if let Err(error) = self.edit_post(&active.post_id, &chunk).await {
+                            if let Err(error) = self.edit_post(&stream.handle, &chunk).await {
                                 tracing::warn!(%error, "failed to finalize streaming message");
                             }
                         } else if let Err(error) =
@@ -980,6 +944,10 @@ impl Messaging for MattermostAdapter {
         Ok(())
     }
 
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
     async fn shutdown(&self) -> crate::Result<()> {
         if let Some(tx) = self.shutdown_tx.write().await.take() {
             tx.send(()).await.ok();
@@ -992,7 +960,7 @@ impl Messaging for MattermostAdapter {
         for (_, handle) in self.typing_tasks.write().await.drain() {
             handle.abort();
         }
-        self.active_messages.write().await.clear();
+        self.streams.clear().await;
 
         tracing::info!(adapter = %self.runtime_key, "mattermost adapter shut down");
         Ok(())
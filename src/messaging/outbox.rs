@@ -0,0 +1,568 @@
+//! Durable outbox for proactive messages (SQLite).
+//!
+//! Every proactive send is written here before its first delivery attempt,
+//! so cron deliveries and other agent-initiated messages survive restarts and
+//! platform outages longer than the in-memory retry budget. Delivered rows are
+//! deleted. Transient failures are rescheduled with exponential backoff;
+//! permanent failures and rows that run out of attempts are dead-lettered for
+//! inspection, replay, or purge through the API.
+
+use crate::OutboundResponse;
+use crate::error::Result;
+use crate::messaging::traits::{BroadcastFailureKind, broadcast_variant_name};
+
+use anyhow::Context as _;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Row as _, SqlitePool};
+use std::time::Duration;
+
+/// Delivery rounds before a transiently failing message is dead-lettered.
+/// With the backoff below this spans roughly six hours.
+const MAX_DELIVERY_ATTEMPTS: i64 = 12;
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(30);
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(60 * 60);
+/// How long a claimed row is hidden from other deliverers. A process that
+/// dies mid-delivery leaves the row to be picked up again after this.
+const CLAIM_LEASE: Duration = Duration::from_secs(5 * 60);
+const PREVIEW_CHARS: usize = 200;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OutboxStatus {
+    /// Waiting for its next delivery attempt.
+    Pending,
+    /// Failed permanently or ran out of attempts; kept until replayed or purged.
+    DeadLetter,
+}
+
+impl OutboxStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OutboxStatus::Pending => "pending",
+            OutboxStatus::DeadLetter => "dead_letter",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(OutboxStatus::Pending),
+            "dead_letter" => Some(OutboxStatus::DeadLetter),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for OutboxStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// An outbox row as shown by the API. The response body is summarized rather
+/// than returned, since file payloads can be large.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct OutboxEntry {
+    pub id: String,
+    pub adapter: String,
+    pub target: String,
+    /// `OutboundResponse` variant, e.g. `Text` or `File`.
+    pub variant: String,
+    /// Leading text of the message (or the file name).
+    pub preview: String,
+    pub status: OutboxStatus,
+    pub attempts: i64,
+    pub last_error: Option<String>,
+    pub next_attempt_at: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// A claimed row, ready to hand to an adapter.
+#[derive(Debug, Clone)]
+pub struct OutboxDelivery {
+    pub id: String,
+    pub adapter: String,
+    pub target: String,
+    pub response: OutboundResponse,
+    pub attempts: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct OutboxStore {
+    pool: SqlitePool,
+}
+
+impl OutboxStore {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Persist a message before delivering it. The row starts claimed by the
+    /// caller, so background retries leave it alone while the first delivery
+    /// is in flight.
+    pub async fn enqueue(
+        &self,
+        adapter: &str,
+        target: &str,
+        response: &OutboundResponse,
+    ) -> Result<String> {
+        let id = uuid::Uuid::new_v4().to_string();
+        let body =
+            serde_json::to_string(response).context("failed to serialize outbox response")?;
+
+        sqlx::query(
+            "INSERT INTO outbox (id, adapter, target, response, status, next_attempt_at) \
+             VALUES (?, ?, ?, ?, 'pending', ?)",
+        )
+        .bind(&id)
+        .bind(adapter)
+        .bind(target)
+        .bind(body)
+        .bind(format_timestamp(Utc::now() + CLAIM_LEASE))
+        .execute(&self.pool)
+        .await
+        .context("failed to enqueue outbox message")?;
+
+        Ok(id)
+    }
+
+    /// Claim up to `limit` pending rows that are due for delivery.
+    ///
+    /// Claiming pushes `next_attempt_at` out by the lease, and only rows this
+    /// call managed to move are returned, so two deliverers never send the
+    /// same row. Rows whose body no longer deserializes are dead-lettered.
+    pub async fn claim_due(&self, limit: i64) -> Result<Vec<OutboxDelivery>> {
+        let now = Utc::now();
+        let rows = sqlx::query(
+            "SELECT id, adapter, target, response, attempts, next_attempt_at FROM outbox \
+             WHERE status = 'pending' AND next_attempt_at <= ? \
+             ORDER BY next_attempt_at ASC, created_at ASC LIMIT ?",
+        )
+        .bind(format_timestamp(now))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to load due outbox messages")?;
+
+        let lease_until = format_timestamp(now + CLAIM_LEASE);
+        let mut claimed = Vec::with_capacity(rows.len());
+        for row in rows {
+            let id: String = row.try_get("id").context("failed to read outbox id")?;
+            let previous: String = row
+                .try_get("next_attempt_at")
+                .context("failed to read outbox next_attempt_at")?;
+
+            let result = sqlx::query(
+                "UPDATE outbox SET next_attempt_at = ? \
+                 WHERE id = ? AND status = 'pending' AND next_attempt_at = ?",
+            )
+            .bind(&lease_until)
+            .bind(&id)
+            .bind(&previous)
+            .execute(&self.pool)
+            .await
+            .context("failed to claim outbox message")?;
+            if result.rows_affected() == 0 {
+                continue;
+            }
+
+            let body: String = row
+                .try_get("response")
+                .context("failed to read outbox response")?;
+            let response = match serde_json::from_str::<OutboundResponse>(&body) {
+                Ok(response) => response,
+                Err(error) => {
+                    self.dead_letter(&id, &format!("unreadable response body: {error}"))
+                        .await?;
+                    continue;
+                }
+            };
+
+            claimed.push(OutboxDelivery {
+                id,
+                adapter: row
+                    .try_get("adapter")
+                    .context("failed to read outbox adapter")?,
+                target: row
+                    .try_get("target")
+                    .context("failed to read outbox target")?,
+                response,
+                attempts: row
+                    .try_get("attempts")
+                    .context("failed to read outbox attempts")?,
+            });
+        }
+
+        Ok(claimed)
+    }
+
+    /// Remove a delivered message.
+    pub async fn mark_delivered(&self, id: &str) -> Result<()> {
+        sqlx::query("DELETE FROM outbox WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .context("failed to remove delivered outbox message")?;
+        Ok(())
+    }
+
+    /// Record a failed delivery round and decide what happens next.
+    ///
+    /// Permanent failures are dead-lettered immediately. Transient failures
+    /// are rescheduled with exponential backoff until the attempt budget runs
+    /// out. Returns the row's new status.
+    pub async fn record_failure(
+        &self,
+        id: &str,
+        error: &str,
+        kind: BroadcastFailureKind,
+    ) -> Result<OutboxStatus> {
+        let attempts: i64 = sqlx::query_scalar(
+            "UPDATE outbox SET attempts = attempts + 1 WHERE id = ? RETURNING attempts",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .context("failed to record outbox attempt")?
+        .with_context(|| format!("outbox message {id} not found"))?;
+
+        if kind == BroadcastFailureKind::Permanent {
+            self.dead_letter(id, error).await?;
+            return Ok(OutboxStatus::DeadLetter);
+        }
+        if attempts >= MAX_DELIVERY_ATTEMPTS {
+            self.dead_letter(
+                id,
+                &format!("retry budget exhausted after {attempts} attempts: {error}"),
+            )
+            .await?;
+            return Ok(OutboxStatus::DeadLetter);
+        }
+
+        sqlx::query(
+            "UPDATE outbox SET last_error = ?, next_attempt_at = ?, \
+             updated_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now') WHERE id = ?",
+        )
+        .bind(error)
+        .bind(format_timestamp(Utc::now() + retry_delay(attempts)))
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .context("failed to reschedule outbox message")?;
+
+        Ok(OutboxStatus::Pending)
+    }
+
+    async fn dead_letter(&self, id: &str, error: &str) -> Result<()> {
+        sqlx::query(
+            "UPDATE outbox SET status = 'dead_letter', last_error = ?, \
+             updated_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now') WHERE id = ?",
+        )
+        .bind(error)
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .context("failed to dead-letter outbox message")?;
+        Ok(())
+    }
+
+    /// List rows, oldest first, optionally filtered by status.
+    pub async fn list(&self, status: Option<OutboxStatus>, limit: i64) -> Result<Vec<OutboxEntry>> {
+        let rows = sqlx::query(
+            "SELECT id, adapter, target, response, status, attempts, last_error, \
+             next_attempt_at, created_at, updated_at FROM outbox \
+             WHERE (? IS NULL OR status = ?) ORDER BY created_at ASC LIMIT ?",
+        )
+        .bind(status.map(OutboxStatus::as_str))
+        .bind(status.map(OutboxStatus::as_str))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to list outbox messages")?;
+
+        rows.into_iter()
+            .map(|row| {
+                let body: String = row.try_get("response").context("failed to read response")?;
+                let status: String = row.try_get("status").context("failed to read status")?;
+                let (variant, preview) = match serde_json::from_str::<OutboundResponse>(&body) {
+                    Ok(response) => (
+                        broadcast_variant_name(&response).to_string(),
+                        response_preview(&response),
+                    ),
+                    Err(_) => ("Unknown".to_string(), String::new()),
+                };
+                Ok(OutboxEntry {
+                    id: row.try_get("id").context("failed to read id")?,
+                    adapter: row.try_get("adapter").context("failed to read adapter")?,
+                    target: row.try_get("target").context("failed to read target")?,
+                    variant,
+                    preview,
+                    status: OutboxStatus::parse(&status)
+                        .with_context(|| format!("invalid outbox status '{status}'"))?,
+                    attempts: row.try_get("attempts").context("failed to read attempts")?,
+                    last_error: row
+                        .try_get("last_error")
+                        .context("failed to read last_error")?,
+                    next_attempt_at: row
+                        .try_get("next_attempt_at")
+                        .context("failed to read next_attempt_at")?,
+                    created_at: row
+                        .try_get("created_at")
+                        .context("failed to read created_at")?,
+                    updated_at: row
+                        .try_get("updated_at")
+                        .context("failed to read updated_at")?,
+                })
+            })
+            .collect()
+    }
+
+    /// Make a dead-lettered row due immediately with a fresh attempt budget.
+    /// Returns `false` if no such row exists. Pending rows are left alone,
+    /// since one may be mid-delivery under a claim lease.
+    pub async fn replay(&self, id: &str) -> Result<bool> {
+        let result = sqlx::query(
+            "UPDATE outbox SET status = 'pending', attempts = 0, next_attempt_at = ?, \
+             updated_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now') \
+             WHERE id = ? AND status = 'dead_letter'",
+        )
+        .bind(format_timestamp(Utc::now()))
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .context("failed to replay outbox message")?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Replay every dead-lettered row. Returns how many were requeued.
+    pub async fn replay_dead_letters(&self) -> Result<u64> {
+        let result = sqlx::query(
+            "UPDATE outbox SET status = 'pending', attempts = 0, next_attempt_at = ?, \
+             updated_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now') WHERE status = 'dead_letter'",
+        )
+        .bind(format_timestamp(Utc::now()))
+        .execute(&self.pool)
+        .await
+        .context("failed to replay dead-lettered outbox messages")?;
+        Ok(result.rows_affected())
+    }
+
+    /// Delete one row. Returns `false` if no such row exists.
+    pub async fn delete(&self, id: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM outbox WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .context("failed to delete outbox message")?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Delete all rows, or all rows with `status`. Returns how many were removed.
+    pub async fn purge(&self, status: Option<OutboxStatus>) -> Result<u64> {
+        let result = sqlx::query("DELETE FROM outbox WHERE (? IS NULL OR status = ?)")
+            .bind(status.map(OutboxStatus::as_str))
+            .bind(status.map(OutboxStatus::as_str))
+            .execute(&self.pool)
+            .await
+            .context("failed to purge outbox messages")?;
+        Ok(result.rows_affected())
+    }
+}
+
+/// Backoff after `attempts` failed rounds: 30s, 1m, 2m, ... capped at an hour.
+fn retry_delay(attempts: i64) -> Duration {
+    let exponent = u32::try_from(attempts.saturating_sub(1).clamp(0, 16)).unwrap_or(16);
+    RETRY_BASE_DELAY
+        .saturating_mul(2u32.saturating_pow(exponent))
+        .min(RETRY_MAX_DELAY)
+}
+
+/// Timestamps use the same fixed-width UTC format as the SQL defaults, so
+/// string comparison orders them correctly.
+fn format_timestamp(time: DateTime<Utc>) -> String {
+    time.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+fn response_preview(response: &OutboundResponse) -> String {
+    let text = match response {
+        OutboundResponse::Text(text)
+        | OutboundResponse::ThreadReply { text, .. }
+        | OutboundResponse::Ephemeral { text, .. }
+        | OutboundResponse::RichMessage { text, .. }
+        | OutboundResponse::ScheduledMessage { text, .. }
+        | OutboundResponse::StreamChunk(text) => text.as_str(),
+        OutboundResponse::File { filename, .. } => filename.as_str(),
+        OutboundResponse::Reaction(emoji) | OutboundResponse::RemoveReaction(emoji) => {
+            emoji.as_str()
+        }
+        OutboundResponse::StreamStart
+        | OutboundResponse::StreamEnd
+        | OutboundResponse::Status(_) => "",
+    };
+    text.chars().take(PREVIEW_CHARS).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_store() -> OutboxStore {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("in-memory sqlite should connect");
+        sqlx::migrate!("./migrations/outbox")
+            .run(&pool)
+            .await
+            .expect("outbox migrations should apply");
+        OutboxStore::new(pool)
+    }
+
+    async fn make_due(store: &OutboxStore, id: &str) {
+        sqlx::query("UPDATE outbox SET next_attempt_at = '2000-01-01T00:00:00Z' WHERE id = ?")
+            .bind(id)
+            .execute(&store.pool)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn enqueued_rows_are_claimed_once_when_due() {
+        let store = setup_store().await;
+        let id = store
+            .enqueue("discord", "123", &OutboundResponse::Text("hello".into()))
+            .await
+            .unwrap();
+
+        // Freshly enqueued rows belong to the sender until the lease expires.
+        assert!(store.claim_due(10).await.unwrap().is_empty());
+
+        make_due(&store, &id).await;
+        let claimed = store.claim_due(10).await.unwrap();
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].adapter, "discord");
+        assert!(matches!(&claimed[0].response, OutboundResponse::Text(text) if text == "hello"));
+        assert!(store.claim_due(10).await.unwrap().is_empty());
+
+        store.mark_delivered(&id).await.unwrap();
+        assert!(store.list(None, 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn failures_back_off_then_dead_letter() {
+        let store = setup_store().await;
+        let id = store
+            .enqueue("slack", "C1", &OutboundResponse::Text("report".into()))
+            .await
+            .unwrap();
+
+        let status = store
+            .record_failure(
+                &id,
+                "503 service unavailable",
+                BroadcastFailureKind::Transient,
+            )
+            .await
+            .unwrap();
+        assert_eq!(status, OutboxStatus::Pending);
+        let entry = &store.list(None, 10).await.unwrap()[0];
+        assert_eq!(entry.attempts, 1);
+        assert_eq!(entry.last_error.as_deref(), Some("503 service unavailable"));
+        assert!(entry.next_attempt_at > format_timestamp(Utc::now()));
+
+        let status = store
+            .record_failure(&id, "channel_not_found", BroadcastFailureKind::Permanent)
+            .await
+            .unwrap();
+        assert_eq!(status, OutboxStatus::DeadLetter);
+        let dead = store
+            .list(Some(OutboxStatus::DeadLetter), 10)
+            .await
+            .unwrap();
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].preview, "report");
+        assert!(store.claim_due(10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn transient_failures_exhaust_the_attempt_budget() {
+        let store = setup_store().await;
+        let id = store
+            .enqueue(
+                "email",
+                "ops@example.com",
+                &OutboundResponse::Text("x".into()),
+            )
+            .await
+            .unwrap();
+
+        for _ in 1..MAX_DELIVERY_ATTEMPTS {
+            let status = store
+                .record_failure(&id, "timeout", BroadcastFailureKind::Transient)
+                .await
+                .unwrap();
+            assert_eq!(status, OutboxStatus::Pending);
+        }
+        let status = store
+            .record_failure(&id, "timeout", BroadcastFailureKind::Transient)
+            .await
+            .unwrap();
+        assert_eq!(status, OutboxStatus::DeadLetter);
+        let entry = &store.list(None, 10).await.unwrap()[0];
+        assert!(
+            entry
+                .last_error
+                .as_deref()
+                .unwrap()
+                .contains("retry budget exhausted")
+        );
+    }
+
+    #[tokio::test]
+    async fn replay_and_purge() {
+        let store = setup_store().await;
+        let first = store
+            .enqueue("telegram", "1", &OutboundResponse::Text("a".into()))
+            .await
+            .unwrap();
+        let second = store
+            .enqueue("telegram", "2", &OutboundResponse::Text("b".into()))
+            .await
+            .unwrap();
+        for id in [&first, &second] {
+            store
+                .record_failure(id, "chat not found", BroadcastFailureKind::Permanent)
+                .await
+                .unwrap();
+        }
+
+        assert!(store.replay(&first).await.unwrap());
+        assert!(!store.replay("missing").await.unwrap());
+        let claimed = store.claim_due(10).await.unwrap();
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].id, first);
+        assert_eq!(claimed[0].attempts, 0);
+        // Claimed and mid-delivery: replay must not reset its lease.
+        assert!(!store.replay(&first).await.unwrap());
+        assert!(store.claim_due(10).await.unwrap().is_empty());
+
+        assert_eq!(store.replay_dead_letters().await.unwrap(), 1);
+        assert_eq!(
+            store.purge(Some(OutboxStatus::DeadLetter)).await.unwrap(),
+            0
+        );
+        assert_eq!(store.purge(Some(OutboxStatus::Pending)).await.unwrap(), 2);
+        assert!(!store.delete(&second).await.unwrap());
+    }
+
+    #[test]
+    fn retry_delay_doubles_up_to_an_hour() {
+        assert_eq!(retry_delay(1), Duration::from_secs(30));
+        assert_eq!(retry_delay(2), Duration::from_secs(60));
+        assert_eq!(retry_delay(4), Duration::from_secs(240));
+        assert_eq!(retry_delay(20), RETRY_MAX_DELAY);
+    }
+}
@@ -21,18 +21,23 @@
 //! - Typing indicator via `assistant.threads.setStatus`
 //! - DM broadcast via `conversations.open`
 
+use crate::commands::CommandSpec;
 use crate::config::{SlackCommandConfig, SlackPermissions};
 use crate::messaging::apply_runtime_adapter_to_conversation_id;
+use crate::messaging::streaming::StreamRegistry;
 use crate::messaging::traits::{HistoryMessage, InboundStream, Messaging};
 use crate::{InboundMessage, MessageContent, OutboundResponse, StatusUpdate};
 
 use anyhow::Context as _;
 use arc_swap::ArcSwap;
 use slack_morphism::prelude::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::{RwLock, mpsc};
-use tokio::time::{Duration, timeout};
+use tokio::time::{Duration, Instant, timeout};
+
+/// How long `usergroups.list` results are reused before refetching.
+const USER_GROUP_CACHE_TTL: Duration = Duration::from_secs(600);
 
 /// State shared with socket mode callbacks via `SlackClientEventsUserState`.
 struct SlackAdapterState {
@@ -44,10 +49,21 @@ struct SlackAdapterState {
     /// Maps slash command string (e.g. `"/ask"`) → agent_id.
     /// Built once at start() from the config; read-only afterwards.
     commands: Arc<HashMap<String, String>>,
+    /// Registry chat commands (e.g. `"/standup"`), routed through bindings.
+    chat_commands: Arc<HashSet<String>>,
     /// Cache of resolved user identities to avoid repeated `users.info` API calls.
     user_identity_cache: Arc<RwLock<HashMap<String, SlackUserIdentity>>>,
     /// Cache of resolved channel names to avoid repeated `conversations.info` API calls.
     channel_name_cache: Arc<RwLock<HashMap<String, String>>>,
+    /// User group membership, used to map Slack user groups to access roles.
+    user_group_cache: Arc<RwLock<SlackUserGroupCache>>,
+}
+
+/// Workspace user group membership from `usergroups.list`, keyed by user ID.
+#[derive(Debug, Default)]
+struct SlackUserGroupCache {
+    fetched_at: Option<Instant>,
+    groups_by_user: HashMap<String, Vec<String>>,
 }
 
 #[derive(Debug, Clone)]
@@ -69,10 +85,12 @@ pub struct SlackAdapter {
     /// Pre-built API token wrapping `bot_token`. Created once alongside `client`.
     token: SlackApiToken,
     /// Maps InboundMessage.id → Slack ts for streaming edits.
-    active_messages: Arc<RwLock<HashMap<String, String>>>,
+    streams: Arc<StreamRegistry<SlackTs>>,
     shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
     /// Slash command routing: command string → agent_id.
     commands: Arc<HashMap<String, String>>,
+    /// Registry chat commands accepted as slash commands.
+    chat_commands: Arc<HashSet<String>>,
 }
 
 impl SlackAdapter {
@@ -100,12 +118,27 @@ impl SlackAdapter {
             permissions,
             client,
             token,
-            active_messages: Arc::new(RwLock::new(HashMap::new())),
+            streams: Arc::new(StreamRegistry::new(12_000)),
             shutdown_tx: Arc::new(RwLock::new(None)),
             commands: Arc::new(commands_map),
+            chat_commands: Arc::new(HashSet::new()),
         })
     }
 
+    /// Accept these chat commands as slash commands.
+    ///
+    /// Slack has no bot API for creating slash commands, so each one also
+    /// needs a matching entry in the app manifest. They're logged on start.
+    pub fn with_commands(mut self, commands: Vec<CommandSpec>) -> Self {
+        self.chat_commands = Arc::new(
+            commands
+                .into_iter()
+                .map(|spec| format!("/{}", spec.name))
+                .collect(),
+        );
+        self
+    }
+
     /// Open a session against the cached client using the cached bot token.
     fn session(&self) -> SlackClientSession<'_, SlackClientHyperHttpsConnector> {
         self.client.open_session(&self.token)
@@ -255,6 +288,7 @@ async fn handle_message_event(
         &adapter_state.bot_token,
         &adapter_state.user_identity_cache,
         &adapter_state.channel_name_cache,
+        &adapter_state.user_group_cache,
     )
     .await;
     let mut metadata = metadata;
@@ -393,6 +427,7 @@ async fn handle_app_mention_event(
         &adapter_state.bot_token,
         &adapter_state.user_identity_cache,
         &adapter_state.channel_name_cache,
+        &adapter_state.user_group_cache,
     )
     .await;
     let mut metadata = metadata;
@@ -440,7 +475,7 @@ fn slack_error_handler(
 /// for permission denials, only for unhandled commands).
 async fn handle_command_event(
     event: SlackCommandEvent,
-    _client: Arc<SlackHyperClient>,
+    client: Arc<SlackHyperClient>,
     states: SlackClientEventsUserState,
 ) -> UserCallbackResult<SlackCommandEventResponse> {
     let state_guard = states.read().await;
@@ -495,7 +530,9 @@ async fn handle_command_event(
         }
     }
 
-    if !adapter_state.commands.contains_key(&command_str) {
+    if !adapter_state.commands.contains_key(&command_str)
+        && !adapter_state.chat_commands.contains(&command_str)
+    {
         tracing::warn!(
             command = %command_str,
             user_id = %user_id,
@@ -510,7 +547,7 @@ async fn handle_command_event(
         });
     }
 
-    let agent_id = adapter_state.commands[&command_str].clone();
+    let agent_id = adapter_state.commands.get(&command_str).cloned();
 
     let base_conversation_id = format!("slack:{}:{}", team_id, channel_id);
     let conversation_id =
@@ -541,12 +578,22 @@ async fn handle_command_event(
         "slack_user_mention".into(),
         serde_json::Value::String(format!("<@{}>", user_id)),
     );
+    let group_ids = user_group_ids(
+        &client,
+        &adapter_state.bot_token,
+        &adapter_state.user_group_cache,
+        &user_id,
+    )
+    .await;
+    insert_user_group_ids(&mut metadata, group_ids);
     // Embed the agent_id hint so the router can honour command-specific routing
     // without requiring a separate binding entry per command.
-    metadata.insert(
-        "slack_command_agent_id".into(),
-        serde_json::Value::String(agent_id),
-    );
+    if let Some(agent_id) = agent_id {
+        metadata.insert(
+            "slack_command_agent_id".into(),
+            serde_json::Value::String(agent_id),
+        );
+    }
 
     let content = MessageContent::Text(format!("{} {}", command_str, text).trim().to_string());
 
@@ -580,7 +627,7 @@ async fn handle_command_event(
 /// types (view submissions, shortcuts, etc.) are logged and acknowledged.
 async fn handle_interaction_event(
     event: SlackInteractionEvent,
-    _client: Arc<SlackHyperClient>,
+    client: Arc<SlackHyperClient>,
     states: SlackClientEventsUserState,
 ) -> UserCallbackResult<()> {
     let SlackInteractionEvent::BlockActions(block_actions) = event else {
@@ -663,6 +710,14 @@ async fn handle_interaction_event(
         return Ok(());
     }
 
+    let group_ids = user_group_ids(
+        &client,
+        &adapter_state.bot_token,
+        &adapter_state.user_group_cache,
+        &user_id,
+    )
+    .await;
+
     for (idx, action) in actions.iter().enumerate() {
         let action_id = action.action_id.0.clone();
         let block_id = action.block_id.as_ref().map(|b| b.0.clone());
@@ -708,6 +763,7 @@ async fn handle_interaction_event(
             "slack_user_mention".into(),
             serde_json::Value::String(format!("<@{}>", user_id)),
         );
+        insert_user_group_ids(&mut metadata, group_ids.clone());
         if let Some(ref ts) = message_ts {
             metadata.insert(
                 "slack_thread_ts".into(),
@@ -773,6 +829,14 @@ impl Messaging for SlackAdapter {
             .context("failed to call auth.test for bot user ID")?;
         let bot_user_id = auth_response.user_id.0.clone();
         tracing::info!(bot_user_id = %bot_user_id, "slack bot user ID resolved");
+        if !self.chat_commands.is_empty() {
+            let mut names: Vec<&str> = self.chat_commands.iter().map(String::as_str).collect();
+            names.sort_unstable();
+            tracing::info!(
+                commands = %names.join(" "),
+                "slack chat commands need matching slash commands in the app manifest"
+            );
+        }
 
         let adapter_state = Arc::new(SlackAdapterState {
             inbound_tx,
@@ -781,8 +845,10 @@ impl Messaging for SlackAdapter {
             bot_token: self.bot_token.clone(),
             bot_user_id,
             commands: self.commands.clone(),
+            chat_commands: self.chat_commands.clone(),
             user_identity_cache: Arc::new(RwLock::new(HashMap::new())),
             channel_name_cache: Arc::new(RwLock::new(HashMap::new())),
+            user_group_cache: Arc::new(RwLock::new(SlackUserGroupCache::default())),
         });
 
         let callbacks = SlackSocketModeListenerCallbacks::new()
@@ -1078,30 +1144,21 @@ impl Messaging for SlackAdapter {
                 let req = SlackApiChatPostMessageRequest::new(
                     channel_id.clone(),
                     SlackMessageContent::new().with_text("\u{200B}".into()),
-                );
+                )
+                .opt_thread_ts(extract_thread_ts(message));
                 let resp = session
                     .chat_post_message(&req)
                     .await
                     .context("failed to send stream placeholder")?;
-                self.active_messages
-                    .write()
-                    .await
-                    .insert(message.id.clone(), resp.ts.0);
+                self.streams.start(&message.id, resp.ts).await;
             }
 
             OutboundResponse::StreamChunk(text) => {
-                let active = self.active_messages.read().await;
-                if let Some(ts) = active.get(&message.id) {
-                    let display_text = if text.len() > 12_000 {
-                        let end = text.floor_char_boundary(11_997);
-                        format!("{}...", &text[..end])
-                    } else {
-                        text
-                    };
+                if let Some(edit) = self.streams.update(&message.id, text).await {
                     let req = SlackApiChatUpdateRequest::new(
                         channel_id.clone(),
-                        markdown_content(display_text),
-                        SlackTs(ts.clone()),
+                        markdown_content(edit.text),
+                        edit.handle,
                     );
                     if let Err(error) = session.chat_update(&req).await {
                         tracing::warn!(%error, "failed to edit streaming message");
@@ -1110,7 +1167,30 @@ impl Messaging for SlackAdapter {
             }
 
             OutboundResponse::StreamEnd => {
-                self.active_messages.write().await.remove(&message.id);
+                if let Some(stream) = self.streams.finish(&message.id).await {
+                    let mut chunks = split_message(&stream.text, 12_000).into_iter();
+                    if let Some(first) = chunks.next() {
+                        let req = SlackApiChatUpdateRequest::new(
+                            channel_id.clone(),
+                            markdown_content(first),
+                            stream.handle,
+                        );
+                        if let Err(error) = session.chat_update(&req).await {
+                            tracing::warn!(%error, "failed to finalize streaming message");
+                        }
+                    }
+                    for chunk in chunks {
+                        let req = SlackApiChatPostMessageRequest::new(
+                            channel_id.clone(),
+                            markdown_content(chunk),
+                        )
+                        .opt_thread_ts(extract_thread_ts(message));
+                        session
+                            .chat_post_message(&req)
+                            .await
+                            .context("failed to send overflow chunk for streaming message")?;
+                    }
+                }
             }
 
             OutboundResponse::Status(_) => {
@@ -1278,8 +1358,12 @@ impl Messaging for SlackAdapter {
         Ok(())
     }
 
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
     async fn shutdown(&self) -> crate::Result<()> {
-        self.active_messages.write().await.clear();
+        self.streams.clear().await;
         if let Some(tx) = self.shutdown_tx.write().await.take() {
             let _ = tx.send(()).await;
         }
@@ -1418,6 +1502,7 @@ async fn build_metadata_and_author(
     bot_token: &str,
     user_identity_cache: &Arc<RwLock<HashMap<String, SlackUserIdentity>>>,
     channel_name_cache: &Arc<RwLock<HashMap<String, String>>>,
+    user_group_cache: &RwLock<SlackUserGroupCache>,
 ) -> (HashMap<String, serde_json::Value>, Option<String>) {
     let mut metadata = HashMap::new();
 
@@ -1456,6 +1541,8 @@ async fn build_metadata_and_author(
             "slack_user_mention".into(),
             serde_json::Value::String(format!("<@{uid}>")),
         );
+        let group_ids = user_group_ids(client, bot_token, user_group_cache, uid).await;
+        insert_user_group_ids(&mut metadata, group_ids);
     }
 
     let token = SlackApiToken::new(SlackApiTokenValue(bot_token.to_string()));
@@ -1568,6 +1655,86 @@ async fn build_metadata_and_author(
     (metadata, formatted_author)
 }
 
+/// User group IDs `user_id` belongs to, refreshing the workspace-wide
+/// membership cache when it is older than [`USER_GROUP_CACHE_TTL`].
+///
+/// Requires the `usergroups:read` scope. Without it the lookup fails once per
+/// TTL and senders simply have no group IDs.
+async fn user_group_ids(
+    client: &SlackHyperClient,
+    bot_token: &str,
+    cache: &RwLock<SlackUserGroupCache>,
+    user_id: &str,
+) -> Vec<String> {
+    let is_fresh = |cache: &SlackUserGroupCache| {
+        cache
+            .fetched_at
+            .is_some_and(|fetched_at| fetched_at.elapsed() < USER_GROUP_CACHE_TTL)
+    };
+    {
+        let cache = cache.read().await;
+        if is_fresh(&cache) {
+            return cache
+                .groups_by_user
+                .get(user_id)
+                .cloned()
+                .unwrap_or_default();
+        }
+    }
+
+    let mut cache = cache.write().await;
+    // Another message may have refreshed the cache while we waited.
+    if !is_fresh(&cache) {
+        let token = SlackApiToken::new(SlackApiTokenValue(bot_token.to_string()));
+        let request = SlackApiUserGroupsListRequest::new().with_include_users(true);
+        match client.open_session(&token).usergroups_list(&request).await {
+            Ok(response) => {
+                let mut groups_by_user: HashMap<String, Vec<String>> = HashMap::new();
+                for group in response.usergroups {
+                    for member in group.users.unwrap_or_default() {
+                        groups_by_user
+                            .entry(member.0)
+                            .or_default()
+                            .push(group.id.0.clone());
+                    }
+                }
+                cache.groups_by_user = groups_by_user;
+            }
+            Err(error) => {
+                tracing::debug!(
+                    %error,
+                    "failed to list Slack user groups; verify usergroups:read scope"
+                );
+            }
+        }
+        cache.fetched_at = Some(Instant::now());
+    }
+    cache
+        .groups_by_user
+        .get(user_id)
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Record the sender's user group IDs for access role mapping.
+fn insert_user_group_ids(
+    metadata: &mut HashMap<String, serde_json::Value>,
+    group_ids: Vec<String>,
+) {
+    if group_ids.is_empty() {
+        return;
+    }
+    metadata.insert(
+        crate::metadata_keys::SENDER_GROUP_IDS.into(),
+        serde_json::Value::Array(
+            group_ids
+                .into_iter()
+                .map(serde_json::Value::String)
+                .collect(),
+        ),
+    );
+}
+
 /// Dispatch a fully-constructed `InboundMessage` to the inbound channel.
 #[allow(clippy::too_many_arguments)]
 async fn send_inbound(
@@ -0,0 +1,375 @@
+//! Shared plumbing for streamed replies.
+//!
+//! Streaming is a three-step protocol on `OutboundResponse`: `StreamStart`
+//! opens a preview message, each `StreamChunk` carries the full reply text
+//! generated so far, and `StreamEnd` finalizes it. Chunks are snapshots rather
+//! than deltas so adapters can drop intermediate ones under rate limits and the
+//! last chunk can replace the preview with the post-processed reply. An empty
+//! last chunk withdraws the reply: the preview is replaced with
+//! [`WITHDRAWN_PREVIEW_TEXT`] instead of keeping the partial text.
+//!
+//! - [`ReplyStream`] is the producer side. The channel hands one to the hook
+//!   and the reply tool for each turn when streaming is enabled.
+//! - [`StreamRegistry`] is the consumer side. Adapters that can edit messages
+//!   keep their in-flight previews in it and get rate-limited edits back.
+//!
+//! Adapters that cannot edit sent messages never see the stream variants;
+//! `MessagingManager` buffers them and delivers one final `Text` instead.
+
+use crate::{OutboundResponse, RoutedSender};
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// Default minimum time between two edits of the same preview message.
+pub const STREAM_EDIT_INTERVAL: Duration = Duration::from_millis(1000);
+
+/// Replaces a preview whose reply was withdrawn, e.g. rejected by the reply
+/// tool after part of it had streamed.
+pub const WITHDRAWN_PREVIEW_TEXT: &str = "(reply withdrawn)";
+
+/// An edit (or final text) for one in-flight preview message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamEdit<H> {
+    /// Adapter-specific handle for the preview message (message ID, ts, ...).
+    pub handle: H,
+    pub text: String,
+    /// The reply was withdrawn; `text` is [`WITHDRAWN_PREVIEW_TEXT`].
+    pub withdrawn: bool,
+}
+
+struct ActiveStream<H> {
+    handle: H,
+    text: String,
+    last_edit: Instant,
+}
+
+/// In-flight preview messages for one adapter, keyed by inbound message ID.
+pub struct StreamRegistry<H> {
+    streams: Mutex<HashMap<String, ActiveStream<H>>>,
+    edit_interval: Duration,
+    max_len: usize,
+}
+
+impl<H> std::fmt::Debug for StreamRegistry<H> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamRegistry")
+            .field("edit_interval", &self.edit_interval)
+            .field("max_len", &self.max_len)
+            .finish()
+    }
+}
+
+impl<H: Clone> StreamRegistry<H> {
+    /// Create a registry whose intermediate edits are truncated to `max_len`.
+    pub fn new(max_len: usize) -> Self {
+        Self {
+            streams: Mutex::new(HashMap::new()),
+            edit_interval: STREAM_EDIT_INTERVAL,
+            max_len,
+        }
+    }
+
+    /// Override the minimum time between edits of one preview.
+    pub fn with_edit_interval(mut self, edit_interval: Duration) -> Self {
+        self.edit_interval = edit_interval;
+        self
+    }
+
+    /// Track a freshly sent preview message. Sending the placeholder counts as
+    /// the first edit for rate-limiting purposes.
+    pub async fn start(&self, key: &str, handle: H) {
+        self.streams.lock().await.insert(
+            key.to_string(),
+            ActiveStream {
+                handle,
+                text: String::new(),
+                last_edit: Instant::now(),
+            },
+        );
+    }
+
+    /// Record the latest reply text. Returns the edit to apply when one is
+    /// due, or `None` when the stream is unknown or still rate-limited.
+    pub async fn update(&self, key: &str, text: String) -> Option<StreamEdit<H>> {
+        let mut streams = self.streams.lock().await;
+        let stream = streams.get_mut(key)?;
+        stream.text = text;
+        if stream.text.trim().is_empty() || stream.last_edit.elapsed() < self.edit_interval {
+            return None;
+        }
+        stream.last_edit = Instant::now();
+        Some(StreamEdit {
+            handle: stream.handle.clone(),
+            text: truncate_preview(&stream.text, self.max_len),
+            withdrawn: false,
+        })
+    }
+
+    /// Stop tracking a stream and return its handle with the full final text.
+    /// The text is not truncated; adapters split it as they would a `Text`.
+    /// A stream whose last snapshot is empty was withdrawn, and comes back
+    /// with [`WITHDRAWN_PREVIEW_TEXT`] so the preview doesn't keep the partial
+    /// reply.
+    pub async fn finish(&self, key: &str) -> Option<StreamEdit<H>> {
+        self.streams.lock().await.remove(key).map(|stream| {
+            let withdrawn = stream.text.trim().is_empty();
+            StreamEdit {
+                handle: stream.handle,
+                text: if withdrawn {
+                    WITHDRAWN_PREVIEW_TEXT.to_string()
+                } else {
+                    stream.text
+                },
+                withdrawn,
+            }
+        })
+    }
+
+    /// Forget every in-flight stream, e.g. on shutdown.
+    pub async fn clear(&self) {
+        self.streams.lock().await.clear();
+    }
+}
+
+/// Cut a preview down to `max_len` bytes, marking the cut with `...`.
+pub fn truncate_preview(text: &str, max_len: usize) -> String {
+    if text.len() <= max_len {
+        return text.to_string();
+    }
+    let end = text.floor_char_boundary(max_len.saturating_sub(3));
+    format!("{}...", &text[..end])
+}
+
+#[derive(Debug, Default)]
+struct ReplyStreamState {
+    open: bool,
+    /// Tool call whose content is currently being previewed.
+    call_id: Option<String>,
+    /// Set when a snapshot of the current call failed the safety checks; the
+    /// preview stays frozen until the reply tool decides what to send.
+    held: bool,
+}
+
+/// Producer side of a streamed reply for one channel turn.
+///
+/// The hook feeds it partial `reply` content as the model generates it, and
+/// the reply tool finalizes it with the post-processed text instead of sending
+/// a separate message.
+#[derive(Debug, Clone)]
+pub struct ReplyStream {
+    sender: RoutedSender,
+    state: Arc<Mutex<ReplyStreamState>>,
+}
+
+impl ReplyStream {
+    pub fn new(sender: RoutedSender) -> Self {
+        Self {
+            sender,
+            state: Arc::new(Mutex::new(ReplyStreamState::default())),
+        }
+    }
+
+    /// Push the reply text generated so far for `call_id`. Opens the preview
+    /// on the first non-empty snapshot.
+    ///
+    /// Snapshots that look like tool syntax or contain a likely secret are
+    /// never shown, and freeze the preview for the rest of that call.
+    pub async fn update(&self, call_id: &str, text: &str) {
+        let mut state = self.state.lock().await;
+        if state.call_id.as_deref() != Some(call_id) {
+            state.call_id = Some(call_id.to_string());
+            state.held = false;
+        }
+        if state.held || text.trim().is_empty() {
+            return;
+        }
+        if crate::tools::should_block_user_visible_text(text)
+            || crate::secrets::scrub::scan_for_leaks(text).is_some()
+        {
+            state.held = true;
+            return;
+        }
+
+        if !state.open {
+            if self.send(OutboundResponse::StreamStart).await.is_err() {
+                return;
+            }
+            state.open = true;
+        }
+        self.send(OutboundResponse::StreamChunk(text.to_string()))
+            .await
+            .ok();
+    }
+
+    /// Whether a preview message is currently open.
+    pub async fn is_open(&self) -> bool {
+        self.state.lock().await.open
+    }
+
+    /// Close the open preview, replacing its text with `final_text` when
+    /// given. Returns `false` if no preview was open, in which case the caller
+    /// should deliver the reply as a regular message.
+    pub async fn finish(&self, final_text: Option<&str>) -> bool {
+        let mut state = self.state.lock().await;
+        state.call_id = None;
+        state.held = false;
+        if !state.open {
+            return false;
+        }
+        state.open = false;
+
+        if let Some(text) = final_text {
+            self.send(OutboundResponse::StreamChunk(text.to_string()))
+                .await
+                .ok();
+        }
+        self.send(OutboundResponse::StreamEnd).await.ok();
+        true
+    }
+
+    /// Withdraw the open preview, e.g. when the reply it was showing was
+    /// rejected. Adapters replace the partial text with
+    /// [`WITHDRAWN_PREVIEW_TEXT`]; buffered streams send nothing.
+    pub async fn withdraw(&self) {
+        self.finish(Some("")).await;
+    }
+
+    async fn send(&self, response: OutboundResponse) -> Result<(), ()> {
+        self.sender.send(response).await.map_err(|error| {
+            tracing::debug!(%error, "failed to send streamed reply update");
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{InboundMessage, RoutedResponse};
+    use tokio::sync::mpsc;
+
+    fn reply_stream() -> (ReplyStream, mpsc::Receiver<RoutedResponse>) {
+        let (tx, rx) = mpsc::channel(16);
+        (
+            ReplyStream::new(RoutedSender::new(tx, InboundMessage::empty())),
+            rx,
+        )
+    }
+
+    fn drain(rx: &mut mpsc::Receiver<RoutedResponse>) -> Vec<String> {
+        let mut sent = Vec::new();
+        while let Ok(routed) = rx.try_recv() {
+            sent.push(match routed.response {
+                OutboundResponse::StreamStart => "start".to_string(),
+                OutboundResponse::StreamChunk(text) => format!("chunk:{text}"),
+                OutboundResponse::StreamEnd => "end".to_string(),
+                other => format!("other:{other:?}"),
+            });
+        }
+        sent
+    }
+
+    #[tokio::test]
+    async fn registry_rate_limits_edits_but_keeps_latest_text() {
+        let registry = StreamRegistry::new(100).with_edit_interval(Duration::from_secs(60));
+        registry.start("msg", 7_u64).await;
+
+        assert_eq!(registry.update("msg", "Hel".into()).await, None);
+        assert_eq!(registry.update("msg", "Hello".into()).await, None);
+        assert_eq!(registry.update("other", "x".into()).await, None);
+
+        let finished = registry.finish("msg").await.unwrap();
+        assert_eq!(finished.handle, 7);
+        assert_eq!(finished.text, "Hello");
+        assert!(registry.finish("msg").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn registry_truncates_intermediate_edits_only() {
+        let registry = StreamRegistry::new(8).with_edit_interval(Duration::ZERO);
+        registry.start("msg", "handle").await;
+
+        let edit = registry
+            .update("msg", "0123456789abc".into())
+            .await
+            .unwrap();
+        assert_eq!(edit.text, "01234...");
+
+        let finished = registry.finish("msg").await.unwrap();
+        assert_eq!(finished.text, "0123456789abc");
+    }
+
+    #[tokio::test]
+    async fn withdrawn_streams_replace_the_partial_preview() {
+        let registry = StreamRegistry::new(100).with_edit_interval(Duration::ZERO);
+        registry.start("msg", 7_u64).await;
+        assert!(
+            registry
+                .update("msg", "I'll paste the ke".into())
+                .await
+                .is_some()
+        );
+        assert_eq!(registry.update("msg", String::new()).await, None);
+
+        let finished = registry.finish("msg").await.unwrap();
+        assert!(finished.withdrawn);
+        assert_eq!(finished.text, WITHDRAWN_PREVIEW_TEXT);
+
+        let (stream, mut rx) = reply_stream();
+        stream.withdraw().await;
+        stream.update("call-1", "Partial").await;
+        stream.withdraw().await;
+        assert!(!stream.is_open().await);
+        assert_eq!(
+            drain(&mut rx),
+            vec!["start", "chunk:Partial", "chunk:", "end"]
+        );
+    }
+
+    #[test]
+    fn truncate_preview_respects_char_boundaries() {
+        assert_eq!(truncate_preview("short", 10), "short");
+        assert_eq!(truncate_preview("ééééé", 7), "éé...");
+    }
+
+    #[tokio::test]
+    async fn reply_stream_opens_streams_and_finalizes() {
+        let (stream, mut rx) = reply_stream();
+
+        stream.update("call-1", "  ").await;
+        assert!(!stream.is_open().await);
+
+        stream.update("call-1", "Hello").await;
+        stream.update("call-1", "Hello there").await;
+        assert!(stream.is_open().await);
+        assert!(stream.finish(Some("Hello there!")).await);
+        assert!(!stream.finish(None).await);
+
+        assert_eq!(
+            drain(&mut rx),
+            vec![
+                "start",
+                "chunk:Hello",
+                "chunk:Hello there",
+                "chunk:Hello there!",
+                "end"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn reply_stream_holds_back_unsafe_previews() {
+        let (stream, mut rx) = reply_stream();
+
+        stream.update("call-1", "[reply] {\"content\"").await;
+        stream.update("call-1", "fine later").await;
+        assert!(!stream.is_open().await);
+        assert!(drain(&mut rx).is_empty());
+
+        // A new tool call starts with a clean slate.
+        stream.update("call-2", "Hi").await;
+        assert_eq!(drain(&mut rx), vec!["start", "chunk:Hi"]);
+    }
+}
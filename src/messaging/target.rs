@@ -29,6 +29,24 @@ pub fn parse_delivery_target(raw: &str) -> Option<BroadcastTarget> {
         return parse_signal_target_parts(parts.get(1..).unwrap_or(&[]));
     }
 
+    // Matrix room IDs and user IDs contain colons themselves
+    // (`!room:server`, `@user:server`), so split on the sigil instead.
+    if let Some(rest) = raw.strip_prefix("matrix:") {
+        return parse_matrix_target(rest);
+    }
+
+    // IRC named instances add a segment, but channel names (`#`/`&`) and
+    // the `dm:` marker make the forms distinguishable.
+    if let Some(rest) = raw.strip_prefix("irc:") {
+        return parse_irc_target(rest);
+    }
+
+    // Teams conversation IDs contain colons (`19:...@thread.tacv2`, `a:...`),
+    // so named instances are told apart by the conversation ID's shape.
+    if let Some(rest) = raw.strip_prefix("teams:") {
+        return parse_teams_target(rest);
+    }
+
     // Handle other platforms with named instances (telegram, discord, slack)
     // Format: platform:<instance>:<target> or platform:<target>
     if raw.starts_with("telegram:") || raw.starts_with("discord:") || raw.starts_with("slack:") {
@@ -185,6 +203,64 @@ pub fn resolve_broadcast_target(channel: &ChannelInfo) -> Option<BroadcastTarget
             let target = normalize_mattermost_target(&raw_target)?;
             return Some(BroadcastTarget { adapter, target });
         }
+        "matrix" => {
+            let adapter = extract_matrix_adapter_from_channel_id(&channel.id);
+            let raw_target = channel
+                .platform_meta
+                .as_ref()
+                .and_then(|meta| meta.get("matrix_room_id"))
+                .and_then(json_value_to_string)
+                .or_else(|| {
+                    // matrix:{room_id} or matrix:{instance}:{room_id}
+                    channel
+                        .id
+                        .find('!')
+                        .map(|index| channel.id[index..].to_string())
+                })?;
+            let target = normalize_matrix_target(&raw_target)?;
+            return Some(BroadcastTarget { adapter, target });
+        }
+        "irc" => {
+            if let Some(irc_target) = channel
+                .platform_meta
+                .as_ref()
+                .and_then(|meta| meta.get("irc_target"))
+                .and_then(json_value_to_string)
+            {
+                let adapter = extract_irc_adapter_from_channel_id(&channel.id);
+                let is_dm = channel
+                    .platform_meta
+                    .as_ref()
+                    .and_then(|meta| meta.get("irc_is_dm"))
+                    .and_then(|value| value.as_bool())
+                    .unwrap_or(false);
+                let raw_target = if is_dm {
+                    format!("dm:{irc_target}")
+                } else {
+                    irc_target
+                };
+                let target = normalize_irc_target(&raw_target)?;
+                return Some(BroadcastTarget { adapter, target });
+            }
+
+            // irc:{target} or irc:{instance}:{target}
+            return parse_irc_target(channel.id.strip_prefix("irc:")?);
+        }
+        "teams" => {
+            if let Some(conversation_id) = channel
+                .platform_meta
+                .as_ref()
+                .and_then(|meta| meta.get("teams_conversation_id"))
+                .and_then(json_value_to_string)
+            {
+                let adapter = extract_teams_adapter_from_channel_id(&channel.id);
+                let target = normalize_teams_target(&conversation_id)?;
+                return Some(BroadcastTarget { adapter, target });
+            }
+
+            // teams:{conversation_id} or teams:{instance}:{conversation_id}
+            return parse_teams_target(channel.id.strip_prefix("teams:")?);
+        }
         _ => return None,
     };
 
@@ -209,6 +285,9 @@ pub fn normalize_target(adapter: &str, raw_target: &str) -> Option<String> {
         "twitch" => normalize_twitch_target(trimmed),
         "email" => normalize_email_target(trimmed),
         "mattermost" => normalize_mattermost_target(trimmed),
+        "matrix" => normalize_matrix_target(trimmed),
+        "irc" => normalize_irc_target(trimmed),
+        "teams" => normalize_teams_target(trimmed),
         // Portal targets are full conversation IDs (e.g. "portal:chat:main")
         "portal" => Some(trimmed.to_string()),
         "signal" => normalize_signal_target(trimmed),
@@ -344,6 +423,199 @@ fn normalize_mattermost_target(raw_target: &str) -> Option<String> {
     }
 }
 
+/// Extract the runtime adapter key from a Matrix conversation ID.
+///
+/// - Default: `matrix:{room_id}` → `"matrix"`
+/// - Named:   `matrix:{instance}:{room_id}` → `"matrix:{instance}"`
+///
+/// Room IDs always start with `!`, which is what separates the two forms.
+fn extract_matrix_adapter_from_channel_id(channel_id: &str) -> String {
+    let rest = channel_id.strip_prefix("matrix:").unwrap_or(channel_id);
+    match rest.split_once(':') {
+        Some((instance, room_id)) if !instance.starts_with('!') && room_id.starts_with('!') => {
+            format!("matrix:{instance}")
+        }
+        _ => "matrix".to_string(),
+    }
+}
+
+/// Parse the part of a Matrix delivery target after `matrix:`.
+///
+/// Handles `{target}` for the default adapter and `{instance}:{target}` for
+/// named adapters, where `{target}` is anything [`normalize_matrix_target`]
+/// accepts.
+fn parse_matrix_target(rest: &str) -> Option<BroadcastTarget> {
+    if let Some(target) = normalize_matrix_target(rest) {
+        return Some(BroadcastTarget {
+            adapter: "matrix".to_string(),
+            target,
+        });
+    }
+
+    let (instance, target) = rest.split_once(':')?;
+    // `dm:` is the direct-message marker, never an instance name.
+    if instance == "dm" || !is_valid_instance_name(instance) {
+        return None;
+    }
+    Some(BroadcastTarget {
+        adapter: format!("matrix:{instance}"),
+        target: normalize_matrix_target(target)?,
+    })
+}
+
+/// Normalize a raw Matrix target to a room ID, room alias, or `dm:{user_id}`.
+///
+/// Accepts (with or without a leading `matrix:` prefix):
+/// - `!room:server` → `!room:server`
+/// - `#alias:server` → `#alias:server`
+/// - `@user:server` or `dm:@user:server` → `dm:@user:server`
+///
+/// Returns `None` for anything without a sigil and a server name.
+fn normalize_matrix_target(raw_target: &str) -> Option<String> {
+    let target = strip_repeated_prefix(raw_target, "matrix");
+    let (dm, identifier) = match target.strip_prefix("dm:") {
+        Some(user_id) => (true, user_id),
+        None => (false, target),
+    };
+
+    let (localpart, server_name) = identifier.get(1..)?.split_once(':')?;
+    if localpart.is_empty() || server_name.is_empty() || identifier.contains(char::is_whitespace) {
+        return None;
+    }
+
+    match identifier.chars().next()? {
+        '@' => Some(format!("dm:{identifier}")),
+        '!' | '#' if !dm => Some(identifier.to_string()),
+        _ => None,
+    }
+}
+
+/// Extract the runtime adapter key from an IRC conversation ID.
+///
+/// - Default: `irc:#channel` or `irc:dm:{nick}` → `"irc"`
+/// - Named:   `irc:{instance}:#channel` or `irc:{instance}:dm:{nick}` → `"irc:{instance}"`
+fn extract_irc_adapter_from_channel_id(channel_id: &str) -> String {
+    let rest = channel_id.strip_prefix("irc:").unwrap_or(channel_id);
+    if rest.starts_with(['#', '&']) || rest.starts_with("dm:") {
+        return "irc".to_string();
+    }
+    match rest.split_once(':') {
+        Some((instance, _)) => format!("irc:{instance}"),
+        None => "irc".to_string(),
+    }
+}
+
+/// Parse the part of an IRC delivery target after `irc:`.
+///
+/// Handles `{target}` for the default adapter and `{instance}:{target}` for
+/// named adapters, where `{target}` is anything [`normalize_irc_target`]
+/// accepts.
+fn parse_irc_target(rest: &str) -> Option<BroadcastTarget> {
+    if let Some(target) = normalize_irc_target(rest) {
+        return Some(BroadcastTarget {
+            adapter: "irc".to_string(),
+            target,
+        });
+    }
+
+    let (instance, target) = rest.split_once(':')?;
+    if instance == "dm" || !is_valid_instance_name(instance) {
+        return None;
+    }
+    Some(BroadcastTarget {
+        adapter: format!("irc:{instance}"),
+        target: normalize_irc_target(target)?,
+    })
+}
+
+/// Normalize a raw IRC target to a channel or `dm:{nick}`.
+///
+/// Accepts (with or without a leading `irc:` prefix):
+/// - `#channel` / `&channel` → unchanged
+/// - `{nick}` or `dm:{nick}` → `dm:{nick}`
+///
+/// Colons are rejected in both forms so instance-prefixed targets never
+/// normalize as a bare target.
+fn normalize_irc_target(raw_target: &str) -> Option<String> {
+    let target = strip_repeated_prefix(raw_target, "irc");
+    let invalid = |value: &str| {
+        value.is_empty()
+            || value.contains(|c: char| c.is_whitespace() || c.is_control() || c == ',' || c == ':')
+    };
+
+    if target.starts_with(['#', '&']) {
+        return (!invalid(&target[1..])).then(|| target.to_string());
+    }
+
+    let nick = target.strip_prefix("dm:").unwrap_or(target);
+    if invalid(nick) || nick.starts_with(['#', '&']) || nick.contains(['!', '@']) {
+        return None;
+    }
+    Some(format!("dm:{nick}"))
+}
+
+/// Extract the runtime adapter key from a Teams conversation ID.
+///
+/// - Default: `teams:{conversation_id}` → `"teams"`
+/// - Named:   `teams:{instance}:{conversation_id}` → `"teams:{instance}"`
+fn extract_teams_adapter_from_channel_id(channel_id: &str) -> String {
+    match parse_teams_target(channel_id.strip_prefix("teams:").unwrap_or(channel_id)) {
+        Some(target) => target.adapter,
+        None => "teams".to_string(),
+    }
+}
+
+/// Parse the part of a Teams delivery target after `teams:`.
+///
+/// Handles `{target}` for the default adapter and `{instance}:{target}` for
+/// named adapters, where `{target}` is anything [`normalize_teams_target`]
+/// accepts.
+fn parse_teams_target(rest: &str) -> Option<BroadcastTarget> {
+    if let Some(target) = normalize_teams_target(rest) {
+        return Some(BroadcastTarget {
+            adapter: "teams".to_string(),
+            target,
+        });
+    }
+
+    let (instance, target) = rest.split_once(':')?;
+    if instance == "dm" || !is_valid_instance_name(instance) {
+        return None;
+    }
+    Some(BroadcastTarget {
+        adapter: format!("teams:{instance}"),
+        target: normalize_teams_target(target)?,
+    })
+}
+
+/// Normalize a raw Teams target to a conversation ID or `dm:{user_id}`.
+///
+/// Accepts (with or without a leading `teams:` prefix):
+/// - `19:...@thread.tacv2`, `a:...` and other `{kind}:{id}` conversation
+///   IDs, where `{kind}` is numeric or `a`; a `;messageid=` thread suffix is
+///   dropped so delivery goes to the conversation root
+/// - `dm:{user_id}` → unchanged, for a user's Azure AD object ID or Teams ID
+fn normalize_teams_target(raw_target: &str) -> Option<String> {
+    let target = strip_repeated_prefix(raw_target, "teams");
+    let target = target
+        .split_once(";messageid=")
+        .map_or(target, |(root, _)| root);
+
+    if let Some(user_id) = target.strip_prefix("dm:") {
+        if user_id.is_empty() || user_id.contains(|c: char| c.is_whitespace() || c == ':') {
+            return None;
+        }
+        return Some(target.to_string());
+    }
+
+    let (kind, id) = target.split_once(':')?;
+    let valid_kind = kind == "a" || (!kind.is_empty() && kind.chars().all(|c| c.is_ascii_digit()));
+    if !valid_kind || id.is_empty() || id.contains(char::is_whitespace) {
+        return None;
+    }
+    Some(target.to_string())
+}
+
 fn normalize_email_target(raw_target: &str) -> Option<String> {
     let target = strip_repeated_prefix(raw_target, "email").trim();
     if target.is_empty() {
@@ -1098,4 +1370,186 @@ mod tests {
         // 21 characters (over boundary)
         assert!(!super::is_valid_instance_name("exactly_twenty_chars_"));
     }
+
+    #[test]
+    fn parse_matrix_room_target() {
+        let parsed = parse_delivery_target("matrix:!abc123:example.org");
+        assert_eq!(
+            parsed,
+            Some(super::BroadcastTarget {
+                adapter: "matrix".to_string(),
+                target: "!abc123:example.org".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_matrix_named_instance_targets() {
+        assert_eq!(
+            parse_delivery_target("matrix:ops:!abc123:example.org"),
+            Some(super::BroadcastTarget {
+                adapter: "matrix:ops".to_string(),
+                target: "!abc123:example.org".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_delivery_target("matrix:ops:#general:example.org"),
+            Some(super::BroadcastTarget {
+                adapter: "matrix:ops".to_string(),
+                target: "#general:example.org".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_matrix_user_target_becomes_dm() {
+        for raw in ["matrix:@alice:example.org", "matrix:dm:@alice:example.org"] {
+            assert_eq!(
+                parse_delivery_target(raw),
+                Some(super::BroadcastTarget {
+                    adapter: "matrix".to_string(),
+                    target: "dm:@alice:example.org".to_string(),
+                }),
+                "{raw}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_matrix_target_rejects_malformed() {
+        assert_eq!(parse_delivery_target("matrix:general"), None);
+        assert_eq!(parse_delivery_target("matrix:!nohomeserver"), None);
+        assert_eq!(parse_delivery_target("matrix:dm:!abc:example.org"), None);
+        assert_eq!(
+            parse_delivery_target("matrix:bad.name:!abc:example.org"),
+            None
+        );
+    }
+
+    #[test]
+    fn resolve_matrix_target_from_channel_id() {
+        let default = test_channel_info("matrix:!abc123:example.org", "matrix");
+        assert_eq!(
+            resolve_broadcast_target(&default),
+            Some(super::BroadcastTarget {
+                adapter: "matrix".to_string(),
+                target: "!abc123:example.org".to_string(),
+            })
+        );
+
+        let named = test_channel_info("matrix:ops:!abc123:example.org", "matrix");
+        assert_eq!(
+            resolve_broadcast_target(&named),
+            Some(super::BroadcastTarget {
+                adapter: "matrix:ops".to_string(),
+                target: "!abc123:example.org".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_irc_targets() {
+        let cases = [
+            ("irc:#rust", "irc", "#rust"),
+            ("irc:alice", "irc", "dm:alice"),
+            ("irc:dm:alice", "irc", "dm:alice"),
+            ("irc:libera:#rust", "irc:libera", "#rust"),
+            ("irc:libera:dm:alice", "irc:libera", "dm:alice"),
+        ];
+        for (raw, adapter, target) in cases {
+            assert_eq!(
+                parse_delivery_target(raw),
+                Some(super::BroadcastTarget {
+                    adapter: adapter.to_string(),
+                    target: target.to_string(),
+                }),
+                "{raw}"
+            );
+        }
+
+        assert_eq!(parse_delivery_target("irc:#"), None);
+        assert_eq!(parse_delivery_target("irc:two words"), None);
+        assert_eq!(parse_delivery_target("irc:dm:#rust"), None);
+        assert_eq!(parse_delivery_target("irc:alice!user@host"), None);
+    }
+
+    #[test]
+    fn resolve_irc_target_from_channel_id() {
+        let named = test_channel_info("irc:libera:#rust", "irc");
+        assert_eq!(
+            resolve_broadcast_target(&named),
+            Some(super::BroadcastTarget {
+                adapter: "irc:libera".to_string(),
+                target: "#rust".to_string(),
+            })
+        );
+
+        let dm = test_channel_info("irc:dm:alice", "irc");
+        assert_eq!(
+            resolve_broadcast_target(&dm),
+            Some(super::BroadcastTarget {
+                adapter: "irc".to_string(),
+                target: "dm:alice".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_teams_targets() {
+        let cases = [
+            ("teams:19:abc@thread.tacv2", "teams", "19:abc@thread.tacv2"),
+            (
+                "teams:19:abc@thread.tacv2;messageid=1700000000000",
+                "teams",
+                "19:abc@thread.tacv2",
+            ),
+            ("teams:a:1xyz", "teams", "a:1xyz"),
+            (
+                "teams:dm:0f8fad5b-d9cb-469f-a165-70867728950e",
+                "teams",
+                "dm:0f8fad5b-d9cb-469f-a165-70867728950e",
+            ),
+            (
+                "teams:support:19:abc@thread.tacv2",
+                "teams:support",
+                "19:abc@thread.tacv2",
+            ),
+        ];
+        for (raw, adapter, target) in cases {
+            assert_eq!(
+                parse_delivery_target(raw),
+                Some(super::BroadcastTarget {
+                    adapter: adapter.to_string(),
+                    target: target.to_string(),
+                }),
+                "{raw}"
+            );
+        }
+
+        assert_eq!(parse_delivery_target("teams:general"), None);
+        assert_eq!(parse_delivery_target("teams:19:"), None);
+        assert_eq!(parse_delivery_target("teams:dm:two words"), None);
+        assert_eq!(parse_delivery_target("teams:support:general"), None);
+    }
+
+    #[test]
+    fn resolve_teams_target_from_channel_id() {
+        let named = test_channel_info("teams:support:19:abc@thread.tacv2", "teams");
+        assert_eq!(
+            resolve_broadcast_target(&named),
+            Some(super::BroadcastTarget {
+                adapter: "teams:support".to_string(),
+                target: "19:abc@thread.tacv2".to_string(),
+            })
+        );
+
+        let dm = test_channel_info("teams:a:1xyz", "teams");
+        assert_eq!(
+            resolve_broadcast_target(&dm),
+            Some(super::BroadcastTarget {
+                adapter: "teams".to_string(),
+                target: "a:1xyz".to_string(),
+            })
+        );
+    }
 }
@@ -0,0 +1,2407 @@
+//! Microsoft Teams messaging adapter using the Bot Framework.
+//!
+//! Binds an HTTP server for the bot's messaging endpoint and receives
+//! activities from the Bot Framework service. Every request carries a JWT
+//! signed with a key from the Bot Framework OpenID metadata; it is verified
+//! before the activity is accepted. Replies go to the connector REST API at
+//! the activity's `serviceUrl`, authenticated with an app token from Azure AD.
+//! Cards and interactive elements are sent as Adaptive Cards, and their
+//! `Action.Submit` results come back as interactions. Conversation references
+//! are persisted so the bot can post proactively to conversations it has seen.
+
+use crate::config::{TeamsConfig, TeamsInstanceConfig, TeamsPermissions};
+use crate::messaging::apply_runtime_adapter_to_conversation_id;
+use crate::messaging::streaming::StreamRegistry;
+use crate::messaging::traits::{
+    InboundStream, Messaging, ensure_supported_broadcast_response, mark_permanent_broadcast,
+    mark_retryable_broadcast,
+};
+use crate::{
+    Attachment, ButtonStyle, Card, InboundMessage, InteractiveElements, MessageContent,
+    OutboundResponse, StatusUpdate,
+};
+
+use anyhow::Context as _;
+use arc_swap::ArcSwap;
+use axum::Router;
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::header::AUTHORIZATION;
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::{get, post};
+use base64::Engine as _;
+use reqwest::{Client, RequestBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, RwLock, mpsc};
+use url::Url;
+
+const BOT_FRAMEWORK_OPENID_METADATA_URL: &str =
+    "https://login.botframework.com/v1/.well-known/openidconfiguration";
+const BOT_FRAMEWORK_TOKEN_SCOPE: &str = "https://api.botframework.com/.default";
+/// Token authority for multi-tenant bots; single-tenant bots use their own tenant.
+const MULTI_TENANT_AUTHORITY: &str = "botframework.com";
+/// Teams rejects messages over roughly 28 KB; leave room for the envelope.
+const MAX_MESSAGE_LENGTH: usize = 25_000;
+/// Teams renders inline images up to 1 MB.
+const MAX_INLINE_IMAGE_BYTES: usize = 1024 * 1024;
+const STREAM_EDIT_THROTTLE: Duration = Duration::from_millis(1500);
+const HTTP_TIMEOUT: Duration = Duration::from_secs(30);
+const JWT_CLOCK_SKEW_SECS: i64 = 300;
+const SIGNING_KEYS_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+/// Unknown key IDs trigger a refetch at most this often.
+const SIGNING_KEYS_MIN_REFRESH: Duration = Duration::from_secs(60);
+const TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(5 * 60);
+const ADAPTIVE_CARD_CONTENT_TYPE: &str = "application/vnd.microsoft.card.adaptive";
+const ADAPTIVE_CARD_VERSION: &str = "1.5";
+const FILE_DOWNLOAD_CONTENT_TYPE: &str = "application/vnd.microsoft.teams.file.download.info";
+/// Key in `Action.Submit` data naming the element that was actioned.
+const SUBMIT_ACTION_KEY: &str = "spacebot_action";
+/// Key in select-menu submit data mapping option values to their labels.
+const SUBMIT_LABELS_KEY: &str = "spacebot_labels";
+const STREAM_PLACEHOLDER: &str = "…";
+
+/// Endpoint and credential settings for one Bot Framework registration.
+#[derive(Clone)]
+pub struct TeamsConnectionSettings {
+    pub app_id: String,
+    pub app_password: String,
+    /// Only activities from this tenant are accepted when set.
+    pub tenant_id: Option<String>,
+    pub bind: String,
+    pub port: u16,
+    /// OpenID metadata document that lists the Bot Framework signing keys.
+    pub openid_metadata_url: String,
+    /// Azure AD token endpoint for the app's client-credentials grant.
+    pub token_url: String,
+}
+
+impl std::fmt::Debug for TeamsConnectionSettings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TeamsConnectionSettings")
+            .field("app_id", &self.app_id)
+            .field("app_password", &"[REDACTED]")
+            .field("tenant_id", &self.tenant_id)
+            .field("bind", &self.bind)
+            .field("port", &self.port)
+            .field("openid_metadata_url", &self.openid_metadata_url)
+            .field("token_url", &self.token_url)
+            .finish()
+    }
+}
+
+impl TeamsConnectionSettings {
+    pub fn from_config(config: &TeamsConfig) -> Self {
+        Self::new(
+            &config.app_id,
+            &config.app_password,
+            config.tenant_id.as_deref(),
+            &config.bind,
+            config.port,
+        )
+    }
+
+    pub fn from_instance_config(instance: &TeamsInstanceConfig) -> Self {
+        Self::new(
+            &instance.app_id,
+            &instance.app_password,
+            instance.tenant_id.as_deref(),
+            &instance.bind,
+            instance.port,
+        )
+    }
+
+    fn new(
+        app_id: &str,
+        app_password: &str,
+        tenant_id: Option<&str>,
+        bind: &str,
+        port: u16,
+    ) -> Self {
+        let tenant_id = tenant_id
+            .map(str::trim)
+            .filter(|tenant_id| !tenant_id.is_empty());
+        Self {
+            app_id: app_id.to_string(),
+            app_password: app_password.to_string(),
+            tenant_id: tenant_id.map(ToOwned::to_owned),
+            bind: bind.to_string(),
+            port,
+            openid_metadata_url: BOT_FRAMEWORK_OPENID_METADATA_URL.to_string(),
+            token_url: format!(
+                "https://login.microsoftonline.com/{}/oauth2/v2.0/token",
+                tenant_id.unwrap_or(MULTI_TENANT_AUTHORITY)
+            ),
+        }
+    }
+}
+
+/// Where an adapter persists its conversation references.
+///
+/// `instance_name` is `None` for the default adapter.
+pub fn conversation_store_path(instance_dir: &Path, instance_name: Option<&str>) -> PathBuf {
+    match instance_name {
+        None => instance_dir.join("teams_conversations.json"),
+        Some(name) => {
+            let name: String = name
+                .chars()
+                .map(|ch| if ch.is_ascii_alphanumeric() { ch } else { '_' })
+                .collect();
+            instance_dir.join(format!("teams_conversations_{name}.json"))
+        }
+    }
+}
+
+pub struct TeamsAdapter {
+    runtime_key: Arc<str>,
+    settings: Arc<TeamsConnectionSettings>,
+    permissions: Arc<ArcSwap<TeamsPermissions>>,
+    connector: ConnectorClient,
+    verifier: Arc<TokenVerifier>,
+    conversations: Arc<ConversationStore>,
+    streams: Arc<StreamRegistry<StreamTarget>>,
+    shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
+}
+
+impl std::fmt::Debug for TeamsAdapter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TeamsAdapter")
+            .field("runtime_key", &self.runtime_key)
+            .field("settings", &self.settings)
+            .finish()
+    }
+}
+
+/// The placeholder activity a streamed reply is written into.
+#[derive(Clone)]
+struct StreamTarget {
+    reference: ConversationReference,
+    activity_id: String,
+}
+
+/// Shared state for the axum handlers.
+#[derive(Clone)]
+struct AppState {
+    runtime_key: Arc<str>,
+    tenant_id: Option<Arc<str>>,
+    permissions: Arc<ArcSwap<TeamsPermissions>>,
+    verifier: Arc<TokenVerifier>,
+    conversations: Arc<ConversationStore>,
+    inbound_tx: mpsc::Sender<InboundMessage>,
+}
+
+impl TeamsAdapter {
+    /// Create a new [`TeamsAdapter`].
+    ///
+    /// `runtime_key` is the adapter's unique identifier within the messaging
+    /// manager (e.g. `"teams"` or `"teams:support"`). Conversation references
+    /// are kept in memory only when `conversation_store` is `None`.
+    pub fn new(
+        runtime_key: impl Into<Arc<str>>,
+        settings: TeamsConnectionSettings,
+        conversation_store: Option<PathBuf>,
+        permissions: Arc<ArcSwap<TeamsPermissions>>,
+    ) -> anyhow::Result<Self> {
+        if settings.app_id.is_empty() || settings.app_password.is_empty() {
+            return Err(anyhow::anyhow!(
+                "teams app_id and app_password are required"
+            ));
+        }
+
+        let client = Client::builder()
+            .timeout(HTTP_TIMEOUT)
+            .pool_idle_timeout(Duration::from_secs(90))
+            .build()
+            .context("failed to build HTTP client")?;
+
+        let verifier = TokenVerifier::new(
+            client.clone(),
+            &settings.app_id,
+            &settings.openid_metadata_url,
+        );
+        let connector = ConnectorClient {
+            client,
+            app_id: settings.app_id.as_str().into(),
+            app_password: settings.app_password.as_str().into(),
+            token_url: settings.token_url.as_str().into(),
+            token: Arc::new(Mutex::new(None)),
+        };
+
+        Ok(Self {
+            runtime_key: runtime_key.into(),
+            settings: Arc::new(settings),
+            permissions,
+            connector,
+            verifier: Arc::new(verifier),
+            conversations: Arc::new(ConversationStore::load(conversation_store)),
+            streams: Arc::new(
+                StreamRegistry::new(MAX_MESSAGE_LENGTH).with_edit_interval(STREAM_EDIT_THROTTLE),
+            ),
+            shutdown_tx: Arc::new(RwLock::new(None)),
+        })
+    }
+
+    /// Send `text` (split to fit) with `attachments` on the last chunk.
+    /// Returns the activity ID of the first message.
+    async fn send_text(
+        &self,
+        reference: &ConversationReference,
+        reply_to: Option<&str>,
+        text: &str,
+        attachments: Vec<serde_json::Value>,
+    ) -> crate::Result<String> {
+        let mut chunks = split_message(text, MAX_MESSAGE_LENGTH);
+        if chunks.len() > 1 && chunks.last().is_some_and(|chunk| chunk.is_empty()) {
+            chunks.pop();
+        }
+        let last_index = chunks.len() - 1;
+        let mut attachments = Some(attachments);
+
+        let mut first_activity_id = None;
+        for (index, chunk) in chunks.iter().enumerate() {
+            let chunk_attachments = if index == last_index {
+                attachments.take().unwrap_or_default()
+            } else {
+                Vec::new()
+            };
+            let activity = message_activity(reference, reply_to, chunk, chunk_attachments);
+            let activity_id = self
+                .connector
+                .send_activity(reference, reply_to, &activity)
+                .await?;
+            first_activity_id.get_or_insert(activity_id);
+        }
+        Ok(first_activity_id.unwrap_or_default())
+    }
+
+    /// Post a file: small images inline, anything else announced by name
+    /// (bots can only share other files through a OneDrive consent flow).
+    async fn send_file(
+        &self,
+        reference: &ConversationReference,
+        reply_to: Option<&str>,
+        filename: String,
+        data: Vec<u8>,
+        mime_type: String,
+        caption: Option<String>,
+    ) -> crate::Result<()> {
+        if is_inline_image(&mime_type, data.len()) {
+            let attachment = serde_json::json!({
+                "contentType": mime_type,
+                "contentUrl": format!(
+                    "data:{mime_type};base64,{}",
+                    base64::engine::general_purpose::STANDARD.encode(&data)
+                ),
+                "name": filename,
+            });
+            self.send_text(
+                reference,
+                reply_to,
+                caption.as_deref().unwrap_or_default(),
+                vec![attachment],
+            )
+            .await?;
+            return Ok(());
+        }
+
+        let text = match caption {
+            Some(caption) => format!("[File: {filename}] {caption}"),
+            None => format!("[File: {filename}]"),
+        };
+        self.send_text(reference, reply_to, &text, Vec::new())
+            .await?;
+        Ok(())
+    }
+
+    async fn send_typing(&self, reference: &ConversationReference) {
+        let activity = serde_json::json!({
+            "type": "typing",
+            "from": { "id": reference.bot_id },
+            "conversation": { "id": reference.conversation_id },
+        });
+        if let Err(error) = self
+            .connector
+            .send_activity(reference, None, &activity)
+            .await
+        {
+            tracing::debug!(%error, "failed to send teams typing indicator");
+        }
+    }
+
+    /// Replace the text of an activity we sent earlier.
+    async fn update_text(
+        &self,
+        reference: &ConversationReference,
+        activity_id: &str,
+        text: &str,
+    ) -> crate::Result<()> {
+        let mut activity = message_activity(reference, None, text, Vec::new());
+        activity["id"] = serde_json::json!(activity_id);
+        self.connector
+            .update_activity(reference, activity_id, &activity)
+            .await
+    }
+
+    /// Resolve a normalized broadcast target to a stored conversation.
+    ///
+    /// `dm:{id}` finds the personal chat with a user by Azure AD object ID or
+    /// Teams user ID; anything else is a conversation ID.
+    async fn resolve_reference(&self, target: &str) -> crate::Result<ConversationReference> {
+        let reference = match target.strip_prefix("dm:") {
+            Some(user_id) => self.conversations.find_personal(user_id).await,
+            None => self.conversations.get(target).await,
+        };
+        reference.ok_or_else(|| {
+            mark_permanent_broadcast(anyhow::anyhow!(
+                "no stored teams conversation for '{target}'; the bot must be installed there or receive a message first"
+            ))
+        })
+    }
+}
+
+impl Messaging for TeamsAdapter {
+    fn name(&self) -> &str {
+        &self.runtime_key
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        let (inbound_tx, inbound_rx) = mpsc::channel(256);
+        let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
+        *self.shutdown_tx.write().await = Some(shutdown_tx);
+
+        let state = AppState {
+            runtime_key: self.runtime_key.clone(),
+            tenant_id: self.settings.tenant_id.as_deref().map(Into::into),
+            permissions: self.permissions.clone(),
+            verifier: self.verifier.clone(),
+            conversations: self.conversations.clone(),
+            inbound_tx,
+        };
+
+        let app = Router::new()
+            .route("/api/messages", post(handle_activity))
+            .route("/health", get(handle_health))
+            .with_state(state);
+
+        let bind = if self.settings.bind.contains(':') {
+            format!("[{}]:{}", self.settings.bind, self.settings.port)
+        } else {
+            format!("{}:{}", self.settings.bind, self.settings.port)
+        };
+        let listener = tokio::net::TcpListener::bind(&bind)
+            .await
+            .with_context(|| format!("failed to bind teams messaging endpoint to {bind}"))?;
+        tracing::info!(adapter = %self.runtime_key, %bind, "teams messaging endpoint listening");
+
+        let runtime_key = self.runtime_key.clone();
+        tokio::spawn(async move {
+            if let Err(error) = axum::serve(listener, app)
+                .with_graceful_shutdown(async move {
+                    let _ = shutdown_rx.recv().await;
+                })
+                .await
+            {
+                tracing::error!(adapter = %runtime_key, %error, "teams server exited with error");
+            }
+        });
+
+        let stream = tokio_stream::wrappers::ReceiverStream::new(inbound_rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn respond(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        let reference = ConversationReference::from_message(message)?;
+        let reply_to = message
+            .metadata
+            .get(crate::metadata_keys::REPLY_TO_MESSAGE_ID)
+            .or_else(|| message.metadata.get("teams_activity_id"))
+            .and_then(|value| value.as_str());
+
+        match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::ThreadReply { text, .. }
+            | OutboundResponse::Ephemeral { text, .. }
+            | OutboundResponse::ScheduledMessage { text, .. } => {
+                self.send_text(&reference, reply_to, &text, Vec::new())
+                    .await?;
+            }
+
+            OutboundResponse::RichMessage {
+                text,
+                cards,
+                interactive_elements,
+                ..
+            } => {
+                let attachments = adaptive_card_attachments(&cards, &interactive_elements);
+                self.send_text(&reference, reply_to, &text, attachments)
+                    .await?;
+            }
+
+            OutboundResponse::File {
+                filename,
+                data,
+                mime_type,
+                caption,
+            } => {
+                self.send_file(&reference, reply_to, filename, data, mime_type, caption)
+                    .await?;
+            }
+
+            // The Bot Framework has no API for bots to react to messages.
+            OutboundResponse::Reaction(_) | OutboundResponse::RemoveReaction(_) => {}
+
+            OutboundResponse::StreamStart => {
+                self.send_typing(&reference).await;
+                let activity_id = self
+                    .send_text(&reference, reply_to, STREAM_PLACEHOLDER, Vec::new())
+                    .await?;
+                self.streams
+                    .start(
+                        &message.id,
+                        StreamTarget {
+                            reference,
+                            activity_id,
+                        },
+                    )
+                    .await;
+            }
+
+            OutboundResponse::StreamChunk(text) => {
+                if let Some(edit) = self.streams.update(&message.id, text).await
+                    && let Err(error) = self
+                        .update_text(&edit.handle.reference, &edit.handle.activity_id, &edit.text)
+                        .await
+                {
+                    tracing::warn!(%error, "failed to update streaming teams message");
+                }
+            }
+
+            OutboundResponse::StreamEnd => {
+                if let Some(stream) = self.streams.finish(&message.id).await {
+                    let target = stream.handle;
+                    let mut chunks = split_message(&stream.text, MAX_MESSAGE_LENGTH).into_iter();
+                    if let Some(first) = chunks.next()
+                        && let Err(error) = self
+                            .update_text(&target.reference, &target.activity_id, &first)
+                            .await
+                    {
+                        tracing::warn!(%error, "failed to finalize streaming teams message");
+                    }
+                    for chunk in chunks {
+                        if let Err(error) = self
+                            .send_text(&target.reference, reply_to, &chunk, Vec::new())
+                            .await
+                        {
+                            tracing::warn!(%error, "failed to send overflow chunk for streaming teams message");
+                        }
+                    }
+                }
+            }
+
+            OutboundResponse::Status(status) => self.send_status(message, status).await?,
+        }
+
+        Ok(())
+    }
+
+    async fn send_status(
+        &self,
+        message: &InboundMessage,
+        status: StatusUpdate,
+    ) -> crate::Result<()> {
+        if matches!(status, StatusUpdate::Thinking) {
+            let reference = ConversationReference::from_message(message)?;
+            self.send_typing(&reference).await;
+        }
+        Ok(())
+    }
+
+    async fn broadcast(&self, target: &str, response: OutboundResponse) -> crate::Result<()> {
+        ensure_supported_broadcast_response("teams", &response, |response| {
+            matches!(
+                response,
+                OutboundResponse::Text(_)
+                    | OutboundResponse::RichMessage { .. }
+                    | OutboundResponse::ThreadReply { .. }
+                    | OutboundResponse::ScheduledMessage { .. }
+                    | OutboundResponse::File { .. }
+            )
+        })?;
+
+        let reference = self.resolve_reference(target).await?;
+
+        match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::ThreadReply { text, .. }
+            | OutboundResponse::ScheduledMessage { text, .. } => {
+                self.send_text(&reference, None, &text, Vec::new()).await?;
+            }
+            OutboundResponse::RichMessage {
+                text,
+                cards,
+                interactive_elements,
+                ..
+            } => {
+                let attachments = adaptive_card_attachments(&cards, &interactive_elements);
+                self.send_text(&reference, None, &text, attachments).await?;
+            }
+            OutboundResponse::File {
+                filename,
+                data,
+                mime_type,
+                caption,
+            } => {
+                self.send_file(&reference, None, filename, data, mime_type, caption)
+                    .await?;
+            }
+            _ => unreachable!("unsupported variants are rejected above"),
+        }
+
+        Ok(())
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        self.connector.access_token().await.map(|_| ())
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        if let Some(tx) = self.shutdown_tx.write().await.take() {
+            tx.send(()).await.ok();
+        }
+        self.streams.clear().await;
+
+        tracing::info!(adapter = %self.runtime_key, "teams adapter shut down");
+        Ok(())
+    }
+}
+
+// -- Axum handlers --
+
+async fn handle_activity(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> StatusCode {
+    let activity: Activity = match serde_json::from_slice(&body) {
+        Ok(activity) => activity,
+        Err(error) => {
+            tracing::debug!(%error, "rejected malformed teams activity");
+            return StatusCode::BAD_REQUEST;
+        }
+    };
+
+    let authorization = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok());
+    if let Err(error) = state.verifier.verify(authorization, &activity).await {
+        tracing::warn!(adapter = %state.runtime_key, %error, "rejected teams activity with invalid token");
+        return StatusCode::UNAUTHORIZED;
+    }
+
+    if !is_allowed_service_url(&activity.service_url) {
+        tracing::warn!(service_url = %activity.service_url, "rejected teams activity with untrusted serviceUrl");
+        return StatusCode::BAD_REQUEST;
+    }
+
+    if let Some(tenant_id) = state.tenant_id.as_deref()
+        && activity.tenant_id() != Some(tenant_id)
+    {
+        tracing::warn!(
+            adapter = %state.runtime_key,
+            tenant_id = activity.tenant_id().unwrap_or("none"),
+            "ignored teams activity from another tenant"
+        );
+        return StatusCode::OK;
+    }
+
+    match activity.activity_type.as_str() {
+        "message" => {
+            if let Some(reference) = ConversationReference::from_activity(&activity) {
+                state.conversations.remember(reference).await;
+            }
+            let permissions = state.permissions.load();
+            let context = MessageBuildContext {
+                runtime_key: &state.runtime_key,
+                permissions: &permissions,
+            };
+            if let Some(message) = build_inbound_message(&activity, &context)
+                && state.inbound_tx.send(message).await.is_err()
+            {
+                tracing::debug!("inbound channel closed");
+            }
+        }
+        "conversationUpdate" => {
+            let bot_added = activity.recipient.as_ref().is_some_and(|bot| {
+                activity
+                    .members_added
+                    .iter()
+                    .any(|member| member.id == bot.id)
+            });
+            if bot_added && let Some(reference) = ConversationReference::from_activity(&activity) {
+                state.conversations.remember(reference).await;
+            }
+        }
+        "installationUpdate" => {
+            if let Some(reference) = ConversationReference::from_activity(&activity) {
+                if activity.action.as_deref() == Some("remove") {
+                    state.conversations.forget(&reference.conversation_id).await;
+                } else {
+                    state.conversations.remember(reference).await;
+                }
+            }
+        }
+        _ => {}
+    }
+
+    StatusCode::OK
+}
+
+async fn handle_health() -> StatusCode {
+    StatusCode::OK
+}
+
+/// Connector endpoints must use HTTPS; plain HTTP is allowed only on
+/// loopback (local emulators and tests).
+fn is_allowed_service_url(service_url: &str) -> bool {
+    let Ok(url) = Url::parse(service_url) else {
+        return false;
+    };
+    match url.scheme() {
+        "https" => url.host().is_some(),
+        "http" => match url.host() {
+            Some(url::Host::Domain(domain)) => domain == "localhost",
+            Some(url::Host::Ipv4(address)) => address.is_loopback(),
+            Some(url::Host::Ipv6(address)) => address.is_loopback(),
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+// -- Activities --
+
+/// The subset of a Bot Framework activity the adapter reads.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Activity {
+    #[serde(rename = "type")]
+    activity_type: String,
+    id: Option<String>,
+    timestamp: Option<chrono::DateTime<chrono::Utc>>,
+    service_url: String,
+    channel_id: String,
+    from: Option<ChannelAccount>,
+    conversation: ConversationAccount,
+    recipient: Option<ChannelAccount>,
+    text: Option<String>,
+    #[serde(default)]
+    attachments: Vec<ActivityAttachment>,
+    #[serde(default)]
+    entities: Vec<serde_json::Value>,
+    value: Option<serde_json::Value>,
+    reply_to_id: Option<String>,
+    channel_data: Option<ChannelData>,
+    #[serde(default)]
+    members_added: Vec<ChannelAccount>,
+    action: Option<String>,
+}
+
+impl Activity {
+    fn tenant_id(&self) -> Option<&str> {
+        self.channel_data
+            .as_ref()
+            .and_then(|data| data.tenant.as_ref())
+            .map(|tenant| tenant.id.as_str())
+            .or(self.conversation.tenant_id.as_deref())
+    }
+
+    fn team(&self) -> Option<&NamedReference> {
+        self.channel_data.as_ref()?.team.as_ref()
+    }
+
+    fn channel(&self) -> Option<&NamedReference> {
+        self.channel_data.as_ref()?.channel.as_ref()
+    }
+
+    fn is_personal(&self) -> bool {
+        self.conversation.conversation_type.as_deref() == Some("personal")
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ChannelAccount {
+    id: String,
+    name: Option<String>,
+    aad_object_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ConversationAccount {
+    id: String,
+    name: Option<String>,
+    conversation_type: Option<String>,
+    tenant_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ActivityAttachment {
+    content_type: String,
+    name: Option<String>,
+    content: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ChannelData {
+    tenant: Option<NamedReference>,
+    team: Option<NamedReference>,
+    channel: Option<NamedReference>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct NamedReference {
+    id: String,
+    name: Option<String>,
+}
+
+/// The conversation a reply thread belongs to. Channel thread IDs carry a
+/// `;messageid=` suffix naming the root post.
+fn root_conversation_id(conversation_id: &str) -> &str {
+    conversation_id
+        .split_once(";messageid=")
+        .map_or(conversation_id, |(root, _)| root)
+}
+
+struct MessageBuildContext<'a> {
+    runtime_key: &'a str,
+    permissions: &'a TeamsPermissions,
+}
+
+/// Convert a `message` activity into an inbound message, applying the
+/// adapter's permissions. Returns `None` for messages that should be ignored.
+fn build_inbound_message(
+    activity: &Activity,
+    context: &MessageBuildContext<'_>,
+) -> Option<InboundMessage> {
+    let from = activity.from.as_ref()?;
+    let bot = activity.recipient.as_ref()?;
+    if from.id == bot.id {
+        return None;
+    }
+
+    let is_dm = activity.is_personal();
+    let root_id = root_conversation_id(&activity.conversation.id);
+    let team = activity.team();
+    let channel = activity.channel();
+    // Team channels filter on the channel ID; group chats on the chat ID.
+    let filter_channel_id = channel.map_or(root_id, |channel| channel.id.as_str());
+
+    if is_dm {
+        if !context.permissions.allows_dm(from.aad_object_id.as_deref()) {
+            tracing::debug!(user_id = %from.id, "teams personal message from user not in dm_allowed_users");
+            return None;
+        }
+    } else if !context
+        .permissions
+        .allows_conversation(team.map(|team| team.id.as_str()), filter_channel_id)
+    {
+        return None;
+    }
+
+    let mut mentions_bot = is_dm;
+    let content = if let Some(interaction) = activity
+        .value
+        .as_ref()
+        .and_then(|value| interaction_from_submit(value, activity.reply_to_id.as_deref()))
+    {
+        // Card submits answer the bot's own message.
+        mentions_bot = true;
+        interaction
+    } else {
+        let raw_text = activity.text.as_deref().unwrap_or_default();
+        let (text, mentioned) = strip_mentions(raw_text, &activity.entities, &bot.id);
+        mentions_bot |= mentioned;
+        let attachments = file_attachments(&activity.attachments);
+        if attachments.is_empty() {
+            if text.is_empty() {
+                return None;
+            }
+            MessageContent::Text(text)
+        } else {
+            MessageContent::Media {
+                text: (!text.is_empty()).then_some(text),
+                attachments,
+            }
+        }
+    };
+
+    let conversation_id =
+        apply_runtime_adapter_to_conversation_id(context.runtime_key, format!("teams:{root_id}"));
+    let id = activity
+        .id
+        .clone()
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let display_name = from.name.clone().unwrap_or_else(|| from.id.clone());
+
+    let mut metadata = HashMap::new();
+    metadata.insert(
+        crate::metadata_keys::MESSAGE_ID.into(),
+        serde_json::json!(id),
+    );
+    if let Some(activity_id) = &activity.id {
+        metadata.insert("teams_activity_id".into(), serde_json::json!(activity_id));
+    }
+    metadata.insert(
+        "teams_conversation_id".into(),
+        serde_json::json!(activity.conversation.id),
+    );
+    if let Some(conversation_type) = &activity.conversation.conversation_type {
+        metadata.insert(
+            "teams_conversation_type".into(),
+            serde_json::json!(conversation_type),
+        );
+    }
+    metadata.insert(
+        "teams_service_url".into(),
+        serde_json::json!(activity.service_url),
+    );
+    metadata.insert("teams_bot_id".into(), serde_json::json!(bot.id));
+    metadata.insert("teams_user_id".into(), serde_json::json!(from.id));
+    if let Some(aad_object_id) = &from.aad_object_id {
+        metadata.insert(
+            "teams_aad_object_id".into(),
+            serde_json::json!(aad_object_id),
+        );
+    }
+    if let Some(tenant_id) = activity.tenant_id() {
+        metadata.insert("teams_tenant_id".into(), serde_json::json!(tenant_id));
+    }
+    metadata.insert("teams_is_dm".into(), serde_json::json!(is_dm));
+    if let Some(team) = team {
+        metadata.insert("teams_team_id".into(), serde_json::json!(team.id));
+        if let Some(name) = &team.name {
+            metadata.insert(
+                crate::metadata_keys::SERVER_NAME.into(),
+                serde_json::json!(name),
+            );
+        }
+    }
+    if !is_dm {
+        metadata.insert(
+            "teams_channel_id".into(),
+            serde_json::json!(filter_channel_id),
+        );
+        let channel_name = channel
+            .and_then(|channel| channel.name.as_ref())
+            .or(activity.conversation.name.as_ref());
+        if let Some(name) = channel_name {
+            metadata.insert(
+                crate::metadata_keys::CHANNEL_NAME.into(),
+                serde_json::json!(name),
+            );
+        }
+    }
+    metadata.insert(
+        "sender_display_name".into(),
+        serde_json::json!(display_name),
+    );
+    metadata.insert(
+        "teams_mentions_or_replies_to_bot".into(),
+        serde_json::json!(mentions_bot),
+    );
+
+    Some(InboundMessage {
+        id,
+        source: "teams".into(),
+        adapter: Some(context.runtime_key.to_string()),
+        conversation_id,
+        sender_id: from
+            .aad_object_id
+            .clone()
+            .unwrap_or_else(|| from.id.clone()),
+        agent_id: None,
+        content,
+        timestamp: activity.timestamp.unwrap_or_else(chrono::Utc::now),
+        metadata,
+        formatted_author: Some(display_name),
+    })
+}
+
+/// Remove `<at>` mentions of the bot from `text` and unwrap the rest to
+/// `@Name`. Returns the cleaned text and whether the bot was mentioned.
+fn strip_mentions(text: &str, entities: &[serde_json::Value], bot_id: &str) -> (String, bool) {
+    let mut cleaned = text.to_string();
+    let mut mentioned = false;
+
+    for entity in entities {
+        if entity.get("type").and_then(|value| value.as_str()) != Some("mention") {
+            continue;
+        }
+        let Some(mention_text) = entity.get("text").and_then(|value| value.as_str()) else {
+            continue;
+        };
+        let mentioned_id = entity
+            .get("mentioned")
+            .and_then(|mentioned| mentioned.get("id"))
+            .and_then(|value| value.as_str());
+        if mentioned_id == Some(bot_id) {
+            mentioned = true;
+            cleaned = cleaned.replace(mention_text, "");
+        }
+    }
+
+    let cleaned = cleaned.replace("<at>", "@").replace("</at>", "");
+    let cleaned = cleaned.split_whitespace().collect::<Vec<_>>().join(" ");
+    (cleaned, mentioned)
+}
+
+/// Files shared in a chat arrive with a pre-authenticated download URL.
+fn file_attachments(attachments: &[ActivityAttachment]) -> Vec<Attachment> {
+    attachments
+        .iter()
+        .filter(|attachment| attachment.content_type == FILE_DOWNLOAD_CONTENT_TYPE)
+        .filter_map(|attachment| {
+            let content = attachment.content.as_ref()?;
+            let url = content.get("downloadUrl")?.as_str()?;
+            let filename = attachment
+                .name
+                .clone()
+                .unwrap_or_else(|| "attachment".to_string());
+            Some(Attachment {
+                mime_type: mime_guess::from_path(&filename)
+                    .first_or_octet_stream()
+                    .to_string(),
+                filename,
+                url: url.to_string(),
+                size_bytes: None,
+                auth_header: None,
+            })
+        })
+        .collect()
+}
+
+/// Map `Action.Submit` data from one of our cards to an interaction.
+///
+/// Select menus submit their choice under the menu's `custom_id`; multi-select
+/// values arrive comma-joined.
+fn interaction_from_submit(
+    value: &serde_json::Value,
+    reply_to_id: Option<&str>,
+) -> Option<MessageContent> {
+    let action_id = value.get(SUBMIT_ACTION_KEY)?.as_str()?.to_string();
+    let values: Vec<String> = value
+        .get(&action_id)
+        .and_then(|selected| selected.as_str())
+        .map(|selected| {
+            selected
+                .split(',')
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(ToOwned::to_owned)
+                .collect()
+        })
+        .unwrap_or_default();
+    let label = match values.as_slice() {
+        [single] => value
+            .get(SUBMIT_LABELS_KEY)
+            .and_then(|labels| labels.get(single))
+            .and_then(|label| label.as_str())
+            .map(ToOwned::to_owned),
+        _ => None,
+    };
+
+    Some(MessageContent::Interaction {
+        action_id,
+        block_id: None,
+        values,
+        label,
+        message_ts: reply_to_id.map(ToOwned::to_owned),
+    })
+}
+
+// -- Conversation references --
+
+/// What the adapter needs to post into a conversation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct ConversationReference {
+    service_url: String,
+    conversation_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    conversation_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tenant_id: Option<String>,
+    bot_id: String,
+    /// The other participant of a personal chat.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    user_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    user_aad_object_id: Option<String>,
+}
+
+impl ConversationReference {
+    /// Reference to the root of the activity's conversation, for proactive sends.
+    fn from_activity(activity: &Activity) -> Option<Self> {
+        let bot = activity.recipient.as_ref()?;
+        let personal_user = activity
+            .from
+            .as_ref()
+            .filter(|from| activity.is_personal() && from.id != bot.id);
+        Some(Self {
+            service_url: activity.service_url.clone(),
+            conversation_id: root_conversation_id(&activity.conversation.id).to_string(),
+            conversation_type: activity.conversation.conversation_type.clone(),
+            tenant_id: activity.tenant_id().map(ToOwned::to_owned),
+            bot_id: bot.id.clone(),
+            user_id: personal_user.map(|user| user.id.clone()),
+            user_aad_object_id: personal_user.and_then(|user| user.aad_object_id.clone()),
+        })
+    }
+
+    /// Reference to the exact conversation (thread included) a message came from.
+    fn from_message(message: &InboundMessage) -> crate::Result<Self> {
+        let get = |key: &str| {
+            message
+                .metadata
+                .get(key)
+                .and_then(|value| value.as_str())
+                .map(ToOwned::to_owned)
+        };
+        Ok(Self {
+            service_url: get("teams_service_url").context("missing teams_service_url metadata")?,
+            conversation_id: get("teams_conversation_id")
+                .context("missing teams_conversation_id metadata")?,
+            conversation_type: get("teams_conversation_type"),
+            tenant_id: get("teams_tenant_id"),
+            bot_id: get("teams_bot_id").context("missing teams_bot_id metadata")?,
+            user_id: None,
+            user_aad_object_id: None,
+        })
+    }
+}
+
+/// Conversation references by root conversation ID, optionally mirrored to
+/// a JSON file so proactive sends survive restarts.
+struct ConversationStore {
+    path: Option<PathBuf>,
+    references: RwLock<HashMap<String, ConversationReference>>,
+    /// Serializes file writes so an older snapshot never lands last.
+    write_lock: Mutex<()>,
+}
+
+impl ConversationStore {
+    fn load(path: Option<PathBuf>) -> Self {
+        let references = path
+            .as_deref()
+            .and_then(|path| match std::fs::read_to_string(path) {
+                Ok(contents) => Some((path, contents)),
+                Err(error) if error.kind() == std::io::ErrorKind::NotFound => None,
+                Err(error) => {
+                    tracing::warn!(%error, path = %path.display(), "failed to read teams conversation references");
+                    None
+                }
+            })
+            .and_then(|(path, contents)| match serde_json::from_str(&contents) {
+                Ok(references) => Some(references),
+                Err(error) => {
+                    tracing::warn!(%error, path = %path.display(), "ignoring unreadable teams conversation references");
+                    None
+                }
+            })
+            .unwrap_or_default();
+
+        Self {
+            path,
+            references: RwLock::new(references),
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    async fn get(&self, conversation_id: &str) -> Option<ConversationReference> {
+        self.references
+            .read()
+            .await
+            .get(root_conversation_id(conversation_id))
+            .cloned()
+    }
+
+    async fn find_personal(&self, user_id: &str) -> Option<ConversationReference> {
+        self.references
+            .read()
+            .await
+            .values()
+            .find(|reference| {
+                reference.conversation_type.as_deref() == Some("personal")
+                    && (reference.user_id.as_deref() == Some(user_id)
+                        || reference
+                            .user_aad_object_id
+                            .as_deref()
+                            .is_some_and(|id| id.eq_ignore_ascii_case(user_id)))
+            })
+            .cloned()
+    }
+
+    async fn remember(&self, reference: ConversationReference) {
+        {
+            let mut references = self.references.write().await;
+            if references.get(&reference.conversation_id) == Some(&reference) {
+                return;
+            }
+            references.insert(reference.conversation_id.clone(), reference);
+        }
+        self.persist().await;
+    }
+
+    async fn forget(&self, conversation_id: &str) {
+        if self
+            .references
+            .write()
+            .await
+            .remove(conversation_id)
+            .is_some()
+        {
+            self.persist().await;
+        }
+    }
+
+    async fn persist(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let _guard = self.write_lock.lock().await;
+        let snapshot = match serde_json::to_vec_pretty(&*self.references.read().await) {
+            Ok(snapshot) => snapshot,
+            Err(error) => {
+                tracing::warn!(%error, "failed to serialize teams conversation references");
+                return;
+            }
+        };
+
+        let temp_path = path.with_extension("json.tmp");
+        let result = async {
+            tokio::fs::write(&temp_path, snapshot).await?;
+            tokio::fs::rename(&temp_path, path).await
+        }
+        .await;
+        if let Err(error) = result {
+            tracing::warn!(%error, path = %path.display(), "failed to persist teams conversation references");
+        }
+    }
+}
+
+// -- Adaptive Cards --
+
+fn message_activity(
+    reference: &ConversationReference,
+    reply_to: Option<&str>,
+    text: &str,
+    attachments: Vec<serde_json::Value>,
+) -> serde_json::Value {
+    let mut activity = serde_json::json!({
+        "type": "message",
+        "from": { "id": reference.bot_id },
+        "conversation": { "id": reference.conversation_id },
+        "text": text,
+        "textFormat": "markdown",
+    });
+    if let Some(reply_to) = reply_to {
+        activity["replyToId"] = serde_json::json!(reply_to);
+    }
+    if !attachments.is_empty() {
+        activity["attachments"] = serde_json::json!(attachments);
+    }
+    activity
+}
+
+/// One Adaptive Card attachment per card. Interactive elements are added to
+/// the last card, or to a card of their own when there are no cards.
+fn adaptive_card_attachments(
+    cards: &[Card],
+    interactive_elements: &[InteractiveElements],
+) -> Vec<serde_json::Value> {
+    let mut bodies: Vec<Vec<serde_json::Value>> = cards.iter().map(card_body).collect();
+    if bodies.is_empty() && !interactive_elements.is_empty() {
+        bodies.push(Vec::new());
+    }
+
+    let last_index = bodies.len().saturating_sub(1);
+    bodies
+        .into_iter()
+        .enumerate()
+        .map(|(index, mut body)| {
+            let mut actions = Vec::new();
+            if index == last_index {
+                for elements in interactive_elements {
+                    append_interactive_elements(elements, &mut body, &mut actions);
+                }
+            }
+            let mut card = serde_json::json!({
+                "type": "AdaptiveCard",
+                "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                "version": ADAPTIVE_CARD_VERSION,
+                "body": body,
+                "msteams": { "width": "Full" },
+            });
+            if !actions.is_empty() {
+                card["actions"] = serde_json::json!(actions);
+            }
+            serde_json::json!({
+                "contentType": ADAPTIVE_CARD_CONTENT_TYPE,
+                "content": card,
+            })
+        })
+        .collect()
+}
+
+fn card_body(card: &Card) -> Vec<serde_json::Value> {
+    let mut body = Vec::new();
+
+    if let Some(author) = &card.author {
+        let name = match &author.url {
+            Some(url) => format!("[{}]({url})", author.name),
+            None => author.name.clone(),
+        };
+        body.push(serde_json::json!({
+            "type": "TextBlock",
+            "text": name,
+            "size": "Small",
+            "isSubtle": true,
+            "wrap": true,
+        }));
+    }
+    if let Some(title) = &card.title {
+        let title = match &card.url {
+            Some(url) => format!("[{title}]({url})"),
+            None => title.clone(),
+        };
+        body.push(serde_json::json!({
+            "type": "TextBlock",
+            "text": title,
+            "size": "Medium",
+            "weight": "Bolder",
+            "wrap": true,
+        }));
+    }
+    if let Some(thumbnail) = &card.thumbnail {
+        body.push(serde_json::json!({
+            "type": "Image",
+            "url": thumbnail.url,
+            "size": "Small",
+        }));
+    }
+    if let Some(description) = &card.description {
+        body.push(serde_json::json!({
+            "type": "TextBlock",
+            "text": description,
+            "wrap": true,
+        }));
+    }
+    if !card.fields.is_empty() {
+        let facts: Vec<_> = card
+            .fields
+            .iter()
+            .map(|field| serde_json::json!({ "title": field.name, "value": field.value }))
+            .collect();
+        body.push(serde_json::json!({ "type": "FactSet", "facts": facts }));
+    }
+    if let Some(image) = &card.image {
+        body.push(serde_json::json!({
+            "type": "Image",
+            "url": image.url,
+            "size": "Stretch",
+        }));
+    }
+    let footer = [
+        card.footer.as_ref().map(|footer| footer.text.clone()),
+        card.timestamp.clone(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(" · ");
+    if !footer.is_empty() {
+        body.push(serde_json::json!({
+            "type": "TextBlock",
+            "text": footer,
+            "size": "Small",
+            "isSubtle": true,
+            "wrap": true,
+        }));
+    }
+
+    body
+}
+
+fn append_interactive_elements(
+    elements: &InteractiveElements,
+    body: &mut Vec<serde_json::Value>,
+    actions: &mut Vec<serde_json::Value>,
+) {
+    match elements {
+        InteractiveElements::Buttons { buttons } => {
+            for button in buttons {
+                if let Some(url) = &button.url {
+                    actions.push(serde_json::json!({
+                        "type": "Action.OpenUrl",
+                        "title": button.label,
+                        "url": url,
+                    }));
+                    continue;
+                }
+                let action_id = button.custom_id.as_deref().unwrap_or(&button.label);
+                let style = match button.style {
+                    ButtonStyle::Primary | ButtonStyle::Success => "positive",
+                    ButtonStyle::Danger => "destructive",
+                    ButtonStyle::Secondary | ButtonStyle::Link => "default",
+                };
+                actions.push(serde_json::json!({
+                    "type": "Action.Submit",
+                    "title": button.label,
+                    "style": style,
+                    "data": { SUBMIT_ACTION_KEY: action_id },
+                }));
+            }
+        }
+        InteractiveElements::Select { select } => {
+            let choices: Vec<_> = select
+                .options
+                .iter()
+                .map(|option| serde_json::json!({ "title": option.label, "value": option.value }))
+                .collect();
+            let labels: serde_json::Map<String, serde_json::Value> = select
+                .options
+                .iter()
+                .map(|option| (option.value.clone(), serde_json::json!(option.label)))
+                .collect();
+            let mut input = serde_json::json!({
+                "type": "Input.ChoiceSet",
+                "id": select.custom_id,
+                "style": "compact",
+                "choices": choices,
+            });
+            if let Some(placeholder) = &select.placeholder {
+                input["placeholder"] = serde_json::json!(placeholder);
+            }
+            body.push(input);
+            actions.push(serde_json::json!({
+                "type": "Action.Submit",
+                "title": "Submit",
+                "data": {
+                    SUBMIT_ACTION_KEY: select.custom_id,
+                    SUBMIT_LABELS_KEY: labels,
+                },
+            }));
+        }
+    }
+}
+
+fn is_inline_image(mime_type: &str, size: usize) -> bool {
+    matches!(mime_type, "image/png" | "image/jpeg" | "image/gif") && size <= MAX_INLINE_IMAGE_BYTES
+}
+
+// -- Connector API --
+
+/// Client for the Bot Framework connector REST API.
+///
+/// Errors are classified for broadcast retries: network failures, 429, and
+/// 5xx are transient; other non-success statuses are permanent.
+#[derive(Clone)]
+struct ConnectorClient {
+    client: Client,
+    app_id: Arc<str>,
+    app_password: Arc<str>,
+    token_url: Arc<str>,
+    token: Arc<Mutex<Option<CachedToken>>>,
+}
+
+struct CachedToken {
+    access_token: String,
+    refresh_at: Instant,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
+#[derive(Deserialize, Default)]
+struct ResourceResponse {
+    #[serde(default)]
+    id: String,
+}
+
+impl ConnectorClient {
+    /// App token for the connector API, cached until shortly before expiry.
+    async fn access_token(&self) -> crate::Result<String> {
+        let mut token = self.token.lock().await;
+        if let Some(cached) = token.as_ref()
+            && Instant::now() < cached.refresh_at
+        {
+            return Ok(cached.access_token.clone());
+        }
+
+        let request = self.client.post(self.token_url.as_ref()).form(&[
+            ("grant_type", "client_credentials"),
+            ("client_id", self.app_id.as_ref()),
+            ("client_secret", self.app_password.as_ref()),
+            ("scope", BOT_FRAMEWORK_TOKEN_SCOPE),
+        ]);
+        let body = send_classified(request, "token").await?;
+        let response: TokenResponse =
+            serde_json::from_slice(&body).context("failed to parse teams token response")?;
+
+        let lifetime = Duration::from_secs(response.expires_in);
+        *token = Some(CachedToken {
+            access_token: response.access_token.clone(),
+            refresh_at: Instant::now() + lifetime.saturating_sub(TOKEN_REFRESH_MARGIN),
+        });
+        Ok(response.access_token)
+    }
+
+    fn activities_url(
+        service_url: &str,
+        conversation_id: &str,
+        activity_id: Option<&str>,
+    ) -> crate::Result<Url> {
+        let mut url = Url::parse(service_url)
+            .with_context(|| format!("invalid teams serviceUrl: {service_url}"))?;
+        {
+            let mut segments = url
+                .path_segments_mut()
+                .map_err(|_| anyhow::anyhow!("invalid teams serviceUrl: {service_url}"))?;
+            segments
+                .pop_if_empty()
+                .extend(["v3", "conversations", conversation_id, "activities"]);
+            if let Some(activity_id) = activity_id {
+                segments.push(activity_id);
+            }
+        }
+        Ok(url)
+    }
+
+    /// Post an activity, as a reply to `reply_to` when given. Returns the new
+    /// activity's ID (empty for activities the service doesn't store).
+    async fn send_activity(
+        &self,
+        reference: &ConversationReference,
+        reply_to: Option<&str>,
+        activity: &serde_json::Value,
+    ) -> crate::Result<String> {
+        let url =
+            Self::activities_url(&reference.service_url, &reference.conversation_id, reply_to)?;
+        let request = self.client.post(url).json(activity);
+        let body = self.execute(request, "send activity").await?;
+        let response: ResourceResponse = serde_json::from_slice(&body).unwrap_or_default();
+        Ok(response.id)
+    }
+
+    async fn update_activity(
+        &self,
+        reference: &ConversationReference,
+        activity_id: &str,
+        activity: &serde_json::Value,
+    ) -> crate::Result<()> {
+        let url = Self::activities_url(
+            &reference.service_url,
+            &reference.conversation_id,
+            Some(activity_id),
+        )?;
+        self.execute(self.client.put(url).json(activity), "update activity")
+            .await?;
+        Ok(())
+    }
+
+    async fn execute(&self, request: RequestBuilder, operation: &str) -> crate::Result<Bytes> {
+        let token = self.access_token().await?;
+        send_classified(request.bearer_auth(token), operation).await
+    }
+}
+
+/// Send a request and return its body, classifying failures for retries.
+async fn send_classified(request: RequestBuilder, operation: &str) -> crate::Result<Bytes> {
+    let response = request.send().await.map_err(|error| {
+        mark_retryable_broadcast(
+            anyhow::Error::new(error).context(format!("teams {operation} request failed")),
+        )
+    })?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        let error = anyhow::anyhow!(
+            "teams {operation} failed with status {}: {body}",
+            status.as_u16()
+        );
+        return Err(
+            if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                mark_retryable_broadcast(error)
+            } else {
+                mark_permanent_broadcast(error)
+            },
+        );
+    }
+
+    response
+        .bytes()
+        .await
+        .map_err(|error| mark_retryable_broadcast(anyhow::Error::new(error)))
+}
+
+// -- Inbound token verification --
+
+/// Verifies the JWT the Bot Framework service attaches to every activity.
+struct TokenVerifier {
+    client: Client,
+    app_id: String,
+    openid_metadata_url: String,
+    keys: RwLock<SigningKeys>,
+    /// Single-flights key refreshes.
+    refresh_lock: Mutex<()>,
+}
+
+#[derive(Default)]
+struct SigningKeys {
+    issuer: String,
+    keys: HashMap<String, SigningKey>,
+    fetched_at: Option<Instant>,
+}
+
+impl SigningKeys {
+    fn is_fresh(&self) -> bool {
+        self.fetched_at
+            .is_some_and(|fetched_at| fetched_at.elapsed() < SIGNING_KEYS_TTL)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SigningKey {
+    modulus: Vec<u8>,
+    exponent: Vec<u8>,
+    /// Channels this key may sign for. `None` means any channel.
+    endorsements: Option<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+struct OpenIdMetadata {
+    issuer: String,
+    jwks_uri: String,
+}
+
+#[derive(Deserialize)]
+struct JsonWebKeySet {
+    keys: Vec<JsonWebKey>,
+}
+
+#[derive(Deserialize)]
+struct JsonWebKey {
+    kty: String,
+    kid: Option<String>,
+    n: Option<String>,
+    e: Option<String>,
+    endorsements: Option<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+struct JwtHeader {
+    alg: String,
+    kid: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BotFrameworkClaims {
+    iss: String,
+    aud: serde_json::Value,
+    exp: i64,
+    nbf: Option<i64>,
+    serviceurl: Option<String>,
+}
+
+impl TokenVerifier {
+    fn new(client: Client, app_id: &str, openid_metadata_url: &str) -> Self {
+        Self {
+            client,
+            app_id: app_id.to_string(),
+            openid_metadata_url: openid_metadata_url.to_string(),
+            keys: RwLock::new(SigningKeys::default()),
+            refresh_lock: Mutex::new(()),
+        }
+    }
+
+    async fn verify(&self, authorization: Option<&str>, activity: &Activity) -> anyhow::Result<()> {
+        let token = authorization
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .context("missing bearer token")?;
+
+        let mut parts = token.split('.');
+        let (Some(header), Some(payload), Some(signature), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            anyhow::bail!("malformed token");
+        };
+        let decode = |part: &str| base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(part);
+        let header: JwtHeader = serde_json::from_slice(&decode(header)?)?;
+        let claims: BotFrameworkClaims = serde_json::from_slice(&decode(payload)?)?;
+        let signature = decode(signature)?;
+
+        if header.alg != "RS256" {
+            anyhow::bail!("unsupported token algorithm {}", header.alg);
+        }
+        let key_id = header.kid.context("token has no key id")?;
+        let (key, issuer) = self.signing_key(&key_id).await?;
+
+        let signing_input_len = token.len() - token.rsplit('.').next().map_or(0, str::len) - 1;
+        ring::signature::RsaPublicKeyComponents {
+            n: &key.modulus,
+            e: &key.exponent,
+        }
+        .verify(
+            &ring::signature::RSA_PKCS1_2048_8192_SHA256,
+            &token.as_bytes()[..signing_input_len],
+            &signature,
+        )
+        .map_err(|_| anyhow::anyhow!("invalid token signature"))?;
+
+        validate_claims(
+            &claims,
+            &issuer,
+            &self.app_id,
+            chrono::Utc::now().timestamp(),
+        )?;
+
+        if let Some(endorsements) = &key.endorsements
+            && !endorsements
+                .iter()
+                .any(|endorsement| endorsement == &activity.channel_id)
+        {
+            anyhow::bail!(
+                "signing key is not endorsed for channel {}",
+                activity.channel_id
+            );
+        }
+        if let Some(service_url) = &claims.serviceurl
+            && service_url.trim_end_matches('/') != activity.service_url.trim_end_matches('/')
+        {
+            anyhow::bail!("token serviceurl does not match the activity");
+        }
+
+        Ok(())
+    }
+
+    /// Look up a signing key, refreshing the key set when it is stale or the
+    /// key is unknown (keys rotate).
+    async fn signing_key(&self, key_id: &str) -> anyhow::Result<(SigningKey, String)> {
+        {
+            let keys = self.keys.read().await;
+            if keys.is_fresh()
+                && let Some(key) = keys.keys.get(key_id)
+            {
+                return Ok((key.clone(), keys.issuer.clone()));
+            }
+        }
+
+        let _guard = self.refresh_lock.lock().await;
+        {
+            let keys = self.keys.read().await;
+            let recently_fetched = keys
+                .fetched_at
+                .is_some_and(|fetched_at| fetched_at.elapsed() < SIGNING_KEYS_MIN_REFRESH);
+            if let Some(key) = keys.keys.get(key_id)
+                && keys.is_fresh()
+            {
+                return Ok((key.clone(), keys.issuer.clone()));
+            }
+            if recently_fetched {
+                anyhow::bail!("unknown signing key {key_id}");
+            }
+        }
+
+        let fetched = self.fetch_keys().await?;
+        let mut keys = self.keys.write().await;
+        *keys = fetched;
+        keys.keys
+            .get(key_id)
+            .map(|key| (key.clone(), keys.issuer.clone()))
+            .with_context(|| format!("unknown signing key {key_id}"))
+    }
+
+    async fn fetch_keys(&self) -> anyhow::Result<SigningKeys> {
+        let metadata: OpenIdMetadata = self
+            .client
+            .get(&self.openid_metadata_url)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .context("failed to fetch bot framework openid metadata")?
+            .json()
+            .await
+            .context("failed to parse bot framework openid metadata")?;
+        let key_set: JsonWebKeySet = self
+            .client
+            .get(&metadata.jwks_uri)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .context("failed to fetch bot framework signing keys")?
+            .json()
+            .await
+            .context("failed to parse bot framework signing keys")?;
+
+        let decode = |part: &str| base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(part);
+        let keys = key_set
+            .keys
+            .into_iter()
+            .filter(|key| key.kty == "RSA")
+            .filter_map(|key| {
+                Some((
+                    key.kid?,
+                    SigningKey {
+                        modulus: decode(key.n.as_deref()?).ok()?,
+                        exponent: decode(key.e.as_deref()?).ok()?,
+                        endorsements: key.endorsements,
+                    },
+                ))
+            })
+            .collect();
+
+        Ok(SigningKeys {
+            issuer: metadata.issuer,
+            keys,
+            fetched_at: Some(Instant::now()),
+        })
+    }
+}
+
+/// Check issuer, audience, and lifetime claims with some clock skew.
+fn validate_claims(
+    claims: &BotFrameworkClaims,
+    issuer: &str,
+    app_id: &str,
+    now: i64,
+) -> anyhow::Result<()> {
+    if claims.iss != issuer {
+        anyhow::bail!("unexpected token issuer {}", claims.iss);
+    }
+    let audience_matches = match &claims.aud {
+        serde_json::Value::String(audience) => audience == app_id,
+        serde_json::Value::Array(audiences) => audiences
+            .iter()
+            .any(|audience| audience.as_str() == Some(app_id)),
+        _ => false,
+    };
+    if !audience_matches {
+        anyhow::bail!("token audience does not match the app id");
+    }
+    if claims.exp + JWT_CLOCK_SKEW_SECS < now {
+        anyhow::bail!("token expired");
+    }
+    if claims
+        .nbf
+        .is_some_and(|not_before| not_before - JWT_CLOCK_SKEW_SECS > now)
+    {
+        anyhow::bail!("token not yet valid");
+    }
+    Ok(())
+}
+
+fn split_message(text: &str, max_len: usize) -> Vec<String> {
+    if text.len() <= max_len {
+        return vec![text.to_string()];
+    }
+
+    let mut chunks = Vec::new();
+    let mut remaining = text;
+
+    while !remaining.is_empty() {
+        if remaining.len() <= max_len {
+            chunks.push(remaining.to_string());
+            break;
+        }
+
+        let search_end = remaining.floor_char_boundary(max_len);
+        let search_region = &remaining[..search_end];
+        let break_point = search_region
+            .rfind('\n')
+            .or_else(|| search_region.rfind(' '))
+            .filter(|&pos| pos > 0)
+            .unwrap_or(search_end);
+
+        let end = remaining.floor_char_boundary(break_point);
+        chunks.push(remaining[..end].to_string());
+        remaining = remaining[end..].trim_start_matches('\n').trim_start();
+    }
+
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Button, CardField, SelectMenu, SelectOption};
+    use futures::StreamExt as _;
+
+    /// 2048-bit RSA key in PKCS#1 DER, used only to sign test tokens.
+    const TEST_SIGNING_KEY: &str = concat!(
+        "MIIEpAIBAAKCAQEAl26qwphnLjvl72CTWHuoTo+0tmmMNMHRQC+iR9OJkYNxvOsc/NbiFqgbF/uTShRJzDGcqrEo",
+        "gnFxaudayjHSXkSkAp+xbPrcf5ndk5z9PKDOwE7FHOSnJ2n08Vv998mDLs6exFPIxbD/H353WI8tdakTPbcaNgZy",
+        "CQltwTW/cS2wZ7XqRiM+LjXjAxHuWe3WKtimcSO0fYf/AaH/Jx68jqlsy/NgP6QCUuQkjBfKruR3qT4ZTzfoiPdA",
+        "3TWMx60LBa68JiHhnTxz5+CzHnzWNcMdbdwYzCopkZ8Q7UA9bop/HAF9pR5wSRjZ9qYLRbglBOsl/Ob/hdGOD9af",
+        "qt392wIDAQABAoIBABAdSLKYgTJcUWWdw1BBKHHjvPlEVhUvItNPvlciXZtMf4f5BQaq3zhF/0wYC15wtkGybdeE",
+        "Lqli5QzZPcISdy5s438b+0d7kodJmlig9hnDivzP5Q3kDx2UmuosPeRmXz1V43NE7zKKWTvCu3SDF/VTXqbMm09w",
+        "dCYeCL3suEiUyCSDswFyI1WxWynDXJH9MB+n6xPQn6xVMk1fBvFgrrK72plhhS3eXdDfb7EWpTGOgCjyvfffHCUv",
+        "LJDzysIHT0ATVA8E/wjqUE9b+CHfCF/kCZGfx0iGYubodO49l6+HZyRkjOR8dOH404UoGEbuIbEHA1u/6oNqxY8w",
+        "s/jXbFECgYEAze1tar2hxMYBZdIA3E94R+R1Zdu+cLvXPTOjqylmVpS3oSPtuM4RYVSNreBscEZxfv+rGsU1JQW7",
+        "ViurB27lLPsAIwQNyp/LiiBlIjOLZvwRYam4wdm8EaYEnf7cDWqJvaMeUVRZrpTUL/0r1e6rJz9yzDT1xiWBKRLh",
+        "ri3ZRbUCgYEAvEEGXk6vjPEZg5x0UOiM0wjKX8uDh9nius8xVyITIx3AQ7gFtPl7S1hyMaQFkgyw3vy4jKYprGA9",
+        "ZOz+mfuv9dnW5fyGP70nKLVWuurTsTEpttwYcNn+q3AkVEqVAviCmmwyoozF4Y0AZ/l905YZhcSQMlQxTmXF5Z0R",
+        "BNA4b08CgYEAu99kVuDhSJPZTJq2fEjolLaCwMYlTdah/U0yggLIQi92BMaxqEwgoSqyA7NfW2N5QoNhCJBo9vju",
+        "vCQk6igrX8kzi760EmuNF9yMSBK8mN1Zwj9Et169bWCIj9inzHmpZuD91Pdexp8zowCYUJppfeouAs5CeF9ZIi7+",
+        "xJlBw40CgYB9z9tItb+fKxaJHcuisvhCpZJSmTmLVjOv836fI/e0NVLkLx0pSL58qVyQDnVmmTKvpChWX0pkmGEP",
+        "jU/OayW64d48EBtMh8ae7ypKe1wFBL1DJy7yZM5NlwHAj6Osu5bVS1A/0GMDDW/jDRtph9wAyVAxQpE9OskaXnEc",
+        "TWgaAwKBgQDH40ZuHsr5+2P9lGuRqHjowwaVvE5O9eQy164wa5BGuFUWJoqp+XqulwNxYP3QG8GPNMoNc1BB1qzI",
+        "4GPwe3m08bfgxET1Mb3JkiuqxeYr7qsD+0R1b77SHwNXltNbBepjx7Xvb5yJQ9v+t3LNkcmhfoNBdKW1S+8qEMaE",
+        "N+DbIQ==",
+    );
+    const TEST_KEY_ID: &str = "test-key";
+    const TEST_ISSUER: &str = "https://api.botframework.com";
+    const APP_ID: &str = "00000000-0000-0000-0000-000000000001";
+    const BOT_ID: &str = "28:00000000-0000-0000-0000-000000000001";
+
+    fn test_key_pair() -> ring::signature::RsaKeyPair {
+        let der = base64::engine::general_purpose::STANDARD
+            .decode(TEST_SIGNING_KEY)
+            .unwrap();
+        ring::signature::RsaKeyPair::from_der(&der).unwrap()
+    }
+
+    fn sign_token(claims: serde_json::Value) -> String {
+        let encode = |value: &serde_json::Value| {
+            base64::engine::general_purpose::URL_SAFE_NO_PAD
+                .encode(serde_json::to_vec(value).unwrap())
+        };
+        let header = serde_json::json!({ "alg": "RS256", "kid": TEST_KEY_ID, "typ": "JWT" });
+        let signing_input = format!("{}.{}", encode(&header), encode(&claims));
+
+        let key_pair = test_key_pair();
+        let mut signature = vec![0; key_pair.public().modulus_len()];
+        key_pair
+            .sign(
+                &ring::signature::RSA_PKCS1_SHA256,
+                &ring::rand::SystemRandom::new(),
+                signing_input.as_bytes(),
+                &mut signature,
+            )
+            .unwrap();
+        format!(
+            "{signing_input}.{}",
+            base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(signature)
+        )
+    }
+
+    fn claims_for(service_url: &str, audience: &str) -> serde_json::Value {
+        let now = chrono::Utc::now().timestamp();
+        serde_json::json!({
+            "iss": TEST_ISSUER,
+            "aud": audience,
+            "nbf": now - 10,
+            "exp": now + 600,
+            "serviceurl": service_url,
+        })
+    }
+
+    fn channel_activity(service_url: &str) -> serde_json::Value {
+        serde_json::json!({
+            "type": "message",
+            "id": "1700000000001",
+            "serviceUrl": service_url,
+            "channelId": "msteams",
+            "from": { "id": "29:ada", "name": "Ada", "aadObjectId": "aad-ada" },
+            "recipient": { "id": BOT_ID, "name": "Spacebot" },
+            "conversation": {
+                "id": "19:general@thread.tacv2;messageid=1700000000000",
+                "conversationType": "channel",
+                "tenantId": "tenant-1",
+            },
+            "text": "<at>Spacebot</at> how is the <at>Deploy Bot</at> doing?",
+            "entities": [{
+                "type": "mention",
+                "text": "<at>Spacebot</at>",
+                "mentioned": { "id": BOT_ID, "name": "Spacebot" },
+            }],
+            "replyToId": "1700000000000",
+            "channelData": {
+                "tenant": { "id": "tenant-1" },
+                "team": { "id": "team-1", "name": "Engineering" },
+                "channel": { "id": "19:general@thread.tacv2", "name": "General" },
+            },
+        })
+    }
+
+    fn build(
+        activity: serde_json::Value,
+        permissions: &TeamsPermissions,
+    ) -> Option<InboundMessage> {
+        let activity: Activity = serde_json::from_value(activity).unwrap();
+        build_inbound_message(
+            &activity,
+            &MessageBuildContext {
+                runtime_key: "teams",
+                permissions,
+            },
+        )
+    }
+
+    #[test]
+    fn builds_channel_message_with_mention_and_metadata() {
+        let message = build(
+            channel_activity("https://smba.trafficmanager.net/amer/"),
+            &TeamsPermissions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(message.conversation_id, "teams:19:general@thread.tacv2");
+        assert_eq!(message.sender_id, "aad-ada");
+        assert!(matches!(
+            &message.content,
+            MessageContent::Text(text) if text == "how is the @Deploy Bot doing?"
+        ));
+        assert_eq!(message.metadata["teams_mentions_or_replies_to_bot"], true);
+        assert_eq!(message.metadata["teams_is_dm"], false);
+        assert_eq!(message.metadata["teams_team_id"], "team-1");
+        assert_eq!(
+            message.metadata["teams_channel_id"],
+            "19:general@thread.tacv2"
+        );
+        assert_eq!(
+            message.metadata["teams_conversation_id"],
+            "19:general@thread.tacv2;messageid=1700000000000"
+        );
+        assert_eq!(
+            message.metadata[crate::metadata_keys::SERVER_NAME],
+            "Engineering"
+        );
+        assert_eq!(
+            message.metadata[crate::metadata_keys::CHANNEL_NAME],
+            "General"
+        );
+
+        let other_team = TeamsPermissions {
+            team_filter: Some(vec!["team-2".into()]),
+            ..Default::default()
+        };
+        assert!(
+            build(
+                channel_activity("https://smba.trafficmanager.net/amer/"),
+                &other_team
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn personal_messages_fail_closed() {
+        let mut activity = channel_activity("https://smba.trafficmanager.net/amer/");
+        activity["conversation"] = serde_json::json!({
+            "id": "a:1personal",
+            "conversationType": "personal",
+        });
+        activity["channelData"] = serde_json::json!({ "tenant": { "id": "tenant-1" } });
+        activity["entities"] = serde_json::json!([]);
+        activity["text"] = serde_json::json!("hello");
+
+        assert!(build(activity.clone(), &TeamsPermissions::default()).is_none());
+
+        let allowed = TeamsPermissions {
+            dm_allowed_users: vec!["AAD-ADA".into()],
+            ..Default::default()
+        };
+        let message = build(activity, &allowed).unwrap();
+        assert_eq!(message.conversation_id, "teams:a:1personal");
+        assert_eq!(message.metadata["teams_is_dm"], true);
+        assert_eq!(message.metadata["teams_mentions_or_replies_to_bot"], true);
+    }
+
+    #[test]
+    fn card_submits_map_to_interactions() {
+        let mut activity = channel_activity("https://smba.trafficmanager.net/amer/");
+        activity["text"] = serde_json::Value::Null;
+        activity["value"] = serde_json::json!({
+            SUBMIT_ACTION_KEY: "environment",
+            "environment": "staging",
+            SUBMIT_LABELS_KEY: { "staging": "Staging", "production": "Production" },
+        });
+        let message = build(activity, &TeamsPermissions::default()).unwrap();
+
+        let MessageContent::Interaction {
+            action_id,
+            values,
+            label,
+            message_ts,
+            ..
+        } = message.content
+        else {
+            panic!("expected an interaction");
+        };
+        assert_eq!(action_id, "environment");
+        assert_eq!(values, vec!["staging".to_string()]);
+        assert_eq!(label.as_deref(), Some("Staging"));
+        assert_eq!(message_ts.as_deref(), Some("1700000000000"));
+
+        let button =
+            interaction_from_submit(&serde_json::json!({ SUBMIT_ACTION_KEY: "approve" }), None)
+                .unwrap();
+        assert!(matches!(
+            button,
+            MessageContent::Interaction { action_id, values, .. }
+                if action_id == "approve" && values.is_empty()
+        ));
+    }
+
+    #[test]
+    fn renders_cards_and_interactive_elements_as_adaptive_cards() {
+        let card = Card {
+            title: Some("Deploy".into()),
+            url: Some("https://ci.example.com/1".into()),
+            description: Some("Ready to ship".into()),
+            fields: vec![CardField {
+                name: "Commit".into(),
+                value: "abc123".into(),
+                inline: true,
+            }],
+            ..Default::default()
+        };
+        let elements = vec![
+            InteractiveElements::Buttons {
+                buttons: vec![
+                    Button {
+                        label: "Approve".into(),
+                        custom_id: Some("approve".into()),
+                        style: ButtonStyle::Primary,
+                        url: None,
+                    },
+                    Button {
+                        label: "Logs".into(),
+                        custom_id: None,
+                        style: ButtonStyle::Link,
+                        url: Some("https://ci.example.com/1/logs".into()),
+                    },
+                ],
+            },
+            InteractiveElements::Select {
+                select: SelectMenu {
+                    custom_id: "environment".into(),
+                    options: vec![SelectOption {
+                        label: "Staging".into(),
+                        value: "staging".into(),
+                        description: None,
+                        emoji: None,
+                    }],
+                    placeholder: Some("Environment".into()),
+                },
+            },
+        ];
+
+        let attachments = adaptive_card_attachments(&[card], &elements);
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(attachments[0]["contentType"], ADAPTIVE_CARD_CONTENT_TYPE);
+
+        let content = &attachments[0]["content"];
+        assert_eq!(
+            content["body"][0]["text"],
+            "[Deploy](https://ci.example.com/1)"
+        );
+        assert_eq!(content["body"][2]["facts"][0]["title"], "Commit");
+        assert_eq!(content["body"][3]["type"], "Input.ChoiceSet");
+        assert_eq!(content["body"][3]["id"], "environment");
+
+        let actions = content["actions"].as_array().unwrap();
+        assert_eq!(actions[0]["type"], "Action.Submit");
+        assert_eq!(actions[0]["style"], "positive");
+        assert_eq!(actions[0]["data"][SUBMIT_ACTION_KEY], "approve");
+        assert_eq!(actions[1]["type"], "Action.OpenUrl");
+        assert_eq!(actions[2]["data"][SUBMIT_ACTION_KEY], "environment");
+        assert_eq!(actions[2]["data"][SUBMIT_LABELS_KEY]["staging"], "Staging");
+
+        // Interactive elements without cards still get a card to live on.
+        assert_eq!(adaptive_card_attachments(&[], &elements).len(), 1);
+        assert!(adaptive_card_attachments(&[], &[]).is_empty());
+    }
+
+    #[test]
+    fn claim_validation_rejects_wrong_issuer_audience_and_expiry() {
+        let now = 1_700_000_000;
+        let claims = |iss: &str, aud: serde_json::Value, exp: i64| BotFrameworkClaims {
+            iss: iss.into(),
+            aud,
+            exp,
+            nbf: Some(now - 60),
+            serviceurl: None,
+        };
+
+        assert!(
+            validate_claims(
+                &claims(TEST_ISSUER, APP_ID.into(), now + 60),
+                TEST_ISSUER,
+                APP_ID,
+                now
+            )
+            .is_ok()
+        );
+        assert!(
+            validate_claims(
+                &claims(TEST_ISSUER, serde_json::json!(["other", APP_ID]), now + 60),
+                TEST_ISSUER,
+                APP_ID,
+                now
+            )
+            .is_ok()
+        );
+        // Within clock skew.
+        assert!(
+            validate_claims(
+                &claims(TEST_ISSUER, APP_ID.into(), now - 60),
+                TEST_ISSUER,
+                APP_ID,
+                now
+            )
+            .is_ok()
+        );
+
+        assert!(
+            validate_claims(
+                &claims("https://evil.example", APP_ID.into(), now + 60),
+                TEST_ISSUER,
+                APP_ID,
+                now
+            )
+            .is_err()
+        );
+        assert!(
+            validate_claims(
+                &claims(TEST_ISSUER, "other-app".into(), now + 60),
+                TEST_ISSUER,
+                APP_ID,
+                now
+            )
+            .is_err()
+        );
+        assert!(
+            validate_claims(
+                &claims(TEST_ISSUER, APP_ID.into(), now - 3600),
+                TEST_ISSUER,
+                APP_ID,
+                now
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn service_urls_must_be_https_or_loopback() {
+        assert!(is_allowed_service_url(
+            "https://smba.trafficmanager.net/amer/"
+        ));
+        assert!(is_allowed_service_url("http://127.0.0.1:3979/"));
+        assert!(is_allowed_service_url("http://localhost:3979"));
+        assert!(!is_allowed_service_url(
+            "http://smba.trafficmanager.net/amer/"
+        ));
+        assert!(!is_allowed_service_url("file:///etc/passwd"));
+    }
+
+    type Recorded = Arc<std::sync::Mutex<Vec<(String, String, serde_json::Value)>>>;
+
+    /// Serve the OpenID metadata, signing keys, token endpoint, and connector
+    /// API the adapter talks to. Connector requests are recorded as
+    /// `(path, authorization, body)`.
+    async fn spawn_connector_stand_in() -> (String, Recorded) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let public_key =
+            ring::signature::RsaPublicKeyComponents::<Vec<u8>>::from(test_key_pair().public());
+        let encode = |bytes: &[u8]| base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes);
+        let keys = serde_json::json!({
+            "keys": [{
+                "kty": "RSA",
+                "use": "sig",
+                "kid": TEST_KEY_ID,
+                "n": encode(&public_key.n),
+                "e": encode(&public_key.e),
+                "endorsements": ["msteams"],
+            }]
+        });
+        let metadata = serde_json::json!({
+            "issuer": TEST_ISSUER,
+            "jwks_uri": format!("{base_url}/keys"),
+        });
+
+        let recorded: Recorded = Arc::default();
+        let app = Router::new()
+            .route("/openid", get(move || async move { axum::Json(metadata) }))
+            .route("/keys", get(move || async move { axum::Json(keys) }))
+            .route(
+                "/token",
+                post(|| async {
+                    axum::Json(serde_json::json!({
+                        "token_type": "Bearer",
+                        "access_token": "connector-token",
+                        "expires_in": 3600,
+                    }))
+                }),
+            )
+            .fallback({
+                let recorded = recorded.clone();
+                move |uri: axum::http::Uri, headers: HeaderMap, body: Bytes| {
+                    let recorded = recorded.clone();
+                    async move {
+                        let authorization = headers
+                            .get(AUTHORIZATION)
+                            .and_then(|value| value.to_str().ok())
+                            .unwrap_or_default()
+                            .to_string();
+                        let body = serde_json::from_slice(&body).unwrap_or_default();
+                        recorded.lock().unwrap().push((
+                            uri.path().to_string(),
+                            authorization,
+                            body,
+                        ));
+                        axum::Json(serde_json::json!({ "id": "activity-1" }))
+                    }
+                }
+            });
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.ok();
+        });
+
+        (base_url, recorded)
+    }
+
+    async fn free_port() -> u16 {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        listener.local_addr().unwrap().port()
+    }
+
+    fn test_adapter(base_url: &str, port: u16, store: PathBuf) -> TeamsAdapter {
+        let settings = TeamsConnectionSettings {
+            app_id: APP_ID.into(),
+            app_password: "secret".into(),
+            tenant_id: Some("tenant-1".into()),
+            bind: "127.0.0.1".into(),
+            port,
+            openid_metadata_url: format!("{base_url}/openid"),
+            token_url: format!("{base_url}/token"),
+        };
+        TeamsAdapter::new(
+            "teams",
+            settings,
+            Some(store),
+            Arc::new(ArcSwap::from_pointee(TeamsPermissions::default())),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn round_trips_against_local_connector() {
+        let (base_url, recorded) = spawn_connector_stand_in().await;
+        let directory = tempfile::tempdir().unwrap();
+        let store_path = conversation_store_path(directory.path(), None);
+        let port = free_port().await;
+        let adapter = test_adapter(&base_url, port, store_path.clone());
+        let mut inbound = adapter.start().await.unwrap();
+
+        let endpoint = format!("http://127.0.0.1:{port}/api/messages");
+        let activity = channel_activity(&base_url);
+        let client = Client::new();
+        let post_activity = |token: Option<String>| {
+            let mut request = client.post(&endpoint).json(&activity);
+            if let Some(token) = token {
+                request = request.bearer_auth(token);
+            }
+            request.send()
+        };
+
+        let unsigned = post_activity(None).await.unwrap();
+        assert_eq!(unsigned.status(), reqwest::StatusCode::UNAUTHORIZED);
+        let wrong_audience = post_activity(Some(sign_token(claims_for(&base_url, "other-app"))))
+            .await
+            .unwrap();
+        assert_eq!(wrong_audience.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+        let accepted = post_activity(Some(sign_token(claims_for(&base_url, APP_ID))))
+            .await
+            .unwrap();
+        assert_eq!(accepted.status(), reqwest::StatusCode::OK);
+
+        let message = tokio::time::timeout(Duration::from_secs(5), inbound.next())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(message.conversation_id, "teams:19:general@thread.tacv2");
+
+        adapter
+            .respond(&message, OutboundResponse::Text("All green.".into()))
+            .await
+            .unwrap();
+        adapter
+            .broadcast(
+                "19:general@thread.tacv2",
+                OutboundResponse::Text("Deploy finished.".into()),
+            )
+            .await
+            .unwrap();
+
+        let requests = recorded.lock().unwrap().clone();
+        assert_eq!(requests.len(), 2);
+        let (reply_path, authorization, reply) = &requests[0];
+        assert_eq!(
+            reply_path,
+            "/v3/conversations/19:general@thread.tacv2;messageid=1700000000000/activities/1700000000001"
+        );
+        assert_eq!(authorization, "Bearer connector-token");
+        assert_eq!(reply["text"], "All green.");
+        assert_eq!(reply["from"]["id"], BOT_ID);
+        let (broadcast_path, _, broadcast) = &requests[1];
+        assert_eq!(
+            broadcast_path,
+            "/v3/conversations/19:general@thread.tacv2/activities"
+        );
+        assert_eq!(broadcast["text"], "Deploy finished.");
+
+        // Unknown conversations are permanent broadcast failures.
+        let error = adapter
+            .broadcast(
+                "19:unknown@thread.tacv2",
+                OutboundResponse::Text("hi".into()),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(
+            crate::messaging::traits::broadcast_failure_kind(&error),
+            crate::messaging::traits::BroadcastFailureKind::Permanent
+        );
+
+        adapter.shutdown().await.unwrap();
+
+        // The conversation reference survives a restart.
+        let restarted = test_adapter(&base_url, port, store_path);
+        let reference = restarted
+            .resolve_reference("19:general@thread.tacv2")
+            .await
+            .unwrap();
+        assert_eq!(reference.service_url, base_url);
+        assert_eq!(reference.bot_id, BOT_ID);
+    }
+}
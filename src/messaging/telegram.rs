@@ -1,7 +1,9 @@
 //! Telegram messaging adapter using teloxide.
 
+use crate::commands::CommandSpec;
 use crate::config::TelegramPermissions;
 use crate::messaging::apply_runtime_adapter_to_conversation_id;
+use crate::messaging::streaming::StreamRegistry;
 use crate::messaging::traits::{InboundStream, Messaging};
 use crate::{Attachment, InboundMessage, MessageContent, OutboundResponse, StatusUpdate};
 
@@ -11,14 +13,13 @@ use regex::Regex;
 use teloxide::payloads::setters::*;
 use teloxide::requests::{Request, Requester};
 use teloxide::types::{
-    ChatAction, ChatId, FileId, InputFile, InputPollOption, MediaKind, MessageId, MessageKind,
-    ParseMode, ReactionType, ReplyParameters, UpdateKind, UserId,
+    BotCommand, ChatAction, ChatId, FileId, InputFile, InputPollOption, MediaKind, MessageId,
+    MessageKind, ParseMode, ReactionType, ReplyParameters, UpdateKind, UserId,
 };
 use teloxide::{ApiError, Bot, RequestError};
 
 use std::collections::{HashMap, VecDeque};
 use std::sync::{Arc, LazyLock};
-use std::time::Instant;
 use tokio::sync::{RwLock, mpsc};
 use tokio::task::JoinHandle;
 
@@ -32,19 +33,14 @@ pub struct TelegramAdapter {
     bot: Bot,
     bot_user_id: Arc<RwLock<Option<UserId>>>,
     bot_username: Arc<RwLock<Option<String>>>,
-    /// Maps conversation_id to the message_id being edited during streaming.
-    active_messages: Arc<RwLock<HashMap<String, ActiveStream>>>,
+    /// Maps InboundMessage.id to the message being edited during streaming.
+    streams: Arc<StreamRegistry<(ChatId, MessageId)>>,
     /// Repeating typing indicator tasks per conversation_id.
     typing_tasks: Arc<RwLock<HashMap<String, JoinHandle<()>>>>,
     /// Shutdown signal for the polling loop.
     shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
-}
-
-/// Tracks an in-progress streaming message edit.
-struct ActiveStream {
-    chat_id: ChatId,
-    message_id: MessageId,
-    last_edit: Instant,
+    /// Chat commands published to the bot's command menu on start.
+    commands: Vec<CommandSpec>,
 }
 
 /// Telegram's per-message character limit.
@@ -71,9 +67,31 @@ impl TelegramAdapter {
             bot,
             bot_user_id: Arc::new(RwLock::new(None)),
             bot_username: Arc::new(RwLock::new(None)),
-            active_messages: Arc::new(RwLock::new(HashMap::new())),
+            streams: Arc::new(
+                StreamRegistry::new(MAX_MESSAGE_LENGTH).with_edit_interval(STREAM_EDIT_INTERVAL),
+            ),
             typing_tasks: Arc::new(RwLock::new(HashMap::new())),
             shutdown_tx: Arc::new(RwLock::new(None)),
+            commands: Vec::new(),
+        }
+    }
+
+    /// Publish these chat commands to the bot's command menu on start.
+    pub fn with_commands(mut self, commands: Vec<CommandSpec>) -> Self {
+        self.commands = commands;
+        self
+    }
+
+    /// Replace the bot's command menu with the registered chat commands.
+    async fn publish_commands(&self) {
+        let commands = telegram_bot_commands(&self.commands);
+        if commands.is_empty() {
+            return;
+        }
+        let count = commands.len();
+        match self.bot.set_my_commands(commands).send().await {
+            Ok(_) => tracing::info!(count, "telegram bot commands registered"),
+            Err(error) => tracing::warn!(%error, "failed to register telegram bot commands"),
         }
     }
 
@@ -101,6 +119,32 @@ impl TelegramAdapter {
             handle.abort();
         }
     }
+
+    /// Edit a streaming preview, retrying as plain text if Telegram rejects
+    /// the HTML rendering.
+    async fn edit_stream_message(
+        &self,
+        chat_id: ChatId,
+        message_id: MessageId,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        let html = markdown_to_telegram_html(text);
+        if let Err(html_error) = self
+            .bot
+            .edit_message_text(chat_id, message_id, &html)
+            .parse_mode(ParseMode::Html)
+            .send()
+            .await
+        {
+            tracing::debug!(%html_error, "HTML edit failed, retrying as plain text");
+            self.bot
+                .edit_message_text(chat_id, message_id, text)
+                .send()
+                .await
+                .context("failed to edit telegram message")?;
+        }
+        Ok(())
+    }
 }
 
 impl Messaging for TelegramAdapter {
@@ -128,6 +172,7 @@ impl Messaging for TelegramAdapter {
             bot_username = ?me.username,
             "telegram connected"
         );
+        self.publish_commands().await;
 
         let bot = self.bot.clone();
         let runtime_key = self.runtime_key.clone();
@@ -435,55 +480,36 @@ impl Messaging for TelegramAdapter {
                     .await
                     .context("failed to send stream placeholder")?;
 
-                self.active_messages.write().await.insert(
-                    message.conversation_id.clone(),
-                    ActiveStream {
-                        chat_id,
-                        message_id: placeholder.id,
-                        last_edit: Instant::now(),
-                    },
-                );
+                self.streams
+                    .start(&message.id, (chat_id, placeholder.id))
+                    .await;
             }
             OutboundResponse::StreamChunk(text) => {
-                let mut active = self.active_messages.write().await;
-                if let Some(stream) = active.get_mut(&message.conversation_id) {
-                    if stream.last_edit.elapsed() < STREAM_EDIT_INTERVAL {
-                        return Ok(());
-                    }
-
-                    let display_text = if text.len() > MAX_MESSAGE_LENGTH {
-                        let end = text.floor_char_boundary(MAX_MESSAGE_LENGTH - 3);
-                        format!("{}...", &text[..end])
-                    } else {
-                        text
-                    };
-
-                    let html = markdown_to_telegram_html(&display_text);
-                    if let Err(html_error) = self
-                        .bot
-                        .edit_message_text(stream.chat_id, stream.message_id, &html)
-                        .parse_mode(ParseMode::Html)
-                        .send()
+                if let Some(edit) = self.streams.update(&message.id, text).await {
+                    let (chat_id, message_id) = edit.handle;
+                    if let Err(error) = self
+                        .edit_stream_message(chat_id, message_id, &edit.text)
                         .await
                     {
-                        tracing::debug!(%html_error, "HTML edit failed, retrying as plain text");
-                        if let Err(error) = self
-                            .bot
-                            .edit_message_text(stream.chat_id, stream.message_id, &display_text)
-                            .send()
-                            .await
-                        {
-                            tracing::debug!(%error, "failed to edit streaming message");
-                        }
+                        tracing::debug!(%error, "failed to edit streaming message");
                     }
-                    stream.last_edit = Instant::now();
                 }
             }
             OutboundResponse::StreamEnd => {
-                self.active_messages
-                    .write()
-                    .await
-                    .remove(&message.conversation_id);
+                if let Some(stream) = self.streams.finish(&message.id).await {
+                    let (chat_id, message_id) = stream.handle;
+                    let mut chunks =
+                        split_message(&stream.text, FORMATTED_SPLIT_LENGTH).into_iter();
+                    if let Some(first) = chunks.next()
+                        && let Err(error) =
+                            self.edit_stream_message(chat_id, message_id, &first).await
+                    {
+                        tracing::warn!(%error, "failed to finalize streaming message");
+                    }
+                    for chunk in chunks {
+                        send_formatted(&self.bot, chat_id, &chunk, None).await?;
+                    }
+                }
             }
             OutboundResponse::Status(status) => {
                 self.send_status(message, status).await?;
@@ -572,7 +598,13 @@ impl Messaging for TelegramAdapter {
         Ok(())
     }
 
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
     async fn shutdown(&self) -> crate::Result<()> {
+        self.streams.clear().await;
+
         // Cancel all typing indicator tasks
         let mut tasks = self.typing_tasks.write().await;
         for (_, handle) in tasks.drain() {
@@ -1000,6 +1032,34 @@ async fn send_poll(bot: &Bot, chat_id: ChatId, poll: &crate::Poll) -> anyhow::Re
     Ok(())
 }
 
+/// Telegram command names are 1-32 lowercase letters, digits or `_`, with a
+/// 1-256 character description. Commands that can't be represented (such as
+/// `/mention-only`) still work when typed; they're just left off the menu.
+fn telegram_bot_commands(specs: &[CommandSpec]) -> Vec<BotCommand> {
+    specs
+        .iter()
+        .filter(|spec| {
+            !spec.name.is_empty()
+                && spec.name.len() <= 32
+                && spec
+                    .name
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        })
+        .map(|spec| {
+            let description = if spec.description.trim().is_empty() {
+                spec.name.as_str()
+            } else {
+                spec.description.trim()
+            };
+            BotCommand::new(
+                &spec.name,
+                description.chars().take(256).collect::<String>(),
+            )
+        })
+        .collect()
+}
+
 /// Split a message into chunks that fit within Telegram's character limit.
 /// Tries to split at newlines, then spaces, then hard-cuts.
 fn split_message(text: &str, max_len: usize) -> Vec<String> {
@@ -1267,6 +1327,30 @@ async fn send_formatted(
 mod tests {
     use super::*;
 
+    #[test]
+    fn bot_commands_skip_names_telegram_rejects() {
+        let spec = |name: &str, description: &str| CommandSpec {
+            name: name.into(),
+            description: description.into(),
+            args: Vec::new(),
+        };
+        let commands = telegram_bot_commands(&[
+            spec("status", "current mode"),
+            spec("mention-only", "mention mode"),
+            spec("standup_notes", ""),
+        ]);
+        assert_eq!(
+            commands
+                .iter()
+                .map(|command| (command.command.as_str(), command.description.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("status", "current mode"),
+                ("standup_notes", "standup_notes")
+            ]
+        );
+    }
+
     #[test]
     fn bold() {
         assert_eq!(
@@ -19,6 +19,14 @@ pub struct HistoryMessage {
     pub timestamp: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+/// Connection state tracked by an adapter's background loop.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, utoipa::ToSchema)]
+pub struct AdapterHealth {
+    pub healthy: bool,
+    /// Short human-readable summary, e.g. which folders get push delivery.
+    pub detail: String,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BroadcastFailureKind {
     Transient,
@@ -245,6 +253,20 @@ pub trait Messaging: Send + Sync + 'static {
     /// Health check.
     fn health_check(&self) -> impl std::future::Future<Output = Result<()>> + Send;
 
+    /// Last known connection state, for status endpoints. Unlike
+    /// `health_check` this must not touch the network. Adapters that don't
+    /// track one return `None`.
+    fn connection_health(&self) -> Option<AdapterHealth> {
+        None
+    }
+
+    /// Whether `respond` handles `StreamStart`/`StreamChunk`/`StreamEnd`
+    /// itself, usually by editing a preview message. When `false`, the
+    /// manager buffers the stream and sends the final text as one `Text`.
+    fn supports_streaming(&self) -> bool {
+        false
+    }
+
     /// Graceful shutdown.
     fn shutdown(&self) -> impl std::future::Future<Output = Result<()>> + Send {
         async { Ok(()) }
@@ -288,6 +310,10 @@ pub trait MessagingDyn: Send + Sync + 'static {
         &'a self,
     ) -> Pin<Box<dyn std::future::Future<Output = Result<()>> + Send + 'a>>;
 
+    fn connection_health(&self) -> Option<AdapterHealth>;
+
+    fn supports_streaming(&self) -> bool;
+
     fn shutdown<'a>(&'a self)
     -> Pin<Box<dyn std::future::Future<Output = Result<()>> + Send + 'a>>;
 }
@@ -342,6 +368,14 @@ impl<T: Messaging> MessagingDyn for T {
         Box::pin(Messaging::health_check(self))
     }
 
+    fn connection_health(&self) -> Option<AdapterHealth> {
+        Messaging::connection_health(self)
+    }
+
+    fn supports_streaming(&self) -> bool {
+        Messaging::supports_streaming(self)
+    }
+
     fn shutdown<'a>(
         &'a self,
     ) -> Pin<Box<dyn std::future::Future<Output = Result<()>> + Send + 'a>> {
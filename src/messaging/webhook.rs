@@ -1,45 +1,218 @@
 //! Webhook messaging adapter for programmatic access.
 //!
-//! Exposes an HTTP server that accepts inbound messages via POST and
-//! delivers responses via a per-conversation polling endpoint. This is
-//! the integration point for scripts, CI pipelines, and other programs
-//! that need to interact with Spacebot programmatically.
+//! Exposes an HTTP server that accepts inbound messages via POST. Responses
+//! are pushed to a `callback_url` as HMAC-signed POSTs when one is given,
+//! streamed over SSE to connected clients, or buffered for a per-conversation
+//! polling endpoint. This is the integration point for scripts, CI pipelines,
+//! and other programs that need to interact with Spacebot programmatically.
 
 use std::collections::HashMap;
+use std::convert::Infallible;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Context as _;
 use axum::Router;
 use axum::extract::{Json, State};
-use axum::http::header::AUTHORIZATION;
+use axum::http::header::{AUTHORIZATION, CONTENT_TYPE};
 use axum::http::{HeaderMap, StatusCode};
+use axum::response::sse::{Event, KeepAlive, Sse};
 use axum::routing::{get, post};
+use base64::Engine as _;
+use futures::Stream;
+use hmac::{Hmac, Mac as _};
 use serde::{Deserialize, Serialize};
-use tokio::sync::{RwLock, mpsc};
+use tokio::sync::{RwLock, broadcast, mpsc};
 
-use crate::messaging::traits::{InboundStream, Messaging};
+use crate::messaging::traits::{
+    BroadcastFailureKind, InboundStream, Messaging, broadcast_failure_kind,
+    mark_permanent_broadcast, mark_retryable_broadcast,
+};
 use crate::{InboundMessage, MessageContent, OutboundResponse};
 
+/// Header carrying the Unix timestamp a callback was signed at.
+pub const CALLBACK_TIMESTAMP_HEADER: &str = "x-spacebot-timestamp";
+/// Header carrying `sha256=<hex>` HMAC of `"{timestamp}.{body}"`.
+pub const CALLBACK_SIGNATURE_HEADER: &str = "x-spacebot-signature";
+
+/// Metadata key holding the per-request callback URL on inbound messages.
+const CALLBACK_URL_METADATA_KEY: &str = "webhook_callback_url";
+
+const CALLBACK_MAX_ATTEMPTS: u32 = 4;
+const CALLBACK_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+#[cfg(test)]
+const CALLBACK_INITIAL_RETRY_DELAY: Duration = Duration::from_millis(1);
+#[cfg(not(test))]
+const CALLBACK_INITIAL_RETRY_DELAY: Duration = Duration::from_millis(500);
+#[cfg(test)]
+const CALLBACK_MAX_RETRY_DELAY: Duration = Duration::from_millis(8);
+#[cfg(not(test))]
+const CALLBACK_MAX_RETRY_DELAY: Duration = Duration::from_secs(8);
+/// How long a conversation's callback worker waits for more work before exiting.
+const CALLBACK_WORKER_IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Capacity of the SSE fan-out channel shared by all conversations.
+const SSE_CHANNEL_CAPACITY: usize = 512;
+
 /// Webhook adapter state.
 pub struct WebhookAdapter {
     port: u16,
     bind: String,
     auth_token: Option<String>,
+    /// Key for signing callbacks. Falls back to `auth_token` when unset.
+    signing_secret: Option<String>,
+    client: reqwest::Client,
     inbound_tx: Arc<RwLock<Option<mpsc::Sender<InboundMessage>>>>,
     /// Buffered responses per conversation_id, waiting to be polled.
     response_buffers: Arc<RwLock<HashMap<String, Vec<WebhookResponse>>>>,
+    /// Callback URL registered per conversation_id by `/send` requests.
+    /// Dropped once the conversation's reply has been delivered.
+    callback_urls: Arc<RwLock<HashMap<String, String>>>,
+    /// Queue of the callback worker for each conversation with replies in flight.
+    callback_queues: CallbackQueues,
+    sse: SseHub,
     shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
 }
 
+type CallbackQueues = Arc<std::sync::Mutex<HashMap<String, mpsc::UnboundedSender<CallbackJob>>>>;
+
+/// A reply waiting to be POSTed to a callback URL.
+struct CallbackJob {
+    url: String,
+    body: Vec<u8>,
+    response: WebhookResponse,
+    /// Whether this ends the reply, after which the conversation's registered
+    /// callback URL is dropped.
+    final_response: bool,
+}
+
+/// Delivers one conversation's callbacks in order, off the channel's turn.
+/// Exits after `CALLBACK_WORKER_IDLE_TIMEOUT` without work.
+struct CallbackWorker {
+    conversation_key: String,
+    client: reqwest::Client,
+    signing_key: Option<String>,
+    response_buffers: Arc<RwLock<HashMap<String, Vec<WebhookResponse>>>>,
+    callback_urls: Arc<RwLock<HashMap<String, String>>>,
+    queues: CallbackQueues,
+}
+
+impl CallbackWorker {
+    async fn run(self, mut rx: mpsc::UnboundedReceiver<CallbackJob>) {
+        loop {
+            let job = match tokio::time::timeout(CALLBACK_WORKER_IDLE_TIMEOUT, rx.recv()).await {
+                Ok(Some(job)) => job,
+                Ok(None) => break,
+                Err(_) => {
+                    // Jobs are only queued under this lock, so nothing can
+                    // arrive between the emptiness check and the removal.
+                    let Ok(mut queues) = self.queues.lock() else {
+                        break;
+                    };
+                    if rx.is_empty() {
+                        queues.remove(&self.conversation_key);
+                        break;
+                    }
+                    continue;
+                }
+            };
+            self.deliver(job).await;
+        }
+    }
+
+    async fn deliver(&self, job: CallbackJob) {
+        if let Err(error) = post_callback(
+            &self.client,
+            self.signing_key.as_deref(),
+            &job.url,
+            &job.body,
+        )
+        .await
+        {
+            tracing::warn!(
+                conversation_key = %self.conversation_key,
+                %error,
+                "webhook callback failed, buffering response for polling"
+            );
+            buffer_response(&self.response_buffers, &self.conversation_key, job.response).await;
+        }
+
+        if job.final_response {
+            let mut callback_urls = self.callback_urls.write().await;
+            if callback_urls.get(&self.conversation_key) == Some(&job.url) {
+                callback_urls.remove(&self.conversation_key);
+            }
+        }
+    }
+}
+
 /// Shared state for axum handlers.
 #[derive(Clone)]
 struct AppState {
     inbound_tx: Arc<RwLock<Option<mpsc::Sender<InboundMessage>>>>,
     response_buffers: Arc<RwLock<HashMap<String, Vec<WebhookResponse>>>>,
+    callback_urls: Arc<RwLock<HashMap<String, String>>>,
+    sse: SseHub,
     auth_token: Option<String>,
     runtime_key: String,
 }
 
+/// Fan-out of responses to SSE clients, with a per-conversation count of
+/// connected clients so responses nobody is listening for get buffered.
+#[derive(Clone)]
+struct SseHub {
+    tx: broadcast::Sender<(String, WebhookResponse)>,
+    subscribers: Arc<std::sync::Mutex<HashMap<String, usize>>>,
+}
+
+impl SseHub {
+    fn new() -> Self {
+        let (tx, _) = broadcast::channel(SSE_CHANNEL_CAPACITY);
+        Self {
+            tx,
+            subscribers: Arc::new(std::sync::Mutex::new(HashMap::new())),
+        }
+    }
+
+    fn has_subscribers(&self, conversation_key: &str) -> bool {
+        self.subscribers
+            .lock()
+            .map(|subscribers| subscribers.contains_key(conversation_key))
+            .unwrap_or(false)
+    }
+
+    fn subscribe(&self, conversation_key: &str) -> SseSubscription {
+        if let Ok(mut subscribers) = self.subscribers.lock() {
+            *subscribers.entry(conversation_key.to_string()).or_default() += 1;
+        }
+        SseSubscription {
+            conversation_key: conversation_key.to_string(),
+            rx: self.tx.subscribe(),
+            subscribers: self.subscribers.clone(),
+        }
+    }
+}
+
+/// A connected SSE client. Unregisters itself when the stream is dropped.
+struct SseSubscription {
+    conversation_key: String,
+    rx: broadcast::Receiver<(String, WebhookResponse)>,
+    subscribers: Arc<std::sync::Mutex<HashMap<String, usize>>>,
+}
+
+impl Drop for SseSubscription {
+    fn drop(&mut self) {
+        if let Ok(mut subscribers) = self.subscribers.lock()
+            && let Some(count) = subscribers.get_mut(&self.conversation_key)
+        {
+            *count -= 1;
+            if *count == 0 {
+                subscribers.remove(&self.conversation_key);
+            }
+        }
+    }
+}
+
 /// Inbound webhook request body.
 #[derive(Debug, Deserialize)]
 struct WebhookRequest {
@@ -52,13 +225,18 @@ struct WebhookRequest {
     content: String,
     /// Optional agent to route to (overrides binding resolution).
     agent_id: Option<String>,
+    /// URL to POST every response for this conversation to instead of
+    /// buffering it for `/poll`. Remembered for proactive sends on the same
+    /// conversation until the reply is delivered; an empty string clears it.
+    /// Only accepted when an `auth_token` is configured.
+    callback_url: Option<String>,
 }
 
 fn default_sender() -> String {
     "webhook".into()
 }
 
-/// A buffered response waiting to be polled.
+/// A response delivered by callback, SSE, or polling.
 #[derive(Debug, Clone, Serialize)]
 struct WebhookResponse {
     #[serde(rename = "type")]
@@ -67,6 +245,67 @@ struct WebhookResponse {
     filename: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     caption: Option<String>,
+    /// Base64-encoded file contents.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mime_type: Option<String>,
+}
+
+impl WebhookResponse {
+    fn new(response_type: &str, content: Option<String>) -> Self {
+        Self {
+            response_type: response_type.into(),
+            content,
+            filename: None,
+            caption: None,
+            data: None,
+            mime_type: None,
+        }
+    }
+
+    /// Map an outbound response to its webhook form. Returns `None` for
+    /// variants that mean nothing over HTTP (reactions, status updates).
+    fn from_outbound(response: OutboundResponse) -> Option<Self> {
+        let webhook_response = match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::RichMessage { text, .. }
+            | OutboundResponse::ThreadReply { text, .. }
+            // Slack-specific rich variants fall back to plain text.
+            | OutboundResponse::Ephemeral { text, .. }
+            | OutboundResponse::ScheduledMessage { text, .. } => Self::new("text", Some(text)),
+            OutboundResponse::File {
+                filename,
+                data,
+                mime_type,
+                caption,
+            } => Self {
+                filename: Some(filename),
+                caption,
+                data: Some(base64::engine::general_purpose::STANDARD.encode(data)),
+                mime_type: Some(mime_type),
+                ..Self::new("file", None)
+            },
+            OutboundResponse::StreamStart => Self::new("stream_start", None),
+            OutboundResponse::StreamChunk(text) => Self::new("stream_chunk", Some(text)),
+            OutboundResponse::StreamEnd => Self::new("stream_end", None),
+            OutboundResponse::Reaction(_)
+            | OutboundResponse::RemoveReaction(_)
+            | OutboundResponse::Status(_) => return None,
+        };
+        Some(webhook_response)
+    }
+}
+
+/// Body of a callback POST.
+#[derive(Debug, Serialize)]
+struct WebhookCallback<'a> {
+    conversation_id: &'a str,
+    /// ID of the inbound message being answered. Absent for proactive sends.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    in_reply_to: Option<&'a str>,
+    #[serde(flatten)]
+    response: &'a WebhookResponse,
 }
 
 /// Response from the poll endpoint.
@@ -77,15 +316,236 @@ struct PollResponse {
 
 impl WebhookAdapter {
     pub fn new(port: u16, bind: impl Into<String>, auth_token: Option<String>) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(CALLBACK_REQUEST_TIMEOUT)
+            .build()
+            .unwrap_or_else(|error| {
+                tracing::warn!(
+                    %error,
+                    "failed to build reqwest client for webhook adapter; falling back to default client"
+                );
+                reqwest::Client::new()
+            });
+
         Self {
             port,
             bind: bind.into(),
             auth_token,
+            signing_secret: None,
+            client,
             inbound_tx: Arc::new(RwLock::new(None)),
             response_buffers: Arc::new(RwLock::new(HashMap::new())),
+            callback_urls: Arc::new(RwLock::new(HashMap::new())),
+            callback_queues: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            sse: SseHub::new(),
             shutdown_tx: Arc::new(RwLock::new(None)),
         }
     }
+
+    /// Sign callbacks with `signing_secret` instead of the auth token.
+    pub fn with_signing_secret(mut self, signing_secret: Option<String>) -> Self {
+        self.signing_secret = signing_secret;
+        self
+    }
+
+    fn signing_key(&self) -> Option<&str> {
+        self.signing_secret
+            .as_deref()
+            .or(self.auth_token.as_deref())
+    }
+
+    /// Route one response: push it to the callback URL if there is one, else
+    /// stream it to connected SSE clients, else buffer it for `/poll`.
+    ///
+    /// A callback that still fails after retries is buffered so the response
+    /// isn't lost, and the failure is returned. Replies to inbound messages
+    /// go through `enqueue_callback` instead, so they never wait on retries.
+    async fn deliver(
+        &self,
+        conversation_key: &str,
+        callback_url: Option<String>,
+        response: WebhookResponse,
+    ) -> crate::Result<()> {
+        if let Some(callback_url) = callback_url {
+            let body = callback_body(conversation_key, None, &response)?;
+            match post_callback(&self.client, self.signing_key(), &callback_url, &body).await {
+                Ok(()) => return Ok(()),
+                Err(error) => {
+                    tracing::warn!(
+                        conversation_key,
+                        %error,
+                        "webhook callback failed, buffering response for polling"
+                    );
+                    self.buffer(conversation_key, response).await;
+                    return Err(error);
+                }
+            }
+        }
+
+        if self.sse.has_subscribers(conversation_key) {
+            // Send fails only when every receiver is gone, which is the same
+            // race as a client disconnecting just after we checked.
+            if let Err(broadcast::error::SendError((_, response))) =
+                self.sse.tx.send((conversation_key.to_string(), response))
+            {
+                self.buffer(conversation_key, response).await;
+            }
+            return Ok(());
+        }
+
+        self.buffer(conversation_key, response).await;
+        Ok(())
+    }
+
+    async fn buffer(&self, conversation_key: &str, response: WebhookResponse) {
+        buffer_response(&self.response_buffers, conversation_key, response).await;
+    }
+
+    /// Hand a reply to the conversation's callback worker, starting one if
+    /// none is running, so a slow receiver never holds up the channel.
+    fn enqueue_callback(&self, conversation_key: &str, job: CallbackJob) {
+        let Ok(mut queues) = self.callback_queues.lock() else {
+            return;
+        };
+        let job = match queues.get(conversation_key) {
+            Some(queue) => match queue.send(job) {
+                Ok(()) => return,
+                Err(mpsc::error::SendError(job)) => job,
+            },
+            None => job,
+        };
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        tx.send(job).ok();
+        queues.insert(conversation_key.to_string(), tx);
+        let worker = CallbackWorker {
+            conversation_key: conversation_key.to_string(),
+            client: self.client.clone(),
+            signing_key: self.signing_key().map(str::to_string),
+            response_buffers: self.response_buffers.clone(),
+            callback_urls: self.callback_urls.clone(),
+            queues: self.callback_queues.clone(),
+        };
+        tokio::spawn(worker.run(rx));
+    }
+}
+
+async fn buffer_response(
+    response_buffers: &RwLock<HashMap<String, Vec<WebhookResponse>>>,
+    conversation_key: &str,
+    response: WebhookResponse,
+) {
+    response_buffers
+        .write()
+        .await
+        .entry(conversation_key.to_string())
+        .or_default()
+        .push(response);
+}
+
+/// Serialize the callback body for a response on `conversation_key`.
+fn callback_body(
+    conversation_key: &str,
+    in_reply_to: Option<&str>,
+    response: &WebhookResponse,
+) -> crate::Result<Vec<u8>> {
+    let conversation_id = conversation_key
+        .strip_prefix("webhook:")
+        .unwrap_or(conversation_key);
+    let body = serde_json::to_vec(&WebhookCallback {
+        conversation_id,
+        in_reply_to,
+        response,
+    })
+    .context("failed to serialize webhook callback")?;
+    Ok(body)
+}
+
+/// POST a callback, retrying transient failures with exponential backoff.
+async fn post_callback(
+    client: &reqwest::Client,
+    signing_key: Option<&str>,
+    url: &str,
+    body: &[u8],
+) -> crate::Result<()> {
+    let mut delay = CALLBACK_INITIAL_RETRY_DELAY;
+
+    for attempt in 1..=CALLBACK_MAX_ATTEMPTS {
+        match post_callback_once(client, signing_key, url, body).await {
+            Ok(()) => return Ok(()),
+            Err(error) => {
+                if broadcast_failure_kind(&error) == BroadcastFailureKind::Transient
+                    && attempt < CALLBACK_MAX_ATTEMPTS
+                {
+                    tracing::warn!(
+                        attempt,
+                        max_attempts = CALLBACK_MAX_ATTEMPTS,
+                        retry_delay_ms = delay.as_millis(),
+                        %error,
+                        "webhook callback failed with retryable error"
+                    );
+                    tokio::time::sleep(delay).await;
+                    delay = (delay * 2).min(CALLBACK_MAX_RETRY_DELAY);
+                    continue;
+                }
+                return Err(error);
+            }
+        }
+    }
+
+    unreachable!("callback retry loop must return on success or terminal error")
+}
+
+async fn post_callback_once(
+    client: &reqwest::Client,
+    signing_key: Option<&str>,
+    url: &str,
+    body: &[u8],
+) -> crate::Result<()> {
+    let timestamp = chrono::Utc::now().timestamp();
+    let mut request = client
+        .post(url)
+        .header(CONTENT_TYPE, "application/json")
+        .header(CALLBACK_TIMESTAMP_HEADER, timestamp.to_string());
+    if let Some(key) = signing_key {
+        request = request.header(
+            CALLBACK_SIGNATURE_HEADER,
+            sign_callback(key, timestamp, body),
+        );
+    }
+
+    let response = request
+        .body(body.to_vec())
+        .send()
+        .await
+        .map_err(mark_retryable_broadcast)?;
+
+    let status = response.status();
+    if status.is_success() {
+        return Ok(());
+    }
+    let error = anyhow::anyhow!("webhook callback to {url} returned {status}");
+    if status.is_server_error()
+        || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+        || status == reqwest::StatusCode::REQUEST_TIMEOUT
+    {
+        Err(mark_retryable_broadcast(error))
+    } else {
+        Err(mark_permanent_broadcast(error))
+    }
+}
+
+/// Compute the `x-spacebot-signature` value for a callback body.
+///
+/// Receivers should recompute `HMAC-SHA256(secret, "{timestamp}.{body}")`,
+/// compare in constant time, and reject stale timestamps.
+pub fn sign_callback(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac = Hmac::<sha2::Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
 }
 
 impl Messaging for WebhookAdapter {
@@ -103,6 +563,8 @@ impl Messaging for WebhookAdapter {
         let state = AppState {
             inbound_tx: self.inbound_tx.clone(),
             response_buffers: self.response_buffers.clone(),
+            callback_urls: self.callback_urls.clone(),
+            sse: self.sse.clone(),
             auth_token: self.auth_token.clone(),
             runtime_key: self.name().to_string(),
         };
@@ -112,10 +574,16 @@ impl Messaging for WebhookAdapter {
                 "webhook authentication is disabled because no auth token is configured"
             );
         }
+        if self.signing_key().is_none() {
+            tracing::warn!(
+                "webhook callbacks will be unsigned because neither a signing secret nor an auth token is configured"
+            );
+        }
 
         let app = Router::new()
             .route("/send", post(handle_send))
             .route("/poll/{conversation_id}", get(handle_poll))
+            .route("/events/{conversation_id}", get(handle_events))
             .route("/health", get(handle_health))
             .with_state(state);
 
@@ -149,84 +617,78 @@ impl Messaging for WebhookAdapter {
         message: &InboundMessage,
         response: OutboundResponse,
     ) -> crate::Result<()> {
-        let webhook_response = match response {
-            OutboundResponse::Text(text) => WebhookResponse {
-                response_type: "text".into(),
-                content: Some(text),
-                filename: None,
-                caption: None,
-            },
-            OutboundResponse::RichMessage { text, .. } => WebhookResponse {
-                response_type: "text".into(),
-                content: Some(text),
-                filename: None,
-                caption: None,
-            },
-            OutboundResponse::ThreadReply { text, .. } => WebhookResponse {
-                response_type: "text".into(),
-                content: Some(text),
-                filename: None,
-                caption: None,
-            },
-            OutboundResponse::File {
-                filename, caption, ..
-            } => WebhookResponse {
-                response_type: "file".into(),
-                content: None,
-                filename: Some(filename),
-                caption,
-            },
-            OutboundResponse::StreamStart => WebhookResponse {
-                response_type: "stream_start".into(),
-                content: None,
-                filename: None,
-                caption: None,
-            },
-            OutboundResponse::StreamChunk(text) => WebhookResponse {
-                response_type: "stream_chunk".into(),
-                content: Some(text),
-                filename: None,
-                caption: None,
-            },
-            OutboundResponse::StreamEnd => WebhookResponse {
-                response_type: "stream_end".into(),
-                content: None,
-                filename: None,
-                caption: None,
-            },
-            // Reactions, status updates, and remove-reaction aren't meaningful over webhook
-            OutboundResponse::Reaction(_)
-            | OutboundResponse::RemoveReaction(_)
-            | OutboundResponse::Status(_) => return Ok(()),
-            // Slack-specific rich variants — fall back to plain text
-            OutboundResponse::Ephemeral { text, .. } => WebhookResponse {
-                response_type: "text".into(),
-                content: Some(text),
-                filename: None,
-                caption: None,
-            },
-            OutboundResponse::ScheduledMessage { text, .. } => WebhookResponse {
-                response_type: "text".into(),
-                content: Some(text),
-                filename: None,
-                caption: None,
+        let Some(webhook_response) = WebhookResponse::from_outbound(response) else {
+            return Ok(());
+        };
+
+        let callback_url = match message
+            .metadata
+            .get(CALLBACK_URL_METADATA_KEY)
+            .and_then(|value| value.as_str())
+        {
+            Some(url) => Some(url.to_string()),
+            None => self
+                .callback_urls
+                .read()
+                .await
+                .get(&message.conversation_id)
+                .cloned(),
+        };
+
+        let Some(callback_url) = callback_url else {
+            return self
+                .deliver(&message.conversation_id, None, webhook_response)
+                .await;
+        };
+
+        // Deliver off the channel's turn: callback retries can take seconds.
+        let body = callback_body(
+            &message.conversation_id,
+            Some(&message.id),
+            &webhook_response,
+        )?;
+        let final_response = !matches!(
+            webhook_response.response_type.as_str(),
+            "stream_start" | "stream_chunk"
+        );
+        self.enqueue_callback(
+            &message.conversation_id,
+            CallbackJob {
+                url: callback_url,
+                body,
+                response: webhook_response,
+                final_response,
             },
+        );
+        Ok(())
+    }
+
+    async fn broadcast(&self, target: &str, response: OutboundResponse) -> crate::Result<()> {
+        let Some(webhook_response) = WebhookResponse::from_outbound(response) else {
+            return Ok(());
         };
 
-        self.response_buffers
-            .write()
+        let conversation_key = format!("webhook:{target}");
+        let callback_url = self
+            .callback_urls
+            .read()
             .await
-            .entry(message.conversation_id.clone())
-            .or_default()
-            .push(webhook_response);
+            .get(&conversation_key)
+            .cloned();
 
-        Ok(())
+        self.deliver(&conversation_key, callback_url, webhook_response)
+            .await
     }
 
     async fn health_check(&self) -> crate::Result<()> {
         Ok(())
     }
 
+    /// Stream events are forwarded to callbacks and SSE clients as-is.
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
     async fn shutdown(&self) -> crate::Result<()> {
         if let Some(tx) = self.shutdown_tx.read().await.as_ref() {
             tx.send(()).await.ok();
@@ -255,7 +717,40 @@ async fn handle_send(
         ));
     };
 
+    let conversation_id = format!("webhook:{}", request.conversation_id);
+
     let mut metadata = HashMap::new();
+    match request.callback_url.as_deref().map(str::trim) {
+        Some("") => {
+            state.callback_urls.write().await.remove(&conversation_id);
+        }
+        Some(callback_url) => {
+            // Without authentication anyone who can reach `/send` could make
+            // this server POST to arbitrary (including internal) addresses.
+            if state.auth_token.is_none() {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    "callback_url requires an auth_token to be configured".into(),
+                ));
+            }
+            if !is_valid_callback_url(callback_url) {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    "callback_url must be an absolute http or https URL".into(),
+                ));
+            }
+            state
+                .callback_urls
+                .write()
+                .await
+                .insert(conversation_id.clone(), callback_url.to_string());
+            metadata.insert(
+                CALLBACK_URL_METADATA_KEY.into(),
+                serde_json::Value::String(callback_url.to_string()),
+            );
+        }
+        None => {}
+    }
     metadata.insert(
         "webhook_conversation_id".into(),
         serde_json::Value::String(request.conversation_id.clone()),
@@ -273,8 +768,6 @@ async fn handle_send(
         serde_json::Value::String(request.conversation_id.clone()),
     );
 
-    let conversation_id = format!("webhook:{}", request.conversation_id);
-
     let inbound = InboundMessage {
         id: uuid::Uuid::new_v4().to_string(),
         source: "webhook".into(),
@@ -315,10 +808,67 @@ async fn handle_poll(
     Ok(Json(PollResponse { messages }))
 }
 
+async fn handle_events(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    axum::extract::Path(conversation_id): axum::extract::Path<String>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, (StatusCode, String)> {
+    if !is_authorized(&headers, state.auth_token.as_deref()) {
+        return Err((StatusCode::UNAUTHORIZED, "unauthorized".into()));
+    }
+
+    let key = format!("webhook:{conversation_id}");
+    // Subscribe before draining the buffer so nothing slips between the two.
+    let mut subscription = state.sse.subscribe(&key);
+    let backlog = state
+        .response_buffers
+        .write()
+        .await
+        .remove(&key)
+        .unwrap_or_default();
+
+    let stream = async_stream::stream! {
+        for response in backlog {
+            if let Ok(json) = serde_json::to_string(&response) {
+                yield Ok(Event::default().event("message").data(json));
+            }
+        }
+        loop {
+            match crate::classify_broadcast_recv_result(subscription.rx.recv().await) {
+                crate::BroadcastRecvResult::Event((event_key, response)) => {
+                    if event_key != subscription.conversation_key {
+                        continue;
+                    }
+                    if let Ok(json) = serde_json::to_string(&response) {
+                        yield Ok(Event::default().event("message").data(json));
+                    }
+                }
+                crate::BroadcastRecvResult::Lagged(count) => {
+                    yield Ok(Event::default()
+                        .event("lagged")
+                        .data(format!("{{\"skipped\":{count}}}")));
+                }
+                crate::BroadcastRecvResult::Closed => break,
+            }
+        }
+    };
+
+    Ok(Sse::new(stream).keep_alive(
+        KeepAlive::new()
+            .interval(Duration::from_secs(15))
+            .text("ping"),
+    ))
+}
+
 async fn handle_health() -> StatusCode {
     StatusCode::OK
 }
 
+fn is_valid_callback_url(url: &str) -> bool {
+    reqwest::Url::parse(url)
+        .is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.host().is_some())
+}
+
 fn is_authorized(headers: &HeaderMap, expected_token: Option<&str>) -> bool {
     let Some(expected_token) = expected_token else {
         return true;
@@ -338,3 +888,286 @@ fn is_authorized(headers: &HeaderMap, expected_token: Option<&str>) -> bool {
         .and_then(|value| value.strip_prefix("Bearer "))
         .is_some_and(|token| token == expected_token)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Serve a callback receiver that answers with `statuses` in order (the
+    /// last one repeats) and records every request it sees.
+    async fn spawn_callback_receiver(
+        statuses: Vec<StatusCode>,
+    ) -> (String, Arc<std::sync::Mutex<Vec<(HeaderMap, String)>>>) {
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = Router::new().route(
+            "/callback",
+            post({
+                let received = received.clone();
+                move |headers: HeaderMap, body: String| {
+                    let received = received.clone();
+                    let calls = calls.clone();
+                    let statuses = statuses.clone();
+                    async move {
+                        received.lock().unwrap().push((headers, body));
+                        let call = calls.fetch_add(1, Ordering::SeqCst);
+                        statuses[call.min(statuses.len() - 1)]
+                    }
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.ok();
+        });
+        (format!("http://{address}/callback"), received)
+    }
+
+    /// Poll `condition` until it holds, for work done on a spawned task.
+    async fn wait_until(mut condition: impl AsyncFnMut() -> bool) {
+        for _ in 0..200 {
+            if condition().await {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        panic!("condition not met in time");
+    }
+
+    fn inbound(conversation_id: &str, callback_url: Option<&str>) -> InboundMessage {
+        let mut message = InboundMessage::empty();
+        message.id = "msg-1".into();
+        message.source = "webhook".into();
+        message.conversation_id = format!("webhook:{conversation_id}");
+        if let Some(callback_url) = callback_url {
+            message.metadata.insert(
+                CALLBACK_URL_METADATA_KEY.into(),
+                serde_json::Value::String(callback_url.into()),
+            );
+        }
+        message
+    }
+
+    #[test]
+    fn sign_callback_matches_manual_hmac() {
+        let body = br#"{"type":"text"}"#;
+        let signature = sign_callback("secret", 1_700_000_000, body);
+
+        let mut mac = Hmac::<sha2::Sha256>::new_from_slice(b"secret").unwrap();
+        mac.update(b"1700000000.");
+        mac.update(body);
+        let expected = hex::encode(mac.finalize().into_bytes());
+
+        assert_eq!(signature, format!("sha256={expected}"));
+        assert_ne!(signature, sign_callback("secret", 1_700_000_001, body));
+        assert_ne!(signature, sign_callback("other", 1_700_000_000, body));
+    }
+
+    #[test]
+    fn file_response_carries_base64_data() {
+        let response = WebhookResponse::from_outbound(OutboundResponse::File {
+            filename: "report.txt".into(),
+            data: b"hello".to_vec(),
+            mime_type: "text/plain".into(),
+            caption: Some("the report".into()),
+        })
+        .unwrap();
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["type"], "file");
+        assert_eq!(json["filename"], "report.txt");
+        assert_eq!(json["data"], "aGVsbG8=");
+        assert_eq!(json["mime_type"], "text/plain");
+        assert_eq!(json["caption"], "the report");
+    }
+
+    #[test]
+    fn stream_and_status_variants_map_as_expected() {
+        let chunk =
+            WebhookResponse::from_outbound(OutboundResponse::StreamChunk("par".into())).unwrap();
+        assert_eq!(chunk.response_type, "stream_chunk");
+        assert_eq!(chunk.content.as_deref(), Some("par"));
+        assert!(WebhookResponse::from_outbound(OutboundResponse::Reaction("👍".into())).is_none());
+    }
+
+    #[test]
+    fn callback_url_validation() {
+        assert!(is_valid_callback_url(
+            "https://ci.example.com/hooks/spacebot"
+        ));
+        assert!(is_valid_callback_url("http://127.0.0.1:8080/cb"));
+        assert!(!is_valid_callback_url("ftp://example.com/cb"));
+        assert!(!is_valid_callback_url("not a url"));
+        assert!(!is_valid_callback_url("file:///etc/passwd"));
+    }
+
+    #[tokio::test]
+    async fn respond_posts_signed_callback_after_transient_failure() {
+        let (url, received) =
+            spawn_callback_receiver(vec![StatusCode::SERVICE_UNAVAILABLE, StatusCode::OK]).await;
+        let adapter = WebhookAdapter::new(0, "127.0.0.1", Some("token".into()))
+            .with_signing_secret(Some("signing".into()));
+
+        adapter
+            .respond(
+                &inbound("ci-42", Some(&url)),
+                OutboundResponse::Text("done".into()),
+            )
+            .await
+            .unwrap();
+
+        wait_until(async || received.lock().unwrap().len() == 2).await;
+        assert!(adapter.response_buffers.read().await.is_empty());
+        let received = received.lock().unwrap();
+        let (headers, body) = &received[1];
+        let timestamp: i64 = headers[CALLBACK_TIMESTAMP_HEADER]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(
+            headers[CALLBACK_SIGNATURE_HEADER].to_str().unwrap(),
+            sign_callback("signing", timestamp, body.as_bytes())
+        );
+        let json: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(json["conversation_id"], "ci-42");
+        assert_eq!(json["in_reply_to"], "msg-1");
+        assert_eq!(json["type"], "text");
+        assert_eq!(json["content"], "done");
+    }
+
+    #[tokio::test]
+    async fn permanent_callback_failure_buffers_for_polling() {
+        let (url, received) = spawn_callback_receiver(vec![StatusCode::BAD_REQUEST]).await;
+        let adapter = WebhookAdapter::new(0, "127.0.0.1", None);
+
+        adapter
+            .respond(
+                &inbound("ci-43", Some(&url)),
+                OutboundResponse::Text("done".into()),
+            )
+            .await
+            .unwrap();
+
+        wait_until(async || {
+            adapter
+                .response_buffers
+                .read()
+                .await
+                .contains_key("webhook:ci-43")
+        })
+        .await;
+        assert_eq!(received.lock().unwrap().len(), 1, "4xx must not be retried");
+        let buffers = adapter.response_buffers.read().await;
+        assert_eq!(buffers["webhook:ci-43"].len(), 1);
+    }
+
+    #[tokio::test]
+    async fn callbacks_keep_order_and_reply_drops_registration() {
+        let (url, received) = spawn_callback_receiver(vec![StatusCode::OK]).await;
+        let adapter = WebhookAdapter::new(0, "127.0.0.1", Some("token".into()));
+        adapter
+            .callback_urls
+            .write()
+            .await
+            .insert("webhook:ci-44".into(), url);
+
+        let message = inbound("ci-44", None);
+        for response in [
+            OutboundResponse::StreamStart,
+            OutboundResponse::StreamChunk("par".into()),
+            OutboundResponse::StreamEnd,
+        ] {
+            adapter.respond(&message, response).await.unwrap();
+        }
+
+        wait_until(async || adapter.callback_urls.read().await.is_empty()).await;
+        let types: Vec<String> = received
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(_, body)| {
+                let json: serde_json::Value = serde_json::from_str(body).unwrap();
+                json["type"].as_str().unwrap().to_string()
+            })
+            .collect();
+        assert_eq!(types, ["stream_start", "stream_chunk", "stream_end"]);
+    }
+
+    #[tokio::test]
+    async fn send_rejects_callback_url_without_auth_token() {
+        let adapter = WebhookAdapter::new(0, "127.0.0.1", None);
+        let (tx, _rx) = mpsc::channel(1);
+        *adapter.inbound_tx.write().await = Some(tx);
+        let state = AppState {
+            inbound_tx: adapter.inbound_tx.clone(),
+            response_buffers: adapter.response_buffers.clone(),
+            callback_urls: adapter.callback_urls.clone(),
+            sse: adapter.sse.clone(),
+            auth_token: None,
+            runtime_key: "webhook".into(),
+        };
+        let request = WebhookRequest {
+            conversation_id: "ci-45".into(),
+            sender_id: default_sender(),
+            content: "hi".into(),
+            agent_id: None,
+            callback_url: Some("http://169.254.169.254/latest".into()),
+        };
+
+        let (status, _) = handle_send(HeaderMap::new(), State(state), Json(request))
+            .await
+            .unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(adapter.callback_urls.read().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn responses_go_to_sse_subscribers_before_the_poll_buffer() {
+        let adapter = WebhookAdapter::new(0, "127.0.0.1", None);
+        let mut subscription = adapter.sse.subscribe("webhook:live");
+
+        adapter
+            .respond(&inbound("live", None), OutboundResponse::Text("hi".into()))
+            .await
+            .unwrap();
+        adapter
+            .respond(&inbound("idle", None), OutboundResponse::Text("hi".into()))
+            .await
+            .unwrap();
+
+        let (key, response) = subscription.rx.recv().await.unwrap();
+        assert_eq!(key, "webhook:live");
+        assert_eq!(response.content.as_deref(), Some("hi"));
+        let buffers = adapter.response_buffers.read().await;
+        assert!(!buffers.contains_key("webhook:live"));
+        assert_eq!(buffers["webhook:idle"].len(), 1);
+
+        drop(subscription);
+        assert!(!adapter.sse.has_subscribers("webhook:live"));
+    }
+
+    #[tokio::test]
+    async fn broadcast_uses_registered_conversation_callback() {
+        let (url, received) = spawn_callback_receiver(vec![StatusCode::NO_CONTENT]).await;
+        let adapter = WebhookAdapter::new(0, "127.0.0.1", None);
+        adapter
+            .callback_urls
+            .write()
+            .await
+            .insert("webhook:nightly".into(), url);
+
+        adapter
+            .broadcast("nightly", OutboundResponse::Text("report".into()))
+            .await
+            .unwrap();
+
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        let json: serde_json::Value = serde_json::from_str(&received[0].1).unwrap();
+        assert_eq!(json["conversation_id"], "nightly");
+        assert!(json.get("in_reply_to").is_none());
+    }
+}
@@ -18,6 +18,17 @@ pub struct RetriggerResult {
     pub result: String,
 }
 
+/// A previous run of a stateful cron job, passed to the cron state template.
+#[derive(Clone, Debug, Serialize)]
+pub struct CronRunContext {
+    /// When the run executed (UTC timestamp string).
+    pub executed_at: String,
+    /// "delivered", "delivery failed", "no output", or "failed".
+    pub outcome: String,
+    /// Delivered summary, or the error for failed runs.
+    pub summary: String,
+}
+
 /// Template engine for rendering system prompts with dynamic variables.
 ///
 /// Prompts are bundled in the binary as `include_str!` embedded templates.
@@ -80,6 +91,11 @@ impl PromptEngine {
             "cortex_profile",
             crate::prompts::text::get("cortex_profile"),
         )?;
+        env.add_template(
+            "cortex_participant",
+            crate::prompts::text::get("cortex_participant"),
+        )?;
+        env.add_template("autonomy_loop", crate::prompts::text::get("autonomy_loop"))?;
         env.add_template("factory", crate::prompts::text::get("factory"))?;
 
         // Adapter-specific prompt fragments
@@ -152,6 +168,14 @@ impl PromptEngine {
             "fragments/system/profile_synthesis",
             crate::prompts::text::get("fragments/system/profile_synthesis"),
         )?;
+        env.add_template(
+            "fragments/system/cron_state",
+            crate::prompts::text::get("fragments/system/cron_state"),
+        )?;
+        env.add_template(
+            "fragments/system/participant_synthesis",
+            crate::prompts::text::get("fragments/system/participant_synthesis"),
+        )?;
         env.add_template(
             "fragments/system/ingestion_chunk",
             crate::prompts::text::get("fragments/system/ingestion_chunk"),
@@ -430,6 +454,49 @@ impl PromptEngine {
         )
     }
 
+    /// Render the prompt for a stateful cron run, wrapping the job prompt with
+    /// its saved state document and recent results.
+    pub fn render_system_cron_state(
+        &self,
+        prompt: &str,
+        state: Option<&str>,
+        state_updated_at: Option<&str>,
+        previous_runs: &[CronRunContext],
+        max_state_chars: usize,
+    ) -> Result<String> {
+        self.render(
+            "fragments/system/cron_state",
+            context! {
+                prompt => prompt,
+                state => state,
+                state_updated_at => state_updated_at,
+                previous_runs => previous_runs,
+                max_state_chars => max_state_chars,
+            },
+        )
+    }
+
+    /// Render the participant summary prompt for one human.
+    pub fn render_system_participant_synthesis(
+        &self,
+        display_name: &str,
+        max_words: usize,
+        known_context: Option<&str>,
+        memory_results: &str,
+        recent_messages: &str,
+    ) -> Result<String> {
+        self.render(
+            "fragments/system/participant_synthesis",
+            context! {
+                display_name => display_name,
+                max_words => max_words,
+                known_context => known_context,
+                memory_results => memory_results,
+                recent_messages => recent_messages,
+            },
+        )
+    }
+
     /// Convenience method for rendering cortex synthesis prompt.
     pub fn render_system_cortex_synthesis(
         &self,
@@ -557,6 +624,7 @@ impl PromptEngine {
             None,
             None,
             None,
+            None,
         )
     }
 
@@ -609,6 +677,34 @@ impl PromptEngine {
         )
     }
 
+    /// Render the autonomy loop system prompt for one cycle.
+    #[allow(clippy::too_many_arguments)]
+    pub fn render_autonomy_prompt(
+        &self,
+        current_time: &str,
+        identity_context: Option<String>,
+        memory_bulletin: Option<String>,
+        goals: Option<String>,
+        previous_findings: Option<String>,
+        active_tasks: Option<String>,
+        max_workers: usize,
+        tasks_require_approval: bool,
+    ) -> Result<String> {
+        self.render(
+            "autonomy_loop",
+            context! {
+                current_time => current_time,
+                identity_context => identity_context,
+                memory_bulletin => memory_bulletin,
+                goals => goals,
+                previous_findings => previous_findings,
+                active_tasks => active_tasks,
+                max_workers => max_workers,
+                tasks_require_approval => tasks_require_approval,
+            },
+        )
+    }
+
     /// Render the factory system prompt for agent creation conversations.
     ///
     /// The factory prompt instructs the LLM on how to create and configure new agents
@@ -666,6 +762,7 @@ impl PromptEngine {
         backfill_transcript: Option<String>,
         working_memory: Option<String>,
         channel_activity_map: Option<String>,
+        participant_info: Option<String>,
     ) -> Result<String> {
         // During the transition, the bulletin is also exposed as knowledge_synthesis
         // so the template can render it under the new heading.
@@ -689,6 +786,7 @@ impl PromptEngine {
                 backfill_transcript => backfill_transcript,
                 working_memory => working_memory,
                 channel_activity_map => channel_activity_map,
+                participant_info => participant_info,
                 knowledge_synthesis => knowledge_synthesis,
             },
         )
@@ -69,6 +69,10 @@ fn lookup(lang: &str, key: &str) -> &'static str {
             include_str!("../../prompts/en/cortex_daily_summary.md.j2")
         }
         ("en", "cortex_profile") => include_str!("../../prompts/en/cortex_profile.md.j2"),
+        ("en", "cortex_participant") => {
+            include_str!("../../prompts/en/cortex_participant.md.j2")
+        }
+        ("en", "autonomy_loop") => include_str!("../../prompts/en/autonomy_loop.md.j2"),
         ("en", "compactor") => include_str!("../../prompts/en/compactor.md.j2"),
         ("en", "memory_persistence") => include_str!("../../prompts/en/memory_persistence.md.j2"),
         ("en", "ingestion") => include_str!("../../prompts/en/ingestion.md.j2"),
@@ -119,6 +123,12 @@ fn lookup(lang: &str, key: &str) -> &'static str {
         ("en", "fragments/system/profile_synthesis") => {
             include_str!("../../prompts/en/fragments/system/profile_synthesis.md.j2")
         }
+        ("en", "fragments/system/cron_state") => {
+            include_str!("../../prompts/en/fragments/system/cron_state.md.j2")
+        }
+        ("en", "fragments/system/participant_synthesis") => {
+            include_str!("../../prompts/en/fragments/system/participant_synthesis.md.j2")
+        }
         ("en", "fragments/system/ingestion_chunk") => {
             include_str!("../../prompts/en/fragments/system/ingestion_chunk.md.j2")
         }
@@ -158,6 +168,9 @@ fn lookup(lang: &str, key: &str) -> &'static str {
             include_str!("../../prompts/en/tools/set_status_description.md.j2")
         }
         ("en", "tools/shell") => include_str!("../../prompts/en/tools/shell_description.md.j2"),
+        ("en", "tools/shell_input") => {
+            include_str!("../../prompts/en/tools/shell_input_description.md.j2")
+        }
         ("en", "tools/install_skill") => {
             include_str!("../../prompts/en/tools/install_skill_description.md.j2")
         }
@@ -189,6 +202,9 @@ fn lookup(lang: &str, key: &str) -> &'static str {
         ("en", "tools/memory_delete") => {
             include_str!("../../prompts/en/tools/memory_delete_description.md.j2")
         }
+        ("en", "tools/memory_promote") => {
+            include_str!("../../prompts/en/tools/memory_promote_description.md.j2")
+        }
         ("en", "tools/channel_recall") => {
             include_str!("../../prompts/en/tools/channel_recall_description.md.j2")
         }
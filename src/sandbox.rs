@@ -87,8 +87,8 @@ const SAFE_ENV_VARS: &[&str] = &["USER", "LANG", "TERM"];
 /// must not be overridden via `passthrough_env`. Allowing user config to replace
 /// PATH would drop `tools/bin` precedence; replacing HOME/TMPDIR would break the
 /// deterministic sandbox-local paths. CI and DEBIAN_FRONTEND suppress interactive
-/// prompts from npm, apt-get, and similar tools that would hang under stdin-less
-/// execution.
+/// prompts from npm, apt-get, and similar tools so workers only fall back to
+/// `shell_input` for prompts that can't be disabled.
 const RESERVED_ENV_VARS: &[&str] = &["PATH", "HOME", "TMPDIR", "CI", "DEBIAN_FRONTEND"];
 
 /// Env vars that enable library injection or alter runtime loading behavior.
@@ -572,7 +572,7 @@ impl Sandbox {
 
         // 12a. Suppress interactive prompts. CI=true prevents npm/npx/yarn
         // from prompting; DEBIAN_FRONTEND=noninteractive prevents apt-get.
-        // Shell tool runs without stdin, so interactive prompts always hang.
+        // Anything that still prompts is answered through `shell_input`.
         cmd.arg("--setenv").arg("CI").arg("true");
         cmd.arg("--setenv")
             .arg("DEBIAN_FRONTEND")
@@ -1101,8 +1101,8 @@ pub trait SystemSecrets {
 /// here.
 pub fn system_secret_registry() -> Vec<&'static SecretField> {
     use crate::config::{
-        DefaultsConfig, DiscordConfig, EmailConfig, LlmConfig, MattermostConfig, SignalConfig,
-        SlackConfig, TelegramConfig, TwitchConfig,
+        DefaultsConfig, DiscordConfig, EmailConfig, IrcConfig, LlmConfig, MatrixConfig,
+        MattermostConfig, SignalConfig, SlackConfig, TeamsConfig, TelegramConfig, TwitchConfig,
     };
 
     let mut fields = Vec::new();
@@ -1118,6 +1118,9 @@ pub fn system_secret_registry() -> Vec<&'static SecretField> {
     fields.extend(EmailConfig::secret_fields());
     fields.extend(SignalConfig::secret_fields());
     fields.extend(MattermostConfig::secret_fields());
+    fields.extend(MatrixConfig::secret_fields());
+    fields.extend(IrcConfig::secret_fields());
+    fields.extend(TeamsConfig::secret_fields());
     fields
 }
 
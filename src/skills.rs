@@ -11,11 +11,17 @@
 //! The channel sees a summary of available skills and is instructed to
 //! delegate skill work to workers. Workers receive the full skill content
 //! in their system prompt.
+//!
+//! A skill can also declare a chat command in its frontmatter (`command`,
+//! plus optional `command_description`, `command_args` and `command_role`).
+//! Invoking the command spawns a worker with the skill.
 
 mod installer;
 
 pub use installer::{install_from_file, install_from_github};
 
+use crate::config::{AccessRole, CommandAction, CommandArgDef, CommandDef};
+
 use anyhow::Context as _;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -37,6 +43,8 @@ pub struct Skill {
     pub source: SkillSource,
     /// GitHub `owner/repo` that this skill was installed from, if any.
     pub source_repo: Option<String>,
+    /// Chat command declared in frontmatter, if any.
+    pub command: Option<CommandDef>,
 }
 
 /// Where a skill was loaded from, used for precedence tracking.
@@ -314,6 +322,7 @@ async fn load_skill(
 
     let description = frontmatter.get("description").cloned().unwrap_or_default();
     let source_repo = frontmatter.get("source_repo").cloned();
+    let command = parse_skill_command(&name, &frontmatter);
 
     // Resolve {baseDir} template variable in the body
     let base_dir_str = base_dir.to_string_lossy();
@@ -327,6 +336,74 @@ async fn load_skill(
         content,
         source,
         source_repo,
+        command,
+    })
+}
+
+/// Build the chat command a skill declares in its frontmatter.
+///
+/// `command_args` is a comma-separated list of argument names; a trailing
+/// `?` marks an argument optional. Invalid declarations are logged and
+/// ignored so a bad command never prevents the skill itself from loading.
+fn parse_skill_command(skill: &str, frontmatter: &HashMap<String, String>) -> Option<CommandDef> {
+    let name = frontmatter.get("command")?.trim().trim_start_matches('/');
+    if !crate::commands::is_valid_command_name(name) || crate::commands::is_builtin(name) {
+        tracing::warn!(%skill, command = %name, "ignoring invalid skill command");
+        return None;
+    }
+
+    let mut args: Vec<CommandArgDef> = Vec::new();
+    for raw in frontmatter
+        .get("command_args")
+        .map(|value| value.split(','))
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .filter(|raw| !raw.is_empty())
+    {
+        let (arg, required) = match raw.strip_suffix('?') {
+            Some(arg) => (arg, false),
+            None => (raw, true),
+        };
+        let misordered = required && args.iter().any(|existing| !existing.required);
+        if !crate::commands::is_valid_arg_name(arg)
+            || misordered
+            || args.iter().any(|existing| existing.name == arg)
+        {
+            tracing::warn!(%skill, command = %name, argument = %raw, "ignoring invalid skill command");
+            return None;
+        }
+        args.push(CommandArgDef {
+            name: arg.to_string(),
+            description: String::new(),
+            required,
+        });
+    }
+
+    let role = match frontmatter.get("command_role") {
+        None => AccessRole::Member,
+        Some(value) => match serde_json::from_value(serde_json::Value::String(value.clone())) {
+            Ok(role) => role,
+            Err(_) => {
+                tracing::warn!(%skill, command = %name, role = %value, "ignoring invalid skill command");
+                return None;
+            }
+        },
+    };
+
+    Some(CommandDef {
+        name: name.to_string(),
+        description: frontmatter
+            .get("command_description")
+            .or_else(|| frontmatter.get("description"))
+            .cloned()
+            .unwrap_or_default(),
+        args,
+        role,
+        action: CommandAction::Skill {
+            skill: skill.to_string(),
+            task: None,
+        },
     })
 }
 
@@ -468,6 +545,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_skill_command() {
+        let content = indoc::indoc! {r#"
+            ---
+            name: release-notes
+            description: Draft release notes
+            command: /notes
+            command_args: version, audience?
+            command_role: admin
+            ---
+
+            Body here.
+        "#};
+
+        let (fm, _body) = parse_frontmatter(content).unwrap();
+        let command = parse_skill_command("release-notes", &fm).unwrap();
+        assert_eq!(command.name, "notes");
+        assert_eq!(command.description, "Draft release notes");
+        assert_eq!(command.role, AccessRole::Admin);
+        assert_eq!(
+            command
+                .args
+                .iter()
+                .map(|arg| (arg.name.as_str(), arg.required))
+                .collect::<Vec<_>>(),
+            vec![("version", true), ("audience", false)]
+        );
+        assert_eq!(
+            command.action,
+            CommandAction::Skill {
+                skill: "release-notes".into(),
+                task: None,
+            }
+        );
+
+        let mut builtin = fm.clone();
+        builtin.insert("command".into(), "status".into());
+        assert!(parse_skill_command("release-notes", &builtin).is_none());
+
+        let mut misordered = fm;
+        misordered.insert("command_args".into(), "audience?, version".into());
+        assert!(parse_skill_command("release-notes", &misordered).is_none());
+    }
+
     #[test]
     fn test_skill_set_channel_prompt_empty() {
         let set = SkillSet::default();
@@ -488,6 +609,7 @@ mod tests {
                 content: "# Weather\n\nUse curl.".into(),
                 source: SkillSource::Instance,
                 source_repo: None,
+                command: None,
             },
         );
 
@@ -511,6 +633,7 @@ mod tests {
                 content: "# Weather\n\nUse curl.".into(),
                 source: SkillSource::Instance,
                 source_repo: None,
+                command: None,
             },
         );
 
@@ -542,6 +665,7 @@ mod tests {
             content: format!("# {name}"),
             source,
             source_repo: None,
+            command: None,
         }
     }
 
@@ -56,6 +56,10 @@ pub struct Metrics {
     /// Labels: agent_id, model, tier, direction, worker_type.
     pub llm_tokens_total: IntCounterVec,
 
+    /// Response cache lookups.
+    /// Labels: agent_id, process_type, result (exact_hit/similar_hit/miss).
+    pub llm_response_cache_total: IntCounterVec,
+
     /// Estimated LLM cost in USD.
     /// Labels: agent_id, model, tier, worker_type.
     pub llm_estimated_cost_dollars: CounterVec,
@@ -201,7 +205,7 @@ impl Metrics {
                 "spacebot_llm_requests_total",
                 "Total LLM completion requests",
             ),
-            &["agent_id", "model", "tier", "worker_type"],
+            &["agent_id", "model", "tier", "worker_type", "prompt_tier"],
         )
         .expect("hardcoded metric descriptor");
 
@@ -271,6 +275,14 @@ impl Metrics {
             &["agent_id", "model", "tier", "direction", "worker_type"],
         )
         .expect("hardcoded metric descriptor");
+        let llm_response_cache_total = IntCounterVec::new(
+            Opts::new(
+                "spacebot_llm_response_cache_total",
+                "LLM response cache lookups",
+            ),
+            &["agent_id", "process_type", "result"],
+        )
+        .expect("hardcoded metric descriptor");
 
         let llm_estimated_cost_dollars = CounterVec::new(
             Opts::new(
@@ -564,6 +576,9 @@ impl Metrics {
         registry
             .register(Box::new(llm_tokens_total.clone()))
             .expect("hardcoded metric");
+        registry
+            .register(Box::new(llm_response_cache_total.clone()))
+            .expect("hardcoded metric");
         registry
             .register(Box::new(llm_estimated_cost_dollars.clone()))
             .expect("hardcoded metric");
@@ -682,6 +697,7 @@ impl Metrics {
             active_workers,
             memory_entry_count,
             llm_tokens_total,
+            llm_response_cache_total,
             llm_estimated_cost_dollars,
             active_branches,
             worker_duration_seconds,
@@ -12,13 +12,14 @@
 //! - No memory tools — the channel delegates memory work to branches.
 //!
 //! **Branch ToolServer** (one per branch, isolated):
-//! - `memory_save` + `memory_recall` + `memory_delete` + `channel_recall`
+//! - `memory_save` + `memory_recall` + `memory_delete` + `memory_promote` + `channel_recall`
 //! - `spacebot_docs` for embedded self-documentation lookup
 //! - `task_create` + `task_list` + `task_update`
 //! - `spawn_worker` is included for channel-originated branches only
 //!
 //! **Worker ToolServer** (one per worker, created at spawn time):
-//! - `shell`, `file_read`/`file_write`/`file_edit`/`file_list` — stateless, registered at creation
+//! - `shell` + `shell_input`, `file_read`/`file_write`/`file_edit`/`file_list` — registered at creation
+//!   (`shell_input` answers commands that `shell` left waiting for input)
 //! - `task_update` — scoped to the worker's assigned task
 //! - `set_status` — per-worker instance, registered at creation
 //!
@@ -41,6 +42,7 @@ pub mod install_skill;
 pub mod mcp;
 pub mod memory_delete;
 pub mod memory_persistence_complete;
+pub mod memory_promote;
 pub mod memory_recall;
 pub mod memory_save;
 pub mod project_manage;
@@ -105,6 +107,9 @@ pub use memory_persistence_complete::{
     MemoryPersistenceCompleteTool, MemoryPersistenceContractState,
     MemoryPersistenceTerminalOutcome,
 };
+pub use memory_promote::{
+    MemoryPromoteArgs, MemoryPromoteError, MemoryPromoteOutput, MemoryPromoteTool,
+};
 pub use memory_recall::{
     MemoryOutput, MemoryRecallArgs, MemoryRecallError, MemoryRecallOutput, MemoryRecallTool,
 };
@@ -127,7 +132,10 @@ pub use send_message_to_another_channel::{
     SendMessageArgs, SendMessageError, SendMessageOutput, SendMessageTool,
 };
 pub use set_status::{SetStatusArgs, SetStatusError, SetStatusOutput, SetStatusTool, StatusKind};
-pub use shell::{EnvVar, ShellArgs, ShellError, ShellOutput, ShellResult, ShellTool};
+pub use shell::{
+    EnvVar, ShellArgs, ShellError, ShellInputArgs, ShellInputTool, ShellOutput, ShellResult,
+    ShellSessions, ShellTool,
+};
 pub use skills_search::{
     SkillsSearchArgs, SkillsSearchError, SkillsSearchOutput, SkillsSearchTool,
 };
@@ -136,7 +144,8 @@ pub use spacebot_docs::{
     SpacebotDocContent, SpacebotDocsArgs, SpacebotDocsError, SpacebotDocsOutput, SpacebotDocsTool,
 };
 pub use spawn_worker::{
-    DetachedSpawnWorkerTool, SpawnWorkerArgs, SpawnWorkerError, SpawnWorkerOutput, SpawnWorkerTool,
+    DetachedSpawnWorkerTool, DetachedWorkerBudget, SpawnWorkerArgs, SpawnWorkerError,
+    SpawnWorkerOutput, SpawnWorkerTool,
 };
 pub use task_create::{TaskCreateArgs, TaskCreateError, TaskCreateOutput, TaskCreateTool};
 pub use task_list::{TaskListArgs, TaskListError, TaskListOutput, TaskListTool};
@@ -171,12 +180,13 @@ pub use factory_update_identity::{
 };
 
 use crate::agent::channel::ChannelState;
-use crate::config::{BrowserConfig, RuntimeConfig};
+use crate::config::{AccessGrant, BrowserConfig, RuntimeConfig};
 use crate::conversation::settings::WorkerMemoryMode;
 use crate::memory::MemorySearch;
+use crate::messaging::streaming::ReplyStream;
 use crate::sandbox::Sandbox;
 use crate::tasks::TaskStore;
-use crate::{AgentId, ChannelId, ProcessEvent, RoutedSender, WorkerId};
+use crate::{AgentId, ChannelId, ProcessEvent, ProcessId, RoutedSender, WorkerId};
 use rig::tool::Tool as _;
 use rig::tool::server::{ToolServer, ToolServerHandle};
 use std::path::PathBuf;
@@ -360,6 +370,7 @@ pub async fn add_channel_tools(
     allow_direct_reply: bool,
     current_adapter: Option<String>,
     slack_thread_ts: Option<&str>,
+    reply_stream: Option<ReplyStream>,
 ) -> Result<(), rig::tool::server::ToolServerError> {
     let conversation_id = conversation_id.into();
 
@@ -371,18 +382,24 @@ pub async fn add_channel_tools(
             .cloned()
             .unwrap_or_else(|| state.deps.agent_id.to_string());
         handle
-            .add_tool(ReplyTool::new(
-                response_tx.clone(),
-                conversation_id.clone(),
-                state.conversation_logger.clone(),
-                state.channel_id.clone(),
-                replied_flag.clone(),
-                agent_display_name,
-            ))
+            .add_tool(
+                ReplyTool::new(
+                    response_tx.clone(),
+                    conversation_id.clone(),
+                    state.conversation_logger.clone(),
+                    state.channel_id.clone(),
+                    replied_flag.clone(),
+                    agent_display_name,
+                )
+                .with_reply_stream(reply_stream),
+            )
             .await?;
     }
     handle.add_tool(BranchTool::new(state.clone())).await?;
-    handle.add_tool(SpawnWorkerTool::new(state.clone())).await?;
+    let access = state.access_grant().await;
+    handle
+        .add_tool(SpawnWorkerTool::new(state.clone()).with_access(Some(access.clone())))
+        .await?;
     handle.add_tool(RouteTool::new(state.clone())).await?;
     if let Some(messaging_manager) = &state.deps.messaging_manager {
         let send_message_display_name = state
@@ -440,7 +457,8 @@ pub async fn add_channel_tools(
                 &conversation_id,
                 slack_thread_ts,
             ))
-            .with_current_adapter(current_adapter.clone());
+            .with_current_adapter(current_adapter.clone())
+            .with_access(Some(access));
         handle.add_tool(cron_tool).await?;
     }
     if let Some(mut agent_msg) = send_agent_message_tool {
@@ -465,6 +483,7 @@ pub async fn add_direct_mode_tools(
     allow_direct_reply: bool,
     current_adapter: Option<String>,
     slack_thread_ts: Option<&str>,
+    reply_stream: Option<ReplyStream>,
 ) -> Result<(), rig::tool::server::ToolServerError> {
     // First add all standard channel tools
     add_channel_tools(
@@ -479,16 +498,21 @@ pub async fn add_direct_mode_tools(
         allow_direct_reply,
         current_adapter.clone(),
         slack_thread_ts,
+        reply_stream,
     )
     .await?;
 
-    // Then add memory tools (normally only available to branches)
+    // Then add memory tools (normally only available to branches), scoped to
+    // the sender of the current turn.
+    let user_id = state.current_user_id.read().await.clone();
     handle
-        .add_tool(MemoryRecallTool::new(state.deps.memory_search.clone()))
+        .add_tool(
+            MemoryRecallTool::new(state.deps.memory_search.clone()).with_user_id(user_id.clone()),
+        )
         .await?;
 
     handle
-        .add_tool(MemorySaveTool::new(state.deps.memory_search.clone()))
+        .add_tool(MemorySaveTool::new(state.deps.memory_search.clone()).with_user_id(user_id))
         .await?;
 
     // Add shell and file tools (normally only available to workers)
@@ -568,7 +592,8 @@ fn memory_save_with_events(
 ///
 /// Each branch gets its own isolated ToolServer so `memory_recall` is never
 /// visible to the channel. Includes memory tools, task-board tools, and
-/// `spacebot_docs` for on-demand self-documentation lookup.
+/// `spacebot_docs` for on-demand self-documentation lookup. `user_id` is the
+/// memory scope of the sender that triggered the branch, if any.
 #[allow(clippy::too_many_arguments)]
 pub fn create_branch_tool_server(
     state: Option<ChannelState>,
@@ -581,32 +606,35 @@ pub fn create_branch_tool_server(
     channel_store: crate::conversation::ChannelStore,
     run_logger: crate::conversation::history::ProcessRunLogger,
     profile: BranchToolProfile,
+    user_id: Option<String>,
+    access: Option<AccessGrant>,
 ) -> ToolServerHandle {
     let mut memory_save = memory_save_with_events(
         memory_search.clone(),
         agent_id.clone(),
         memory_event_tx.clone(),
         None,
-    );
+    )
+    .with_user_id(user_id.clone());
     if let BranchToolProfile::MemoryPersistence { contract_state, .. } = &profile {
         memory_save = memory_save.with_contract_state(contract_state.clone());
     }
 
     let mut server = ToolServer::new()
         .tool(memory_save)
-        .tool(MemoryRecallTool::new(memory_search.clone()))
-        .tool(MemoryDeleteTool::new(memory_search))
+        .tool(MemoryRecallTool::new(memory_search.clone()).with_user_id(user_id.clone()))
+        .tool(MemoryDeleteTool::new(memory_search.clone()))
+        .tool(MemoryPromoteTool::new(memory_search, runtime_config.clone()).with_user_id(user_id))
         .tool(ChannelRecallTool::new(conversation_logger, channel_store))
         .tool(SpacebotDocsTool::new())
         .tool(EmailSearchTool::new(runtime_config))
         .tool(WorkerInspectTool::new(run_logger, agent_id.to_string()))
-        .tool(TaskCreateTool::new(
-            task_store.clone(),
-            agent_id.to_string(),
-            "branch",
-        ))
+        .tool(
+            TaskCreateTool::new(task_store.clone(), agent_id.to_string(), "branch")
+                .with_access(access.clone()),
+        )
         .tool(TaskListTool::new(task_store.clone(), agent_id.to_string()))
-        .tool(TaskUpdateTool::for_branch(task_store, agent_id.clone()));
+        .tool(TaskUpdateTool::for_branch(task_store, agent_id.clone()).with_access(access.clone()));
 
     if let BranchToolProfile::MemoryPersistence {
         contract_state,
@@ -622,7 +650,7 @@ pub fn create_branch_tool_server(
     }
 
     if let Some(state) = state {
-        server = server.tool(SpawnWorkerTool::new(state));
+        server = server.tool(SpawnWorkerTool::new(state).with_access(access));
     }
 
     server.run()
@@ -652,9 +680,25 @@ pub fn create_worker_tool_server(
     runtime_config: Arc<RuntimeConfig>,
     worker_memory_mode: WorkerMemoryMode,
     memory_search: Arc<MemorySearch>,
+    access: Option<AccessGrant>,
 ) -> ToolServerHandle {
+    let shell_tool = {
+        let mut tool = ShellTool::new(workspace.clone(), sandbox.clone()).with_streaming(
+            event_tx.clone(),
+            ProcessId::Worker(worker_id),
+            channel_id.clone(),
+            agent_id.clone(),
+        );
+        if let Some(store) = runtime_config.secrets.load().as_ref() {
+            tool = tool.with_tool_secrets(store.tool_secret_pairs());
+        }
+        tool
+    };
+    let shell_input_tool = ShellInputTool::new(shell_tool.sessions());
+
     let mut server = ToolServer::new()
-        .tool(ShellTool::new(workspace.clone(), sandbox.clone()))
+        .tool(shell_tool)
+        .tool(shell_input_tool)
         .tool(TaskUpdateTool::for_worker(
             task_store,
             agent_id.clone(),
@@ -673,7 +717,7 @@ pub fn create_worker_tool_server(
     server = register_file_tools(server, workspace, sandbox);
 
     if let Some(store) = runtime_config.secrets.load().as_ref() {
-        server = server.tool(SecretSetTool::new(store.clone()));
+        server = server.tool(SecretSetTool::new(store.clone()).with_access(access));
     }
 
     if browser_config.enabled {
@@ -726,6 +770,49 @@ pub fn create_cortex_tool_server(
         .run()
 }
 
+/// Create a ToolServer for an autonomy loop cycle.
+///
+/// Memory, task-board, and detached worker tools. There is no `task_update`,
+/// so the loop cannot approve its own tasks. Every memory it saves is
+/// tagged with `memory_source`, workers are capped by `worker_budget`, and
+/// tasks are filed as `pending_approval` when `tasks_require_approval` is set.
+pub fn create_autonomy_tool_server(
+    deps: crate::AgentDeps,
+    memory_source: &str,
+    worker_budget: DetachedWorkerBudget,
+    tasks_require_approval: bool,
+) -> ToolServerHandle {
+    let workspace = deps.runtime_config.workspace_dir.clone();
+    let screenshot_dir = workspace.join(".spacebot").join("screenshots");
+    let logs_dir = workspace.join(".spacebot").join("logs");
+    let run_logger = crate::conversation::history::ProcessRunLogger::new(deps.sqlite_pool.clone());
+    let agent_id = deps.agent_id.clone();
+    let memory_search = deps.memory_search.clone();
+    let task_store = deps.task_store.clone();
+
+    ToolServer::new()
+        .tool(
+            memory_save_with_events(
+                memory_search.clone(),
+                agent_id.clone(),
+                deps.event_tx.clone(),
+                None,
+            )
+            .with_source(memory_source),
+        )
+        .tool(MemoryRecallTool::new(memory_search))
+        .tool(WorkerInspectTool::new(run_logger, agent_id.to_string()))
+        .tool(
+            TaskCreateTool::new(task_store.clone(), agent_id.to_string(), "autonomy")
+                .with_approval_required(tasks_require_approval),
+        )
+        .tool(TaskListTool::new(task_store, agent_id.to_string()))
+        .tool(
+            DetachedSpawnWorkerTool::new(deps, screenshot_dir, logs_dir).with_budget(worker_budget),
+        )
+        .run()
+}
+
 /// Create a ToolServer for cortex chat sessions.
 ///
 /// Combines branch tools (memory) with worker tools (shell, file) to give
@@ -787,8 +874,12 @@ pub fn create_cortex_chat_tool_server(
             "cortex",
         ))
         .tool(TaskListTool::new(task_store.clone(), agent_id.to_string()))
-        .tool(TaskUpdateTool::for_branch(task_store, agent_id.clone()))
-        .tool(ShellTool::new(workspace.clone(), sandbox.clone()));
+        .tool(TaskUpdateTool::for_branch(task_store, agent_id.clone()));
+
+    let shell_tool = ShellTool::new(workspace.clone(), sandbox.clone());
+    server = server
+        .tool(ShellInputTool::new(shell_tool.sessions()))
+        .tool(shell_tool);
 
     server = register_file_tools(server, workspace, sandbox);
 
@@ -1042,6 +1133,139 @@ mod tests {
         );
     }
 
+    fn interactive_shell_tool() -> shell::ShellTool {
+        let config = std::sync::Arc::new(arc_swap::ArcSwap::from_pointee(
+            crate::sandbox::SandboxConfig::default(),
+        ));
+        let workspace = std::env::temp_dir();
+        let sandbox = std::sync::Arc::new(crate::sandbox::Sandbox::new_for_test(
+            config,
+            workspace.clone(),
+        ));
+        shell::ShellTool::new(workspace, sandbox)
+            .with_quiesce_timeout(std::time::Duration::from_millis(500))
+    }
+
+    fn shell_args(command: &str) -> shell::ShellArgs {
+        shell::ShellArgs {
+            command: command.into(),
+            working_dir: None,
+            env: Vec::new(),
+            timeout_seconds: 10,
+        }
+    }
+
+    fn shell_input_args(session_id: &str, input: Option<&str>) -> shell::ShellInputArgs {
+        serde_json::from_value(serde_json::json!({
+            "session_id": session_id,
+            "input": input,
+        }))
+        .unwrap()
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn shell_pauses_on_prompt_and_resumes_with_shell_input() {
+        let tool = interactive_shell_tool();
+        let input_tool = ShellInputTool::new(tool.sessions());
+
+        let output = rig::tool::Tool::call(
+            &tool,
+            shell_args("printf 'Ok to proceed? (y) '; read answer; echo \"answered $answer\""),
+        )
+        .await
+        .unwrap();
+        assert!(output.waiting_for_input);
+        assert_eq!(output.exit_code, shell::WAITING_FOR_INPUT_EXIT_CODE);
+        assert_eq!(output.stdout, "Ok to proceed? (y) ");
+        let session_id = output
+            .session_id
+            .expect("waiting output should carry a session");
+
+        let output = rig::tool::Tool::call(&input_tool, shell_input_args(&session_id, Some("y")))
+            .await
+            .unwrap();
+        assert!(!output.waiting_for_input);
+        assert!(output.success);
+        assert_eq!(output.stdout, "answered y\n");
+
+        let result =
+            rig::tool::Tool::call(&input_tool, shell_input_args(&session_id, Some("y"))).await;
+        assert!(result.is_err(), "finished sessions should be released");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn shell_input_can_kill_a_waiting_command() {
+        let tool = interactive_shell_tool();
+        let input_tool = ShellInputTool::new(tool.sessions());
+
+        let output = rig::tool::Tool::call(&tool, shell_args("read answer"))
+            .await
+            .unwrap();
+        let session_id = output.session_id.unwrap();
+
+        let args: shell::ShellInputArgs = serde_json::from_value(serde_json::json!({
+            "session_id": session_id,
+            "kill": true,
+        }))
+        .unwrap();
+        let output = rig::tool::Tool::call(&input_tool, args).await.unwrap();
+        assert!(!output.success);
+        assert!(!output.waiting_for_input);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn shell_streams_scrubbed_output_lines() {
+        let (event_tx, mut event_rx) = broadcast::channel(16);
+        let worker_id = uuid::Uuid::new_v4();
+        let tool = interactive_shell_tool()
+            .with_streaming(
+                event_tx,
+                ProcessId::Worker(worker_id),
+                None,
+                std::sync::Arc::from("agent"),
+            )
+            .with_tool_secrets(vec![("GH_TOKEN".into(), "ghs-secret-value".into())]);
+
+        let output = rig::tool::Tool::call(
+            &tool,
+            shell_args("echo token=ghs-secret-value; echo oops >&2"),
+        )
+        .await
+        .unwrap();
+        assert!(output.success);
+        assert_eq!(output.stdout, "token=[REDACTED:GH_TOKEN]\n");
+
+        let mut lines = Vec::new();
+        while let Ok(event) = event_rx.try_recv() {
+            if let ProcessEvent::ToolOutput {
+                process_id,
+                tool_name,
+                line,
+                stream,
+                ..
+            } = event
+            {
+                assert_eq!(process_id, ProcessId::Worker(worker_id));
+                assert_eq!(tool_name, "shell");
+                lines.push((stream, line));
+            }
+        }
+        lines.sort();
+        assert_eq!(
+            lines,
+            vec![
+                ("stderr".to_string(), "oops".to_string()),
+                (
+                    "stdout".to_string(),
+                    "token=[REDACTED:GH_TOKEN]".to_string()
+                ),
+            ]
+        );
+    }
+
     #[test]
     fn blocks_json_bracket_and_tool_syntax_output() {
         assert!(should_block_user_visible_text("{\"content\":\"hello\"}"));
@@ -1,5 +1,6 @@
 //! Cron job management tool for creating, listing, and deleting scheduled tasks.
 
+use crate::config::{AccessCapability, AccessGrant};
 use crate::cron::scheduler::{CronConfig, Scheduler};
 use crate::cron::store::CronStore;
 use crate::messaging::MessagingManager;
@@ -24,6 +25,8 @@ pub struct CronTool {
     messaging_manager: Arc<MessagingManager>,
     default_delivery_target: Option<String>,
     current_adapter: Option<String>,
+    /// Role of the sender the tool acts for. `None` means unrestricted.
+    access: Option<AccessGrant>,
 }
 
 impl std::fmt::Debug for CronTool {
@@ -33,6 +36,7 @@ impl std::fmt::Debug for CronTool {
             .field("scheduler", &self.scheduler)
             .field("default_delivery_target", &self.default_delivery_target)
             .field("current_adapter", &self.current_adapter)
+            .field("access", &self.access)
             .finish_non_exhaustive()
     }
 }
@@ -49,6 +53,7 @@ impl CronTool {
             messaging_manager,
             default_delivery_target: None,
             current_adapter: None,
+            access: None,
         }
     }
 
@@ -61,6 +66,16 @@ impl CronTool {
         self.current_adapter = current_adapter;
         self
     }
+
+    pub fn with_access(mut self, access: Option<AccessGrant>) -> Self {
+        self.access = access;
+        self
+    }
+
+    /// The agent's cron scheduler, for triggering jobs outside a tool call.
+    pub fn scheduler(&self) -> Arc<Scheduler> {
+        self.scheduler.clone()
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -103,6 +118,9 @@ pub struct CronArgs {
     /// Optional for "create": if true, run only once and disable after first execution attempt.
     #[serde(default)]
     pub run_once: Option<bool>,
+    /// Optional for "create": if true, carry a state document and recent results across runs.
+    #[serde(default)]
+    pub stateful: Option<bool>,
 }
 
 #[derive(Debug, Serialize)]
@@ -122,6 +140,7 @@ pub struct CronEntry {
     pub interval_secs: u64,
     pub delivery_target: String,
     pub run_once: bool,
+    pub stateful: bool,
     pub active_hours: Option<String>,
 }
 
@@ -183,6 +202,10 @@ impl Tool for CronTool {
                     "run_once": {
                         "type": "boolean",
                         "description": "For 'create': if true, run this job once and auto-disable after the first execution attempt."
+                    },
+                    "stateful": {
+                        "type": "boolean",
+                        "description": "For 'create': if true, each run sees the job's saved state notes and recent results, and can write updated notes back. Use for recurring tracking tasks that should build on previous runs."
                     }
                 },
                 "required": ["action"]
@@ -191,6 +214,17 @@ impl Tool for CronTool {
     }
 
     async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        if matches!(args.action.as_str(), "create" | "delete")
+            && let Some(access) = &self.access
+            && let Err(denied) = access.check(AccessCapability::Cron)
+        {
+            return Ok(CronOutput {
+                success: false,
+                message: denied.to_string(),
+                jobs: None,
+            });
+        }
+
         match args.action.as_str() {
             "create" => self.create(args).await,
             "list" => self.list().await,
@@ -373,6 +407,7 @@ impl CronTool {
             _ => None,
         };
         let run_once = args.run_once.unwrap_or(false);
+        let stateful = args.stateful.unwrap_or(false);
 
         let config = CronConfig {
             id: id.clone(),
@@ -383,6 +418,7 @@ impl CronTool {
             active_hours,
             enabled: true,
             run_once,
+            stateful,
             next_run_at: None,
             timeout_secs: args.timeout_secs,
         };
@@ -409,6 +445,9 @@ impl CronTool {
         } else {
             format!("Cron job '{id}' created. Runs {schedule_desc}.")
         };
+        if stateful {
+            message.push_str(" Stateful: each run sees the previous state notes and results.");
+        }
         if let Some((start, end)) = active_hours {
             if timezone == "system" {
                 message.push_str(&format!(
@@ -448,6 +487,7 @@ impl CronTool {
                 interval_secs: config.interval_secs,
                 delivery_target: config.delivery_target,
                 run_once: config.run_once,
+                stateful: config.stateful,
                 active_hours: config
                     .active_hours
                     .map(|(s, e)| format!("{s:02}:00-{e:02}:00")),
@@ -0,0 +1,164 @@
+//! Memory promote tool for branches.
+//!
+//! Moves a long-term memory back into the recent tier so it gets the recency
+//! boost in hybrid search and is exempt from decay while it stays in use.
+
+use crate::config::RuntimeConfig;
+use crate::memory::{MemorySearch, MemoryTier, MemoryType};
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Tool for promoting memories into the recent tier.
+#[derive(Debug, Clone)]
+pub struct MemoryPromoteTool {
+    memory_search: Arc<MemorySearch>,
+    runtime_config: Arc<RuntimeConfig>,
+    user_id: Option<String>,
+}
+
+impl MemoryPromoteTool {
+    /// Create a new memory promote tool.
+    pub fn new(memory_search: Arc<MemorySearch>, runtime_config: Arc<RuntimeConfig>) -> Self {
+        Self {
+            memory_search,
+            runtime_config,
+            user_id: None,
+        }
+    }
+
+    /// Only allow promoting memories visible to this user (their own plus
+    /// global memories).
+    pub fn with_user_id(mut self, user_id: Option<String>) -> Self {
+        self.user_id = user_id;
+        self
+    }
+}
+
+/// Error type for memory promote tool.
+#[derive(Debug, thiserror::Error)]
+#[error("Memory promote failed: {0}")]
+pub struct MemoryPromoteError(String);
+
+/// Arguments for memory promote tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct MemoryPromoteArgs {
+    /// The ID of the memory to promote.
+    pub memory_id: String,
+}
+
+/// Output from memory promote tool.
+#[derive(Debug, Serialize)]
+pub struct MemoryPromoteOutput {
+    /// Whether the memory is now in the recent tier.
+    pub promoted: bool,
+    /// Description of what happened.
+    pub message: String,
+}
+
+impl Tool for MemoryPromoteTool {
+    const NAME: &'static str = "memory_promote";
+
+    type Error = MemoryPromoteError;
+    type Args = MemoryPromoteArgs;
+    type Output = MemoryPromoteOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/memory_promote").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "memory_id": {
+                        "type": "string",
+                        "description": "The ID of the memory to promote (from memory_recall results)"
+                    }
+                },
+                "required": ["memory_id"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> std::result::Result<Self::Output, Self::Error> {
+        let store = self.memory_search.store();
+
+        let memory = store
+            .load(&args.memory_id)
+            .await
+            .map_err(|e| MemoryPromoteError(format!("Failed to look up memory: {e}")))?;
+
+        let Some(memory) = memory.filter(|m| !m.forgotten && m.visible_to(self.user_id.as_deref()))
+        else {
+            return Ok(MemoryPromoteOutput {
+                promoted: false,
+                message: format!("No memory found with ID: {}", args.memory_id),
+            });
+        };
+
+        if memory.memory_type == MemoryType::Identity {
+            return Ok(MemoryPromoteOutput {
+                promoted: false,
+                message: "Identity memories are permanent and stay in the long-term tier."
+                    .to_string(),
+            });
+        }
+
+        let was_long_term = memory.tier == MemoryTier::LongTerm;
+        let promoted = store
+            .promote_to_recent(&args.memory_id)
+            .await
+            .map_err(|e| MemoryPromoteError(format!("Failed to promote memory: {e}")))?;
+
+        if !promoted {
+            return Ok(MemoryPromoteOutput {
+                promoted: false,
+                message: format!("Failed to promote memory {}.", args.memory_id),
+            });
+        }
+
+        // Keep the recent tier bounded; the least-recently-accessed memories
+        // fall back to long-term.
+        let recent_tier_max = self.runtime_config.cortex.load().recent_tier_max;
+        if let Err(error) = store.enforce_recent_capacity(recent_tier_max).await {
+            tracing::warn!(%error, "failed to enforce recent tier capacity after promotion");
+        }
+
+        tracing::info!(
+            memory_id = %args.memory_id,
+            memory_type = %memory.memory_type,
+            was_long_term,
+            "memory promoted to recent tier"
+        );
+
+        let preview = memory.content.lines().next().unwrap_or("(empty)");
+        let message = if was_long_term {
+            format!(
+                "Promoted [{type}] memory to the recent tier: \"{preview}\".",
+                type = memory.memory_type,
+                preview = truncate(preview, 80),
+            )
+        } else {
+            format!(
+                "Refreshed recent-tier [{type}] memory: \"{preview}\".",
+                type = memory.memory_type,
+                preview = truncate(preview, 80),
+            )
+        };
+
+        Ok(MemoryPromoteOutput {
+            promoted: true,
+            message,
+        })
+    }
+}
+
+fn truncate(s: &str, max: usize) -> &str {
+    if s.len() <= max {
+        s
+    } else {
+        &s[..s.floor_char_boundary(max)]
+    }
+}
@@ -16,12 +16,23 @@ use std::sync::Arc;
 #[derive(Debug, Clone)]
 pub struct MemoryRecallTool {
     memory_search: Arc<MemorySearch>,
+    user_id: Option<String>,
 }
 
 impl MemoryRecallTool {
     /// Create a new memory recall tool.
     pub fn new(memory_search: Arc<MemorySearch>) -> Self {
-        Self { memory_search }
+        Self {
+            memory_search,
+            user_id: None,
+        }
+    }
+
+    /// Scope recall to the user who triggered this process by default, so
+    /// results contain their memories plus global ones.
+    pub fn with_user_id(mut self, user_id: Option<String>) -> Self {
+        self.user_id = user_id;
+        self
     }
 }
 
@@ -53,6 +64,10 @@ pub struct MemoryRecallArgs {
     /// Sort order for non-hybrid modes: "recent" (default), "importance", "most_accessed".
     #[serde(default)]
     pub sort_by: Option<String>,
+    /// Recall as this user (their memories plus globals). When the tool is
+    /// bound to a sender, only that sender may be named.
+    #[serde(default)]
+    pub user_id: Option<String>,
 }
 
 fn default_max_results() -> usize {
@@ -82,6 +97,21 @@ fn parse_search_sort(s: &str) -> std::result::Result<SearchSort, MemoryRecallErr
     }
 }
 
+/// The user to recall as. A tool bound to a sender only recalls as that
+/// sender, so the model can't read another user's scoped memories.
+fn resolve_user_id(
+    bound: Option<&str>,
+    requested: Option<String>,
+) -> std::result::Result<Option<String>, MemoryRecallError> {
+    match (bound, requested) {
+        (Some(bound), Some(requested)) if requested != bound => Err(MemoryRecallError(format!(
+            "cannot recall memories for user \"{requested}\"; recall is limited to the current user"
+        ))),
+        (Some(bound), _) => Ok(Some(bound.to_string())),
+        (None, requested) => Ok(requested),
+    }
+}
+
 fn parse_memory_type(s: &str) -> std::result::Result<crate::memory::MemoryType, MemoryRecallError> {
     use crate::memory::MemoryType;
     match s {
@@ -130,6 +160,9 @@ pub struct MemoryOutput {
     pub created_at: String,
     /// The relevance score from the search.
     pub relevance_score: f32,
+    /// The user this memory is scoped to, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
 }
 
 impl Tool for MemoryRecallTool {
@@ -176,6 +209,10 @@ impl Tool for MemoryRecallTool {
                         "enum": ["recent", "importance", "most_accessed"],
                         "default": "recent",
                         "description": "Sort order for non-hybrid modes. Default: recent."
+                    },
+                    "user_id": {
+                        "type": "string",
+                        "description": "Recall as a specific user: returns their memories plus global ones. Defaults to, and is limited to, the current user when there is one."
                     }
                 }
             }),
@@ -217,6 +254,7 @@ impl Tool for MemoryRecallTool {
             sort_by,
             max_results: args.max_results,
             max_results_per_source: args.max_results * 2,
+            user_id: resolve_user_id(self.user_id.as_deref(), args.user_id)?,
             ..Default::default()
         };
 
@@ -248,6 +286,7 @@ impl Tool for MemoryRecallTool {
                 importance: result.memory.importance,
                 created_at: result.memory.created_at.to_rfc3339(),
                 relevance_score: result.score,
+                user_id: result.memory.user_id.clone(),
             });
         }
 
@@ -312,6 +351,7 @@ pub async fn memory_recall(
         memory_type: None,
         mode: None,
         sort_by: None,
+        user_id: None,
     };
 
     let output = tool
@@ -385,4 +425,25 @@ mod tests {
     fn test_parse_memory_type_invalid() {
         assert!(parse_memory_type("invalid").is_err());
     }
+
+    #[test]
+    fn test_bound_user_cannot_recall_as_another_user() {
+        assert!(resolve_user_id(Some("alice"), Some("bob".into())).is_err());
+        assert_eq!(
+            resolve_user_id(Some("alice"), None).unwrap().as_deref(),
+            Some("alice")
+        );
+        assert_eq!(
+            resolve_user_id(Some("alice"), Some("alice".into()))
+                .unwrap()
+                .as_deref(),
+            Some("alice")
+        );
+        assert_eq!(
+            resolve_user_id(None, Some("bob".into()))
+                .unwrap()
+                .as_deref(),
+            Some("bob")
+        );
+    }
 }
@@ -2,7 +2,7 @@
 
 use crate::error::Result;
 use crate::memory::types::Association;
-use crate::memory::{Memory, MemorySearch, MemoryType};
+use crate::memory::{Memory, MemorySearch, MemoryTier, MemoryType};
 use crate::{AgentId, ProcessEvent};
 use rig::completion::ToolDefinition;
 use rig::tool::Tool;
@@ -21,6 +21,8 @@ pub struct MemorySaveTool {
     event_context: Option<MemorySaveEventContext>,
     contract_state: Option<Arc<super::memory_persistence_complete::MemoryPersistenceContractState>>,
     working_memory: Option<Arc<crate::memory::WorkingMemoryStore>>,
+    user_id: Option<String>,
+    source: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -37,9 +39,24 @@ impl MemorySaveTool {
             event_context: None,
             contract_state: None,
             working_memory: None,
+            user_id: None,
+            source: None,
         }
     }
 
+    /// Tag saved memories with the user who triggered this process unless
+    /// the call marks the memory as global.
+    pub fn with_user_id(mut self, user_id: Option<String>) -> Self {
+        self.user_id = user_id;
+        self
+    }
+
+    /// Tag every saved memory with this source, overriding the model's choice.
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
     /// Enable process event emission for successful memory saves.
     pub fn with_event_bus(
         mut self,
@@ -93,6 +110,15 @@ pub struct MemorySaveArgs {
     pub source: Option<String>,
     /// Optional channel ID to associate this memory with the conversation it came from.
     pub channel_id: Option<String>,
+    /// Optional user this memory is about. Defaults to the current sender.
+    #[serde(default)]
+    pub user_id: Option<String>,
+    /// Save as shared knowledge not tied to any one person.
+    #[serde(default)]
+    pub global: bool,
+    /// Storage tier: "recent" (default) for things in active use, "long_term"
+    /// for reference material that should skip the hot path.
+    pub tier: Option<String>,
     /// Optional associations to create with other memories.
     #[serde(default)]
     pub associations: Vec<AssociationInput>,
@@ -174,6 +200,21 @@ impl Tool for MemorySaveTool {
                         "type": "string",
                         "description": "Optional channel ID to associate this memory with the conversation it came from"
                     },
+                    "user_id": {
+                        "type": "string",
+                        "description": "Optional user this memory is about. Defaults to the person you are talking with."
+                    },
+                    "global": {
+                        "type": "boolean",
+                        "default": false,
+                        "description": "Set true for shared facts, decisions, or observations that are not about one specific person. Global memories are recalled for everyone."
+                    },
+                    "tier": {
+                        "type": "string",
+                        "enum": ["recent", "long_term"],
+                        "default": "recent",
+                        "description": "Use 'recent' (default) for things relevant to ongoing work; they rank higher in recall for a few days. Use 'long_term' for reference material."
+                    },
                     "associations": {
                         "type": "array",
                         "description": "Optional associations to link this memory to other memories",
@@ -244,7 +285,7 @@ impl Tool for MemorySaveTool {
             memory = memory.with_importance(importance);
         }
 
-        if let Some(source) = args.source {
+        if let Some(source) = self.source.clone().or(args.source) {
             memory = memory.with_source(source);
         }
 
@@ -252,6 +293,21 @@ impl Tool for MemorySaveTool {
             memory = memory.with_channel_id(Arc::from(channel_id.as_str()));
         }
 
+        // New memories enter the recent tier unless saved as long-term
+        // reference material. Identity memories always stay long-term.
+        let tier = args
+            .tier
+            .as_deref()
+            .map(MemoryTier::parse)
+            .unwrap_or(MemoryTier::Recent);
+        memory = memory.with_tier(tier);
+
+        if !args.global
+            && let Some(user_id) = args.user_id.or_else(|| self.user_id.clone())
+        {
+            memory = memory.with_user_id(user_id);
+        }
+
         // Save to SQLite database
         let store = self.memory_search.store();
         store
@@ -345,7 +401,12 @@ impl Tool for MemorySaveTool {
         match self
             .memory_search
             .embedding_table()
-            .store(&memory.id, &args.content, &embedding)
+            .store(
+                &memory.id,
+                &args.content,
+                memory.user_id.as_deref(),
+                &embedding,
+            )
             .await
         {
             Ok(()) => {
@@ -467,6 +528,9 @@ pub async fn save_fact(
         importance: None,
         source: None,
         channel_id: channel_id.map(|id| id.to_string()),
+        user_id: None,
+        global: true,
+        tier: None,
         associations: vec![],
     };
 
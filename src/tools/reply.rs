@@ -2,6 +2,7 @@
 
 use crate::conversation::ConversationLogger;
 
+use crate::messaging::streaming::ReplyStream;
 use crate::{ChannelId, OutboundResponse, RoutedSender};
 use regex::Regex;
 use rig::completion::ToolDefinition;
@@ -45,6 +46,7 @@ pub struct ReplyTool {
     channel_id: ChannelId,
     replied_flag: RepliedFlag,
     agent_display_name: String,
+    reply_stream: Option<ReplyStream>,
 }
 
 impl ReplyTool {
@@ -64,8 +66,15 @@ impl ReplyTool {
             channel_id,
             replied_flag,
             agent_display_name: agent_display_name.into(),
+            reply_stream: None,
         }
     }
+
+    /// Finalize streamed previews instead of sending a separate message.
+    pub fn with_reply_stream(mut self, reply_stream: Option<ReplyStream>) -> Self {
+        self.reply_stream = reply_stream;
+        self
+    }
 }
 
 /// Error type for reply tool.
@@ -471,10 +480,23 @@ impl Tool for ReplyTool {
             OutboundResponse::Text(converted_content.clone())
         };
 
-        self.response_tx
-            .send(response)
-            .await
-            .map_err(|e| ReplyError(format!("failed to send reply: {e}")))?;
+        // A streamed preview becomes the reply itself. Non-text replies close
+        // the preview and are still sent in full.
+        let streamed = match (&self.reply_stream, &response) {
+            (Some(stream), OutboundResponse::Text(text)) => stream.finish(Some(text)).await,
+            (Some(stream), _) => {
+                stream.finish(None).await;
+                false
+            }
+            (None, _) => false,
+        };
+
+        if !streamed {
+            self.response_tx
+                .send(response)
+                .await
+                .map_err(|e| ReplyError(format!("failed to send reply: {e}")))?;
+        }
 
         self.conversation_logger.log_bot_message_with_name(
             &self.channel_id,
@@ -3,6 +3,7 @@
 //! Useful for autonomous workflows where a worker creates accounts, generates
 //! API keys, or obtains credentials that should be persisted for future use.
 
+use crate::config::{AccessCapability, AccessGrant};
 use crate::secrets::store::{SecretCategory, SecretsStore, auto_categorize};
 use rig::completion::ToolDefinition;
 use rig::tool::Tool;
@@ -14,12 +15,22 @@ use std::sync::Arc;
 #[derive(Debug, Clone)]
 pub struct SecretSetTool {
     secrets_store: Arc<SecretsStore>,
+    /// Role of whoever asked for the worker. `None` means unrestricted.
+    access: Option<AccessGrant>,
 }
 
 impl SecretSetTool {
     /// Create a new secret set tool with access to the instance-level store.
     pub fn new(secrets_store: Arc<SecretsStore>) -> Self {
-        Self { secrets_store }
+        Self {
+            secrets_store,
+            access: None,
+        }
+    }
+
+    pub fn with_access(mut self, access: Option<AccessGrant>) -> Self {
+        self.access = access;
+        self
     }
 }
 
@@ -89,6 +100,12 @@ impl Tool for SecretSetTool {
     }
 
     async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        if let Some(access) = &self.access {
+            access
+                .check(AccessCapability::SecretSet)
+                .map_err(|denied| SecretSetError(denied.to_string()))?;
+        }
+
         let name = args.name.trim().to_uppercase();
 
         if name.is_empty() {
@@ -3,16 +3,29 @@
 //! This is the unified execution tool — it replaces the previous `shell` + `exec`
 //! split. Commands run through `sh -c` with optional per-command environment
 //! variables. Dangerous env vars that enable library injection are blocked.
+//!
+//! Commands are spawned with piped stdin. Output is read as it arrives, scrubbed
+//! of tool secrets, and streamed line-by-line as `ProcessEvent::ToolOutput`. When
+//! a running command goes quiet, it is treated as waiting for input: the call
+//! returns early with the partial output and a session ID, and the process stays
+//! alive so `shell_input` can answer the prompt.
 
 use crate::sandbox::Sandbox;
+use crate::secrets::scrub::scrub_secrets;
+use crate::{AgentId, ChannelId, ProcessEvent, ProcessId};
 use rig::completion::ToolDefinition;
 use rig::tool::Tool;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::sync::Arc;
-use tokio::process::Command;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt as _, AsyncWriteExt as _};
+use tokio::process::{Child, ChildStdin, Command};
+use tokio::sync::{Mutex, broadcast, mpsc};
+use tokio::time::Instant;
 
 /// Env vars that enable library injection or alter runtime loading behavior.
 /// These are blocked even when sandbox mode is disabled because they allow
@@ -32,17 +45,84 @@ const DANGEROUS_ENV_VARS: &[&str] = &[
     "ENV",
 ];
 
+/// How long a running command may stay silent before it is treated as waiting
+/// for input. Long enough to ride out compiler and resolver pauses, short
+/// enough that prompts surface quickly.
+const QUIESCE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Maximum number of paused commands a single tool server keeps alive.
+const MAX_SHELL_SESSIONS: usize = 4;
+
+/// Exit code reported while a command is paused waiting for input. Real exit
+/// codes are non-negative and -1 is used for spawn failures and timeouts.
+pub const WAITING_FOR_INPUT_EXIT_CODE: i32 = -2;
+
 /// Tool for executing shell commands within a sandboxed environment.
 #[derive(Debug, Clone)]
 pub struct ShellTool {
     workspace: PathBuf,
     sandbox: Arc<Sandbox>,
+    sessions: Arc<ShellSessions>,
+    streaming: Option<ShellStreaming>,
+    tool_secrets: Vec<(String, String)>,
+    quiesce_timeout: Duration,
+}
+
+/// Where live output lines are broadcast.
+#[derive(Debug, Clone)]
+struct ShellStreaming {
+    event_tx: broadcast::Sender<ProcessEvent>,
+    agent_id: AgentId,
+    process_id: ProcessId,
+    channel_id: Option<ChannelId>,
 }
 
 impl ShellTool {
     /// Create a new shell tool with sandbox containment.
     pub fn new(workspace: PathBuf, sandbox: Arc<Sandbox>) -> Self {
-        Self { workspace, sandbox }
+        Self {
+            workspace,
+            sandbox,
+            sessions: Arc::new(ShellSessions::default()),
+            streaming: None,
+            tool_secrets: Vec::new(),
+            quiesce_timeout: QUIESCE_TIMEOUT,
+        }
+    }
+
+    /// Broadcast output lines as `ProcessEvent::ToolOutput` while commands run.
+    pub fn with_streaming(
+        mut self,
+        event_tx: broadcast::Sender<ProcessEvent>,
+        process_id: ProcessId,
+        channel_id: Option<ChannelId>,
+        agent_id: AgentId,
+    ) -> Self {
+        self.streaming = Some(ShellStreaming {
+            event_tx,
+            agent_id,
+            process_id,
+            channel_id,
+        });
+        self
+    }
+
+    /// Set tool secret pairs to redact from command output.
+    pub fn with_tool_secrets(mut self, pairs: Vec<(String, String)>) -> Self {
+        self.tool_secrets = pairs;
+        self
+    }
+
+    /// Override how long a command may stay silent before it is treated as
+    /// waiting for input.
+    pub fn with_quiesce_timeout(mut self, timeout: Duration) -> Self {
+        self.quiesce_timeout = timeout;
+        self
+    }
+
+    /// Paused commands started by this tool, shared with `ShellInputTool`.
+    pub fn sessions(&self) -> Arc<ShellSessions> {
+        self.sessions.clone()
     }
 }
 
@@ -98,6 +178,11 @@ pub struct ShellOutput {
     pub stderr: String,
     /// Formatted summary for LLM consumption.
     pub summary: String,
+    /// The command is still running and appears to be waiting for input.
+    pub waiting_for_input: bool,
+    /// Session to pass to `shell_input` while the command is waiting.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
 }
 
 impl Tool for ShellTool {
@@ -227,6 +312,16 @@ impl Tool for ShellTool {
             }
         }
 
+        if self.sessions.len().await >= MAX_SHELL_SESSIONS {
+            return Err(ShellError {
+                message: format!(
+                    "{MAX_SHELL_SESSIONS} commands are already waiting for input. \
+                     Answer or kill one with shell_input before starting another."
+                ),
+                exit_code: -1,
+            });
+        }
+
         // Build per-command env map for sandbox-aware injection. The sandbox
         // injects these via --setenv (bubblewrap) or .env() (other backends),
         // so they always reach the inner sandboxed process.
@@ -244,41 +339,561 @@ impl Tool for ShellTool {
                 .wrap("sh", &["-c", &args.command], &working_dir, &command_env)
         };
 
-        cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+        cmd.stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+
+        let child = cmd.spawn().map_err(|e| ShellError {
+            message: format!("Failed to execute command: {e}"),
+            exit_code: -1,
+        })?;
+
+        let mut session = ShellSession::start(
+            child,
+            args.command,
+            self.streaming.clone(),
+            &self.tool_secrets,
+            self.quiesce_timeout,
+        );
+
+        let timeout = Duration::from_secs(args.timeout_seconds);
+        let outcome = session.drive(Self::NAME, timeout).await;
+        self.sessions.settle(session, outcome).await
+    }
+}
+
+/// Paused commands waiting for input, keyed by session ID.
+///
+/// Dropping the map kills every paused process (`kill_on_drop`), so sessions
+/// never outlive the worker that started them.
+#[derive(Default)]
+pub struct ShellSessions {
+    sessions: Mutex<HashMap<String, ShellSession>>,
+}
 
-        let timeout = tokio::time::Duration::from_secs(args.timeout_seconds);
+impl std::fmt::Debug for ShellSessions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShellSessions").finish_non_exhaustive()
+    }
+}
+
+impl ShellSessions {
+    async fn len(&self) -> usize {
+        self.sessions.lock().await.len()
+    }
 
-        let output = tokio::time::timeout(timeout, cmd.output())
+    async fn take(&self, session_id: &str) -> Option<ShellSession> {
+        self.sessions.lock().await.remove(session_id)
+    }
+
+    async fn park(&self, session: ShellSession) {
+        self.sessions
+            .lock()
             .await
-            .map_err(|_| ShellError {
-                message: "Command timed out".to_string(),
-                exit_code: -1,
-            })?
-            .map_err(|e| ShellError {
-                message: format!("Failed to execute command: {e}"),
+            .insert(session.id.clone(), session);
+    }
+
+    /// Turn a drive outcome into tool output, parking the session again if
+    /// the command is still waiting for input.
+    async fn settle(
+        &self,
+        session: ShellSession,
+        outcome: DriveOutcome,
+    ) -> Result<ShellOutput, ShellError> {
+        let stdout =
+            crate::tools::truncate_output(&outcome.stdout, crate::tools::MAX_TOOL_OUTPUT_BYTES);
+        let stderr =
+            crate::tools::truncate_output(&outcome.stderr, crate::tools::MAX_TOOL_OUTPUT_BYTES);
+
+        match outcome.state {
+            DriveState::Exited(exit_code) => {
+                let summary = format_shell_output(exit_code, &stdout, &stderr);
+                Ok(ShellOutput {
+                    success: exit_code == 0,
+                    exit_code,
+                    stdout,
+                    stderr,
+                    summary,
+                    waiting_for_input: false,
+                    session_id: None,
+                })
+            }
+            DriveState::WaitingForInput => {
+                let session_id = session.id.clone();
+                let summary = format_waiting_output(&session_id, &stdout, &stderr);
+                self.park(session).await;
+                Ok(ShellOutput {
+                    success: false,
+                    exit_code: WAITING_FOR_INPUT_EXIT_CODE,
+                    stdout,
+                    stderr,
+                    summary,
+                    waiting_for_input: true,
+                    session_id: Some(session_id),
+                })
+            }
+            DriveState::TimedOut => {
+                let mut message = "Command timed out".to_string();
+                let partial = format!("{stdout}{stderr}");
+                if !partial.trim().is_empty() {
+                    message.push_str(". Output before the timeout:\n");
+                    message.push_str(last_lines(&partial, 20));
+                }
+                Err(ShellError {
+                    message,
+                    exit_code: -1,
+                })
+            }
+            DriveState::Failed(error) => Err(ShellError {
+                message: format!("Failed to wait for command: {error}"),
                 exit_code: -1,
-            })?;
+            }),
+        }
+    }
+}
 
-        let stdout = crate::tools::truncate_output(
-            &String::from_utf8_lossy(&output.stdout),
-            crate::tools::MAX_TOOL_OUTPUT_BYTES,
-        );
-        let stderr = crate::tools::truncate_output(
-            &String::from_utf8_lossy(&output.stderr),
-            crate::tools::MAX_TOOL_OUTPUT_BYTES,
-        );
-        let exit_code = output.status.code().unwrap_or(-1);
-        let success = output.status.success();
+/// Which pipe an output chunk came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+impl OutputStream {
+    fn as_str(self) -> &'static str {
+        match self {
+            OutputStream::Stdout => "stdout",
+            OutputStream::Stderr => "stderr",
+        }
+    }
+}
+
+/// Raw bytes read from one of the child's pipes.
+struct OutputChunk {
+    stream: OutputStream,
+    bytes: Vec<u8>,
+}
+
+/// Splits a byte stream into scrubbed lines.
+///
+/// Bytes are decoded as UTF-8 across chunk boundaries and split on newlines;
+/// each line is redacted with `scrub_secrets` before it leaves the buffer. A
+/// trailing partial line (such as a `Continue? [y/N] ` prompt) is held until
+/// `flush()`.
+struct LineBuffer {
+    tool_secrets: Vec<(String, String)>,
+    pending_bytes: Vec<u8>,
+    partial_line: String,
+}
+
+impl LineBuffer {
+    fn new(tool_secrets: &[(String, String)]) -> Self {
+        Self {
+            tool_secrets: tool_secrets.to_vec(),
+            pending_bytes: Vec::new(),
+            partial_line: String::new(),
+        }
+    }
+
+    /// Feed raw bytes and return every line completed by them, scrubbed.
+    ///
+    /// Scrubbing happens per completed line rather than per chunk, so a secret
+    /// split across two reads is still whole by the time it is redacted.
+    fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.pending_bytes.extend_from_slice(bytes);
+        let text = decode_utf8_prefix(&mut self.pending_bytes);
+        self.partial_line.push_str(&text);
+
+        let mut lines = Vec::new();
+        while let Some(newline) = self.partial_line.find('\n') {
+            let line: String = self.partial_line.drain(..=newline).collect();
+            let line = line.trim_end_matches(['\n', '\r']);
+            lines.push(scrub_secrets(line, &self.tool_secrets));
+        }
+        lines
+    }
+
+    /// Release everything still buffered, including an unterminated line.
+    fn flush(&mut self) -> Option<String> {
+        if !self.pending_bytes.is_empty() {
+            let bytes = std::mem::take(&mut self.pending_bytes);
+            self.partial_line.push_str(&String::from_utf8_lossy(&bytes));
+        }
+
+        if self.partial_line.is_empty() {
+            None
+        } else {
+            let line = std::mem::take(&mut self.partial_line);
+            Some(scrub_secrets(&line, &self.tool_secrets))
+        }
+    }
+}
+
+/// Decode the longest valid UTF-8 prefix of `pending`, leaving an incomplete
+/// trailing sequence in place for the next chunk. Invalid bytes are replaced.
+fn decode_utf8_prefix(pending: &mut Vec<u8>) -> String {
+    match std::str::from_utf8(pending) {
+        Ok(text) => {
+            let text = text.to_string();
+            pending.clear();
+            text
+        }
+        Err(error) if error.error_len().is_none() => {
+            let valid = error.valid_up_to();
+            let rest = pending.split_off(valid);
+            let text = String::from_utf8_lossy(pending).into_owned();
+            *pending = rest;
+            text
+        }
+        Err(_) => {
+            let text = String::from_utf8_lossy(pending).into_owned();
+            pending.clear();
+            text
+        }
+    }
+}
+
+/// A spawned command and everything needed to resume reading from it.
+struct ShellSession {
+    id: String,
+    command: String,
+    child: Child,
+    stdin: Option<ChildStdin>,
+    output_rx: mpsc::UnboundedReceiver<OutputChunk>,
+    stdout: LineBuffer,
+    stderr: LineBuffer,
+    streaming: Option<ShellStreaming>,
+    quiesce_timeout: Duration,
+}
 
-        let summary = format_shell_output(exit_code, &stdout, &stderr);
+/// How a single drive of a session ended.
+enum DriveState {
+    Exited(i32),
+    WaitingForInput,
+    TimedOut,
+    Failed(std::io::Error),
+}
+
+/// Output collected during one drive plus how it ended.
+struct DriveOutcome {
+    state: DriveState,
+    stdout: String,
+    stderr: String,
+}
+
+impl ShellSession {
+    fn start(
+        mut child: Child,
+        command: String,
+        streaming: Option<ShellStreaming>,
+        tool_secrets: &[(String, String)],
+        quiesce_timeout: Duration,
+    ) -> Self {
+        let (output_tx, output_rx) = mpsc::unbounded_channel();
+        if let Some(stdout) = child.stdout.take() {
+            spawn_pipe_reader(stdout, OutputStream::Stdout, output_tx.clone());
+        }
+        if let Some(stderr) = child.stderr.take() {
+            spawn_pipe_reader(stderr, OutputStream::Stderr, output_tx);
+        }
+
+        Self {
+            id: uuid::Uuid::new_v4().simple().to_string()[..12].to_string(),
+            command,
+            stdin: child.stdin.take(),
+            child,
+            output_rx,
+            stdout: LineBuffer::new(tool_secrets),
+            stderr: LineBuffer::new(tool_secrets),
+            streaming,
+            quiesce_timeout,
+        }
+    }
 
-        Ok(ShellOutput {
-            success,
-            exit_code,
+    /// Write to the command's stdin. Returns an error if stdin was closed.
+    async fn write_input(&mut self, input: &str) -> std::io::Result<()> {
+        let Some(stdin) = self.stdin.as_mut() else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::BrokenPipe,
+                "stdin is closed",
+            ));
+        };
+        stdin.write_all(input.as_bytes()).await?;
+        stdin.flush().await
+    }
+
+    /// Close stdin so the command sees EOF.
+    fn close_stdin(&mut self) {
+        self.stdin = None;
+    }
+
+    /// Read output until the command exits, goes quiet, or `timeout` elapses.
+    async fn drive(&mut self, tool_name: &str, timeout: Duration) -> DriveOutcome {
+        let deadline = Instant::now() + timeout;
+        let mut stdout = String::new();
+        let mut stderr = String::new();
+
+        let state = loop {
+            tokio::select! {
+                chunk = self.output_rx.recv() => {
+                    let Some(chunk) = chunk else {
+                        // Both pipes closed; the command is exiting.
+                        self.flush_partial_lines(tool_name, &mut stdout, &mut stderr);
+                        break match tokio::time::timeout_at(deadline, self.child.wait()).await {
+                            Ok(Ok(status)) => DriveState::Exited(status.code().unwrap_or(-1)),
+                            Ok(Err(error)) => DriveState::Failed(error),
+                            Err(_) => {
+                                self.child.kill().await.ok();
+                                DriveState::TimedOut
+                            }
+                        };
+                    };
+                    let (buffer, collected) = match chunk.stream {
+                        OutputStream::Stdout => (&mut self.stdout, &mut stdout),
+                        OutputStream::Stderr => (&mut self.stderr, &mut stderr),
+                    };
+                    for line in buffer.push(&chunk.bytes) {
+                        collected.push_str(&line);
+                        collected.push('\n');
+                        emit_line(self.streaming.as_ref(), tool_name, chunk.stream, line);
+                    }
+                }
+                _ = tokio::time::sleep(self.quiesce_timeout) => {
+                    match self.child.try_wait() {
+                        Ok(Some(_)) => {
+                            // Exited while a descendant still holds the pipes open.
+                            // Keep draining until they close or the deadline hits.
+                            continue;
+                        }
+                        Ok(None) => {
+                            self.flush_partial_lines(tool_name, &mut stdout, &mut stderr);
+                            tracing::debug!(
+                                session_id = %self.id,
+                                command = %self.command,
+                                "shell command went quiet, assuming it is waiting for input"
+                            );
+                            break DriveState::WaitingForInput;
+                        }
+                        Err(error) => break DriveState::Failed(error),
+                    }
+                }
+                _ = tokio::time::sleep_until(deadline) => {
+                    self.child.kill().await.ok();
+                    self.flush_partial_lines(tool_name, &mut stdout, &mut stderr);
+                    break DriveState::TimedOut;
+                }
+            }
+        };
+
+        DriveOutcome {
+            state,
             stdout,
             stderr,
-            summary,
+        }
+    }
+
+    fn flush_partial_lines(&mut self, tool_name: &str, stdout: &mut String, stderr: &mut String) {
+        for (stream, buffer, collected) in [
+            (OutputStream::Stdout, &mut self.stdout, &mut *stdout),
+            (OutputStream::Stderr, &mut self.stderr, &mut *stderr),
+        ] {
+            if let Some(line) = buffer.flush() {
+                collected.push_str(&line);
+                emit_line(self.streaming.as_ref(), tool_name, stream, line);
+            }
+        }
+    }
+}
+
+/// Forward raw pipe reads into the session's output channel until EOF.
+fn spawn_pipe_reader<R>(
+    mut reader: R,
+    stream: OutputStream,
+    output_tx: mpsc::UnboundedSender<OutputChunk>,
+) where
+    R: AsyncRead + Unpin + Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut buffer = vec![0u8; 4096];
+        loop {
+            match reader.read(&mut buffer).await {
+                Ok(0) | Err(_) => break,
+                Ok(read) => {
+                    let chunk = OutputChunk {
+                        stream,
+                        bytes: buffer[..read].to_vec(),
+                    };
+                    if output_tx.send(chunk).is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+}
+
+fn emit_line(
+    streaming: Option<&ShellStreaming>,
+    tool_name: &str,
+    stream: OutputStream,
+    line: String,
+) {
+    let Some(streaming) = streaming else {
+        return;
+    };
+    streaming
+        .event_tx
+        .send(ProcessEvent::ToolOutput {
+            agent_id: streaming.agent_id.clone(),
+            process_id: streaming.process_id.clone(),
+            channel_id: streaming.channel_id.clone(),
+            tool_name: tool_name.to_string(),
+            line,
+            stream: stream.as_str().to_string(),
         })
+        .ok();
+}
+
+/// Tool for answering a command that is waiting for input.
+#[derive(Debug, Clone)]
+pub struct ShellInputTool {
+    sessions: Arc<ShellSessions>,
+}
+
+impl ShellInputTool {
+    /// Create a shell input tool over the sessions of a `ShellTool`.
+    pub fn new(sessions: Arc<ShellSessions>) -> Self {
+        Self { sessions }
+    }
+}
+
+/// Arguments for shell input tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ShellInputArgs {
+    /// Session ID returned by `shell` when the command started waiting.
+    pub session_id: String,
+    /// Text to write to the command's stdin. Omit to keep waiting for output.
+    #[serde(default)]
+    pub input: Option<String>,
+    /// Append a newline after `input` (default: true).
+    #[serde(default = "default_append_newline")]
+    pub append_newline: bool,
+    /// Close stdin after writing so the command sees EOF.
+    #[serde(default)]
+    pub close_stdin: bool,
+    /// Kill the command instead of sending input.
+    #[serde(default)]
+    pub kill: bool,
+    /// Optional timeout in seconds (default: 60).
+    #[serde(
+        default = "default_timeout",
+        deserialize_with = "crate::tools::deserialize_string_or_u64"
+    )]
+    pub timeout_seconds: u64,
+}
+
+fn default_append_newline() -> bool {
+    true
+}
+
+impl Tool for ShellInputTool {
+    const NAME: &'static str = "shell_input";
+
+    type Error = ShellError;
+    type Args = ShellInputArgs;
+    type Output = ShellOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/shell_input").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "session_id": {
+                        "type": "string",
+                        "description": "The session_id returned by shell when waiting_for_input was true"
+                    },
+                    "input": {
+                        "type": "string",
+                        "description": "Text to send to the command's stdin. Omit to keep waiting for more output."
+                    },
+                    "append_newline": {
+                        "type": "boolean",
+                        "default": true,
+                        "description": "Append a newline after the input, like pressing Enter"
+                    },
+                    "close_stdin": {
+                        "type": "boolean",
+                        "default": false,
+                        "description": "Close stdin after writing so the command sees end-of-input"
+                    },
+                    "kill": {
+                        "type": "boolean",
+                        "default": false,
+                        "description": "Kill the command instead of answering it"
+                    },
+                    "timeout_seconds": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "maximum": 300,
+                        "default": 60,
+                        "description": "Maximum time to wait for the command after sending input (1-300 seconds)"
+                    }
+                },
+                "required": ["session_id"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let Some(mut session) = self.sessions.take(&args.session_id).await else {
+            return Err(ShellError {
+                message: format!(
+                    "No command is waiting for input with session ID '{}'. It may have exited or been killed.",
+                    args.session_id
+                ),
+                exit_code: -1,
+            });
+        };
+
+        if args.kill {
+            session.child.kill().await.ok();
+            let summary = format!("Killed `{}`.", session.command);
+            return Ok(ShellOutput {
+                success: false,
+                exit_code: -1,
+                stdout: String::new(),
+                stderr: String::new(),
+                summary,
+                waiting_for_input: false,
+                session_id: None,
+            });
+        }
+
+        if let Some(input) = &args.input {
+            let mut input = input.clone();
+            if args.append_newline {
+                input.push('\n');
+            }
+            if let Err(error) = session.write_input(&input).await {
+                // Park the session again so the output can still be read.
+                self.sessions.park(session).await;
+                return Err(ShellError {
+                    message: format!("Failed to write input: {error}"),
+                    exit_code: -1,
+                });
+            }
+        }
+
+        if args.close_stdin {
+            session.close_stdin();
+        }
+
+        let timeout = Duration::from_secs(args.timeout_seconds);
+        let outcome = session.drive(Self::NAME, timeout).await;
+        self.sessions.settle(session, outcome).await
     }
 }
 
@@ -305,6 +920,38 @@ fn format_shell_output(exit_code: i32, stdout: &str, stderr: &str) -> String {
     output
 }
 
+/// Format the summary for a command paused waiting for input.
+fn format_waiting_output(session_id: &str, stdout: &str, stderr: &str) -> String {
+    let combined = format!("{stdout}{stderr}");
+    let last_output = last_lines(&combined, 5);
+    let mut output =
+        String::from("Command is still running and appears to be waiting for interactive input.\n");
+    if last_output.trim().is_empty() {
+        output.push_str("It has produced no output yet.\n");
+    } else {
+        output.push_str("The last output was:\n");
+        output.push_str(last_output);
+        output.push('\n');
+    }
+    output.push_str(&format!(
+        "\nAnswer it with shell_input (session_id: \"{session_id}\"), call shell_input without \
+         input to keep waiting, or kill it and retry with non-interactive flags \
+         (--yes, -y) or piped input (echo y | command)."
+    ));
+    output
+}
+
+/// Return the last `count` lines of `text`.
+fn last_lines(text: &str, count: usize) -> &str {
+    let trimmed = text.trim_end_matches('\n');
+    let start = trimmed
+        .rmatch_indices('\n')
+        .nth(count.saturating_sub(1))
+        .map(|(index, _)| index + 1)
+        .unwrap_or(0);
+    &trimmed[start..]
+}
+
 /// System-internal shell execution that bypasses path restrictions.
 /// Used by the system itself, not LLM-facing.
 pub async fn shell(
@@ -357,3 +1004,42 @@ impl ShellResult {
         format_shell_output(self.exit_code, &self.stdout, &self.stderr)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_buffer_holds_partial_lines_until_flush() {
+        let mut buffer = LineBuffer::new(&[]);
+        assert_eq!(buffer.push(b"one\ntw"), vec!["one".to_string()]);
+        assert_eq!(
+            buffer.push(b"o\r\nOk to proceed? (y) "),
+            vec!["two".to_string()]
+        );
+        assert_eq!(buffer.flush().as_deref(), Some("Ok to proceed? (y) "));
+        assert_eq!(buffer.flush(), None);
+    }
+
+    #[test]
+    fn line_buffer_decodes_utf8_split_across_chunks() {
+        let mut buffer = LineBuffer::new(&[]);
+        let bytes = "héllo\n".as_bytes();
+        assert!(buffer.push(&bytes[..2]).is_empty());
+        assert_eq!(buffer.push(&bytes[2..]), vec!["héllo".to_string()]);
+    }
+
+    #[test]
+    fn line_buffer_scrubs_secrets_split_across_chunks() {
+        let mut buffer = LineBuffer::new(&[("API_TOKEN".to_string(), "tok-abcdef".to_string())]);
+        let mut lines = buffer.push(b"token=tok-ab");
+        lines.extend(buffer.push(b"cdef\n"));
+        assert_eq!(lines, vec!["token=[REDACTED:API_TOKEN]".to_string()]);
+    }
+
+    #[test]
+    fn last_lines_returns_tail() {
+        assert_eq!(last_lines("a\nb\nc\n", 2), "b\nc");
+        assert_eq!(last_lines("only", 3), "only");
+    }
+}
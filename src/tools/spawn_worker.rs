@@ -2,12 +2,14 @@
 //!
 //! Two variants:
 //! - `SpawnWorkerTool`: full-featured, used by channels and branches. Requires `ChannelState`.
-//! - `DetachedSpawnWorkerTool`: lightweight, used by cortex chat. Spawns workers with no
-//!   parent channel — they log directly to `worker_runs` and emit events with `channel_id: None`.
+//! - `DetachedSpawnWorkerTool`: lightweight, used by cortex chat and the autonomy loop. Spawns
+//!   workers with no parent channel — they log directly to `worker_runs` and emit events with
+//!   `channel_id: None`.
 
 use crate::WorkerId;
 use crate::agent::channel::ChannelState;
 use crate::agent::channel_dispatch::{spawn_opencode_worker_from_state, spawn_worker_from_state};
+use crate::config::{AccessCapability, AccessGrant};
 use rig::completion::ToolDefinition;
 use rig::tool::Tool;
 use schemars::JsonSchema;
@@ -20,12 +22,22 @@ use tracing::Instrument as _;
 #[derive(Debug, Clone)]
 pub struct SpawnWorkerTool {
     state: ChannelState,
+    /// Role of the sender the tool acts for. `None` means unrestricted.
+    access: Option<AccessGrant>,
 }
 
 impl SpawnWorkerTool {
     /// Create a new spawn worker tool with access to channel state.
     pub fn new(state: ChannelState) -> Self {
-        Self { state }
+        Self {
+            state,
+            access: None,
+        }
+    }
+
+    pub fn with_access(mut self, access: Option<AccessGrant>) -> Self {
+        self.access = access;
+        self
     }
 }
 
@@ -176,6 +188,12 @@ impl Tool for SpawnWorkerTool {
     }
 
     async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        if let Some(access) = &self.access {
+            access
+                .check(AccessCapability::SpawnWorker)
+                .map_err(|denied| SpawnWorkerError(denied.to_string()))?;
+        }
+
         let readiness = self.state.deps.runtime_config.work_readiness();
         let is_opencode = args.worker_type.as_deref() == Some("opencode");
 
@@ -237,6 +255,7 @@ impl Tool for SpawnWorkerTool {
                     .map(String::as_str)
                     .collect::<Vec<_>>(),
                 &worker_context,
+                self.access.clone(),
             )
             .await
             .map_err(|e| SpawnWorkerError(format!("{e}")))?
@@ -308,7 +327,53 @@ pub struct CortexChatContext {
     >,
 }
 
-/// Spawn worker tool for cortex chat sessions.
+/// Cap on the number of workers a `DetachedSpawnWorkerTool` may spawn.
+///
+/// Clones share the same count, so the owner can hand one to the tool and
+/// later read back which workers were admitted.
+#[derive(Debug, Clone)]
+pub struct DetachedWorkerBudget {
+    max_workers: usize,
+    reserved: Arc<std::sync::atomic::AtomicUsize>,
+    spawned: Arc<std::sync::Mutex<Vec<WorkerId>>>,
+}
+
+impl DetachedWorkerBudget {
+    pub fn new(max_workers: usize) -> Self {
+        Self {
+            max_workers,
+            reserved: Arc::new(std::sync::atomic::AtomicUsize::new(0)),
+            spawned: Arc::new(std::sync::Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Reserve a slot. Returns false once the budget is spent.
+    fn try_reserve(&self) -> bool {
+        self.reserved
+            .fetch_update(
+                std::sync::atomic::Ordering::SeqCst,
+                std::sync::atomic::Ordering::SeqCst,
+                |reserved| (reserved < self.max_workers).then_some(reserved + 1),
+            )
+            .is_ok()
+    }
+
+    fn record(&self, worker_id: WorkerId) {
+        if let Ok(mut spawned) = self.spawned.lock() {
+            spawned.push(worker_id);
+        }
+    }
+
+    /// IDs of the workers spawned against this budget so far.
+    pub fn spawned(&self) -> Vec<WorkerId> {
+        self.spawned
+            .lock()
+            .map(|spawned| spawned.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Spawn worker tool for cortex chat sessions and the autonomy loop.
 ///
 /// Unlike `SpawnWorkerTool` (which requires `ChannelState`), this creates
 /// workers with no parent channel. Workers are logged directly to `worker_runs`
@@ -319,6 +384,7 @@ pub struct DetachedSpawnWorkerTool {
     screenshot_dir: PathBuf,
     logs_dir: PathBuf,
     cortex_ctx: Option<CortexChatContext>,
+    budget: Option<DetachedWorkerBudget>,
 }
 
 impl std::fmt::Debug for DetachedSpawnWorkerTool {
@@ -335,6 +401,7 @@ impl DetachedSpawnWorkerTool {
             screenshot_dir,
             logs_dir,
             cortex_ctx: None,
+            budget: None,
         }
     }
 
@@ -342,6 +409,12 @@ impl DetachedSpawnWorkerTool {
         self.cortex_ctx = Some(ctx);
         self
     }
+
+    /// Refuse spawns once `budget` is spent.
+    pub fn with_budget(mut self, budget: DetachedWorkerBudget) -> Self {
+        self.budget = Some(budget);
+        self
+    }
 }
 
 /// Arguments for the detached spawn worker tool.
@@ -397,6 +470,15 @@ impl Tool for DetachedSpawnWorkerTool {
     }
 
     async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        if let Some(budget) = &self.budget
+            && !budget.try_reserve()
+        {
+            return Err(SpawnWorkerError(format!(
+                "worker budget of {} for this run is spent; save what you have learned and defer the rest",
+                budget.max_workers
+            )));
+        }
+
         let rc = &self.deps.runtime_config;
         let prompt_engine = rc.prompts.load();
 
@@ -466,6 +548,9 @@ impl Tool for DetachedSpawnWorkerTool {
 
         let (worker, _input_tx) = worker;
         let worker_id = worker.id;
+        if let Some(budget) = &self.budget {
+            budget.record(worker_id);
+        }
 
         // Emit WorkerStarted event so the UI can track it.
         let _ = self.deps.event_tx.send(crate::ProcessEvent::WorkerStarted {
@@ -1,5 +1,6 @@
 //! Task creation tool for branch processes.
 
+use crate::config::{AccessCapability, AccessGrant};
 use crate::tasks::{CreateTaskInput, TaskPriority, TaskStatus, TaskStore, TaskSubtask};
 use rig::completion::ToolDefinition;
 use rig::tool::Tool;
@@ -13,6 +14,9 @@ pub struct TaskCreateTool {
     agent_id: String,
     created_by: String,
     working_memory: Option<Arc<crate::memory::WorkingMemoryStore>>,
+    require_approval: bool,
+    /// Role of the sender the tool acts for. `None` means unrestricted.
+    access: Option<AccessGrant>,
 }
 
 impl TaskCreateTool {
@@ -26,13 +30,27 @@ impl TaskCreateTool {
             agent_id: agent_id.into(),
             created_by: created_by.into(),
             working_memory: None,
+            require_approval: false,
+            access: None,
         }
     }
 
+    /// File every created task as `pending_approval`, whatever status the
+    /// caller asked for, so a human signs off before anything executes.
+    pub fn with_approval_required(mut self, require_approval: bool) -> Self {
+        self.require_approval = require_approval;
+        self
+    }
+
     pub fn with_working_memory(mut self, store: Arc<crate::memory::WorkingMemoryStore>) -> Self {
         self.working_memory = Some(store);
         self
     }
+
+    pub fn with_access(mut self, access: Option<AccessGrant>) -> Self {
+        self.access = access;
+        self
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -114,6 +132,19 @@ impl Tool for TaskCreateTool {
             Some(value) => TaskStatus::parse(value)
                 .ok_or_else(|| TaskCreateError(format!("invalid status: {value}")))?,
         };
+        let status = if self.require_approval {
+            TaskStatus::PendingApproval
+        } else {
+            status
+        };
+        // Filing a task straight into ready skips the approval step.
+        if status == TaskStatus::Ready
+            && let Some(access) = &self.access
+        {
+            access
+                .check(AccessCapability::TaskApproval)
+                .map_err(|denied| TaskCreateError(denied.to_string()))?;
+        }
 
         let subtasks = args
             .subtasks
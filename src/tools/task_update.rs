@@ -1,5 +1,6 @@
 //! Task update tool for branch and worker processes.
 
+use crate::config::{AccessCapability, AccessGrant};
 use crate::tasks::{TaskPriority, TaskStatus, TaskStore, TaskSubtask, UpdateTaskInput};
 use crate::{AgentId, WorkerId};
 use rig::completion::ToolDefinition;
@@ -22,6 +23,8 @@ pub struct TaskUpdateTool {
     agent_id: AgentId,
     scope: TaskUpdateScope,
     working_memory: Option<Arc<crate::memory::WorkingMemoryStore>>,
+    /// Role of the sender the tool acts for. `None` means unrestricted.
+    access: Option<AccessGrant>,
 }
 
 impl TaskUpdateTool {
@@ -31,6 +34,7 @@ impl TaskUpdateTool {
             agent_id,
             scope: TaskUpdateScope::Branch,
             working_memory: None,
+            access: None,
         }
     }
 
@@ -40,6 +44,7 @@ impl TaskUpdateTool {
             agent_id,
             scope: TaskUpdateScope::Worker(worker_id),
             working_memory: None,
+            access: None,
         }
     }
 
@@ -47,6 +52,11 @@ impl TaskUpdateTool {
         self.working_memory = Some(store);
         self
     }
+
+    pub fn with_access(mut self, access: Option<AccessGrant>) -> Self {
+        self.access = access;
+        self
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -205,6 +215,14 @@ impl Tool for TaskUpdateTool {
                     .ok_or_else(|| TaskUpdateError(format!("invalid priority: {value}")))?,
             ),
         };
+        // Moving a task to ready, or recording who approved it, is an approval.
+        if (status == Some(TaskStatus::Ready) || args.approved_by.is_some())
+            && let Some(access) = &self.access
+        {
+            access
+                .check(AccessCapability::TaskApproval)
+                .map_err(|denied| TaskUpdateError(denied.to_string()))?;
+        }
         let complete_subtask = match args.complete_subtask {
             None => None,
             Some(value) => Some(
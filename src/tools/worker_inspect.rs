@@ -155,6 +155,20 @@ impl Tool for WorkerInspectTool {
                                     "**Result ({label}):**\n```\n{display}\n```\n\n"
                                 ));
                             }
+                            worker_transcript::TranscriptStep::ToolApproval {
+                                name,
+                                decision,
+                                resolved_by,
+                                ..
+                            } => {
+                                let by = resolved_by
+                                    .as_deref()
+                                    .map(|name| format!(" by {name}"))
+                                    .unwrap_or_default();
+                                summary.push_str(&format!(
+                                    "**Approval:** `{name}` {decision}{by}\n\n"
+                                ));
+                            }
                         }
                     }
                 }
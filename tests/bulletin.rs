@@ -126,6 +126,7 @@ async fn bootstrap_deps() -> anyhow::Result<spacebot::AgentDeps> {
         process_control_registry: Arc::new(
             spacebot::agent::process_control::ProcessControlRegistry::new(),
         ),
+        tool_approvals: Arc::new(spacebot::agent::tool_approval::ToolApprovalRegistry::new()),
         injection_tx: tokio::sync::mpsc::channel(1).0,
         working_memory: spacebot::memory::WorkingMemoryStore::new(
             db.sqlite.clone(),
@@ -125,6 +125,7 @@ async fn bootstrap_deps() -> anyhow::Result<(spacebot::AgentDeps, spacebot::conf
         process_control_registry: Arc::new(
             spacebot::agent::process_control::ProcessControlRegistry::new(),
         ),
+        tool_approvals: Arc::new(spacebot::agent::tool_approval::ToolApprovalRegistry::new()),
         injection_tx: tokio::sync::mpsc::channel(1).0,
         working_memory: spacebot::memory::WorkingMemoryStore::new(
             db.sqlite.clone(),
@@ -243,9 +244,14 @@ async fn dump_channel_context() {
         conversation_logger,
         process_run_logger: spacebot::conversation::ProcessRunLogger::new(deps.sqlite_pool.clone()),
         channel_store,
+        human_store: spacebot::conversation::HumanStore::new(deps.sqlite_pool.clone()),
         screenshot_dir: std::path::PathBuf::from("/tmp/screenshots"),
         logs_dir: std::path::PathBuf::from("/tmp/logs"),
         reply_target_message_id: Arc::new(tokio::sync::RwLock::new(None)),
+        current_user_id: Arc::new(tokio::sync::RwLock::new(None)),
+        sender_role: Arc::new(tokio::sync::RwLock::new(
+            spacebot::config::AccessRole::Owner,
+        )),
         prompt_snapshot_store: None,
         live_worker_transcripts: Arc::new(tokio::sync::RwLock::new(
             std::collections::HashMap::new(),
@@ -269,6 +275,7 @@ async fn dump_channel_context() {
         true,
         None,
         None,
+        None,
     )
     .await
     .expect("failed to add channel tools");
@@ -332,6 +339,8 @@ async fn dump_branch_context() {
         channel_store,
         run_logger,
         spacebot::tools::BranchToolProfile::Default,
+        None,
+        None,
     );
 
     let tool_defs = branch_tool_server
@@ -405,6 +414,7 @@ async fn dump_worker_context() {
         deps.runtime_config.clone(),
         Default::default(),
         deps.memory_search.clone(),
+        None,
     );
 
     let tool_defs = worker_tool_server
@@ -487,9 +497,14 @@ async fn dump_all_contexts() {
         conversation_logger: conversation_logger.clone(),
         process_run_logger: spacebot::conversation::ProcessRunLogger::new(deps.sqlite_pool.clone()),
         channel_store: channel_store.clone(),
+        human_store: spacebot::conversation::HumanStore::new(deps.sqlite_pool.clone()),
         screenshot_dir: std::path::PathBuf::from("/tmp/screenshots"),
         logs_dir: std::path::PathBuf::from("/tmp/logs"),
         reply_target_message_id: Arc::new(tokio::sync::RwLock::new(None)),
+        current_user_id: Arc::new(tokio::sync::RwLock::new(None)),
+        sender_role: Arc::new(tokio::sync::RwLock::new(
+            spacebot::config::AccessRole::Owner,
+        )),
         prompt_snapshot_store: None,
         live_worker_transcripts: Arc::new(tokio::sync::RwLock::new(
             std::collections::HashMap::new(),
@@ -512,6 +527,7 @@ async fn dump_all_contexts() {
         true,
         None,
         None,
+        None,
     )
     .await
     .expect("failed to add channel tools");
@@ -544,6 +560,8 @@ async fn dump_all_contexts() {
         channel_store,
         run_logger,
         spacebot::tools::BranchToolProfile::Default,
+        None,
+        None,
     );
     let branch_tool_defs = branch_tool_server.get_tool_defs(None).await.unwrap();
     let branch_tools_text = format_tool_defs(&branch_tool_defs);
@@ -589,6 +607,7 @@ async fn dump_all_contexts() {
         deps.runtime_config.clone(),
         Default::default(),
         deps.memory_search.clone(),
+        None,
     );
     let worker_tool_defs = worker_tool_server.get_tool_defs(None).await.unwrap();
     let worker_tools_text = format_tool_defs(&worker_tool_defs);
@@ -46,6 +46,7 @@ async fn claim_and_advance_is_atomic_only_one_succeeds() {
             active_hours: None,
             enabled: true,
             run_once: false,
+            stateful: false,
             next_run_at: Some(scheduled_text.clone()),
             timeout_secs: None,
         })
@@ -133,6 +134,7 @@ async fn duplicate_claim_fails_after_successful_advance() {
             active_hours: None,
             enabled: true,
             run_once: false,
+            stateful: false,
             next_run_at: Some(scheduled_text.clone()),
             timeout_secs: None,
         })
@@ -183,6 +185,7 @@ async fn run_once_claim_prevents_subsequent_execution() {
             active_hours: None,
             enabled: true,
             run_once: true,
+            stateful: false,
             next_run_at: Some(scheduled_text.clone()),
             timeout_secs: None,
         })
@@ -234,6 +237,7 @@ async fn stale_cursor_detection_after_lost_claim() {
             active_hours: None,
             enabled: true,
             run_once: false,
+            stateful: false,
             next_run_at: Some(original_text.clone()),
             timeout_secs: None,
         })
@@ -0,0 +1,238 @@
+//! Record/replay cassette integration test.
+//!
+//! Records a tool-calling conversation and a model fallback against a local
+//! Anthropic-compatible stand-in, then shuts the stand-in down and replays
+//! both flows from the cassettes alone.
+
+use axum::Router;
+use axum::http::StatusCode;
+use axum::response::IntoResponse as _;
+use axum::routing::post;
+use rig::completion::CompletionModel as _;
+use rig::completion::{Prompt as _, ToolDefinition};
+use rig::tool::Tool;
+use serde::Deserialize;
+use spacebot::config::{ApiType, CassetteConfig, CassetteMode, LlmConfig, ProviderConfig};
+use spacebot::llm::{LlmManager, RoutingConfig, SpacebotModel};
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Model that always fails with a retriable error, to exercise fallbacks.
+const BROKEN_MODEL: &str = "standin/broken-model";
+const WORKING_MODEL: &str = "standin/claude-test";
+
+#[derive(Debug, thiserror::Error)]
+#[error("weather lookup failed")]
+struct WeatherError;
+
+#[derive(Deserialize)]
+struct WeatherArgs {
+    city: String,
+}
+
+struct WeatherTool;
+
+impl Tool for WeatherTool {
+    const NAME: &'static str = "get_weather";
+
+    type Error = WeatherError;
+    type Args = WeatherArgs;
+    type Output = String;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: "Look up the current weather for a city.".to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {"city": {"type": "string"}},
+                "required": ["city"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        Ok(format!("Sunny and 21°C in {}", args.city))
+    }
+}
+
+/// Serve `/v1/messages`: the broken model is always overloaded; otherwise
+/// the first turn calls `get_weather` and the turn after its result answers.
+async fn spawn_anthropic_stand_in(
+    requests: Arc<AtomicUsize>,
+) -> (String, tokio::task::JoinHandle<()>) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+    let app = Router::new().route(
+        "/v1/messages",
+        post(
+            move |axum::Json(body): axum::Json<serde_json::Value>| async move {
+                requests.fetch_add(1, Ordering::SeqCst);
+
+                if body["model"] == "broken-model" {
+                    return (
+                        StatusCode::from_u16(529).unwrap(),
+                        axum::Json(serde_json::json!({
+                            "type": "error",
+                            "error": {"type": "overloaded_error", "message": "Overloaded"}
+                        })),
+                    )
+                        .into_response();
+                }
+
+                let answered_tool = body["messages"]
+                    .as_array()
+                    .and_then(|messages| messages.last())
+                    .and_then(|message| message["content"].as_array())
+                    .is_some_and(|content| {
+                        content.iter().any(|block| block["type"] == "tool_result")
+                    });
+                let content = if answered_tool {
+                    serde_json::json!([{"type": "text", "text": "It's sunny in Paris."}])
+                } else {
+                    serde_json::json!([{
+                        "type": "tool_use",
+                        "id": "toolu_1",
+                        "name": "get_weather",
+                        "input": {"city": "Paris"}
+                    }])
+                };
+
+                axum::Json(serde_json::json!({
+                    "id": "msg_1",
+                    "type": "message",
+                    "role": "assistant",
+                    "model": body["model"],
+                    "content": content,
+                    "stop_reason": if answered_tool { "end_turn" } else { "tool_use" },
+                    "usage": {"input_tokens": 20, "output_tokens": 10}
+                }))
+                .into_response()
+            },
+        ),
+    );
+    let server = tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    (base_url, server)
+}
+
+async fn manager(base_url: &str, mode: CassetteMode, dir: &Path) -> Arc<LlmManager> {
+    let provider = ProviderConfig {
+        api_type: ApiType::Anthropic,
+        base_url: base_url.to_string(),
+        api_key: "test-key".into(),
+        name: None,
+        use_bearer_auth: false,
+        extra_headers: Vec::new(),
+        api_version: None,
+        deployment: None,
+        bedrock: None,
+        cassette: Some(CassetteConfig {
+            mode,
+            dir: dir.to_path_buf(),
+        }),
+    };
+    let config = LlmConfig {
+        anthropic_key: None,
+        openai_key: None,
+        openrouter_key: None,
+        kilo_key: None,
+        zhipu_key: None,
+        groq_key: None,
+        together_key: None,
+        fireworks_key: None,
+        deepseek_key: None,
+        xai_key: None,
+        mistral_key: None,
+        gemini_key: None,
+        ollama_key: None,
+        ollama_base_url: None,
+        opencode_zen_key: None,
+        opencode_go_key: None,
+        nvidia_key: None,
+        minimax_key: None,
+        minimax_cn_key: None,
+        moonshot_key: None,
+        zai_coding_plan_key: None,
+        github_copilot_key: None,
+        providers: HashMap::from([("standin".to_string(), provider)]),
+    };
+    Arc::new(LlmManager::new(config).await.unwrap())
+}
+
+/// A tool loop on the working model, then a prompt that falls back to it.
+async fn run_flows(manager: &Arc<LlmManager>) -> (String, String) {
+    let agent = rig::agent::AgentBuilder::new(SpacebotModel::make(manager, WORKING_MODEL))
+        .preamble("You report the weather.")
+        .tool(WeatherTool)
+        .default_max_turns(3)
+        .build();
+    let weather = agent
+        .prompt("What's the weather in Paris?")
+        .await
+        .expect("tool loop failed");
+
+    let routing = RoutingConfig {
+        fallbacks: HashMap::from([(BROKEN_MODEL.to_string(), vec![WORKING_MODEL.to_string()])]),
+        ..RoutingConfig::default()
+    };
+    let agent = rig::agent::AgentBuilder::new(
+        SpacebotModel::make(manager, BROKEN_MODEL).with_routing(routing),
+    )
+    .preamble("You report the weather.")
+    .tool(WeatherTool)
+    .default_max_turns(3)
+    .build();
+    let fallback = agent
+        .prompt("What's the weather in Paris?")
+        .await
+        .expect("fallback flow failed");
+
+    (weather, fallback)
+}
+
+#[tokio::test]
+async fn conversations_replay_offline_from_recorded_cassettes() {
+    let cassettes = tempfile::tempdir().unwrap();
+    let requests = Arc::new(AtomicUsize::new(0));
+    let (base_url, server) = spawn_anthropic_stand_in(requests.clone()).await;
+
+    let recording = manager(&base_url, CassetteMode::Record, cassettes.path()).await;
+    let recorded = run_flows(&recording).await;
+    assert_eq!(recorded.0, "It's sunny in Paris.");
+    assert_eq!(recorded.1, "It's sunny in Paris.");
+
+    // Both turns of the tool loop, plus the broken model's error on each turn
+    // of the fallback flow. The fallback's own requests reuse the tool loop's.
+    let recorded_requests = requests.load(Ordering::SeqCst);
+    assert!(recorded_requests >= 5, "got {recorded_requests} requests");
+    let cassette_count = std::fs::read_dir(cassettes.path()).unwrap().count();
+    assert_eq!(cassette_count, 4);
+
+    server.abort();
+    let _ = server.await;
+
+    let replaying = manager(&base_url, CassetteMode::Replay, cassettes.path()).await;
+    let replayed = run_flows(&replaying).await;
+    assert_eq!(replayed, recorded);
+    assert_eq!(requests.load(Ordering::SeqCst), recorded_requests);
+}
+
+#[tokio::test]
+async fn replay_fails_for_unrecorded_requests() {
+    let cassettes = tempfile::tempdir().unwrap();
+    let manager = manager("http://127.0.0.1:9", CassetteMode::Replay, cassettes.path()).await;
+
+    let agent = rig::agent::AgentBuilder::new(SpacebotModel::make(&manager, WORKING_MODEL))
+        .preamble("You report the weather.")
+        .build();
+    let error = agent.prompt("Anything new?").await.unwrap_err();
+    assert!(
+        error.to_string().contains("no cassette recorded"),
+        "unexpected error: {error}"
+    );
+}
@@ -67,7 +67,7 @@ async fn maintenance_run_merges_duplicate_memory_and_links_updates_edge() {
             .await
             .expect("failed to save survivor memory");
         embedding_table
-            .store(&memory.id, &memory.content, &vec![1.0; 384])
+            .store(&memory.id, &memory.content, None, &vec![1.0; 384])
             .await
             .expect("failed to store survivor embedding");
         memory
@@ -84,7 +84,7 @@ async fn maintenance_run_merges_duplicate_memory_and_links_updates_edge() {
             .await
             .expect("failed to save duplicate memory");
         embedding_table
-            .store(&memory.id, &memory.content, &vec![1.0; 384])
+            .store(&memory.id, &memory.content, None, &vec![1.0; 384])
             .await
             .expect("failed to store duplicate embedding");
         memory
@@ -99,7 +99,7 @@ async fn maintenance_run_merges_duplicate_memory_and_links_updates_edge() {
             .await
             .expect("failed to save related memory");
         embedding_table
-            .store(&memory.id, &memory.content, &vec![0.0; 384])
+            .store(&memory.id, &memory.content, None, &vec![0.0; 384])
             .await
             .expect("failed to store related embedding");
         memory
@@ -132,6 +132,8 @@ async fn maintenance_run_merges_duplicate_memory_and_links_updates_edge() {
             decay_rate: 0.05,
             min_age_days: 30,
             merge_similarity_threshold: 0.95,
+            recent_tier_ttl_days: 3,
+            recent_tier_max: 64,
         },
     )
     .await
@@ -225,6 +227,8 @@ async fn maintenance_config_validation_rejects_negative_min_age() {
             decay_rate: 0.05,
             min_age_days: -5,
             merge_similarity_threshold: 0.95,
+            recent_tier_ttl_days: 3,
+            recent_tier_max: 64,
         },
     )
     .await;